@@ -0,0 +1,81 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use tokio::sync::Mutex;
+
+use sqlx::mysql::{MySql, MySqlConnection, MySqlRow};
+use sqlx::{FromRow, Row};
+use sqlx_test::new;
+
+const ROWS: i64 = 100_000;
+
+macro_rules! wide_structs {
+    ($($column:ident),+) => {
+        /// Reads every column by name, with the lookups cached by `#[derive(FromRow)]`.
+        #[derive(sqlx::FromRow)]
+        #[allow(dead_code)]
+        struct Derived {
+            $($column: i64,)+
+        }
+
+        /// Reads every column by name for every row, as `#[derive(FromRow)]` used to.
+        #[allow(dead_code)]
+        struct ByName {
+            $($column: i64,)+
+        }
+
+        impl FromRow<'_, MySqlRow> for ByName {
+            fn from_row(row: &MySqlRow) -> sqlx::Result<Self> {
+                Ok(ByName {
+                    $($column: row.try_get(stringify!($column))?,)+
+                })
+            }
+        }
+
+        fn select() -> String {
+            let columns = [$(concat!("i AS ", stringify!($column))),+].join(", ");
+            // 10^5 rows from a cross join of the digits
+            format!(
+                "WITH d(n) AS (SELECT 0 UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 \
+                 UNION ALL SELECT 4 UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 \
+                 UNION ALL SELECT 8 UNION ALL SELECT 9) \
+                 SELECT {columns} FROM (SELECT CAST(d1.n + 10 * d2.n + 100 * d3.n + 1000 * d4.n \
+                 + 10000 * d5.n AS SIGNED) AS i FROM d d1, d d2, d d3, d d4, d d5 LIMIT {ROWS}) t"
+            )
+        }
+    };
+}
+
+wide_structs!(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z);
+
+async fn fetch<T>(conn: &Mutex<MySqlConnection>, sql: &str)
+where
+    T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+{
+    let rows: Vec<T> = sqlx::query_as(sql)
+        .fetch_all(&mut *conn.lock().await)
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), usize::try_from(ROWS).unwrap());
+}
+
+fn from_row(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let db = Mutex::new(runtime.block_on(new::<MySql>()).unwrap());
+    let sql = select();
+
+    let mut group = c.benchmark_group("from_row/26x100k");
+    group.sample_size(10);
+
+    group.bench_function("by_name", |b| {
+        b.to_async(&runtime).iter(|| fetch::<ByName>(&db, &sql));
+    });
+
+    group.bench_function("derived", |b| {
+        b.to_async(&runtime).iter(|| fetch::<Derived>(&db, &sql));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, from_row);
+criterion_main!(benches);
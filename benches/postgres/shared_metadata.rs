@@ -0,0 +1,90 @@
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{Executor, Postgres};
+
+const CONNECTIONS: u32 = 10;
+
+// the user-defined types of the columns have to be looked up by every connection
+// that builds the metadata of the statement itself
+const SETUP: &str = "\
+DROP TABLE IF EXISTS bench_shared_metadata;
+DROP TYPE IF EXISTS bench_mood;
+DROP TYPE IF EXISTS bench_point;
+CREATE TYPE bench_mood AS ENUM ('sad', 'ok', 'happy');
+CREATE TYPE bench_point AS (x INT4, y INT4);
+CREATE TABLE bench_shared_metadata (id INT4, mood bench_mood, point bench_point, moods bench_mood[]);
+";
+
+const SELECT: &str = "SELECT * FROM bench_shared_metadata WHERE id = $1";
+
+/// Times the first execution of `SELECT` on every connection of a new pool but the first,
+/// which prepared it already.
+async fn first_rows(options: PgConnectOptions, iters: u64) -> Duration {
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..iters {
+        let pool = PgPoolOptions::new()
+            .min_connections(CONNECTIONS)
+            .max_connections(CONNECTIONS)
+            .connect_with(options.clone())
+            .await
+            .unwrap();
+
+        let mut conns = Vec::new();
+
+        for _ in 0..CONNECTIONS {
+            conns.push(pool.acquire().await.unwrap());
+        }
+
+        sqlx::query(SELECT)
+            .bind(1)
+            .fetch_optional(&mut *conns[0])
+            .await
+            .unwrap();
+
+        let start = Instant::now();
+
+        for conn in &mut conns[1..] {
+            sqlx::query(SELECT)
+                .bind(1)
+                .fetch_optional(&mut **conn)
+                .await
+                .unwrap();
+        }
+
+        elapsed += start.elapsed();
+    }
+
+    elapsed
+}
+
+fn shared_metadata(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let options: PgConnectOptions = dotenvy::var("DATABASE_URL").unwrap().parse().unwrap();
+
+    runtime.block_on(async {
+        let mut conn = sqlx_test::new::<Postgres>().await.unwrap();
+        conn.execute(SETUP).await.unwrap();
+    });
+
+    let mut group = c.benchmark_group("time_to_first_row");
+    group.sample_size(10);
+
+    group.bench_function("per_connection_metadata", |b| {
+        b.to_async(&runtime)
+            .iter_custom(|iters| first_rows(options.clone(), iters));
+    });
+
+    group.bench_function("shared_metadata", |b| {
+        b.to_async(&runtime)
+            .iter_custom(|iters| first_rows(options.clone().share_statement_metadata(true), iters));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, shared_metadata);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use std::io;
+use std::process::Stdio;
+
+use sqlx::net::StreamSocket;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use tokio::process::Command;
+
+/// Open a tunnel to `host:port` through `jump_host`, using the stdin and stdout of `ssh -W`.
+async fn open_tunnel(jump_host: &str, host: &str, port: u16) -> io::Result<StreamSocket> {
+    let mut child = Command::new("ssh")
+        .arg("-W")
+        .arg(format!("{host}:{port}"))
+        .arg(jump_host)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let stdin = child.stdin.take().expect("stdin is piped");
+    let stdout = child.stdout.take().expect("stdout is piped");
+
+    // `ssh` exits when the connection closes its stdin, and is then reaped by Tokio.
+    drop(child);
+
+    Ok(StreamSocket::from_tokio(tokio::io::join(stdout, stdin)))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let jump_host = std::env::var("SSH_JUMP_HOST")
+        .expect("Env var SSH_JUMP_HOST is required for this example.");
+    let conn_str =
+        std::env::var("DATABASE_URL").expect("Env var DATABASE_URL is required for this example.");
+
+    let options: PgConnectOptions = conn_str.parse()?;
+
+    // The host and port in `DATABASE_URL` are resolved by the jump host.
+    let host = options.get_host().to_owned();
+    let port = options.get_port();
+
+    let options = options.socket_connector(move || {
+        let jump_host = jump_host.clone();
+        let host = host.clone();
+
+        async move { open_tunnel(&jump_host, &host, port).await }
+    });
+
+    // Every connection in the pool gets its own tunnel.
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect_with(options)
+        .await?;
+
+    let version: String = sqlx::query_scalar("SELECT version()")
+        .fetch_one(&pool)
+        .await?;
+
+    println!(
+        "Connected through {}: {version}",
+        std::env::var("SSH_JUMP_HOST")?
+    );
+
+    pool.close().await;
+
+    Ok(())
+}
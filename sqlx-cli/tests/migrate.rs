@@ -56,10 +56,19 @@ async fn run_reversible_migrations() {
         assert_eq!(db.applied_migrations().await, vec![20230101000000]);
 
         // Dry run upgrade + 2
-        db.run_migration(false, Some(20230301000000), true)
+        let output = db
+            .run_migration(false, Some(20230301000000), true)
             .success();
         assert_eq!(db.applied_migrations().await, vec![20230101000000]);
 
+        // only the pending migrations up to the target version are listed as applicable
+        let stdout = String::from_utf8_lossy(&output.get_output().stdout).into_owned();
+        assert!(!stdout.contains("20230101000000"), "{stdout}");
+        assert!(stdout.contains("Can apply 20230201000000"), "{stdout}");
+        assert!(stdout.contains("Can apply 20230301000000"), "{stdout}");
+        assert!(stdout.contains("Skipped 20230401000000"), "{stdout}");
+        assert!(stdout.contains("Skipped 20230501000000"), "{stdout}");
+
         // Upgrade to non-existent version.
         db.run_migration(false, Some(20230901000000999), false)
             .failure();
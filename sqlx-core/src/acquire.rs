@@ -29,6 +29,29 @@ use std::ops::{Deref, DerefMut};
 /// }
 /// ```
 ///
+/// This is implemented for `&Pool`, `&mut PoolConnection`, `&mut` connections such as
+/// `&mut PgConnection`, and `&mut Transaction`. Calling [`begin`][`Acquire::begin`] on a
+/// transaction starts a nested transaction, using a savepoint:
+///
+/// ```rust
+/// # use sqlx::{Acquire, postgres::Postgres, error::BoxDynError};
+/// # #[cfg(any(postgres_9_6, postgres_15))]
+/// async fn insert_user<'a, A>(conn: A, name: &str) -> Result<(), BoxDynError>
+/// where
+///     A: Acquire<'a, Database = Postgres>,
+/// {
+///     let mut tx = conn.begin().await?;
+///
+///     sqlx::query!("INSERT INTO users (name) VALUES ($1)", name)
+///         .execute(&mut *tx)
+///         .await?;
+///
+///     tx.commit().await?;
+///
+///     Ok(())
+/// }
+/// ```
+///
 /// If you run into a lifetime error about "implementation of `sqlx::Acquire` is
 /// not general enough", the [workaround] looks like this:
 ///
@@ -1,10 +1,12 @@
 use crate::any::value::AnyValueKind;
-use crate::any::{Any, AnyTypeInfoKind};
-use crate::arguments::Arguments;
+use crate::any::{Any, AnyTypeInfo, AnyTypeInfoKind};
+use crate::arguments::{ArgumentSummary, Arguments, ArgumentsSummary};
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::type_info::TypeInfo;
 use crate::types::Type;
 
+#[derive(Clone)]
 pub struct AnyArguments<'q> {
     #[doc(hidden)]
     pub values: AnyArgumentBuffer<'q>,
@@ -28,8 +30,37 @@ impl<'q> Arguments<'q> for AnyArguments<'q> {
     fn len(&self) -> usize {
         self.values.0.len()
     }
+
+    fn summary(&self) -> ArgumentsSummary {
+        let arguments = self
+            .values
+            .0
+            .iter()
+            .map(|value| {
+                let (kind, size) = match value {
+                    AnyValueKind::Null(kind) => (*kind, None),
+                    AnyValueKind::Text(text) => (AnyTypeInfoKind::Text, Some(text.len())),
+                    AnyValueKind::Blob(blob) => (AnyTypeInfoKind::Blob, Some(blob.len())),
+                    AnyValueKind::Bool(_) => (AnyTypeInfoKind::Bool, Some(1)),
+                    AnyValueKind::SmallInt(_) => (AnyTypeInfoKind::SmallInt, Some(2)),
+                    AnyValueKind::Integer(_) => (AnyTypeInfoKind::Integer, Some(4)),
+                    AnyValueKind::BigInt(_) => (AnyTypeInfoKind::BigInt, Some(8)),
+                    AnyValueKind::Real(_) => (AnyTypeInfoKind::Real, Some(4)),
+                    AnyValueKind::Double(_) => (AnyTypeInfoKind::Double, Some(8)),
+                };
+
+                ArgumentSummary {
+                    type_name: AnyTypeInfo { kind }.name().to_owned(),
+                    size,
+                }
+            })
+            .collect();
+
+        ArgumentsSummary::new::<Any>(arguments)
+    }
 }
 
+#[derive(Clone)]
 pub struct AnyArgumentBuffer<'q>(#[doc(hidden)] pub Vec<AnyValueKind<'q>>);
 
 impl<'q> Default for AnyArguments<'q> {
@@ -1,5 +1,6 @@
 use crate::any::{Any, AnyArguments, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
 use crate::describe::Describe;
+use crate::placeholders::PlaceholderSyntax;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
@@ -9,6 +10,9 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// The backend name.
     fn name(&self) -> &str;
 
+    /// The syntax of bind parameter placeholders in SQL for this backend.
+    fn placeholder_syntax(&self) -> PlaceholderSyntax;
+
     /// Explicitly close this database connection.
     ///
     /// This method is **not required** for safe and consistent operation. However, it is
@@ -25,6 +29,13 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
+    /// Forward to [`Connection::reset()`].
+    ///
+    /// [`Connection::reset()`]: method@crate::connection::Connection::reset
+    fn reset(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        Box::pin(async move { Ok(()) })
+    }
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     fn begin(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
@@ -56,6 +67,11 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    #[doc(hidden)]
+    fn take_fatal_error(&mut self) -> Option<crate::Error> {
+        None
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(&mut self) -> crate::Result<&mut (dyn crate::migrate::Migrate + Send + 'static)> {
         Err(crate::Error::Configuration(
@@ -5,7 +5,7 @@ use crate::executor::{Execute, Executor};
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
-use futures_util::{stream, FutureExt, StreamExt};
+use futures_util::{stream, FutureExt, StreamExt, TryStreamExt};
 use std::future;
 
 impl<'c> Executor<'c> for &'c mut AnyConnection {
@@ -23,8 +23,18 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
             Ok(arguments) => arguments,
             Err(error) => return stream::once(future::ready(Err(error))).boxed(),
         };
-        self.backend
-            .fetch_many(query.sql(), query.persistent(), arguments)
+        let persistent = query.persistent();
+
+        // the stream owns the query, as the SQL may be borrowed from it
+        Box::pin(try_stream! {
+            let mut s = self.backend.fetch_many(query.sql(), persistent, arguments);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            Ok(())
+        })
     }
 
     fn fetch_optional<'e, 'q: 'e, E>(
@@ -39,8 +49,13 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
             Ok(arguments) => arguments,
             Err(error) => return future::ready(Err(error)).boxed(),
         };
-        self.backend
-            .fetch_optional(query.sql(), query.persistent(), arguments)
+        let persistent = query.persistent();
+
+        Box::pin(async move {
+            self.backend
+                .fetch_optional(query.sql(), persistent, arguments)
+                .await
+        })
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -3,6 +3,7 @@ use futures_core::future::BoxFuture;
 use crate::any::{Any, AnyConnectOptions};
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
+use crate::placeholders::PlaceholderSyntax;
 
 use crate::database::Database;
 pub use backend::AnyConnectionBackend;
@@ -32,6 +33,13 @@ impl AnyConnection {
         self.backend.name()
     }
 
+    /// Returns the syntax of bind parameter placeholders in SQL for the database backend in use.
+    ///
+    /// See [`translate_placeholders()`][crate::placeholders::translate_placeholders].
+    pub fn placeholder_syntax(&self) -> PlaceholderSyntax {
+        self.backend.placeholder_syntax()
+    }
+
     pub(crate) fn connect(options: &AnyConnectOptions) -> BoxFuture<'_, crate::Result<Self>> {
         Box::pin(async {
             let driver = crate::any::driver::from_url(&options.database_url)?;
@@ -83,6 +91,10 @@ impl Connection for AnyConnection {
         self.backend.ping()
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.backend.reset()
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -111,4 +123,8 @@ impl Connection for AnyConnection {
     fn should_flush(&self) -> bool {
         self.backend.should_flush()
     }
+
+    fn take_fatal_error(&mut self) -> Option<Error> {
+        self.backend.take_fatal_error()
+    }
 }
@@ -3,6 +3,7 @@ use crate::any::{
     AnyStatement, AnyTransactionManager, AnyTypeInfo, AnyValue, AnyValueRef,
 };
 use crate::database::{Database, HasStatementCache};
+use crate::placeholders::PlaceholderSyntax;
 
 /// Opaque database driver. Capable of being used in place of any SQLx database driver. The actual
 /// driver used will be selected at runtime, from the connection url.
@@ -33,6 +34,8 @@ impl Database for Any {
     const NAME: &'static str = "Any";
 
     const URL_SCHEMES: &'static [&'static str] = &[];
+
+    const PLACEHOLDER_SYNTAX: PlaceholderSyntax = PlaceholderSyntax::Backend;
 }
 
 // This _may_ be true, depending on the selected database
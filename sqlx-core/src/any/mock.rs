@@ -0,0 +1,485 @@
+//! A mock [`AnyConnection`] for unit testing code that talks to a database, without a database.
+//!
+//! A [`MockConnection`] holds a script of the statements it expects to execute, in order,
+//! and what each should return. It is converted into an [`AnyConnection`], so any code that
+//! accepts an [`AnyExecutor`][crate::any::AnyExecutor], a `&mut AnyConnection` or a
+//! `Transaction<'_, Any>` can be tested with it.
+//!
+//! The connection panics as soon as it sees a statement it did not expect, and when it is dropped
+//! or closed if any expectations were left unmet.
+//!
+//! ### Note: compile-time checked macros
+//! The `query!()` family of macros cannot be used with the `Any` driver and so not with this
+//! connection either; use `query()`, `query_as()` or `query_scalar()` instead.
+//!
+//! ```rust,ignore
+//! use sqlx::any::mock::{MockConnection, MockRow};
+//! use sqlx::{AnyConnection, Connection};
+//!
+//! /// Creates the user if it doesn't exist yet, returning its ID.
+//! async fn ensure_user(conn: &mut AnyConnection, name: &str) -> sqlx::Result<i64> {
+//!     let mut tx = conn.begin().await?;
+//!
+//!     let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM users WHERE name = ?")
+//!         .bind(name)
+//!         .fetch_optional(&mut *tx)
+//!         .await?;
+//!
+//!     let id = match existing {
+//!         Some(id) => id,
+//!         None => {
+//!             sqlx::query_scalar("INSERT INTO users (name) VALUES (?) RETURNING id")
+//!                 .bind(name)
+//!                 .fetch_one(&mut *tx)
+//!                 .await?
+//!         }
+//!     };
+//!
+//!     tx.commit().await?;
+//!
+//!     Ok(id)
+//! }
+//!
+//! let mut mock = MockConnection::new();
+//!
+//! mock.expect_begin();
+//! mock.expect_query("SELECT id FROM users WHERE name = ?")
+//!     .bind("ferris")
+//!     .returning([]);
+//! mock.expect_query_matching(r"^INSERT INTO users")
+//!     .bind("ferris")
+//!     .returning([MockRow::new().column("id", 42_i64)])
+//!     .rows_affected(1);
+//! mock.expect_commit();
+//!
+//! let mut conn = AnyConnection::from(mock);
+//!
+//! assert_eq!(ensure_user(&mut conn, "ferris").await?, 42);
+//! ```
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::{stream, StreamExt};
+use regex::Regex;
+
+use crate::any::{
+    Any, AnyArgumentBuffer, AnyArguments, AnyColumn, AnyConnection, AnyConnectionBackend,
+    AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo, AnyValue, AnyValueKind,
+};
+use crate::describe::Describe;
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::ext::ustr::UStr;
+use crate::placeholders::PlaceholderSyntax;
+use crate::types::Type;
+use crate::HashMap;
+
+/// A scripted connection for unit tests. See [the module documentation][self] for details.
+#[derive(Debug, Default)]
+pub struct MockConnection {
+    expectations: VecDeque<Expectation>,
+}
+
+/// An expected statement, created by [`MockConnection::expect_query()`]
+/// or [`MockConnection::expect_query_matching()`].
+#[derive(Debug)]
+pub struct MockQuery {
+    sql: SqlMatcher,
+    arguments: Option<Vec<AnyValueKind<'static>>>,
+    rows: Vec<MockRow>,
+    result: AnyQueryResult,
+    error: Option<Error>,
+}
+
+/// A row to be returned by a [`MockQuery`].
+#[derive(Debug, Clone, Default)]
+pub struct MockRow {
+    columns: Vec<(UStr, AnyValueKind<'static>)>,
+}
+
+#[derive(Debug)]
+enum Expectation {
+    Query(MockQuery),
+    Begin,
+    Commit,
+    Rollback,
+}
+
+enum SqlMatcher {
+    Exact(String),
+    Regex(Regex),
+}
+
+impl MockConnection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect a statement with exactly this SQL, ignoring differences in whitespace.
+    pub fn expect_query(&mut self, sql: &str) -> &mut MockQuery {
+        self.push_query(SqlMatcher::Exact(normalize_whitespace(sql)))
+    }
+
+    /// Expect a statement with SQL matching this regular expression.
+    ///
+    /// ### Panics
+    /// If `pattern` is not a valid regular expression.
+    pub fn expect_query_matching(&mut self, pattern: &str) -> &mut MockQuery {
+        let regex = Regex::new(pattern)
+            .unwrap_or_else(|e| panic!("invalid regex for mock query {pattern:?}: {e}"));
+
+        self.push_query(SqlMatcher::Regex(regex))
+    }
+
+    /// Expect a transaction to be started, or a savepoint to be created within one.
+    pub fn expect_begin(&mut self) -> &mut Self {
+        self.expectations.push_back(Expectation::Begin);
+        self
+    }
+
+    /// Expect the innermost transaction or savepoint to be committed.
+    pub fn expect_commit(&mut self) -> &mut Self {
+        self.expectations.push_back(Expectation::Commit);
+        self
+    }
+
+    /// Expect the innermost transaction or savepoint to be rolled back,
+    /// either explicitly or by dropping the [`Transaction`][crate::transaction::Transaction].
+    pub fn expect_rollback(&mut self) -> &mut Self {
+        self.expectations.push_back(Expectation::Rollback);
+        self
+    }
+
+    fn push_query(&mut self, sql: SqlMatcher) -> &mut MockQuery {
+        self.expectations.push_back(Expectation::Query(MockQuery {
+            sql,
+            arguments: None,
+            rows: Vec::new(),
+            result: AnyQueryResult::default(),
+            error: None,
+        }));
+
+        match self.expectations.back_mut() {
+            Some(Expectation::Query(query)) => query,
+            _ => unreachable!(),
+        }
+    }
+
+    fn next(&mut self, actual: &dyn Fn() -> String) -> Expectation {
+        self.expectations
+            .pop_front()
+            .unwrap_or_else(|| panic!("mock connection: unexpected {}", actual()))
+    }
+
+    fn expect_transaction(&mut self, expected: &str) {
+        match self.next(&|| expected.to_owned()) {
+            Expectation::Begin if expected == "BEGIN" => {}
+            Expectation::Commit if expected == "COMMIT" => {}
+            Expectation::Rollback if expected == "ROLLBACK" => {}
+            other => panic!("mock connection: expected {other:?}, got {expected}"),
+        }
+    }
+
+    fn execute(
+        &mut self,
+        sql: &str,
+        arguments: Option<AnyArguments<'_>>,
+    ) -> Result<(Vec<AnyRow>, AnyQueryResult), Error> {
+        let query = match self.next(&|| format!("query `{sql}`")) {
+            Expectation::Query(query) => query,
+            other => panic!("mock connection: expected {other:?}, got query `{sql}`"),
+        };
+
+        if !query.sql.matches(sql) {
+            panic!(
+                "mock connection: expected query {:?}, got `{sql}`",
+                query.sql
+            );
+        }
+
+        if let Some(expected) = &query.arguments {
+            let actual = arguments.map(|args| args.values.0).unwrap_or_default();
+
+            if expected.len() != actual.len()
+                || !expected.iter().zip(&actual).all(|(e, a)| value_eq(e, a))
+            {
+                panic!(
+                    "mock connection: unexpected bind values for query `{sql}`\n  \
+                     expected: {expected:?}\n    actual: {actual:?}"
+                );
+            }
+        }
+
+        if let Some(error) = query.error {
+            return Err(error);
+        }
+
+        let rows = query.rows.into_iter().map(MockRow::into_row).collect();
+
+        Ok((rows, query.result))
+    }
+}
+
+impl Drop for MockConnection {
+    fn drop(&mut self) {
+        if !self.expectations.is_empty() && !std::thread::panicking() {
+            panic!(
+                "mock connection dropped with unmet expectations: {:?}",
+                self.expectations
+            );
+        }
+    }
+}
+
+impl From<MockConnection> for AnyConnection {
+    fn from(mock: MockConnection) -> Self {
+        AnyConnection {
+            backend: Box::new(mock),
+        }
+    }
+}
+
+impl MockQuery {
+    /// Expect the next bind value of the statement to be `value`.
+    ///
+    /// If this is never called, the bind values of the statement are not checked.
+    pub fn bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'static + Encode<'static, Any> + Type<Any>,
+    {
+        self.arguments
+            .get_or_insert_with(Vec::new)
+            .push(encode_value(value));
+        self
+    }
+
+    /// Return these rows from the statement.
+    pub fn returning(&mut self, rows: impl IntoIterator<Item = MockRow>) -> &mut Self {
+        self.rows.extend(rows);
+        self
+    }
+
+    /// Report this many rows affected by the statement.
+    pub fn rows_affected(&mut self, rows_affected: u64) -> &mut Self {
+        self.result.rows_affected = rows_affected;
+        self
+    }
+
+    /// Report this ID as the last inserted by the statement.
+    pub fn last_insert_id(&mut self, last_insert_id: i64) -> &mut Self {
+        self.result.last_insert_id = Some(last_insert_id);
+        self
+    }
+
+    /// Fail the statement with `error` instead of returning rows.
+    pub fn returning_error(&mut self, error: Error) -> &mut Self {
+        self.error = Some(error);
+        self
+    }
+}
+
+impl MockRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a column with the given name and value to the row.
+    pub fn column<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'static + Encode<'static, Any> + Type<Any>,
+    {
+        self.columns.push((UStr::new(name), encode_value(value)));
+        self
+    }
+
+    fn into_row(self) -> AnyRow {
+        let mut column_names = HashMap::with_capacity(self.columns.len());
+        let mut columns = Vec::with_capacity(self.columns.len());
+        let mut values = Vec::with_capacity(self.columns.len());
+
+        for (ordinal, (name, kind)) in self.columns.into_iter().enumerate() {
+            let type_info = kind.type_info();
+
+            column_names.insert(name.clone(), ordinal);
+            columns.push(AnyColumn {
+                ordinal,
+                name,
+                type_info,
+            });
+            values.push(AnyValue { kind });
+        }
+
+        AnyRow {
+            column_names: Arc::new(column_names),
+            columns,
+            values,
+        }
+    }
+}
+
+impl SqlMatcher {
+    fn matches(&self, sql: &str) -> bool {
+        match self {
+            SqlMatcher::Exact(expected) => *expected == normalize_whitespace(sql),
+            SqlMatcher::Regex(regex) => regex.is_match(sql),
+        }
+    }
+}
+
+impl Debug for SqlMatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SqlMatcher::Exact(sql) => write!(f, "`{sql}`"),
+            SqlMatcher::Regex(regex) => write!(f, "matching /{regex}/"),
+        }
+    }
+}
+
+impl AnyConnectionBackend for MockConnection {
+    fn name(&self) -> &str {
+        "Mock"
+    }
+
+    fn placeholder_syntax(&self) -> PlaceholderSyntax {
+        // translating is then a no-op, so expectations match the SQL as written
+        PlaceholderSyntax::Dollar
+    }
+
+    fn close(self: Box<Self>) -> BoxFuture<'static, crate::Result<()>> {
+        // unmet expectations are reported on drop
+        drop(self);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn close_hard(self: Box<Self>) -> BoxFuture<'static, crate::Result<()>> {
+        self.close()
+    }
+
+    fn ping(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn begin(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        self.expect_transaction("BEGIN");
+        Box::pin(async { Ok(()) })
+    }
+
+    fn commit(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        self.expect_transaction("COMMIT");
+        Box::pin(async { Ok(()) })
+    }
+
+    fn rollback(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        self.expect_transaction("ROLLBACK");
+        Box::pin(async { Ok(()) })
+    }
+
+    fn start_rollback(&mut self) {
+        // called from `Drop for Transaction`; don't turn an unwinding panic into an abort
+        if !std::thread::panicking() {
+            self.expect_transaction("ROLLBACK");
+        }
+    }
+
+    fn shrink_buffers(&mut self) {}
+
+    fn flush(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn should_flush(&self) -> bool {
+        false
+    }
+
+    fn fetch_many<'q>(
+        &'q mut self,
+        query: &'q str,
+        _persistent: bool,
+        arguments: Option<AnyArguments<'q>>,
+    ) -> BoxStream<'q, crate::Result<Either<AnyQueryResult, AnyRow>>> {
+        match self.execute(query, arguments) {
+            Ok((rows, result)) => stream::iter(
+                rows.into_iter()
+                    .map(|row| Ok(Either::Right(row)))
+                    .chain([Ok(Either::Left(result))]),
+            )
+            .boxed(),
+            Err(error) => stream::once(async { Err(error) }).boxed(),
+        }
+    }
+
+    fn fetch_optional<'q>(
+        &'q mut self,
+        query: &'q str,
+        _persistent: bool,
+        arguments: Option<AnyArguments<'q>>,
+    ) -> BoxFuture<'q, crate::Result<Option<AnyRow>>> {
+        let res = self
+            .execute(query, arguments)
+            .map(|(rows, _)| rows.into_iter().next());
+
+        Box::pin(async { res })
+    }
+
+    fn prepare_with<'c, 'q: 'c>(
+        &'c mut self,
+        sql: &'q str,
+        parameters: &[AnyTypeInfo],
+    ) -> BoxFuture<'c, crate::Result<AnyStatement<'q>>> {
+        let statement = AnyStatement {
+            sql: Cow::Borrowed(sql),
+            parameters: Some(Either::Left(parameters.to_vec())),
+            column_names: Default::default(),
+            columns: Vec::new(),
+        };
+
+        Box::pin(async { Ok(statement) })
+    }
+
+    fn describe<'q>(&'q mut self, _sql: &'q str) -> BoxFuture<'q, crate::Result<Describe<Any>>> {
+        Box::pin(async {
+            Err(Error::Configuration(
+                "mock connections do not support `describe()`".into(),
+            ))
+        })
+    }
+}
+
+fn encode_value<T>(value: T) -> AnyValueKind<'static>
+where
+    T: 'static + Encode<'static, Any> + Type<Any>,
+{
+    let mut buf = AnyArgumentBuffer(Vec::with_capacity(1));
+
+    if let Err(e) = value.encode(&mut buf) {
+        panic!("failed to encode mock value: {e}");
+    }
+
+    buf.0.pop().expect("`Encode` impl did not produce a value")
+}
+
+fn value_eq(expected: &AnyValueKind<'_>, actual: &AnyValueKind<'_>) -> bool {
+    use AnyValueKind::*;
+
+    match (expected, actual) {
+        (Null(expected), Null(actual)) => expected == actual,
+        (Bool(expected), Bool(actual)) => expected == actual,
+        (SmallInt(expected), SmallInt(actual)) => expected == actual,
+        (Integer(expected), Integer(actual)) => expected == actual,
+        (BigInt(expected), BigInt(actual)) => expected == actual,
+        (Real(expected), Real(actual)) => expected == actual,
+        (Double(expected), Double(actual)) => expected == actual,
+        (Text(expected), Text(actual)) => expected == actual,
+        (Blob(expected), Blob(actual)) => expected == actual,
+        _ => false,
+    }
+}
+
+fn normalize_whitespace(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -22,6 +22,9 @@ pub(crate) mod value;
 
 pub mod driver;
 
+#[cfg(feature = "test-util")]
+pub mod mock;
+
 #[cfg(feature = "migrate")]
 mod migrate;
 
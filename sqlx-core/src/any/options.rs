@@ -1,9 +1,11 @@
 use crate::any::AnyConnection;
 use crate::connection::{ConnectOptions, LogSettings};
 use crate::error::Error;
+use crate::metrics::Metrics;
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -62,4 +64,9 @@ impl ConnectOptions for AnyConnectOptions {
         self.log_settings.slow_statements_duration = duration;
         self
     }
+
+    fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.log_settings.metrics(metrics);
+        self
+    }
 }
@@ -13,6 +13,10 @@ impl AnyQueryResult {
         self.rows_affected
     }
 
+    /// The ID of the last inserted row, if the database reports one.
+    ///
+    /// This is `None` for MySQL IDs greater than `i64::MAX`; use
+    /// `MySqlQueryResult::last_insert_id()` for those.
     pub fn last_insert_id(&self) -> Option<i64> {
         self.last_insert_id
     }
@@ -4,7 +4,7 @@ use crate::any::{Any, AnyTypeInfo, AnyTypeInfoKind};
 use crate::database::Database;
 use crate::error::BoxDynError;
 use crate::types::Type;
-use crate::value::{Value, ValueRef};
+use crate::value::{DynamicValue, ToDynamicValue, Value, ValueRef};
 
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -21,7 +21,7 @@ pub enum AnyValueKind<'a> {
 }
 
 impl AnyValueKind<'_> {
-    fn type_info(&self) -> AnyTypeInfo {
+    pub(in crate::any) fn type_info(&self) -> AnyTypeInfo {
         AnyTypeInfo {
             kind: match self {
                 AnyValueKind::Null(_) => AnyTypeInfoKind::Null,
@@ -123,3 +123,19 @@ impl<'a> ValueRef<'a> for AnyValueRef<'a> {
         matches!(self.kind, AnyValueKind::Null(_))
     }
 }
+
+impl ToDynamicValue for AnyValueRef<'_> {
+    fn to_dynamic(&self) -> Result<DynamicValue, BoxDynError> {
+        Ok(match &self.kind {
+            AnyValueKind::Null(_) => DynamicValue::Null,
+            AnyValueKind::Bool(b) => DynamicValue::Bool(*b),
+            AnyValueKind::SmallInt(i) => DynamicValue::Int((*i).into()),
+            AnyValueKind::Integer(i) => DynamicValue::Int((*i).into()),
+            AnyValueKind::BigInt(i) => DynamicValue::Int(*i),
+            AnyValueKind::Real(r) => DynamicValue::Double((*r).into()),
+            AnyValueKind::Double(d) => DynamicValue::Double(*d),
+            AnyValueKind::Text(t) => DynamicValue::Text(t.to_string()),
+            AnyValueKind::Blob(b) => DynamicValue::Bytes(b.to_vec()),
+        })
+    }
+}
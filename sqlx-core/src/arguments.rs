@@ -3,8 +3,9 @@
 use crate::database::Database;
 use crate::encode::Encode;
 use crate::error::BoxDynError;
+use crate::placeholders::PlaceholderSyntax;
 use crate::types::Type;
-use std::fmt::{self, Write};
+use std::fmt::{self, Display, Write};
 
 /// A tuple of arguments to be sent to the database.
 // This lint is designed for general collections, but `Arguments` is not meant to be as such.
@@ -24,11 +25,81 @@ pub trait Arguments<'q>: Send + Sized + Default {
     /// The number of arguments that were already added.
     fn len(&self) -> usize;
 
+    /// The type and size of each argument that was already added, but not its value.
+    ///
+    /// The default implementation returns an empty summary, for drivers which can't tell.
+    fn summary(&self) -> ArgumentsSummary {
+        ArgumentsSummary::new::<Self::Database>(Vec::new())
+    }
+
     fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
         writer.write_str("?")
     }
 }
 
+/// A summary of the arguments bound to a query, e.g. for logging.
+///
+/// It has the type and size of each argument, but not its value, and is displayed like
+/// `[$1: INT8 (8 bytes), $2: TEXT (11 bytes), $3: TEXT NULL]`.
+///
+/// The arguments are numbered like `#1` if the placeholder syntax is only known at run time,
+/// as for `Any`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentsSummary {
+    syntax: PlaceholderSyntax,
+    arguments: Vec<ArgumentSummary>,
+}
+
+/// The type and size of an argument in an [`ArgumentsSummary`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentSummary {
+    /// The name of the type the argument was sent as, e.g. `INT8`.
+    pub type_name: String,
+
+    /// The size of the value in bytes, not including any length prefix,
+    /// or `None` if it is `NULL`.
+    pub size: Option<usize>,
+}
+
+impl ArgumentsSummary {
+    pub fn new<DB: Database>(arguments: Vec<ArgumentSummary>) -> Self {
+        Self {
+            syntax: DB::PLACEHOLDER_SYNTAX,
+            arguments,
+        }
+    }
+
+    pub fn arguments(&self) -> &[ArgumentSummary] {
+        &self.arguments
+    }
+}
+
+impl Display for ArgumentsSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('[')?;
+
+        for (i, argument) in self.arguments.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            match self.syntax {
+                PlaceholderSyntax::Dollar => write!(f, "${}: ", i + 1)?,
+                PlaceholderSyntax::QuestionMark => f.write_str("?: ")?,
+                PlaceholderSyntax::Backend => write!(f, "#{}: ", i + 1)?,
+            }
+
+            match argument.size {
+                Some(1) => write!(f, "{} (1 byte)", argument.type_name)?,
+                Some(size) => write!(f, "{} ({size} bytes)", argument.type_name)?,
+                None => write!(f, "{} NULL", argument.type_name)?,
+            }
+        }
+
+        f.write_char(']')
+    }
+}
+
 pub trait IntoArguments<'q, DB: Database>: Sized + Send {
     fn into_arguments(self) -> <DB as Database>::Arguments<'q>;
 }
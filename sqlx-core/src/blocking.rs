@@ -0,0 +1,175 @@
+//! A synchronous interface to a database connection, for use in code that is not async,
+//! such as small command-line tools.
+//!
+//! ```rust,ignore
+//! use sqlx::blocking::{Close, Connect, Connection};
+//! use sqlx::{Row, Sqlite};
+//!
+//! let mut conn = Connection::<Sqlite>::connect("sqlite::memory:")?;
+//!
+//! conn.execute(sqlx::query("CREATE TABLE users (name TEXT NOT NULL)"))?;
+//! conn.execute(sqlx::query("INSERT INTO users (name) VALUES (?)").bind("Ferris"))?;
+//!
+//! let row = conn.fetch_one(sqlx::query("SELECT name FROM users"))?;
+//! let name: String = row.try_get("name")?;
+//!
+//! conn.close()?;
+//! ```
+use std::future::Future;
+
+use crate::connection::Connection as _;
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+
+/// A database connection that can be used from synchronous code.
+///
+/// Each method runs the corresponding async operation to completion on the current thread.
+///
+/// With the `runtime-tokio` feature, each connection owns a single-threaded Tokio runtime to
+/// drive its I/O. Because of this, these methods will panic if called from within an
+/// async context; use the regular, async connection types there instead.
+///
+/// Without a runtime feature, only SQLite connections are supported, as SQLite does its
+/// I/O synchronously on a background thread. Other drivers will panic when connecting.
+pub struct Connection<DB: Database> {
+    // NOTE: must be dropped before `block_on` so I/O resources are released while
+    // the runtime that owns them is still alive
+    conn: DB::Connection,
+    block_on: BlockOn,
+}
+
+/// Synchronously establish a database connection.
+pub trait Connect: Sized {
+    type Options;
+
+    /// Establish a new database connection.
+    ///
+    /// See [`Connection::connect()`][crate::connection::Connection::connect].
+    fn connect(url: &str) -> Result<Self, Error>;
+
+    /// Establish a new database connection with the provided options.
+    ///
+    /// See [`Connection::connect_with()`][crate::connection::Connection::connect_with].
+    fn connect_with(options: &Self::Options) -> Result<Self, Error>;
+}
+
+/// Synchronously close a database connection.
+pub trait Close {
+    /// Explicitly close this database connection.
+    ///
+    /// See [`Connection::close()`][crate::connection::Connection::close].
+    fn close(self) -> Result<(), Error>;
+}
+
+impl<DB: Database> Connect for Connection<DB> {
+    type Options = <DB::Connection as crate::connection::Connection>::Options;
+
+    fn connect(url: &str) -> Result<Self, Error> {
+        let block_on = BlockOn::new()?;
+        let conn = block_on.block_on(DB::Connection::connect(url))?;
+
+        Ok(Self { conn, block_on })
+    }
+
+    fn connect_with(options: &Self::Options) -> Result<Self, Error> {
+        let block_on = BlockOn::new()?;
+        let conn = block_on.block_on(DB::Connection::connect_with(options))?;
+
+        Ok(Self { conn, block_on })
+    }
+}
+
+impl<DB: Database> Close for Connection<DB> {
+    fn close(self) -> Result<(), Error> {
+        let Self { conn, block_on } = self;
+
+        block_on.block_on(conn.close())
+    }
+}
+
+impl<DB: Database> Connection<DB> {
+    /// Checks if a connection to the database is still valid.
+    pub fn ping(&mut self) -> Result<(), Error> {
+        self.block_on.block_on(self.conn.ping())
+    }
+
+    /// Execute the query and return the total number of rows affected.
+    pub fn execute<'q, E>(&mut self, query: E) -> Result<DB::QueryResult, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.block_on.block_on(self.conn.execute(query))
+    }
+
+    /// Execute the query and return all the generated results.
+    pub fn fetch_all<'q, E>(&mut self, query: E) -> Result<Vec<DB::Row>, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.block_on.block_on(self.conn.fetch_all(query))
+    }
+
+    /// Execute the query and returns exactly one row.
+    pub fn fetch_one<'q, E>(&mut self, query: E) -> Result<DB::Row, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.block_on.block_on(self.conn.fetch_one(query))
+    }
+
+    /// Execute the query and returns at most one row.
+    pub fn fetch_optional<'q, E>(&mut self, query: E) -> Result<Option<DB::Row>, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.block_on.block_on(self.conn.fetch_optional(query))
+    }
+
+    /// Run an async operation on the underlying connection to completion.
+    ///
+    /// This makes the full async API available, e.g. to decode rows with `query_as()`:
+    ///
+    /// ```rust,ignore
+    /// let names: Vec<(String,)> = conn.run(|conn| {
+    ///     sqlx::query_as("SELECT name FROM users").fetch_all(conn)
+    /// })?;
+    /// ```
+    pub fn run<'c, F>(&'c mut self, f: impl FnOnce(&'c mut DB::Connection) -> F) -> F::Output
+    where
+        F: Future,
+    {
+        self.block_on.block_on(f(&mut self.conn))
+    }
+}
+
+struct BlockOn {
+    #[cfg(feature = "_rt-tokio")]
+    runtime: tokio::runtime::Runtime,
+}
+
+impl BlockOn {
+    fn new() -> Result<Self, Error> {
+        Ok(Self {
+            #[cfg(feature = "_rt-tokio")]
+            runtime: tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?,
+        })
+    }
+
+    fn block_on<F: Future>(&self, f: F) -> F::Output {
+        #[cfg(feature = "_rt-tokio")]
+        return self.runtime.block_on(f);
+
+        #[cfg(all(feature = "_rt-async-std", not(feature = "_rt-tokio")))]
+        return async_std::task::block_on(f);
+
+        #[cfg(not(any(feature = "_rt-async-std", feature = "_rt-tokio")))]
+        return futures_executor::block_on(f);
+    }
+}
@@ -0,0 +1,288 @@
+//! Checking queries against the types they are decoded into, at runtime.
+//!
+//! The query macros check queries at compile time, but that isn't possible for SQL that is
+//! only known at runtime. [`verify()`] performs the same checks on startup instead:
+//!
+//! ```rust,no_run
+//! # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+//! #[derive(sqlx::FromRow)]
+//! struct User {
+//!     id: i64,
+//!     name: String,
+//!     email: Option<String>,
+//! }
+//!
+//! let report = sqlx::check::verify::<User, _>(pool, "SELECT id, name, email FROM users").await?;
+//!
+//! if !report.is_ok() {
+//!     panic!("{report}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::any::type_name;
+use std::fmt::{self, Debug, Display, Formatter};
+
+use crate::column::Column;
+use crate::database::Database;
+use crate::describe::Describe;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::from_row::FromRow;
+use crate::type_info::TypeInfo;
+use crate::types::Type;
+
+/// A column that a [`FromRow`] implementation reads, as returned by [`FromRow::columns()`].
+pub struct ExpectedColumn<DB: Database> {
+    name: Option<&'static str>,
+    index: usize,
+    type_name: &'static str,
+    nullable: bool,
+    optional: bool,
+    compatible: fn(&DB::TypeInfo) -> bool,
+}
+
+impl<DB: Database> ExpectedColumn<DB> {
+    /// A column that is read by its name into a `T`.
+    pub fn named<T: Type<DB>>(name: &'static str) -> Self {
+        ExpectedColumn {
+            name: Some(name),
+            ..Self::indexed::<T>(0)
+        }
+    }
+
+    /// A column that is read by its index into a `T`.
+    pub fn indexed<T: Type<DB>>(index: usize) -> Self {
+        ExpectedColumn {
+            name: None,
+            index,
+            type_name: type_name::<T>(),
+            nullable: false,
+            optional: false,
+            compatible: T::compatible,
+        }
+    }
+
+    /// Set whether the column may be `NULL`, i.e. it's read into an `Option`.
+    pub fn nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    /// Set whether the column may be missing from the row,
+    /// i.e. it has a default value.
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    /// The name of the column, or `None` if it's read by its index.
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// The name of the Rust type the column is read into.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Whether the column may be `NULL`.
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    /// Whether the column may be missing from the row.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Whether the Rust type accepts values of the SQL type `ty`, as checked by
+    /// [`Row::try_get()`][crate::row::Row::try_get].
+    pub fn is_compatible(&self, ty: &DB::TypeInfo) -> bool {
+        (self.compatible)(ty)
+    }
+
+    fn describe(&self) -> String {
+        match self.name {
+            Some(name) => format!("`{name}`"),
+            None => format!("#{}", self.index),
+        }
+    }
+}
+
+impl<DB: Database> Debug for ExpectedColumn<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpectedColumn")
+            .field("name", &self.name)
+            .field("index", &self.index)
+            .field("type_name", &self.type_name)
+            .field("nullable", &self.nullable)
+            .field("optional", &self.optional)
+            .finish()
+    }
+}
+
+/// A difference between a query and the type it is decoded into, found by [`verify()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Mismatch {
+    /// The query returns a different number of columns than the type reads.
+    ColumnCount { expected: usize, found: usize },
+
+    /// A column is read by the type, but not returned by the query.
+    MissingColumn { column: String },
+
+    /// A column has a SQL type that the Rust type can't be decoded from.
+    Type {
+        column: String,
+        rust_type: &'static str,
+        sql_type: String,
+    },
+
+    /// A column may be `NULL`, but the Rust type is not an `Option`.
+    Nullability {
+        column: String,
+        rust_type: &'static str,
+    },
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::ColumnCount { expected, found } => {
+                write!(f, "expected {expected} columns, but the query returns {found}")
+            }
+            Mismatch::MissingColumn { column } => {
+                write!(f, "column {column} is not returned by the query")
+            }
+            Mismatch::Type {
+                column,
+                rust_type,
+                sql_type,
+            } => write!(
+                f,
+                "column {column} has SQL type `{sql_type}`, which is not compatible with Rust type `{rust_type}`"
+            ),
+            Mismatch::Nullability { column, rust_type } => write!(
+                f,
+                "column {column} may be NULL, but is read into non-optional Rust type `{rust_type}`"
+            ),
+        }
+    }
+}
+
+/// The result of [`verify()`]: every difference found between a query and a type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl Report {
+    /// Returns `true` if no mismatches were found.
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return f.write_str("no mismatches");
+        }
+
+        for (i, mismatch) in self.mismatches.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+
+            write!(f, "{mismatch}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that the rows returned by `sql` can be decoded into `T`.
+///
+/// The query is [described][Executor::describe] by the database, but not executed.
+/// Its columns are compared to the columns returned by [`FromRow::columns()`], which is
+/// implemented by `#[derive(FromRow)]`:
+///
+/// * the number of columns,
+/// * the name of every column that is read by name,
+/// * the SQL type of every column, in the same way as [`Row::try_get()`][crate::row::Row::try_get],
+/// * and whether columns that may be `NULL` are read into an `Option`.
+///
+/// Nullability is only checked if the database reports it for a column. Columns that are not
+/// `NULL` may still be read into an `Option`.
+///
+/// # Errors
+/// * [`Error::InvalidArgument`] if `T` does not implement [`FromRow::columns()`].
+/// * Any error returned by the database while describing the query, e.g. if it is invalid.
+pub async fn verify<'c, T, E>(executor: E, sql: &str) -> Result<Report, Error>
+where
+    E: Executor<'c>,
+    T: for<'r> FromRow<'r, <E::Database as Database>::Row>,
+{
+    let expected = T::columns().ok_or_else(|| {
+        Error::InvalidArgument(format!(
+            "`{}` does not describe the columns it reads from a row",
+            type_name::<T>()
+        ))
+    })?;
+
+    let describe = executor.describe(sql).await?;
+
+    Ok(compare(&expected, &describe))
+}
+
+fn compare<DB: Database>(expected: &[ExpectedColumn<DB>], describe: &Describe<DB>) -> Report {
+    let mut mismatches = Vec::new();
+
+    let columns = describe.columns();
+    let required = expected.iter().filter(|c| !c.optional).count();
+
+    if columns.len() < required || columns.len() > expected.len() {
+        mismatches.push(Mismatch::ColumnCount {
+            expected: expected.len(),
+            found: columns.len(),
+        });
+    }
+
+    for expected in expected {
+        let index = match expected.name {
+            Some(name) => columns.iter().position(|column| column.name() == name),
+            None => (expected.index < columns.len()).then_some(expected.index),
+        };
+
+        let Some(index) = index else {
+            // a missing column that is read by index is already reported as a column count
+            if !expected.optional && expected.name.is_some() {
+                mismatches.push(Mismatch::MissingColumn {
+                    column: expected.describe(),
+                });
+            }
+
+            continue;
+        };
+
+        let ty = columns[index].type_info();
+
+        if !ty.is_null() && !expected.is_compatible(ty) {
+            mismatches.push(Mismatch::Type {
+                column: expected.describe(),
+                rust_type: expected.type_name,
+                sql_type: ty.name().to_owned(),
+            });
+        }
+
+        if !expected.nullable && describe.nullable(index) == Some(true) {
+            mismatches.push(Mismatch::Nullability {
+                column: expected.describe(),
+                rust_type: expected.type_name,
+            });
+        }
+    }
+
+    Report { mismatches }
+}
@@ -49,6 +49,77 @@ impl<T: ?Sized, I: ColumnIndex<T> + ?Sized> ColumnIndex<T> for &'_ I {
     }
 }
 
+/// Resolve a column by name when there is no column with exactly that name.
+///
+/// Used by drivers as a fallback after an exact lookup fails:
+///
+/// * a plain name matches case-insensitively, preferring an exact-case match;
+/// * a name of the form `table.column` matches a column from that table, compared against
+///   any of the names the driver knows for it (e.g. the alias used in the query and the
+///   original table name), also case-insensitively.
+///
+/// `columns` yields the ordinal, name and known table names of each column.
+///
+/// Returns [`Error::ColumnAmbiguous`] if more than one column matches equally well,
+/// or [`Error::ColumnNotFound`] if none do.
+#[doc(hidden)]
+pub fn resolve_column_name<'a, C, T>(name: &str, columns: C) -> Result<usize, Error>
+where
+    C: IntoIterator<Item = (usize, &'a str, T)>,
+    T: IntoIterator<Item = &'a str>,
+{
+    // (exact match, ordinal, display name)
+    let mut matches: Vec<(bool, usize, String)> = Vec::new();
+
+    let qualified = name.split_once('.');
+
+    for (ordinal, column_name, tables) in columns {
+        if column_name.eq_ignore_ascii_case(name) {
+            matches.push((column_name == name, ordinal, column_name.to_owned()));
+            continue;
+        }
+
+        let Some((table, qualified_name)) = qualified else {
+            continue;
+        };
+
+        if !column_name.eq_ignore_ascii_case(qualified_name) {
+            continue;
+        }
+
+        let tables: Vec<&str> = tables.into_iter().collect();
+
+        let Some(table_exact) = tables
+            .iter()
+            .filter(|candidate| candidate.eq_ignore_ascii_case(table))
+            .map(|candidate| *candidate == table)
+            .reduce(|a, b| a || b)
+        else {
+            continue;
+        };
+
+        // Display with the first table name, which should disambiguate the column if possible.
+        matches.push((
+            table_exact && column_name == qualified_name,
+            ordinal,
+            format!("{}.{column_name}", tables[0]),
+        ));
+    }
+
+    if matches.iter().any(|(exact, ..)| *exact) {
+        matches.retain(|(exact, ..)| *exact);
+    }
+
+    match &matches[..] {
+        [] => Err(Error::ColumnNotFound(name.into())),
+        [(_, ordinal, _)] => Ok(*ordinal),
+        _ => Err(Error::ColumnAmbiguous {
+            name: name.into(),
+            candidates: matches.into_iter().map(|(.., display)| display).collect(),
+        }),
+    }
+}
+
 #[macro_export]
 macro_rules! impl_column_index_for_row {
     ($R:ident) => {
@@ -82,3 +153,65 @@ macro_rules! impl_column_index_for_statement {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: &[(usize, &str, &[&str])] = &[
+        (0, "id", &["a", "accounts"]),
+        (1, "Name", &["a", "accounts"]),
+        (2, "id", &["b", "accounts"]),
+        (3, "NAME", &["b", "accounts"]),
+        (4, "total", &[]),
+    ];
+
+    fn resolve(name: &str) -> Result<usize, Error> {
+        resolve_column_name(
+            name,
+            COLUMNS
+                .iter()
+                .map(|(ordinal, name, tables)| (*ordinal, *name, tables.iter().copied())),
+        )
+    }
+
+    #[test]
+    fn it_resolves_case_insensitively() {
+        assert_eq!(resolve("TOTAL").unwrap(), 4);
+        assert_eq!(resolve("Name").unwrap(), 1);
+        assert_eq!(resolve("NAME").unwrap(), 3);
+    }
+
+    #[test]
+    fn it_resolves_qualified_names() {
+        assert_eq!(resolve("a.id").unwrap(), 0);
+        assert_eq!(resolve("b.id").unwrap(), 2);
+        assert_eq!(resolve("B.ID").unwrap(), 2);
+        assert_eq!(resolve("b.name").unwrap(), 3);
+    }
+
+    #[test]
+    fn it_reports_ambiguous_names() {
+        match resolve("name") {
+            Err(Error::ColumnAmbiguous { name, candidates }) => {
+                assert_eq!(name, "name");
+                assert_eq!(candidates, ["Name", "NAME"]);
+            }
+            other => panic!("expected `ColumnAmbiguous`, got {other:?}"),
+        }
+
+        // the same table joined twice
+        match resolve("accounts.id") {
+            Err(Error::ColumnAmbiguous { candidates, .. }) => {
+                assert_eq!(candidates, ["a.id", "b.id"]);
+            }
+            other => panic!("expected `ColumnAmbiguous`, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn it_reports_missing_names() {
+        assert!(matches!(resolve("c.id"), Err(Error::ColumnNotFound(_))));
+        assert!(matches!(resolve("missing"), Err(Error::ColumnNotFound(_))));
+    }
+}
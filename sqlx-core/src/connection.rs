@@ -1,11 +1,13 @@
 use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
+use crate::metrics::Metrics;
 
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Formatter};
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -20,6 +22,10 @@ pub trait Connection: Send {
     /// This notifies the database server that the connection is closing so that it can
     /// free up any server-side resources in use.
     ///
+    /// Any work still pending on the connection is finished first, such as the rollback of
+    /// a [`Transaction`] that was dropped, so the locks it held
+    /// are released by the time this returns.
+    ///
     /// While connections can simply be dropped to clean up local resources,
     /// the `Drop` handler itself cannot notify the server that the connection is being closed
     /// because that may require I/O to send a termination message. That can result in a delay
@@ -42,6 +48,22 @@ pub trait Connection: Send {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Reset the session state of this connection to what it was when it was first opened.
+    ///
+    /// This rolls back any open transaction and discards session-level state such as temporary
+    /// tables, user variables and settings changed with `SET`. What exactly is reset, and
+    /// whether cached prepared statements survive, depends on the database; see the
+    /// documentation of the driver's connection type.
+    ///
+    /// The pool calls this when a connection is released if
+    /// [`PoolOptions::reset_on_release()`][crate::pool::PoolOptions::reset_on_release]
+    /// is enabled.
+    ///
+    /// The default implementation does nothing, which is the case for SQLite.
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move { Ok(()) })
+    }
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
@@ -49,6 +71,40 @@ pub trait Connection: Send {
     where
         Self: Sized;
 
+    /// Begin a new transaction with the given options, or establish a savepoint within the
+    /// active transaction.
+    ///
+    /// The options are database-specific, e.g. [`PgTransactionOptions`] for Postgres,
+    /// and are ignored when establishing a savepoint.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::postgres::PgTransactionOptions;
+    /// use sqlx::{Connection, IsolationLevel};
+    ///
+    /// let mut tx = conn
+    ///     .begin_with(
+    ///         PgTransactionOptions::new()
+    ///             .isolation_level(IsolationLevel::Serializable)
+    ///             .read_only(true),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`PgTransactionOptions`]: https://docs.rs/sqlx/latest/sqlx/postgres/struct.PgTransactionOptions.html
+    fn begin_with<O>(
+        &mut self,
+        options: O,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+        O: TransactionOptions<Database = Self::Database>,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     /// Execute the function inside a transaction.
     ///
     /// If the function returns an error, the transaction will be rolled back. If it does not
@@ -132,6 +188,13 @@ pub trait Connection: Send {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Take the last connection-fatal error the connection saw, if any, so the pool can
+    /// start a new generation when the connection is released.
+    #[doc(hidden)]
+    fn take_fatal_error(&mut self) -> Option<Error> {
+        None
+    }
+
     /// Establish a new database connection.
     ///
     /// A value of [`Options`][Self::Options] is parsed from the provided connection string. This parsing
@@ -155,12 +218,24 @@ pub trait Connection: Send {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 #[non_exhaustive]
 pub struct LogSettings {
     pub statements_level: LevelFilter,
     pub slow_statements_level: LevelFilter,
     pub slow_statements_duration: Duration,
+    pub metrics: Option<Arc<dyn Metrics>>,
+}
+
+impl Debug for LogSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogSettings")
+            .field("statements_level", &self.statements_level)
+            .field("slow_statements_level", &self.slow_statements_level)
+            .field("slow_statements_duration", &self.slow_statements_duration)
+            .field("metrics", &self.metrics.is_some())
+            .finish()
+    }
 }
 
 impl Default for LogSettings {
@@ -169,6 +244,7 @@ impl Default for LogSettings {
             statements_level: LevelFilter::Debug,
             slow_statements_level: LevelFilter::Warn,
             slow_statements_duration: Duration::from_secs(1),
+            metrics: None,
         }
     }
 }
@@ -181,6 +257,9 @@ impl LogSettings {
         self.slow_statements_level = level;
         self.slow_statements_duration = duration;
     }
+    pub fn metrics(&mut self, metrics: Arc<dyn Metrics>) {
+        self.metrics = Some(metrics);
+    }
 }
 
 pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug + Clone {
@@ -210,6 +289,10 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     /// Additional settings are driver-specific. Refer to the source of a given implementation
     /// to see which options are preserved in the URL.
     ///
+    /// Parsing the URL with [`from_url()`][Self::from_url] gives back the same settings
+    /// for everything the URL preserves. Unlike the `Debug` output, the URL includes
+    /// the password, so take care where it is logged.
+    ///
     /// ### Panics
     /// This defaults to `unimplemented!()`.
     ///
@@ -230,6 +313,20 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     /// at the specified `level`.
     fn log_slow_statements(self, level: LevelFilter, duration: Duration) -> Self;
 
+    /// Report the queries executed on connections made with these options to `metrics`.
+    ///
+    /// A [`Pool`][crate::pool::Pool] with [`PoolOptions::metrics()`][crate::pool::PoolOptions::metrics]
+    /// sets this on its connect options.
+    ///
+    /// Queries the driver executes internally, such as type lookups and session setup,
+    /// are not reported.
+    ///
+    /// The default implementation ignores `metrics`, for drivers which don't report queries.
+    fn metrics(self, metrics: Arc<dyn Metrics>) -> Self {
+        let _ = metrics;
+        self
+    }
+
     /// Entirely disables statement logging (both slow and regular).
     fn disable_statement_logging(self) -> Self {
         self.log_statements(LevelFilter::Off)
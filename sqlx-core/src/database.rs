@@ -58,6 +58,8 @@ use std::fmt::Debug;
 use crate::arguments::Arguments;
 use crate::column::Column;
 use crate::connection::Connection;
+use crate::placeholders::PlaceholderSyntax;
+use crate::query_builder::UpsertSyntax;
 use crate::row::Row;
 
 use crate::statement::Statement;
@@ -108,6 +110,19 @@ pub trait Database: 'static + Sized + Send + Debug {
 
     /// The schemes for database URLs that should match this driver.
     const URL_SCHEMES: &'static [&'static str];
+
+    /// The syntax of bind parameter placeholders in SQL for this database.
+    ///
+    /// See [`translate_placeholders()`][crate::placeholders::translate_placeholders].
+    const PLACEHOLDER_SYNTAX: PlaceholderSyntax = PlaceholderSyntax::QuestionMark;
+
+    /// The character that quotes identifiers in SQL for this database.
+    const IDENTIFIER_QUOTE: char = '"';
+
+    /// The syntax of an `INSERT` that updates the row it conflicts with for this database.
+    ///
+    /// See [`Upsert`][crate::query_builder::Upsert].
+    const UPSERT_SYNTAX: UpsertSyntax = UpsertSyntax::OnConflict;
 }
 
 /// A [`Database`] that maintains a client-side cache of prepared statements.
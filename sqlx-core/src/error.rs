@@ -75,6 +75,15 @@ pub enum Error {
     #[error("no column found for name: {0}")]
     ColumnNotFound(String),
 
+    /// More than one column matched the given name, and none of them was an exact match.
+    ///
+    /// Qualify the name with a table (`"table.column"`), where supported, or use a positional index.
+    #[error("column name {name:?} is ambiguous; candidates: {}", candidates.join(", "))]
+    ColumnAmbiguous {
+        name: String,
+        candidates: Vec<String>,
+    },
+
     /// Error occurred while decoding a value from a specific column.
     #[error("error occurred while decoding column {index}: {source}")]
     ColumnDecode {
@@ -88,6 +97,14 @@ pub enum Error {
     #[error("error occurred while encoding a value: {0}")]
     Encode(#[source] BoxDynError),
 
+    /// The number of arguments bound to a query does not match the number of
+    /// bind parameters in its SQL.
+    ///
+    /// This is checked before the query is executed, except for the statements of a SQLite
+    /// query before the last one; see [`query()`][crate::query::query].
+    #[error("query has {expected} bind parameters, but {got} arguments were bound")]
+    ParameterCountMismatch { expected: usize, got: usize },
+
     /// Error occurred while decoding a value.
     #[error("error occurred while decoding: {0}")]
     Decode(#[source] BoxDynError),
@@ -122,6 +139,7 @@ pub enum Error {
 impl StdError for Box<dyn DatabaseError> {}
 
 impl Error {
+    /// Returns the error returned from the database, if this is [`Error::Database`].
     pub fn into_database_error(self) -> Option<Box<dyn DatabaseError + 'static>> {
         match self {
             Error::Database(err) => Some(err),
@@ -129,6 +147,8 @@ impl Error {
         }
     }
 
+    /// Returns a reference to the error returned from the database,
+    /// if this is [`Error::Database`].
     pub fn as_database_error(&self) -> Option<&(dyn DatabaseError + 'static)> {
         match self {
             Error::Database(err) => Some(&**err),
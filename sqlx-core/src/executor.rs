@@ -34,6 +34,9 @@ pub trait Executor<'c>: Send + Debug + Sized {
     type Database: Database;
 
     /// Execute the query and return the total number of rows affected.
+    ///
+    /// If the query contains multiple statements (see [`raw_sql()`][crate::raw_sql::raw_sql]),
+    /// the rows affected by each statement are summed.
     fn execute<'e, 'q: 'e, E>(
         self,
         query: E,
@@ -100,6 +103,9 @@ pub trait Executor<'c>: Send + Debug + Sized {
         E: 'q + Execute<'q, Self::Database>;
 
     /// Execute the query and return all the generated results, collected into a [`Vec`].
+    ///
+    /// If the query contains multiple statements, the rows of every result set are returned
+    /// in order.
     fn fetch_all<'e, 'q: 'e, E>(
         self,
         query: E,
@@ -112,6 +118,10 @@ pub trait Executor<'c>: Send + Debug + Sized {
     }
 
     /// Execute the query and returns exactly one row.
+    ///
+    /// This is the first row of the first result set that has one, as with
+    /// [`fetch_optional()`][Self::fetch_optional], which drivers may override to ask the
+    /// database for only one row.
     fn fetch_one<'e, 'q: 'e, E>(
         self,
         query: E,
@@ -129,6 +139,10 @@ pub trait Executor<'c>: Send + Debug + Sized {
     }
 
     /// Execute the query and returns at most one row.
+    ///
+    /// If the query contains multiple statements, this is the first row of the first result
+    /// set that has one; statements that return no rows are skipped over. Every statement is
+    /// still executed, and an error from any of them is returned instead of the row.
     fn fetch_optional<'e, 'q: 'e, E>(
         self,
         query: E,
@@ -192,7 +206,11 @@ pub trait Executor<'c>: Send + Debug + Sized {
 ///
 pub trait Execute<'q, DB: Database>: Send + Sized {
     /// Gets the SQL that will be executed.
-    fn sql(&self) -> &'q str;
+    ///
+    /// This borrows from `self` rather than `'q`, so that the SQL may be owned by the query,
+    /// as with [`query_owned()`][crate::query::query_owned]. An executor that returns a stream
+    /// or future must then move the query into it to read the SQL.
+    fn sql(&self) -> &str;
 
     /// Gets the previously cached statement, if available.
     fn statement(&self) -> Option<&DB::Statement<'q>>;
@@ -214,7 +232,7 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 //       involved to write `conn.execute(format!("SELECT {val}"))`
 impl<'q, DB: Database> Execute<'q, DB> for &'q str {
     #[inline]
-    fn sql(&self) -> &'q str {
+    fn sql(&self) -> &str {
         self
     }
 
@@ -236,7 +254,7 @@ impl<'q, DB: Database> Execute<'q, DB> for &'q str {
 
 impl<'q, DB: Database> Execute<'q, DB> for (&'q str, Option<<DB as Database>::Arguments<'q>>) {
     #[inline]
-    fn sql(&self) -> &'q str {
+    fn sql(&self) -> &str {
         self.0
     }
 
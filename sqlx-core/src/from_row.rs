@@ -1,4 +1,16 @@
-use crate::{error::Error, row::Row};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    check::ExpectedColumn,
+    column::{Column, ColumnIndex},
+    database::Database,
+    decode::Decode,
+    error::Error,
+    row::Row,
+    types::Type,
+    value::{DynamicValue, ToDynamicValue},
+};
 
 /// A record that can be built from a row returned by the database.
 ///
@@ -65,6 +77,9 @@ use crate::{error::Error, row::Row};
 /// reason), `lowercase`, `UPPERCASE`, `camelCase`, `PascalCase`, `SCREAMING_SNAKE_CASE` and `kebab-case`.
 /// The styling of each option is intended to be an example of its behavior.
 ///
+/// A `rename` on a field takes precedence over `rename_all`. To check such a struct at compile
+/// time with `sqlx::query_as!()`, give it the same `rename_all`.
+///
 /// #### `default`
 ///
 /// When your struct contains a field that is not present in your query,
@@ -300,6 +315,15 @@ use crate::{error::Error, row::Row};
 /// ```
 pub trait FromRow<'r, R: Row>: Sized {
     fn from_row(row: &'r R) -> Result<Self, Error>;
+
+    /// The columns that [`from_row()`][Self::from_row] reads, if known.
+    ///
+    /// This is used by [`check::verify()`][crate::check::verify] to check a query against
+    /// this type without executing it. It is implemented by `#[derive(FromRow)]`;
+    /// the default implementation returns `None`.
+    fn columns() -> Option<Vec<ExpectedColumn<R::Database>>> {
+        None
+    }
 }
 
 impl<'r, R> FromRow<'r, R> for ()
@@ -312,6 +336,110 @@ where
     }
 }
 
+/// Reads every column of the row, in order, into its name and a [`DynamicValue`].
+///
+/// Columns with the same name are all kept.
+impl<'r, R> FromRow<'r, R> for Vec<(String, DynamicValue)>
+where
+    R: Row,
+    usize: ColumnIndex<R>,
+    <R::Database as Database>::ValueRef<'r>: ToDynamicValue,
+{
+    fn from_row(row: &'r R) -> Result<Self, Error> {
+        row.columns()
+            .iter()
+            .map(|column| {
+                let value = row
+                    .try_get_raw(column.ordinal())?
+                    .to_dynamic()
+                    .map_err(|source| Error::ColumnDecode {
+                        index: format!("{:?}", column.name()),
+                        source,
+                    })?;
+
+                Ok((column.name().to_owned(), value))
+            })
+            .collect()
+    }
+}
+
+/// Reads every column of the row into a map from its name to a [`DynamicValue`].
+///
+/// If several columns have the same name, the value of the last one is kept.
+impl<'r, R> FromRow<'r, R> for HashMap<String, DynamicValue>
+where
+    R: Row,
+    usize: ColumnIndex<R>,
+    <R::Database as Database>::ValueRef<'r>: ToDynamicValue,
+{
+    fn from_row(row: &'r R) -> Result<Self, Error> {
+        Ok(Vec::<(String, DynamicValue)>::from_row(row)?
+            .into_iter()
+            .collect())
+    }
+}
+
+/// Remembers the position of a column that is read by name, so it's only looked up once
+/// for all the rows of a query instead of once per row.
+///
+/// Used by `#[derive(FromRow)]`, which keeps one in a `static` for every field.
+/// Rows of the same result set share their column list, so the position is reused for as long
+/// as the rows passed in come from the same result set; otherwise it's looked up again.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct ColumnOrdinal(
+    // the low 32 bits of the address of the row's column list, followed by the position
+    AtomicU64,
+);
+
+impl ColumnOrdinal {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        ColumnOrdinal(AtomicU64::new(u64::MAX))
+    }
+
+    /// Decode the column `name` of `row`, like [`Row::try_get()`], returning the same errors.
+    pub fn try_get<'r, R, T>(&self, row: &'r R, name: &'r str) -> Result<T, Error>
+    where
+        R: Row,
+        &'r str: ColumnIndex<R>,
+        usize: ColumnIndex<R>,
+        T: Decode<'r, R::Database> + Type<R::Database>,
+    {
+        let columns = row.columns();
+        let key = (columns.as_ptr() as usize as u64) << 32;
+
+        let cached = self.0.load(Ordering::Relaxed);
+
+        let index = match usize::try_from(cached & u64::from(u32::MAX)) {
+            Ok(index)
+                if cached & !u64::from(u32::MAX) == key
+                    && columns.get(index).is_some_and(|c| c.name() == name) =>
+            {
+                index
+            }
+            _ => {
+                let index = name.index(row)?;
+
+                if let Ok(ordinal) = u32::try_from(index) {
+                    self.0.store(key | u64::from(ordinal), Ordering::Relaxed);
+                }
+
+                index
+            }
+        };
+
+        row.try_get(index).map_err(|e| match e {
+            // report the column by name, as `row.try_get(name)` would
+            Error::ColumnDecode { source, .. } => Error::ColumnDecode {
+                index: format!("{name:?}"),
+                source,
+            },
+            e => e,
+        })
+    }
+}
+
 // implement FromRow for tuples of types that implement Decode
 // up to tuples of 16 values
 
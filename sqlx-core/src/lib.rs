@@ -60,6 +60,9 @@ pub mod column;
 #[macro_use]
 pub mod statement;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod check;
 pub mod common;
 pub mod database;
 pub mod describe;
@@ -68,7 +71,9 @@ pub mod from_row;
 pub mod fs;
 pub mod io;
 pub mod logger;
+pub mod metrics;
 pub mod net;
+pub mod placeholders;
 pub mod query_as;
 pub mod query_builder;
 pub mod query_scalar;
@@ -109,8 +114,8 @@ pub use bytes;
 /// would want to implement itself.
 pub mod driver_prelude {
     pub use crate::{
-        acquire, common, decode, describe, encode, executor, ext, from_row, fs, io, logger, net,
-        pool, query, query_as, query_builder, query_scalar, rt, sync,
+        acquire, common, decode, describe, encode, executor, ext, from_row, fs, io, logger,
+        metrics, net, pool, query, query_as, query_builder, query_scalar, rt, sync,
     };
 
     pub use crate::error::{Error, Result};
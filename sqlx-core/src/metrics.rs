@@ -0,0 +1,229 @@
+//! Hooks for recording pool and query metrics.
+//!
+//! Implement [`Metrics`] and set it with [`PoolOptions::metrics()`][crate::pool::PoolOptions::metrics]
+//! or, for connections outside of a pool, [`ConnectOptions::metrics()`].
+//!
+//! With the `metrics` feature enabled, [`MetricsFacade`] records them with the
+//! [`metrics`](https://docs.rs/metrics) crate.
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_core::Stream;
+use futures_util::StreamExt;
+
+use crate::connection::LogSettings;
+use crate::error::Error;
+use crate::Either;
+
+#[cfg(doc)]
+use crate::connection::ConnectOptions;
+
+/// Callbacks for pool and query events.
+///
+/// These are called inline, so they should be cheap: update a counter or histogram,
+/// don't perform I/O. Every method does nothing by default.
+pub trait Metrics: Send + Sync + 'static {
+    /// A pool's `acquire()` returned after waiting for `wait`.
+    ///
+    /// This includes the time spent opening a new connection, if one was needed.
+    fn on_acquire(&self, wait: Duration, result: Result<(), &Error>) {
+        let _ = (wait, result);
+    }
+
+    /// A pool opened a new connection.
+    fn on_connect(&self) {}
+
+    /// A pool closed one of its connections.
+    fn on_disconnect(&self) {}
+
+    /// A query finished after `elapsed`, returning `rows_returned` rows.
+    ///
+    /// A query whose results were dropped before they were all read is reported as successful.
+    fn on_query(&self, elapsed: Duration, rows_returned: u64, result: Result<(), &Error>) {
+        let _ = (elapsed, rows_returned, result);
+    }
+}
+
+impl<M: Metrics + ?Sized> Metrics for Arc<M> {
+    fn on_acquire(&self, wait: Duration, result: Result<(), &Error>) {
+        (**self).on_acquire(wait, result)
+    }
+
+    fn on_connect(&self) {
+        (**self).on_connect()
+    }
+
+    fn on_disconnect(&self) {
+        (**self).on_disconnect()
+    }
+
+    fn on_query(&self, elapsed: Duration, rows_returned: u64, result: Result<(), &Error>) {
+        (**self).on_query(elapsed, rows_returned, result)
+    }
+}
+
+/// Measures a single query for [`Metrics::on_query()`].
+///
+/// If dropped before [`finish()`][Self::finish], the query is reported as successful.
+#[doc(hidden)]
+pub struct QueryMetrics {
+    metrics: Arc<dyn Metrics>,
+    start: Instant,
+    rows_returned: u64,
+    finished: bool,
+}
+
+impl QueryMetrics {
+    /// Start measuring a query, if `settings` has a [`Metrics`] set.
+    pub fn start(settings: &LogSettings) -> Option<Self> {
+        settings.metrics.clone().map(|metrics| QueryMetrics {
+            metrics,
+            start: Instant::now(),
+            rows_returned: 0,
+            finished: false,
+        })
+    }
+
+    pub fn increment_rows_returned(&mut self) {
+        self.rows_returned += 1;
+    }
+
+    pub fn finish(mut self, result: Result<(), &Error>) {
+        self.report(result);
+    }
+
+    fn report(&mut self, result: Result<(), &Error>) {
+        if !self.finished {
+            self.finished = true;
+            self.metrics
+                .on_query(self.start.elapsed(), self.rows_returned, result);
+        }
+    }
+
+    /// Measure the stream of results of a query, as returned by a driver.
+    ///
+    /// The stream is returned as-is if `metrics` is `None`.
+    pub fn instrument<'e, S, T, R>(
+        metrics: Option<Self>,
+        result: Result<S, Error>,
+    ) -> Result<impl Stream<Item = Result<Either<T, R>, Error>> + Send + 'e, Error>
+    where
+        S: Stream<Item = Result<Either<T, R>, Error>> + Send + 'e,
+        T: Send + 'e,
+        R: Send + 'e,
+    {
+        match (metrics, result) {
+            (None, result) => result.map(StreamExt::left_stream),
+
+            (Some(metrics), Err(e)) => {
+                metrics.finish(Err(&e));
+                Err(e)
+            }
+
+            (Some(metrics), Ok(stream)) => Ok(Instrumented {
+                stream: Box::pin(stream),
+                metrics: Some(metrics),
+            }
+            .right_stream()),
+        }
+    }
+}
+
+impl Drop for QueryMetrics {
+    fn drop(&mut self) {
+        self.report(Ok(()));
+    }
+}
+
+struct Instrumented<'e, T, R> {
+    stream: Pin<Box<dyn Stream<Item = Result<Either<T, R>, Error>> + Send + 'e>>,
+    metrics: Option<QueryMetrics>,
+}
+
+impl<T, R> Stream for Instrumented<'_, T, R> {
+    type Item = Result<Either<T, R>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = std::task::ready!(self.stream.as_mut().poll_next(cx));
+
+        match &item {
+            Some(Ok(Either::Right(_))) => {
+                if let Some(metrics) = &mut self.metrics {
+                    metrics.increment_rows_returned();
+                }
+            }
+
+            Some(Ok(Either::Left(_))) => {}
+
+            Some(Err(e)) => {
+                if let Some(metrics) = self.metrics.take() {
+                    metrics.finish(Err(e));
+                }
+            }
+
+            None => {
+                if let Some(metrics) = self.metrics.take() {
+                    metrics.finish(Ok(()));
+                }
+            }
+        }
+
+        Poll::Ready(item)
+    }
+}
+
+/// Records [`Metrics`] with the [`metrics`](https://docs.rs/metrics) crate.
+///
+/// | Metric | Type | Labels |
+/// |---|---|---|
+/// | `sqlx_pool_acquire_seconds` | histogram | |
+/// | `sqlx_pool_acquire_errors_total` | counter | |
+/// | `sqlx_pool_connections` | gauge | |
+/// | `sqlx_query_seconds` | histogram | |
+/// | `sqlx_query_rows_returned` | histogram | |
+/// | `sqlx_query_errors_total` | counter | `sqlstate_class` |
+///
+/// `sqlstate_class` is the first two characters of the SQLSTATE of a database error,
+/// or `none` for other errors.
+///
+/// The number of idle connections is not tracked; use `Pool::num_idle()` for that.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MetricsFacade;
+
+#[cfg(feature = "metrics")]
+impl Metrics for MetricsFacade {
+    fn on_acquire(&self, wait: Duration, result: Result<(), &Error>) {
+        metrics::histogram!("sqlx_pool_acquire_seconds").record(wait.as_secs_f64());
+
+        if result.is_err() {
+            metrics::counter!("sqlx_pool_acquire_errors_total").increment(1);
+        }
+    }
+
+    fn on_connect(&self) {
+        metrics::gauge!("sqlx_pool_connections").increment(1.0);
+    }
+
+    fn on_disconnect(&self) {
+        metrics::gauge!("sqlx_pool_connections").decrement(1.0);
+    }
+
+    fn on_query(&self, elapsed: Duration, rows_returned: u64, result: Result<(), &Error>) {
+        metrics::histogram!("sqlx_query_seconds").record(elapsed.as_secs_f64());
+        #[allow(clippy::cast_precision_loss)]
+        metrics::histogram!("sqlx_query_rows_returned").record(rows_returned as f64);
+
+        if let Err(e) = result {
+            let class = e
+                .as_database_error()
+                .and_then(|e| e.code())
+                .and_then(|code| code.get(..2).map(str::to_owned))
+                .unwrap_or_else(|| "none".to_owned());
+
+            metrics::counter!("sqlx_query_errors_total", "sqlstate_class" => class).increment(1);
+        }
+    }
+}
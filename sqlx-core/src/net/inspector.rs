@@ -0,0 +1,71 @@
+use std::fmt::{self, Debug, Display, Formatter, Write};
+use std::sync::Arc;
+
+/// How many bytes of a frame [`format_frame()`] prints.
+const MAX_DUMP_LEN: usize = 64;
+
+/// Whether a frame passed to a [`PacketInspector`] was sent to or received from the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The frame was written to the server.
+    Sent,
+    /// The frame was read from the server.
+    Received,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Sent => "->",
+            Direction::Received => "<-",
+        })
+    }
+}
+
+/// A callback that is invoked with every raw protocol frame that a connection
+/// reads or writes, for debugging the wire protocol.
+///
+/// Frames are passed as raw bytes, including their headers, after TLS decryption
+/// (and, for MySQL, decompression) and before they are parsed. Frames that are written are
+/// passed before they are sent. The callback can't modify the frames.
+///
+/// Set with `packet_inspector()` on the connect options of Postgres or MySQL.
+/// Requires the `debug-protocol` feature.
+#[derive(Clone)]
+pub struct PacketInspector(Arc<dyn Fn(Direction, &[u8]) + Send + Sync + 'static>);
+
+impl PacketInspector {
+    pub fn new(f: impl Fn(Direction, &[u8]) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    #[inline]
+    pub fn call(&self, direction: Direction, frame: &[u8]) {
+        (self.0)(direction, frame)
+    }
+}
+
+impl Debug for PacketInspector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PacketInspector").finish_non_exhaustive()
+    }
+}
+
+/// Format a frame as its direction, its name, its length, and a hex dump of its
+/// first 64 bytes, e.g. `-> Sync (5 bytes): 53 00 00 00 04`.
+///
+/// Used by the database drivers, which know the name of the frame.
+#[doc(hidden)]
+pub fn format_frame(direction: Direction, name: &str, frame: &[u8]) -> String {
+    let mut s = format!("{direction} {name} ({} bytes):", frame.len());
+
+    for byte in frame.iter().take(MAX_DUMP_LEN) {
+        let _ = write!(s, " {byte:02x}");
+    }
+
+    if frame.len() > MAX_DUMP_LEN {
+        s.push_str(" ..");
+    }
+
+    s
+}
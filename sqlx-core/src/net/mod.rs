@@ -1,6 +1,12 @@
+#[cfg(feature = "debug-protocol")]
+mod inspector;
 mod socket;
 pub mod tls;
 
+#[cfg(feature = "debug-protocol")]
+pub use inspector::{format_frame, Direction, PacketInspector};
+
 pub use socket::{
-    connect_tcp, connect_uds, BufferedSocket, Socket, SocketIntoBox, WithSocket, WriteBuffer,
+    connect_tcp, connect_uds, connect_with, BufferedSocket, Socket, SocketConnector, SocketIntoBox,
+    StreamSocket, WithSocket, WriteBuffer,
 };
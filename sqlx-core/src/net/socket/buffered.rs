@@ -80,6 +80,19 @@ impl<S: Socket> BufferedSocket<S> {
         }
     }
 
+    /// Like [`Self::try_read()`], but only looks at data that was already read from the socket.
+    ///
+    /// Returns `None` instead of reading more if the callback returns [`ControlFlow::Continue`].
+    pub fn try_read_buffered<F, R>(&mut self, try_read: F) -> Result<Option<R>, Error>
+    where
+        F: FnOnce(&mut BytesMut) -> Result<ControlFlow<R, usize>, Error>,
+    {
+        Ok(match try_read(&mut self.read_buf.read)? {
+            ControlFlow::Continue(_) => None,
+            ControlFlow::Break(ret) => Some(ret),
+        })
+    }
+
     pub fn write_buffer(&self) -> &WriteBuffer {
         &self.write_buf
     }
@@ -0,0 +1,262 @@
+use std::cmp;
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::future::BoxFuture;
+
+use crate::io::ReadBuf;
+use crate::net::Socket;
+
+/// How many bytes [`StreamSocket`] reads or buffers for writing at once.
+const BUF_SIZE: usize = 8192;
+
+/// Opens the stream that a connection talks to the database over, instead of connecting
+/// to a host and port or a Unix domain socket.
+///
+/// This can be used to tunnel connections, e.g. through an SSH jump host or a VSOCK proxy.
+/// It's called for every new connection, including every connection opened by a pool.
+/// TLS and authentication are negotiated on the returned stream as usual.
+///
+/// This is implemented for closures returning a future that resolves to a [`Socket`],
+/// such as a [`StreamSocket`]:
+///
+/// ```rust,ignore
+/// use sqlx::net::StreamSocket;
+///
+/// let options = PgConnectOptions::new().socket_connector(|| async {
+///     let stream = open_tunnel().await?;
+///     Ok(StreamSocket::new(stream))
+/// });
+/// ```
+pub trait SocketConnector: Send + Sync + 'static {
+    /// Open a new stream to the database.
+    fn connect(&self) -> BoxFuture<'_, io::Result<Box<dyn Socket>>>;
+}
+
+impl Debug for dyn SocketConnector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketConnector").finish_non_exhaustive()
+    }
+}
+
+impl<F, Fut, S> SocketConnector for F
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = io::Result<S>> + Send + 'static,
+    S: Socket,
+{
+    fn connect(&self) -> BoxFuture<'_, io::Result<Box<dyn Socket>>> {
+        let fut = self();
+        Box::pin(async move { Ok(Box::new(fut.await?) as Box<dyn Socket>) })
+    }
+}
+
+/// Adapts a stream implementing `AsyncRead` and `AsyncWrite` into a [`Socket`],
+/// e.g. to return it from a [`SocketConnector`].
+///
+/// Streams implementing the [`futures-io`](https://docs.rs/futures-io) traits are wrapped
+/// with [`new()`][Self::new], and streams implementing the Tokio traits
+/// with [`from_tokio()`][Self::from_tokio].
+pub struct StreamSocket {
+    stream: Box<dyn AsyncStream>,
+    // bytes read ahead of `try_read()` while waiting for the stream to be readable
+    read_buf: Vec<u8>,
+    read_pos: usize,
+    eof: bool,
+    // bytes accepted by `try_write()` that have not been written to the stream yet
+    write_buf: Vec<u8>,
+}
+
+impl StreamSocket {
+    /// Wrap a stream implementing [`futures_io::AsyncRead`] and [`futures_io::AsyncWrite`].
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: futures_io::AsyncRead + futures_io::AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        Self::from_boxed(Box::new(FuturesIo(stream)))
+    }
+
+    /// Wrap a stream implementing [`tokio::io::AsyncRead`] and [`tokio::io::AsyncWrite`].
+    #[cfg(feature = "_rt-tokio")]
+    pub fn from_tokio<S>(stream: S) -> Self
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        Self::from_boxed(Box::new(TokioIo(stream)))
+    }
+
+    fn from_boxed(stream: Box<dyn AsyncStream>) -> Self {
+        StreamSocket {
+            stream,
+            read_buf: Vec::new(),
+            read_pos: 0,
+            eof: false,
+            write_buf: Vec::new(),
+        }
+    }
+
+    /// Write out everything in `write_buf`.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.write_buf.is_empty() {
+            let written = ready!(self.stream.poll_write(cx, &self.write_buf))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+
+            self.write_buf.drain(..written);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Socket for StreamSocket {
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        let available = &self.read_buf[self.read_pos..];
+
+        if available.is_empty() {
+            return if self.eof {
+                Ok(0)
+            } else {
+                Err(io::ErrorKind::WouldBlock.into())
+            };
+        }
+
+        let dst = buf.init_mut();
+        let read = cmp::min(dst.len(), available.len());
+
+        dst[..read].copy_from_slice(&available[..read]);
+        self.read_pos += read;
+
+        Ok(read)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let free = BUF_SIZE.saturating_sub(self.write_buf.len());
+
+        if free == 0 {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        let written = cmp::min(free, buf.len());
+        self.write_buf.extend_from_slice(&buf[..written]);
+
+        Ok(written)
+    }
+
+    fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.read_pos < self.read_buf.len() || self.eof {
+            return Poll::Ready(Ok(()));
+        }
+
+        let mut buf = mem::take(&mut self.read_buf);
+        buf.resize(BUF_SIZE, 0);
+        self.read_pos = 0;
+
+        let res = self.stream.poll_read(cx, &mut buf);
+
+        let read = match &res {
+            Poll::Ready(Ok(read)) => *read,
+            _ => 0,
+        };
+
+        buf.truncate(read);
+        self.read_buf = buf;
+
+        match res {
+            Poll::Ready(Ok(read)) => {
+                self.eof = read == 0;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_drain(cx)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_drain(cx))?;
+        self.stream.poll_flush(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_drain(cx))?;
+        self.stream.poll_shutdown(cx)
+    }
+}
+
+impl Debug for StreamSocket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamSocket").finish_non_exhaustive()
+    }
+}
+
+/// The `AsyncRead` and `AsyncWrite` methods used by [`StreamSocket`],
+/// so it can wrap streams implementing either the `futures-io` or Tokio traits.
+trait AsyncStream: Send + Sync + Unpin {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>>;
+
+    fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>>;
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+}
+
+struct FuturesIo<S>(S);
+
+impl<S> AsyncStream for FuturesIo<S>
+where
+    S: futures_io::AsyncRead + futures_io::AsyncWrite + Send + Sync + Unpin,
+{
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+
+    fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_close(cx)
+    }
+}
+
+#[cfg(feature = "_rt-tokio")]
+struct TokioIo<S>(S);
+
+#[cfg(feature = "_rt-tokio")]
+impl<S> AsyncStream for TokioIo<S>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Sync + Unpin,
+{
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let mut buf = tokio::io::ReadBuf::new(buf);
+        ready!(Pin::new(&mut self.0).poll_read(cx, &mut buf))?;
+        Poll::Ready(Ok(buf.filled().len()))
+    }
+
+    fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
@@ -7,10 +7,12 @@ use std::task::{ready, Context, Poll};
 use bytes::BufMut;
 
 pub use buffered::{BufferedSocket, WriteBuffer};
+pub use connector::{SocketConnector, StreamSocket};
 
 use crate::io::ReadBuf;
 
 mod buffered;
+mod connector;
 
 pub trait Socket: Send + Sync + Unpin + 'static {
     fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize>;
@@ -287,3 +289,44 @@ pub async fn connect_uds<P: AsRef<Path>, Ws: WithSocket>(
         .into())
     }
 }
+
+/// Open a stream with the given [`SocketConnector`].
+pub async fn connect_with<Ws: WithSocket>(
+    connector: &dyn SocketConnector,
+    with_socket: Ws,
+) -> crate::Result<Ws::Output> {
+    let socket = ConnectedSocket(connector.connect().await?);
+
+    Ok(with_socket.with_socket(socket).await)
+}
+
+// Passing `Box<dyn Socket>` to `WithSocket::with_socket()` directly makes the connection future
+// fail the `Send` check when a TLS backend is enabled, as the compiler can't prove it for every
+// lifetime through the blanket `Socket` impl for `Box<S>`. A concrete wrapper avoids that.
+struct ConnectedSocket(Box<dyn Socket>);
+
+impl Socket for ConnectedSocket {
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        self.0.try_read(buf)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.try_write(buf)
+    }
+
+    fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_read_ready(cx)
+    }
+
+    fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_write_ready(cx)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_flush(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_shutdown(cx)
+    }
+}
@@ -47,11 +47,12 @@ impl CertificateInput {
     }
 }
 
+// Formats the input the way it's parsed by `From<String>`, so it can be put in a URL.
 impl std::fmt::Display for CertificateInput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CertificateInput::Inline(v) => write!(f, "{}", String::from_utf8_lossy(v.as_slice())),
-            CertificateInput::File(path) => write!(f, "file: {}", path.display()),
+            CertificateInput::File(path) => write!(f, "{}", path.display()),
         }
     }
 }
@@ -65,6 +66,11 @@ pub struct TlsConfig<'a> {
     pub client_key_path: Option<&'a CertificateInput>,
 }
 
+/// Upgrade `socket` to TLS using the backend selected by Cargo features.
+///
+/// Because features are additive, both backends may be enabled at once; in that case
+/// `native-tls` takes precedence. If neither is enabled, callers are expected to have
+/// checked [`error_if_unavailable()`] first.
 pub async fn handshake<S, Ws>(
     socket: S,
     config: TlsConfig<'_>,
@@ -105,3 +111,31 @@ pub fn error_if_unavailable() -> crate::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CertificateInput;
+
+    #[test]
+    fn certificate_input_from_path() {
+        let input = CertificateInput::from("/etc/ssl/ca.pem".to_string());
+
+        assert!(matches!(input, CertificateInput::File(path) if path.ends_with("ca.pem")));
+    }
+
+    #[test]
+    fn certificate_input_from_inline_pem() {
+        let pem = "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n";
+        let input = CertificateInput::from(pem.to_string());
+
+        assert!(matches!(input, CertificateInput::Inline(data) if data == pem.as_bytes()));
+    }
+
+    #[test]
+    fn certificate_input_from_truncated_pem() {
+        // missing the end marker, so it's not recognized as inline PEM
+        let input = CertificateInput::from("-----BEGIN CERTIFICATE-----\nMIIB".to_string());
+
+        assert!(matches!(input, CertificateInput::File(_)));
+    }
+}
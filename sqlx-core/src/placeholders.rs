@@ -0,0 +1,434 @@
+//! Translation of bind parameter placeholders between SQL dialects.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::database::Database;
+use crate::error::Error;
+
+/// The syntax a database uses for bind parameter placeholders in SQL.
+///
+/// See [`Database::PLACEHOLDER_SYNTAX`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PlaceholderSyntax {
+    /// Numbered placeholders, `$1`, `$2`, etc., which may be used in any order and more than once.
+    ///
+    /// Used by Postgres and SQLite.
+    Dollar,
+
+    /// Anonymous placeholders, `?`, which are bound in the order they appear.
+    ///
+    /// Used by MySQL.
+    QuestionMark,
+
+    /// The syntax of the database that a connection is to, which is only known at run time.
+    ///
+    /// Used by `Any`; see `AnyConnection::placeholder_syntax()` for the syntax of a connection.
+    Backend,
+}
+
+/// Rewrite the `$N` placeholders in `sql` to the placeholder syntax of `DB`.
+///
+/// This lets the same SQL be shared between databases with different placeholder syntax.
+/// Bind arguments in the order of their `$N` placeholders, as you would for Postgres:
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::MySqlConnection) -> sqlx::Result<()> {
+/// let sql = sqlx::translate_placeholders::<sqlx::MySql>(
+///     "SELECT * FROM users WHERE id = $1 AND name <> '$2'",
+/// )?;
+///
+/// assert_eq!(sql, "SELECT * FROM users WHERE id = ? AND name <> '$2'");
+///
+/// let user = sqlx::query(&sql).bind(1_i64).fetch_optional(conn).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// If `DB` uses [`PlaceholderSyntax::Dollar`], `sql` is returned as-is.
+///
+/// Placeholders are not rewritten inside string literals (including `E'...'` strings),
+/// quoted identifiers (`"..."` and `` `...` ``), comments, or dollar-quoted strings (`$tag$...$tag$`).
+/// The SQL is scanned with the Postgres rules for these, so a single quote in a string
+/// must be escaped by doubling it (`''`), not with a backslash, unless it is an `E'...'` string.
+///
+/// ### Errors
+/// Anonymous `?` placeholders are bound in the order they appear, so translating to
+/// [`PlaceholderSyntax::QuestionMark`] returns [`Error::InvalidArgument`] if the `$N`
+/// placeholders are not `$1`, `$2`, ... in order, including if a placeholder is used twice.
+/// Bind the value again with a new placeholder instead.
+///
+/// An unterminated string, identifier, comment or dollar-quoted string is also an error.
+///
+/// ### `Any`
+/// The placeholder syntax of `Any` depends on the database it is connected to, so
+/// `translate_placeholders::<Any>()` always returns [`Error::InvalidArgument`].
+/// Translate with the syntax of the connection instead:
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::AnyConnection) -> sqlx::Result<()> {
+/// let sql = conn
+///     .placeholder_syntax()
+///     .translate("SELECT * FROM users WHERE id = $1")?;
+///
+/// let user = sqlx::query(&sql).bind(1_i64).fetch_optional(conn).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn translate_placeholders<DB: Database>(sql: &str) -> Result<Cow<'_, str>, Error> {
+    DB::PLACEHOLDER_SYNTAX.translate(sql)
+}
+
+impl PlaceholderSyntax {
+    /// Rewrite the `$N` placeholders in `sql` to this syntax.
+    ///
+    /// See [`translate_placeholders()`] for details.
+    pub fn translate(self, sql: &str) -> Result<Cow<'_, str>, Error> {
+        match self {
+            PlaceholderSyntax::Dollar => Ok(Cow::Borrowed(sql)),
+            PlaceholderSyntax::QuestionMark => to_question_marks(sql),
+            PlaceholderSyntax::Backend => Err(Error::InvalidArgument(
+                "the placeholder syntax depends on the database that is connected to; \
+                 translate with the syntax of the connection instead"
+                    .into(),
+            )),
+        }
+    }
+}
+
+fn to_question_marks(sql: &str) -> Result<Cow<'_, str>, Error> {
+    let mut translated = String::new();
+    // the end of the part of `sql` that was already copied to `translated`
+    let mut copied = 0;
+    let mut next_placeholder = 1;
+
+    for_each_placeholder(sql, |n, range| {
+        let placeholder = &sql[range.clone()];
+
+        if n != next_placeholder {
+            return Err(Error::InvalidArgument(if n != 0 && n < next_placeholder {
+                format!(
+                    "placeholder `{placeholder}` is used more than once, but `?` placeholders \
+                     can only be bound once each; bind the value again with a new placeholder"
+                )
+            } else {
+                format!(
+                    "expected placeholder `${next_placeholder}`, found `{placeholder}`; \
+                     `?` placeholders are bound in the order they appear, \
+                     so `$N` placeholders must appear in order"
+                )
+            }));
+        }
+
+        next_placeholder += 1;
+
+        translated.push_str(&sql[copied..range.start]);
+        translated.push('?');
+        copied = range.end;
+
+        Ok(())
+    })?;
+
+    if copied == 0 {
+        return Ok(Cow::Borrowed(sql));
+    }
+
+    translated.push_str(&sql[copied..]);
+
+    Ok(Cow::Owned(translated))
+}
+
+/// Call `f` with the number and the range in `sql` of each `$N` placeholder, in order.
+fn for_each_placeholder(
+    sql: &str,
+    mut f: impl FnMut(usize, Range<usize>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let bytes = sql.as_bytes();
+
+    let mut i = 0;
+
+    while i < bytes.len() {
+        i = match bytes[i] {
+            b'\'' => {
+                // `E'...'` strings allow backslash escapes
+                let backslash_escapes = i > 0
+                    && bytes[i - 1].eq_ignore_ascii_case(&b'e')
+                    && !(i > 1 && is_identifier_byte(bytes[i - 2]));
+
+                skip_quoted(bytes, i, b'\'', backslash_escapes)
+                    .ok_or_else(|| unterminated("string literal", i))?
+            }
+
+            quote @ (b'"' | b'`') => skip_quoted(bytes, i, quote, false)
+                .ok_or_else(|| unterminated("quoted identifier", i))?,
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => bytes[i..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |end| i + end + 1),
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                skip_block_comment(bytes, i).ok_or_else(|| unterminated("block comment", i))?
+            }
+
+            // `$` may also be part of an identifier, e.g. `foo$1`
+            b'$' if i > 0 && is_identifier_byte(bytes[i - 1]) => i + 1,
+
+            b'$' if bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => {
+                let end = bytes[i + 1..]
+                    .iter()
+                    .position(|b| !b.is_ascii_digit())
+                    .map_or(bytes.len(), |len| i + 1 + len);
+
+                let placeholder = &sql[i..end];
+
+                let n: usize = placeholder[1..].parse().map_err(|_| {
+                    Error::InvalidArgument(format!("invalid placeholder `{placeholder}`"))
+                })?;
+
+                f(n, i..end)?;
+
+                end
+            }
+
+            b'$' => match dollar_quote_tag(bytes, i) {
+                Some(tag) => {
+                    let body = i + tag.len();
+
+                    sql[body..]
+                        .find(tag)
+                        .map(|len| body + len + tag.len())
+                        .ok_or_else(|| unterminated("dollar-quoted string", i))?
+                }
+
+                None => i + 1,
+            },
+
+            _ => i + 1,
+        };
+    }
+
+    Ok(())
+}
+
+// Postgres allows any non-ASCII character in identifiers.
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || !b.is_ascii()
+}
+
+/// Return the index after the closing `quote`, where a doubled `quote` is an escaped `quote`.
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8, backslash_escapes: bool) -> Option<usize> {
+    let mut i = start + 1;
+
+    loop {
+        match *bytes.get(i)? {
+            b'\\' if backslash_escapes => i += 2,
+            b if b == quote && bytes.get(i + 1) == Some(&quote) => i += 2,
+            b if b == quote => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+}
+
+/// Return the index after the end of the block comment, which may be nested as in Postgres.
+fn skip_block_comment(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = start;
+
+    loop {
+        match (*bytes.get(i)?, bytes.get(i + 1)) {
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                i += 2;
+            }
+
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                i += 2;
+
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+
+            _ => i += 1,
+        }
+    }
+}
+
+/// If a dollar-quoted string starts at `start`, return its tag, e.g. `$$` or `$body$`.
+fn dollar_quote_tag(bytes: &[u8], start: usize) -> Option<&str> {
+    let len = bytes[start + 1..]
+        .iter()
+        .position(|&b| !is_identifier_byte(b) || b == b'$')?;
+
+    let end = start + 1 + len;
+
+    if bytes.get(end) != Some(&b'$') || bytes.get(start + 1).is_some_and(u8::is_ascii_digit) {
+        return None;
+    }
+
+    std::str::from_utf8(&bytes[start..=end]).ok()
+}
+
+fn unterminated(what: &str, offset: usize) -> Error {
+    Error::InvalidArgument(format!("unterminated {what} starting at byte {offset}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(sql: &str) -> String {
+        PlaceholderSyntax::QuestionMark
+            .translate(sql)
+            .unwrap_or_else(|e| panic!("failed to translate {sql:?}: {e}"))
+            .into_owned()
+    }
+
+    fn translate_err(sql: &str) -> String {
+        match PlaceholderSyntax::QuestionMark.translate(sql) {
+            Err(Error::InvalidArgument(message)) => message,
+            other => panic!("expected an error translating {sql:?}, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn it_translates_placeholders() {
+        assert_eq!(
+            translate("SELECT * FROM users WHERE id = $1 AND name = $2"),
+            "SELECT * FROM users WHERE id = ? AND name = ?"
+        );
+        assert_eq!(
+            translate("SELECT $1::int, $2::text[]"),
+            "SELECT ?::int, ?::text[]"
+        );
+        assert_eq!(translate("SELECT ($1)+$2"), "SELECT (?)+?");
+        assert_eq!(translate("VALUES($1,$2,$3)"), "VALUES(?,?,?)");
+
+        let sql = (1..=12).map(|n| format!("${n}")).collect::<Vec<_>>();
+        assert_eq!(translate(&sql.join(",")), ["?"; 12].join(","));
+    }
+
+    #[test]
+    fn it_borrows_sql_without_placeholders() {
+        let sql = "SELECT '$1', \"$2\" -- $3";
+
+        assert!(matches!(
+            PlaceholderSyntax::QuestionMark.translate(sql),
+            Ok(Cow::Borrowed(s)) if s == sql
+        ));
+        assert!(matches!(
+            PlaceholderSyntax::Dollar.translate("SELECT $1"),
+            Ok(Cow::Borrowed("SELECT $1"))
+        ));
+    }
+
+    #[test]
+    fn it_does_not_translate_to_the_backend_syntax() {
+        assert!(matches!(
+            PlaceholderSyntax::Backend.translate("SELECT $1"),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn it_skips_string_literals() {
+        assert_eq!(translate("SELECT '$1', $1"), "SELECT '$1', ?");
+        assert_eq!(translate("SELECT 'it''s $1', $1"), "SELECT 'it''s $1', ?");
+        assert_eq!(translate("SELECT '', $1, ''"), "SELECT '', ?, ''");
+        assert_eq!(translate(r"SELECT E'\' $1', $1"), r"SELECT E'\' $1', ?");
+        assert_eq!(translate(r"SELECT e'\\', $1"), r"SELECT e'\\', ?");
+        // not an `E'...'` string, so the backslash is not an escape
+        assert_eq!(translate(r"SELECT name'\', $1"), r"SELECT name'\', ?");
+    }
+
+    #[test]
+    fn it_skips_quoted_identifiers() {
+        assert_eq!(translate(r#"SELECT "$1", $1"#), r#"SELECT "$1", ?"#);
+        assert_eq!(translate(r#"SELECT "a""$1", $1"#), r#"SELECT "a""$1", ?"#);
+        assert_eq!(translate("SELECT `$1`, $1"), "SELECT `$1`, ?");
+    }
+
+    #[test]
+    fn it_skips_comments() {
+        assert_eq!(translate("SELECT $1 -- $2\n, $2"), "SELECT ? -- $2\n, ?");
+        assert_eq!(translate("SELECT $1 -- $2"), "SELECT ? -- $2");
+        assert_eq!(translate("SELECT /* $1 */ $1"), "SELECT /* $1 */ ?");
+        assert_eq!(
+            translate("SELECT /* /* $1 */ $1 */ $1"),
+            "SELECT /* /* $1 */ $1 */ ?"
+        );
+        // not a comment
+        assert_eq!(translate("SELECT $1-$2, $3/$4"), "SELECT ?-?, ?/?");
+    }
+
+    #[test]
+    fn it_skips_dollar_quoted_strings() {
+        assert_eq!(translate("SELECT $$ $1 $$, $1"), "SELECT $$ $1 $$, ?");
+        assert_eq!(
+            translate("SELECT $body$ $1 $$ $1 $body$, $1"),
+            "SELECT $body$ $1 $$ $1 $body$, ?"
+        );
+        assert_eq!(
+            translate("DO $do$ BEGIN PERFORM $1; END $do$"),
+            "DO $do$ BEGIN PERFORM $1; END $do$"
+        );
+    }
+
+    #[test]
+    fn it_skips_identifiers_containing_dollar_signs() {
+        assert_eq!(translate("SELECT foo$1, $1"), "SELECT foo$1, ?");
+    }
+
+    #[test]
+    fn it_handles_non_ascii() {
+        assert_eq!(translate("SELECT 'é$1', $1, 'ü'"), "SELECT 'é$1', ?, 'ü'");
+        assert_eq!(translate("SELECT é$1, $1"), "SELECT é$1, ?");
+    }
+
+    #[test]
+    fn it_rejects_reused_placeholders() {
+        let err = translate_err("SELECT $1, $1");
+        assert!(err.contains("`$1` is used more than once"), "{err}");
+
+        let err = translate_err("SELECT $1, $2 WHERE $1");
+        assert!(err.contains("`$1` is used more than once"), "{err}");
+    }
+
+    #[test]
+    fn it_rejects_out_of_order_placeholders() {
+        let err = translate_err("SELECT $2, $1");
+        assert!(
+            err.contains("expected placeholder `$1`, found `$2`"),
+            "{err}"
+        );
+
+        let err = translate_err("SELECT $1, $3");
+        assert!(
+            err.contains("expected placeholder `$2`, found `$3`"),
+            "{err}"
+        );
+
+        let err = translate_err("SELECT $0");
+        assert!(err.contains("`$0`"), "{err}");
+    }
+
+    #[test]
+    fn it_rejects_unterminated_tokens() {
+        for sql in [
+            "SELECT '$1",
+            r"SELECT E'\'",
+            r#"SELECT "$1"#,
+            "SELECT `$1",
+            "SELECT /* $1",
+            "SELECT /* /* */ $1",
+            "SELECT $$ $1",
+            "SELECT $a$ $1 $b$",
+        ] {
+            let err = translate_err(sql);
+            assert!(err.starts_with("unterminated"), "{sql:?}: {err}");
+        }
+    }
+}
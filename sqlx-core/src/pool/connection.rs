@@ -9,7 +9,7 @@ use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
 
-use super::inner::{is_beyond_max_lifetime, DecrementSizeGuard, PoolInner};
+use super::inner::{is_beyond_max_lifetime, is_stale, DecrementSizeGuard, PoolInner};
 use crate::pool::options::PoolConnectionMetadata;
 use std::future::Future;
 
@@ -27,6 +27,8 @@ pub struct PoolConnection<DB: Database> {
 pub(super) struct Live<DB: Database> {
     pub(super) raw: DB::Connection,
     pub(super) created_at: Instant,
+    /// The pool's generation when the connection was opened.
+    pub(super) generation: u64,
 }
 
 pub(super) struct Idle<DB: Database> {
@@ -85,6 +87,7 @@ impl<DB: Database> PoolConnection<DB> {
     /// [`.close()`]: Connection::close
     pub async fn close(mut self) -> Result<(), Error> {
         let floating = self.take_live().float(self.pool.clone());
+        floating.report_disconnect();
         floating.inner.raw.close().await
     }
 
@@ -123,6 +126,11 @@ impl<DB: Database> PoolConnection<DB> {
         self.take_live().raw
     }
 
+    /// The pool's generation when the connection was opened.
+    pub(super) fn generation(&self) -> u64 {
+        self.live.as_ref().expect(EXPECT_MSG).generation
+    }
+
     fn take_live(&mut self) -> Live<DB> {
         self.live.take().expect(EXPECT_MSG)
     }
@@ -247,6 +255,7 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             inner: Live {
                 raw: conn,
                 created_at: Instant::now(),
+                generation: guard.pool.generation(),
             },
             guard,
         }
@@ -279,6 +288,16 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             return false;
         }
 
+        // The pool doesn't see errors returned to whoever had checked out the connection,
+        // so the connection keeps the last one that means the server has likely gone away.
+        if let Some(error) = self.raw.take_fatal_error() {
+            let pool = self.guard.pool.clone();
+            let generation = self.inner.generation;
+            self.close_hard().await;
+            pool.handle_error(&error, generation).await;
+            return false;
+        }
+
         // If the connection is beyond max lifetime, close the connection and
         // immediately create a new connection
         if is_beyond_max_lifetime(&self.inner, &self.guard.pool.options) {
@@ -286,6 +305,13 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             return false;
         }
 
+        // If a connection-fatal error was seen since the connection was opened,
+        // the server has likely gone away, so don't try to close nicely.
+        if is_stale(&self.inner, &self.guard.pool) {
+            self.close_hard().await;
+            return false;
+        }
+
         if let Some(test) = &self.guard.pool.options.after_release {
             let meta = self.metadata();
             match (test)(&mut self.inner.raw, meta).await {
@@ -296,9 +322,12 @@ impl<DB: Database> Floating<DB, Live<DB>> {
                 }
                 Err(error) => {
                     tracing::warn!(%error, "error from `after_release`");
+                    let pool = self.guard.pool.clone();
+                    let generation = self.inner.generation;
                     // Connection is broken, don't try to gracefully close as
                     // something weird might happen.
                     self.close_hard().await;
+                    pool.handle_error(&error, generation).await;
                     return false;
                 }
             }
@@ -311,14 +340,26 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         // returned to the pool; also of course, if it was dropped due to an error
         // this is simply a band-aid as SQLx-next connections should be able
         // to recover from cancellations
-        if let Err(error) = self.raw.ping().await {
+        //
+        // resetting the connection does all of that as well
+        let res = if self.guard.pool.options.reset_on_release {
+            self.raw.reset().await
+        } else {
+            self.raw.ping().await
+        };
+
+        if let Err(error) = res {
             tracing::warn!(
                 %error,
                 "error occurred while testing the connection on-release",
             );
 
+            let pool = self.guard.pool.clone();
+            let generation = self.inner.generation;
+
             // Connection is broken, don't try to gracefully close.
             self.close_hard().await;
+            pool.handle_error(&error, generation).await;
             false
         } else {
             // if the connection is still viable, release it to the pool
@@ -328,6 +369,8 @@ impl<DB: Database> Floating<DB, Live<DB>> {
     }
 
     pub async fn close(self) {
+        self.report_disconnect();
+
         // This isn't used anywhere that we care about the return value
         let _ = self.inner.raw.close().await;
 
@@ -335,10 +378,14 @@ impl<DB: Database> Floating<DB, Live<DB>> {
     }
 
     pub async fn close_hard(self) {
+        self.report_disconnect();
+
         let _ = self.inner.raw.close_hard().await;
     }
 
     pub fn detach(self) -> DB::Connection {
+        self.report_disconnect();
+
         self.inner.raw
     }
 
@@ -381,6 +428,8 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
     }
 
     pub async fn close(self) -> DecrementSizeGuard<DB> {
+        self.report_disconnect();
+
         if let Err(error) = self.inner.live.raw.close().await {
             tracing::debug!(%error, "error occurred while closing the pool connection");
         }
@@ -388,6 +437,8 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
     }
 
     pub async fn close_hard(self) -> DecrementSizeGuard<DB> {
+        self.report_disconnect();
+
         let _ = self.inner.live.raw.close_hard().await;
 
         self.guard
@@ -406,6 +457,14 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
     }
 }
 
+impl<DB: Database, C> Floating<DB, C> {
+    fn report_disconnect(&self) {
+        if let Some(metrics) = &self.guard.pool.options.metrics {
+            metrics.on_disconnect();
+        }
+    }
+}
+
 impl<DB: Database, C> Deref for Floating<DB, C> {
     type Target = C;
 
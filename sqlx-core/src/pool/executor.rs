@@ -26,10 +26,18 @@ where
 
         Box::pin(try_stream! {
             let mut conn = pool.acquire().await?;
+            let generation = conn.generation();
             let mut s = conn.fetch_many(query);
 
-            while let Some(v) = s.try_next().await? {
-                r#yield!(v);
+            loop {
+                match s.try_next().await {
+                    Ok(Some(v)) => r#yield!(v),
+                    Ok(None) => break,
+                    Err(e) => {
+                        pool.0.handle_error(&e, generation).await;
+                        return Err(e);
+                    }
+                }
             }
 
             Ok(())
@@ -45,7 +53,17 @@ where
     {
         let pool = self.clone();
 
-        Box::pin(async move { pool.acquire().await?.fetch_optional(query).await })
+        Box::pin(async move {
+            let mut conn = pool.acquire().await?;
+            let generation = conn.generation();
+            let res = conn.fetch_optional(query).await;
+
+            if let Err(e) = &res {
+                pool.0.handle_error(e, generation).await;
+            }
+
+            res
+        })
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -11,7 +11,7 @@ use crate::sync::{AsyncSemaphore, AsyncSemaphoreReleaser};
 use std::cmp;
 use std::future::Future;
 use std::pin::pin;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::task::Poll;
 
@@ -29,6 +29,9 @@ pub(crate) struct PoolInner<DB: Database> {
     pub(super) semaphore: AsyncSemaphore,
     pub(super) size: AtomicU32,
     pub(super) num_idle: AtomicUsize,
+    num_waiting: AtomicUsize,
+    acquire_wait_nanos: AtomicU64,
+    generation: AtomicU64,
     is_closed: AtomicBool,
     pub(super) on_closed: event_listener::Event,
     pub(super) options: PoolOptions<DB>,
@@ -52,12 +55,17 @@ impl<DB: Database> PoolInner<DB> {
             capacity
         };
 
+        let connect_options = options.apply_metrics(connect_options);
+
         let pool = Self {
             connect_options: RwLock::new(Arc::new(connect_options)),
             idle_conns: ArrayQueue::new(capacity),
             semaphore: AsyncSemaphore::new(options.fair, semaphore_capacity),
             size: AtomicU32::new(0),
             num_idle: AtomicUsize::new(0),
+            num_waiting: AtomicUsize::new(0),
+            acquire_wait_nanos: AtomicU64::new(0),
+            generation: AtomicU64::new(0),
             is_closed: AtomicBool::new(false),
             on_closed: event_listener::Event::new(),
             acquire_time_level: private_level_filter_to_trace_level(options.acquire_time_level),
@@ -85,10 +93,72 @@ impl<DB: Database> PoolInner<DB> {
         self.num_idle.load(Ordering::Acquire)
     }
 
+    pub(super) fn num_waiting(&self) -> usize {
+        self.num_waiting.load(Ordering::Acquire)
+    }
+
+    pub(super) fn acquire_wait_time(&self) -> Duration {
+        Duration::from_nanos(self.acquire_wait_nanos.load(Ordering::Acquire))
+    }
+
     pub(super) fn is_closed(&self) -> bool {
         self.is_closed.load(Ordering::Acquire)
     }
 
+    pub(super) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// If `error` means that the server has gone away, start a new generation of connections
+    /// and close any idle connections from before it.
+    ///
+    /// `generation` is the generation of the connection which failed with `error`.
+    ///
+    /// When the server restarts, every connection in the pool is dead, but we would otherwise
+    /// only find out one acquire at a time. Connections that are checked out are closed
+    /// when they're released instead.
+    pub(super) async fn handle_error(self: &Arc<Self>, error: &Error, generation: u64) {
+        if !is_connection_fatal(error) {
+            return;
+        }
+
+        // Only the first connection of a generation to fail starts a new one;
+        // the others failing as well doesn't mean the server went away again.
+        let generation = match self.generation.compare_exchange(
+            generation,
+            generation + 1,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => generation + 1,
+            Err(_) => return,
+        };
+
+        tracing::info!(
+            %error,
+            generation,
+            "connection-fatal error; closing idle connections from previous generations"
+        );
+
+        let mut closed = false;
+
+        for _ in 0..self.num_idle() {
+            if let Some(conn) = self.try_acquire() {
+                if is_stale(&conn, self) {
+                    // the server is likely gone, don't try to close nicely
+                    let _ = conn.close_hard().await;
+                    closed = true;
+                } else {
+                    self.release(conn.into_live());
+                }
+            }
+        }
+
+        if closed {
+            self.min_connections_maintenance(None).await;
+        }
+    }
+
     fn mark_closed(&self) {
         self.is_closed.store(true, Ordering::Release);
         self.on_closed.notify(usize::MAX);
@@ -176,13 +246,46 @@ impl<DB: Database> PoolInner<DB> {
 
     #[inline]
     pub(super) fn try_acquire(self: &Arc<Self>) -> Option<Floating<DB, Idle<DB>>> {
+        loop {
+            if self.is_closed() {
+                return None;
+            }
+
+            let permit = self.semaphore.try_acquire(1)?;
+
+            let conn = self.pop_idle(permit).ok()?;
+
+            // Like `check_idle_conn()`, but we can't wait for the connection to close here.
+            // Its permit is only released once it has, so the next one may not be available yet.
+            if is_stale(&conn, self) {
+                crate::rt::spawn(conn.close_hard());
+            } else if is_beyond_idle_timeout(&conn, &self.options)
+                || is_beyond_max_lifetime(&conn, &self.options)
+            {
+                crate::rt::spawn(conn.close());
+            } else {
+                return Some(conn);
+            }
+        }
+    }
+
+    /// Add an externally opened connection to the idle queue, if there is room for it.
+    pub(super) fn try_attach(self: &Arc<Self>, conn: DB::Connection) -> Result<(), DB::Connection> {
         if self.is_closed() {
-            return None;
+            return Err(conn);
         }
 
-        let permit = self.semaphore.try_acquire(1)?;
+        let Some(permit) = self.semaphore.try_acquire(1) else {
+            return Err(conn);
+        };
 
-        self.pop_idle(permit).ok()
+        match self.try_increment_size(permit) {
+            Ok(guard) => {
+                self.release(Floating::new_live(conn, guard));
+                Ok(())
+            }
+            Err(_) => Err(conn),
+        }
     }
 
     fn pop_idle<'a>(
@@ -240,15 +343,26 @@ impl<DB: Database> PoolInner<DB> {
     }
 
     pub(super) async fn acquire(self: &Arc<Self>) -> Result<Floating<DB, Live<DB>>, Error> {
+        self.acquire_with_timeout(self.options.acquire_timeout)
+            .await
+    }
+
+    pub(super) async fn acquire_with_timeout(
+        self: &Arc<Self>,
+        timeout: Duration,
+    ) -> Result<Floating<DB, Live<DB>>, Error> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
 
         let acquire_started_at = Instant::now();
-        let deadline = acquire_started_at + self.options.acquire_timeout;
+        let deadline = acquire_started_at + timeout;
+
+        // Also covers timeouts and cancellation (the future being dropped).
+        let waiting = WaitingGuard::new(self, acquire_started_at);
 
         let acquired = crate::rt::timeout(
-            self.options.acquire_timeout,
+            timeout,
             async {
                 loop {
                     // Handles the close-event internally
@@ -259,7 +373,7 @@ impl<DB: Database> PoolInner<DB> {
                     let guard = match self.pop_idle(permit) {
 
                         // Then, check that we can use it...
-                        Ok(conn) => match check_idle_conn(conn, &self.options).await {
+                        Ok(conn) => match check_idle_conn(conn, self).await {
 
                             // All good!
                             Ok(live) => return Ok(live),
@@ -290,10 +404,19 @@ impl<DB: Database> PoolInner<DB> {
             }
         )
             .await
-            .map_err(|_| Error::PoolTimedOut)??;
+            .map_err(|_| Error::PoolTimedOut)
+            .and_then(|res| res);
+
+        drop(waiting);
 
         let acquired_after = acquire_started_at.elapsed();
 
+        if let Some(metrics) = &self.options.metrics {
+            metrics.on_acquire(acquired_after, acquired.as_ref().map(drop));
+        }
+
+        let acquired = acquired?;
+
         let acquire_slow_level = self
             .acquire_slow_level
             .filter(|_| acquired_after > self.options.acquire_slow_threshold);
@@ -359,7 +482,13 @@ impl<DB: Database> PoolInner<DB> {
                     };
 
                     match res {
-                        Ok(()) => return Ok(Floating::new_live(raw, guard)),
+                        Ok(()) => {
+                            if let Some(metrics) = &self.options.metrics {
+                                metrics.on_connect();
+                            }
+
+                            return Ok(Floating::new_live(raw, guard));
+                        }
                         Err(error) => {
                             tracing::error!(%error, "error returned from after_connect");
                             // The connection is broken, don't try to close nicely.
@@ -455,6 +584,34 @@ pub(super) fn is_beyond_max_lifetime<DB: Database>(
         .map_or(false, |max| live.created_at.elapsed() > max)
 }
 
+/// Returns `true` if the connection was opened before the pool's current generation,
+/// i.e. before a connection-fatal error was seen.
+pub(super) fn is_stale<DB: Database>(live: &Live<DB>, pool: &PoolInner<DB>) -> bool {
+    live.generation < pool.generation()
+}
+
+/// Returns `true` if `error` means the connection, and likely every other connection
+/// to the same server, is unusable:
+///
+/// * SQLSTATE class `08` (connection exception),
+/// * `57P01` (`admin_shutdown`), `57P02` (`crash_shutdown`) or `57P03` (`cannot_connect_now`),
+/// * or an I/O error from the connection being closed by the other end.
+pub fn is_connection_fatal(error: &Error) -> bool {
+    match error {
+        Error::Database(e) => e.code().is_some_and(|code| {
+            code.starts_with("08") || matches!(&*code, "57P01" | "57P02" | "57P03")
+        }),
+        Error::Io(e) => matches!(
+            e.kind(),
+            std::io::ErrorKind::UnexpectedEof
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+        ),
+        _ => false,
+    }
+}
+
 /// Returns `true` if the connection has exceeded `options.idle_timeout` if set, `false` otherwise.
 fn is_beyond_idle_timeout<DB: Database>(idle: &Idle<DB>, options: &PoolOptions<DB>) -> bool {
     options
@@ -464,8 +621,20 @@ fn is_beyond_idle_timeout<DB: Database>(idle: &Idle<DB>, options: &PoolOptions<D
 
 async fn check_idle_conn<DB: Database>(
     mut conn: Floating<DB, Idle<DB>>,
-    options: &PoolOptions<DB>,
+    pool: &Arc<PoolInner<DB>>,
 ) -> Result<Floating<DB, Live<DB>>, DecrementSizeGuard<DB>> {
+    let options = &pool.options;
+
+    if is_stale(&conn, pool) {
+        // opened before a connection-fatal error; the server has likely gone away since
+        return Err(conn.close_hard().await);
+    }
+
+    // the maintenance task only checks these periodically, and isn't spawned without a runtime
+    if is_beyond_idle_timeout(&conn, options) || is_beyond_max_lifetime(&conn, options) {
+        return Err(conn.close().await);
+    }
+
     if options.test_before_acquire {
         // Check that the connection is still live
         if let Err(error) = conn.ping().await {
@@ -473,8 +642,11 @@ async fn check_idle_conn<DB: Database>(
             // either way we're fine to just discard the connection
             // the error itself here isn't necessarily unexpected so WARN is too strong
             tracing::info!(%error, "ping on idle connection returned error");
+            let generation = conn.generation;
             // connection is broken so don't try to close nicely
-            return Err(conn.close_hard().await);
+            let guard = conn.close_hard().await;
+            pool.handle_error(&error, generation).await;
+            return Err(guard);
         }
     }
 
@@ -545,6 +717,7 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
                         if let Some(conn) = pool.try_acquire() {
                             if is_beyond_idle_timeout(&conn, &pool.options)
                                 || is_beyond_max_lifetime(&conn, &pool.options)
+                                || is_stale(&conn, &pool)
                             {
                                 let _ = conn.close().await;
                                 pool.min_connections_maintenance(Some(next_run)).await;
@@ -617,3 +790,29 @@ impl<DB: Database> Drop for DecrementSizeGuard<DB> {
         }
     }
 }
+
+/// RAII guard counting a task as waiting in `PoolInner::acquire()`.
+///
+/// Records the time spent waiting on-drop, whether or not a connection was acquired.
+struct WaitingGuard<'a, DB: Database> {
+    pool: &'a PoolInner<DB>,
+    started_at: Instant,
+}
+
+impl<'a, DB: Database> WaitingGuard<'a, DB> {
+    fn new(pool: &'a PoolInner<DB>, started_at: Instant) -> Self {
+        pool.num_waiting.fetch_add(1, Ordering::AcqRel);
+        Self { pool, started_at }
+    }
+}
+
+impl<DB: Database> Drop for WaitingGuard<'_, DB> {
+    fn drop(&mut self) {
+        let waited = u64::try_from(self.started_at.elapsed().as_nanos()).unwrap_or(u64::MAX);
+
+        self.pool
+            .acquire_wait_nanos
+            .fetch_add(waited, Ordering::AcqRel);
+        self.pool.num_waiting.fetch_sub(1, Ordering::AcqRel);
+    }
+}
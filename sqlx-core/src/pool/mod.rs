@@ -68,9 +68,11 @@ use futures_util::FutureExt;
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 
 pub use self::connection::PoolConnection;
+#[doc(hidden)]
+pub use self::inner::is_connection_fatal;
 use self::inner::PoolInner;
 #[doc(hidden)]
 pub use self::maybe::MaybePoolConnection;
@@ -334,7 +336,9 @@ impl<DB: Database> Pool<DB> {
     ///
     /// The total time this method is allowed to execute is capped by
     /// [`PoolOptions::acquire_timeout`].
-    /// If that timeout elapses, this will return [`Error::PoolClosed`].
+    /// If that timeout elapses, this will return [`Error::PoolTimedOut`].
+    ///
+    /// To use a different timeout for a single call, see [`Pool::acquire_timeout()`].
     ///
     /// ### Note: Cancellation/Timeout May Drop Connections
     /// If `acquire` is cancelled or times out after it acquires a connection from the idle queue or
@@ -358,6 +362,29 @@ impl<DB: Database> Pool<DB> {
         async move { shared.acquire().await.map(|conn| conn.reattach()) }
     }
 
+    /// Retrieves a connection from the pool, waiting at most `timeout`.
+    ///
+    /// This is the same as [`Pool::acquire()`] but overrides [`PoolOptions::acquire_timeout`]
+    /// for this call only, e.g. to fail fast in a latency-sensitive request handler while
+    /// background jobs keep waiting for the configured timeout.
+    ///
+    /// If `timeout` elapses, this will return [`Error::PoolTimedOut`]. A task that times out
+    /// gives up its place in the queue of tasks waiting for a connection.
+    ///
+    /// The same note about cancellation applies as for [`Pool::acquire()`].
+    pub fn acquire_timeout(
+        &self,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        let shared = self.0.clone();
+        async move {
+            shared
+                .acquire_with_timeout(timeout)
+                .await
+                .map(|conn| conn.reattach())
+        }
+    }
+
     /// Attempts to retrieve a connection from the pool if there is one available.
     ///
     /// Returns `None` immediately if there are no idle connections available in the pool
@@ -366,12 +393,58 @@ impl<DB: Database> Pool<DB> {
         self.0.try_acquire().map(|conn| conn.into_live().reattach())
     }
 
+    /// Retrieves a connection from the pool and detaches it, taking ownership of it.
+    ///
+    /// This is a shortcut for `pool.acquire().await?.detach()`; see [`PoolConnection::detach()`].
+    /// The pool may open a new connection to replace it.
+    ///
+    /// Useful for dedicating a connection to a long-lived task, such as listening for
+    /// notifications, without holding one of the pool's slots indefinitely.
+    pub async fn acquire_detached(&self) -> Result<DB::Connection, Error> {
+        Ok(self.acquire().await?.detach())
+    }
+
+    /// Adds a connection opened outside of the pool, e.g. to warm up the pool in advance or to
+    /// return a connection taken with [`PoolConnection::detach()`].
+    ///
+    /// The connection is added to the idle queue and counts against [`PoolOptions::max_connections`].
+    /// It will be subject to the same idle timeout and lifetime checks as other connections,
+    /// with its lifetime starting when it is attached.
+    ///
+    /// Returns the connection back if the pool is closed, already at `max_connections`,
+    /// or there are tasks waiting for a connection which have yet to wake.
+    pub fn attach(&self, conn: DB::Connection) -> Result<(), DB::Connection> {
+        self.0.try_attach(conn)
+    }
+
     /// Retrieves a connection and immediately begins a new transaction.
+    ///
+    /// The transaction owns the connection, so it isn't borrowed from the pool and can be held
+    /// across `.await`s or stored in a struct. The connection is returned to the pool as soon as
+    /// the transaction is committed or rolled back. If the transaction is dropped instead,
+    /// the connection is returned with a rollback queued to run before it's used again.
     pub async fn begin(&self) -> Result<Transaction<'static, DB>, Error> {
         Transaction::begin(MaybePoolConnection::PoolConnection(self.acquire().await?)).await
     }
 
+    /// Retrieves a connection and immediately begins a new transaction with the given options.
+    ///
+    /// See [`Connection::begin_with()`][crate::connection::Connection::begin_with].
+    pub async fn begin_with<O>(&self, options: O) -> Result<Transaction<'static, DB>, Error>
+    where
+        O: TransactionOptions<Database = DB>,
+    {
+        Transaction::begin_with(
+            MaybePoolConnection::PoolConnection(self.acquire().await?),
+            options,
+        )
+        .await
+    }
+
     /// Attempts to retrieve a connection and immediately begins a new transaction if successful.
+    ///
+    /// Returns `Ok(None)` if no connection is idle, like [`Pool::try_acquire()`].
+    /// See [`Pool::begin()`] for details.
     pub async fn try_begin(&self) -> Result<Option<Transaction<'static, DB>>, Error> {
         match self.try_acquire() {
             Some(conn) => Transaction::begin(MaybePoolConnection::PoolConnection(conn))
@@ -506,6 +579,44 @@ impl<DB: Database> Pool<DB> {
         self.0.num_idle()
     }
 
+    /// Returns the number of tasks currently waiting in [`Pool::acquire()`] (or methods
+    /// that call it, like [`Pool::begin()`]).
+    ///
+    /// When the pool is fair (`fair(true)`, the default), waiting tasks are served in first-in,
+    /// first-out order: each connection returned to the pool wakes only the task that has been
+    /// waiting the longest. An unfair pool gives no ordering guarantee, and a task that just
+    /// called `acquire()` may take a connection ahead of tasks that were already waiting.
+    /// A task that times out or is cancelled leaves the queue without taking a connection.
+    pub fn num_waiting(&self) -> usize {
+        self.0.num_waiting()
+    }
+
+    /// Returns the current generation of the pool's connections.
+    ///
+    /// The generation starts at `0` and is incremented whenever a connection fails with an error
+    /// meaning the server has gone away, such as when it's restarted: SQLSTATE class `08`,
+    /// `57P01`/`57P02`/`57P03`, or the connection being closed by the other end. Idle connections
+    /// opened before the new generation are closed right away, and ones that are checked out are
+    /// closed when they're returned to the pool, so that later acquires open fresh connections.
+    /// Further errors from connections opened before the new generation don't increment it again.
+    ///
+    /// Errors are seen by the pool when it's used as an [`Executor`][crate::executor::Executor]
+    /// and when testing a connection before acquiring it. Errors that a [`PoolConnection`] or a
+    /// transaction from [`Pool::begin()`] returned while checked out are seen when the connection
+    /// is released back to the pool; only the Postgres and MySQL drivers keep them until then.
+    pub fn generation(&self) -> u64 {
+        self.0.generation()
+    }
+
+    /// Returns the total time tasks have spent waiting in [`Pool::acquire()`] over the lifetime
+    /// of the pool, including acquires that timed out or were cancelled.
+    ///
+    /// Combined with the number of acquires made by the application, this can be used to
+    /// compute the average time to acquire a connection.
+    pub fn acquire_wait_time(&self) -> Duration {
+        self.0.acquire_wait_time()
+    }
+
     /// Gets a clone of the connection options for this pool
     pub fn connect_options(&self) -> Arc<<DB::Connection as Connection>::Options> {
         self.0
@@ -525,7 +636,7 @@ impl<DB: Database> Pool<DB> {
             .connect_options
             .write()
             .expect("write-lock holder panicked");
-        *guard = Arc::new(connect_options);
+        *guard = Arc::new(self.0.options.apply_metrics(connect_options));
     }
 
     /// Get the options for this pool
@@ -546,6 +657,7 @@ impl<DB: Database> fmt::Debug for Pool<DB> {
         fmt.debug_struct("Pool")
             .field("size", &self.0.size())
             .field("num_idle", &self.0.num_idle())
+            .field("num_waiting", &self.0.num_waiting())
             .field("is_closed", &self.0.is_closed())
             .field("options", &self.0.options)
             .finish()
@@ -1,6 +1,7 @@
-use crate::connection::Connection;
+use crate::connection::{ConnectOptions, Connection};
 use crate::database::Database;
 use crate::error::Error;
+use crate::metrics::Metrics;
 use crate::pool::inner::PoolInner;
 use crate::pool::Pool;
 use futures_core::future::BoxFuture;
@@ -44,6 +45,7 @@ use std::time::{Duration, Instant};
 /// the perspectives of both API designer and consumer.
 pub struct PoolOptions<DB: Database> {
     pub(crate) test_before_acquire: bool,
+    pub(crate) reset_on_release: bool,
     pub(crate) after_connect: Option<
         Arc<
             dyn Fn(&mut DB::Connection, PoolConnectionMetadata) -> BoxFuture<'_, Result<(), Error>>
@@ -83,6 +85,7 @@ pub struct PoolOptions<DB: Database> {
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
     pub(crate) fair: bool,
+    pub(crate) metrics: Option<Arc<dyn Metrics>>,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
 }
@@ -94,9 +97,11 @@ impl<DB: Database> Clone for PoolOptions<DB> {
     fn clone(&self) -> Self {
         PoolOptions {
             test_before_acquire: self.test_before_acquire,
+            reset_on_release: self.reset_on_release,
             after_connect: self.after_connect.clone(),
             before_acquire: self.before_acquire.clone(),
             after_release: self.after_release.clone(),
+            metrics: self.metrics.clone(),
             max_connections: self.max_connections,
             acquire_time_level: self.acquire_time_level,
             acquire_slow_threshold: self.acquire_slow_threshold,
@@ -146,7 +151,9 @@ impl<DB: Database> PoolOptions<DB> {
             after_connect: None,
             before_acquire: None,
             after_release: None,
+            metrics: None,
             test_before_acquire: true,
+            reset_on_release: false,
             // A production application will want to set a higher limit than this.
             max_connections: 10,
             min_connections: 0,
@@ -321,6 +328,27 @@ impl<DB: Database> PoolOptions<DB> {
         self.test_before_acquire
     }
 
+    /// If true, the session state of a connection will be reset by a call to
+    /// [`Connection::reset`] when it is released back to the pool, so that temporary tables,
+    /// variables and `SET` statements from one checkout don't leak into the next.
+    ///
+    /// This replaces the health check that is otherwise done on release.
+    /// If the reset fails, the connection is closed instead.
+    ///
+    /// Note that settings applied in [`after_connect`][Self::after_connect] are reset as well.
+    /// Prefer setting them through the connect options, which are reapplied, where possible.
+    ///
+    /// Defaults to `false`.
+    pub fn reset_on_release(mut self, reset: bool) -> Self {
+        self.reset_on_release = reset;
+        self
+    }
+
+    /// Get whether `reset_on_release` is currently set.
+    pub fn get_reset_on_release(&self) -> bool {
+        self.reset_on_release
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -500,6 +528,29 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Report connection usage and the queries executed on the pool's connections to `metrics`.
+    ///
+    /// This is also set on the pool's connect options with
+    /// [`ConnectOptions::metrics()`][crate::connection::ConnectOptions::metrics],
+    /// so it's kept if they're replaced with [`Pool::set_connect_options()`].
+    ///
+    /// With the `metrics` feature enabled, [`MetricsFacade`][crate::metrics::MetricsFacade]
+    /// records them with the `metrics` crate, for exporting to Prometheus and others.
+    pub fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub(crate) fn apply_metrics(
+        &self,
+        connect_options: <DB::Connection as Connection>::Options,
+    ) -> <DB::Connection as Connection>::Options {
+        match &self.metrics {
+            Some(metrics) => connect_options.metrics(metrics.clone()),
+            None => connect_options,
+        }
+    }
+
     /// Set the parent `Pool` from which the new pool will inherit its semaphore.
     ///
     /// This is currently an internal-only API.
@@ -591,6 +642,8 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("reset_on_release", &self.reset_on_release)
+            .field("metrics", &self.metrics.is_some())
             .finish()
     }
 }
@@ -1,10 +1,11 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
 use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{future, StreamExt, TryFutureExt, TryStreamExt};
 
-use crate::arguments::{Arguments, IntoArguments};
+use crate::arguments::{Arguments, ArgumentsSummary, IntoArguments};
 use crate::database::{Database, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
@@ -15,10 +16,11 @@ use crate::types::Type;
 /// A single SQL query as a prepared statement. Returned by [`query()`].
 #[must_use = "query must be executed to affect database"]
 pub struct Query<'q, DB: Database, A> {
-    pub(crate) statement: Either<&'q str, &'q DB::Statement<'q>>,
+    pub(crate) statement: Either<Cow<'q, str>, &'q DB::Statement<'q>>,
     pub(crate) arguments: Option<Result<A, BoxDynError>>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) simple: bool,
 }
 
 /// A single SQL query that will map its results to an owned Rust type.
@@ -32,27 +34,72 @@ pub struct Query<'q, DB: Database, A> {
 /// [`Query::bind`] is also omitted; stylistically we recommend placing your `.bind()` calls
 /// before `.try_map()`. This is also to prevent adding superfluous binds to the result of
 /// `query!()` et al.
+///
+/// The mapper closure is a type parameter, so a mapped query can be returned from a function
+/// by naming it with `impl Trait` in that position:
+///
+/// ```rust,no_run
+/// # use sqlx::postgres::{PgArguments, PgRow, Postgres};
+/// use sqlx::query::Map;
+/// use sqlx::Row;
+///
+/// fn user_names<'q>(
+///     min_id: i64,
+/// ) -> Map<'q, Postgres, impl FnMut(PgRow) -> Result<String, sqlx::Error> + Send, PgArguments> {
+///     sqlx::query("SELECT name FROM users WHERE id >= $1")
+///         .bind(min_id)
+///         .try_map(|row: PgRow| row.try_get("name"))
+/// }
+/// ```
 #[must_use = "query must be executed to affect database"]
 pub struct Map<'q, DB: Database, F, A> {
     inner: Query<'q, DB, A>,
     mapper: F,
 }
 
+/// Clones the query along with its bound arguments, e.g. to execute it again in a retry loop.
+///
+/// If binding an argument failed, the clone carries the same error message, but the original
+/// error value cannot be recovered from it.
+impl<'q, DB: Database, A: Clone> Clone for Query<'q, DB, A> {
+    fn clone(&self) -> Self {
+        Query {
+            statement: self.statement.clone(),
+            arguments: self.arguments.as_ref().map(|arguments| match arguments {
+                Ok(arguments) => Ok(arguments.clone()),
+                Err(error) => Err(error.to_string().into()),
+            }),
+            database: PhantomData,
+            persistent: self.persistent,
+            simple: self.simple,
+        }
+    }
+}
+
+impl<'q, DB: Database, F: Clone, A: Clone> Clone for Map<'q, DB, F, A> {
+    fn clone(&self) -> Self {
+        Map {
+            inner: self.inner.clone(),
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
 impl<'q, DB, A> Execute<'q, DB> for Query<'q, DB, A>
 where
     DB: Database,
     A: Send + IntoArguments<'q, DB>,
 {
     #[inline]
-    fn sql(&self) -> &'q str {
-        match self.statement {
+    fn sql(&self) -> &str {
+        match &self.statement {
             Either::Right(statement) => statement.sql(),
             Either::Left(sql) => sql,
         }
     }
 
     fn statement(&self) -> Option<&DB::Statement<'q>> {
-        match self.statement {
+        match &self.statement {
             Either::Right(statement) => Some(statement),
             Either::Left(_) => None,
         }
@@ -60,10 +107,24 @@ where
 
     #[inline]
     fn take_arguments(&mut self) -> Result<Option<<DB as Database>::Arguments<'q>>, BoxDynError> {
-        self.arguments
+        let arguments = self
+            .arguments
             .take()
-            .transpose()
-            .map(|option| option.map(IntoArguments::into_arguments))
+            .transpose()?
+            .map(IntoArguments::into_arguments);
+
+        if !self.simple {
+            return Ok(arguments);
+        }
+
+        match arguments {
+            Some(arguments) if arguments.len() > 0 => Err(format!(
+                "cannot bind {} argument(s) to a query executed with `.simple()`",
+                arguments.len()
+            )
+            .into()),
+            _ => Ok(None),
+        }
     }
 
     #[inline]
@@ -109,6 +170,29 @@ impl<'q, DB: Database> Query<'q, DB, <DB as Database>::Arguments<'q>> {
         arguments.add(value)
     }
 
+    /// A summary of the arguments bound so far, with the type and size of each but not its value,
+    /// e.g. for logging alongside the SQL from [`Execute::sql()`].
+    ///
+    /// Returns `None` if binding an argument failed or the query was already executed.
+    ///
+    /// ```rust
+    /// # use sqlx::postgres::Postgres;
+    /// let query = sqlx::query::<Postgres>("SELECT * FROM users WHERE id = $1 AND name = $2")
+    ///     .bind(1_i64)
+    ///     .bind("hello world");
+    ///
+    /// assert_eq!(
+    ///     query.arguments_summary().unwrap().to_string(),
+    ///     "[$1: INT8 (8 bytes), $2: TEXT (11 bytes)]"
+    /// );
+    /// ```
+    pub fn arguments_summary(&self) -> Option<ArgumentsSummary> {
+        match &self.arguments {
+            Some(Ok(arguments)) => Some(arguments.summary()),
+            _ => None,
+        }
+    }
+
     fn get_arguments(&mut self) -> Result<&mut DB::Arguments<'q>, BoxDynError> {
         let Some(Ok(arguments)) = self.arguments.as_mut().map(Result::as_mut) else {
             return Err("A previous call to Query::bind produced an error"
@@ -140,6 +224,21 @@ where
     }
 }
 
+impl<'q, DB: Database, A> Query<'q, DB, A> {
+    /// Execute the query without preparing it, like [`raw_sql()`][crate::raw_sql::raw_sql].
+    ///
+    /// This is for statements which the database refuses to prepare, such as some utility
+    /// statements, or several statements in one query string. With Postgres, it uses the
+    /// simple query protocol, and with MySQL, the text protocol.
+    ///
+    /// The query can't have any parameters then: executing it returns an error if any
+    /// arguments were bound. `query!()` and friends still check the query at compile time.
+    pub fn simple(mut self) -> Self {
+        self.simple = true;
+        self
+    }
+}
+
 impl<'q, DB, A: Send> Query<'q, DB, A>
 where
     DB: Database,
@@ -252,6 +351,42 @@ where
         executor.fetch_all(self).await
     }
 
+    /// Execute the query and return all the resulting rows collected into a [`Vec`],
+    /// along with the [`QueryResult`][Database::QueryResult] of the statement.
+    ///
+    /// This is intended for `INSERT`, `UPDATE` and `DELETE` statements with a `RETURNING` clause,
+    /// where both the returned rows and the number of rows affected are of interest.
+    ///
+    /// ### Note: beware result set size.
+    /// This will attempt to collect the full result set of the query into memory.
+    ///
+    /// To avoid exhausting available memory, ensure the result set has a known upper bound,
+    /// e.g. using `LIMIT`.
+    pub async fn fetch_all_with_result<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<(Vec<DB::Row>, DB::QueryResult), Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        #[allow(deprecated)]
+        let mut s = self.fetch_many(executor);
+
+        let mut rows = Vec::new();
+        let mut result = DB::QueryResult::default();
+
+        while let Some(step) = s.try_next().await? {
+            match step {
+                Either::Left(r) => result.extend(Some(r)),
+                Either::Right(row) => rows.push(row),
+            }
+        }
+
+        Ok((rows, result))
+    }
+
     /// Execute the query, returning the first row or [`Error::RowNotFound`] otherwise.
     ///
     /// ### Note: for best performance, ensure the query returns at most one row.
@@ -303,7 +438,7 @@ where
     A: IntoArguments<'q, DB>,
 {
     #[inline]
-    fn sql(&self) -> &'q str {
+    fn sql(&self) -> &str {
         self.inner.sql()
     }
 
@@ -319,7 +454,17 @@ where
 
     #[inline]
     fn persistent(&self) -> bool {
-        self.inner.arguments.is_some()
+        self.inner.persistent()
+    }
+}
+
+impl<'q, DB: Database, F, A> Map<'q, DB, F, A> {
+    /// Execute the query without preparing it.
+    ///
+    /// See [`Query::simple`].
+    pub fn simple(mut self) -> Self {
+        self.inner = self.inner.simple();
+        self
     }
 }
 
@@ -372,7 +517,7 @@ where
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
         'q: 'e,
-        E: 'e + Executor<'c, Database = DB>,
+        E: Executor<'c, Database = DB>,
         DB: 'e,
         F: 'e,
         O: 'e,
@@ -399,14 +544,14 @@ where
     ) -> BoxStream<'e, Result<Either<DB::QueryResult, O>, Error>>
     where
         'q: 'e,
-        E: 'e + Executor<'c, Database = DB>,
+        E: Executor<'c, Database = DB>,
         DB: 'e,
         F: 'e,
         O: 'e,
     {
-        Box::pin(try_stream! {
-            let mut s = executor.fetch_many(self.inner);
+        let mut s = executor.fetch_many(self.inner);
 
+        Box::pin(try_stream! {
             while let Some(v) = s.try_next().await? {
                 r#yield!(match v {
                     Either::Left(v) => Either::Left(v),
@@ -508,6 +653,7 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Right(statement),
         persistent: true,
+        simple: false,
     }
 }
 
@@ -525,6 +671,7 @@ where
         arguments: Some(Ok(arguments)),
         statement: Either::Right(statement),
         persistent: true,
+        simple: false,
     }
 }
 
@@ -652,6 +799,23 @@ where
 ///
 /// As an additional benefit, query parameters are usually sent in a compact binary encoding instead of a human-readable
 /// text encoding, which saves bandwidth.
+///
+/// ##### The number of bound values must match the number of placeholders.
+/// Before the query is executed, the number of values passed to [`.bind()`][Query::bind] is checked
+/// against the number of parameters in the query, and [`Error::ParameterCountMismatch`] is returned
+/// if binding too few or too many values. Nothing is sent to the server to execute in that case.
+///
+/// Postgres only reports missing values: the server counts a parameter for every value that is
+/// sent, whether the query uses it or not.
+///
+/// SQLite prepares the statements of a query one at a time, as a statement may use a table created
+/// by the one before it, so values left over after the last statement are only reported once
+/// it is reached. The statements before it have already been executed by then.
+///
+/// This does not apply to queries without arguments, such as a `&str` passed directly to
+/// an [`Executor`] or [`raw_sql()`][crate::raw_sql::raw_sql], which can't have placeholders.
+///
+/// [`Error::ParameterCountMismatch`]: crate::error::Error::ParameterCountMismatch
 pub fn query<DB>(sql: &str) -> Query<'_, DB, <DB as Database>::Arguments<'_>>
 where
     DB: Database,
@@ -659,8 +823,43 @@ where
     Query {
         database: PhantomData,
         arguments: Some(Ok(Default::default())),
-        statement: Either::Left(sql),
+        statement: Either::Left(Cow::Borrowed(sql)),
+        persistent: true,
+        simple: false,
+    }
+}
+
+/// Execute a single SQL query as a prepared statement (transparently cached),
+/// taking ownership of the SQL.
+///
+/// Unlike [`query()`], the query doesn't borrow the SQL, so a query built from a dynamically
+/// generated `String` may outlive it. With arguments that don't borrow either, the futures and
+/// streams returned by executing the query on a [`Pool`][crate::pool::Pool] are `'static`,
+/// so they may be returned from a function:
+///
+/// ```rust,no_run
+/// # use futures_core::stream::BoxStream;
+/// use sqlx::postgres::{PgPool, PgRow};
+///
+/// fn stream_users(pool: &PgPool, table: &str, min_id: i64) -> BoxStream<'static, sqlx::Result<PgRow>> {
+///     sqlx::query_owned(format!("SELECT * FROM {table} WHERE id >= $1"))
+///         .bind(min_id)
+///         .fetch(pool)
+/// }
+/// ```
+///
+/// See [`query()`] for details, such as supported syntax, and for why the SQL should not be
+/// built from untrusted input.
+pub fn query_owned<'q, DB>(sql: String) -> Query<'q, DB, <DB as Database>::Arguments<'q>>
+where
+    DB: Database,
+{
+    Query {
+        database: PhantomData,
+        arguments: Some(Ok(Default::default())),
+        statement: Either::Left(Cow::Owned(sql)),
         persistent: true,
+        simple: false,
     }
 }
 
@@ -687,7 +886,8 @@ where
     Query {
         database: PhantomData,
         arguments: Some(arguments),
-        statement: Either::Left(sql),
+        statement: Either::Left(Cow::Borrowed(sql)),
         persistent: true,
+        simple: false,
     }
 }
@@ -4,13 +4,15 @@ use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryStreamExt};
 
-use crate::arguments::IntoArguments;
+use crate::arguments::{ArgumentsSummary, IntoArguments};
 use crate::database::{Database, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
-use crate::query::{query, query_statement, query_statement_with, query_with_result, Query};
+use crate::query::{
+    query, query_owned, query_statement, query_statement_with, query_with_result, Map, Query,
+};
 use crate::types::Type;
 
 /// A single SQL query as a prepared statement, mapping results using [`FromRow`].
@@ -21,13 +23,22 @@ pub struct QueryAs<'q, DB: Database, O, A> {
     pub(crate) output: PhantomData<O>,
 }
 
+impl<'q, DB: Database, O, A: Clone> Clone for QueryAs<'q, DB, O, A> {
+    fn clone(&self) -> Self {
+        QueryAs {
+            inner: self.inner.clone(),
+            output: PhantomData,
+        }
+    }
+}
+
 impl<'q, DB, O: Send, A: Send> Execute<'q, DB> for QueryAs<'q, DB, O, A>
 where
     DB: Database,
     A: 'q + IntoArguments<'q, DB>,
 {
     #[inline]
-    fn sql(&self) -> &'q str {
+    fn sql(&self) -> &str {
         self.inner.sql()
     }
 
@@ -55,6 +66,12 @@ impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as Database>::Arguments<'q>> {
         self.inner = self.inner.bind(value);
         self
     }
+    /// A summary of the arguments bound so far, e.g. for logging.
+    ///
+    /// See [`Query::arguments_summary`](Query::arguments_summary).
+    pub fn arguments_summary(&self) -> Option<ArgumentsSummary> {
+        self.inner.arguments_summary()
+    }
 }
 
 impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
@@ -77,6 +94,16 @@ where
     }
 }
 
+impl<'q, DB: Database, O, A> QueryAs<'q, DB, O, A> {
+    /// Execute the query without preparing it.
+    ///
+    /// See [`Query::simple`](Query::simple).
+    pub fn simple(mut self) -> Self {
+        self.inner = self.inner.simple();
+        self
+    }
+}
+
 // FIXME: This is very close, nearly 1:1 with `Map`
 // noinspection DuplicatedCode
 impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
@@ -85,11 +112,44 @@ where
     A: 'q + IntoArguments<'q, DB>,
     O: Send + Unpin + for<'r> FromRow<'r, DB::Row>,
 {
+    /// Map each row in the result to another type, after first decoding it using [`FromRow`].
+    ///
+    /// See [`try_map`](QueryAs::try_map) for a fallible version of this method.
+    #[inline]
+    pub fn map<G, P>(
+        self,
+        mut g: G,
+    ) -> Map<'q, DB, impl FnMut(DB::Row) -> Result<P, Error> + Send, A>
+    where
+        G: FnMut(O) -> P + Send,
+        P: Unpin,
+        A: Send,
+    {
+        self.try_map(move |data| Ok(g(data)))
+    }
+
+    /// Map each row in the result to another type, after first decoding it using [`FromRow`].
+    ///
+    /// An error returned by either [`FromRow`] or the closure is surfaced for that row.
+    #[inline]
+    pub fn try_map<G, P>(
+        self,
+        mut g: G,
+    ) -> Map<'q, DB, impl FnMut(DB::Row) -> Result<P, Error> + Send, A>
+    where
+        G: FnMut(O) -> Result<P, Error> + Send,
+        P: Unpin,
+        A: Send,
+    {
+        self.inner
+            .try_map(move |row: DB::Row| O::from_row(&row).and_then(&mut g))
+    }
+
     /// Execute the query and return the generated results as a stream.
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
         'q: 'e,
-        E: 'e + Executor<'c, Database = DB>,
+        E: Executor<'c, Database = DB>,
         DB: 'e,
         O: 'e,
         A: 'e,
@@ -111,7 +171,7 @@ where
     ) -> BoxStream<'e, Result<Either<DB::QueryResult, O>, Error>>
     where
         'q: 'e,
-        E: 'e + Executor<'c, Database = DB>,
+        E: Executor<'c, Database = DB>,
         DB: 'e,
         O: 'e,
         A: 'e,
@@ -145,6 +205,31 @@ where
         self.fetch(executor).try_collect().await
     }
 
+    /// Execute the query and return all the resulting rows collected into a [`Vec`],
+    /// along with the [`QueryResult`][Database::QueryResult] of the statement.
+    ///
+    /// See [`Query::fetch_all_with_result()`].
+    pub async fn fetch_all_with_result<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<(Vec<O>, DB::QueryResult), Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        O: 'e,
+        A: 'e,
+    {
+        let (rows, result) = self.inner.fetch_all_with_result(executor).await?;
+
+        let rows = rows
+            .iter()
+            .map(O::from_row)
+            .collect::<Result<Vec<O>, Error>>()?;
+
+        Ok((rows, result))
+    }
+
     /// Execute the query, returning the first row or [`Error::RowNotFound`] otherwise.
     ///
     /// ### Note: for best performance, ensure the query returns at most one row.
@@ -350,6 +435,22 @@ where
     }
 }
 
+/// Execute a single SQL query as a prepared statement (transparently cached),
+/// taking ownership of the SQL. Maps rows to Rust types using [`FromRow`].
+///
+/// See [`query_owned()`][crate::query::query_owned] for why this is useful.
+#[inline]
+pub fn query_as_owned<'q, DB, O>(sql: String) -> QueryAs<'q, DB, O, <DB as Database>::Arguments<'q>>
+where
+    DB: Database,
+    O: for<'r> FromRow<'r, DB::Row>,
+{
+    QueryAs {
+        inner: query_owned(sql),
+        output: PhantomData,
+    }
+}
+
 /// Execute a single SQL query, with the given arguments as a prepared statement (transparently cached).
 /// Maps rows to Rust types using [`FromRow`].
 ///
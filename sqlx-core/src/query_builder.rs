@@ -1,5 +1,6 @@
 //! Runtime query-builder API.
 
+use std::borrow::Cow;
 use std::fmt::Display;
 use std::fmt::Write;
 use std::marker::PhantomData;
@@ -14,6 +15,10 @@ use crate::query_scalar::QueryScalar;
 use crate::types::Type;
 use crate::Either;
 
+pub use upsert::{Upsert, UpsertSyntax};
+
+mod upsert;
+
 /// A builder type for constructing queries at runtime.
 ///
 /// See [`.push_values()`][Self::push_values] for an example of building a bulk `INSERT` statement.
@@ -454,10 +459,11 @@ where
         self.sanity_check();
 
         Query {
-            statement: Either::Left(&self.query),
+            statement: Either::Left(Cow::Borrowed(&self.query)),
             arguments: self.arguments.take().map(Ok),
             database: PhantomData,
             persistent: true,
+            simple: false,
         }
     }
 
@@ -0,0 +1,263 @@
+use crate::database::Database;
+use crate::query_builder::{QueryBuilder, Separated};
+
+/// The syntax a database uses for an `INSERT` that updates the row it conflicts with.
+///
+/// See [`Database::UPSERT_SYNTAX`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UpsertSyntax {
+    /// `ON CONFLICT (a) DO UPDATE SET b = excluded.b`.
+    ///
+    /// Used by Postgres and SQLite (3.24.0+).
+    OnConflict,
+
+    /// `ON DUPLICATE KEY UPDATE b = VALUES(b)`.
+    ///
+    /// Used by MySQL, which updates the row conflicting on any unique key
+    /// and so ignores the conflict columns.
+    OnDuplicateKey,
+}
+
+/// A builder for an `INSERT` that updates the existing row if it conflicts, in the syntax of `DB`.
+///
+/// The table and column names are quoted as identifiers with [`Database::IDENTIFIER_QUOTE`],
+/// and the values are bound as arguments, so none of them can inject SQL.
+/// A table name containing a `.` is taken to be qualified with a schema, and each part is
+/// quoted separately.
+///
+/// ```rust
+/// # #[cfg(all(feature = "mysql", feature = "postgres"))] {
+/// use sqlx::{MySql, Postgres, Upsert};
+///
+/// let rows = [(1_i32, "Alice"), (2, "Bob")];
+///
+/// let upsert = Upsert::<Postgres>::into("users")
+///     .columns(&["id", "name"])
+///     .values(&rows, |mut b, (id, name)| {
+///         b.push_bind(id).push_bind(name);
+///     })
+///     .conflict(&["id"])
+///     .update(&["name"]);
+///
+/// assert_eq!(
+///     upsert.into_query_builder().sql(),
+///     r#"INSERT INTO "users" ("id", "name") VALUES ($1, $2), ($3, $4) "#.to_owned()
+///         + r#"ON CONFLICT ("id") DO UPDATE SET "name" = excluded."name""#
+/// );
+///
+/// let upsert = Upsert::<MySql>::into("users")
+///     .columns(&["id", "name"])
+///     .values(&rows, |mut b, (id, name)| {
+///         b.push_bind(id).push_bind(name);
+///     })
+///     .conflict(&["id"])
+///     .update(&["name"]);
+///
+/// assert_eq!(
+///     upsert.into_query_builder().sql(),
+///     "INSERT INTO `users` (`id`, `name`) VALUES (?, ?), (?, ?) \
+///      ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"
+/// );
+/// # }
+/// ```
+///
+/// Call [`.into_query_builder()`][Self::into_query_builder] and then
+/// [`.build()`][QueryBuilder::build] on the result to execute the query.
+///
+/// ### Note: `Any`
+/// [`Any`][crate::any::Any] uses the syntax of Postgres and SQLite, as it can't know
+/// which database it will be connected to.
+pub struct Upsert<'args, DB>
+where
+    DB: Database,
+{
+    query_builder: QueryBuilder<'args, DB>,
+    table: String,
+    columns: Vec<String>,
+    conflict: Vec<String>,
+    update: Vec<String>,
+    values_pushed: bool,
+}
+
+impl<'args, DB: Database> Upsert<'args, DB>
+where
+    <DB as Database>::Arguments<'args>: Default,
+{
+    /// Start building an upsert into `table`.
+    pub fn into(table: impl Into<String>) -> Self {
+        Upsert {
+            query_builder: QueryBuilder::new(""),
+            table: table.into(),
+            columns: Vec::new(),
+            conflict: Vec::new(),
+            update: Vec::new(),
+            values_pushed: false,
+        }
+    }
+}
+
+impl<'args, DB: Database> Upsert<'args, DB> {
+    /// Set the columns to insert the values into.
+    ///
+    /// Must be called before [`.values()`][Self::values].
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.columns = columns.iter().map(|&column| column.to_owned()).collect();
+        self
+    }
+
+    /// Push the rows to insert, binding the values of each row with `push_row`
+    /// in the order of [`.columns()`][Self::columns].
+    ///
+    /// See [`QueryBuilder::push_values()`] for details, including the limits on the number
+    /// of bind arguments.
+    ///
+    /// ### Panics
+    /// If the columns have not been set, or if this has been called already.
+    pub fn values<I, F>(mut self, rows: I, push_row: F) -> Self
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, 'args, DB, &'static str>, I::Item),
+    {
+        assert!(
+            !self.columns.is_empty(),
+            "`Upsert::columns()` must be called before `.values()`"
+        );
+        assert!(!self.values_pushed, "`Upsert::values()` called twice");
+
+        self.query_builder.push("INSERT INTO ");
+
+        for (i, part) in self.table.split('.').enumerate() {
+            if i > 0 {
+                self.query_builder.push(".");
+            }
+
+            self.query_builder.push(quote_identifier::<DB>(part));
+        }
+
+        self.query_builder.push(" (");
+
+        let mut separated = self.query_builder.separated(", ");
+
+        for column in &self.columns {
+            separated.push(quote_identifier::<DB>(column));
+        }
+
+        self.query_builder.push(") ");
+        self.query_builder.push_values(rows, push_row);
+        self.values_pushed = true;
+
+        self
+    }
+
+    /// Set the columns of the unique index or constraint that the rows may conflict on.
+    ///
+    /// Ignored by MySQL; see [`UpsertSyntax::OnDuplicateKey`].
+    pub fn conflict(mut self, columns: &[&str]) -> Self {
+        self.conflict = columns.iter().map(|&column| column.to_owned()).collect();
+        self
+    }
+
+    /// Set the columns to update with the values of the row that was inserted,
+    /// if it conflicts with an existing row.
+    ///
+    /// If this is empty, the conflicting row is left as it is.
+    /// Otherwise, [`.conflict()`][Self::conflict] must be called as well, as Postgres and SQLite
+    /// need to know which constraint to update the row on; this is checked for MySQL too,
+    /// so that an upsert built for one database works with the others.
+    pub fn update(mut self, columns: &[&str]) -> Self {
+        self.update = columns.iter().map(|&column| column.to_owned()).collect();
+        self
+    }
+
+    /// Finish the query, returning a `QueryBuilder` to [`.build()`][QueryBuilder::build] it.
+    ///
+    /// ### Panics
+    /// If [`.values()`][Self::values] has not been called, or if [`.update()`][Self::update]
+    /// was called with columns but [`.conflict()`][Self::conflict] was not.
+    pub fn into_query_builder(mut self) -> QueryBuilder<'args, DB> {
+        assert!(
+            self.values_pushed,
+            "`Upsert::values()` must be called before `.into_query_builder()`"
+        );
+        assert!(
+            self.update.is_empty() || !self.conflict.is_empty(),
+            "`Upsert::conflict()` must be called before `.into_query_builder()` \
+             when `.update()` is"
+        );
+
+        match DB::UPSERT_SYNTAX {
+            UpsertSyntax::OnConflict => self.push_on_conflict(),
+            UpsertSyntax::OnDuplicateKey => self.push_on_duplicate_key(),
+        }
+
+        self.query_builder
+    }
+
+    fn push_on_conflict(&mut self) {
+        self.query_builder.push(" ON CONFLICT ");
+
+        if !self.conflict.is_empty() {
+            self.query_builder.push("(");
+
+            let mut separated = self.query_builder.separated(", ");
+
+            for column in &self.conflict {
+                separated.push(quote_identifier::<DB>(column));
+            }
+
+            self.query_builder.push(") ");
+        }
+
+        if self.update.is_empty() {
+            self.query_builder.push("DO NOTHING");
+            return;
+        }
+
+        self.query_builder.push("DO UPDATE SET ");
+
+        let mut separated = self.query_builder.separated(", ");
+
+        for column in &self.update {
+            let column = quote_identifier::<DB>(column);
+            separated.push(format_args!("{column} = excluded.{column}"));
+        }
+    }
+
+    fn push_on_duplicate_key(&mut self) {
+        self.query_builder.push(" ON DUPLICATE KEY UPDATE ");
+
+        // MySQL has no `DO NOTHING`, so set a column to itself instead
+        if self.update.is_empty() {
+            let column = quote_identifier::<DB>(&self.columns[0]);
+            self.query_builder.push(format_args!("{column} = {column}"));
+            return;
+        }
+
+        let mut separated = self.query_builder.separated(", ");
+
+        for column in &self.update {
+            let column = quote_identifier::<DB>(column);
+            separated.push(format_args!("{column} = VALUES({column})"));
+        }
+    }
+}
+
+/// Quote `ident` with [`Database::IDENTIFIER_QUOTE`], doubling any quotes within it.
+fn quote_identifier<DB: Database>(ident: &str) -> String {
+    let quote = DB::IDENTIFIER_QUOTE;
+    let mut quoted = String::with_capacity(ident.len() + 2);
+
+    quoted.push(quote);
+
+    for c in ident.chars() {
+        if c == quote {
+            quoted.push(quote);
+        }
+
+        quoted.push(c);
+    }
+
+    quoted.push(quote);
+    quoted
+}
@@ -2,14 +2,15 @@ use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
 
-use crate::arguments::IntoArguments;
+use crate::arguments::{ArgumentsSummary, IntoArguments};
 use crate::database::{Database, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
 use crate::query_as::{
-    query_as, query_as_with_result, query_statement_as, query_statement_as_with, QueryAs,
+    query_as, query_as_owned, query_as_with_result, query_statement_as, query_statement_as_with,
+    QueryAs,
 };
 use crate::types::Type;
 
@@ -20,12 +21,20 @@ pub struct QueryScalar<'q, DB: Database, O, A> {
     pub(crate) inner: QueryAs<'q, DB, (O,), A>,
 }
 
+impl<'q, DB: Database, O, A: Clone> Clone for QueryScalar<'q, DB, O, A> {
+    fn clone(&self) -> Self {
+        QueryScalar {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
 impl<'q, DB: Database, O: Send, A: Send> Execute<'q, DB> for QueryScalar<'q, DB, O, A>
 where
     A: 'q + IntoArguments<'q, DB>,
 {
     #[inline]
-    fn sql(&self) -> &'q str {
+    fn sql(&self) -> &str {
         self.inner.sql()
     }
 
@@ -52,6 +61,12 @@ impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as Database>::Arguments<'q>
         self.inner = self.inner.bind(value);
         self
     }
+    /// A summary of the arguments bound so far, e.g. for logging.
+    ///
+    /// See [`Query::arguments_summary`](crate::query::Query::arguments_summary).
+    pub fn arguments_summary(&self) -> Option<ArgumentsSummary> {
+        self.inner.arguments_summary()
+    }
 }
 
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
@@ -74,6 +89,16 @@ where
     }
 }
 
+impl<'q, DB: Database, O, A> QueryScalar<'q, DB, O, A> {
+    /// Execute the query without preparing it.
+    ///
+    /// See [`Query::simple`](crate::query::Query::simple).
+    pub fn simple(mut self) -> Self {
+        self.inner = self.inner.simple();
+        self
+    }
+}
+
 // FIXME: This is very close, nearly 1:1 with `Map`
 // noinspection DuplicatedCode
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
@@ -88,7 +113,7 @@ where
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
         'q: 'e,
-        E: 'e + Executor<'c, Database = DB>,
+        E: Executor<'c, Database = DB>,
         DB: 'e,
         A: 'e,
         O: 'e,
@@ -106,7 +131,7 @@ where
     ) -> BoxStream<'e, Result<Either<DB::QueryResult, O>, Error>>
     where
         'q: 'e,
-        E: 'e + Executor<'c, Database = DB>,
+        E: Executor<'c, Database = DB>,
         DB: 'e,
         A: 'e,
         O: 'e,
@@ -141,6 +166,26 @@ where
             .await
     }
 
+    /// Execute the query and return all the resulting values collected into a [`Vec`],
+    /// along with the [`QueryResult`][Database::QueryResult] of the statement.
+    ///
+    /// See [`Query::fetch_all_with_result()`][crate::query::Query::fetch_all_with_result].
+    pub async fn fetch_all_with_result<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<(Vec<O>, DB::QueryResult), Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        (O,): 'e,
+        A: 'e,
+    {
+        let (rows, result) = self.inner.fetch_all_with_result(executor).await?;
+
+        Ok((rows.into_iter().map(|it| it.0).collect(), result))
+    }
+
     /// Execute the query, returning the first row or [`Error::RowNotFound`] otherwise.
     ///
     /// ### Note: for best performance, ensure the query returns at most one row.
@@ -330,6 +375,23 @@ where
     }
 }
 
+/// Execute a single SQL query as a prepared statement (transparently cached),
+/// taking ownership of the SQL, and extract the first column of each row.
+///
+/// See [`query_owned()`][crate::query::query_owned] for why this is useful.
+#[inline]
+pub fn query_scalar_owned<'q, DB, O>(
+    sql: String,
+) -> QueryScalar<'q, DB, O, <DB as Database>::Arguments<'q>>
+where
+    DB: Database,
+    (O,): for<'r> FromRow<'r, DB::Row>,
+{
+    QueryScalar {
+        inner: query_as_owned(sql),
+    }
+}
+
 /// Execute a SQL query as a prepared statement (transparently cached), with the given arguments,
 /// and extract the first column of each row.
 ///
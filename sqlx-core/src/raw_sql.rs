@@ -119,7 +119,7 @@ pub fn raw_sql(sql: &str) -> RawSql<'_> {
 }
 
 impl<'q, DB: Database> Execute<'q, DB> for RawSql<'q> {
-    fn sql(&self) -> &'q str {
+    fn sql(&self) -> &str {
         self.0
     }
 
@@ -255,15 +255,19 @@ impl<'q> RawSql<'q> {
     /// If your query has a `WHERE` clause filtering a unique column by a single value, you're good.
     ///
     /// Otherwise, you might want to add `LIMIT 1` to your query.
+    ///
+    /// ### Note: breaking change since 0.8.3
+    /// This returned the row itself and [`Error::RowNotFound`][crate::error::Error::RowNotFound]
+    /// if there was none, like [`fetch_one()`][Self::fetch_one], which it called by mistake.
     #[inline]
     pub async fn fetch_optional<'e, E>(
         self,
         executor: E,
-    ) -> crate::Result<<E::Database as Database>::Row>
+    ) -> crate::Result<Option<<E::Database as Database>::Row>>
     where
         'q: 'e,
         E: Executor<'e>,
     {
-        executor.fetch_one(self).await
+        executor.fetch_optional(self).await
     }
 }
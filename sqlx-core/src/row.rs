@@ -2,6 +2,7 @@ use crate::column::ColumnIndex;
 use crate::database::Database;
 use crate::decode::Decode;
 use crate::error::{mismatched_types, Error};
+use crate::from_row::FromRow;
 
 use crate::type_info::TypeInfo;
 use crate::types::Type;
@@ -175,4 +176,53 @@ pub trait Row: Unpin + Send + Sync + 'static {
     fn try_get_raw<I>(&self, index: I) -> Result<<Self::Database as Database>::ValueRef<'_>, Error>
     where
         I: ColumnIndex<Self>;
+
+    /// Decode the whole row into a `T`, such as a struct that derives [`FromRow`].
+    ///
+    /// This is equivalent to `T::from_row(&row)`. `T` may borrow from the row,
+    /// e.g. to read a column as `&'r str` without copying it:
+    ///
+    /// ```rust,ignore
+    /// #[derive(sqlx::FromRow)]
+    /// struct NameRef<'r> {
+    ///     name: &'r str,
+    /// }
+    ///
+    /// let row = sqlx::query("SELECT name FROM users").fetch_one(&mut conn).await?;
+    /// let name: NameRef<'_> = row.try_into_struct()?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by [`FromRow::from_row()`].
+    #[inline]
+    fn try_into_struct<'r, T>(&'r self) -> Result<T, Error>
+    where
+        Self: Sized,
+        T: FromRow<'r, Self>,
+    {
+        T::from_row(self)
+    }
+}
+
+/// Decode a list of rows, such as the `Vec` returned by
+/// [`fetch_all()`][crate::query::Query::fetch_all], into structs.
+///
+/// This trait is implemented for slices, so it can be called on a `Vec` directly.
+pub trait RowSliceExt<R: Row> {
+    /// Decode every row into a `T`, stopping at the first error.
+    ///
+    /// See [`Row::try_into_struct()`].
+    fn try_into_structs<'r, T>(&'r self) -> Result<Vec<T>, Error>
+    where
+        T: FromRow<'r, R>;
+}
+
+impl<R: Row> RowSliceExt<R> for [R] {
+    fn try_into_structs<'r, T>(&'r self) -> Result<Vec<T>, Error>
+    where
+        T: FromRow<'r, R>,
+    {
+        self.iter().map(T::from_row).collect()
+    }
 }
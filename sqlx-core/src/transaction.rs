@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::borrow::Cow;
 use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, DerefMut};
@@ -20,6 +21,23 @@ pub trait TransactionManager {
         conn: &mut <Self::Database as Database>::Connection,
     ) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Begin a new transaction with the given options, or establish a savepoint within the
+    /// active transaction, in which case `options` are ignored.
+    ///
+    /// The default implementation ignores `options` and calls [`begin()`][Self::begin].
+    /// Drivers which support options for their database override it.
+    fn begin_with<O>(
+        conn: &mut <Self::Database as Database>::Connection,
+        options: O,
+    ) -> BoxFuture<'_, Result<(), Error>>
+    where
+        O: TransactionOptions<Database = Self::Database>,
+    {
+        let _ = options;
+
+        Self::begin(conn)
+    }
+
     /// Commit the active transaction or release the most recent savepoint.
     fn commit(
         conn: &mut <Self::Database as Database>::Connection,
@@ -32,6 +50,85 @@ pub trait TransactionManager {
 
     /// Starts to abort the active transaction or restore from the most recent snapshot.
     fn start_rollback(conn: &mut <Self::Database as Database>::Connection);
+
+    /// Prepare the active transaction for two-phase commit with the transaction ID `gid`.
+    ///
+    /// Returns an error if two-phase commit is not supported by the database.
+    fn prepare<'c>(
+        conn: &'c mut <Self::Database as Database>::Connection,
+        gid: &'c str,
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        let _ = (conn, gid);
+
+        Box::pin(async {
+            Err(Error::InvalidArgument(format!(
+                "two-phase commit is not supported by {}",
+                <Self::Database as Database>::NAME
+            )))
+        })
+    }
+}
+
+/// Options for beginning a new transaction with a connection to [`Self::Database`],
+/// e.g. its isolation level.
+///
+/// See [`Connection::begin_with()`][crate::connection::Connection::begin_with].
+///
+/// This trait is sealed; it is implemented by the options type of each driver, which is the only
+/// type its [`TransactionManager`] accepts.
+pub trait TransactionOptions: sealed::Sealed + Any + Send {
+    type Database: Database;
+}
+
+/// Not part of the public API; only for implementing [`TransactionOptions`] in the drivers.
+#[doc(hidden)]
+pub mod sealed {
+    pub trait Sealed {}
+}
+
+/// Recover the options type of a driver from the `options` passed to
+/// [`TransactionManager::begin_with()`].
+///
+/// As [`TransactionOptions`] is sealed, `O` is always `T` for the drivers in SQLx.
+#[doc(hidden)]
+pub fn downcast_options<T, O>(options: O) -> Result<T, Error>
+where
+    T: TransactionOptions,
+    O: TransactionOptions<Database = T::Database>,
+{
+    let options: Box<dyn Any> = Box::new(options);
+
+    options.downcast().map(|options| *options).map_err(|_| {
+        Error::InvalidArgument(format!(
+            "unsupported transaction options for {}: expected `{}`",
+            <T::Database as Database>::NAME,
+            std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// The isolation level of a transaction.
+///
+/// See the documentation of your database for the exact guarantees of each level.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// The SQL name of this isolation level, e.g. `READ COMMITTED`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
 }
 
 /// An in-progress database transaction or savepoint.
@@ -96,21 +193,93 @@ where
         })
     }
 
+    #[doc(hidden)]
+    pub fn begin_with<O>(
+        conn: impl Into<MaybePoolConnection<'c, DB>>,
+        options: O,
+    ) -> BoxFuture<'c, Result<Self, Error>>
+    where
+        O: TransactionOptions<Database = DB>,
+    {
+        let mut conn = conn.into();
+
+        Box::pin(async move {
+            DB::TransactionManager::begin_with(&mut conn, options).await?;
+
+            Ok(Self {
+                connection: conn,
+                open: true,
+            })
+        })
+    }
+
     /// Commits this transaction or savepoint.
+    ///
+    /// If the transaction owns a connection from a pool, e.g. if it was started by
+    /// [`Pool::begin()`][crate::pool::Pool::begin], the connection is returned to the pool
+    /// before this completes.
     pub async fn commit(mut self) -> Result<(), Error> {
         DB::TransactionManager::commit(&mut self.connection).await?;
         self.open = false;
+        self.return_to_pool().await;
 
         Ok(())
     }
 
     /// Aborts this transaction or savepoint.
+    ///
+    /// If the transaction owns a connection from a pool, the connection is returned to the pool
+    /// before this completes, like with [`.commit()`][Self::commit].
     pub async fn rollback(mut self) -> Result<(), Error> {
         DB::TransactionManager::rollback(&mut self.connection).await?;
         self.open = false;
+        self.return_to_pool().await;
 
         Ok(())
     }
+
+    /// Prepares this transaction for two-phase commit with the transaction ID `gid`.
+    ///
+    /// The transaction is ended on this connection, but its changes are kept by the database
+    /// until it's committed or rolled back by its ID, from any connection to the same database.
+    /// This survives the connection closing and the database restarting.
+    ///
+    /// Only supported by Postgres, where this is `PREPARE TRANSACTION`; see
+    /// `PgConnection::commit_prepared()` and `PgConnection::rollback_prepared()`.
+    /// This can't be used on a savepoint.
+    pub async fn prepare_two_phase(mut self, gid: &str) -> Result<PreparedTransaction, Error> {
+        DB::TransactionManager::prepare(&mut self.connection, gid).await?;
+        self.open = false;
+        self.return_to_pool().await;
+
+        Ok(PreparedTransaction {
+            gid: gid.to_owned(),
+        })
+    }
+
+    /// Return the connection to the pool now if it's owned by this transaction,
+    /// rather than in a task spawned when the `PoolConnection` is dropped.
+    async fn return_to_pool(&mut self) {
+        if let MaybePoolConnection::PoolConnection(conn) = &mut self.connection {
+            conn.return_to_pool().await;
+        }
+    }
+}
+
+/// A transaction prepared for two-phase commit with [`Transaction::prepare_two_phase()`].
+///
+/// Dropping this does nothing: the prepared transaction is kept by the database until it's
+/// committed or rolled back by its ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedTransaction {
+    gid: String,
+}
+
+impl PreparedTransaction {
+    /// The transaction ID it was prepared with.
+    pub fn gid(&self) -> &str {
+        &self.gid
+    }
 }
 
 // NOTE: fails to compile due to lack of lazy normalization
@@ -0,0 +1,71 @@
+use std::ops::{Deref, DerefMut};
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+/// A [`DateTime<Utc>`] stored in a column without a time zone, such as a Postgres `TIMESTAMP`
+/// or a MySQL `DATETIME`.
+///
+/// The database can't know that such a column holds UTC, so with `strict_timestamps` enabled
+/// in the connect options, decoding it as `DateTime<Utc>` is an error. Wrap the type in
+/// `AssumeUtc` to decode it anyway, for columns that are known to hold UTC.
+///
+/// It is encoded as the date and time in UTC, without the time zone.
+///
+/// ```rust,ignore
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::types::chrono::AssumeUtc;
+/// use sqlx::{Connection, PgConnection};
+///
+/// let mut conn = PgConnection::connect("<DATABASE URL>").await?;
+///
+/// // CREATE TABLE logins (user_id INT8, login_at TIMESTAMP);
+/// let login_at: AssumeUtc = sqlx::query_scalar("SELECT login_at FROM logins WHERE user_id = $1")
+///     .bind(1_i64)
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// println!("last login at {}", login_at.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssumeUtc(pub DateTime<Utc>);
+
+impl AssumeUtc {
+    /// Returns the date and time in UTC, without the time zone.
+    pub fn naive_utc(&self) -> NaiveDateTime {
+        self.0.naive_utc()
+    }
+}
+
+impl From<NaiveDateTime> for AssumeUtc {
+    fn from(naive: NaiveDateTime) -> Self {
+        AssumeUtc(Utc.from_utc_datetime(&naive))
+    }
+}
+
+impl From<DateTime<Utc>> for AssumeUtc {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        AssumeUtc(datetime)
+    }
+}
+
+impl From<AssumeUtc> for DateTime<Utc> {
+    fn from(assume_utc: AssumeUtc) -> Self {
+        assume_utc.0
+    }
+}
+
+impl Deref for AssumeUtc {
+    type Target = DateTime<Utc>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for AssumeUtc {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
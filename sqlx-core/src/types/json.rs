@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
 use std::ops::{Deref, DerefMut};
 
 use serde::{Deserialize, Serialize};
@@ -182,6 +184,70 @@ where
     }
 }
 
+/// Implements `Type`, `Encode` and `Decode` for a map of JSON values, mapped to a JSON object.
+macro_rules! impl_json_object {
+    ($map:ident<String, JsonValue $(, $S:ident)?> $(where $($bound:tt)+)?) => {
+        impl<DB, $($S)?> Type<DB> for $map<String, JsonValue $(, $S)?>
+        where
+            Json<JsonValue>: Type<DB>,
+            DB: Database,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <Json<JsonValue> as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <Json<JsonValue> as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB, $($S)?> Encode<'q, DB> for $map<String, JsonValue $(, $S)?>
+        where
+            for<'a> Json<&'a Self>: Encode<'q, DB>,
+            DB: Database,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+            ) -> Result<IsNull, BoxDynError> {
+                <Json<&Self> as Encode<'q, DB>>::encode(Json(self), buf)
+            }
+        }
+
+        impl<'r, DB, $($S)?> Decode<'r, DB> for $map<String, JsonValue $(, $S)?>
+        where
+            Json<JsonValue>: Decode<'r, DB>,
+            DB: Database,
+            $($($bound)+)?
+        {
+            fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                match <Json<JsonValue> as Decode<DB>>::decode(value)?.0 {
+                    JsonValue::Object(object) => Ok(object.into_iter().collect()),
+                    other => Err(format!(
+                        "expected a JSON object, found {}",
+                        json_type_name(&other)
+                    )
+                    .into()),
+                }
+            }
+        }
+    };
+}
+
+impl_json_object!(HashMap<String, JsonValue, S> where S: BuildHasher + Default);
+impl_json_object!(BTreeMap<String, JsonValue>);
+
+fn json_type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "a boolean",
+        JsonValue::Number(_) => "a number",
+        JsonValue::String(_) => "a string",
+        JsonValue::Array(_) => "an array",
+        JsonValue::Object(_) => "an object",
+    }
+}
+
 impl<DB> Type<DB> for JsonRawValue
 where
     for<'a> Json<&'a Self>: Type<DB>,
@@ -44,8 +44,13 @@ pub mod chrono {
     pub use chrono::{
         DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
     };
+
+    pub use super::assume_utc::AssumeUtc;
 }
 
+#[cfg(feature = "chrono")]
+mod assume_utc;
+
 #[cfg(feature = "bit-vec")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bit-vec")))]
 #[doc(no_inline)]
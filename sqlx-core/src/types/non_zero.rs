@@ -56,7 +56,9 @@ macro_rules! impl_non_zero {
         {
             fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, crate::error::BoxDynError> {
                 let int = <$int as Decode<'r, DB>>::decode(value)?;
-                let non_zero = Self::try_from(int)?;
+                let non_zero = Self::new(int).ok_or_else(|| {
+                    format!("expected a non-zero value for `{}`, got 0", stringify!($non_zero))
+                })?;
 
                 Ok(non_zero)
             }
@@ -1,6 +1,6 @@
 use crate::database::Database;
 use crate::decode::Decode;
-use crate::error::{mismatched_types, Error};
+use crate::error::{mismatched_types, BoxDynError, Error};
 use crate::type_info::TypeInfo;
 use crate::types::Type;
 use std::borrow::Cow;
@@ -111,3 +111,99 @@ pub trait ValueRef<'r>: Sized {
     /// Returns `true` if the SQL value is `NULL`.
     fn is_null(&self) -> bool;
 }
+
+/// A value of any SQL type, converted into the closest matching Rust type.
+///
+/// This is meant for queries whose columns are not known ahead of time, e.g. to display the
+/// results of arbitrary SQL. A whole row can be read as a `HashMap<String, DynamicValue>` or,
+/// preserving the order and duplicates of columns, as a `Vec<(String, DynamicValue)>`:
+///
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+/// use std::collections::HashMap;
+/// use sqlx::DynamicValue;
+///
+/// let rows: Vec<HashMap<String, DynamicValue>> = sqlx::query_as("SELECT * FROM users")
+///     .fetch_all(pool)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Single values are converted with [`ToDynamicValue::to_dynamic()`]. Values of SQL types
+/// that have no matching variant are converted to [`Text`][Self::Text] if the driver has
+/// a text representation of them; see the documentation of each driver for details.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum DynamicValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Double(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    #[cfg(feature = "chrono")]
+    Date(chrono::NaiveDate),
+    #[cfg(feature = "chrono")]
+    Time(chrono::NaiveTime),
+    #[cfg(feature = "chrono")]
+    DateTime(chrono::NaiveDateTime),
+    #[cfg(feature = "chrono")]
+    DateTimeUtc(chrono::DateTime<chrono::Utc>),
+}
+
+impl DynamicValue {
+    /// Returns `true` if the SQL value is `NULL`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, DynamicValue::Null)
+    }
+}
+
+impl<T: Into<DynamicValue>> From<Option<T>> for DynamicValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(DynamicValue::Null, Into::into)
+    }
+}
+
+macro_rules! impl_from_for_dynamic_value {
+    ($($variant:ident($($ty:ty),+);)+) => {
+        $($(
+            impl From<$ty> for DynamicValue {
+                fn from(value: $ty) -> Self {
+                    DynamicValue::$variant(value.into())
+                }
+            }
+        )+)+
+    };
+}
+
+impl_from_for_dynamic_value!(
+    Bool(bool);
+    Int(i8, i16, i32, i64);
+    UInt(u8, u16, u32, u64);
+    Double(f32, f64);
+    Text(String, &str, Box<str>);
+    Bytes(Vec<u8>, &[u8], Box<[u8]>);
+);
+
+#[cfg(feature = "chrono")]
+impl_from_for_dynamic_value!(
+    Date(chrono::NaiveDate);
+    Time(chrono::NaiveTime);
+    DateTime(chrono::NaiveDateTime);
+    DateTimeUtc(chrono::DateTime<chrono::Utc>);
+);
+
+/// Converts a value from the database into a [`DynamicValue`], choosing the variant
+/// by its SQL type.
+///
+/// This is implemented by the [`ValueRef`] of every driver.
+pub trait ToDynamicValue {
+    /// Convert this value into a [`DynamicValue`].
+    ///
+    /// # Errors
+    /// If the value can't be decoded, or its SQL type has no matching variant and
+    /// no text representation.
+    fn to_dynamic(&self) -> Result<DynamicValue, BoxDynError>;
+}
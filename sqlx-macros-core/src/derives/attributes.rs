@@ -1,5 +1,7 @@
+use crate::rename_all::RenameAll;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote_spanned;
+
 use syn::{
     parenthesized, punctuated::Punctuated, token::Comma, Attribute, DeriveInput, Field, LitStr,
     Meta, Token, Type, Variant,
@@ -40,18 +42,6 @@ impl TypeName {
     }
 }
 
-#[derive(Copy, Clone)]
-#[allow(clippy::enum_variant_names)]
-pub enum RenameAll {
-    LowerCase,
-    SnakeCase,
-    UpperCase,
-    ScreamingSnakeCase,
-    KebabCase,
-    CamelCase,
-    PascalCase,
-}
-
 pub struct SqlxContainerAttributes {
     pub transparent: bool,
     pub type_name: Option<TypeName>,
@@ -96,16 +86,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                     meta.input.parse::<Token![=]>()?;
                     let lit: LitStr = meta.input.parse()?;
 
-                    let val = match lit.value().as_str() {
-                        "lowercase" => RenameAll::LowerCase,
-                        "snake_case" => RenameAll::SnakeCase,
-                        "UPPERCASE" => RenameAll::UpperCase,
-                        "SCREAMING_SNAKE_CASE" => RenameAll::ScreamingSnakeCase,
-                        "kebab-case" => RenameAll::KebabCase,
-                        "camelCase" => RenameAll::CamelCase,
-                        "PascalCase" => RenameAll::PascalCase,
-                        _ => fail!(lit, "unexpected value for rename_all"),
-                    };
+                    let val = RenameAll::parse(&lit)?;
 
                     try_set!(rename_all, val, lit)
                 } else if meta.path.is_ident("type_name") {
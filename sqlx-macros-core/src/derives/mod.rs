@@ -9,8 +9,7 @@ pub use encode::expand_derive_encode;
 pub use r#type::expand_derive_type;
 pub use row::expand_derive_from_row;
 
-use self::attributes::RenameAll;
-use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+pub(crate) use crate::rename_all::rename_all;
 use proc_macro2::TokenStream;
 use syn::DeriveInput;
 
@@ -23,15 +22,3 @@ pub fn expand_derive_type_encode_decode(input: &DeriveInput) -> syn::Result<Toke
 
     Ok(combined)
 }
-
-pub(crate) fn rename_all(s: &str, pattern: RenameAll) -> String {
-    match pattern {
-        RenameAll::LowerCase => s.to_lowercase(),
-        RenameAll::SnakeCase => s.to_snake_case(),
-        RenameAll::UpperCase => s.to_uppercase(),
-        RenameAll::ScreamingSnakeCase => s.to_shouty_snake_case(),
-        RenameAll::KebabCase => s.to_kebab_case(),
-        RenameAll::CamelCase => s.to_lower_camel_case(),
-        RenameAll::PascalCase => s.to_upper_camel_case(),
-    }
-}
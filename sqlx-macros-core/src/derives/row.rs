@@ -2,7 +2,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
     parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Expr, Field,
-    Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt,
+    Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt, Type, TypePath,
 };
 
 use super::{
@@ -62,6 +62,7 @@ fn expand_derive_from_row_struct(
     let predicates = &mut generics.make_where_clause().predicates;
 
     predicates.push(parse_quote!(&#lifetime ::std::primitive::str: ::sqlx::ColumnIndex<R>));
+    predicates.push(parse_quote!(::std::primitive::usize: ::sqlx::ColumnIndex<R>));
 
     let container_attributes = parse_container_attributes(&input.attrs)?;
 
@@ -74,6 +75,9 @@ fn expand_derive_from_row_struct(
         None
     };
 
+    // statements pushing the columns that are read to `__columns`, for `FromRow::columns()`
+    let mut columns: Vec<Stmt> = Vec::new();
+
     let reads: Vec<Stmt> = fields
         .iter()
         .filter_map(|field| -> Option<Stmt> {
@@ -97,23 +101,61 @@ fn expand_derive_from_row_struct(
                 }
             };
 
+            let optional = attributes.default || container_attributes.default;
+
+            let named_column = |column_ty: &Type, nullable: bool| -> Stmt {
+                parse_quote!(
+                    __columns.push(
+                        ::sqlx::check::ExpectedColumn::named::<#column_ty>(#id_s)
+                            .nullable(#nullable)
+                            .optional(#optional)
+                    );
+                )
+            };
+
+            // reads the column `id_s` into a `T`, looking up its position only once per query
+            let get = |value_ty: Type| -> Expr {
+                parse_quote!({
+                    static __ORDINAL: ::sqlx::__ColumnOrdinal = ::sqlx::__ColumnOrdinal::new();
+                    __ORDINAL.try_get::<R, #value_ty>(__row, #id_s)
+                })
+            };
+
+            let flattened_columns = |from_row_ty: &Type| -> Stmt {
+                parse_quote!(
+                    __columns.extend(
+                        <#from_row_ty as ::sqlx::FromRow<#lifetime, R>>::columns()?
+                            .into_iter()
+                            .map(|column| {
+                                let optional = column.is_optional() || #optional;
+                                column.optional(optional)
+                            })
+                    );
+                )
+            };
+
             let expr: Expr = match (attributes.flatten, attributes.try_from, attributes.json) {
                 // <No attributes>
                 (false, None, None) => {
                     predicates
                         .push(parse_quote!(#ty: ::sqlx::decode::Decode<#lifetime, R::Database>));
                     predicates.push(parse_quote!(#ty: ::sqlx::types::Type<R::Database>));
+                    columns.push(named_column(ty, is_option(ty)));
 
-                    parse_quote!(__row.try_get(#id_s))
+                    get(parse_quote!(_))
                 }
                 // Flatten
                 (true, None, None) => {
                     predicates.push(parse_quote!(#ty: ::sqlx::FromRow<#lifetime, R>));
+                    columns.push(flattened_columns(ty));
+
                     parse_quote!(<#ty as ::sqlx::FromRow<#lifetime, R>>::from_row(__row))
                 }
                 // Flatten + Try from
                 (true, Some(try_from), None) => {
                     predicates.push(parse_quote!(#try_from: ::sqlx::FromRow<#lifetime, R>));
+                    columns.push(flattened_columns(&try_from));
+
                     parse_quote!(
                         <#try_from as ::sqlx::FromRow<#lifetime, R>>::from_row(__row)
                             .and_then(|v| {
@@ -137,10 +179,13 @@ fn expand_derive_from_row_struct(
                 (false, Some(try_from), None) => {
                     predicates
                         .push(parse_quote!(#try_from: ::sqlx::decode::Decode<#lifetime, R::Database>));
-                    predicates.push(parse_quote!(#try_from: ::sqlx::types::Type<R::Database>)); 
+                    predicates.push(parse_quote!(#try_from: ::sqlx::types::Type<R::Database>));
+                    columns.push(named_column(&try_from, is_option(&try_from)));
+
+                    let get = get(parse_quote!(_));
 
                     parse_quote!(
-                        __row.try_get(#id_s)
+                        #get
                             .and_then(|v| {
                                 <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v)
                                     .map_err(|e| {
@@ -159,9 +204,12 @@ fn expand_derive_from_row_struct(
                     predicates
                         .push(parse_quote!(::sqlx::types::Json<#try_from>: ::sqlx::decode::Decode<#lifetime, R::Database>));
                     predicates.push(parse_quote!(::sqlx::types::Json<#try_from>: ::sqlx::types::Type<R::Database>));
+                    columns.push(named_column(&parse_quote!(::sqlx::types::Json<#try_from>), false));
+
+                    let get = get(parse_quote!(::sqlx::types::Json<_>));
 
                     parse_quote!(
-                        __row.try_get::<::sqlx::types::Json<_>, _>(#id_s)
+                        #get
                             .and_then(|v| {
                                 <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v.0)
                                     .map_err(|e| {
@@ -184,15 +232,21 @@ fn expand_derive_from_row_struct(
                     predicates
                         .push(parse_quote!(::sqlx::types::Json<#ty>: ::sqlx::decode::Decode<#lifetime, R::Database>));
                     predicates.push(parse_quote!(::sqlx::types::Json<#ty>: ::sqlx::types::Type<R::Database>));
+                    columns.push(named_column(&parse_quote!(::sqlx::types::Json<#ty>), false));
 
-                    parse_quote!(__row.try_get::<::sqlx::types::Json<_>, _>(#id_s).map(|x| x.0))
+                    let get = get(parse_quote!(::sqlx::types::Json<_>));
+
+                    parse_quote!(#get.map(|x| x.0))
                 },
                 (false, None, Some(JsonAttribute::Nullable)) => {
                     predicates
                         .push(parse_quote!(::core::option::Option<::sqlx::types::Json<#ty>>: ::sqlx::decode::Decode<#lifetime, R::Database>));
                     predicates.push(parse_quote!(::core::option::Option<::sqlx::types::Json<#ty>>: ::sqlx::types::Type<R::Database>));
+                    columns.push(named_column(&parse_quote!(::core::option::Option<::sqlx::types::Json<#ty>>), true));
+
+                    let get = get(parse_quote!(::core::option::Option<::sqlx::types::Json<_>>));
 
-                    parse_quote!(__row.try_get::<::core::option::Option<::sqlx::types::Json<_>>, _>(#id_s).map(|x| x.and_then(|y| y.0)))
+                    parse_quote!(#get.map(|x| x.and_then(|y| y.0)))
                 },
             };
 
@@ -238,6 +292,15 @@ fn expand_derive_from_row_struct(
                     #(#names),*
                 })
             }
+
+            fn columns() -> ::std::option::Option<::std::vec::Vec<::sqlx::check::ExpectedColumn<R::Database>>> {
+                #[allow(unused_mut)]
+                let mut __columns = ::std::vec::Vec::new();
+
+                #(#columns)*
+
+                ::std::option::Option::Some(__columns)
+            }
         }
     ))
 }
@@ -285,6 +348,13 @@ fn expand_derive_from_row_struct_unnamed(
         .enumerate()
         .map(|(idx, _)| quote!(row.try_get(#idx)?));
 
+    let columns = fields.iter().enumerate().map(|(idx, field)| {
+        let ty = &field.ty;
+        let nullable = is_option(ty);
+
+        quote!(::sqlx::check::ExpectedColumn::indexed::<#ty>(#idx).nullable(#nullable))
+    });
+
     Ok(quote!(
         #[automatically_derived]
         impl #impl_generics ::sqlx::FromRow<#lifetime, R> for #ident #ty_generics #where_clause {
@@ -293,6 +363,21 @@ fn expand_derive_from_row_struct_unnamed(
                     #(#gets),*
                 ))
             }
+
+            fn columns() -> ::std::option::Option<::std::vec::Vec<::sqlx::check::ExpectedColumn<R::Database>>> {
+                ::std::option::Option::Some(::std::vec![#(#columns),*])
+            }
         }
     ))
 }
+
+/// Whether `ty` is an `Option`, going by its name.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
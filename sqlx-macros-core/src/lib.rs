@@ -28,6 +28,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 mod common;
 mod database;
+#[cfg(any(feature = "derive", feature = "macros"))]
+mod rename_all;
 
 #[cfg(feature = "derive")]
 pub mod derives;
@@ -4,6 +4,7 @@ use either::Either;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, quote_spanned};
 use sqlx_core::describe::Describe;
+use sqlx_core::type_checking::ParamChecking;
 use syn::spanned::Spanned;
 use syn::{Expr, ExprCast, ExprGroup, Type};
 
@@ -55,6 +56,15 @@ pub fn quote_args<DB: DatabaseExt>(
                         return Ok(quote!());
                     }
 
+                    if DB::PARAM_CHECKING == ParamChecking::Weak {
+                        return Ok(quote_weak_param_check(
+                            DB::param_type_for_id(param_ty),
+                            name,
+                            expr,
+                            i + 1,
+                        ));
+                    }
+
                     let param_ty =
                         DB::param_type_for_id(param_ty)
                             .ok_or_else(|| {
@@ -115,6 +125,43 @@ pub fn quote_args<DB: DatabaseExt>(
     })
 }
 
+/// The database converts arguments to the types of their parameters itself, so the expected type
+/// is only a hint; all we check is that text isn't bound to a numeric parameter.
+///
+/// MySQL reports every parameter as text before 8.0.22, in which case nothing is checked.
+fn quote_weak_param_check(
+    param_ty: Option<&str>,
+    name: &syn::Ident,
+    expr: &Expr,
+    param_no: usize,
+) -> TokenStream {
+    const NUMERIC_TYPES: &[&str] = &[
+        "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64",
+    ];
+
+    if !param_ty.is_some_and(|ty| NUMERIC_TYPES.contains(&ty)) {
+        return TokenStream::new();
+    }
+
+    quote_spanned!(expr.span() =>
+        // this shouldn't actually run
+        #[allow(clippy::missing_panics_doc, clippy::unreachable)]
+        if false {
+            use ::sqlx::ty_match::CheckNumericExt as _;
+
+            // evaluate the expression only once in case it contains moves
+            let expr = ::sqlx::ty_match::dupe_value(#name);
+
+            ::sqlx::ty_match::numeric_param::<#param_no, _>(
+                ::sqlx::ty_match::CheckNumeric::new(&expr).check_numeric(),
+            );
+
+            // this causes move-analysis to effectively ignore this block
+            ::std::unreachable!();
+        }
+    )
+}
+
 fn get_type_override(expr: &Expr) -> Option<&Type> {
     match expr {
         Expr::Group(group) => get_type_override(&group.expr),
@@ -150,10 +150,14 @@ where
         }
     }
 
-    pub(super) fn save_in(&self, dir: impl AsRef<Path>) -> crate::Result<()> {
+    pub(super) fn save_in(
+        &self,
+        dir: impl AsRef<Path>,
+        database: Option<&str>,
+    ) -> crate::Result<()> {
         use std::io::ErrorKind;
 
-        let path = dir.as_ref().join(format!("query-{}.json", self.hash));
+        let path = dir.as_ref().join(data_file_name(database, &self.hash));
         match std::fs::remove_file(&path) {
             Ok(()) => {}
             Err(err)
@@ -190,6 +194,15 @@ where
     }
 }
 
+/// The name of the file the data of a query is saved in, given the `hash_string()` of the query
+/// and the name of the database it's checked against, if any.
+pub(super) fn data_file_name(database: Option<&str>, hash: &str) -> String {
+    match database {
+        Some(database) => format!("query-{database}-{hash}.json"),
+        None => format!("query-{hash}.json"),
+    }
+}
+
 pub(super) fn hash_string(query: &str) -> String {
     // picked `sha2` because it's already in the dependency tree for both MySQL and Postgres
     use sha2::{Digest, Sha256};
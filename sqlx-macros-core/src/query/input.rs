@@ -4,7 +4,9 @@ use proc_macro2::{Ident, Span};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::{Expr, LitBool, LitStr, Token};
-use syn::{ExprArray, Type};
+use syn::{ExprArray, GenericArgument, PathArguments, Type, TypePath};
+
+use crate::rename_all::RenameAll;
 
 /// Macro input shared by `query!()` and `query_file!()`
 pub struct QueryMacroInput {
@@ -19,6 +21,14 @@ pub struct QueryMacroInput {
     pub(super) checked: bool,
 
     pub(super) file_path: Option<String>,
+
+    /// The name given with `database = "<name>"`, to check the query against
+    /// `DATABASE_URL_<NAME>` instead of `DATABASE_URL`.
+    pub(super) database: Option<String>,
+
+    /// The case given with `rename_all = ".."`, which the column names are in
+    /// while the fields of the record are in `snake_case`.
+    pub(super) rename_all: Option<RenameAll>,
 }
 
 enum QuerySrc {
@@ -28,6 +38,8 @@ enum QuerySrc {
 
 pub enum RecordType {
     Given(Type),
+    /// `record = Option<T>`: a row where every column is `NULL` maps to `None`.
+    OptionalGiven(Type),
     Scalar,
     Generated,
 }
@@ -38,6 +50,8 @@ impl Parse for QueryMacroInput {
         let mut args: Option<Vec<Expr>> = None;
         let mut record_type = RecordType::Generated;
         let mut checked = true;
+        let mut database = None;
+        let mut rename_all = None;
 
         let mut expect_comma = false;
 
@@ -68,7 +82,12 @@ impl Parse for QueryMacroInput {
                     return Err(input.error("colliding `scalar` or `record` key"));
                 }
 
-                record_type = RecordType::Given(input.parse()?);
+                let ty: Type = input.parse()?;
+
+                record_type = match option_inner_type(&ty) {
+                    Some(inner) => RecordType::OptionalGiven(inner.clone()),
+                    None => RecordType::Given(ty),
+                };
             } else if key == "scalar" {
                 if !matches!(record_type, RecordType::Generated) {
                     return Err(input.error("colliding `scalar` or `record` key"));
@@ -82,6 +101,25 @@ impl Parse for QueryMacroInput {
             } else if key == "checked" {
                 let lit_bool = input.parse::<LitBool>()?;
                 checked = lit_bool.value;
+            } else if key == "database" {
+                let lit_str = input.parse::<LitStr>()?;
+                let name = lit_str.value();
+
+                if name.is_empty()
+                    || !name
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+                {
+                    return Err(syn::Error::new_spanned(
+                        lit_str,
+                        "database names may only contain ASCII letters, digits, `_` and `-`",
+                    ));
+                }
+
+                database = Some(name);
+            } else if key == "rename_all" {
+                let lit_str = input.parse::<LitStr>()?;
+                rename_all = Some(RenameAll::parse(&lit_str)?);
             } else {
                 let message = format!("unexpected input key: {key}");
                 return Err(syn::Error::new_spanned(key, message));
@@ -93,6 +131,11 @@ impl Parse for QueryMacroInput {
         let (src, src_span) =
             query_src.ok_or_else(|| input.error("expected `source` or `source_file` key"))?;
 
+        if rename_all.is_some() && matches!(record_type, RecordType::Generated | RecordType::Scalar)
+        {
+            return Err(input.error("`rename_all` requires a `record` key"));
+        }
+
         let arg_exprs = args.unwrap_or_default();
 
         let file_path = src.file_path(src_span)?;
@@ -104,6 +147,8 @@ impl Parse for QueryMacroInput {
             arg_exprs,
             checked,
             file_path,
+            database,
+            rename_all,
         })
     }
 }
@@ -139,6 +184,33 @@ impl QuerySrc {
     }
 }
 
+/// If `ty` is `Option<T>` (or `std::option::Option<T>`, etc.), return `T`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    // a `$ty:path` forwarded by `macro_rules!` arrives wrapped in an invisible group
+    if let Type::Group(group) = ty {
+        return option_inner_type(&group.elem);
+    }
+
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+
+    let segment = path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.iter().collect::<Vec<_>>()[..] {
+        [GenericArgument::Type(inner)] => Some(inner),
+        _ => None,
+    }
+}
+
 fn read_file_src(source: &str, source_span: Span) -> syn::Result<String> {
     let file_path = crate::common::resolve_path(source, source_span)?;
 
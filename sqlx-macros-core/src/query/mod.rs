@@ -12,7 +12,7 @@ use sqlx_core::database::Database;
 use sqlx_core::{column::Column, describe::Describe, type_info::TypeInfo};
 
 use crate::database::DatabaseExt;
-use crate::query::data::{hash_string, DynQueryData, QueryData};
+use crate::query::data::{data_file_name, hash_string, DynQueryData, QueryData};
 use crate::query::input::RecordType;
 use either::Either;
 use url::Url;
@@ -104,6 +104,19 @@ impl Metadata {
         }
         root.clone().unwrap()
     }
+
+    /// The URL of the database named with `database = "<name>"`, from `DATABASE_URL_<NAME>`.
+    fn named_database_url(&self, name: &str) -> Option<String> {
+        env(&database_url_var(name)).ok()
+    }
+}
+
+/// The environment variable holding the URL of the database named `name`.
+fn database_url_var(name: &str) -> String {
+    format!(
+        "DATABASE_URL_{}",
+        name.to_ascii_uppercase().replace('-', "_")
+    )
 }
 
 // If we are in a workspace, lookup `workspace_root` since `CARGO_MANIFEST_DIR` won't
@@ -153,16 +166,44 @@ pub fn expand_input<'a>(
     input: QueryMacroInput,
     drivers: impl IntoIterator<Item = &'a QueryDriver>,
 ) -> crate::Result<TokenStream> {
-    let data_source = match &*METADATA {
-        Metadata {
-            offline: false,
-            database_url: Some(db_url),
-            ..
-        } => QueryDataSource::live(db_url)?,
+    let Some(name) = input.database.clone() else {
+        return expand_input_with(input, drivers);
+    };
+
+    expand_input_with(input, drivers).map_err(|e| {
+        let context = format!("error checking query against database {name:?}");
+
+        // keep the spans of errors pointing at the macro input
+        match e.downcast::<syn::Error>() {
+            Ok(e) => e
+                .into_iter()
+                .map(|e| syn::Error::new(e.span(), format!("{context}: {e}")))
+                .reduce(|mut errors, e| {
+                    errors.combine(e);
+                    errors
+                })
+                .expect("BUG: syn::Error without any errors")
+                .into(),
+            Err(e) => format!("{context}: {e}").into(),
+        }
+    })
+}
 
-        Metadata { offline, .. } => {
+fn expand_input_with<'a>(
+    input: QueryMacroInput,
+    drivers: impl IntoIterator<Item = &'a QueryDriver>,
+) -> crate::Result<TokenStream> {
+    let database_url = match &input.database {
+        Some(name) => METADATA.named_database_url(name),
+        None => METADATA.database_url.clone(),
+    };
+
+    let data_source = match &database_url {
+        Some(db_url) if !METADATA.offline => QueryDataSource::live(db_url)?,
+
+        _ => {
             // Try load the cached query metadata file.
-            let filename = format!("query-{}.json", hash_string(&input.sql));
+            let filename = data_file_name(input.database.as_deref(), &hash_string(&input.sql));
 
             // Check SQLX_OFFLINE_DIR, then local .sqlx, then workspace .sqlx.
             let dirs = [
@@ -177,10 +218,15 @@ pub fn expand_input<'a>(
                 .find(|path| path.exists())
             else {
                 return Err(
-                    if *offline {
-                        "`SQLX_OFFLINE=true` but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or unset `SQLX_OFFLINE`"
+                    if METADATA.offline {
+                        "`SQLX_OFFLINE=true` but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or unset `SQLX_OFFLINE`".into()
                     } else {
-                        "set `DATABASE_URL` to use query macros online, or run `cargo sqlx prepare` to update the query cache"
+                        let url_var = match &input.database {
+                            Some(name) => database_url_var(name),
+                            None => "DATABASE_URL".into(),
+                        };
+
+                        format!("set `{url_var}` to use query macros online, or run `cargo sqlx prepare` to update the query cache")
                     }.into()
                 );
             };
@@ -281,7 +327,7 @@ where
     } else {
         match input.record_type {
             RecordType::Generated => {
-                let columns = output::columns_to_rust::<DB>(&data.describe)?;
+                let columns = output::columns_to_rust::<DB>(&data.describe, None)?;
 
                 let record_name: Type = syn::parse_str("Record").unwrap();
 
@@ -312,14 +358,20 @@ where
                     &record_name,
                     &query_args,
                     &columns,
+                    false,
                 ));
 
                 record_tokens
             }
             RecordType::Given(ref out_ty) => {
-                let columns = output::columns_to_rust::<DB>(&data.describe)?;
+                let columns = output::columns_to_rust::<DB>(&data.describe, input.rename_all)?;
+
+                output::quote_query_as::<DB>(&input, out_ty, &query_args, &columns, false)
+            }
+            RecordType::OptionalGiven(ref out_ty) => {
+                let columns = output::columns_to_rust::<DB>(&data.describe, input.rename_all)?;
 
-                output::quote_query_as::<DB>(&input, out_ty, &query_args, &columns)
+                output::quote_query_as::<DB>(&input, out_ty, &query_args, &columns, true)
             }
             RecordType::Scalar => {
                 output::quote_query_scalar::<DB>(&input, &query_args, &data.describe)?
@@ -366,7 +418,7 @@ where
                     }
 
                     // .sqlx exists and is a directory, store data.
-                    data.save_in(path)?;
+                    data.save_in(path, input.database.as_deref())?;
                 }
             }
         }
@@ -6,6 +6,7 @@ use sqlx_core::column::Column;
 use sqlx_core::describe::Describe;
 
 use crate::database::DatabaseExt;
+use crate::rename_all::{rename_all, RenameAll};
 
 use crate::query::QueryMacroInput;
 use sqlx_core::type_checking::TypeChecking;
@@ -76,17 +77,24 @@ impl Display for DisplayColumn<'_> {
     }
 }
 
-pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Result<Vec<RustColumn>> {
+pub fn columns_to_rust<DB: DatabaseExt>(
+    describe: &Describe<DB>,
+    rename_all: Option<RenameAll>,
+) -> crate::Result<Vec<RustColumn>> {
     (0..describe.columns().len())
-        .map(|i| column_to_rust(describe, i))
+        .map(|i| column_to_rust(describe, i, rename_all))
         .collect::<crate::Result<Vec<_>>>()
 }
 
-fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::Result<RustColumn> {
+fn column_to_rust<DB: DatabaseExt>(
+    describe: &Describe<DB>,
+    i: usize,
+    rename_all: Option<RenameAll>,
+) -> crate::Result<RustColumn> {
     let column = &describe.columns()[i];
 
     // add raw prefix to all identifiers
-    let decl = ColumnDecl::parse(column.name())
+    let decl = ColumnDecl::parse(column.name(), rename_all)
         .map_err(|e| format!("column name {:?} is invalid: {}", column.name(), e))?;
 
     let ColumnOverride { nullability, type_ } = decl.r#override;
@@ -129,6 +137,7 @@ pub fn quote_query_as<DB: DatabaseExt>(
     out_ty: &Type,
     bind_args: &Ident,
     columns: &[RustColumn],
+    optional: bool,
 ) -> TokenStream {
     let instantiations = columns.iter().enumerate().map(
         |(
@@ -180,13 +189,43 @@ pub fn quote_query_as<DB: DatabaseExt>(
         quote! { #sql }
     };
 
+    let record = quote! { #out_ty { #(#ident: #var_name),* } };
+
+    if optional {
+        // without columns, there is nothing that could be `NULL`
+        let none_if_all_null = (!columns.is_empty()).then(|| {
+            let index = 0..columns.len();
+
+            quote! {
+                use ::sqlx::ValueRef as _;
+
+                // e.g. the unmatched side of a `LEFT JOIN`
+                if #(row.try_get_raw(#index)?.is_null())&&* {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+            }
+        });
+
+        return quote! {
+            ::sqlx::__query_with_result::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
+                use ::sqlx::Row as _;
+
+                #none_if_all_null
+
+                #(#instantiations)*
+
+                ::std::result::Result::Ok(::std::option::Option::Some(#record))
+            })
+        };
+    }
+
     quote! {
         ::sqlx::__query_with_result::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
             use ::sqlx::Row as _;
 
             #(#instantiations)*
 
-            ::std::result::Result::Ok(#out_ty { #(#ident: #var_name),* })
+            ::std::result::Result::Ok(#record)
         })
     }
 }
@@ -207,7 +246,7 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
     }
 
     // attempt to parse a column override, otherwise fall back to the inferred type of the column
-    let ty = if let Ok(rust_col) = column_to_rust(describe, 0) {
+    let ty = if let Ok(rust_col) = column_to_rust(describe, 0, None) {
         rust_col.type_.to_token_stream()
     } else if input.checked {
         let ty = get_column_type::<DB>(0, &columns[0]);
@@ -261,16 +300,18 @@ fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStrea
 }
 
 impl ColumnDecl {
-    fn parse(col_name: &str) -> crate::Result<Self> {
+    fn parse(col_name: &str, rename_all: Option<RenameAll>) -> crate::Result<Self> {
         // find the end of the identifier because we want to use our own logic to parse it
         // if we tried to feed this into `syn::parse_str()` we might get an un-great error
         // for some kinds of invalid identifiers
-        let (ident, remainder) = if let Some(i) = col_name.find(&[':', '!', '?'][..]) {
-            let (ident, remainder) = col_name.split_at(i);
+        let (name, remainder) = match col_name.find(&[':', '!', '?'][..]) {
+            Some(i) => col_name.split_at(i),
+            None => (col_name, ""),
+        };
 
-            (parse_ident(ident)?, remainder)
-        } else {
-            (parse_ident(col_name)?, "")
+        let ident = match rename_all {
+            Some(pattern) => parse_ident(&field_name(name, pattern)?)?,
+            None => parse_ident(name)?,
         };
 
         Ok(ColumnDecl {
@@ -321,6 +362,23 @@ impl Parse for ColumnOverride {
     }
 }
 
+/// Returns the `snake_case` name of the field that `#[sqlx(rename_all = "..")]` would map
+/// to the column `name`, so that `query_as!()` agrees with `#[derive(FromRow)]`.
+fn field_name(name: &str, pattern: RenameAll) -> crate::Result<String> {
+    let field = rename_all(name, RenameAll::SnakeCase);
+    let renamed = rename_all(&field, pattern);
+
+    if renamed != name {
+        return Err(format!(
+            "{name:?} is not in {}; the field `{field}` would be renamed to {renamed:?}",
+            pattern.name()
+        )
+        .into());
+    }
+
+    Ok(field)
+}
+
 fn parse_ident(name: &str) -> crate::Result<Ident> {
     // workaround for the following issue (it's semi-fixed but still spits out extra diagnostics)
     // https://github.com/dtolnay/syn/issues/749#issuecomment-575451318
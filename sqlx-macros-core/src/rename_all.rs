@@ -0,0 +1,57 @@
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use syn::LitStr;
+
+/// The case given with `rename_all = ".."`, both to `#[sqlx(..)]` and to `query_as!()`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
+pub enum RenameAll {
+    LowerCase,
+    SnakeCase,
+    UpperCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    CamelCase,
+    PascalCase,
+}
+
+impl RenameAll {
+    const NAMES: [(&'static str, RenameAll); 7] = [
+        ("lowercase", RenameAll::LowerCase),
+        ("snake_case", RenameAll::SnakeCase),
+        ("UPPERCASE", RenameAll::UpperCase),
+        ("SCREAMING_SNAKE_CASE", RenameAll::ScreamingSnakeCase),
+        ("kebab-case", RenameAll::KebabCase),
+        ("camelCase", RenameAll::CamelCase),
+        ("PascalCase", RenameAll::PascalCase),
+    ];
+
+    pub(crate) fn parse(lit: &LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+
+        Self::NAMES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|&(_, pattern)| pattern)
+            .ok_or_else(|| syn::Error::new_spanned(lit, "unexpected value for rename_all"))
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        Self::NAMES
+            .iter()
+            .find(|&&(_, pattern)| pattern == self)
+            .map(|&(name, _)| name)
+            .expect("every pattern has a name")
+    }
+}
+
+pub(crate) fn rename_all(s: &str, pattern: RenameAll) -> String {
+    match pattern {
+        RenameAll::LowerCase => s.to_lowercase(),
+        RenameAll::SnakeCase => s.to_snake_case(),
+        RenameAll::UpperCase => s.to_uppercase(),
+        RenameAll::ScreamingSnakeCase => s.to_shouty_snake_case(),
+        RenameAll::KebabCase => s.to_kebab_case(),
+        RenameAll::CamelCase => s.to_lower_camel_case(),
+        RenameAll::PascalCase => s.to_upper_camel_case(),
+    }
+}
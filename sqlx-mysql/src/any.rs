@@ -15,6 +15,7 @@ use sqlx_core::connection::Connection;
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
+use sqlx_core::placeholders::PlaceholderSyntax;
 use sqlx_core::transaction::TransactionManager;
 use std::{future, pin::pin};
 
@@ -25,6 +26,10 @@ impl AnyConnectionBackend for MySqlConnection {
         <MySql as Database>::NAME
     }
 
+    fn placeholder_syntax(&self) -> PlaceholderSyntax {
+        <MySql as Database>::PLACEHOLDER_SYNTAX
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -37,6 +42,10 @@ impl AnyConnectionBackend for MySqlConnection {
         Connection::ping(self)
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self)
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         MySqlTransactionManager::begin(self)
     }
@@ -65,6 +74,10 @@ impl AnyConnectionBackend for MySqlConnection {
         Connection::should_flush(self)
     }
 
+    fn take_fatal_error(&mut self) -> Option<sqlx_core::Error> {
+        Connection::take_fatal_error(self)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -87,7 +100,7 @@ impl AnyConnectionBackend for MySqlConnection {
         };
 
         Box::pin(
-            self.run(query, arguments, persistent)
+            self.run(query, arguments, persistent, true)
                 .try_flatten_stream()
                 .map(|res| {
                     Ok(match res? {
@@ -113,7 +126,7 @@ impl AnyConnectionBackend for MySqlConnection {
 
         Box::pin(async move {
             let arguments = arguments?;
-            let mut stream = pin!(self.run(query, arguments, persistent).await?);
+            let mut stream = pin!(self.run(query, arguments, persistent, true).await?);
 
             while let Some(result) = stream.try_next().await? {
                 if let Either::Right(row) = result {
@@ -142,7 +155,14 @@ impl AnyConnectionBackend for MySqlConnection {
 
     fn describe<'q>(&'q mut self, sql: &'q str) -> BoxFuture<'q, sqlx_core::Result<Describe<Any>>> {
         Box::pin(async move {
-            let describe = Executor::describe(self, sql).await?;
+            let mut describe = Executor::describe(self, sql).await?;
+
+            // the types the server reports for parameters are only hints,
+            // and may not be supported by `Any`
+            describe.parameters = describe
+                .parameters
+                .map(|parameters| Either::Right(parameters.either(|types| types.len(), |n| n)));
+
             describe.try_into_any()
         })
     }
@@ -210,10 +230,5 @@ impl<'a> TryFrom<&'a AnyConnectOptions> for MySqlConnectOptions {
 }
 
 fn map_result(result: MySqlQueryResult) -> AnyQueryResult {
-    AnyQueryResult {
-        rows_affected: result.rows_affected,
-        // Don't expect this to be a problem
-        #[allow(clippy::cast_possible_wrap)]
-        last_insert_id: Some(result.last_insert_id as i64),
-    }
+    result.into()
 }
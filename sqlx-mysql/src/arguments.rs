@@ -1,8 +1,12 @@
+use crate::collation::CharSet;
 use crate::encode::{Encode, IsNull};
+use crate::protocol::text::{ColumnFlags, ColumnType};
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo};
 pub(crate) use sqlx_core::arguments::*;
+use sqlx_core::arguments::{ArgumentSummary, ArgumentsSummary};
 use sqlx_core::error::BoxDynError;
+use sqlx_core::type_info::TypeInfo;
 use std::ops::Deref;
 
 /// Implementation of [`Arguments`] for MySQL.
@@ -11,6 +15,8 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: NullBitMap,
+    // offset of each value in `values`, for `summary()`
+    offsets: Vec<usize>,
 }
 
 impl MySqlArguments {
@@ -32,9 +38,74 @@ impl MySqlArguments {
 
         self.types.push(ty);
         self.null_bitmap.push(is_null);
+        self.offsets.push(value_length_before_encoding);
 
         Ok(())
     }
+
+    /// The encoded value of the argument at `index`.
+    fn value(&self, index: usize) -> &[u8] {
+        let end = self
+            .offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.values.len());
+
+        &self.values[self.offsets[index]..end]
+    }
+
+    /// Check that the text arguments only contain characters that `charset` can represent,
+    /// which the server would replace or garble otherwise.
+    pub(crate) fn check_charset(&self, charset: CharSet) -> Result<(), BoxDynError> {
+        // every character can be sent with `utf8mb4`, the default
+        if charset.can_encode(char::MAX) {
+            return Ok(());
+        }
+
+        for (i, ty) in self.types.iter().enumerate() {
+            if self.null_bitmap.is_null(i) || !is_text(ty) {
+                continue;
+            }
+
+            let value = self.value(i);
+
+            let Some(&first) = value.first() else {
+                continue;
+            };
+
+            let Ok(text) = std::str::from_utf8(&value[lenenc_prefix_len(first)..]) else {
+                continue;
+            };
+
+            if let Some(c) = text.chars().find(|&c| !charset.can_encode(c)) {
+                return Err(format!(
+                    "argument #{} contains {c:?}, which the character set of the connection \
+                     ({}) cannot represent",
+                    i + 1,
+                    charset.as_str()
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_text(ty: &MySqlTypeInfo) -> bool {
+    matches!(
+        ty.r#type,
+        ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::String
+            | ColumnType::Blob
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Enum
+            | ColumnType::Set
+            | ColumnType::Json
+    ) && !ty.flags.contains(ColumnFlags::BINARY)
 }
 
 impl<'q> Arguments<'q> for MySqlArguments {
@@ -55,6 +126,60 @@ impl<'q> Arguments<'q> for MySqlArguments {
     fn len(&self) -> usize {
         self.types.len()
     }
+
+    fn summary(&self) -> ArgumentsSummary {
+        let arguments = self
+            .types
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| {
+                let value = self.value(i);
+
+                let size = if self.null_bitmap.is_null(i) {
+                    None
+                } else if value.is_empty() {
+                    Some(0)
+                } else {
+                    // skip the length prefix, as in `BinaryRow::decode_with()`
+                    Some(match ty.r#type {
+                        ColumnType::Time
+                        | ColumnType::Timestamp
+                        | ColumnType::Date
+                        | ColumnType::Datetime => value.len() - 1,
+
+                        ColumnType::LongLong
+                        | ColumnType::Long
+                        | ColumnType::Int24
+                        | ColumnType::Short
+                        | ColumnType::Year
+                        | ColumnType::Tiny
+                        | ColumnType::Float
+                        | ColumnType::Double
+                        | ColumnType::Null => value.len(),
+
+                        _ => value.len() - lenenc_prefix_len(value[0]),
+                    })
+                };
+
+                ArgumentSummary {
+                    type_name: ty.name().to_owned(),
+                    size,
+                }
+            })
+            .collect();
+
+        ArgumentsSummary::new::<MySql>(arguments)
+    }
+}
+
+// <https://dev.mysql.com/doc/internals/en/integer.html#packet-Protocol::LengthEncodedInteger>
+fn lenenc_prefix_len(first: u8) -> usize {
+    match first {
+        0xfc => 3,
+        0xfd => 4,
+        0xfe => 9,
+        _ => 1,
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -75,6 +200,13 @@ impl NullBitMap {
         self.bytes[byte_index] |= u8::from(is_null.is_null()) << bit_offset;
         self.length += 1;
     }
+
+    fn is_null(&self, index: usize) -> bool {
+        let byte_index = index / (u8::BITS as usize);
+        let bit_offset = index % (u8::BITS as usize);
+
+        self.bytes[byte_index] & (1 << bit_offset) != 0
+    }
 }
 
 impl Deref for NullBitMap {
@@ -105,4 +237,44 @@ mod test {
 
         assert_eq!([0b01010101, 0b1].as_slice(), bit_map.deref());
     }
+
+    #[test]
+    fn summary_should_skip_length_prefixes() {
+        let mut args = MySqlArguments::default();
+
+        args.add(1_i64).unwrap();
+        args.add("hello world").unwrap();
+        args.add(None::<&str>).unwrap();
+        args.add("x".repeat(300)).unwrap();
+
+        assert_eq!(
+            args.summary().to_string(),
+            "[?: BIGINT (8 bytes), ?: VARCHAR (11 bytes), ?: VARCHAR NULL, ?: VARCHAR (300 bytes)]"
+        );
+    }
+
+    #[test]
+    fn check_charset_should_reject_unrepresentable_text() {
+        let mut args = MySqlArguments::default();
+
+        args.add("caf\u{e9}").unwrap();
+        args.add(&b"\xF0\x9F\x98\x80"[..]).unwrap();
+        args.add(None::<&str>).unwrap();
+
+        assert!(args.check_charset(CharSet::utf8mb4).is_ok());
+        assert!(args.check_charset(CharSet::utf8).is_ok());
+
+        let err = args.check_charset(CharSet::latin1).unwrap_err();
+        assert!(
+            err.to_string().starts_with("argument #1 contains '\u{e9}'"),
+            "{err}"
+        );
+
+        args.add("\u{1F600}").unwrap();
+
+        assert!(args.check_charset(CharSet::utf8mb4).is_ok());
+
+        let err = args.check_charset(CharSet::utf8).unwrap_err();
+        assert!(err.to_string().starts_with("argument #4"), "{err}");
+    }
 }
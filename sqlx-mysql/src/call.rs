@@ -0,0 +1,244 @@
+use std::fmt::Write;
+use std::pin::pin;
+
+use either::Either;
+use futures_util::TryStreamExt;
+
+use crate::encode::Encode;
+use crate::error::{BoxDynError, Error};
+use crate::executor::Executor;
+use crate::types::Type;
+use crate::{MySql, MySqlArguments, MySqlConnection, MySqlRow};
+
+impl MySqlConnection {
+    /// Prepare a `CALL` of the stored procedure `procedure`.
+    ///
+    /// Parameters are passed in the order they are added with [`MySqlCall::bind()`] and
+    /// [`MySqlCall::out_param()`], so they must match the order in the procedure's signature.
+    ///
+    /// `OUT` and `INOUT` parameters are passed as session variables, which are read back
+    /// with a `SELECT` after the procedure returns.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::MySqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::Row;
+    ///
+    /// // CREATE PROCEDURE count_users(IN min_age INT, OUT total BIGINT) ...
+    /// let res = conn
+    ///     .call("count_users")
+    ///     .bind(18)
+    ///     .out_param("@total")
+    ///     .execute()
+    ///     .await?;
+    ///
+    /// let total: i64 = res.out_params().unwrap().try_get("@total")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn call(&mut self, procedure: &str) -> MySqlCall<'_> {
+        MySqlCall {
+            conn: self,
+            procedure: procedure.to_owned(),
+            params: Vec::new(),
+            arguments: Ok(MySqlArguments::default()),
+        }
+    }
+}
+
+/// A `CALL` of a stored procedure, created by [`MySqlConnection::call()`].
+#[must_use = "a stored procedure is not called until `.execute()` is awaited"]
+pub struct MySqlCall<'c> {
+    conn: &'c mut MySqlConnection,
+    procedure: String,
+    params: Vec<Param>,
+    arguments: Result<MySqlArguments, BoxDynError>,
+}
+
+enum Param {
+    In,
+    Out(String),
+}
+
+/// The results of a stored procedure call, returned by [`MySqlCall::execute()`].
+#[derive(Debug, Default)]
+pub struct MySqlCallResult {
+    result_sets: Vec<Vec<MySqlRow>>,
+    rows_affected: u64,
+    out_params: Option<MySqlRow>,
+}
+
+impl<'c> MySqlCall<'c> {
+    /// Bind a value for the next `IN` parameter of the procedure.
+    pub fn bind<'q, T>(mut self, value: T) -> Self
+    where
+        T: 'q + Encode<'q, MySql> + Type<MySql>,
+    {
+        if let Ok(arguments) = &mut self.arguments {
+            if let Err(error) = arguments.add(value) {
+                self.arguments = Err(format!(
+                    "encoding a value for parameter {} of procedure {}: {error}",
+                    self.params.len() + 1,
+                    self.procedure
+                )
+                .into());
+            }
+        }
+
+        self.params.push(Param::In);
+        self
+    }
+
+    /// Pass the session variable `name` (e.g. `@total`) as the next `OUT` or `INOUT` parameter.
+    ///
+    /// Its value after the call is returned in [`MySqlCallResult::out_params()`],
+    /// in a column of the same name.
+    pub fn out_param(mut self, name: &str) -> Self {
+        self.params.push(Param::Out(name.to_owned()));
+        self
+    }
+
+    /// Call the procedure, collecting all result sets it returns, and then read back the values
+    /// of the `OUT` parameters, if any.
+    pub async fn execute(self) -> Result<MySqlCallResult, Error> {
+        let arguments = self.arguments.map_err(Error::Encode)?;
+
+        if !is_procedure_name(&self.procedure) {
+            return Err(Error::InvalidArgument(format!(
+                "invalid procedure name {:?}",
+                self.procedure
+            )));
+        }
+
+        let mut call = format!("CALL {}(", self.procedure);
+        let mut select = String::from("SELECT ");
+
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                call.push_str(", ");
+            }
+
+            match param {
+                Param::In => call.push('?'),
+
+                Param::Out(name) => {
+                    if !is_session_variable(name) {
+                        return Err(Error::InvalidArgument(format!(
+                            "invalid session variable {name:?} for an OUT parameter"
+                        )));
+                    }
+
+                    call.push_str(name);
+
+                    if select.len() > "SELECT ".len() {
+                        select.push_str(", ");
+                    }
+
+                    // the column name of `@name` is `@name`, but let's be explicit
+                    let _ = write!(select, "{name} AS `{name}`");
+                }
+            }
+        }
+
+        call.push(')');
+
+        let mut result = MySqlCallResult::default();
+        let mut rows = Vec::new();
+
+        {
+            let mut s = pin!(self.conn.run(&call, Some(arguments), false, true).await?);
+
+            // every result set is terminated by an OK packet, and the `CALL` itself
+            // is terminated by one last OK packet with the status of the procedure
+            while let Some(v) = s.try_next().await? {
+                match v {
+                    Either::Left(done) => {
+                        result.rows_affected += done.rows_affected();
+                        result.result_sets.push(std::mem::take(&mut rows));
+                    }
+
+                    Either::Right(row) => rows.push(row),
+                }
+            }
+        }
+
+        result.result_sets.pop();
+
+        if select.len() > "SELECT ".len() {
+            result.out_params = Some(self.conn.fetch_one(&*select).await?);
+        }
+
+        Ok(result)
+    }
+}
+
+impl MySqlCallResult {
+    /// The result sets returned by the procedure, in order.
+    ///
+    /// A result set without any rows is returned as an empty `Vec`.
+    pub fn result_sets(&self) -> &[Vec<MySqlRow>] {
+        &self.result_sets
+    }
+
+    /// Take the result sets returned by the procedure, in order.
+    pub fn into_result_sets(self) -> Vec<Vec<MySqlRow>> {
+        self.result_sets
+    }
+
+    /// The total number of rows affected by the statements in the procedure.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+
+    /// A row with a column for every `OUT` parameter, named after its session variable,
+    /// or `None` if there were no `OUT` parameters.
+    pub fn out_params(&self) -> Option<&MySqlRow> {
+        self.out_params.as_ref()
+    }
+}
+
+/// `name`, `db.name` or the same with backquoted identifiers.
+fn is_procedure_name(name: &str) -> bool {
+    let mut parts = 0;
+
+    for part in name.split('.') {
+        parts += 1;
+
+        let valid = match part.strip_prefix('`').and_then(|p| p.strip_suffix('`')) {
+            Some(quoted) => !quoted.is_empty() && !quoted.contains('`'),
+            None => is_identifier(part),
+        };
+
+        if !valid {
+            return false;
+        }
+    }
+
+    parts <= 2
+}
+
+fn is_session_variable(name: &str) -> bool {
+    name.strip_prefix('@').is_some_and(is_identifier)
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[test]
+fn test_is_procedure_name() {
+    assert!(is_procedure_name("my_proc"));
+    assert!(is_procedure_name("db.my_proc"));
+    assert!(is_procedure_name("`my db`.`my proc`"));
+
+    assert!(!is_procedure_name(""));
+    assert!(!is_procedure_name("a.b.c"));
+    assert!(!is_procedure_name("p(); DROP TABLE users; --"));
+    assert!(!is_procedure_name("`a`b`"));
+
+    assert!(is_session_variable("@out_total"));
+    assert!(!is_session_variable("out_total"));
+    assert!(!is_session_variable("@"));
+    assert!(!is_session_variable("@a, @b"));
+}
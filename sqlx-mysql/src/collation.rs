@@ -94,6 +94,24 @@ impl CharSet {
         }
     }
 
+    /// Returns `true` if the server reports this character set as `name`.
+    pub(crate) fn is_named(&self, name: &str) -> bool {
+        // MySQL 8.0 and MariaDB 10.6 report `utf8` as `utf8mb3`
+        name == self.as_str() || matches!((self, name), (CharSet::utf8, "utf8mb3"))
+    }
+
+    /// Returns `true` if `c` can be sent as UTF-8 with this character set,
+    /// without the server replacing or garbling it.
+    pub(crate) fn can_encode(&self, c: char) -> bool {
+        match self {
+            CharSet::utf8mb4 | CharSet::binary => true,
+            // `utf8` is `utf8mb3`, which only has the characters of up to 3 bytes
+            CharSet::utf8 => c.len_utf8() <= 3,
+            // any other character set reads the bytes of other characters as something else
+            _ => c.is_ascii(),
+        }
+    }
+
     pub(crate) fn default_collation(&self) -> Collation {
         match self {
             CharSet::armscii8 => Collation::armscii8_general_ci,
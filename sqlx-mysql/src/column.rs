@@ -12,6 +12,22 @@ pub struct MySqlColumn {
 
     #[cfg_attr(feature = "offline", serde(skip))]
     pub(crate) flags: Option<ColumnFlags>,
+
+    // the table as named in the query, and the original table; `None` for expressions
+    #[cfg_attr(feature = "offline", serde(skip))]
+    pub(crate) table_alias: Option<UStr>,
+    #[cfg_attr(feature = "offline", serde(skip))]
+    pub(crate) table: Option<UStr>,
+}
+
+impl MySqlColumn {
+    // names by which this column can be qualified in a lookup, like `table.column`
+    pub(crate) fn table_names(&self) -> impl Iterator<Item = &str> {
+        self.table_alias
+            .iter()
+            .chain(&self.table)
+            .map(|name| &**name)
+    }
 }
 
 impl Column for MySqlColumn {
@@ -0,0 +1,103 @@
+use crate::collation::CharSet;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::row::Row;
+use crate::{MySqlConnection, MySqlRow};
+
+/// The variables that have to be the character set of the connection,
+/// as checked by [`MySqlConnection::verify_charset()`].
+pub(crate) const CHARSET_VARIABLES: [&str; 3] = [
+    "@@character_set_client",
+    "@@character_set_connection",
+    "@@character_set_results",
+];
+
+impl MySqlConnection {
+    /// Checks that `@@character_set_client`, `@@character_set_connection` and
+    /// `@@character_set_results` are [the character set of the connection][Self::charset],
+    /// and passes `SET NAMES '{charset}' COLLATE '{collation}'` if any of them isn't,
+    /// e.g. after `SET character_set_results = latin1`.
+    ///
+    /// This is done after connecting unless [`MySqlConnectOptions::verify_charset()`] is
+    /// disabled. If the character set still doesn't match afterwards, an error is returned.
+    ///
+    /// [`MySqlConnectOptions::verify_charset()`]: crate::MySqlConnectOptions::verify_charset
+    pub async fn verify_charset(&mut self) -> Result<(), Error> {
+        let row = self.fetch_charset_variables().await?;
+
+        self.verify_charset_variables(&row).await
+    }
+
+    /// Like [`verify_charset()`][Self::verify_charset], given a row that starts with the values
+    /// of [`CHARSET_VARIABLES`].
+    pub(crate) async fn verify_charset_variables(&mut self, row: &MySqlRow) -> Result<(), Error> {
+        let charset = self.inner.stream.charset;
+
+        let mismatched = mismatched_charset_variables(charset, row)?;
+
+        if mismatched.is_empty() {
+            return Ok(());
+        }
+
+        tracing::warn!(
+            "the connection does not use the character set {}, setting it again: {}",
+            charset.as_str(),
+            mismatched
+        );
+
+        let set_names = format!(
+            "SET NAMES {} COLLATE {};",
+            charset.as_str(),
+            self.inner.stream.collation.as_str()
+        );
+
+        self.unmetered().execute(&*set_names).await?;
+
+        // the correction needs to be run again after a reset, too
+        let setup = match self.inner.session_setup.take() {
+            Some(setup) if setup.ends_with(&set_names) => setup.into(),
+            Some(setup) => format!("{setup}{set_names}"),
+            None => set_names,
+        };
+
+        self.inner.session_setup = Some(setup.into());
+
+        let row = self.fetch_charset_variables().await?;
+        let mismatched = mismatched_charset_variables(charset, &row)?;
+
+        if !mismatched.is_empty() {
+            return Err(Error::Configuration(
+                format!(
+                    "the connection does not use the character set {}: {}",
+                    charset.as_str(),
+                    mismatched
+                )
+                .into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_charset_variables(&mut self) -> Result<MySqlRow, Error> {
+        self.unmetered()
+            .fetch_one(&*format!("SELECT {}", CHARSET_VARIABLES.join(", ")))
+            .await
+    }
+}
+
+/// Lists the variables of [`CHARSET_VARIABLES`] in `row` that aren't `charset`,
+/// e.g. `@@character_set_results = Some("latin1")`.
+fn mismatched_charset_variables(charset: CharSet, row: &MySqlRow) -> Result<String, Error> {
+    let mut mismatched = Vec::new();
+
+    for (i, variable) in CHARSET_VARIABLES.iter().enumerate() {
+        let name: Option<String> = row.try_get(i)?;
+
+        if !name.as_deref().is_some_and(|name| charset.is_named(name)) {
+            mismatched.push(format!("{variable} = {name:?}"));
+        }
+    }
+
+    Ok(mismatched.join(", "))
+}
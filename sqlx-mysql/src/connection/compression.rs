@@ -0,0 +1,277 @@
+// Compressed packet framing, which wraps the regular packet stream once negotiated.
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.32/page_protocol_basic_compression_packet.html
+//
+// Each frame has a 7-byte header:
+//  * int<3> length of the (possibly compressed) payload
+//  * int<1> compressed sequence ID (separate from the regular packet sequence ID)
+//  * int<3> length of the payload before compression, or 0 if it was sent uncompressed
+//
+// The payloads, once decompressed, concatenate to regular packets including their own headers.
+
+use bytes::{Buf, Bytes, BytesMut};
+
+use crate::error::Error;
+use crate::options::MySqlCompression;
+
+pub(crate) const COMPRESSED_HEADER_LEN: usize = 7;
+
+// Payloads smaller than this are not worth compressing and are sent as-is inside a frame.
+// This is the same threshold used by `libmysqlclient`.
+const MIN_COMPRESS_LEN: usize = 50;
+
+const MAX_FRAME_LEN: usize = 0xFF_FF_FF;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CompressedHeader {
+    pub(crate) payload_len: usize,
+    pub(crate) sequence_id: u8,
+    pub(crate) uncompressed_len: usize,
+}
+
+impl CompressedHeader {
+    pub(crate) fn decode(mut buf: Bytes) -> Self {
+        // `get_uint_le(3)` cannot overflow
+        #[allow(clippy::cast_possible_truncation)]
+        let payload_len = buf.get_uint_le(3) as usize;
+        let sequence_id = buf.get_u8();
+        #[allow(clippy::cast_possible_truncation)]
+        let uncompressed_len = buf.get_uint_le(3) as usize;
+
+        Self {
+            payload_len,
+            sequence_id,
+            uncompressed_len,
+        }
+    }
+
+    fn encode(&self, buf: &mut Vec<u8>) {
+        // both lengths are bounded by `MAX_FRAME_LEN`
+        #[allow(clippy::cast_possible_truncation)]
+        buf.extend_from_slice(&(self.payload_len as u32).to_le_bytes()[..3]);
+        buf.push(self.sequence_id);
+        #[allow(clippy::cast_possible_truncation)]
+        buf.extend_from_slice(&(self.uncompressed_len as u32).to_le_bytes()[..3]);
+    }
+}
+
+/// Wrap `packets` (one or more encoded regular packets) in compressed frames.
+pub(crate) fn encode_frames(
+    compression: MySqlCompression,
+    packets: &[u8],
+    sequence_id: &mut u8,
+    buf: &mut Vec<u8>,
+) -> Result<(), Error> {
+    for chunk in packets.chunks(MAX_FRAME_LEN) {
+        let compressed = if chunk.len() >= MIN_COMPRESS_LEN {
+            Some(compress(compression, chunk)?).filter(|compressed| compressed.len() < chunk.len())
+        } else {
+            None
+        };
+
+        let (payload, uncompressed_len) = match &compressed {
+            Some(compressed) => (&compressed[..], chunk.len()),
+            None => (chunk, 0),
+        };
+
+        CompressedHeader {
+            payload_len: payload.len(),
+            sequence_id: *sequence_id,
+            uncompressed_len,
+        }
+        .encode(buf);
+
+        buf.extend_from_slice(payload);
+
+        *sequence_id = sequence_id.wrapping_add(1);
+    }
+
+    Ok(())
+}
+
+/// Unwrap the payload of a single compressed frame, appending the regular packet data to `buf`.
+pub(crate) fn decode_frame(
+    compression: MySqlCompression,
+    header: &CompressedHeader,
+    payload: Bytes,
+    buf: &mut BytesMut,
+) -> Result<(), Error> {
+    if payload.len() != header.payload_len {
+        return Err(err_protocol!(
+            "compressed frame payload length mismatch: expected {}, got {}",
+            header.payload_len,
+            payload.len()
+        ));
+    }
+
+    if header.uncompressed_len == 0 {
+        buf.extend_from_slice(&payload);
+        return Ok(());
+    }
+
+    let decompressed = decompress(compression, &payload, header.uncompressed_len)?;
+
+    if decompressed.len() != header.uncompressed_len {
+        return Err(err_protocol!(
+            "decompressed frame length mismatch: expected {}, got {}",
+            header.uncompressed_len,
+            decompressed.len()
+        ));
+    }
+
+    buf.extend_from_slice(&decompressed);
+
+    Ok(())
+}
+
+/// Returns an error if support for `compression` was not compiled in.
+pub(crate) fn check_available(compression: MySqlCompression) -> Result<(), Error> {
+    match compression {
+        MySqlCompression::None => Ok(()),
+        MySqlCompression::Zlib if cfg!(feature = "compression-zlib") => Ok(()),
+        MySqlCompression::Zstd { .. } if cfg!(feature = "compression-zstd") => Ok(()),
+        MySqlCompression::Zlib => Err(Error::Configuration(
+            "zlib compression requires the `mysql-compression-zlib` feature".into(),
+        )),
+        MySqlCompression::Zstd { .. } => Err(Error::Configuration(
+            "zstd compression requires the `mysql-compression-zstd` feature".into(),
+        )),
+    }
+}
+
+#[allow(unused_variables)]
+fn compress(compression: MySqlCompression, data: &[u8]) -> Result<Vec<u8>, Error> {
+    match compression {
+        #[cfg(feature = "compression-zlib")]
+        MySqlCompression::Zlib => {
+            use std::io::Write;
+
+            let mut encoder =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            Ok(encoder.finish()?)
+        }
+
+        #[cfg(feature = "compression-zstd")]
+        MySqlCompression::Zstd { level } => Ok(zstd::bulk::compress(data, level.into())?),
+
+        _ => Err(err_protocol!(
+            "attempted to compress with unsupported algorithm {compression:?}"
+        )),
+    }
+}
+
+#[allow(unused_variables)]
+fn decompress(
+    compression: MySqlCompression,
+    data: &[u8],
+    uncompressed_len: usize,
+) -> Result<Vec<u8>, Error> {
+    match compression {
+        #[cfg(feature = "compression-zlib")]
+        MySqlCompression::Zlib => {
+            use std::io::Read;
+
+            let mut decompressed = Vec::with_capacity(uncompressed_len);
+            flate2::read::ZlibDecoder::new(data).read_to_end(&mut decompressed)?;
+            Ok(decompressed)
+        }
+
+        #[cfg(feature = "compression-zstd")]
+        MySqlCompression::Zstd { .. } => Ok(zstd::bulk::decompress(data, uncompressed_len)?),
+
+        _ => Err(err_protocol!(
+            "attempted to decompress with unsupported algorithm {compression:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(compression: MySqlCompression, packets: &[u8]) -> (usize, Vec<u8>) {
+        let mut sequence_id = 0;
+        let mut framed = Vec::new();
+
+        encode_frames(compression, packets, &mut sequence_id, &mut framed).unwrap();
+
+        let mut framed = Bytes::from(framed);
+        let mut decoded = BytesMut::new();
+        let mut frames = 0;
+
+        while !framed.is_empty() {
+            let header = CompressedHeader::decode(framed.split_to(COMPRESSED_HEADER_LEN));
+            assert_eq!(header.sequence_id, frames);
+
+            let payload = framed.split_to(header.payload_len);
+            decode_frame(compression, &header, payload, &mut decoded).unwrap();
+
+            frames += 1;
+        }
+
+        assert_eq!(sequence_id, frames);
+
+        (frames.into(), decoded.to_vec())
+    }
+
+    #[test]
+    fn it_sends_small_payloads_uncompressed() {
+        // COM_PING
+        const PACKET: &[u8] = b"\x01\x00\x00\x00\x0e";
+
+        let mut sequence_id = 0;
+        let mut framed = Vec::new();
+        encode_frames(
+            MySqlCompression::Zlib,
+            PACKET,
+            &mut sequence_id,
+            &mut framed,
+        )
+        .unwrap();
+
+        assert_eq!(
+            &framed[..],
+            b"\x05\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x0e"
+        );
+        assert_eq!(
+            round_trip(MySqlCompression::Zlib, PACKET),
+            (1, PACKET.to_vec())
+        );
+    }
+
+    #[test]
+    fn it_decodes_header() {
+        let header = CompressedHeader::decode(Bytes::from_static(b"\x22\x00\x00\x05\x1a\x01\x00"));
+
+        assert_eq!(
+            header,
+            CompressedHeader {
+                payload_len: 0x22,
+                sequence_id: 5,
+                uncompressed_len: 0x011a,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "compression-zlib")]
+    fn it_round_trips_zlib() {
+        let packets = b"SELECT 1".repeat(1024);
+        assert_eq!(round_trip(MySqlCompression::Zlib, &packets), (1, packets));
+    }
+
+    #[test]
+    #[cfg(feature = "compression-zlib")]
+    fn it_splits_large_payloads_into_frames() {
+        let packets = vec![0x55; MAX_FRAME_LEN + 100];
+        assert_eq!(round_trip(MySqlCompression::Zlib, &packets), (2, packets));
+    }
+
+    #[test]
+    #[cfg(feature = "compression-zstd")]
+    fn it_round_trips_zstd() {
+        let compression = MySqlCompression::Zstd { level: 3 };
+        let packets = b"SELECT 1".repeat(1024);
+        assert_eq!(round_trip(compression, &packets), (1, packets));
+    }
+}
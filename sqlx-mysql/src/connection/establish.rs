@@ -3,22 +3,26 @@ use bytes::Bytes;
 
 use crate::collation::{CharSet, Collation};
 use crate::common::StatementCache;
-use crate::connection::{tls, MySqlConnectionInner, MySqlStream, MAX_PACKET_SIZE};
+use crate::connection::{compression, tls, MySqlConnectionInner, MySqlStream, MAX_PACKET_SIZE};
 use crate::error::Error;
 use crate::net::{Socket, WithSocket};
 use crate::protocol::connect::{
     AuthSwitchRequest, AuthSwitchResponse, Handshake, HandshakeResponse,
 };
 use crate::protocol::Capabilities;
-use crate::{MySqlConnectOptions, MySqlConnection, MySqlSslMode};
+use crate::{MySqlCompression, MySqlConnectOptions, MySqlConnection, MySqlSslMode};
 
 impl MySqlConnection {
     pub(crate) async fn establish(options: &MySqlConnectOptions) -> Result<Self, Error> {
         let do_handshake = DoHandshake::new(options)?;
 
-        let handshake = match &options.socket {
-            Some(path) => crate::net::connect_uds(path, do_handshake).await?,
-            None => crate::net::connect_tcp(&options.host, options.port, do_handshake).await?,
+        let handshake = if let Some(connector) = &options.socket_connector {
+            crate::net::connect_with(&**connector, do_handshake).await?
+        } else {
+            match &options.socket {
+                Some(path) => crate::net::connect_uds(path, do_handshake).await?,
+                None => crate::net::connect_tcp(&options.host, options.port, do_handshake).await?,
+            }
         };
 
         let stream = handshake?;
@@ -27,7 +31,11 @@ impl MySqlConnection {
             inner: Box::new(MySqlConnectionInner {
                 stream,
                 transaction_depth: 0,
+                session_setup: None,
                 cache_statement: StatementCache::new(options.statement_cache_capacity),
+                cache_statement_schema: options.database.clone(),
+                fetch_size: options.fetch_size,
+                strict_timestamps: options.strict_timestamps,
                 log_settings: options.log_settings.clone(),
             }),
         })
@@ -50,7 +58,10 @@ impl<'a> DoHandshake<'a> {
             .transpose()?
             .unwrap_or_else(|| charset.default_collation());
 
+        compression::check_available(options.compression)?;
+
         if options.enable_cleartext_plugin
+            && options.socket.is_none()
             && matches!(
                 options.ssl_mode,
                 MySqlSslMode::Disabled | MySqlSslMode::Preferred
@@ -83,40 +94,22 @@ impl<'a> DoHandshake<'a> {
         let mut plugin = handshake.auth_plugin;
         let nonce = handshake.auth_plugin_data;
 
-        // FIXME: server version parse is a bit ugly
-        // expecting MAJOR.MINOR.PATCH
-
-        let mut server_version = handshake.server_version.split('.');
-
-        let server_version_major: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
-
-        let server_version_minor: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
-
-        let server_version_patch: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
-
-        stream.server_version = (
-            server_version_major,
-            server_version_minor,
-            server_version_patch,
-        );
+        stream.server_version = parse_server_version(&handshake.server_version);
 
         stream.capabilities &= handshake.server_capabilities;
+        stream.status = handshake.status;
         stream.capabilities |= Capabilities::PROTOCOL_41;
 
         let mut stream = tls::maybe_upgrade(stream, self.options).await?;
 
+        // the password may be sent in cleartext if the connection is already secure
+        let allow_cleartext =
+            options.enable_cleartext_plugin || stream.is_tls || options.socket.is_some();
+
+        if let Some(plugin) = plugin {
+            plugin.check_allowed(allow_cleartext)?;
+        }
+
         let auth_response = if let (Some(plugin), Some(password)) = (plugin, &options.password) {
             Some(plugin.scramble(&mut stream, password, &nonce).await?)
         } else {
@@ -130,6 +123,10 @@ impl<'a> DoHandshake<'a> {
             database: options.database.as_deref(),
             auth_plugin: plugin,
             auth_response: auth_response.as_deref(),
+            zstd_compression_level: match options.compression {
+                MySqlCompression::Zstd { level } => Some(level),
+                _ => None,
+            },
         })?;
 
         stream.flush().await?;
@@ -138,14 +135,14 @@ impl<'a> DoHandshake<'a> {
             let packet = stream.recv_packet().await?;
             match packet[0] {
                 0x00 => {
-                    let _ok = packet.ok()?;
+                    let ok = packet.ok()?;
+                    stream.set_ok_status(&ok);
 
                     break;
                 }
 
                 0xfe => {
-                    let switch: AuthSwitchRequest =
-                        packet.decode_with(self.options.enable_cleartext_plugin)?;
+                    let switch: AuthSwitchRequest = packet.decode_with(allow_cleartext)?;
 
                     plugin = Some(switch.plugin);
                     let nonce = switch.data.chain(Bytes::new());
@@ -181,6 +178,8 @@ impl<'a> DoHandshake<'a> {
             }
         }
 
+        stream.maybe_enable_compression();
+
         Ok(stream)
     }
 }
@@ -192,3 +191,132 @@ impl<'a> WithSocket for DoHandshake<'a> {
         self.do_handshake(socket).await
     }
 }
+
+/// Parse `MAJOR.MINOR.PATCH` from the start of the version string sent by the server,
+/// e.g. `8.0.36-0ubuntu0.22.04.1` or `5.5.5-10.11.2-MariaDB`.
+///
+/// Missing or unparseable components are returned as 0.
+fn parse_server_version(version: &str) -> (u16, u16, u16) {
+    // MariaDB 10+ prefixes its version with `5.5.5-` for compatibility with old clients
+    let version = version.strip_prefix("5.5.5-").unwrap_or(version);
+
+    let mut components = version.split('.').map(|component| {
+        let end = component
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(component.len());
+
+        component[..end].parse().unwrap_or(0)
+    });
+
+    let mut next = || components.next().unwrap_or(0);
+
+    (next(), next(), next())
+}
+
+#[test]
+fn test_parse_server_version() {
+    assert_eq!(parse_server_version("8.0.36"), (8, 0, 36));
+    assert_eq!(parse_server_version("8.0.36-0ubuntu0.22.04.1"), (8, 0, 36));
+    assert_eq!(parse_server_version("5.7.44-log"), (5, 7, 44));
+    assert_eq!(parse_server_version("5.5.5-10.11.2-MariaDB"), (10, 11, 2));
+    assert_eq!(parse_server_version("9.0"), (9, 0, 0));
+    assert_eq!(parse_server_version("unknown"), (0, 0, 0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::mock::{packet, poll_ready, MockSocket};
+
+    const HANDSHAKE_MYSQL_8_0_18: &[u8] = b"\n8.0.18\x00\x19\x00\x00\x00\x114aB0c\x06g\x00\xff\xff\xff\x02\x00\xff\xc7\x15\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00tL\x03s\x0f[4\rl4. \x00caching_sha2_password\x00";
+
+    const AUTH_SWITCH_CLEARTEXT: &[u8] = b"\xfemysql_clear_password\x00";
+
+    const OK: &[u8] = b"\x00\x00\x00\x02\x00\x00\x00";
+
+    fn options() -> MySqlConnectOptions {
+        MySqlConnectOptions::new()
+            .password("secret")
+            .ssl_mode(MySqlSslMode::Disabled)
+    }
+
+    // runs the handshake against the given server packets, returning what the client wrote
+    fn handshake(options: &MySqlConnectOptions, server: &[&[u8]]) -> (Result<(), Error>, Vec<u8>) {
+        let mut incoming = Vec::new();
+
+        // the client replies to each server packet, so every other sequence id is theirs
+        for (sequence_id, payload) in (0..).step_by(2).zip(server) {
+            incoming.extend(packet(sequence_id, payload));
+        }
+
+        let socket = MockSocket::new(incoming);
+        let written = socket.written.clone();
+
+        let res = poll_ready(DoHandshake::new(options).unwrap().do_handshake(socket)).map(drop);
+        let written = written.lock().unwrap().clone();
+
+        (res, written)
+    }
+
+    fn assert_refused(res: Result<(), Error>, written: &[u8]) {
+        assert!(
+            matches!(&res, Err(Error::Configuration(e)) if e.to_string().contains("mysql_clear_password")),
+            "{res:?}"
+        );
+        assert!(!written.windows(6).any(|w| w == b"secret"));
+    }
+
+    #[test]
+    fn it_switches_to_cleartext_password_when_enabled() {
+        let options = options().enable_cleartext_plugin(true);
+
+        let (res, written) = handshake(
+            &options,
+            &[HANDSHAKE_MYSQL_8_0_18, AUTH_SWITCH_CLEARTEXT, OK],
+        );
+
+        res.unwrap();
+        assert!(written.ends_with(&packet(3, b"secret\0")));
+    }
+
+    #[test]
+    fn it_switches_to_cleartext_password_over_unix_socket() {
+        let options = options().socket("/run/mysqld/mysqld.sock");
+
+        let (res, written) = handshake(
+            &options,
+            &[HANDSHAKE_MYSQL_8_0_18, AUTH_SWITCH_CLEARTEXT, OK],
+        );
+
+        res.unwrap();
+        assert!(written.ends_with(&packet(3, b"secret\0")));
+    }
+
+    #[test]
+    fn it_refuses_to_switch_to_cleartext_password() {
+        let (res, written) = handshake(
+            &options(),
+            &[HANDSHAKE_MYSQL_8_0_18, AUTH_SWITCH_CLEARTEXT, OK],
+        );
+
+        assert_refused(res, &written);
+    }
+
+    #[test]
+    fn it_refuses_cleartext_password_in_initial_handshake() {
+        let mut handshake_cleartext = HANDSHAKE_MYSQL_8_0_18
+            [..HANDSHAKE_MYSQL_8_0_18.len() - b"caching_sha2_password\0".len()]
+            .to_vec();
+        handshake_cleartext.extend_from_slice(b"mysql_clear_password\0");
+
+        let (res, written) = handshake(&options(), &[&handshake_cleartext, OK]);
+
+        assert_refused(res, &written);
+
+        let options = options().enable_cleartext_plugin(true);
+        let (res, written) = handshake(&options, &[&handshake_cleartext, OK]);
+
+        res.unwrap();
+        assert!(written.windows(7).any(|w| w == b"secret\0"));
+    }
+}
@@ -1,16 +1,20 @@
 use super::MySqlStream;
+use crate::arguments::Arguments;
 use crate::connection::stream::Waiting;
+use crate::connection::Connection;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::ext::ustr::UStr;
 use crate::io::MySqlBufExt;
 use crate::logger::QueryLogger;
-use crate::protocol::response::Status;
+use crate::metrics::QueryMetrics;
+use crate::protocol::response::{EofPacket, Status};
 use crate::protocol::statement::{
-    BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
+    BinaryRow, Execute as StatementExecute, Fetch as StatementFetch, Prepare, PrepareOk, StmtClose,
 };
 use crate::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
+use crate::protocol::Capabilities;
 use crate::statement::{MySqlStatement, MySqlStatementMetadata};
 use crate::HashMap;
 use crate::{
@@ -39,12 +43,16 @@ impl MySqlConnection {
 
         let ok: PrepareOk = self.inner.stream.recv().await?;
 
-        // the parameter definitions are very unreliable so we skip over them
-        // as we have little use
+        // the parameter definitions are only hints: before MySQL 8.0.22, and on MariaDB,
+        // every parameter is reported as `VAR_STRING`, and the server converts the arguments
+        // to the types it needs anyway
+
+        let mut parameters = Vec::with_capacity(ok.params as usize);
 
         if ok.params > 0 {
             for _ in 0..ok.params {
-                let _def: ColumnDefinition = self.inner.stream.recv().await?;
+                let def: ColumnDefinition = self.inner.stream.recv().await?;
+                parameters.push(MySqlTypeInfo::from_column(&def));
             }
 
             self.inner.stream.maybe_recv_eof().await?;
@@ -64,7 +72,7 @@ impl MySqlConnection {
 
         let id = ok.statement_id;
         let metadata = MySqlStatementMetadata {
-            parameters: ok.params as usize,
+            parameters: Arc::new(parameters),
             columns: Arc::new(columns),
             column_names: Arc::new(column_names),
         };
@@ -76,6 +84,13 @@ impl MySqlConnection {
         &mut self,
         sql: &str,
     ) -> Result<(u32, MySqlStatementMetadata), Error> {
+        // a statement keeps resolving unqualified names in the database it was prepared in,
+        // so they have to be prepared again after e.g. `USE`
+        if self.inner.cache_statement_schema.as_deref() != self.database() {
+            self.clear_cached_statements().await?;
+            self.inner.cache_statement_schema = self.database().map(ToOwned::to_owned);
+        }
+
         if let Some(statement) = self.inner.cache_statement.get_mut(sql) {
             // <MySqlStatementMetadata> is internally reference-counted
             return Ok((*statement).clone());
@@ -99,15 +114,44 @@ impl MySqlConnection {
     }
 
     #[allow(clippy::needless_lifetimes)]
+    /// Run a query, reporting it to the [`Metrics`][sqlx_core::metrics::Metrics] of the
+    /// connection if `metered`.
+    ///
+    /// Queries issued by the driver itself, such as the session setup, are not `metered`.
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         sql: &'q str,
         arguments: Option<MySqlArguments>,
         persistent: bool,
+        metered: bool,
+    ) -> Result<impl Stream<Item = Result<Either<MySqlQueryResult, MySqlRow>, Error>> + 'e, Error>
+    {
+        let metrics = if metered {
+            QueryMetrics::start(&self.inner.log_settings)
+        } else {
+            None
+        };
+        let result = self.run_unmetered(sql, arguments, persistent).await;
+
+        QueryMetrics::instrument(metrics, result)
+    }
+
+    #[allow(clippy::needless_lifetimes)]
+    async fn run_unmetered<'e, 'c: 'e, 'q: 'e>(
+        &'c mut self,
+        sql: &'q str,
+        arguments: Option<MySqlArguments>,
+        persistent: bool,
     ) -> Result<impl Stream<Item = Result<Either<MySqlQueryResult, MySqlRow>, Error>> + 'e, Error>
     {
         let mut logger = QueryLogger::new(sql, self.inner.log_settings.clone());
 
+        if let Some(arguments) = &arguments {
+            arguments
+                .check_charset(self.inner.stream.charset)
+                .map_err(Error::Encode)?;
+        }
+
         self.inner.stream.wait_until_ready().await?;
         self.inner.stream.waiting.push_back(Waiting::Result);
 
@@ -117,17 +161,35 @@ impl MySqlConnection {
             // to re-use this memory freely between result sets
             let mut columns = Arc::new(Vec::new());
 
+            // the statement to fetch the rows of the first result set from, if using a cursor
+            let mut cursor = None;
+
             let (mut column_names, format, mut needs_metadata) = if let Some(arguments) = arguments {
                 if persistent && self.inner.cache_statement.is_enabled() {
                     let (id, metadata) = self
                         .get_or_prepare_statement(sql)
                         .await?;
 
+                    if metadata.parameters.len() != arguments.len() {
+                        // we won't be reading a result after all
+                        self.inner.stream.waiting.pop_back();
+
+                        return Err(Error::ParameterCountMismatch {
+                            expected: metadata.parameters.len(),
+                            got: arguments.len(),
+                        });
+                    }
+
+                    if self.inner.fetch_size > 0 {
+                        cursor = Some(id);
+                    }
+
                     // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                     self.inner.stream
                         .send_packet(StatementExecute {
                             statement: id,
                             arguments: &arguments,
+                            cursor: cursor.is_some(),
                         })
                         .await?;
 
@@ -137,11 +199,23 @@ impl MySqlConnection {
                         .prepare_statement(sql)
                         .await?;
 
+                    if metadata.parameters.len() != arguments.len() {
+                        self.inner.stream.waiting.pop_back();
+                        self.inner.stream.send_packet(StmtClose { statement: id }).await?;
+
+                        return Err(Error::ParameterCountMismatch {
+                            expected: metadata.parameters.len(),
+                            got: arguments.len(),
+                        });
+                    }
+
                     // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                     self.inner.stream
                         .send_packet(StatementExecute {
                             statement: id,
                             arguments: &arguments,
+                            // the statement is closed right away, which would close the cursor
+                            cursor: false,
                         })
                         .await?;
 
@@ -165,12 +239,14 @@ impl MySqlConnection {
                     // first packet in a query response is OK or ERR
                     // this indicates either a successful query with no rows at all or a failed query
                     let ok = packet.ok()?;
+                    self.inner.stream.set_ok_status(&ok);
 
                     let rows_affected = ok.affected_rows;
                     logger.increase_rows_affected(rows_affected);
                     let done = MySqlQueryResult {
                         rows_affected,
                         last_insert_id: ok.last_insert_id,
+                        warnings: ok.warnings,
                     };
 
                     r#yield!(Either::Left(done));
@@ -198,22 +274,63 @@ impl MySqlConnection {
                     // full metadata
                     needs_metadata = true;
 
-                    recv_result_columns(&mut self.inner.stream, num_columns, Arc::make_mut(&mut columns)).await?;
+                    let eof = recv_result_columns(&mut self.inner.stream, num_columns, Arc::make_mut(&mut columns)).await?;
+
+                    // without `DEPRECATE_EOF`, whether a cursor was opened is only
+                    // indicated by the EOF packet after the columns
+                    if let (Some(id), Some(eof)) = (cursor, eof) {
+                        if eof.status.contains(Status::SERVER_STATUS_CURSOR_EXISTS) {
+                            self.inner.stream.send_packet(StatementFetch {
+                                statement: id,
+                                rows: self.inner.fetch_size,
+                            }).await?;
+                        }
+                    }
                 }
 
                 // finally, there will be none or many result-rows
                 loop {
                     let packet = self.inner.stream.recv_packet().await?;
 
-                    if packet[0] == 0xfe && packet.len() < 9 {
-                        let eof = packet.eof(self.inner.stream.capabilities)?;
+                    if packet.is_result_set_end(self.inner.stream.capabilities) {
+                        // with `DEPRECATE_EOF`, the result set is terminated by an OK packet
+                        // which carries the rows affected by e.g. `INSERT ... RETURNING`
+                        let (status, warnings, rows_affected, last_insert_id) =
+                            if self.inner.stream.capabilities.contains(Capabilities::DEPRECATE_EOF) {
+                                let ok = packet.ok()?;
+                                self.inner.stream.set_ok_status(&ok);
+
+                                (ok.status, ok.warnings, ok.affected_rows, ok.last_insert_id)
+                            } else {
+                                let eof = packet.eof(self.inner.stream.capabilities)?;
+                                self.inner.stream.set_status(eof.status, eof.warnings);
+
+                                (eof.status, eof.warnings, 0, 0)
+                            };
+
+                        // a cursor was opened, or it has more rows: fetch the next batch of rows
+                        if let Some(id) = cursor {
+                            if status.contains(Status::SERVER_STATUS_CURSOR_EXISTS)
+                                && !status.contains(Status::SERVER_STATUS_LAST_ROW_SENT)
+                            {
+                                self.inner.stream.send_packet(StatementFetch {
+                                    statement: id,
+                                    rows: self.inner.fetch_size,
+                                }).await?;
+
+                                continue;
+                            }
+                        }
+
+                        logger.increase_rows_affected(rows_affected);
 
                         r#yield!(Either::Left(MySqlQueryResult {
-                            rows_affected: 0,
-                            last_insert_id: 0,
+                            rows_affected,
+                            last_insert_id,
+                            warnings,
                         }));
 
-                        if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+                        if status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                             // more result sets exist, continue to the next one
                             *self.inner.stream.waiting.front_mut().unwrap() = Waiting::Result;
                             break;
@@ -233,6 +350,7 @@ impl MySqlConnection {
                         format,
                         columns: Arc::clone(&columns),
                         column_names: Arc::clone(&column_names),
+                        strict_timestamps: self.inner.strict_timestamps,
                     });
 
                     logger.increment_rows_returned();
@@ -244,26 +362,33 @@ impl MySqlConnection {
     }
 }
 
-impl<'c> Executor<'c> for &'c mut MySqlConnection {
-    type Database = MySql;
+impl MySqlConnection {
+    /// Queries issued with the returned executor are not reported to the
+    /// [`Metrics`][sqlx_core::metrics::Metrics] of the connection.
+    ///
+    /// This is used for the queries the driver issues itself, such as the session setup.
+    pub(crate) fn unmetered(&mut self) -> Unmetered<'_> {
+        Unmetered(self)
+    }
 
-    fn fetch_many<'e, 'q, E>(
-        self,
+    fn fetch_many_with<'e, 'q, E>(
+        &'e mut self,
         mut query: E,
+        metered: bool,
     ) -> BoxStream<'e, Result<Either<MySqlQueryResult, MySqlRow>, Error>>
     where
-        'c: 'e,
-        E: Execute<'q, Self::Database>,
+        E: Execute<'q, MySql>,
         'q: 'e,
         E: 'q,
     {
-        let sql = query.sql();
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
 
+        // the stream owns the query, as the SQL may be borrowed from it
         Box::pin(try_stream! {
             let arguments = arguments?;
-            let mut s = pin!(self.run(sql, arguments, persistent).await?);
+            let sql = query.sql();
+            let mut s = pin!(self.run(sql, arguments, persistent, metered).await?);
 
             while let Some(v) = s.try_next().await? {
                 r#yield!(v);
@@ -273,25 +398,62 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         })
     }
 
-    fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<MySqlRow>, Error>>
+    fn fetch_optional_with<'e, 'q, E>(
+        &'e mut self,
+        query: E,
+        metered: bool,
+    ) -> BoxFuture<'e, Result<Option<MySqlRow>, Error>>
     where
-        'c: 'e,
-        E: Execute<'q, Self::Database>,
+        E: Execute<'q, MySql>,
         'q: 'e,
         E: 'q,
     {
-        let mut s = self.fetch_many(query);
+        let mut s = self.fetch_many_with(query, metered);
 
         Box::pin(async move {
+            // read the whole response, so an error from a later statement is returned here
+            // instead of from the next query on this connection
+            let mut ret = None;
             while let Some(v) = s.try_next().await? {
                 if let Either::Right(r) = v {
-                    return Ok(Some(r));
+                    ret.get_or_insert(r);
                 }
             }
 
-            Ok(None)
+            Ok(ret)
         })
     }
+}
+
+/// See [`MySqlConnection::unmetered()`].
+#[derive(Debug)]
+pub(crate) struct Unmetered<'c>(&'c mut MySqlConnection);
+
+impl<'c> Executor<'c> for &'c mut MySqlConnection {
+    type Database = MySql;
+
+    fn fetch_many<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<MySqlQueryResult, MySqlRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_many_with(query, true)
+    }
+
+    fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<MySqlRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_optional_with(query, true)
+    }
 
     fn prepare_with<'e, 'q: 'e>(
         self,
@@ -340,6 +502,7 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
                 .send_packet(StmtClose { statement: id })
                 .await?;
 
+            let parameters = (*metadata.parameters).clone();
             let columns = (*metadata.columns).clone();
 
             let nullable = columns
@@ -351,7 +514,7 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
                 .collect();
 
             Ok(Describe {
-                parameters: Some(Either::Right(metadata.parameters)),
+                parameters: Some(Either::Left(parameters)),
                 columns,
                 nullable,
             })
@@ -359,11 +522,12 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
     }
 }
 
+/// Returns the EOF packet after the columns, if the server sends one.
 async fn recv_result_columns(
     stream: &mut MySqlStream,
     num_columns: usize,
     columns: &mut Vec<MySqlColumn>,
-) -> Result<(), Error> {
+) -> Result<Option<EofPacket>, Error> {
     columns.clear();
     columns.reserve(num_columns);
 
@@ -372,10 +536,10 @@ async fn recv_result_columns(
     }
 
     if num_columns > 0 {
-        stream.maybe_recv_eof().await?;
+        return stream.maybe_recv_eof().await;
     }
 
-    Ok(())
+    Ok(None)
 }
 
 fn recv_next_result_column(def: &ColumnDefinition, ordinal: usize) -> Result<MySqlColumn, Error> {
@@ -393,6 +557,12 @@ fn recv_next_result_column(def: &ColumnDefinition, ordinal: usize) -> Result<MyS
         type_info,
         ordinal,
         flags: Some(def.flags),
+        table_alias: Some(def.table_alias()?)
+            .filter(|alias| !alias.is_empty())
+            .map(UStr::new),
+        table: Some(def.table()?)
+            .filter(|table| !table.is_empty())
+            .map(UStr::new),
     })
 }
 
@@ -414,7 +584,8 @@ async fn recv_result_metadata(
 
         let column = recv_next_result_column(&def, ordinal)?;
 
-        column_names.insert(column.name.clone(), ordinal);
+        // if more than one column has the same name, lookups return the first
+        column_names.entry(column.name.clone()).or_insert(ordinal);
         columns.push(column);
     }
 
@@ -422,3 +593,172 @@ async fn recv_result_metadata(
 
     Ok(column_names)
 }
+
+impl<'c> Executor<'c> for Unmetered<'c> {
+    type Database = MySql;
+
+    fn fetch_many<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<MySqlQueryResult, MySqlRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.0.fetch_many_with(query, false)
+    }
+
+    fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<MySqlRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.0.fetch_optional_with(query, false)
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [MySqlTypeInfo],
+    ) -> BoxFuture<'e, Result<MySqlStatement<'q>, Error>>
+    where
+        'c: 'e,
+    {
+        self.0.prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<MySql>, Error>>
+    where
+        'c: 'e,
+    {
+        self.0.describe(sql)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::mock::{mock_connection, packet, poll_ready};
+    use crate::{MySqlConnection, MySqlDatabaseError};
+    use futures_util::StreamExt;
+    use sqlx_core::connection::Connection;
+    use std::fmt::Debug;
+
+    // a result set of one BIGINT column, `x`
+    const COLUMN_COUNT: &[u8] = b"\x01";
+    const COLUMN: &[u8] =
+        b"\x03def\x00\x00\x00\x01x\x00\x0c\x3f\x00\x14\x00\x00\x00\x08\x00\x00\x00\x00\x00";
+    const ROW: &[u8] = b"\x011";
+
+    // with `DEPRECATE_EOF`, a result set ends with an OK packet with the header of an EOF packet
+    const END: &[u8] = b"\xfe\x00\x00\x02\x00\x00\x00";
+    const END_MORE_RESULTS: &[u8] = b"\xfe\x00\x00\x0a\x00\x00\x00";
+    const OK: &[u8] = b"\x00\x00\x00\x02\x00\x00\x00";
+
+    // ER_DIVISION_BY_ZERO
+    const ERR: &[u8] = b"\xff\x55\x05#22012Division by 0";
+
+    // the packets the server sends in response to a command
+    fn response(payloads: &[&[u8]]) -> Vec<u8> {
+        (1..)
+            .zip(payloads)
+            .flat_map(|(sequence_id, payload)| packet(sequence_id, payload))
+            .collect()
+    }
+
+    // a connection that gets `payloads` in response to the first query,
+    // followed by the responses to a ping and a successful query
+    fn connection(payloads: &[&[u8]]) -> MySqlConnection {
+        let mut incoming = response(payloads);
+        incoming.extend(response(&[OK]));
+        incoming.extend(response(&[COLUMN_COUNT, COLUMN, ROW, END]));
+
+        mock_connection(incoming)
+    }
+
+    fn assert_division_by_zero<T: Debug>(res: Result<T, Error>) {
+        let err = res.unwrap_err();
+        let err = err
+            .as_database_error()
+            .and_then(|e| e.try_downcast_ref::<MySqlDatabaseError>())
+            .unwrap_or_else(|| panic!("expected a database error, got {err:?}"));
+
+        assert_eq!(err.number(), 1365);
+        assert_eq!(err.code(), Some("22012"));
+    }
+
+    fn assert_usable(mut conn: MySqlConnection) {
+        poll_ready(conn.ping()).unwrap();
+
+        let rows = poll_ready(conn.fetch_all("SELECT 1")).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(conn.inner.stream.waiting.is_empty());
+    }
+
+    #[test]
+    fn it_recovers_from_an_error_in_place_of_a_row() {
+        let mut conn = connection(&[COLUMN_COUNT, COLUMN, ERR]);
+
+        assert_division_by_zero(poll_ready(conn.fetch_all("SELECT 1 / 0")));
+        assert_usable(conn);
+    }
+
+    #[test]
+    fn it_recovers_from_an_error_in_place_of_the_end_of_a_result_set() {
+        let mut conn = connection(&[COLUMN_COUNT, COLUMN, ROW, ROW, ERR]);
+
+        let mut results = poll_ready(conn.fetch("SELECT 1 / x").collect::<Vec<_>>());
+
+        assert_division_by_zero(results.pop().unwrap());
+        assert_eq!(results.len(), 2);
+        assert!(results.into_iter().all(|res| res.is_ok()));
+        assert_usable(conn);
+    }
+
+    #[test]
+    fn it_recovers_from_an_error_between_result_sets() {
+        let mut conn = connection(&[COLUMN_COUNT, COLUMN, ROW, END_MORE_RESULTS, ERR]);
+
+        let mut results = poll_ready(
+            conn.fetch_many("SELECT 1; SELECT 1 / 0")
+                .collect::<Vec<_>>(),
+        );
+
+        assert_division_by_zero(results.pop().unwrap());
+        assert!(matches!(
+            results[..],
+            [Ok(Either::Right(_)), Ok(Either::Left(_))]
+        ));
+        assert_usable(conn);
+    }
+
+    #[test]
+    fn it_discards_the_error_of_an_abandoned_result_set() {
+        let mut conn = connection(&[COLUMN_COUNT, COLUMN, ROW, ROW, ERR]);
+
+        {
+            let mut rows = conn.fetch("SELECT 1 / x");
+            poll_ready(rows.next()).unwrap().unwrap();
+        }
+
+        // the rest of the result set and the error are read before the ping
+        assert_usable(conn);
+    }
+
+    #[test]
+    fn it_discards_the_error_of_an_abandoned_multi_statement() {
+        let mut conn = connection(&[COLUMN_COUNT, COLUMN, ROW, END_MORE_RESULTS, ERR]);
+
+        {
+            let mut results = conn.fetch_many("SELECT 1; SELECT 1 / 0");
+            poll_ready(results.next()).unwrap().unwrap();
+        }
+
+        assert_usable(conn);
+    }
+}
@@ -0,0 +1,102 @@
+//! A [`Socket`] for testing the protocol without a server.
+
+use std::cmp::min;
+use std::future::Future;
+use std::io;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use bytes::{BufMut, Bytes};
+use futures_util::task::noop_waker_ref;
+use sqlx_core::io::ReadBuf;
+
+use crate::collation::CharSet;
+use crate::common::StatementCache;
+use crate::connection::{MySqlConnectionInner, MySqlStream};
+use crate::net::Socket;
+use crate::{MySqlConnectOptions, MySqlConnection};
+
+/// Reads from a fixed buffer and records writes.
+pub(crate) struct MockSocket {
+    incoming: Bytes,
+    pub(crate) written: Arc<Mutex<Vec<u8>>>,
+}
+
+impl MockSocket {
+    pub(crate) fn new(incoming: Vec<u8>) -> Self {
+        MockSocket {
+            incoming: incoming.into(),
+            written: Arc::default(),
+        }
+    }
+}
+
+impl Socket for MockSocket {
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        let len = min(self.incoming.len(), buf.remaining_mut());
+        buf.put_slice(&self.incoming.split_to(len));
+        Ok(len)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn poll_read_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_write_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A connection that reads the responses of the server from `incoming`.
+///
+/// The capabilities are the ones requested by the client, e.g. `DEPRECATE_EOF`.
+pub(crate) fn mock_connection(incoming: Vec<u8>) -> MySqlConnection {
+    let options = MySqlConnectOptions::new();
+    let charset = CharSet::utf8mb4;
+
+    let stream = MySqlStream::with_socket(
+        charset,
+        charset.default_collation(),
+        &options,
+        Box::new(MockSocket::new(incoming)) as Box<dyn Socket>,
+    );
+
+    MySqlConnection {
+        inner: Box::new(MySqlConnectionInner {
+            stream,
+            transaction_depth: 0,
+            session_setup: None,
+            cache_statement: StatementCache::new(options.statement_cache_capacity),
+            cache_statement_schema: options.database.clone(),
+            fetch_size: options.fetch_size,
+            strict_timestamps: options.strict_timestamps,
+            log_settings: options.log_settings.clone(),
+        }),
+    }
+}
+
+/// Poll `future` once; the mock socket never blocks, so neither does anything reading from it.
+pub(crate) fn poll_ready<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(noop_waker_ref())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future should not block on the mock socket"),
+    }
+}
+
+/// Frame `payload` as a packet with the given sequence id.
+pub(crate) fn packet(sequence_id: u8, payload: &[u8]) -> Vec<u8> {
+    let mut packet = u32::try_from(payload.len()).unwrap().to_le_bytes().to_vec();
+    packet[3] = sequence_id;
+    packet.extend_from_slice(payload);
+    packet
+}
@@ -1,5 +1,6 @@
 use std::fmt::{self, Debug, Formatter};
 
+pub(crate) use charset::CHARSET_VARIABLES;
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
 pub(crate) use sqlx_core::connection::*;
@@ -7,21 +8,35 @@ pub(crate) use stream::{MySqlStream, Waiting};
 
 use crate::common::StatementCache;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::protocol::statement::StmtClose;
-use crate::protocol::text::{Ping, Quit};
+use crate::protocol::text::{Ping, Quit, ResetConnection};
 use crate::statement::MySqlStatementMetadata;
 use crate::transaction::Transaction;
-use crate::{MySql, MySqlConnectOptions};
+use crate::{MySql, MySqlConnectOptions, MySqlSessionState, MySqlStatusFlags};
 
 mod auth;
+mod charset;
+mod compression;
 mod establish;
 mod executor;
+#[cfg(test)]
+mod mock;
 mod stream;
 mod tls;
 
 const MAX_PACKET_SIZE: u32 = 1024;
 
 /// A connection to a MySQL database.
+///
+/// ### Resetting Session State
+/// [`Connection::reset()`] sends `COM_RESET_CONNECTION`, which requires MySQL 5.7.3 or
+/// MariaDB 10.2.4. Older servers return an error, so the pool closes the connection instead
+/// if [`reset_on_release`][sqlx_core::pool::PoolOptions::reset_on_release] is enabled.
+///
+/// The server deallocates all prepared statements, so the statement cache is cleared.
+/// The session settings from [`MySqlConnectOptions`] (`sql_mode`, `time_zone` and `NAMES`)
+/// are applied again afterwards.
 pub struct MySqlConnection {
     pub(crate) inner: Box<MySqlConnectionInner>,
 }
@@ -35,12 +50,83 @@ pub(crate) struct MySqlConnectionInner {
     // transaction status
     pub(crate) transaction_depth: usize,
 
+    // `SET` statement run after connecting, which needs to be run again after a reset
+    pub(crate) session_setup: Option<Box<str>>,
+
     // cache by query string to the statement id and metadata
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
+    // the default database the cached statements were prepared in, which they stay bound to
+    cache_statement_schema: Option<String>,
+
+    // the number of rows to fetch at a time with a cursor, or `0` to not use one
+    fetch_size: u32,
+
+    // see `MySqlConnectOptions::strict_timestamps()`
+    strict_timestamps: bool,
+
     log_settings: LogSettings,
 }
 
+impl MySqlConnection {
+    /// The `(major, minor, patch)` version of the server, as reported in its handshake.
+    ///
+    /// For MariaDB, this is the MariaDB version (e.g. `(10, 11, 2)`)
+    /// and not the `5.5.5` it reports for compatibility with old clients.
+    pub fn server_version(&self) -> (u16, u16, u16) {
+        self.inner.stream.server_version
+    }
+
+    /// The status flags sent by the server at the end of the last statement,
+    /// e.g. whether a transaction is open or autocommit is enabled.
+    ///
+    /// This is updated from every OK and EOF packet the server sends.
+    pub fn server_status(&self) -> MySqlStatusFlags {
+        self.inner.stream.status
+    }
+
+    /// The number of warnings produced by the last statement.
+    ///
+    /// The warnings themselves can be read with `SHOW WARNINGS`.
+    pub fn warnings(&self) -> u16 {
+        self.inner.stream.warnings
+    }
+
+    /// The default database of the connection.
+    ///
+    /// This is the database given in the connect options until it is changed, e.g. by `USE`,
+    /// which the server reports if `session_track_schema` is enabled (the default).
+    pub fn database(&self) -> Option<&str> {
+        self.inner.stream.session_state.schema()
+    }
+
+    /// The session state of the connection, as far as the server reported changes of it
+    /// at the end of the statements run so far.
+    ///
+    /// See [`MySqlSessionState`] for what is tracked.
+    pub fn last_session_state(&self) -> &MySqlSessionState {
+        &self.inner.stream.session_state
+    }
+
+    /// The character set of the connection, e.g. `utf8mb4`.
+    ///
+    /// This is the character set given in the connect options, which the connection is checked
+    /// to use after connecting unless [`MySqlConnectOptions::verify_charset()`] is disabled.
+    ///
+    /// [`MySqlConnectOptions::verify_charset()`]: crate::MySqlConnectOptions::verify_charset
+    pub fn charset(&self) -> &str {
+        self.inner.stream.charset.as_str()
+    }
+
+    /// The collation of the connection, e.g. `utf8mb4_unicode_ci`.
+    ///
+    /// This is the collation given in the connect options, or the default collation of
+    /// [the character set][Self::charset].
+    pub fn collation(&self) -> &str {
+        self.inner.stream.collation.as_str()
+    }
+}
+
 impl Debug for MySqlConnection {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("MySqlConnection").finish()
@@ -54,10 +140,14 @@ impl Connection for MySqlConnection {
 
     fn close(mut self) -> BoxFuture<'static, Result<(), Error>> {
         Box::pin(async move {
+            // Finish the work that is still pending first, like the `ROLLBACK` queued by
+            // a dropped transaction, so its locks are released by the time this returns.
+            let pending = self.inner.stream.wait_until_ready().await;
+
             self.inner.stream.send_packet(Quit).await?;
             self.inner.stream.shutdown().await?;
 
-            Ok(())
+            pending
         })
     }
 
@@ -78,6 +168,25 @@ impl Connection for MySqlConnection {
         })
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.inner.stream.wait_until_ready().await?;
+            self.inner.stream.send_packet(ResetConnection).await?;
+            self.inner.stream.recv_ok().await?;
+
+            // the server rolled back any transaction and deallocated all prepared statements
+            self.inner.transaction_depth = 0;
+            self.inner.cache_statement.clear();
+            self.inner.stream.session_state.reset();
+
+            if let Some(setup) = self.inner.session_setup.clone() {
+                self.unmetered().execute(&*setup).await?;
+            }
+
+            Ok(())
+        })
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.inner.stream.wait_until_ready().boxed()
@@ -107,6 +216,11 @@ impl Connection for MySqlConnection {
         !self.inner.stream.write_buffer().is_empty()
     }
 
+    #[doc(hidden)]
+    fn take_fatal_error(&mut self) -> Option<Error> {
+        self.inner.stream.fatal_error.take()
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
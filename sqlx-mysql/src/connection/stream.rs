@@ -1,16 +1,23 @@
 use std::collections::VecDeque;
+use std::io;
 use std::ops::{Deref, DerefMut};
 
 use bytes::{Buf, Bytes, BytesMut};
+use sqlx_core::pool::is_connection_fatal;
 
 use crate::collation::{CharSet, Collation};
+use crate::connection::compression::{self, CompressedHeader, COMPRESSED_HEADER_LEN};
 use crate::error::Error;
+#[cfg(feature = "debug-protocol")]
+use crate::inspect;
 use crate::io::MySqlBufExt;
 use crate::io::{ProtocolDecode, ProtocolEncode};
 use crate::net::{BufferedSocket, Socket};
-use crate::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
+#[cfg(feature = "debug-protocol")]
+use crate::net::{Direction, PacketInspector};
+use crate::protocol::response::{EofPacket, ErrPacket, MySqlSessionState, OkPacket, Status};
 use crate::protocol::{Capabilities, Packet};
-use crate::{MySqlConnectOptions, MySqlDatabaseError};
+use crate::{MySqlCompression, MySqlConnectOptions, MySqlDatabaseError};
 
 pub struct MySqlStream<S = Box<dyn Socket>> {
     // Wrapping the socket in `Box` allows us to unsize in-place.
@@ -22,6 +29,25 @@ pub struct MySqlStream<S = Box<dyn Socket>> {
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
     pub(crate) is_tls: bool,
+    // the compression requested by the options; only used once negotiated
+    pub(super) compression: MySqlCompression,
+    // set after the handshake, if the server agreed to compression
+    pub(super) compression_enabled: bool,
+    pub(super) compressed_sequence_id: u8,
+    // packet data that was decompressed but not yet read
+    pub(super) decompressed: BytesMut,
+    // the server's `max_allowed_packet`, once it has been read after connecting
+    pub(crate) max_allowed_packet: Option<usize>,
+    // the status flags and warning count of the last OK or EOF packet
+    pub(crate) status: Status,
+    pub(crate) warnings: u16,
+    // the session state, as far as the server reported changes of it
+    pub(crate) session_state: MySqlSessionState,
+    // the last error meaning the server has likely gone away, for the pool to see on release
+    pub(crate) fatal_error: Option<Error>,
+    // invoked for every packet read or written, if set by the user
+    #[cfg(feature = "debug-protocol")]
+    pub(crate) packet_inspector: Option<PacketInspector>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -57,6 +83,14 @@ impl<S: Socket> MySqlStream<S> {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
+        match options.compression {
+            MySqlCompression::None => {}
+            MySqlCompression::Zlib => capabilities |= Capabilities::COMPRESS,
+            MySqlCompression::Zstd { .. } => {
+                capabilities |= Capabilities::ZSTD_COMPRESSION_ALGORITHM;
+            }
+        }
+
         Self {
             waiting: VecDeque::new(),
             capabilities,
@@ -66,20 +100,54 @@ impl<S: Socket> MySqlStream<S> {
             charset,
             socket: BufferedSocket::new(socket),
             is_tls: false,
+            compression: options.compression,
+            compression_enabled: false,
+            compressed_sequence_id: 0,
+            decompressed: BytesMut::new(),
+            max_allowed_packet: None,
+            status: Status::empty(),
+            warnings: 0,
+            session_state: MySqlSessionState::new(options.database.clone()),
+            fatal_error: None,
+            #[cfg(feature = "debug-protocol")]
+            packet_inspector: options.packet_inspector.clone(),
+        }
+    }
+
+    /// Switch to the compressed protocol if it was negotiated during the handshake.
+    ///
+    /// Must be called right after authentication completes.
+    pub(crate) fn maybe_enable_compression(&mut self) {
+        self.compression_enabled = match self.compression {
+            MySqlCompression::None => false,
+            MySqlCompression::Zlib => self.capabilities.contains(Capabilities::COMPRESS),
+            MySqlCompression::Zstd { .. } => self
+                .capabilities
+                .contains(Capabilities::ZSTD_COMPRESSION_ALGORITHM),
+        };
+
+        if self.compression != MySqlCompression::None && !self.compression_enabled {
+            log::debug!(
+                "server does not support {:?} compression; continuing uncompressed",
+                self.compression
+            );
         }
     }
 
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.socket.write_buffer().is_empty() {
-            self.socket.flush().await?;
+            self.flush().await?;
         }
 
         while !self.waiting.is_empty() {
             while self.waiting.front() == Some(&Waiting::Row) {
-                let packet = self.recv_packet().await?;
+                let Some(packet) = self.recv_abandoned_packet().await? else {
+                    continue;
+                };
 
-                if !packet.is_empty() && packet[0] == 0xfe && packet.len() < 9 {
+                if packet.is_result_set_end(self.capabilities) {
                     let eof = packet.eof(self.capabilities)?;
+                    self.set_status(eof.status, eof.warnings);
 
                     if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         *self.waiting.front_mut().unwrap() = Waiting::Result;
@@ -90,17 +158,24 @@ impl<S: Socket> MySqlStream<S> {
             }
 
             while self.waiting.front() == Some(&Waiting::Result) {
-                let packet = self.recv_packet().await?;
+                let Some(packet) = self.recv_abandoned_packet().await? else {
+                    continue;
+                };
 
                 if !packet.is_empty() && (packet[0] == 0x00 || packet[0] == 0xff) {
                     let ok = packet.ok()?;
+                    self.set_ok_status(&ok);
 
                     if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         self.waiting.pop_front();
                     }
                 } else {
                     *self.waiting.front_mut().unwrap() = Waiting::Row;
-                    self.skip_result_metadata(packet).await?;
+
+                    if self.skip_result_metadata(packet).await? {
+                        // the rows of a cursor are only sent when fetched
+                        self.waiting.pop_front();
+                    }
                 }
             }
         }
@@ -108,11 +183,33 @@ impl<S: Socket> MySqlStream<S> {
         Ok(())
     }
 
+    // receive the next packet of a result that is no longer read by anyone, e.g. because the
+    // stream of rows was dropped; an ERR packet in place of a result or a row ends the response
+    // and is discarded with it, instead of failing whatever is run next on the connection
+    async fn recv_abandoned_packet(&mut self) -> Result<Option<Packet<Bytes>>, Error> {
+        match self.recv_packet().await {
+            Ok(packet) => Ok(Some(packet)),
+
+            Err(Error::Database(e)) => {
+                log::debug!("discarding error of an abandoned result: {e}");
+                Ok(None)
+            }
+
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reset the sequence ids for a new command, before its first packet is written.
+    pub(crate) fn begin_command(&mut self) {
+        self.sequence_id = 0;
+        self.compressed_sequence_id = 0;
+    }
+
     pub(crate) async fn send_packet<'en, T>(&mut self, payload: T) -> Result<(), Error>
     where
         T: ProtocolEncode<'en, Capabilities>,
     {
-        self.sequence_id = 0;
+        self.begin_command();
         self.write_packet(payload)?;
         self.flush().await?;
         Ok(())
@@ -122,15 +219,109 @@ impl<S: Socket> MySqlStream<S> {
     where
         T: ProtocolEncode<'en, Capabilities>,
     {
-        self.socket
-            .write_with(Packet(payload), (self.capabilities, &mut self.sequence_id))
+        let payload = MaxAllowedPacket {
+            payload,
+            max_allowed_packet: self.max_allowed_packet,
+        };
+
+        if !self.compression_enabled {
+            #[cfg(feature = "debug-protocol")]
+            let offset = self.socket.write_buffer().get().len();
+
+            self.socket
+                .write_with(Packet(payload), (self.capabilities, &mut self.sequence_id))?;
+
+            #[cfg(feature = "debug-protocol")]
+            if let Some(inspector) = &self.packet_inspector {
+                let packets = &self.socket.write_buffer().get()[offset..];
+                inspect::inspect_packets(inspector, Direction::Sent, packets);
+            }
+
+            return Ok(());
+        }
+
+        let mut packets = Vec::new();
+        Packet(payload).encode_with(&mut packets, (self.capabilities, &mut self.sequence_id))?;
+
+        #[cfg(feature = "debug-protocol")]
+        if let Some(inspector) = &self.packet_inspector {
+            inspect::inspect_packets(inspector, Direction::Sent, &packets);
+        }
+
+        let mut frames = Vec::new();
+        compression::encode_frames(
+            self.compression,
+            &packets,
+            &mut self.compressed_sequence_id,
+            &mut frames,
+        )?;
+
+        self.socket.write(&frames[..])
+    }
+
+    pub(crate) async fn flush(&mut self) -> io::Result<()> {
+        let res = self.socket.flush().await;
+
+        if let Err(e) = &res {
+            self.record_if_fatal(&Error::Io(io::Error::new(e.kind(), e.to_string())));
+        }
+
+        res
+    }
+
+    async fn read_socket(&mut self, len: usize) -> Result<Bytes, Error> {
+        let res = self.socket.read(len).await;
+
+        if let Err(e) = &res {
+            self.record_if_fatal(e);
+        }
+
+        res
+    }
+
+    // Keep a copy of `error` if it means the server has likely gone away
+    fn record_if_fatal(&mut self, error: &Error) {
+        if !is_connection_fatal(error) {
+            return;
+        }
+
+        self.fatal_error = match error {
+            Error::Io(e) => Some(Error::Io(io::Error::new(e.kind(), e.to_string()))),
+            Error::Database(e) => e
+                .try_downcast_ref::<MySqlDatabaseError>()
+                .cloned()
+                .map(Into::into),
+            _ => return,
+        };
+    }
+
+    // read the next `len` bytes of packet data, decompressing frames as necessary
+    async fn read_packet_data(&mut self, len: usize) -> Result<Bytes, Error> {
+        if !self.compression_enabled {
+            return self.read_socket(len).await;
+        }
+
+        while self.decompressed.len() < len {
+            let header = CompressedHeader::decode(self.read_socket(COMPRESSED_HEADER_LEN).await?);
+
+            self.compressed_sequence_id = header.sequence_id.wrapping_add(1);
+
+            let payload: Bytes = self.read_socket(header.payload_len).await?;
+
+            compression::decode_frame(self.compression, &header, payload, &mut self.decompressed)?;
+        }
+
+        Ok(self.decompressed.split_to(len).freeze())
     }
 
     async fn recv_packet_part(&mut self) -> Result<Bytes, Error> {
         // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_basic_packets.html
         // https://mariadb.com/kb/en/library/0-packet/#standard-packet
 
-        let mut header: Bytes = self.socket.read(4).await?;
+        let mut header: Bytes = self.read_packet_data(4).await?;
+
+        #[cfg(feature = "debug-protocol")]
+        let raw_header = header.clone();
 
         // cannot overflow
         #[allow(clippy::cast_possible_truncation)]
@@ -139,9 +330,13 @@ impl<S: Socket> MySqlStream<S> {
 
         self.sequence_id = sequence_id.wrapping_add(1);
 
-        let payload: Bytes = self.socket.read(packet_size).await?;
+        let payload: Bytes = self.read_packet_data(packet_size).await?;
 
-        // TODO: packet compression
+        #[cfg(feature = "debug-protocol")]
+        if let Some(inspector) = &self.packet_inspector {
+            let packet = [&raw_header[..], &payload[..]].concat();
+            inspector.call(Direction::Received, &packet);
+        }
 
         Ok(payload)
     }
@@ -176,9 +371,11 @@ impl<S: Socket> MySqlStream<S> {
 
             // instead of letting this packet be looked at everywhere, we check here
             // and emit a proper Error
-            return Err(
-                MySqlDatabaseError(ErrPacket::decode_with(payload, self.capabilities)?).into(),
-            );
+            let error: Error =
+                MySqlDatabaseError(ErrPacket::decode_with(payload, self.capabilities)?).into();
+            self.record_if_fatal(&error);
+
+            return Err(error);
         }
 
         Ok(Packet(payload))
@@ -192,27 +389,49 @@ impl<S: Socket> MySqlStream<S> {
     }
 
     pub(crate) async fn recv_ok(&mut self) -> Result<OkPacket, Error> {
-        self.recv_packet().await?.ok()
+        let ok = self.recv_packet().await?.ok()?;
+        self.set_ok_status(&ok);
+
+        Ok(ok)
     }
 
     pub(crate) async fn maybe_recv_eof(&mut self) -> Result<Option<EofPacket>, Error> {
         if self.capabilities.contains(Capabilities::DEPRECATE_EOF) {
             Ok(None)
         } else {
-            self.recv().await.map(Some)
+            let eof: EofPacket = self.recv().await?;
+            self.set_status(eof.status, eof.warnings);
+
+            Ok(Some(eof))
         }
     }
 
-    async fn skip_result_metadata(&mut self, mut packet: Packet<Bytes>) -> Result<(), Error> {
+    /// Record the status flags and warning count of an OK or EOF packet,
+    /// which are exposed on the connection.
+    pub(crate) fn set_status(&mut self, status: Status, warnings: u16) {
+        self.status = status;
+        self.warnings = warnings;
+    }
+
+    /// Record the status flags, warning count and session state changes of an OK packet.
+    pub(crate) fn set_ok_status(&mut self, ok: &OkPacket) {
+        self.set_status(ok.status, ok.warnings);
+        self.session_state.apply(&ok.session_state_changes);
+    }
+
+    /// Returns `true` if the metadata ends with an EOF packet that says a cursor was opened.
+    ///
+    /// With `DEPRECATE_EOF`, this is instead indicated by the packet that would end the rows.
+    async fn skip_result_metadata(&mut self, mut packet: Packet<Bytes>) -> Result<bool, Error> {
         let num_columns: u64 = packet.get_uint_lenenc(); // column count
 
         for _ in 0..num_columns {
             let _ = self.recv_packet().await?;
         }
 
-        self.maybe_recv_eof().await?;
+        let eof = self.maybe_recv_eof().await?;
 
-        Ok(())
+        Ok(eof.is_some_and(|eof| eof.status.contains(Status::SERVER_STATUS_CURSOR_EXISTS)))
     }
 
     pub fn boxed_socket(self) -> MySqlStream {
@@ -225,6 +444,52 @@ impl<S: Socket> MySqlStream<S> {
             charset: self.charset,
             collation: self.collation,
             is_tls: self.is_tls,
+            compression: self.compression,
+            compression_enabled: self.compression_enabled,
+            compressed_sequence_id: self.compressed_sequence_id,
+            decompressed: self.decompressed,
+            max_allowed_packet: self.max_allowed_packet,
+            status: self.status,
+            warnings: self.warnings,
+            session_state: self.session_state,
+            fatal_error: self.fatal_error,
+            #[cfg(feature = "debug-protocol")]
+            packet_inspector: self.packet_inspector,
+        }
+    }
+}
+
+/// Checks the length of an encoded payload against the server's `max_allowed_packet`.
+///
+/// The server responds to a larger payload by closing the connection, so it's better
+/// not to send it at all. Nothing is written to the stream if the check fails.
+struct MaxAllowedPacket<T> {
+    payload: T,
+    max_allowed_packet: Option<usize>,
+}
+
+impl<'en, T> ProtocolEncode<'en, Capabilities> for MaxAllowedPacket<T>
+where
+    T: ProtocolEncode<'en, Capabilities>,
+{
+    fn encode_with(&self, buf: &mut Vec<u8>, capabilities: Capabilities) -> Result<(), Error> {
+        let offset = buf.len();
+
+        self.payload.encode_with(buf, capabilities)?;
+
+        let len = buf.len() - offset;
+
+        match self.max_allowed_packet {
+            Some(max_allowed_packet) if len > max_allowed_packet => {
+                buf.truncate(offset);
+
+                Err(Error::InvalidArgument(format!(
+                    "packet of {len} bytes exceeds the server's max_allowed_packet of \
+                     {max_allowed_packet} bytes; the query or its arguments are too large"
+                )))
+            }
+
+            _ => Ok(()),
         }
     }
 }
@@ -242,3 +507,203 @@ impl<S> DerefMut for MySqlStream<S> {
         &mut self.socket
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::mock::{mock_connection, packet, poll_ready, MockSocket};
+    use crate::protocol::text::Query;
+    use crate::transaction::MySqlTransactionManager;
+    use sqlx_core::transaction::TransactionManager;
+
+    const MAX_PACKET_LEN: usize = 0xFF_FF_FF;
+
+    fn mock_stream(incoming: Vec<u8>) -> MySqlStream<MockSocket> {
+        let charset = CharSet::utf8mb4;
+
+        MySqlStream::with_socket(
+            charset,
+            charset.default_collation(),
+            &MySqlConnectOptions::new(),
+            MockSocket::new(incoming),
+        )
+    }
+
+    // (payload length, sequence id) of each packet
+    fn packet_headers(mut buf: &[u8]) -> Vec<(usize, u8)> {
+        let mut headers = Vec::new();
+
+        while !buf.is_empty() {
+            let len = usize::try_from(buf.get_uint_le(3)).unwrap();
+            headers.push((len, buf.get_u8()));
+            buf.advance(len);
+        }
+
+        headers
+    }
+
+    fn packet_header(len: usize, sequence_id: u8) -> [u8; 4] {
+        let mut header = u32::try_from(len).unwrap().to_le_bytes();
+        header[3] = sequence_id;
+        header
+    }
+
+    #[test]
+    fn it_splits_large_packets() {
+        let mut stream = mock_stream(Vec::new());
+        let sql = "x".repeat(17 * 1024 * 1024);
+
+        stream.write_packet(Query(&sql)).unwrap();
+
+        // COM_QUERY + the query
+        let payload_len = 1 + sql.len();
+
+        assert_eq!(
+            packet_headers(stream.socket.write_buffer().get()),
+            [(MAX_PACKET_LEN, 0), (payload_len - MAX_PACKET_LEN, 1)]
+        );
+        assert_eq!(stream.sequence_id, 2);
+    }
+
+    #[test]
+    fn it_terminates_split_packets_with_an_empty_packet() {
+        let mut stream = mock_stream(Vec::new());
+        let sql = "x".repeat(MAX_PACKET_LEN - 1);
+
+        stream.write_packet(Query(&sql)).unwrap();
+
+        assert_eq!(
+            packet_headers(stream.socket.write_buffer().get()),
+            [(MAX_PACKET_LEN, 0), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn it_reassembles_split_packets() {
+        let payload: Vec<u8> = (0..MAX_PACKET_LEN + 1000)
+            .map(|i| u8::try_from(i % 251).unwrap())
+            .collect();
+
+        let mut incoming = Vec::new();
+        incoming.extend(packet_header(MAX_PACKET_LEN, 1));
+        incoming.extend(&payload[..MAX_PACKET_LEN]);
+        incoming.extend(packet_header(1000, 2));
+        incoming.extend(&payload[MAX_PACKET_LEN..]);
+
+        let mut stream = mock_stream(incoming);
+        let packet = poll_ready(stream.recv_packet()).unwrap();
+
+        assert_eq!(packet.len(), payload.len());
+        assert!(packet[..] == payload[..]);
+        assert_eq!(stream.sequence_id, 3);
+    }
+
+    #[test]
+    fn it_starts_a_new_compressed_sequence_for_a_queued_rollback() {
+        let mut conn = mock_connection(Vec::new());
+        let stream = &mut conn.inner.stream;
+
+        stream.compression = MySqlCompression::Zlib;
+        stream.compression_enabled = true;
+
+        // as left by the response to the last command
+        stream.sequence_id = 2;
+        stream.compressed_sequence_id = 3;
+
+        conn.inner.transaction_depth = 1;
+
+        // a dropped transaction queues its `ROLLBACK` without flushing it
+        MySqlTransactionManager::start_rollback(&mut conn);
+
+        let written = conn.inner.stream.socket.write_buffer().get();
+        let header =
+            CompressedHeader::decode(Bytes::copy_from_slice(&written[..COMPRESSED_HEADER_LEN]));
+
+        assert_eq!(header.sequence_id, 0);
+
+        // COM_QUERY + `ROLLBACK`, too short to be compressed
+        assert_eq!(packet_headers(&written[COMPRESSED_HEADER_LEN..]), [(9, 0)]);
+    }
+
+    #[test]
+    fn it_records_status_and_warnings_of_ok_packets() {
+        // OK, 0 rows affected, last insert id 0, IN_TRANS | AUTOCOMMIT, 2 warnings
+        let incoming = packet(1, &[0x00, 0, 0, 0x03, 0, 0x02, 0]);
+
+        let mut stream = mock_stream(incoming);
+        poll_ready(stream.recv_ok()).unwrap();
+
+        assert_eq!(
+            stream.status,
+            Status::SERVER_STATUS_IN_TRANS | Status::SERVER_STATUS_AUTOCOMMIT
+        );
+        assert_eq!(stream.warnings, 2);
+    }
+
+    #[test]
+    fn it_rejects_packets_larger_than_max_allowed_packet() {
+        let mut stream = mock_stream(Vec::new());
+        stream.max_allowed_packet = Some(1024);
+
+        let err = stream.write_packet(Query(&"x".repeat(1024))).unwrap_err();
+
+        assert!(
+            matches!(&err, Error::InvalidArgument(msg) if msg.contains("max_allowed_packet")),
+            "{err:?}"
+        );
+
+        // nothing was written
+        assert!(stream.socket.write_buffer().is_empty());
+        assert_eq!(stream.sequence_id, 0);
+
+        stream.write_packet(Query(&"x".repeat(1023))).unwrap();
+
+        assert_eq!(
+            packet_headers(stream.socket.write_buffer().get()),
+            [(1024, 0)]
+        );
+    }
+
+    #[cfg(feature = "debug-protocol")]
+    #[test]
+    fn it_passes_packets_to_the_inspector() {
+        use std::sync::{Arc, Mutex};
+
+        let frames = Arc::new(Mutex::new(Vec::new()));
+
+        let options = MySqlConnectOptions::new().packet_inspector({
+            let frames = frames.clone();
+            move |direction, frame| frames.lock().unwrap().push((direction, frame.to_vec()))
+        });
+
+        let charset = CharSet::utf8mb4;
+        let ok = packet(1, &[0x00, 0, 0, 0x02, 0, 0, 0]);
+
+        let mut stream = MySqlStream::with_socket(
+            charset,
+            charset.default_collation(),
+            &options,
+            MockSocket::new(ok.clone()),
+        );
+
+        stream.write_packet(Query("SELECT 1")).unwrap();
+        poll_ready(stream.recv_ok()).unwrap();
+
+        let frames = frames.lock().unwrap();
+
+        assert_eq!(
+            *frames,
+            [
+                (Direction::Sent, packet(0, b"\x03SELECT 1")),
+                (Direction::Received, ok)
+            ]
+        );
+
+        let names: Vec<_> = frames
+            .iter()
+            .map(|(direction, frame)| inspect::frame_name(*direction, frame))
+            .collect();
+
+        assert_eq!(names, [Some("COM_QUERY"), Some("OK")]);
+    }
+}
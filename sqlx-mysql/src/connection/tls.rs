@@ -2,10 +2,14 @@ use crate::collation::{CharSet, Collation};
 use crate::connection::{MySqlStream, Waiting};
 use crate::error::Error;
 use crate::net::tls::TlsConfig;
+#[cfg(feature = "debug-protocol")]
+use crate::net::PacketInspector;
 use crate::net::{tls, BufferedSocket, Socket, WithSocket};
 use crate::protocol::connect::SslRequest;
 use crate::protocol::Capabilities;
-use crate::{MySqlConnectOptions, MySqlSslMode};
+use crate::{
+    MySqlCompression, MySqlConnectOptions, MySqlSessionState, MySqlSslMode, MySqlStatusFlags,
+};
 use std::collections::VecDeque;
 
 struct MapStream {
@@ -15,6 +19,11 @@ struct MapStream {
     waiting: VecDeque<Waiting>,
     charset: CharSet,
     collation: Collation,
+    compression: MySqlCompression,
+    status: MySqlStatusFlags,
+    session_state: MySqlSessionState,
+    #[cfg(feature = "debug-protocol")]
+    packet_inspector: Option<PacketInspector>,
 }
 
 pub(super) async fn maybe_upgrade<S: Socket>(
@@ -86,6 +95,11 @@ pub(super) async fn maybe_upgrade<S: Socket>(
             waiting: stream.waiting,
             charset: stream.charset,
             collation: stream.collation,
+            compression: stream.compression,
+            status: stream.status,
+            session_state: stream.session_state,
+            #[cfg(feature = "debug-protocol")]
+            packet_inspector: stream.packet_inspector,
         },
     )
     .await
@@ -104,6 +118,18 @@ impl WithSocket for MapStream {
             charset: self.charset,
             collation: self.collation,
             is_tls: true,
+            compression: self.compression,
+            // compression is only enabled after authentication, which follows the TLS upgrade
+            compression_enabled: false,
+            compressed_sequence_id: 0,
+            decompressed: Default::default(),
+            max_allowed_packet: None,
+            status: self.status,
+            warnings: 0,
+            session_state: self.session_state,
+            fatal_error: None,
+            #[cfg(feature = "debug-protocol")]
+            packet_inspector: self.packet_inspector,
         }
     }
 }
@@ -4,6 +4,7 @@ use crate::{
     MySqlTransactionManager, MySqlTypeInfo,
 };
 pub(crate) use sqlx_core::database::{Database, HasStatementCache};
+use sqlx_core::query_builder::UpsertSyntax;
 
 /// MySQL database driver.
 #[derive(Debug)]
@@ -33,6 +34,10 @@ impl Database for MySql {
     const NAME: &'static str = "MySQL";
 
     const URL_SCHEMES: &'static [&'static str] = &["mysql", "mariadb"];
+
+    const IDENTIFIER_QUOTE: char = '`';
+
+    const UPSERT_SYNTAX: UpsertSyntax = UpsertSyntax::OnDuplicateKey;
 }
 
 impl HasStatementCache for MySql {}
@@ -8,6 +8,7 @@ use std::borrow::Cow;
 pub(crate) use sqlx_core::error::*;
 
 /// An error returned from the MySQL database.
+#[derive(Clone)]
 pub struct MySqlDatabaseError(pub(super) ErrPacket);
 
 impl MySqlDatabaseError {
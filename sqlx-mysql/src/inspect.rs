@@ -0,0 +1,86 @@
+//! Naming and formatting the packets passed to a
+//! [packet inspector][crate::MySqlConnectOptions::packet_inspector].
+//!
+//! <https://dev.mysql.com/doc/dev/mysql-server/latest/PAGE_PROTOCOL.html>
+use std::cmp;
+
+use crate::net::{self, PacketInspector};
+
+pub use crate::net::Direction;
+
+/// The length of the header of a packet: the payload length and the sequence ID.
+const HEADER_LEN: usize = 4;
+
+/// Returns the name of the packet in `frame`, e.g. `"COM_QUERY"` or `"OK"`,
+/// or `None` if it can't be determined.
+///
+/// Packets sent by the server are named by their first byte only, as their meaning
+/// depends on the command they respond to: result set metadata and rows are named `"Data"`,
+/// and rows of the binary protocol are named `"OK"`.
+pub fn frame_name(direction: Direction, frame: &[u8]) -> Option<&'static str> {
+    let sequence_id = *frame.get(HEADER_LEN - 1)?;
+    let payload = frame.get(HEADER_LEN..)?;
+
+    Some(match direction {
+        // only the first packet of a command has a sequence ID of 0
+        Direction::Sent if sequence_id == 0 => match payload.first()? {
+            0x01 => "COM_QUIT",
+            0x02 => "COM_INIT_DB",
+            0x03 => "COM_QUERY",
+            0x04 => "COM_FIELD_LIST",
+            0x09 => "COM_STATISTICS",
+            0x0e => "COM_PING",
+            0x11 => "COM_CHANGE_USER",
+            0x16 => "COM_STMT_PREPARE",
+            0x17 => "COM_STMT_EXECUTE",
+            0x18 => "COM_STMT_SEND_LONG_DATA",
+            0x19 => "COM_STMT_CLOSE",
+            0x1a => "COM_STMT_RESET",
+            0x1b => "COM_SET_OPTION",
+            0x1c => "COM_STMT_FETCH",
+            0x1f => "COM_RESET_CONNECTION",
+            _ => return None,
+        },
+
+        // an `SSLRequest` is the start of a `HandshakeResponse`, which is always longer
+        Direction::Sent if sequence_id == 1 && payload.len() == 32 => "SSLRequest",
+        Direction::Sent if sequence_id == 1 => "HandshakeResponse",
+        Direction::Sent => return None,
+
+        Direction::Received => match payload.first()? {
+            0x0a if sequence_id == 0 => "Handshake",
+            0x00 => "OK",
+            0xfe if payload.len() < 9 => "EOF",
+            // an OK packet in place of an EOF packet, or an `AuthSwitchRequest`
+            0xfe => "OK",
+            0xff => "ERR",
+            _ => "Data",
+        },
+    })
+}
+
+/// Formats the packet in `frame` for printing, as its direction, name, length,
+/// and a hex dump of its first bytes.
+///
+/// ```text
+/// -> COM_PING (5 bytes): 01 00 00 00 0e
+/// ```
+pub fn format_frame(direction: Direction, frame: &[u8]) -> String {
+    net::format_frame(
+        direction,
+        frame_name(direction, frame).unwrap_or("Unknown"),
+        frame,
+    )
+}
+
+/// Passes every packet in `buf`, which holds whole packets, to `inspector`.
+pub(crate) fn inspect_packets(inspector: &PacketInspector, direction: Direction, mut buf: &[u8]) {
+    while buf.len() >= HEADER_LEN {
+        let payload_len =
+            usize::from(buf[0]) | usize::from(buf[1]) << 8 | usize::from(buf[2]) << 16;
+        let (frame, rest) = buf.split_at(cmp::min(HEADER_LEN + payload_len, buf.len()));
+
+        inspector.call(direction, frame);
+        buf = rest;
+    }
+}
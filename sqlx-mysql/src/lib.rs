@@ -14,11 +14,14 @@ pub(crate) use sqlx_core::driver_prelude::*;
 pub mod any;
 
 mod arguments;
+mod call;
 mod collation;
 mod column;
 mod connection;
 mod database;
 mod error;
+#[cfg(feature = "debug-protocol")]
+pub mod inspect;
 mod io;
 mod options;
 mod protocol;
@@ -38,15 +41,18 @@ mod migrate;
 mod testing;
 
 pub use arguments::MySqlArguments;
+pub use call::{MySqlCall, MySqlCallResult};
 pub use column::MySqlColumn;
 pub use connection::MySqlConnection;
 pub use database::MySql;
 pub use error::MySqlDatabaseError;
-pub use options::{MySqlConnectOptions, MySqlSslMode};
+pub use options::{MySqlCompression, MySqlConnectOptions, MySqlSslMode};
+pub use protocol::response::MySqlSessionState;
+pub use protocol::response::Status as MySqlStatusFlags;
 pub use query_result::MySqlQueryResult;
 pub use row::MySqlRow;
 pub use statement::MySqlStatement;
-pub use transaction::MySqlTransactionManager;
+pub use transaction::{MySqlTransactionManager, MySqlTransactionOptions};
 pub use type_info::MySqlTypeInfo;
 pub use value::{MySqlValue, MySqlValueFormat, MySqlValueRef};
 
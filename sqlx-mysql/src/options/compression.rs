@@ -0,0 +1,27 @@
+/// Options for compressing traffic between the client and the MySQL server.
+///
+/// It is used by the [`compression`](super::MySqlConnectOptions::compression) method.
+///
+/// Compression is only used if the server also supports the chosen algorithm;
+/// otherwise the connection silently falls back to the uncompressed protocol.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MySqlCompression {
+    /// Do not compress traffic.
+    ///
+    /// This is the default if `compression` is not specified.
+    #[default]
+    None,
+
+    /// Compress traffic using zlib.
+    ///
+    /// Requires the `mysql-compression-zlib` feature.
+    Zlib,
+
+    /// Compress traffic using zstd at the given level (1-22).
+    ///
+    /// Requires the `mysql-compression-zstd` feature and MySQL 8.0.18 or later.
+    Zstd {
+        /// The compression level, which is also sent to the server for the traffic it sends.
+        level: u8,
+    },
+}
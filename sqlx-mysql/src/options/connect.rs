@@ -1,10 +1,13 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, CHARSET_VARIABLES};
 use crate::error::Error;
 use crate::executor::Executor;
+use crate::row::Row;
 use crate::{MySqlConnectOptions, MySqlConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
+use sqlx_core::metrics::Metrics;
 use sqlx_core::Url;
+use std::sync::Arc;
 use std::time::Duration;
 
 impl ConnectOptions for MySqlConnectOptions {
@@ -77,8 +80,30 @@ impl ConnectOptions for MySqlConnectOptions {
             }
 
             if !options.is_empty() {
-                conn.execute(&*format!(r#"SET {};"#, options.join(",")))
-                    .await?;
+                let setup = format!(r#"SET {};"#, options.join(","));
+                conn.unmetered().execute(&*setup).await?;
+
+                conn.inner.session_setup = Some(setup.into());
+            }
+
+            // The character set variables are fetched along with `max_allowed_packet`
+            // to save a round trip when verifying them.
+            let row = conn
+                .unmetered()
+                .fetch_one(&*format!(
+                    "SELECT {}, CAST(@@max_allowed_packet AS UNSIGNED)",
+                    CHARSET_VARIABLES.join(", ")
+                ))
+                .await?;
+
+            // The server closes the connection if it receives a packet larger than this,
+            // so we check outgoing packets against it ourselves. It is read-only for a session.
+            let max_allowed_packet: u64 = row.try_get(CHARSET_VARIABLES.len())?;
+
+            conn.inner.stream.max_allowed_packet = usize::try_from(max_allowed_packet).ok();
+
+            if self.verify_charset.unwrap_or(self.set_names) {
+                conn.verify_charset_variables(&row).await?;
             }
 
             Ok(conn)
@@ -94,4 +119,9 @@ impl ConnectOptions for MySqlConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.log_settings.metrics(metrics);
+        self
+    }
 }
@@ -1,10 +1,17 @@
+use std::fmt::{self, Debug, Formatter};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+mod compression;
 mod connect;
 mod parse;
 mod ssl_mode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::connection::LogSettings;
+use crate::net::{tls::CertificateInput, SocketConnector};
+#[cfg(feature = "debug-protocol")]
+use crate::net::{Direction, PacketInspector};
+pub use compression::MySqlCompression;
 pub use ssl_mode::MySqlSslMode;
 
 /// Options and flags which can be used to configure a MySQL connection.
@@ -21,6 +28,13 @@ pub use ssl_mode::MySqlSslMode;
 /// This type also implements [`FromStr`][std::str::FromStr] so you can parse it from a string
 /// containing a connection URL and then further adjust options if necessary (see example below).
 ///
+/// IPv6 addresses are given in brackets, e.g. `mysql://root@[::1]:3306/db`, and a
+/// percent-encoded host starting with `/` is used as the path to a unix domain socket,
+/// e.g. `mysql://root@%2Fvar%2Frun%2Fmysqld%2Fmysqld.sock/db`.
+///
+/// Userinfo is percent-decoded. An explicitly empty password (`mysql://user:@host/db`) is kept
+/// as an empty password rather than no password when parsing from a string.
+///
 /// ## Properties
 ///
 /// |Parameter|Default|Description|
@@ -28,6 +42,8 @@ pub use ssl_mode::MySqlSslMode;
 /// | `ssl-mode` | `PREFERRED` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`MySqlSslMode`]. |
 /// | `ssl-ca` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
+/// | `fetch-size` | `0` | The number of rows to fetch at a time with a cursor. Set to `0` to disable. See [`MySqlConnectOptions::fetch_size()`]. |
+/// | `strict-timestamps` | `false` | Refuse to decode `DATETIME` as a date and time with a time zone, and `TIMESTAMP` as one without. See [`MySqlConnectOptions::strict_timestamps()`]. |
 /// | `socket` | `None` | Path to the unix domain socket, which will be used instead of TCP if set. |
 ///
 /// # Example
@@ -59,11 +75,12 @@ pub use ssl_mode::MySqlSslMode;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MySqlConnectOptions {
     pub(crate) host: String,
     pub(crate) port: u16,
     pub(crate) socket: Option<PathBuf>,
+    pub(crate) socket_connector: Option<Arc<dyn SocketConnector>>,
     pub(crate) username: String,
     pub(crate) password: Option<String>,
     pub(crate) database: Option<String>,
@@ -72,6 +89,8 @@ pub struct MySqlConnectOptions {
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) fetch_size: u32,
+    pub(crate) strict_timestamps: bool,
     pub(crate) charset: String,
     pub(crate) collation: Option<String>,
     pub(crate) log_settings: LogSettings,
@@ -80,6 +99,10 @@ pub struct MySqlConnectOptions {
     pub(crate) no_engine_substitution: bool,
     pub(crate) timezone: Option<String>,
     pub(crate) set_names: bool,
+    pub(crate) verify_charset: Option<bool>,
+    pub(crate) compression: MySqlCompression,
+    #[cfg(feature = "debug-protocol")]
+    pub(crate) packet_inspector: Option<PacketInspector>,
 }
 
 impl Default for MySqlConnectOptions {
@@ -88,6 +111,43 @@ impl Default for MySqlConnectOptions {
     }
 }
 
+// The password is redacted so the options can be logged safely.
+impl Debug for MySqlConnectOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("MySqlConnectOptions");
+
+        s.field("host", &self.host)
+            .field("port", &self.port)
+            .field("socket", &self.socket)
+            .field("socket_connector", &self.socket_connector)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "********"))
+            .field("database", &self.database)
+            .field("ssl_mode", &self.ssl_mode)
+            .field("ssl_ca", &self.ssl_ca)
+            .field("ssl_client_cert", &self.ssl_client_cert)
+            .field("ssl_client_key", &self.ssl_client_key)
+            .field("statement_cache_capacity", &self.statement_cache_capacity)
+            .field("fetch_size", &self.fetch_size)
+            .field("strict_timestamps", &self.strict_timestamps)
+            .field("charset", &self.charset)
+            .field("collation", &self.collation)
+            .field("log_settings", &self.log_settings)
+            .field("pipes_as_concat", &self.pipes_as_concat)
+            .field("enable_cleartext_plugin", &self.enable_cleartext_plugin)
+            .field("no_engine_substitution", &self.no_engine_substitution)
+            .field("timezone", &self.timezone)
+            .field("set_names", &self.set_names)
+            .field("verify_charset", &self.verify_charset)
+            .field("compression", &self.compression);
+
+        #[cfg(feature = "debug-protocol")]
+        s.field("packet_inspector", &self.packet_inspector);
+
+        s.finish()
+    }
+}
+
 impl MySqlConnectOptions {
     /// Creates a new, default set of options ready for configuration
     pub fn new() -> Self {
@@ -95,6 +155,7 @@ impl MySqlConnectOptions {
             port: 3306,
             host: String::from("localhost"),
             socket: None,
+            socket_connector: None,
             username: String::from("root"),
             password: None,
             database: None,
@@ -105,12 +166,18 @@ impl MySqlConnectOptions {
             ssl_client_cert: None,
             ssl_client_key: None,
             statement_cache_capacity: 100,
+            fetch_size: 0,
+            strict_timestamps: false,
             log_settings: Default::default(),
             pipes_as_concat: true,
             enable_cleartext_plugin: false,
             no_engine_substitution: true,
             timezone: Some(String::from("+00:00")),
             set_names: true,
+            verify_charset: None,
+            compression: MySqlCompression::None,
+            #[cfg(feature = "debug-protocol")]
+            packet_inspector: None,
         }
     }
 
@@ -140,6 +207,31 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// Sets a connector that opens the stream to the server, instead of connecting to
+    /// the host and port or a Unix socket, e.g. to tunnel the connection through a proxy.
+    ///
+    /// The connector is called for every new connection, including those opened by a pool.
+    /// TLS is negotiated on the returned stream according to [`ssl_mode`][Self::ssl_mode],
+    /// with the host used as the server name to verify.
+    ///
+    /// By default set to `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use sqlx::net::StreamSocket;
+    /// use sqlx::mysql::MySqlConnectOptions;
+    ///
+    /// let options = MySqlConnectOptions::new().socket_connector(|| async {
+    ///     let stream = open_tunnel().await?;
+    ///     Ok(StreamSocket::from_tokio(stream))
+    /// });
+    /// ```
+    pub fn socket_connector(mut self, connector: impl SocketConnector) -> Self {
+        self.socket_connector = Some(Arc::new(connector));
+        self
+    }
+
     /// Sets the username to connect as.
     pub fn username(mut self, username: &str) -> Self {
         username.clone_into(&mut self.username);
@@ -297,6 +389,52 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// Sets the number of rows to fetch at a time from the server, with a cursor.
+    ///
+    /// By default, the server sends all rows of a result set as soon as the query is executed,
+    /// and they are buffered by the socket until they are read. With a fetch size, the rows are
+    /// instead read by a cursor, `fetch_size` rows at a time as the stream of results is polled,
+    /// so that only that many rows need to be buffered at once. Dropping the stream stops
+    /// fetching rows; the cursor is closed when the statement is executed again or closed.
+    ///
+    /// This only applies to queries with arguments that are cached as prepared statements
+    /// (see [`Self::statement_cache_capacity()`] and `Query::persistent()`). Other queries,
+    /// and statements the server can't open a cursor for (e.g. `CALL`), are executed as usual.
+    ///
+    /// Each fetch is a round trip to the server, so a small fetch size makes large
+    /// results slower to read.
+    ///
+    /// The default is `0`, which does not use a cursor.
+    pub fn fetch_size(mut self, fetch_size: u32) -> Self {
+        self.fetch_size = fetch_size;
+        self
+    }
+
+    /// If `true`, decoding a `DATETIME` as a date and time with a time zone, such as
+    /// `chrono::DateTime<Utc>` or `time::OffsetDateTime`, is an error, as is decoding
+    /// a `TIMESTAMP` as one without, such as `chrono::NaiveDateTime` or
+    /// `time::PrimitiveDateTime`.
+    ///
+    /// MySQL converts a `TIMESTAMP` from the session time zone to UTC to store it, while
+    /// a `DATETIME` is stored as it is given, so only a `TIMESTAMP` is known to be in UTC
+    /// (if the session time zone is UTC, see [`Self::timezone()`]).
+    /// To decode a `DATETIME` that is known to hold UTC, use `sqlx::types::chrono::AssumeUtc`,
+    /// or decode a `time::PrimitiveDateTime` and call `.assume_utc()` on it.
+    ///
+    /// The default is `false`, which will change to `true` in a future release.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .strict_timestamps(true);
+    /// ```
+    pub fn strict_timestamps(mut self, enabled: bool) -> Self {
+        self.strict_timestamps = enabled;
+        self
+    }
+
     /// Sets the character set for the connection.
     ///
     /// The default character set is `utf8mb4`. This is supported from MySQL 5.5.3.
@@ -325,7 +463,11 @@ impl MySqlConnectOptions {
         self
     }
 
-    /// Enables mysql_clear_password plugin support.
+    /// Enables mysql_clear_password plugin support on unencrypted connections.
+    ///
+    /// The plugin, which is needed for e.g. PAM or LDAP authentication, sends the password
+    /// as-is. It is always allowed over TLS or a unix socket; otherwise, connecting fails
+    /// unless this is enabled.
     ///
     /// Security Note:
     /// Sending passwords as cleartext may be a security problem in some
@@ -399,10 +541,84 @@ impl MySqlConnectOptions {
     ///
     /// Instead of disabling this, you may also consider setting [`.charset()`] to a charset that
     /// is supported by your MySQL or MariaDB server version and compatible with UTF-8.
+    ///
+    /// Disabling this also disables [`.verify_charset()`], unless it's enabled explicitly,
+    /// to leave the character set of the connection to the server entirely.
     pub fn set_names(mut self, flag_val: bool) -> Self {
         self.set_names = flag_val;
         self
     }
+
+    /// If enabled, the character set of the connection is checked after connecting to
+    /// the database, and `SET NAMES '{charset}' COLLATE '{collation}'` is passed if
+    /// `@@character_set_client`, `@@character_set_connection` or `@@character_set_results`
+    /// is not [`.charset()`].
+    ///
+    /// This catches servers and proxies that don't apply the character set requested in
+    /// the handshake or by [`.set_names()`], e.g. because of an `init_connect` statement.
+    /// If the character set still doesn't match afterwards, connecting fails.
+    ///
+    /// Defaults to the value of [`.set_names()`], which is enabled by default.
+    /// See also [`MySqlConnection::verify_charset()`].
+    ///
+    /// Regardless of this option, binding a string containing characters that
+    /// the character set of the connection can't represent fails with [`Error::Encode`],
+    /// instead of the server replacing or garbling them.
+    ///
+    /// [`Error::Encode`]: crate::error::Error::Encode
+    /// [`MySqlConnection::verify_charset()`]: crate::MySqlConnection::verify_charset
+    pub fn verify_charset(mut self, flag_val: bool) -> Self {
+        self.verify_charset = Some(flag_val);
+        self
+    }
+
+    /// Sets the algorithm used to compress traffic between the client and the server.
+    ///
+    /// Compression is negotiated during the handshake; if the server does not support
+    /// the chosen algorithm, the connection continues uncompressed.
+    ///
+    /// By default, traffic is not compressed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_mysql::{MySqlCompression, MySqlConnectOptions};
+    /// let options = MySqlConnectOptions::new()
+    ///     .compression(MySqlCompression::Zstd { level: 3 });
+    /// ```
+    pub fn compression(mut self, compression: MySqlCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets a callback to be invoked with every packet read from or written to the server,
+    /// for debugging the wire protocol.
+    ///
+    /// Packets are passed as raw bytes, including their header, after TLS decryption and
+    /// decompression, and before they are parsed. Packets that are written are passed as they
+    /// are added to the write buffer. [`inspect::format_frame()`][crate::inspect::format_frame]
+    /// formats a packet for printing.
+    ///
+    /// The callback is called synchronously from the connection, so it should return quickly.
+    /// Nothing is done for connections without a callback.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_mysql::MySqlConnectOptions;
+    /// use sqlx_mysql::inspect::format_frame;
+    ///
+    /// let options = MySqlConnectOptions::new()
+    ///     .packet_inspector(|direction, frame| eprintln!("{}", format_frame(direction, frame)));
+    /// ```
+    #[cfg(feature = "debug-protocol")]
+    pub fn packet_inspector(
+        mut self,
+        inspector: impl Fn(Direction, &[u8]) + Send + Sync + 'static,
+    ) -> Self {
+        self.packet_inspector = Some(PacketInspector::new(inspector));
+        self
+    }
 }
 
 impl MySqlConnectOptions {
@@ -515,4 +731,17 @@ impl MySqlConnectOptions {
     pub fn get_collation(&self) -> Option<&str> {
         self.collation.as_deref()
     }
+
+    /// Get the compression algorithm.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_mysql::{MySqlCompression, MySqlConnectOptions};
+    /// let options = MySqlConnectOptions::new();
+    /// assert_eq!(options.get_compression(), MySqlCompression::None);
+    /// ```
+    pub fn get_compression(&self) -> MySqlCompression {
+        self.compression
+    }
 }
@@ -21,6 +21,21 @@ impl AuthPlugin {
             AuthPlugin::MySqlClearPassword => "mysql_clear_password",
         }
     }
+
+    /// `mysql_clear_password` sends the password as-is, so it may only be used
+    /// when `allow_cleartext` is set.
+    pub(crate) fn check_allowed(self, allow_cleartext: bool) -> Result<(), Error> {
+        if matches!(self, AuthPlugin::MySqlClearPassword) && !allow_cleartext {
+            return Err(Error::Configuration(
+                "the server requested the mysql_clear_password authentication plugin, \
+                 which would send the password unencrypted; connect using TLS or a unix socket, \
+                 or allow it with `MySqlConnectOptions::enable_cleartext_plugin(true)`"
+                    .into(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 impl FromStr for AuthPlugin {
@@ -25,6 +25,9 @@ pub struct HandshakeResponse<'a> {
 
     /// Opaque authentication response
     pub auth_response: Option<&'a [u8]>,
+
+    /// Compression level for zstd, if `ZSTD_COMPRESSION_ALGORITHM` was negotiated
+    pub zstd_compression_level: Option<u8>,
 }
 
 impl ProtocolEncode<'_, Capabilities> for HandshakeResponse<'_> {
@@ -77,6 +80,10 @@ impl ProtocolEncode<'_, Capabilities> for HandshakeResponse<'_> {
             }
         }
 
+        if context.contains(Capabilities::ZSTD_COMPRESSION_ALGORITHM) {
+            buf.push(self.zstd_compression_level.unwrap_or(3));
+        }
+
         Ok(())
     }
 }
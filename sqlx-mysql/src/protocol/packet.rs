@@ -91,6 +91,24 @@ impl Packet<Bytes> {
         self.decode()
     }
 
+    /// Returns `true` if this packet ends a result set, i.e. it is an EOF packet, or the OK
+    /// packet that replaces it with `DEPRECATE_EOF`.
+    ///
+    /// Both start with `0xfe`. A row only starts with `0xfe` if its first value is a string of
+    /// at least 16 MiB, so it can be told apart by its size. With `DEPRECATE_EOF`, the OK packet
+    /// is longer than an EOF packet when it carries e.g. a large affected row count.
+    pub(crate) fn is_result_set_end(&self, capabilities: Capabilities) -> bool {
+        if self.first() != Some(&0xfe) {
+            return false;
+        }
+
+        if capabilities.contains(Capabilities::DEPRECATE_EOF) {
+            self.len() < 0xFF_FF_FF
+        } else {
+            self.len() < 9
+        }
+    }
+
     pub(crate) fn eof(self, capabilities: Capabilities) -> Result<EofPacket, Error> {
         if capabilities.contains(Capabilities::DEPRECATE_EOF) {
             let ok = self.ok()?;
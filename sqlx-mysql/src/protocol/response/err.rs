@@ -8,7 +8,7 @@ use crate::protocol::Capabilities;
 // https://mariadb.com/kb/en/err_packet/
 
 /// Indicates that an error occurred.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ErrPacket {
     pub error_code: u16,
     pub sql_state: Option<String>,
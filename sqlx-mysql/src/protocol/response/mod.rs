@@ -6,9 +6,11 @@
 mod eof;
 mod err;
 mod ok;
+mod session_state;
 mod status;
 
 pub use eof::EofPacket;
 pub use err::ErrPacket;
 pub use ok::OkPacket;
+pub use session_state::{MySqlSessionState, SessionStateChange};
 pub use status::Status;
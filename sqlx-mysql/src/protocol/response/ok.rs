@@ -3,7 +3,7 @@ use bytes::{Buf, Bytes};
 use crate::error::Error;
 use crate::io::MySqlBufExt;
 use crate::io::ProtocolDecode;
-use crate::protocol::response::Status;
+use crate::protocol::response::{SessionStateChange, Status};
 
 /// Indicates successful completion of a previous command sent by the client.
 #[derive(Debug)]
@@ -12,6 +12,7 @@ pub struct OkPacket {
     pub last_insert_id: u64,
     pub status: Status,
     pub warnings: u16,
+    pub session_state_changes: Vec<SessionStateChange>,
 }
 
 impl ProtocolDecode<'_> for OkPacket {
@@ -29,11 +30,23 @@ impl ProtocolDecode<'_> for OkPacket {
         let status = Status::from_bits_truncate(buf.get_u16_le());
         let warnings = buf.get_u16_le();
 
+        // with `SESSION_TRACK`, the human readable info is length-encoded
+        // and followed by the changes of the session state
+        let session_state_changes =
+            if status.contains(Status::SERVER_SESSION_STATE_CHANGED) && buf.has_remaining() {
+                let _info = buf.get_bytes_lenenc()?;
+
+                SessionStateChange::decode_all(buf.get_bytes_lenenc()?)?
+            } else {
+                Vec::new()
+            };
+
         Ok(Self {
             affected_rows,
             last_insert_id,
             status,
             warnings,
+            session_state_changes,
         })
     }
 }
@@ -49,4 +62,18 @@ fn test_decode_ok_packet() {
     assert_eq!(p.warnings, 0);
     assert!(p.status.contains(Status::SERVER_STATUS_AUTOCOMMIT));
     assert!(p.status.contains(Status::SERVER_SESSION_STATE_CHANGED));
+    assert!(p.session_state_changes.is_empty());
+}
+
+#[test]
+fn test_decode_ok_packet_with_session_state() {
+    // `USE db`
+    const DATA: &[u8] = b"\x00\x00\x00\x02\x40\x00\x00\x00\x05\x01\x03\x02db";
+
+    let p = OkPacket::decode(DATA.into()).unwrap();
+
+    assert_eq!(
+        p.session_state_changes,
+        [SessionStateChange::Schema("db".into())]
+    );
 }
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use bytes::{Buf, Bytes};
+
+use crate::error::Error;
+use crate::io::MySqlBufExt;
+
+// https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_basic_ok_packet.html
+// https://mariadb.com/kb/en/ok_packet/#session-change-type
+const SESSION_TRACK_SYSTEM_VARIABLES: u8 = 0x00;
+const SESSION_TRACK_SCHEMA: u8 = 0x01;
+const SESSION_TRACK_TRANSACTION_CHARACTERISTICS: u8 = 0x04;
+const SESSION_TRACK_TRANSACTION_STATE: u8 = 0x05;
+
+/// A change of the session state, as reported at the end of an OK packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStateChange {
+    SystemVariable { name: String, value: String },
+    Schema(String),
+    TransactionCharacteristics(String),
+    TransactionState(String),
+}
+
+impl SessionStateChange {
+    /// Decode the session state changes of an OK packet, skipping the kinds that aren't tracked.
+    pub(crate) fn decode_all(mut buf: Bytes) -> Result<Vec<Self>, Error> {
+        let mut changes = Vec::new();
+
+        while buf.has_remaining() {
+            let kind = buf.get_u8();
+            let mut data = buf.get_bytes_lenenc()?;
+
+            match kind {
+                // one entry for each variable
+                SESSION_TRACK_SYSTEM_VARIABLES => {
+                    changes.push(SessionStateChange::SystemVariable {
+                        name: data.get_str_lenenc()?,
+                        value: data.get_str_lenenc()?,
+                    })
+                }
+
+                SESSION_TRACK_SCHEMA => {
+                    changes.push(SessionStateChange::Schema(data.get_str_lenenc()?));
+                }
+
+                SESSION_TRACK_TRANSACTION_CHARACTERISTICS => changes.push(
+                    SessionStateChange::TransactionCharacteristics(data.get_str_lenenc()?),
+                ),
+
+                SESSION_TRACK_TRANSACTION_STATE => {
+                    changes.push(SessionStateChange::TransactionState(data.get_str_lenenc()?));
+                }
+
+                // `SESSION_TRACK_STATE_CHANGE`, `SESSION_TRACK_GTIDS` or a kind added later
+                _ => {}
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+/// The session state of a connection, as tracked by the server.
+///
+/// The server reports changes of the session state at the end of a statement if the connection
+/// supports it, which it does with MySQL 5.7 and MariaDB 10.2 or newer. What is tracked depends
+/// on server variables:
+///
+/// * `session_track_schema` for the default database, which is enabled by default.
+/// * `session_track_system_variables` for the system variables, which defaults to
+///   `time_zone`, `autocommit` and the character set variables.
+/// * `session_track_transaction_info` for the transaction, which is disabled by default.
+///   Set it to `CHARACTERISTICS` to track the characteristics and the state of the transaction.
+///
+/// Nothing is known about the variables that haven't changed since the connection was opened.
+///
+/// See [`MySqlConnection::last_session_state()`][crate::MySqlConnection::last_session_state].
+///
+/// * [MySQL Manual 7.1.18: Server Tracking of Client Session State](https://dev.mysql.com/doc/refman/8.4/en/session-state-tracking.html)
+/// * [MariaDB Manual: session_track_system_variables](https://mariadb.com/kb/en/server-system-variables/#session_track_system_variables)
+#[derive(Debug, Clone, Default)]
+pub struct MySqlSessionState {
+    schema: Option<String>,
+    system_variables: BTreeMap<String, String>,
+    transaction_characteristics: Option<String>,
+    transaction_state: Option<String>,
+}
+
+impl MySqlSessionState {
+    /// The state of a new connection to `schema`.
+    pub(crate) fn new(schema: Option<String>) -> Self {
+        MySqlSessionState {
+            schema,
+            ..Default::default()
+        }
+    }
+
+    /// The default database of the session, e.g. after `USE`.
+    ///
+    /// Before it is changed, this is the database that was connected to.
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// The last value that the system variable `name` was changed to, e.g. by `SET SESSION`.
+    pub fn system_variable(&self, name: &str) -> Option<&str> {
+        self.system_variables.get(name).map(String::as_str)
+    }
+
+    /// The names and values of the system variables that have changed, in order of their names.
+    pub fn system_variables(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.system_variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The statements that would restart the current transaction with the same characteristics,
+    /// e.g. `SET TRANSACTION ISOLATION LEVEL SERIALIZABLE; START TRANSACTION READ ONLY;`.
+    ///
+    /// This is empty if the characteristics are the defaults, and `None` if they aren't tracked.
+    pub fn transaction_characteristics(&self) -> Option<&str> {
+        self.transaction_characteristics.as_deref()
+    }
+
+    /// The state of the current transaction as 8 characters, e.g. `T_______` for an explicit
+    /// transaction that hasn't read or written anything yet.
+    ///
+    /// See the MySQL manual for the meaning of each character; `None` if it isn't tracked.
+    pub fn transaction_state(&self) -> Option<&str> {
+        self.transaction_state.as_deref()
+    }
+
+    pub(crate) fn apply(&mut self, changes: &[SessionStateChange]) {
+        for change in changes {
+            match change {
+                SessionStateChange::SystemVariable { name, value } => {
+                    self.system_variables.insert(name.clone(), value.clone());
+                }
+                SessionStateChange::Schema(schema) => {
+                    // the default database is unset if it is dropped
+                    self.schema = Some(schema.clone()).filter(|schema| !schema.is_empty());
+                }
+                SessionStateChange::TransactionCharacteristics(characteristics) => {
+                    self.transaction_characteristics = Some(characteristics.clone());
+                }
+                SessionStateChange::TransactionState(state) => {
+                    self.transaction_state = Some(state.clone());
+                }
+            }
+        }
+    }
+
+    /// Forget everything but the default database, which is kept by `COM_RESET_CONNECTION`.
+    pub(crate) fn reset(&mut self) {
+        *self = MySqlSessionState::new(self.schema.take());
+    }
+}
+
+#[test]
+fn test_decode_session_state_changes() {
+    const DATA: &[u8] = b"\x00\x11\x09time_zone\x06+02:00\x01\x03\x02db\x03\x02\x01\x31";
+
+    let changes = SessionStateChange::decode_all(Bytes::from_static(DATA)).unwrap();
+
+    assert_eq!(
+        changes,
+        [
+            SessionStateChange::SystemVariable {
+                name: "time_zone".into(),
+                value: "+02:00".into(),
+            },
+            SessionStateChange::Schema("db".into()),
+        ]
+    );
+
+    let mut state = MySqlSessionState::default();
+
+    state.apply(&changes);
+    assert_eq!(state.schema(), Some("db"));
+    assert_eq!(state.system_variable("time_zone"), Some("+02:00"));
+
+    state.reset();
+
+    assert_eq!(state.schema(), Some("db"));
+    assert_eq!(state.system_variable("time_zone"), None);
+}
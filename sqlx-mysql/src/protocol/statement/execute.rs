@@ -9,13 +9,19 @@ use crate::MySqlArguments;
 pub struct Execute<'q> {
     pub statement: u32,
     pub arguments: &'q MySqlArguments,
+    // open a read-only cursor, to read the rows with `COM_STMT_FETCH`
+    pub cursor: bool,
 }
 
 impl<'q> ProtocolEncode<'_, Capabilities> for Execute<'q> {
     fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) -> Result<(), crate::Error> {
         buf.push(0x17); // COM_STMT_EXECUTE
         buf.extend(&self.statement.to_le_bytes());
-        buf.push(0); // NO_CURSOR
+        buf.push(if self.cursor {
+            0x01 // CURSOR_TYPE_READ_ONLY
+        } else {
+            0x00 // CURSOR_TYPE_NO_CURSOR
+        });
         buf.extend(&1_u32.to_le_bytes()); // iterations (always 1): int<4>
 
         if !self.arguments.types.is_empty() {
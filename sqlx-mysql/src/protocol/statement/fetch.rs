@@ -0,0 +1,19 @@
+use crate::io::ProtocolEncode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_fetch.html
+
+#[derive(Debug)]
+pub struct Fetch {
+    pub statement: u32,
+    pub rows: u32,
+}
+
+impl ProtocolEncode<'_, Capabilities> for Fetch {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) -> Result<(), crate::Error> {
+        buf.push(0x1c); // COM_STMT_FETCH
+        buf.extend(&self.statement.to_le_bytes());
+        buf.extend(&self.rows.to_le_bytes());
+        Ok(())
+    }
+}
@@ -1,10 +1,12 @@
 mod execute;
+mod fetch;
 mod prepare;
 mod prepare_ok;
 mod row;
 mod stmt_close;
 
 pub(crate) use execute::Execute;
+pub(crate) use fetch::Fetch;
 pub(crate) use prepare::Prepare;
 pub(crate) use prepare_ok::PrepareOk;
 pub(crate) use row::BinaryRow;
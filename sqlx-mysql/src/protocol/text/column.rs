@@ -106,9 +106,7 @@ pub(crate) struct ColumnDefinition {
     catalog: Bytes,
     #[allow(unused)]
     schema: Bytes,
-    #[allow(unused)]
     table_alias: Bytes,
-    #[allow(unused)]
     table: Bytes,
     alias: Bytes,
     name: Bytes,
@@ -132,6 +130,14 @@ impl ColumnDefinition {
     pub(crate) fn alias(&self) -> Result<&str, Error> {
         from_utf8(&self.alias).map_err(Error::protocol)
     }
+
+    pub(crate) fn table(&self) -> Result<&str, Error> {
+        from_utf8(&self.table).map_err(Error::protocol)
+    }
+
+    pub(crate) fn table_alias(&self) -> Result<&str, Error> {
+        from_utf8(&self.table_alias).map_err(Error::protocol)
+    }
 }
 
 impl ProtocolDecode<'_, Capabilities> for ColumnDefinition {
@@ -170,6 +176,7 @@ impl ColumnType {
         let is_binary = flags.contains(ColumnFlags::BINARY);
         let is_unsigned = flags.contains(ColumnFlags::UNSIGNED);
         let is_enum = flags.contains(ColumnFlags::ENUM);
+        let is_set = flags.contains(ColumnFlags::SET);
 
         match self {
             ColumnType::Tiny if max_size == Some(1) => "BOOLEAN",
@@ -200,6 +207,7 @@ impl ColumnType {
 
             ColumnType::String if is_binary => "BINARY",
             ColumnType::String if is_enum => "ENUM",
+            ColumnType::String if is_set => "SET",
             ColumnType::VarChar | ColumnType::VarString if is_binary => "VARBINARY",
 
             ColumnType::String => "CHAR",
@@ -0,0 +1,14 @@
+use crate::io::ProtocolEncode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.32/page_protocol_com_reset_connection.html
+
+#[derive(Debug)]
+pub(crate) struct ResetConnection;
+
+impl ProtocolEncode<'_, Capabilities> for ResetConnection {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) -> Result<(), crate::Error> {
+        buf.push(0x1f); // COM_RESET_CONNECTION
+        Ok(())
+    }
+}
@@ -4,9 +4,13 @@ use std::iter::{Extend, IntoIterator};
 pub struct MySqlQueryResult {
     pub(super) rows_affected: u64,
     pub(super) last_insert_id: u64,
+    pub(super) warnings: u16,
 }
 
 impl MySqlQueryResult {
+    /// The `AUTO_INCREMENT` value generated by the last statement, or 0 if none was generated.
+    ///
+    /// This is unsigned, as `BIGINT UNSIGNED` keys may exceed `i64::MAX`.
     pub fn last_insert_id(&self) -> u64 {
         self.last_insert_id
     }
@@ -14,6 +18,13 @@ impl MySqlQueryResult {
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// The number of warnings produced by the statement, which can be read with `SHOW WARNINGS`.
+    ///
+    /// When results are combined, this is the sum of their warnings.
+    pub fn warnings(&self) -> u16 {
+        self.warnings
+    }
 }
 
 impl Extend<MySqlQueryResult> for MySqlQueryResult {
@@ -21,6 +32,7 @@ impl Extend<MySqlQueryResult> for MySqlQueryResult {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
             self.last_insert_id = elem.last_insert_id;
+            self.warnings = self.warnings.saturating_add(elem.warnings);
         }
     }
 }
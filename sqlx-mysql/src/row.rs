@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 pub(crate) use sqlx_core::row::*;
 
-use crate::column::ColumnIndex;
+use crate::column::{resolve_column_name, ColumnIndex};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::HashMap;
@@ -15,6 +15,8 @@ pub struct MySqlRow {
     pub(crate) format: MySqlValueFormat,
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    // see `MySqlConnectOptions::strict_timestamps()`
+    pub(crate) strict_timestamps: bool,
 }
 
 impl Row for MySqlRow {
@@ -37,15 +39,40 @@ impl Row for MySqlRow {
             row: Some(&self.row.storage),
             type_info: column.type_info.clone(),
             value,
+            strict_timestamps: self.strict_timestamps,
         })
     }
 }
 
+impl MySqlRow {
+    /// Get the value of a string or binary column as a `String`, replacing any invalid UTF-8
+    /// with `U+FFFD REPLACEMENT CHARACTER` instead of returning an error.
+    ///
+    /// This is useful for legacy data that isn't valid UTF-8, or text that the server doesn't
+    /// convert to the connection character set, such as in `BLOB` columns or with
+    /// `character_set_results = NULL`. Decode into `Vec<u8>` or `&[u8]` to get the raw bytes.
+    pub fn try_get_lossy<I>(&self, index: I) -> Result<String, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let bytes: &[u8] = self.try_get(index)?;
+
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
 impl ColumnIndex<MySqlRow> for &'_ str {
     fn index(&self, row: &MySqlRow) -> Result<usize, Error> {
-        row.column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        if let Some(&index) = row.column_names.get(*self) {
+            return Ok(index);
+        }
+
+        // fall back to a case-insensitive or `table.column` lookup
+        resolve_column_name(
+            self,
+            row.columns
+                .iter()
+                .map(|column| (column.ordinal, &*column.name, column.table_names())),
+        )
     }
 }
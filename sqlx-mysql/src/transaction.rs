@@ -8,22 +8,126 @@ use crate::{MySql, MySqlConnection};
 
 pub(crate) use sqlx_core::transaction::*;
 
-/// Implementation of [`TransactionManager`] for MySQL.
-pub struct MySqlTransactionManager;
+/// Options for beginning a transaction with [`Connection::begin_with()`].
+///
+/// These only apply to a new transaction; they are ignored when establishing a savepoint.
+///
+/// [`Connection::begin_with()`]: crate::connection::Connection::begin_with
+#[derive(Debug, Clone, Default)]
+pub struct MySqlTransactionOptions {
+    isolation_level: Option<IsolationLevel>,
+    consistent_snapshot: bool,
+    read_only: Option<bool>,
+}
 
-impl TransactionManager for MySqlTransactionManager {
+impl MySqlTransactionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the isolation level of the transaction.
+    ///
+    /// If not set, the `transaction_isolation` of the session is used,
+    /// which defaults to `REPEATABLE READ`.
+    pub fn isolation_level(mut self, level: IsolationLevel) -> Self {
+        self.isolation_level = Some(level);
+        self
+    }
+
+    /// Sets whether to start the transaction `WITH CONSISTENT SNAPSHOT`.
+    ///
+    /// This only has an effect with InnoDB tables at the `REPEATABLE READ` isolation level.
+    pub fn consistent_snapshot(mut self, consistent_snapshot: bool) -> Self {
+        self.consistent_snapshot = consistent_snapshot;
+        self
+    }
+
+    /// Sets whether the transaction is `READ ONLY` or `READ WRITE`.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
+    fn begin_sql(&self) -> String {
+        let mut sql = String::new();
+
+        // `SET TRANSACTION` without `SESSION` or `GLOBAL` applies only to the next transaction
+        if let Some(level) = self.isolation_level {
+            sql.push_str("SET TRANSACTION ISOLATION LEVEL ");
+            sql.push_str(level.as_str());
+            sql.push_str("; ");
+        }
+
+        sql.push_str("START TRANSACTION");
+
+        let mut characteristics = Vec::new();
+
+        if self.consistent_snapshot {
+            characteristics.push("WITH CONSISTENT SNAPSHOT");
+        }
+
+        match self.read_only {
+            Some(true) => characteristics.push("READ ONLY"),
+            Some(false) => characteristics.push("READ WRITE"),
+            None => (),
+        }
+
+        if !characteristics.is_empty() {
+            sql.push(' ');
+            sql.push_str(&characteristics.join(", "));
+        }
+
+        sql
+    }
+}
+
+impl sealed::Sealed for MySqlTransactionOptions {}
+
+impl TransactionOptions for MySqlTransactionOptions {
     type Database = MySql;
+}
 
-    fn begin(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
+/// Implementation of [`TransactionManager`] for MySQL.
+pub struct MySqlTransactionManager;
+
+impl MySqlTransactionManager {
+    fn begin_with_options(
+        conn: &mut MySqlConnection,
+        options: MySqlTransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.inner.transaction_depth;
 
-            conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            if depth == 0 {
+                conn.execute(&*options.begin_sql()).await?;
+            } else {
+                conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            }
+
             conn.inner.transaction_depth = depth + 1;
 
             Ok(())
         })
     }
+}
+
+impl TransactionManager for MySqlTransactionManager {
+    type Database = MySql;
+
+    fn begin(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with_options(conn, MySqlTransactionOptions::default())
+    }
+
+    fn begin_with<O>(conn: &mut MySqlConnection, options: O) -> BoxFuture<'_, Result<(), Error>>
+    where
+        O: TransactionOptions<Database = MySql>,
+    {
+        Box::pin(async move {
+            let options = downcast_options(options)?;
+
+            Self::begin_with_options(conn, options).await
+        })
+    }
 
     fn commit(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
@@ -56,7 +160,7 @@ impl TransactionManager for MySqlTransactionManager {
 
         if depth > 0 {
             conn.inner.stream.waiting.push_back(Waiting::Result);
-            conn.inner.stream.sequence_id = 0;
+            conn.inner.stream.begin_command();
             conn.inner
                 .stream
                 .write_packet(Query(&rollback_ansi_transaction_sql(depth)))
@@ -66,3 +170,20 @@ impl TransactionManager for MySqlTransactionManager {
         }
     }
 }
+
+#[test]
+fn test_begin_sql() {
+    assert_eq!(
+        MySqlTransactionOptions::new().begin_sql(),
+        "START TRANSACTION"
+    );
+    assert_eq!(
+        MySqlTransactionOptions::new()
+            .isolation_level(IsolationLevel::Serializable)
+            .consistent_snapshot(true)
+            .read_only(true)
+            .begin_sql(),
+        "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE; \
+         START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY"
+    );
+}
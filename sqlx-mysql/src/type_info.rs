@@ -14,6 +14,10 @@ pub struct MySqlTypeInfo {
     // [max_size] for integer types, this is (M) in BIT(M) or TINYINT(M)
     #[cfg_attr(feature = "offline", serde(default))]
     pub(crate) max_size: Option<u32>,
+
+    // collation ID of a result column, for error messages
+    #[cfg_attr(feature = "offline", serde(skip))]
+    pub(crate) collation: Option<u16>,
 }
 
 impl MySqlTypeInfo {
@@ -22,6 +26,7 @@ impl MySqlTypeInfo {
             r#type: ty,
             flags: ColumnFlags::BINARY,
             max_size: None,
+            collation: None,
         }
     }
 
@@ -39,6 +44,7 @@ impl MySqlTypeInfo {
             r#type: ColumnType::String,
             flags: ColumnFlags::ENUM,
             max_size: None,
+            collation: None,
         }
     }
 
@@ -61,6 +67,7 @@ impl MySqlTypeInfo {
             r#type: column.r#type,
             flags: column.flags,
             max_size: Some(column.max_size),
+            collation: Some(column.collation),
         }
     }
 }
@@ -13,6 +13,7 @@ impl Type<MySql> for bool {
         MySqlTypeInfo {
             flags: ColumnFlags::BINARY | ColumnFlags::UNSIGNED,
             max_size: Some(1),
+            collation: None,
             r#type: ColumnType::Tiny,
         }
     }
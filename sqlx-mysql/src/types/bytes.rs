@@ -22,6 +22,10 @@ impl Type<MySql> for [u8] {
                 | ColumnType::String
                 | ColumnType::VarString
                 | ColumnType::Enum
+                // the big-endian bits of a `BIT(M)` value
+                | ColumnType::Bit
+                // the raw text of a JSON value
+                | ColumnType::Json
         )
     }
 }
@@ -62,6 +66,36 @@ impl<'r> Decode<'r, MySql> for Box<[u8]> {
     }
 }
 
+impl<const N: usize> Type<MySql> for [u8; N] {
+    fn type_info() -> MySqlTypeInfo {
+        <[u8] as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <&[u8] as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<const N: usize> Encode<'_, MySql> for [u8; N] {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        <&[u8] as Encode<MySql>>::encode(self.as_slice(), buf)
+    }
+}
+
+impl<const N: usize> Decode<'_, MySql> for [u8; N] {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let bytes = <&[u8] as Decode<MySql>>::decode(value)?;
+
+        bytes.try_into().map_err(|_| {
+            format!(
+                "expected {N} bytes to decode `[u8; {N}]`, got {} bytes",
+                bytes.len()
+            )
+            .into()
+        })
+    }
+}
+
 impl Type<MySql> for Vec<u8> {
     fn type_info() -> MySqlTypeInfo {
         <[u8] as Type<MySql>>::type_info()
@@ -9,8 +9,9 @@ use crate::encode::{Encode, IsNull};
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::protocol::text::ColumnType;
 use crate::type_info::MySqlTypeInfo;
-use crate::types::{MySqlTime, MySqlTimeSign, Type};
+use crate::types::{check_time_zone, MySqlTime, MySqlTimeSign, Type};
 use crate::{MySql, MySqlValueFormat, MySqlValueRef};
+use sqlx_core::types::chrono::AssumeUtc;
 
 impl Type<MySql> for DateTime<Utc> {
     fn type_info() -> MySqlTypeInfo {
@@ -32,9 +33,14 @@ impl Encode<'_, MySql> for DateTime<Utc> {
 /// Note: assumes the connection's `time_zone` is set to `+00:00` (UTC).
 impl<'r> Decode<'r, MySql> for DateTime<Utc> {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
-        let naive: NaiveDateTime = Decode::<MySql>::decode(value)?;
-
-        Ok(Utc.from_utc_datetime(&naive))
+        check_time_zone(
+            &value,
+            "DateTime<Tz>",
+            true,
+            "use `NaiveDateTime`, or `AssumeUtc` if it holds UTC",
+        )?;
+
+        Ok(Utc.from_utc_datetime(&decode_naive(value)?))
     }
 }
 
@@ -230,31 +236,69 @@ impl Encode<'_, MySql> for NaiveDateTime {
 
 impl<'r> Decode<'r, MySql> for NaiveDateTime {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
-        match value.format() {
-            MySqlValueFormat::Binary => {
-                let buf = value.as_bytes()?;
+        check_time_zone(
+            &value,
+            "NaiveDateTime",
+            false,
+            "use `DateTime<Utc>` or `DateTime<Local>`",
+        )?;
+
+        decode_naive(value)
+    }
+}
 
-                if buf.is_empty() {
-                    return Err("empty buffer".into());
-                }
+impl Type<MySql> for AssumeUtc {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo::binary(ColumnType::Datetime)
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        ty.r#type == ColumnType::Datetime
+    }
+}
+
+impl Encode<'_, MySql> for AssumeUtc {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        Encode::<MySql>::encode(self.naive_utc(), buf)
+    }
 
-                let len = buf[0];
-                let date = decode_date(&buf[1..])?.ok_or(UnexpectedNullError)?;
+    fn size_hint(&self) -> usize {
+        naive_dt_encoded_len(&self.naive_utc()) as usize + 1 // plus length byte
+    }
+}
 
-                let dt = if len > 4 {
-                    date.and_time(decode_time(len - 4, &buf[5..])?)
-                } else {
-                    date.and_hms_opt(0, 0, 0)
-                        .expect("expected `NaiveDate::and_hms_opt(0, 0, 0)` to be valid")
-                };
+impl<'r> Decode<'r, MySql> for AssumeUtc {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(decode_naive(value)?.into())
+    }
+}
 
-                Ok(dt)
-            }
+// decodes `DATETIME` and `TIMESTAMP` alike
+fn decode_naive(value: MySqlValueRef<'_>) -> Result<NaiveDateTime, BoxDynError> {
+    match value.format() {
+        MySqlValueFormat::Binary => {
+            let buf = value.as_bytes()?;
 
-            MySqlValueFormat::Text => {
-                let s = value.as_str()?;
-                NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").map_err(Into::into)
+            if buf.is_empty() {
+                return Err("empty buffer".into());
             }
+
+            let len = buf[0];
+            let date = decode_date(&buf[1..])?.ok_or(UnexpectedNullError)?;
+
+            let dt = if len > 4 {
+                date.and_time(decode_time(len - 4, &buf[5..])?)
+            } else {
+                date.and_hms_opt(0, 0, 0)
+                    .expect("expected `NaiveDate::and_hms_opt(0, 0, 0)` to be valid")
+            };
+
+            Ok(dt)
+        }
+
+        MySqlValueFormat::Text => {
+            let s = value.as_str()?;
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").map_err(Into::into)
         }
     }
 }
@@ -262,7 +306,7 @@ impl<'r> Decode<'r, MySql> for NaiveDateTime {
 fn encode_date(date: &NaiveDate, buf: &mut Vec<u8>) -> Result<(), BoxDynError> {
     // MySQL supports years 1000 - 9999
     let year = u16::try_from(date.year())
-        .map_err(|_| format!("NaiveDateTime out of range for Mysql: {date}"))?;
+        .map_err(|_| format!("NaiveDate out of range for MySQL: {date}"))?;
 
     buf.extend_from_slice(&year.to_le_bytes());
 
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use byteorder::{ByteOrder, LittleEndian};
 
 use crate::decode::Decode;
@@ -12,6 +14,23 @@ fn real_compatible(ty: &MySqlTypeInfo) -> bool {
     matches!(ty.r#type, ColumnType::Float | ColumnType::Double)
 }
 
+fn parse_text_real<T>(s: &str) -> Result<T, BoxDynError>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    // MySQL has no infinity or NaN, so don't accept the spellings `str::parse()` does
+    // (`inf`, `NaN`, `infinity`, ...); these can only come from a non-float column
+    if !s
+        .trim_start_matches(['-', '+'])
+        .starts_with(|c: char| c.is_ascii_digit() || c == '.')
+    {
+        return Err(format!("invalid floating-point value {s:?}").into());
+    }
+
+    Ok(s.parse()?)
+}
+
 impl Type<MySql> for f32 {
     fn type_info() -> MySqlTypeInfo {
         MySqlTypeInfo::binary(ColumnType::Float)
@@ -74,7 +93,7 @@ impl Decode<'_, MySql> for f32 {
                 }
             }
 
-            MySqlValueFormat::Text => value.as_str()?.parse()?,
+            MySqlValueFormat::Text => parse_text_real(value.as_str()?)?,
         })
     }
 }
@@ -102,7 +121,19 @@ impl Decode<'_, MySql> for f64 {
                     }
                 }
             }
-            MySqlValueFormat::Text => value.as_str()?.parse()?,
+            MySqlValueFormat::Text => parse_text_real(value.as_str()?)?,
         })
     }
 }
+
+#[test]
+fn test_parse_text_real() {
+    assert_eq!(parse_text_real::<f64>("1.5").unwrap(), 1.5);
+    assert_eq!(parse_text_real::<f64>("-1e-5").unwrap(), -1e-5);
+    assert_eq!(parse_text_real::<f32>(".5").unwrap(), 0.5);
+
+    assert!(parse_text_real::<f64>("inf").is_err());
+    assert!(parse_text_real::<f64>("-Infinity").is_err());
+    assert!(parse_text_real::<f32>("NaN").is_err());
+    assert!(parse_text_real::<f64>("").is_err());
+}
@@ -1,5 +1,9 @@
+use std::any::type_name;
+use std::fmt::Display;
+
 use byteorder::{ByteOrder, LittleEndian};
 
+use super::uint::uint_decode;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
@@ -7,15 +11,19 @@ use crate::protocol::text::{ColumnFlags, ColumnType};
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 
-fn int_compatible(ty: &MySqlTypeInfo) -> bool {
-    matches!(
-        ty.r#type,
+// Unsigned columns are accepted as well; values that don't fit are rejected by `decode_int()`.
+//
+// `YEAR` is only accepted by types that always fit it, like in `uint_compatible()`.
+fn int_compatible(ty: &MySqlTypeInfo, bits: u32) -> bool {
+    match ty.r#type {
         ColumnType::Tiny
-            | ColumnType::Short
-            | ColumnType::Long
-            | ColumnType::Int24
-            | ColumnType::LongLong
-    ) && !ty.flags.contains(ColumnFlags::UNSIGNED)
+        | ColumnType::Short
+        | ColumnType::Long
+        | ColumnType::Int24
+        | ColumnType::LongLong => true,
+        ColumnType::Year => bits >= 16,
+        _ => false,
+    }
 }
 
 impl Type<MySql> for i8 {
@@ -24,7 +32,7 @@ impl Type<MySql> for i8 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        int_compatible(ty)
+        int_compatible(ty, i8::BITS)
     }
 }
 
@@ -34,7 +42,7 @@ impl Type<MySql> for i16 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        int_compatible(ty)
+        int_compatible(ty, i16::BITS)
     }
 }
 
@@ -44,7 +52,7 @@ impl Type<MySql> for i32 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        int_compatible(ty)
+        int_compatible(ty, i32::BITS)
     }
 }
 
@@ -54,7 +62,7 @@ impl Type<MySql> for i64 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        int_compatible(ty)
+        int_compatible(ty, i64::BITS)
     }
 }
 
@@ -114,26 +122,48 @@ fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
     })
 }
 
+/// Decode an integer column of either signedness as `T`,
+/// returning an error if the value is out of range for `T`.
+pub(super) fn decode_int<T>(value: MySqlValueRef<'_>) -> Result<T, BoxDynError>
+where
+    T: TryFrom<i64> + TryFrom<u64>,
+{
+    let unsigned = value.type_info.flags.contains(ColumnFlags::UNSIGNED)
+        || value.type_info.r#type == ColumnType::Bit;
+
+    if unsigned {
+        let int = uint_decode(value)?;
+        T::try_from(int).map_err(|_| out_of_range::<T>(int))
+    } else {
+        let int = int_decode(value)?;
+        T::try_from(int).map_err(|_| out_of_range::<T>(int))
+    }
+}
+
+fn out_of_range<T>(int: impl Display) -> BoxDynError {
+    format!("value {int} is out of range for `{}`", type_name::<T>()).into()
+}
+
 impl Decode<'_, MySql> for i8 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        decode_int(value)
     }
 }
 
 impl Decode<'_, MySql> for i16 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        decode_int(value)
     }
 }
 
 impl Decode<'_, MySql> for i32 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        decode_int(value)
     }
 }
 
 impl Decode<'_, MySql> for i64 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)
+        decode_int(value)
     }
 }
@@ -10,19 +10,53 @@
 //! | `i32`                                 | INT                                                  |
 //! | `i64`                                 | BIGINT                                               |
 //! | `u8`                                  | TINYINT UNSIGNED                                     |
-//! | `u16`                                 | SMALLINT UNSIGNED                                    |
+//! | `u16`                                 | SMALLINT UNSIGNED, YEAR (see below)                  |
 //! | `u32`                                 | INT UNSIGNED                                         |
-//! | `u64`                                 | BIGINT UNSIGNED                                      |
+//! | `u64`                                 | BIGINT UNSIGNED, BIT (see below)                     |
 //! | `f32`                                 | FLOAT                                                |
 //! | `f64`                                 | DOUBLE                                               |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
-//! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
+//! | `char`                                | VARCHAR, CHAR, TEXT (a single character)             |
+//! | `&[u8]`, `Vec<u8>`, `[u8; N]`         | VARBINARY, BINARY, BLOB, JSON and BIT (decoding only)|
 //! | `IpAddr`                              | VARCHAR, TEXT                                        |
 //! | `Ipv4Addr`                            | INET4 (MariaDB-only), VARCHAR, TEXT                  |
 //! | `Ipv6Addr`                            | INET6 (MariaDB-only), VARCHAR, TEXT                  |
 //! | [`MySqlTime`]                         | TIME (encode and decode full range)                  |
+//! | [`MySqlSet`]                          | SET, VARCHAR, CHAR, TEXT                             |
 //! | [`Duration`][std::time::Duration]     | TIME (for decoding positive values only)             |
 //!
+//! ### Integer conversions
+//! Every integer type can be decoded from every integer column, signed or unsigned.
+//! If a value doesn't fit in the Rust type, decoding returns an error that includes the value.
+//!
+//! | Column type            | Always fits                      | Checked at runtime          |
+//! |------------------------|----------------------------------|-----------------------------|
+//! | TINYINT                | `i8`, `i16`, `i32`, `i64`        | `u8`, `u16`, `u32`, `u64`   |
+//! | SMALLINT               | `i16`, `i32`, `i64`              | `i8`, unsigned types        |
+//! | INT, MEDIUMINT         | `i32`, `i64`                     | `i8`, `i16`, unsigned types |
+//! | BIGINT                 | `i64`                            | all others                  |
+//! | TINYINT UNSIGNED       | `u8`..`u64`, `i16`, `i32`, `i64` | `i8`                        |
+//! | SMALLINT UNSIGNED      | `u16`..`u64`, `i32`, `i64`       | `u8`, `i8`, `i16`           |
+//! | INT UNSIGNED           | `u32`, `u64`, `i64`              | `u8`, `u16`, `i8`..`i32`    |
+//! | BIGINT UNSIGNED        | `u64`                            | all others                  |
+//!
+//! Unsigned types can be decoded from signed columns as long as the value is not negative.
+//! Encoding is unaffected: each Rust type is sent as the column type in the table above.
+//!
+//! ### `YEAR` and `BIT`
+//! `YEAR` values are `0` or between `1901` and `2155`, so they can be decoded as any integer type
+//! of 16 bits or more, e.g. `u16` or `i32`. The query macros infer `u16`.
+//!
+//! `BIT(M)` values are decoded as big-endian unsigned integers, and can be decoded as any unsigned
+//! type of at least `M` bits; the query macros infer the smallest one. They can also be decoded
+//! as their raw big-endian bytes with `Vec<u8>`.
+//!
+//! Both can be encoded as any integer type, which MySQL converts to the column type.
+//!
+//! ### `SET`
+//! `SET` values can be decoded as a [`String`] of the members separated by commas,
+//! or as a [`MySqlSet`] of the members.
+//!
 //! ##### Note: `BOOLEAN`/`BOOL` Type
 //! MySQL and MariaDB treat `BOOLEAN` as an alias of the `TINYINT` type:
 //!
@@ -55,6 +89,7 @@
 //! | `chrono::DateTime<Utc>`               | TIMESTAMP                                            |
 //! | `chrono::DateTime<Local>`             | TIMESTAMP                                            |
 //! | `chrono::NaiveDateTime`               | DATETIME                                             |
+//! | `sqlx::types::chrono::AssumeUtc`      | DATETIME (holding UTC)                               |
 //! | `chrono::NaiveDate`                   | DATE                                                 |
 //! | `chrono::NaiveTime`                   | TIME (time-of-day only)                              |
 //! | `chrono::TimeDelta`                   | TIME (decodes full range; see note for encoding)     |
@@ -71,6 +106,14 @@
 //!
 //! To encode a `chrono::TimeDelta`, convert it to [`MySqlTime`] first using `TryFrom`/`TryInto`.
 //!
+//! ### NOTE: `DATETIME` and `TIMESTAMP`
+//! By default, a `DATETIME` can also be decoded as `DateTime<Utc>` or `OffsetDateTime`,
+//! assuming it holds UTC, and a `TIMESTAMP` as `NaiveDateTime` or `PrimitiveDateTime`,
+//! dropping its time zone. With
+//! [`MySqlConnectOptions::strict_timestamps()`][crate::MySqlConnectOptions::strict_timestamps]
+//! both are errors, and a `DATETIME` known to hold UTC has to be decoded as `AssumeUtc`
+//! (or as `PrimitiveDateTime`, calling `.assume_utc()`).
+//!
 //! ### [`time`](https://crates.io/crates/time)
 //!
 //! Requires the `time` Cargo feature flag.
@@ -152,6 +195,8 @@
 //! | [`Json<T>`]                           | JSON                                                 |
 //! | `serde_json::JsonValue`               | JSON                                                 |
 //! | `&serde_json::value::RawValue`        | JSON                                                 |
+//! | `HashMap<String, serde_json::Value>`  | JSON                                                 |
+//! | `BTreeMap<String, serde_json::Value>` | JSON                                                 |
 //!
 //! # Nullable
 //!
@@ -160,6 +205,7 @@
 
 pub(crate) use sqlx_core::types::*;
 
+pub use mysql_set::MySqlSet;
 pub use mysql_time::{MySqlTime, MySqlTimeError, MySqlTimeSign};
 
 mod bool;
@@ -167,6 +213,7 @@ mod bytes;
 mod float;
 mod inet;
 mod int;
+mod mysql_set;
 mod mysql_time;
 mod str;
 mod text;
@@ -189,3 +236,33 @@ mod time;
 
 #[cfg(feature = "uuid")]
 mod uuid;
+
+/// With [`MySqlConnectOptions::strict_timestamps()`][crate::MySqlConnectOptions::strict_timestamps],
+/// returns an error if `value` is a `DATETIME` and `type_name` has a time zone, or if it is
+/// a `TIMESTAMP` and `type_name` doesn't; `suggestion` names the types to use instead.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn check_time_zone(
+    value: &crate::MySqlValueRef<'_>,
+    type_name: &str,
+    with_time_zone: bool,
+    suggestion: &str,
+) -> Result<(), crate::error::BoxDynError> {
+    use crate::protocol::text::ColumnType;
+
+    if !value.strict_timestamps {
+        return Ok(());
+    }
+
+    match (value.type_info.r#type, with_time_zone) {
+        (ColumnType::Datetime, true) => Err(format!(
+            "cannot decode DATETIME as `{type_name}` because it has no time zone; {suggestion}"
+        )
+        .into()),
+        (ColumnType::Timestamp, false) => Err(format!(
+            "cannot decode TIMESTAMP as `{type_name}` without dropping its time zone; \
+             {suggestion}"
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,135 @@
+//! The [`MySqlSet`] type.
+
+use std::ops::Deref;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::protocol::text::ColumnType;
+use crate::types::Type;
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+/// The members of a MySQL `SET` value.
+///
+/// `SET` values are sent as the names of their members separated by commas,
+/// which this splits into a list; the empty set is decoded as an empty list.
+/// MySQL returns the members in the order they are declared in the column definition.
+///
+/// Members can't contain a comma, so encoding a member that does is an error.
+///
+/// * [MySQL Manual 13.3.6: The SET Type](https://dev.mysql.com/doc/refman/8.3/en/set.html)
+/// * [MariaDB Manual: SET Data Type](https://mariadb.com/kb/en/set-data-type/)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MySqlSet(pub Vec<String>);
+
+impl MySqlSet {
+    /// Returns `true` if `member` is in the set.
+    pub fn contains(&self, member: &str) -> bool {
+        self.0.iter().any(|m| m == member)
+    }
+}
+
+impl Deref for MySqlSet {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl From<Vec<String>> for MySqlSet {
+    fn from(members: Vec<String>) -> Self {
+        MySqlSet(members)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for MySqlSet {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        MySqlSet(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Type<MySql> for MySqlSet {
+    fn type_info() -> MySqlTypeInfo {
+        <str as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        // `SET` columns are usually sent as `CHAR` with the `SET` flag
+        ty.r#type == ColumnType::Set || <str as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for MySqlSet {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        if let Some(member) = self.0.iter().find(|member| member.contains(',')) {
+            return Err(format!("`SET` member {member:?} must not contain a comma").into());
+        }
+
+        <&str as Encode<MySql>>::encode(&*self.0.join(","), buf)
+    }
+}
+
+impl Decode<'_, MySql> for MySqlSet {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let members = value.as_str()?;
+
+        if members.is_empty() {
+            return Ok(MySqlSet::default());
+        }
+
+        Ok(members.split(',').collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MySqlSet;
+    use crate::decode::Decode;
+    use crate::encode::Encode;
+    use crate::protocol::text::{ColumnFlags, ColumnType};
+    use crate::types::Type;
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn set_type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo {
+            r#type: ColumnType::String,
+            flags: ColumnFlags::SET,
+            max_size: None,
+            collation: None,
+        }
+    }
+
+    fn decode(value: &str) -> MySqlSet {
+        MySqlSet::decode(MySqlValueRef {
+            value: Some(value.as_bytes()),
+            row: None,
+            type_info: set_type_info(),
+            format: MySqlValueFormat::Text,
+            strict_timestamps: false,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(""), MySqlSet::default());
+        assert_eq!(decode("a"), MySqlSet::from_iter(["a"]));
+        assert_eq!(decode("a,b,c"), MySqlSet::from_iter(["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_compatible() {
+        assert!(<MySqlSet as Type<MySql>>::compatible(&set_type_info()));
+        assert_eq!(set_type_info().to_string(), "SET");
+    }
+
+    #[test]
+    fn test_encode_rejects_comma() {
+        let mut buf = Vec::new();
+
+        assert!(MySqlSet::from_iter(["a,b"])
+            .encode_by_ref(&mut buf)
+            .is_err());
+    }
+}
@@ -13,6 +13,7 @@ impl Type<MySql> for str {
             r#type: ColumnType::VarString, // VARCHAR
             flags: ColumnFlags::empty(),
             max_size: None,
+            collation: None,
         }
     }
 
@@ -114,3 +115,35 @@ impl<'r> Decode<'r, MySql> for Cow<'r, str> {
         value.as_str().map(Cow::Borrowed)
     }
 }
+
+impl Type<MySql> for char {
+    fn type_info() -> MySqlTypeInfo {
+        <&str as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <&str as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for char {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<MySql>>::encode(self.encode_utf8(&mut [0; 4]), buf)
+    }
+}
+
+impl Decode<'_, MySql> for char {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let s = value.as_str()?;
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(format!(
+                "expected a single character, got {} characters",
+                s.chars().count()
+            )
+            .into()),
+        }
+    }
+}
@@ -9,7 +9,7 @@ use crate::encode::{Encode, IsNull};
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::protocol::text::ColumnType;
 use crate::type_info::MySqlTypeInfo;
-use crate::types::{MySqlTime, MySqlTimeSign, Type};
+use crate::types::{check_time_zone, MySqlTime, MySqlTimeSign, Type};
 use crate::{MySql, MySqlValueFormat, MySqlValueRef};
 
 impl Type<MySql> for OffsetDateTime {
@@ -33,9 +33,14 @@ impl Encode<'_, MySql> for OffsetDateTime {
 
 impl<'r> Decode<'r, MySql> for OffsetDateTime {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
-        let primitive: PrimitiveDateTime = Decode::<MySql>::decode(value)?;
-
-        Ok(primitive.assume_utc())
+        check_time_zone(
+            &value,
+            "OffsetDateTime",
+            true,
+            "use `PrimitiveDateTime`, and `.assume_utc()` if it holds UTC",
+        )?;
+
+        Ok(decode_primitive(value)?.assume_utc())
     }
 }
 
@@ -207,46 +212,53 @@ impl Encode<'_, MySql> for PrimitiveDateTime {
 
 impl<'r> Decode<'r, MySql> for PrimitiveDateTime {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
-        match value.format() {
-            MySqlValueFormat::Binary => {
-                let mut buf = value.as_bytes()?;
+        check_time_zone(&value, "PrimitiveDateTime", false, "use `OffsetDateTime`")?;
 
-                if buf.is_empty() {
-                    return Err("empty buffer".into());
-                }
+        decode_primitive(value)
+    }
+}
 
-                let len = buf.get_u8();
+// decodes `DATETIME` and `TIMESTAMP` alike
+fn decode_primitive(value: MySqlValueRef<'_>) -> Result<PrimitiveDateTime, BoxDynError> {
+    match value.format() {
+        MySqlValueFormat::Binary => {
+            let mut buf = value.as_bytes()?;
 
-                let date = decode_date(buf)?.ok_or(UnexpectedNullError)?;
+            if buf.is_empty() {
+                return Err("empty buffer".into());
+            }
 
-                let dt = if len > 4 {
-                    date.with_time(decode_time(&buf[4..])?)
-                } else {
-                    date.midnight()
-                };
+            let len = buf.get_u8();
 
-                Ok(dt)
-            }
+            let date = decode_date(buf)?.ok_or(UnexpectedNullError)?;
 
-            MySqlValueFormat::Text => {
-                let s = value.as_str()?;
+            let dt = if len > 4 {
+                date.with_time(decode_time(&buf[4..])?)
+            } else {
+                date.midnight()
+            };
 
-                // If there are no nanoseconds parse without them
-                if s.contains('.') {
-                    PrimitiveDateTime::parse(
-                        s,
-                        &format_description!(
-                            "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
-                        ),
-                    )
-                    .map_err(Into::into)
-                } else {
-                    PrimitiveDateTime::parse(
-                        s,
-                        &format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"),
-                    )
-                    .map_err(Into::into)
-                }
+            Ok(dt)
+        }
+
+        MySqlValueFormat::Text => {
+            let s = value.as_str()?;
+
+            // If there are no nanoseconds parse without them
+            if s.contains('.') {
+                PrimitiveDateTime::parse(
+                    s,
+                    &format_description!(
+                        "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
+                    ),
+                )
+                .map_err(Into::into)
+            } else {
+                PrimitiveDateTime::parse(
+                    s,
+                    &format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"),
+                )
+                .map_err(Into::into)
             }
         }
     }
@@ -255,7 +267,7 @@ impl<'r> Decode<'r, MySql> for PrimitiveDateTime {
 fn encode_date(date: &Date, buf: &mut Vec<u8>) -> Result<(), BoxDynError> {
     // MySQL supports years from 1000 - 9999
     let year =
-        u16::try_from(date.year()).map_err(|_| format!("Date out of range for Mysql: {date}"))?;
+        u16::try_from(date.year()).map_err(|_| format!("Date out of range for MySQL: {date}"))?;
 
     buf.extend_from_slice(&year.to_le_bytes());
     buf.push(date.month().into());
@@ -1,3 +1,4 @@
+use super::int::decode_int;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
@@ -11,20 +12,25 @@ fn uint_type_info(ty: ColumnType) -> MySqlTypeInfo {
         r#type: ty,
         flags: ColumnFlags::BINARY | ColumnFlags::UNSIGNED,
         max_size: None,
+        collation: None,
     }
 }
 
-fn uint_compatible(ty: &MySqlTypeInfo) -> bool {
-    matches!(
-        ty.r#type,
+// Signed columns are accepted as well; negative values are rejected by `decode_int()`.
+//
+// `YEAR` and `BIT(M)` are only accepted by types of at least `bits` that always fit them,
+// so the macros infer `u16` for `YEAR` and the smallest type that fits `BIT(M)`.
+fn uint_compatible(ty: &MySqlTypeInfo, bits: u32) -> bool {
+    match ty.r#type {
         ColumnType::Tiny
-            | ColumnType::Short
-            | ColumnType::Long
-            | ColumnType::Int24
-            | ColumnType::LongLong
-            | ColumnType::Year
-            | ColumnType::Bit
-    ) && ty.flags.contains(ColumnFlags::UNSIGNED)
+        | ColumnType::Short
+        | ColumnType::Long
+        | ColumnType::Int24
+        | ColumnType::LongLong => true,
+        ColumnType::Year => bits >= 16,
+        ColumnType::Bit => ty.max_size.is_none_or(|max_size| max_size <= bits),
+        _ => false,
+    }
 }
 
 impl Type<MySql> for u8 {
@@ -33,7 +39,7 @@ impl Type<MySql> for u8 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        uint_compatible(ty)
+        uint_compatible(ty, u8::BITS)
     }
 }
 
@@ -43,7 +49,7 @@ impl Type<MySql> for u16 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        uint_compatible(ty)
+        uint_compatible(ty, u16::BITS)
     }
 }
 
@@ -53,7 +59,7 @@ impl Type<MySql> for u32 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        uint_compatible(ty)
+        uint_compatible(ty, u32::BITS)
     }
 }
 
@@ -63,7 +69,7 @@ impl Type<MySql> for u64 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        uint_compatible(ty)
+        uint_compatible(ty, u64::BITS)
     }
 }
 
@@ -99,7 +105,7 @@ impl Encode<'_, MySql> for u64 {
     }
 }
 
-fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
+pub(super) fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
     if value.type_info.r#type == ColumnType::Bit {
         // NOTE: Regardless of the value format, there is raw binary data here
 
@@ -139,24 +145,24 @@ fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
 
 impl Decode<'_, MySql> for u8 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        decode_int(value)
     }
 }
 
 impl Decode<'_, MySql> for u16 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        decode_int(value)
     }
 }
 
 impl Decode<'_, MySql> for u32 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        decode_int(value)
     }
 }
 
 impl Decode<'_, MySql> for u64 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)
+        decode_int(value)
     }
 }
@@ -4,8 +4,10 @@ use std::str::from_utf8;
 use bytes::Bytes;
 pub(crate) use sqlx_core::value::*;
 
+use crate::decode::Decode;
 use crate::error::{BoxDynError, UnexpectedNullError};
-use crate::protocol::text::ColumnType;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::MySqlTime;
 use crate::{MySql, MySqlTypeInfo};
 
 #[derive(Debug, Clone, Copy)]
@@ -21,6 +23,7 @@ pub struct MySqlValue {
     value: Option<Bytes>,
     type_info: MySqlTypeInfo,
     format: MySqlValueFormat,
+    strict_timestamps: bool,
 }
 
 /// Implementation of [`ValueRef`] for MySQL.
@@ -30,6 +33,8 @@ pub struct MySqlValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: MySqlTypeInfo,
     pub(crate) format: MySqlValueFormat,
+    // see `MySqlConnectOptions::strict_timestamps()`
+    pub(crate) strict_timestamps: bool,
 }
 
 impl<'r> MySqlValueRef<'r> {
@@ -45,7 +50,14 @@ impl<'r> MySqlValueRef<'r> {
     }
 
     pub(crate) fn as_str(&self) -> Result<&'r str, BoxDynError> {
-        Ok(from_utf8(self.as_bytes()?)?)
+        from_utf8(self.as_bytes()?).map_err(|e| match self.type_info.collation {
+            Some(collation) => format!(
+                "{e} (column collation ID {collation}); \
+                 decode into `Vec<u8>` or use `MySqlRow::try_get_lossy()` instead"
+            )
+            .into(),
+            None => e.into(),
+        })
     }
 }
 
@@ -58,6 +70,7 @@ impl Value for MySqlValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            strict_timestamps: self.strict_timestamps,
         }
     }
 
@@ -86,6 +99,7 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            strict_timestamps: self.strict_timestamps,
         }
     }
 
@@ -99,6 +113,118 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
     }
 }
 
+/// Integers, `BOOLEAN`, `YEAR`, `BIT`, floating-point numbers, string types and binary string
+/// types are converted to the matching variant, and with the `chrono` feature, `DATE`,
+/// `DATETIME` and `TIMESTAMP` as well. `TIMESTAMP` is converted to [`DynamicValue::DateTime`]
+/// as it's returned in the session time zone.
+///
+/// All other types, such as `DECIMAL`, `JSON`, `ENUM` and, without the `chrono` feature,
+/// temporal types, are converted to [`DynamicValue::Text`].
+impl ToDynamicValue for MySqlValueRef<'_> {
+    fn to_dynamic(&self) -> Result<DynamicValue, BoxDynError> {
+        if self.is_null() {
+            return Ok(DynamicValue::Null);
+        }
+
+        let value = self.clone();
+        let ty = &self.type_info;
+        let is_binary = ty.flags.contains(ColumnFlags::BINARY);
+
+        Ok(match ty.r#type {
+            ColumnType::Null => DynamicValue::Null,
+
+            ColumnType::Tiny if ty.max_size == Some(1) => DynamicValue::Bool(decode(value)?),
+            ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Long
+            | ColumnType::Int24
+            | ColumnType::LongLong
+                if ty.flags.contains(ColumnFlags::UNSIGNED) =>
+            {
+                DynamicValue::UInt(decode(value)?)
+            }
+            ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Long
+            | ColumnType::Int24
+            | ColumnType::LongLong => DynamicValue::Int(decode(value)?),
+            ColumnType::Year | ColumnType::Bit => DynamicValue::UInt(decode(value)?),
+
+            ColumnType::Float | ColumnType::Double => DynamicValue::Double(decode(value)?),
+
+            #[cfg(feature = "chrono")]
+            ColumnType::Date => DynamicValue::Date(decode(value)?),
+            #[cfg(feature = "chrono")]
+            ColumnType::Datetime | ColumnType::Timestamp => DynamicValue::DateTime(decode(value)?),
+            #[cfg(not(feature = "chrono"))]
+            ColumnType::Date | ColumnType::Datetime | ColumnType::Timestamp
+                if matches!(self.format, MySqlValueFormat::Binary) =>
+            {
+                DynamicValue::Text(binary_datetime_to_string(
+                    self.as_bytes()?,
+                    ty.r#type == ColumnType::Date,
+                )?)
+            }
+            ColumnType::Time => {
+                let time = decode::<MySqlTime>(value)?;
+
+                #[cfg(feature = "chrono")]
+                if let Ok(time) = chrono::NaiveTime::try_from(time) {
+                    return Ok(DynamicValue::Time(time));
+                }
+
+                DynamicValue::Text(time.to_string())
+            }
+
+            ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::String
+            | ColumnType::TinyBlob
+            | ColumnType::Blob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+                if is_binary =>
+            {
+                DynamicValue::Bytes(self.as_bytes()?.to_vec())
+            }
+            ColumnType::Geometry => DynamicValue::Bytes(self.as_bytes()?.to_vec()),
+
+            _ => DynamicValue::Text(self.as_str()?.to_owned()),
+        })
+    }
+}
+
+/// Format a `DATE`, `DATETIME` or `TIMESTAMP` in the binary format as MySQL does in the text format.
+#[cfg(not(feature = "chrono"))]
+fn binary_datetime_to_string(buf: &[u8], date_only: bool) -> Result<String, BoxDynError> {
+    use byteorder::{ByteOrder, LittleEndian};
+
+    // the value is truncated after the last nonzero part
+    let mut parts = [0u8; 11];
+
+    if buf.len() > parts.len() {
+        return Err(format!("expected at most 11 bytes for a date, got {}", buf.len()).into());
+    }
+
+    parts[..buf.len()].copy_from_slice(buf);
+
+    let year = LittleEndian::read_u16(&parts[..2]);
+    let [month, day, hour, minute, second] = [parts[2], parts[3], parts[4], parts[5], parts[6]];
+    let micros = LittleEndian::read_u32(&parts[7..]);
+
+    let mut text = format!("{year:04}-{month:02}-{day:02}");
+
+    if !date_only {
+        text.push_str(&format!(" {hour:02}:{minute:02}:{second:02}"));
+
+        if micros != 0 {
+            text.push_str(&format!(".{micros:06}"));
+        }
+    }
+
+    Ok(text)
+}
+
 fn is_null(value: Option<&[u8]>, ty: &MySqlTypeInfo) -> bool {
     if let Some(value) = value {
         // zero dates and date times should be treated the same as NULL
@@ -113,3 +239,9 @@ fn is_null(value: Option<&[u8]>, ty: &MySqlTypeInfo) -> bool {
 
     value.is_none()
 }
+
+/// [`Decode::decode()`] for a value of this driver, whose database can't be inferred
+/// from the value type alone.
+fn decode<'r, T: Decode<'r, MySql>>(value: MySqlValueRef<'r>) -> Result<T, BoxDynError> {
+    T::decode(value)
+}
@@ -18,6 +18,7 @@ use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
 use sqlx_core::ext::ustr::UStr;
+use sqlx_core::placeholders::PlaceholderSyntax;
 use sqlx_core::transaction::TransactionManager;
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = Postgres);
@@ -27,6 +28,10 @@ impl AnyConnectionBackend for PgConnection {
         <Postgres as Database>::NAME
     }
 
+    fn placeholder_syntax(&self) -> PlaceholderSyntax {
+        <Postgres as Database>::PLACEHOLDER_SYNTAX
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -39,6 +44,10 @@ impl AnyConnectionBackend for PgConnection {
         Connection::ping(self)
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self)
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         PgTransactionManager::begin(self)
     }
@@ -67,6 +76,10 @@ impl AnyConnectionBackend for PgConnection {
         Connection::should_flush(self)
     }
 
+    fn take_fatal_error(&mut self) -> Option<sqlx_core::Error> {
+        Connection::take_fatal_error(self)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -89,7 +102,7 @@ impl AnyConnectionBackend for PgConnection {
         };
 
         Box::pin(
-            self.run(query, arguments, 0, persistent, None)
+            self.run(query, arguments, 0, persistent, None, true)
                 .try_flatten_stream()
                 .map(
                     move |res: sqlx_core::Result<Either<PgQueryResult, PgRow>>| match res? {
@@ -115,7 +128,10 @@ impl AnyConnectionBackend for PgConnection {
 
         Box::pin(async move {
             let arguments = arguments?;
-            let mut stream = pin!(self.run(query, arguments, 1, persistent, None).await?);
+            let mut stream = pin!(
+                self.run(query, arguments, 1, persistent, None, true)
+                    .await?
+            );
 
             if let Some(Either::Right(row)) = stream.try_next().await? {
                 return Ok(Some(AnyRow::try_from(&row)?));
@@ -10,7 +10,9 @@ use crate::{PgConnection, PgTypeInfo, Postgres};
 
 use crate::type_info::PgArrayOf;
 pub(crate) use sqlx_core::arguments::Arguments;
+use sqlx_core::arguments::{ArgumentSummary, ArgumentsSummary};
 use sqlx_core::error::BoxDynError;
+use sqlx_core::type_info::TypeInfo;
 
 // TODO: buf.patch(|| ...) is a poor name, can we think of a better name? Maybe `buf.lazy(||)` ?
 // TODO: Extend the patch system to support dynamic lengths
@@ -105,6 +107,11 @@ impl PgArguments {
         Ok(())
     }
 
+    /// Whether encoding needs the parameter types inferred by the server.
+    pub(crate) fn has_patches(&self) -> bool {
+        !self.buffer.patches.is_empty()
+    }
+
     // Apply patches
     // This should only go out and ask postgres if we have not seen the type name yet
     pub(crate) async fn apply_patches(
@@ -161,6 +168,30 @@ impl<'q> Arguments<'q> for PgArguments {
     fn len(&self) -> usize {
         self.buffer.count
     }
+
+    fn summary(&self) -> ArgumentsSummary {
+        let mut buf = &self.buffer[..];
+
+        let arguments = self
+            .types
+            .iter()
+            .map(|ty| {
+                // each value is prefixed with its length, or -1 for NULL
+                let (len, rest) = buf.split_at(4);
+                let size =
+                    usize::try_from(i32::from_be_bytes([len[0], len[1], len[2], len[3]])).ok();
+
+                buf = &rest[size.unwrap_or(0)..];
+
+                ArgumentSummary {
+                    type_name: TypeInfo::name(ty).to_owned(),
+                    size,
+                }
+            })
+            .collect();
+
+        ArgumentsSummary::new::<Postgres>(arguments)
+    }
 }
 
 impl PgArgumentBuffer {
@@ -194,6 +225,11 @@ impl PgArgumentBuffer {
         Ok(())
     }
 
+    /// Whether an encoded value needs the OID of a type that is only known by name.
+    pub(crate) fn has_type_holes(&self) -> bool {
+        !self.type_holes.is_empty()
+    }
+
     // Adds a callback to be invoked later when we know the parameter type
     #[allow(dead_code)]
     pub(crate) fn patch<F>(&mut self, callback: F)
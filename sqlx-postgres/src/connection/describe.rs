@@ -131,12 +131,21 @@ impl PgConnection {
             };
 
             columns.push(column);
-            column_names.insert(name, index);
+            // if more than one column has the same name, lookups return the first
+            column_names.entry(name).or_insert(index);
         }
 
         Ok((columns, column_names))
     }
 
+    /// Returns `true` if the description has columns of types that would have to be looked up.
+    pub(super) fn has_unknown_types(&self, desc: &RowDescription) -> bool {
+        desc.fields.iter().any(|field| {
+            PgTypeInfo::try_from_oid(field.data_type_id).is_none()
+                && !self.inner.cache_type_info.contains_key(&field.data_type_id)
+        })
+    }
+
     pub(super) async fn handle_parameter_description(
         &mut self,
         desc: ParameterDescription,
@@ -211,7 +220,7 @@ impl PgConnection {
                      WHERE oid = $1",
         )
         .bind(oid)
-        .fetch_one(&mut *self)
+        .fetch_one(self.unmetered())
         .await?;
 
         let typ_type = TypType::try_from(typ_type);
@@ -266,7 +275,7 @@ ORDER BY enumsortorder
             "#,
         )
         .bind(oid)
-        .fetch_all(self)
+        .fetch_all(self.unmetered())
         .await?;
 
         Ok(PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
@@ -293,7 +302,7 @@ ORDER BY attnum
                 "#,
         )
         .bind(relation_id)
-        .fetch_all(&mut *self)
+        .fetch_all(self.unmetered())
         .await?;
 
         let mut fields = Vec::new();
@@ -335,7 +344,7 @@ WHERE rngtypid = $1
                 "#,
         )
         .bind(oid)
-        .fetch_one(&mut *self)
+        .fetch_one(self.unmetered())
         .await?;
 
         let element = self.maybe_fetch_type_info_by_oid(element_oid, true).await?;
@@ -368,7 +377,7 @@ WHERE rngtypid = $1
         // language=SQL
         let (oid,): (Oid,) = query_as("SELECT $1::regtype::oid")
             .bind(name)
-            .fetch_optional(&mut *self)
+            .fetch_optional(self.unmetered())
             .await?
             .ok_or_else(|| Error::TypeNotFound {
                 type_name: name.into(),
@@ -394,7 +403,7 @@ WHERE rngtypid = $1
         let (elem_oid, array_oid): (Oid, Oid) =
             query_as("SELECT oid, typarray FROM pg_catalog.pg_type WHERE oid = $1::regtype::oid")
                 .bind(&*array.elem_name)
-                .fetch_optional(&mut *self)
+                .fetch_optional(self.unmetered())
                 .await?
                 .ok_or_else(|| Error::TypeNotFound {
                     type_name: array.name.to_string(),
@@ -486,7 +495,7 @@ WHERE rngtypid = $1
 
         let mut nullables: Vec<Option<bool>> = nullable_query
             .build_query_scalar()
-            .fetch_all(&mut *self)
+            .fetch_all(self.unmetered())
             .await
             .map_err(|e| {
                 err_protocol!(
@@ -543,7 +552,7 @@ WHERE rngtypid = $1
         }
 
         let (Json(explains),): (Json<SmallVec<[Explain; 1]>>,) =
-            query_as(&explain).fetch_one(self).await?;
+            query_as(&explain).fetch_one(self.unmetered()).await?;
 
         let mut nullables = Vec::new();
 
@@ -16,6 +16,8 @@ use super::PgConnectionInner;
 
 impl PgConnection {
     pub(crate) async fn establish(options: &PgConnectOptions) -> Result<Self, Error> {
+        options.check_options()?;
+
         // Upgrade to TLS if we were asked to and the server supports it
         let mut stream = PgStream::connect(options).await?;
 
@@ -101,6 +103,15 @@ impl PgConnection {
                         sasl::authenticate(&mut stream, options, body).await?;
                     }
 
+                    Authentication::Gss | Authentication::Sspi => {
+                        return Err(Error::Configuration(
+                            "the server requested GSSAPI (Kerberos) or SSPI authentication, \
+                             which is not supported; allow another authentication method \
+                             for this user in pg_hba.conf"
+                                .into(),
+                        ));
+                    }
+
                     method => {
                         return Err(err_protocol!(
                             "unsupported authentication method: {:?}",
@@ -145,6 +156,9 @@ impl PgConnection {
                 pending_ready_for_query_count: 0,
                 next_statement_id: StatementId::NAMED_START,
                 cache_statement: StatementCache::new(options.statement_cache_capacity),
+                shared_metadata: options.shared_metadata.clone(),
+                unnamed_statements: options.unnamed_statements,
+                strict_timestamps: options.strict_timestamps,
                 cache_type_oid: HashMap::new(),
                 cache_type_info: HashMap::new(),
                 cache_elem_type_to_array: HashMap::new(),
@@ -153,3 +167,322 @@ impl PgConnection {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::io;
+    use std::pin::pin;
+    use std::task::{Context, Poll};
+
+    use base64::prelude::{Engine as _, BASE64_STANDARD};
+    use futures_util::task::noop_waker;
+    use hmac::{Hmac, Mac};
+    use md5::Md5;
+    use sha2::{Digest, Sha256};
+    use sqlx_core::io::ReadBuf;
+    use sqlx_core::net::Socket;
+
+    use crate::error::Error;
+    use crate::{PgConnectOptions, PgConnection, PgSslMode};
+
+    const USERNAME: &str = "root";
+    const MD5_SALT: [u8; 4] = [147, 24, 57, 152];
+    const SCRAM_SALT: &[u8] = b"sqlx-salt";
+    const SCRAM_ITERATIONS: u32 = 4096;
+    const SCRAM_SERVER_NONCE: &str = "server-nonce";
+
+    /// How the mock server authenticates the client, with the password it expects.
+    #[derive(Clone, Copy)]
+    enum Auth {
+        Cleartext(&'static str),
+        Md5(&'static str),
+        /// SCRAM-SHA-256, with the password as the server stores it, i.e. after SASLprep.
+        Scram(&'static str),
+    }
+
+    /// An in-memory server that answers the startup and authentication messages of a client.
+    struct MockServer {
+        auth: Auth,
+        started: bool,
+        // client-first-message-bare and server-first-message
+        scram: Option<(String, String)>,
+        received: Vec<u8>,
+        response: Vec<u8>,
+        response_pos: usize,
+    }
+
+    impl MockServer {
+        fn new(auth: Auth) -> Self {
+            MockServer {
+                auth,
+                started: false,
+                scram: None,
+                received: Vec::new(),
+                response: Vec::new(),
+                response_pos: 0,
+            }
+        }
+
+        fn handle_messages(&mut self) {
+            loop {
+                // the startup message has no format code
+                let header = if self.started { 1 } else { 0 };
+
+                let Some(len) = self.received.get(header..header + 4) else {
+                    return;
+                };
+
+                let end =
+                    header + usize::try_from(i32::from_be_bytes(len.try_into().unwrap())).unwrap();
+
+                if self.received.len() < end {
+                    return;
+                }
+
+                let message: Vec<u8> = self.received.drain(..end).collect();
+
+                if self.started {
+                    assert_eq!(message[0], b'p', "expected a password message");
+                    self.handle_password(&message[5..]);
+                } else {
+                    self.started = true;
+                    self.handle_startup();
+                }
+            }
+        }
+
+        fn handle_startup(&mut self) {
+            match self.auth {
+                Auth::Cleartext(_) => self.send_auth(3, &[]),
+                Auth::Md5(_) => self.send_auth(5, &MD5_SALT),
+                Auth::Scram(_) => self.send_auth(10, b"SCRAM-SHA-256\0\0"),
+            }
+        }
+
+        fn handle_password(&mut self, body: &[u8]) {
+            match self.auth {
+                Auth::Cleartext(password) => {
+                    self.finish(body == [password.as_bytes(), b"\0"].concat());
+                }
+
+                Auth::Md5(password) => {
+                    let inner = hex::encode(Md5::digest([password, USERNAME].concat()));
+                    let outer = Md5::digest([inner.as_bytes(), &MD5_SALT].concat());
+                    let expected = format!("md5{}\0", hex::encode(outer));
+
+                    self.finish(body == expected.as_bytes());
+                }
+
+                Auth::Scram(password) => match self.scram.take() {
+                    None => {
+                        // SASLInitialResponse: mechanism, length of the response, response
+                        let mechanism_end = body.iter().position(|&b| b == 0).unwrap();
+                        assert_eq!(&body[..mechanism_end], b"SCRAM-SHA-256");
+
+                        let client_first = std::str::from_utf8(&body[mechanism_end + 5..]).unwrap();
+                        let client_first_bare = client_first.strip_prefix("n,,").unwrap();
+                        let (_, client_nonce) = client_first_bare.split_once(",r=").unwrap();
+
+                        let server_first = format!(
+                            "r={client_nonce}{SCRAM_SERVER_NONCE},s={},i={SCRAM_ITERATIONS}",
+                            BASE64_STANDARD.encode(SCRAM_SALT)
+                        );
+
+                        self.send_auth(11, server_first.as_bytes());
+                        self.scram = Some((client_first_bare.to_owned(), server_first));
+                    }
+
+                    Some((client_first_bare, server_first)) => {
+                        let client_final = std::str::from_utf8(body).unwrap();
+                        let (without_proof, proof) = client_final.rsplit_once(",p=").unwrap();
+                        let proof = BASE64_STANDARD.decode(proof).unwrap();
+
+                        let salted_password = hi(password.as_bytes());
+                        let client_key = hmac(&salted_password, b"Client Key");
+                        let stored_key = Sha256::digest(&client_key);
+
+                        let auth_message =
+                            format!("{client_first_bare},{server_first},{without_proof}");
+                        let client_signature = hmac(&stored_key, auth_message.as_bytes());
+
+                        // ClientKey := ClientProof XOR ClientSignature
+                        let client_key: Vec<u8> = proof
+                            .iter()
+                            .zip(&client_signature)
+                            .map(|(a, b)| a ^ b)
+                            .collect();
+
+                        if Sha256::digest(&client_key) != stored_key {
+                            self.finish(false);
+                            return;
+                        }
+
+                        let server_key = hmac(&salted_password, b"Server Key");
+                        let server_signature = hmac(&server_key, auth_message.as_bytes());
+                        let server_final =
+                            format!("v={}", BASE64_STANDARD.encode(server_signature));
+
+                        self.send_auth(12, server_final.as_bytes());
+                        self.finish(true);
+                    }
+                },
+            }
+        }
+
+        fn send_auth(&mut self, code: i32, data: &[u8]) {
+            self.send(b'R', &[&code.to_be_bytes(), data].concat());
+        }
+
+        fn finish(&mut self, authenticated: bool) {
+            if authenticated {
+                self.send_auth(0, &[]);
+                self.send(b'Z', b"I");
+            } else {
+                self.send(
+                    b'E',
+                    b"SFATAL\0VFATAL\0C28P01\0Mpassword authentication failed\0\0",
+                );
+            }
+        }
+
+        fn send(&mut self, format: u8, body: &[u8]) {
+            self.response.push(format);
+            self.response
+                .extend_from_slice(&(i32::try_from(body.len()).unwrap() + 4).to_be_bytes());
+            self.response.extend_from_slice(body);
+        }
+    }
+
+    impl Socket for MockServer {
+        fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+            let available = &self.response[self.response_pos..];
+
+            if available.is_empty() {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+
+            let dst = buf.init_mut();
+            let read = std::cmp::min(dst.len(), available.len());
+
+            dst[..read].copy_from_slice(&available[..read]);
+            self.response_pos += read;
+
+            Ok(read)
+        }
+
+        fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.received.extend_from_slice(buf);
+            self.handle_messages();
+
+            Ok(buf.len())
+        }
+
+        fn poll_read_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            if self.response_pos < self.response.len() {
+                Poll::Ready(Ok(()))
+            } else {
+                Poll::Pending
+            }
+        }
+
+        fn poll_write_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    // Hi() from RFC 5802, i.e. PBKDF2 with HMAC-SHA-256
+    fn hi(password: &[u8]) -> Vec<u8> {
+        let mut u = hmac(password, &[SCRAM_SALT, &1u32.to_be_bytes()].concat());
+        let mut result = u.clone();
+
+        for _ in 1..SCRAM_ITERATIONS {
+            u = hmac(password, &u);
+            result.iter_mut().zip(&u).for_each(|(r, u)| *r ^= u);
+        }
+
+        result
+    }
+
+    /// Poll `fut` to completion; the mock server never makes the client wait.
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = pin!(fut);
+
+        for _ in 0..100 {
+            if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+
+        panic!("the client is waiting for a message the mock server didn't send");
+    }
+
+    fn connect(auth: Auth, password: &str) -> Result<PgConnection, Error> {
+        let options = PgConnectOptions::new_without_pgpass()
+            .username(USERNAME)
+            .password(password)
+            .ssl_mode(PgSslMode::Disable)
+            .socket_connector(move || {
+                let server = MockServer::new(auth);
+                async move { Ok(server) }
+            });
+
+        block_on(PgConnection::establish(&options))
+    }
+
+    fn assert_auth_failed(result: Result<PgConnection, Error>) {
+        let err = result.expect_err("expected authentication to fail");
+
+        assert_eq!(
+            err.as_database_error().and_then(|e| e.code()).as_deref(),
+            Some("28P01"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn test_auth_cleartext_password() {
+        let password = "pass\u{A0}word\u{1F980}";
+
+        connect(Auth::Cleartext(password), password).unwrap();
+        assert_auth_failed(connect(Auth::Cleartext(password), "password"));
+    }
+
+    #[test]
+    fn test_auth_md5_password() {
+        // the password is hashed as UTF-8 without any normalization, like libpq does
+        let password = "pass\u{A0}word\u{1F980}";
+
+        connect(Auth::Md5(password), password).unwrap();
+        assert_auth_failed(connect(Auth::Md5("pass word\u{1F980}"), password));
+    }
+
+    #[test]
+    fn test_auth_scram_password() {
+        connect(Auth::Scram("password"), "password").unwrap();
+        assert_auth_failed(connect(Auth::Scram("password"), "passw0rd"));
+    }
+
+    #[test]
+    fn test_auth_scram_password_saslprep() {
+        // non-ASCII spaces are mapped to U+0020
+        connect(Auth::Scram("pass word"), "pass\u{A0}word").unwrap();
+        // soft hyphens are removed
+        connect(Auth::Scram("password"), "pass\u{AD}word").unwrap();
+        // passwords that SASLprep rejects, like ones with emoji, are used as-is
+        connect(Auth::Scram("pass\u{1F980}word"), "pass\u{1F980}word").unwrap();
+        assert_auth_failed(connect(Auth::Scram("pass\u{A0}word"), "pass\u{A0}word"));
+    }
+}
@@ -7,6 +7,7 @@ use crate::message::{
     self, BackendMessageFormat, Bind, Close, CommandComplete, DataRow, ParameterDescription, Parse,
     ParseComplete, Query, RowDescription,
 };
+use crate::metrics::QueryMetrics;
 use crate::statement::PgStatementMetadata;
 use crate::{
     statement::PgStatement, PgArguments, PgConnection, PgQueryResult, PgRow, PgTypeInfo,
@@ -18,6 +19,7 @@ use futures_core::Stream;
 use futures_util::TryStreamExt;
 use sqlx_core::arguments::Arguments;
 use sqlx_core::Either;
+use std::collections::VecDeque;
 use std::{borrow::Cow, pin::pin, sync::Arc};
 
 async fn prepare(
@@ -25,9 +27,17 @@ async fn prepare(
     sql: &str,
     parameters: &[PgTypeInfo],
     metadata: Option<Arc<PgStatementMetadata>>,
+    persistent: bool,
 ) -> Result<(StatementId, Arc<PgStatementMetadata>), Error> {
-    let id = conn.inner.next_statement_id;
-    conn.inner.next_statement_id = id.next();
+    // a statement that is not going to be cached is parsed into the unnamed statement,
+    // which the server replaces on the next PARSE so it never needs to be closed
+    let id = if persistent {
+        let id = conn.inner.next_statement_id;
+        conn.inner.next_statement_id = id.next();
+        id
+    } else {
+        StatementId::UNNAMED
+    };
 
     // build a list of type OIDs to send to the database in the PARSE command
     // we have not yet started the query sequence, so we are *safe* to cleanly make
@@ -77,6 +87,16 @@ async fn prepare(
         // each SYNC produces one READY FOR QUERY
         conn.recv_ready_for_query().await?;
 
+        // another connection may have already built the metadata for this description
+        let shared = conn.inner.shared_metadata.clone().filter(|_| persistent);
+
+        if let Some(metadata) = shared
+            .as_ref()
+            .and_then(|shared| shared.get_matching(sql, &parameters, rows.as_ref()))
+        {
+            return Ok((id, metadata));
+        }
+
         let parameters = conn.handle_parameter_description(parameters).await?;
 
         let (columns, column_names) = conn.handle_row_description(rows, true).await?;
@@ -85,16 +105,37 @@ async fn prepare(
         // continuing
         conn.wait_until_ready().await?;
 
-        Arc::new(PgStatementMetadata {
+        let metadata = Arc::new(PgStatementMetadata {
             parameters,
             columns,
             column_names: Arc::new(column_names),
-        })
+        });
+
+        if let Some(shared) = shared {
+            shared.insert(sql, Arc::clone(&metadata));
+        }
+
+        metadata
     };
 
     Ok((id, metadata))
 }
 
+/// The SQLSTATE of `cached plan must not change result type`, returned when executing
+/// a statement prepared before a schema change that changed its columns, e.g. an `ALTER TABLE`.
+///
+/// The code is `feature_not_supported`, so it's returned for other errors as well, but evicting
+/// a statement that is still valid only means it's prepared again.
+const STALE_STATEMENT: &str = "0A000";
+
+fn check_parameter_count(expected: usize, got: usize) -> Result<(), Error> {
+    if expected != got {
+        return Err(Error::ParameterCountMismatch { expected, got });
+    }
+
+    Ok(())
+}
+
 async fn recv_desc_params(conn: &mut PgConnection) -> Result<ParameterDescription, Error> {
     conn.inner.stream.recv_expect().await
 }
@@ -159,6 +200,28 @@ impl PgConnection {
         self.inner.pending_ready_for_query_count += 1;
     }
 
+    /// Evict the statement of `sql` from the statement cache and close it,
+    /// so it's prepared again the next time it's executed.
+    ///
+    /// The shared metadata is not evicted, as it's checked against the description of
+    /// the statement when it's prepared again, and another connection may have replaced it.
+    async fn evict_statement(&mut self, sql: &str) -> Result<(), Error> {
+        if let Some((id, _)) = self.inner.cache_statement.remove(sql) {
+            // consume the `ReadyForQuery` following the error first
+            self.wait_until_ready().await?;
+
+            self.inner.stream.write_msg(Close::Statement(id))?;
+            self.write_sync();
+
+            self.inner.stream.flush().await?;
+
+            self.wait_for_close_complete(1).await?;
+            self.recv_ready_for_query().await?;
+        }
+
+        Ok(())
+    }
+
     async fn get_or_prepare<'a>(
         &mut self,
         sql: &str,
@@ -173,7 +236,10 @@ impl PgConnection {
             return Ok((*statement).clone());
         }
 
-        let statement = prepare(self, sql, parameters, metadata).await?;
+        // only statements the caller doesn't want to keep are parsed into the unnamed statement;
+        // `describe()` needs a named statement, and so does a statement whose columns have types
+        // that are looked up while preparing it, as the lookups may parse into the unnamed one
+        let statement = prepare(self, sql, parameters, metadata, store_to_cache).await?;
 
         if store_to_cache && self.inner.cache_statement.is_enabled() {
             if let Some((id, _)) = self.inner.cache_statement.insert(sql, statement.clone()) {
@@ -190,6 +256,11 @@ impl PgConnection {
         Ok(statement)
     }
 
+    /// Run a query, reporting it to the [`Metrics`][sqlx_core::metrics::Metrics] of the
+    /// connection if `metered`.
+    ///
+    /// Queries issued by the driver itself, such as type lookups, are not `metered`.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         query: &'q str,
@@ -197,6 +268,28 @@ impl PgConnection {
         limit: u8,
         persistent: bool,
         metadata_opt: Option<Arc<PgStatementMetadata>>,
+        metered: bool,
+    ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
+        let metrics = if metered {
+            QueryMetrics::start(&self.inner.log_settings)
+        } else {
+            None
+        };
+
+        let result = self
+            .run_unmetered(query, arguments, limit, persistent, metadata_opt)
+            .await;
+
+        QueryMetrics::instrument(metrics, result)
+    }
+
+    async fn run_unmetered<'e, 'c: 'e, 'q: 'e>(
+        &'c mut self,
+        query: &'q str,
+        arguments: Option<PgArguments>,
+        limit: u8,
+        persistent: bool,
+        metadata_opt: Option<Arc<PgStatementMetadata>>,
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
         let mut logger = QueryLogger::new(query, self.inner.log_settings.clone());
 
@@ -205,7 +298,7 @@ impl PgConnection {
 
         let mut metadata: Arc<PgStatementMetadata>;
 
-        let format = if let Some(mut arguments) = arguments {
+        let (format, describe_portal) = if let Some(mut arguments) = arguments {
             // Check this before we write anything to the stream.
             //
             // Note: Postgres actually interprets this value as unsigned,
@@ -219,19 +312,80 @@ impl PgConnection {
                 )
             })?;
 
-            // prepare the statement if this our first time executing it
-            // always return the statement ID here
-            let (statement, metadata_) = self
-                .get_or_prepare(query, &arguments.types, persistent, metadata_opt)
-                .await?;
+            let (statement, describe_portal) = if self.inner.unnamed_statements {
+                // parse, bind and execute the unnamed statement in one round-trip
+                let (param_types, describe_portal) = if arguments.has_patches() {
+                    // encoding needs the parameter types inferred by the server, so the statement
+                    // is described first; it's not cached, and it's parsed again below as
+                    // looking up types may have replaced the unnamed statement since
+                    let (_, metadata_) = self
+                        .get_or_prepare(query, &arguments.types, false, metadata_opt)
+                        .await?;
 
-            metadata = metadata_;
+                    metadata = metadata_;
 
-            // patch holes created during encoding
-            arguments.apply_patches(self, &metadata.parameters).await?;
+                    check_parameter_count(metadata.parameters.len(), arguments.len())?;
 
-            // consume messages till `ReadyForQuery` before bind and execute
-            self.wait_until_ready().await?;
+                    // patch holes created during encoding
+                    arguments.apply_patches(self, &metadata.parameters).await?;
+
+                    let mut param_types = Vec::with_capacity(metadata.parameters.len());
+
+                    for ty in &metadata.parameters {
+                        param_types.push(self.resolve_type_id(&ty.0).await?);
+                    }
+
+                    (param_types, false)
+                } else {
+                    let mut param_types = Vec::with_capacity(arguments.types.len());
+
+                    for ty in &arguments.types {
+                        param_types.push(self.resolve_type_id(&ty.0).await?);
+                    }
+
+                    // fill type holes created during encoding
+                    arguments.apply_patches(self, &[]).await?;
+
+                    // if we weren't given the columns, the portal will describe them
+                    // before any rows are returned
+                    let describe_portal = metadata_opt.is_none();
+                    metadata = metadata_opt.unwrap_or_default();
+
+                    (param_types, describe_portal)
+                };
+
+                self.wait_until_ready().await?;
+
+                self.inner.stream.write_msg(Parse {
+                    param_types: &param_types,
+                    query,
+                    statement: StatementId::UNNAMED,
+                })?;
+
+                (StatementId::UNNAMED, describe_portal)
+            } else {
+                // prepare the statement if this our first time executing it
+                // always return the statement ID here
+                let (statement, metadata_) = self
+                    .get_or_prepare(query, &arguments.types, persistent, metadata_opt)
+                    .await?;
+
+                metadata = metadata_;
+
+                // The server counts a parameter for every type we send in `Parse`, even if
+                // the query doesn't use it, so this only catches missing arguments. The SQL
+                // can't be scanned for placeholders instead, as `$N` can also refer to
+                // the arguments of a function defined in it.
+                check_parameter_count(metadata.parameters.len(), arguments.len())?;
+
+                // patch holes created during encoding
+                arguments.apply_patches(self, &metadata.parameters).await?;
+
+                // consume messages till `ReadyForQuery` before bind and execute
+                self.wait_until_ready().await?;
+
+                (statement, false)
+            };
 
             // bind to attach the arguments to the statement and create a portal
             self.inner.stream.write_msg(Bind {
@@ -243,6 +397,12 @@ impl PgConnection {
                 result_formats: &[PgValueFormat::Binary],
             })?;
 
+            if describe_portal {
+                self.inner
+                    .stream
+                    .write_msg(message::Describe::Portal(PortalId::UNNAMED))?;
+            }
+
             // executes the portal up to the passed limit
             // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
             self.inner.stream.write_msg(message::Execute {
@@ -270,7 +430,7 @@ impl PgConnection {
             self.write_sync();
 
             // prepared statements are binary
-            PgValueFormat::Binary
+            (PgValueFormat::Binary, describe_portal)
         } else {
             // Query will trigger a ReadyForQuery
             self.inner.stream.write_msg(Query(query))?;
@@ -280,14 +440,32 @@ impl PgConnection {
             metadata = Arc::new(PgStatementMetadata::default());
 
             // and unprepared statements are text
-            PgValueFormat::Text
+            (PgValueFormat::Text, false)
         };
 
         self.inner.stream.flush().await?;
 
         Ok(try_stream! {
+            // messages received ahead of time while looking up the types of the columns
+            let mut buffered = VecDeque::new();
+            let mut ready = false;
+
             loop {
-                let message = self.inner.stream.recv().await?;
+                let received = match buffered.pop_front() {
+                    Some(message) => Ok(message),
+                    // the `ReadyForQuery` was already handled while buffering
+                    None if ready => break,
+                    None => self.inner.stream.recv().await,
+                };
+
+                let message = match received {
+                    Ok(message) => message,
+                    Err(Error::Database(error)) if error.code().as_deref() == Some(STALE_STATEMENT) => {
+                        self.evict_statement(query).await?;
+                        return Err(Error::Database(error));
+                    }
+                    Err(error) => return Err(error),
+                };
 
                 match message.format {
                     BackendMessageFormat::BindComplete
@@ -326,8 +504,29 @@ impl PgConnection {
 
                     BackendMessageFormat::RowDescription => {
                         // indicates that a *new* set of rows are about to be returned
+                        let desc: RowDescription = message.decode()?;
+
+                        // the portal is described right before its rows are sent, so types we
+                        // haven't seen yet can only be looked up after the rest of the response
+                        // has been received
+                        let should_fetch = describe_portal && self.has_unknown_types(&desc);
+
+                        if should_fetch {
+                            loop {
+                                let message = self.inner.stream.recv().await?;
+
+                                if let BackendMessageFormat::ReadyForQuery = message.format {
+                                    self.handle_ready_for_query(message)?;
+                                    ready = true;
+                                    break;
+                                }
+
+                                buffered.push_back(message);
+                            }
+                        }
+
                         let (columns, column_names) = self
-                            .handle_row_description(Some(message.decode()?), false)
+                            .handle_row_description(Some(desc), should_fetch)
                             .await?;
 
                         metadata = Arc::new(PgStatementMetadata {
@@ -346,6 +545,7 @@ impl PgConnection {
                             data,
                             format,
                             metadata: Arc::clone(&metadata),
+                            strict_timestamps: self.inner.strict_timestamps,
                         };
 
                         r#yield!(Either::Right(row));
@@ -371,29 +571,37 @@ impl PgConnection {
     }
 }
 
-impl<'c> Executor<'c> for &'c mut PgConnection {
-    type Database = Postgres;
+impl PgConnection {
+    /// Queries issued with the returned executor are not reported to the
+    /// [`Metrics`][sqlx_core::metrics::Metrics] of the connection.
+    ///
+    /// This is used for the queries the driver issues itself, such as type lookups.
+    pub(crate) fn unmetered(&mut self) -> Unmetered<'_> {
+        Unmetered(self)
+    }
 
-    fn fetch_many<'e, 'q, E>(
-        self,
+    fn fetch_many_with<'e, 'q, E>(
+        &'e mut self,
         mut query: E,
+        metered: bool,
     ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, Error>>
     where
-        'c: 'e,
-        E: Execute<'q, Self::Database>,
+        E: Execute<'q, Postgres>,
         'q: 'e,
         E: 'q,
     {
-        let sql = query.sql();
         // False positive: https://github.com/rust-lang/rust-clippy/issues/12560
         #[allow(clippy::map_clone)]
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
 
+        // the stream owns the query, as the SQL may be borrowed from it
         Box::pin(try_stream! {
             let arguments = arguments?;
-            let mut s = pin!(self.run(sql, arguments, 0, persistent, metadata).await?);
+            let sql = query.sql();
+            let mut s = pin!(self.run(sql, arguments, 0, persistent, metadata, metered)
+                .await?);
 
             while let Some(v) = s.try_next().await? {
                 r#yield!(v);
@@ -403,14 +611,16 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         })
     }
 
-    fn fetch_optional<'e, 'q, E>(self, mut query: E) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
+    fn fetch_optional_with<'e, 'q, E>(
+        &'e mut self,
+        mut query: E,
+        metered: bool,
+    ) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
     where
-        'c: 'e,
-        E: Execute<'q, Self::Database>,
+        E: Execute<'q, Postgres>,
         'q: 'e,
         E: 'q,
     {
-        let sql = query.sql();
         // False positive: https://github.com/rust-lang/rust-clippy/issues/12560
         #[allow(clippy::map_clone)]
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
@@ -419,7 +629,11 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
 
         Box::pin(async move {
             let arguments = arguments?;
-            let mut s = pin!(self.run(sql, arguments, 1, persistent, metadata).await?);
+            let sql = query.sql();
+            let mut s = pin!(
+                self.run(sql, arguments, 1, persistent, metadata, metered)
+                    .await?
+            );
 
             // With deferred constraints we need to check all responses as we
             // could get a OK response (with uncommitted data), only to get an
@@ -435,6 +649,37 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
             Ok(ret)
         })
     }
+}
+
+/// See [`PgConnection::unmetered()`].
+#[derive(Debug)]
+pub(crate) struct Unmetered<'c>(&'c mut PgConnection);
+
+impl<'c> Executor<'c> for &'c mut PgConnection {
+    type Database = Postgres;
+
+    fn fetch_many<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_many_with(query, true)
+    }
+
+    fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_optional_with(query, true)
+    }
 
     fn prepare_with<'e, 'q: 'e>(
         self,
@@ -478,3 +723,51 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         })
     }
 }
+
+impl<'c> Executor<'c> for Unmetered<'c> {
+    type Database = Postgres;
+
+    fn fetch_many<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.0.fetch_many_with(query, false)
+    }
+
+    fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.0.fetch_optional_with(query, false)
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [PgTypeInfo],
+    ) -> BoxFuture<'e, Result<PgStatement<'q>, Error>>
+    where
+        'c: 'e,
+    {
+        self.0.prepare_with(sql, parameters)
+    }
+
+    fn describe<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>>
+    where
+        'c: 'e,
+    {
+        self.0.describe(sql)
+    }
+}
@@ -5,8 +5,10 @@ use crate::HashMap;
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
 
+use self::shared_metadata::SharedStatementMetadata;
 use crate::common::StatementCache;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::ext::ustr::UStr;
 use crate::io::StatementId;
 use crate::message::{
@@ -26,10 +28,20 @@ pub(crate) mod describe;
 mod establish;
 mod executor;
 mod sasl;
+pub(crate) mod shared_metadata;
 mod stream;
 mod tls;
 
 /// A connection to a PostgreSQL database.
+///
+/// ### Resetting Session State
+/// [`Connection::reset()`] rolls back any open transaction and then runs
+/// [`DISCARD ALL`](https://www.postgresql.org/docs/current/sql-discard.html), which also
+/// deallocates all prepared statements, so the statement cache is cleared rather than
+/// keeping it valid with piecemeal `UNLISTEN`/`RESET ALL`/etc. statements.
+///
+/// Settings passed in the startup packet, like [`PgConnectOptions::options()`], are
+/// the session defaults and so are kept.
 pub struct PgConnection {
     pub(crate) inner: Box<PgConnectionInner>,
 }
@@ -57,6 +69,17 @@ pub struct PgConnectionInner {
     // cache statement by query string to the id and columns
     cache_statement: StatementCache<(StatementId, Arc<PgStatementMetadata>)>,
 
+    // statement metadata shared with the other connections opened with the same options,
+    // see `PgConnectOptions::share_statement_metadata()`
+    shared_metadata: Option<Arc<SharedStatementMetadata>>,
+
+    // parse queries with arguments into the unnamed statement in the same round-trip as
+    // binding and executing them, see `PgConnectOptions::unnamed_statements()`
+    unnamed_statements: bool,
+
+    // see `PgConnectOptions::strict_timestamps()`
+    pub(crate) strict_timestamps: bool,
+
     // cache user-defined types by id <-> info
     cache_type_info: HashMap<Oid, PgTypeInfo>,
     cache_type_oid: HashMap<UStr, Oid>,
@@ -66,7 +89,7 @@ pub struct PgConnectionInner {
     pub(crate) pending_ready_for_query_count: usize,
 
     // current transaction status
-    transaction_status: TransactionStatus,
+    pub(crate) transaction_status: TransactionStatus,
     pub(crate) transaction_depth: usize,
 
     log_settings: LogSettings,
@@ -78,6 +101,24 @@ impl PgConnection {
         self.inner.stream.server_version_num
     }
 
+    /// The current value of a parameter reported by the server, e.g. `TimeZone`,
+    /// `server_version` or `standard_conforming_strings`.
+    ///
+    /// The server reports the parameters in [`ParameterStatus`] messages when connecting and
+    /// again whenever one of them changes, e.g. after a `SET TIME ZONE`. The values are updated
+    /// as those messages are received by the connection.
+    ///
+    /// Returns `None` if the server did not report the parameter.
+    ///
+    /// [`ParameterStatus`]: https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-ASYNC
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.inner
+            .stream
+            .parameter_statuses
+            .get(name)
+            .map(String::as_str)
+    }
+
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.inner.stream.write_buffer_mut().is_empty() {
@@ -95,7 +136,7 @@ impl PgConnection {
         Ok(())
     }
 
-    async fn recv_ready_for_query(&mut self) -> Result<(), Error> {
+    pub(crate) async fn recv_ready_for_query(&mut self) -> Result<(), Error> {
         let r: ReadyForQuery = self.inner.stream.recv_expect().await?;
 
         self.inner.pending_ready_for_query_count -= 1;
@@ -148,10 +189,14 @@ impl Connection for PgConnection {
         // connection and terminates.
 
         Box::pin(async move {
+            // Finish the work that is still pending first, like the `ROLLBACK` queued by
+            // a dropped transaction, so its locks are released by the time this returns.
+            let pending = self.wait_until_ready().await;
+
             self.inner.stream.send(Terminate).await?;
             self.inner.stream.shutdown().await?;
 
-            Ok(())
+            pending
         })
     }
 
@@ -175,6 +220,25 @@ impl Connection for PgConnection {
         })
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            // `DISCARD ALL` cannot run inside a transaction block
+            if !matches!(self.inner.transaction_status, TransactionStatus::Idle) {
+                self.unmetered().execute("ROLLBACK").await?;
+            }
+
+            self.unmetered().execute("DISCARD ALL").await?;
+
+            // the server deallocated all prepared statements
+            self.inner.transaction_depth = 0;
+            self.inner.cache_statement.clear();
+
+            Ok(())
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -224,6 +288,11 @@ impl Connection for PgConnection {
     fn should_flush(&self) -> bool {
         !self.inner.stream.write_buffer().is_empty()
     }
+
+    #[doc(hidden)]
+    fn take_fatal_error(&mut self) -> Option<Error> {
+        self.inner.stream.fatal_error.take()
+    }
 }
 
 // Implement `AsMut<Self>` so that `PgConnection` can be wrapped in
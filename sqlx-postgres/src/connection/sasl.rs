@@ -8,6 +8,7 @@ use sha2::{Digest, Sha256};
 use stringprep::saslprep;
 
 use base64::prelude::{Engine as _, BASE64_STANDARD};
+use std::borrow::Cow;
 
 const GS2_HEADER: &str = "n,,";
 const CHANNEL_ATTR: &str = "c";
@@ -87,7 +88,7 @@ pub(crate) async fn authenticate(
 
     // SaltedPassword := Hi(Normalize(password), salt, i)
     let salted_password = hi(
-        options.password.as_deref().unwrap_or_default(),
+        &normalize(options.password.as_deref().unwrap_or_default()),
         &cont.salt,
         cont.iterations,
     )?;
@@ -186,6 +187,16 @@ fn gen_nonce() -> String {
     format!("{NONCE_ATTR}={nonce}")
 }
 
+/// `Normalize(str)`: prepare the password with SASLprep (RFC 4013), which maps non-ASCII
+/// spaces to U+0020, removes characters like soft hyphens and applies NFKC.
+///
+/// Like libpq, a password that SASLprep rejects (e.g. because it contains emoji, which are
+/// unassigned in the Unicode version it uses) is used as-is. The server does the same when it
+/// computes the stored verifier, so both sides agree.
+fn normalize(password: &str) -> Cow<'_, str> {
+    saslprep(password).unwrap_or(Cow::Borrowed(password))
+}
+
 // Hi(str, salt, i):
 fn hi<'a>(s: &'a str, salt: &'a [u8], iter_count: u32) -> Result<[u8; 32], Error> {
     let mut mac = Hmac::<Sha256>::new_from_slice(s.as_bytes()).map_err(Error::protocol)?;
@@ -205,6 +216,21 @@ fn hi<'a>(s: &'a str, salt: &'a [u8], iter_count: u32) -> Result<[u8; 32], Error
     Ok(hi.into())
 }
 
+#[test]
+fn test_normalize_password() {
+    assert_eq!(normalize("password"), "password");
+    // non-ASCII space
+    assert_eq!(normalize("pass\u{A0}word"), "pass word");
+    // soft hyphen, which is mapped to nothing
+    assert_eq!(normalize("pass\u{AD}word"), "password");
+    // NFKC
+    assert_eq!(normalize("\u{FB01}ve"), "five");
+    // unassigned in Unicode 3.2, so used as-is
+    assert_eq!(normalize("pass\u{1F980}word"), "pass\u{1F980}word");
+    // prohibited, so used as-is
+    assert_eq!(normalize("pass\u{7}word"), "pass\u{7}word");
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 #[bench]
 fn bench_sasl_hi(b: &mut test::Bencher) {
@@ -0,0 +1,74 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::common::StatementCache;
+use crate::message::{ParameterDescription, RowDescription};
+use crate::statement::PgStatementMetadata;
+
+/// The metadata of the statements prepared by all connections opened with the same
+/// [`PgConnectOptions`][crate::PgConnectOptions], keyed by their SQL.
+///
+/// See [`PgConnectOptions::share_statement_metadata()`][crate::PgConnectOptions::share_statement_metadata].
+#[derive(Debug)]
+pub(crate) struct SharedStatementMetadata {
+    cache: Mutex<StatementCache<Arc<PgStatementMetadata>>>,
+}
+
+impl SharedStatementMetadata {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            cache: Mutex::new(StatementCache::new(capacity)),
+        }
+    }
+
+    fn cache(&self) -> MutexGuard<'_, StatementCache<Arc<PgStatementMetadata>>> {
+        // the cache can't be left inconsistent by a panic
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the metadata of `sql` if another connection prepared it with the same
+    /// parameters and columns as described by the server now.
+    ///
+    /// If the descriptions don't match, e.g. after an `ALTER TABLE`, the metadata is evicted.
+    pub(crate) fn get_matching(
+        &self,
+        sql: &str,
+        parameters: &ParameterDescription,
+        rows: Option<&RowDescription>,
+    ) -> Option<Arc<PgStatementMetadata>> {
+        let mut cache = self.cache();
+        let metadata = cache.get_mut(sql)?;
+
+        if matches(metadata, parameters, rows) {
+            return Some(Arc::clone(metadata));
+        }
+
+        cache.remove(sql);
+        None
+    }
+
+    pub(crate) fn insert(&self, sql: &str, metadata: Arc<PgStatementMetadata>) {
+        self.cache().insert(sql, metadata);
+    }
+}
+
+fn matches(
+    metadata: &PgStatementMetadata,
+    parameters: &ParameterDescription,
+    rows: Option<&RowDescription>,
+) -> bool {
+    let fields = rows.map_or(&[][..], |rows| &rows.fields);
+
+    metadata.parameters.len() == parameters.types.len()
+        && metadata
+            .parameters
+            .iter()
+            .zip(&parameters.types)
+            .all(|(param, &oid)| param.oid() == Some(oid))
+        && metadata.columns.len() == fields.len()
+        && metadata.columns.iter().zip(fields).all(|(column, field)| {
+            *column.name == *field.name
+                && column.type_info.oid() == Some(field.data_type_id)
+                && column.relation_id == field.relation_id
+                && column.relation_attribute_no == field.relation_attribute_no
+        })
+}
@@ -1,20 +1,27 @@
 use std::collections::BTreeMap;
+use std::io;
 use std::ops::{ControlFlow, Deref, DerefMut};
 use std::str::FromStr;
 
 use futures_channel::mpsc::UnboundedSender;
 use futures_util::SinkExt;
 use log::Level;
-use sqlx_core::bytes::Buf;
+use sqlx_core::bytes::{Buf, BytesMut};
+use sqlx_core::pool::is_connection_fatal;
 
 use crate::connection::tls::MaybeUpgradeTls;
 use crate::error::Error;
+#[cfg(feature = "debug-protocol")]
+use crate::inspect;
 use crate::message::{
     BackendMessage, BackendMessageFormat, EncodeMessage, FrontendMessage, Notice, Notification,
     ParameterStatus, ReceivedMessage,
 };
 use crate::net::{self, BufferedSocket, Socket};
-use crate::{PgConnectOptions, PgDatabaseError, PgSeverity};
+#[cfg(feature = "debug-protocol")]
+use crate::net::{Direction, PacketInspector};
+use crate::notice::NoticeCallback;
+use crate::{PgConnectOptions, PgDatabaseError, PgNotice, PgSeverity};
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
 // [PgConnection] is a **valid** connection to postgres
@@ -38,13 +45,29 @@ pub struct PgStream {
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
     pub(crate) server_version_num: Option<u32>,
+
+    // invoked for every `NoticeResponse`, if set by the user
+    notice_callback: Option<NoticeCallback>,
+
+    // the last error meaning the server has likely gone away, for the pool to see on release
+    pub(crate) fatal_error: Option<Error>,
+
+    // invoked for every message read or written, if set by the user
+    #[cfg(feature = "debug-protocol")]
+    packet_inspector: Option<PacketInspector>,
 }
 
 impl PgStream {
     pub(super) async fn connect(options: &PgConnectOptions) -> Result<Self, Error> {
-        let socket_result = match options.fetch_socket() {
-            Some(ref path) => net::connect_uds(path, MaybeUpgradeTls(options)).await?,
-            None => net::connect_tcp(&options.host, options.port, MaybeUpgradeTls(options)).await?,
+        let socket_result = if let Some(connector) = &options.socket_connector {
+            net::connect_with(&**connector, MaybeUpgradeTls(options)).await?
+        } else {
+            match options.fetch_socket() {
+                Some(ref path) => net::connect_uds(path, MaybeUpgradeTls(options)).await?,
+                None => {
+                    net::connect_tcp(&options.host, options.port, MaybeUpgradeTls(options)).await?
+                }
+            }
         };
 
         let socket = socket_result?;
@@ -54,6 +77,10 @@ impl PgStream {
             notifications: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
+            notice_callback: options.notice_callback.clone(),
+            fatal_error: None,
+            #[cfg(feature = "debug-protocol")]
+            packet_inspector: options.packet_inspector.clone(),
         })
     }
 
@@ -71,6 +98,21 @@ impl PgStream {
         Ok(())
     }
 
+    pub(crate) async fn flush(&mut self) -> io::Result<()> {
+        #[cfg(feature = "debug-protocol")]
+        if let Some(inspector) = &self.packet_inspector {
+            inspect::inspect_sent(inspector, self.inner.write_buffer().get());
+        }
+
+        let res = self.inner.flush().await;
+
+        if let Err(e) = &res {
+            self.record_if_fatal(&Error::Io(io::Error::new(e.kind(), e.to_string())));
+        }
+
+        res
+    }
+
     // Expect a specific type and format
     pub(crate) async fn recv_expect<B: BackendMessage>(&mut self) -> Result<B, Error> {
         self.recv().await?.decode()
@@ -79,43 +121,39 @@ impl PgStream {
     pub(crate) async fn recv_unchecked(&mut self) -> Result<ReceivedMessage, Error> {
         // NOTE: to not break everything, this should be cancel-safe;
         // DO NOT modify `buf` unless a full message has been read
-        self.inner
-            .try_read(|buf| {
-                // all packets in postgres start with a 5-byte header
-                // this header contains the message type and the total length of the message
-                let Some(mut header) = buf.get(..5) else {
-                    return Ok(ControlFlow::Continue(5));
-                };
-
-                let format = BackendMessageFormat::try_from_u8(header.get_u8())?;
-
-                let message_len = header.get_u32() as usize;
-
-                let expected_len = message_len
-                    .checked_add(1)
-                    // this shouldn't really happen but is mostly a sanity check
-                    .ok_or_else(|| {
-                        err_protocol!("message_len + 1 overflows usize: {message_len}")
-                    })?;
-
-                if buf.len() < expected_len {
-                    return Ok(ControlFlow::Continue(expected_len));
-                }
+        #[cfg(feature = "debug-protocol")]
+        if let Some(inspector) = &self.packet_inspector {
+            let res = self
+                .inner
+                .try_read(|buf| decode_inspected_message(buf, inspector))
+                .await;
+
+            if let Err(e) = &res {
+                self.record_if_fatal(e);
+            }
+
+            return res;
+        }
 
-                // `buf` SHOULD NOT be modified ABOVE this line
+        let res = self.inner.try_read(decode_message).await;
 
-                // pop off the format code since it's not counted in `message_len`
-                buf.advance(1);
+        if let Err(e) = &res {
+            self.record_if_fatal(e);
+        }
 
-                // consume the message, including the length prefix
-                let mut contents = buf.split_to(message_len).freeze();
+        res
+    }
 
-                // cut off the length prefix
-                contents.advance(4);
+    // Get the next message that was already read from the server, without waiting
+    pub(crate) fn recv_buffered(&mut self) -> Result<Option<ReceivedMessage>, Error> {
+        #[cfg(feature = "debug-protocol")]
+        if let Some(inspector) = &self.packet_inspector {
+            return self
+                .inner
+                .try_read_buffered(|buf| decode_inspected_message(buf, inspector));
+        }
 
-                Ok(ControlFlow::Break(ReceivedMessage { format, contents }))
-            })
-            .await
+        self.inner.try_read_buffered(decode_message)
     }
 
     // Get the next message from the server
@@ -127,7 +165,9 @@ impl PgStream {
             match message.format {
                 BackendMessageFormat::ErrorResponse => {
                     // An error returned from the database server.
-                    return Err(message.decode::<PgDatabaseError>()?.into());
+                    let error: Error = message.decode::<PgDatabaseError>()?.into();
+                    self.record_if_fatal(&error);
+                    return Err(error);
                 }
 
                 BackendMessageFormat::NotificationResponse => {
@@ -146,22 +186,16 @@ impl PgStream {
                     let ParameterStatus { name, value } = message.decode()?;
                     // TODO: handle `client_encoding`, `DateStyle` change
 
-                    match name.as_str() {
-                        "server_version" => {
-                            self.server_version_num = parse_server_version(&value);
-                        }
-                        _ => {
-                            self.parameter_statuses.insert(name, value);
-                        }
+                    if name == "server_version" {
+                        self.server_version_num = parse_server_version(&value);
                     }
 
+                    self.parameter_statuses.insert(name, value);
+
                     continue;
                 }
 
                 BackendMessageFormat::NoticeResponse => {
-                    // do we need this to be more configurable?
-                    // if you are reading this comment and think so, open an issue
-
                     let notice: Notice = message.decode()?;
 
                     let (log_level, tracing_level) = match notice.severity() {
@@ -189,6 +223,10 @@ impl PgStream {
                         );
                     }
 
+                    if let Some(callback) = &self.notice_callback {
+                        callback.call(PgNotice(notice));
+                    }
+
                     continue;
                 }
 
@@ -198,6 +236,22 @@ impl PgStream {
             return Ok(message);
         }
     }
+
+    // Keep a copy of `error` if it means the server has likely gone away
+    fn record_if_fatal(&mut self, error: &Error) {
+        if !is_connection_fatal(error) {
+            return;
+        }
+
+        self.fatal_error = match error {
+            Error::Io(e) => Some(Error::Io(io::Error::new(e.kind(), e.to_string()))),
+            Error::Database(e) => e
+                .try_downcast_ref::<PgDatabaseError>()
+                .cloned()
+                .map(Into::into),
+            _ => return,
+        };
+    }
 }
 
 impl Deref for PgStream {
@@ -216,6 +270,54 @@ impl DerefMut for PgStream {
     }
 }
 
+// Remove the next message from `buf` if it was read completely
+fn decode_message(buf: &mut BytesMut) -> Result<ControlFlow<ReceivedMessage, usize>, Error> {
+    // all packets in postgres start with a 5-byte header
+    // this header contains the message type and the total length of the message
+    let Some(mut header) = buf.get(..5) else {
+        return Ok(ControlFlow::Continue(5));
+    };
+
+    let format = BackendMessageFormat::try_from_u8(header.get_u8())?;
+
+    let message_len = header.get_u32() as usize;
+
+    let expected_len = message_len
+        .checked_add(1)
+        // this shouldn't really happen but is mostly a sanity check
+        .ok_or_else(|| err_protocol!("message_len + 1 overflows usize: {message_len}"))?;
+
+    if buf.len() < expected_len {
+        return Ok(ControlFlow::Continue(expected_len));
+    }
+
+    // `buf` SHOULD NOT be modified ABOVE this line
+
+    // pop off the format code since it's not counted in `message_len`
+    buf.advance(1);
+
+    // consume the message, including the length prefix
+    let mut contents = buf.split_to(message_len).freeze();
+
+    // cut off the length prefix
+    contents.advance(4);
+
+    Ok(ControlFlow::Break(ReceivedMessage { format, contents }))
+}
+
+// Like `decode_message()`, but passes the message to `inspector` once it was read completely
+#[cfg(feature = "debug-protocol")]
+fn decode_inspected_message(
+    buf: &mut BytesMut,
+    inspector: &PacketInspector,
+) -> Result<ControlFlow<ReceivedMessage, usize>, Error> {
+    if let Some(frame) = inspect::frame_len(buf).and_then(|len| buf.get(..len)) {
+        inspector.call(Direction::Received, frame);
+    }
+
+    decode_message(buf)
+}
+
 // reference:
 // https://github.com/postgres/postgres/blob/6feebcb6b44631c3dc435e971bd80c2dd218a5ab/src/interfaces/libpq/fe-exec.c#L1030-L1065
 fn parse_server_version(s: &str) -> Option<u32> {
@@ -276,6 +378,14 @@ mod tests {
         // new style without minor version, e.g.  */
         assert_eq!(parse_server_version("10devel"), Some(100000));
         assert_eq!(parse_server_version("13devel87"), Some(130000));
+        // pre-releases
+        assert_eq!(parse_server_version("15beta1"), Some(150000));
+        assert_eq!(parse_server_version("16rc1"), Some(160000));
+        // with a distribution suffix
+        assert_eq!(
+            parse_server_version("14.2 (Debian 14.2-1.pgdg110+1)"),
+            Some(140002)
+        );
         // unknown
         assert_eq!(parse_server_version("unknown"), None);
     }
@@ -3,11 +3,15 @@ use std::ops::{Deref, DerefMut};
 
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
 
-use sqlx_core::bytes::{BufMut, Bytes};
+use sqlx_core::bytes::{Buf, BufMut, Bytes, BytesMut};
 
+use crate::arguments::{Arguments, PgArguments};
 use crate::connection::PgConnection;
-use crate::error::{Error, Result};
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::error::{BoxDynError, Error, Result};
 use crate::ext::async_stream::TryAsyncStream;
 use crate::io::AsyncRead;
 use crate::message::{
@@ -15,7 +19,8 @@ use crate::message::{
     CopyOutResponse, CopyResponseData, Query, ReadyForQuery,
 };
 use crate::pool::{Pool, PoolConnection};
-use crate::Postgres;
+use crate::types::Type;
+use crate::{PgValueFormat, PgValueRef, Postgres};
 
 impl PgConnection {
     /// Issue a `COPY FROM STDIN` statement and transition the connection to streaming data
@@ -59,7 +64,66 @@ impl PgConnection {
         &'c mut self,
         statement: &str,
     ) -> Result<BoxStream<'c, Result<Bytes>>> {
-        pg_begin_copy_out(self, statement).await
+        Ok(pg_begin_copy_out(self, statement).await?.1)
+    }
+
+    /// Issue a `COPY ... FROM STDIN WITH (FORMAT binary)` statement and transition the connection
+    /// to streaming rows to Postgres with [`PgCopyIn::send_row()`].
+    ///
+    /// Each row is a tuple with a field for every column in the `COPY` column list, which is
+    /// encoded with its [`Encode`] impl. The binary `COPY` header and trailer are written for you.
+    ///
+    /// If `statement` is not a `COPY ... FROM STDIN` command in binary format, an error is returned.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// let mut copy = conn
+    ///     .copy_in_typed("COPY users (id, name) FROM STDIN WITH (FORMAT binary)")
+    ///     .await?;
+    ///
+    /// copy.send_row((1_i64, "Alice")).await?;
+    /// copy.send_row((2_i64, None::<&str>)).await?;
+    ///
+    /// let rows_inserted = copy.finish().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ### Note
+    /// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the connection
+    /// will return an error the next time it is used.
+    pub async fn copy_in_typed(&mut self, statement: &str) -> Result<PgCopyIn<&mut Self>> {
+        PgCopyIn::begin_typed(self, statement).await
+    }
+
+    /// Issue a `COPY ... TO STDOUT WITH (FORMAT binary)` statement and decode each row it returns
+    /// as a tuple `R`.
+    ///
+    /// `COPY` does not report the types of its columns, so each field is decoded as the type
+    /// of the tuple field without checking that it's compatible with the column type.
+    /// A row with a different number of fields than `R` is an error.
+    ///
+    /// The same notes about cancellation apply as for [`copy_out_raw()`][Self::copy_out_raw].
+    ///
+    /// If `statement` is not a `COPY ... TO STDOUT` command in binary format, an error is
+    /// returned.
+    pub async fn copy_out_typed<'c, R>(
+        &'c mut self,
+        statement: &str,
+    ) -> Result<BoxStream<'c, Result<R>>>
+    where
+        R: PgCopyOutRow + Send + 'c,
+    {
+        let strict_timestamps = self.inner.strict_timestamps;
+        let (response, raw) = pg_begin_copy_out(self, statement).await?;
+
+        if response.format == 0 {
+            return Err(Error::InvalidArgument(
+                "copy_out_typed() requires `COPY ... TO STDOUT WITH (FORMAT binary)`".into(),
+            ));
+        }
+
+        Ok(Box::pin(decode_binary_copy_out(raw, strict_timestamps)))
     }
 }
 
@@ -125,13 +189,16 @@ impl PgPoolCopyExt for Pool<Postgres> {
         &'a self,
         statement: &'a str,
     ) -> BoxFuture<'a, Result<BoxStream<'static, Result<Bytes>>>> {
-        Box::pin(async { pg_begin_copy_out(self.acquire().await?, statement).await })
+        Box::pin(async { Ok(pg_begin_copy_out(self.acquire().await?, statement).await?.1) })
     }
 }
 
 // (1 GiB - 1) - 1 - length prefix (4 bytes)
 pub const PG_COPY_MAX_DATA_LEN: usize = 0x3fffffff - 1 - 4;
 
+// https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9.4.5
+const PG_COPY_BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
 /// A connection in streaming `COPY FROM STDIN` mode.
 ///
 /// Created by [PgConnection::copy_in_raw] or [Pool::copy_out_raw].
@@ -143,6 +210,8 @@ pub const PG_COPY_MAX_DATA_LEN: usize = 0x3fffffff - 1 - 4;
 pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
     conn: Option<C>,
     response: CopyResponseData,
+    // created by `copy_in_typed()`; rows are sent with `send_row()`
+    typed: bool,
 }
 
 impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
@@ -161,9 +230,32 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         Ok(PgCopyIn {
             conn: Some(conn),
             response,
+            typed: false,
         })
     }
 
+    async fn begin_typed(conn: C, statement: &str) -> Result<Self> {
+        let mut copy = Self::begin(conn, statement).await?;
+
+        if copy.is_textual() {
+            copy.abort("copy_in_typed() requires binary format").await?;
+
+            return Err(Error::InvalidArgument(
+                "copy_in_typed() requires `COPY ... FROM STDIN WITH (FORMAT binary)`".into(),
+            ));
+        }
+
+        // signature, flags field, header extension length
+        let mut header = PG_COPY_BINARY_SIGNATURE.to_vec();
+        header.extend_from_slice(&0_i32.to_be_bytes());
+        header.extend_from_slice(&0_i32.to_be_bytes());
+
+        copy.send(header).await?;
+        copy.typed = true;
+
+        Ok(copy)
+    }
+
     /// Returns `true` if Postgres is expecting data in text or CSV format.
     pub fn is_textual(&self) -> bool {
         self.response.format == 0
@@ -207,6 +299,53 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         Ok(self)
     }
 
+    /// Encode and send a row of a `COPY` started with
+    /// [`copy_in_typed()`][PgConnection::copy_in_typed].
+    ///
+    /// If the number of fields in `row` doesn't match the number of columns being copied,
+    /// an error is returned and nothing is sent.
+    ///
+    /// Types whose OID has to be looked up by name, such as arrays of custom types,
+    /// are not supported because the connection can't run queries during a `COPY`.
+    pub async fn send_row<'q, R>(&mut self, row: R) -> Result<&mut Self>
+    where
+        R: PgCopyInRow<'q>,
+    {
+        if !self.typed {
+            return Err(Error::InvalidArgument(
+                "send_row() requires a `PgCopyIn` created by `copy_in_typed()`".into(),
+            ));
+        }
+
+        let mut args = PgArguments::default();
+        row.encode_fields(&mut args).map_err(Error::Encode)?;
+
+        let num_fields = args.len();
+
+        if num_fields != self.num_columns() {
+            return Err(Error::InvalidArgument(format!(
+                "row has {num_fields} fields but the COPY expects {} columns",
+                self.num_columns()
+            )));
+        }
+
+        if args.buffer.has_type_holes() {
+            return Err(Error::InvalidArgument(
+                "send_row() does not support types whose OID must be looked up by name".into(),
+            ));
+        }
+
+        let num_fields = i16::try_from(num_fields)
+            .map_err(|_| err_protocol!("too many fields in COPY row: {num_fields}"))?;
+
+        // the fields are encoded exactly like bind parameters: a length prefix, or -1 for NULL
+        let mut data = Vec::with_capacity(2 + args.buffer.len());
+        data.extend_from_slice(&num_fields.to_be_bytes());
+        data.extend_from_slice(&args.buffer);
+
+        self.send(data).await
+    }
+
     /// Copy data directly from `source` to the database without requiring an intermediate buffer.
     ///
     /// `source` will be read to the end.
@@ -290,6 +429,14 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
             .take()
             .expect("CopyWriter::finish: conn taken illegally");
 
+        if self.typed {
+            // the trailer is a field count of -1
+            conn.inner
+                .stream
+                .send(CopyData(&(-1_i16).to_be_bytes()[..]))
+                .await?;
+        }
+
         conn.inner.stream.send(CopyDone).await?;
         let cc: CommandComplete = match conn.inner.stream.recv_expect().await {
             Ok(cc) => cc,
@@ -321,17 +468,21 @@ impl<C: DerefMut<Target = PgConnection>> Drop for PgCopyIn<C> {
 async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
     mut conn: C,
     statement: &str,
-) -> Result<BoxStream<'c, Result<Bytes>>> {
+) -> Result<(CopyResponseData, BoxStream<'c, Result<Bytes>>)> {
     conn.wait_until_ready().await?;
     conn.inner.stream.send(Query(statement)).await?;
 
-    let _: CopyOutResponse = conn.inner.stream.recv_expect().await?;
+    // if the stream is dropped before it's read to completion, or we return an error,
+    // the rest of the output is discarded the next time the connection is used
+    conn.inner.pending_ready_for_query_count += 1;
+
+    let CopyOutResponse(response) = conn.inner.stream.recv_expect().await?;
 
     let stream: TryAsyncStream<'c, Bytes> = try_stream! {
         loop {
             match conn.inner.stream.recv().await {
                 Err(e) => {
-                    conn.inner.stream.recv_expect::<ReadyForQuery>().await?;
+                    conn.recv_ready_for_query().await?;
                     return Err(e);
                 },
                 Ok(msg) => match msg.format {
@@ -339,7 +490,7 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
                     BackendMessageFormat::CopyDone => {
                         let _ = msg.decode::<CopyDone>()?;
                         conn.inner.stream.recv_expect::<CommandComplete>().await?;
-                        conn.inner.stream.recv_expect::<ReadyForQuery>().await?;
+                        conn.recv_ready_for_query().await?;
                         return Ok(())
                     },
                     _ => return Err(err_protocol!("unexpected message format during copy out: {:?}", msg.format))
@@ -348,5 +499,466 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
         }
     };
 
-    Ok(Box::pin(stream))
+    Ok((response, Box::pin(stream)))
+}
+
+fn decode_binary_copy_out<'c, R>(
+    mut raw: BoxStream<'c, Result<Bytes>>,
+    strict_timestamps: bool,
+) -> TryAsyncStream<'c, R>
+where
+    R: PgCopyOutRow + Send + 'c,
+{
+    try_stream! {
+        let mut buf = BytesMut::new();
+        let mut header_read = false;
+        let mut trailer_read = false;
+
+        while let Some(chunk) = raw.try_next().await? {
+            buf.extend_from_slice(&chunk);
+
+            if !header_read {
+                if !read_binary_header(&mut buf)? {
+                    continue;
+                }
+
+                header_read = true;
+            }
+
+            while !trailer_read {
+                match read_binary_row::<R>(&mut buf, strict_timestamps)? {
+                    Some(Some(row)) => r#yield!(row),
+                    Some(None) => trailer_read = true,
+                    None => break,
+                }
+            }
+        }
+
+        if !header_read {
+            return Err(err_protocol!("binary COPY data ended before the end of its header"));
+        }
+
+        if !buf.is_empty() {
+            return Err(err_protocol!(
+                "binary COPY data has {} unexpected trailing bytes",
+                buf.len()
+            ));
+        }
+
+        if !trailer_read {
+            return Err(err_protocol!("binary COPY data ended without a trailer"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Consumes the binary `COPY` header from `buf`, returning `false` if it's not complete yet.
+fn read_binary_header(buf: &mut BytesMut) -> Result<bool> {
+    let fixed_len = PG_COPY_BINARY_SIGNATURE.len() + 8;
+
+    if buf.len() < fixed_len {
+        return Ok(false);
+    }
+
+    if !buf.starts_with(PG_COPY_BINARY_SIGNATURE) {
+        return Err(err_protocol!("binary COPY data has an invalid signature"));
+    }
+
+    let ext_len = (&buf[fixed_len - 4..]).get_i32();
+    let ext_len = usize::try_from(ext_len)
+        .map_err(|_| err_protocol!("invalid binary COPY header extension length: {ext_len}"))?;
+
+    if buf.len() < fixed_len + ext_len {
+        return Ok(false);
+    }
+
+    buf.advance(fixed_len + ext_len);
+
+    Ok(true)
+}
+
+/// Decodes the next row in `buf`, if it's complete.
+///
+/// Returns `Some(None)` for the trailer.
+fn read_binary_row<R: PgCopyOutRow>(
+    buf: &mut BytesMut,
+    strict_timestamps: bool,
+) -> Result<Option<Option<R>>> {
+    let mut data = &buf[..];
+
+    if data.len() < 2 {
+        return Ok(None);
+    }
+
+    let num_fields = data.get_i16();
+
+    if num_fields == -1 {
+        buf.advance(2);
+        return Ok(Some(None));
+    }
+
+    let num_fields = usize::try_from(num_fields)
+        .map_err(|_| err_protocol!("invalid binary COPY field count: {num_fields}"))?;
+
+    let mut fields = Vec::with_capacity(num_fields);
+
+    for _ in 0..num_fields {
+        if data.len() < 4 {
+            return Ok(None);
+        }
+
+        let len = data.get_i32();
+
+        if len == -1 {
+            fields.push(None);
+            continue;
+        }
+
+        let len = usize::try_from(len)
+            .map_err(|_| err_protocol!("invalid binary COPY field length: {len}"))?;
+
+        if data.len() < len {
+            return Ok(None);
+        }
+
+        fields.push(Some(&data[..len]));
+        data.advance(len);
+    }
+
+    let row = R::decode_fields(&fields, strict_timestamps).map_err(Error::Decode)?;
+    let consumed = buf.len() - data.len();
+
+    buf.advance(consumed);
+
+    Ok(Some(Some(row)))
+}
+
+/// A tuple which can be sent as a row with [`PgCopyIn::send_row()`].
+///
+/// Implemented for tuples of up to 16 fields which implement [`Encode`] and [`Type`].
+pub trait PgCopyInRow<'q> {
+    /// Encode every field of this row into `args`, in column order.
+    fn encode_fields(self, args: &mut PgArguments) -> Result<(), BoxDynError>;
+}
+
+/// A tuple which can be decoded from a row by [`PgConnection::copy_out_typed()`].
+///
+/// Implemented for tuples of up to 16 fields which implement [`Decode`] and [`Type`].
+pub trait PgCopyOutRow: Sized {
+    /// Decode a row from the binary value of each field, or `None` for `NULL`.
+    ///
+    /// `strict_timestamps` is the [`PgConnectOptions::strict_timestamps()`] setting of the
+    /// connection the row was read from.
+    ///
+    /// [`PgConnectOptions::strict_timestamps()`]: crate::PgConnectOptions::strict_timestamps
+    fn decode_fields(
+        fields: &[Option<&[u8]>],
+        strict_timestamps: bool,
+    ) -> Result<Self, BoxDynError>;
+}
+
+fn decode_copy_field<'r, T>(
+    fields: &[Option<&'r [u8]>],
+    index: usize,
+    strict_timestamps: bool,
+) -> Result<T, BoxDynError>
+where
+    T: Decode<'r, Postgres> + Type<Postgres>,
+{
+    let value = PgValueRef {
+        value: fields[index],
+        row: None,
+        // `COPY` doesn't tell us the column types
+        type_info: T::type_info(),
+        format: PgValueFormat::Binary,
+        strict_timestamps,
+    };
+
+    T::decode(value).map_err(|e| format!("error decoding field {index}: {e}").into())
+}
+
+macro_rules! impl_copy_row_for_tuple {
+    ($( ($idx:tt) -> $T:ident );+;) => {
+        impl<'q, $($T,)+> PgCopyInRow<'q> for ($($T,)+)
+        where
+            $($T: 'q + Encode<'q, Postgres> + Type<Postgres>,)+
+        {
+            fn encode_fields(self, args: &mut PgArguments) -> Result<(), BoxDynError> {
+                $(args.add(self.$idx)?;)+
+                Ok(())
+            }
+        }
+
+        impl<$($T,)+> PgCopyOutRow for ($($T,)+)
+        where
+            $($T: for<'r> Decode<'r, Postgres> + Type<Postgres>,)+
+        {
+            fn decode_fields(
+                fields: &[Option<&[u8]>],
+                strict_timestamps: bool,
+            ) -> Result<Self, BoxDynError> {
+                const NUM_FIELDS: usize = [$($idx),+].len();
+
+                if fields.len() != NUM_FIELDS {
+                    return Err(format!(
+                        "COPY row has {} fields, expected {NUM_FIELDS}",
+                        fields.len()
+                    )
+                    .into());
+                }
+
+                Ok(($(decode_copy_field::<$T>(fields, $idx, strict_timestamps)?,)+))
+            }
+        }
+    };
+}
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+    (15) -> T16;
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{stream, FutureExt};
+
+    fn header() -> Vec<u8> {
+        let mut buf = PG_COPY_BINARY_SIGNATURE.to_vec();
+        // flags and header extension length
+        buf.extend_from_slice(&[0; 8]);
+        buf
+    }
+
+    fn row(value: i32) -> Vec<u8> {
+        let mut buf = 1_i16.to_be_bytes().to_vec();
+        buf.extend_from_slice(&4_i32.to_be_bytes());
+        buf.extend_from_slice(&value.to_be_bytes());
+        buf
+    }
+
+    const TRAILER: [u8; 2] = (-1_i16).to_be_bytes();
+
+    fn decode(chunks: Vec<Vec<u8>>) -> Result<Vec<(i32,)>> {
+        let raw = stream::iter(chunks.into_iter().map(|chunk| Ok(Bytes::from(chunk))));
+
+        decode_binary_copy_out::<(i32,)>(Box::pin(raw), false)
+            .try_collect()
+            .now_or_never()
+            .expect("decoding should not wait")
+    }
+
+    #[test]
+    fn it_decodes_rows_split_across_chunks() {
+        let data = [header(), row(1), row(2), TRAILER.to_vec()].concat();
+        let (first, second) = data.split_at(PG_COPY_BINARY_SIGNATURE.len() + 13);
+
+        let rows = decode(vec![first.to_vec(), second.to_vec()]).unwrap();
+
+        assert_eq!(rows, [(1,), (2,)]);
+    }
+
+    #[test]
+    fn it_rejects_missing_header() {
+        assert!(matches!(decode(vec![]), Err(Error::Protocol(_))));
+        assert!(matches!(
+            decode(vec![header()[..5].to_vec()]),
+            Err(Error::Protocol(_))
+        ));
+    }
+
+    #[test]
+    fn it_rejects_trailing_bytes() {
+        let truncated = [header(), row(1), row(2)[..5].to_vec()].concat();
+        assert!(matches!(decode(vec![truncated]), Err(Error::Protocol(_))));
+
+        let after_trailer = [header(), row(1), TRAILER.to_vec(), row(2)].concat();
+        assert!(matches!(
+            decode(vec![after_trailer]),
+            Err(Error::Protocol(_))
+        ));
+
+        let no_trailer = [header(), row(1)].concat();
+        assert!(matches!(decode(vec![no_trailer]), Err(Error::Protocol(_))));
+    }
 }
@@ -9,12 +9,15 @@ pub(crate) use sqlx_core::error::*;
 use crate::message::{BackendMessage, BackendMessageFormat, Notice, PgSeverity};
 
 /// An error returned from the PostgreSQL database.
+#[derive(Clone)]
 pub struct PgDatabaseError(pub(crate) Notice);
 
 // Error message fields are documented:
 // https://www.postgresql.org/docs/current/protocol-error-fields.html
 
 impl PgDatabaseError {
+    /// The severity of the error, e.g. [`PgSeverity::Error`] for an error that aborted the
+    /// current statement or [`PgSeverity::Fatal`] for one that ended the session.
     #[inline]
     pub fn severity(&self) -> PgSeverity {
         self.0.severity()
@@ -51,6 +54,10 @@ impl PgDatabaseError {
 
     /// Indicates an error cursor position as an index into the original query string; or,
     /// a position into an internally generated query.
+    ///
+    /// Positions are 1-based and counted in characters, not bytes;
+    /// use [`PgErrorPosition::byte_offset()`] to find the position in the query string.
+    /// If the server encoding is `SQL_ASCII`, every byte is counted as a character.
     #[inline]
     pub fn position(&self) -> Option<PgErrorPosition<'_>> {
         self.0
@@ -58,13 +65,26 @@ impl PgDatabaseError {
             .and_then(atoi)
             .map(PgErrorPosition::Original)
             .or_else(|| {
-                let position = self.0.get_raw(b'p').and_then(atoi)?;
-                let query = self.0.get(b'q')?;
+                let position = self.internal_position()?;
+                let query = self.internal_query()?;
 
                 Some(PgErrorPosition::Internal { position, query })
             })
     }
 
+    /// The position of the error in the internally generated query, in characters starting at 1.
+    ///
+    /// See [`internal_query()`][Self::internal_query] for the query.
+    pub fn internal_position(&self) -> Option<usize> {
+        self.0.get_raw(b'p').and_then(atoi)
+    }
+
+    /// The text of a failed internally generated command, e.g. the SQL query issued by a
+    /// PL/pgSQL function.
+    pub fn internal_query(&self) -> Option<&str> {
+        self.0.get(b'q')
+    }
+
     /// An indication of the context in which the error occurred. Presently this includes a call
     /// stack traceback of active procedural language functions and internally-generated queries.
     /// The trace is one entry per line, most recent first.
@@ -132,6 +152,26 @@ pub enum PgErrorPosition<'a> {
     },
 }
 
+impl PgErrorPosition<'_> {
+    /// The byte offset of the position in `original_query`, or in the internally generated
+    /// query if this is [`PgErrorPosition::Internal`].
+    ///
+    /// An error at the end of the input, e.g. an unterminated statement, is at the length of the
+    /// query. Returns `None` if the position is outside of the query.
+    pub fn byte_offset(&self, original_query: &str) -> Option<usize> {
+        let (position, query) = match *self {
+            PgErrorPosition::Original(position) => (position, original_query),
+            PgErrorPosition::Internal { position, query } => (position, query),
+        };
+
+        query
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(Some(query.len()))
+            .nth(position.checked_sub(1)?)
+    }
+}
+
 impl Debug for PgDatabaseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("PgDatabaseError")
@@ -240,3 +280,27 @@ pub(crate) mod error_codes {
     /// Caused when a check constraint is violated.
     pub const CHECK_VIOLATION: &str = "23514";
 }
+
+#[test]
+fn test_error_position_byte_offset() {
+    let query = "SELECT 'ünïcödé' FORM t";
+
+    // `FORM` is the 18th character, but starts at the 22nd byte
+    assert_eq!(PgErrorPosition::Original(18).byte_offset(query), Some(21));
+    assert_eq!(&query[21..], "FORM t");
+
+    assert_eq!(PgErrorPosition::Original(1).byte_offset(query), Some(0));
+    assert_eq!(
+        PgErrorPosition::Original(24).byte_offset(query),
+        Some(query.len())
+    );
+    assert_eq!(PgErrorPosition::Original(0).byte_offset(query), None);
+    assert_eq!(PgErrorPosition::Original(25).byte_offset(query), None);
+
+    let internal = PgErrorPosition::Internal {
+        position: 3,
+        query: "ä + ö",
+    };
+
+    assert_eq!(internal.byte_offset(query), Some(3));
+}
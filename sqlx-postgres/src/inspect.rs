@@ -0,0 +1,162 @@
+//! Naming and formatting the messages passed to a
+//! [packet inspector][crate::PgConnectOptions::packet_inspector].
+//!
+//! <https://www.postgresql.org/docs/current/protocol-message-formats.html>
+use crate::net::{self, PacketInspector};
+
+pub use crate::net::Direction;
+
+// the protocol versions sent in place of a message type by messages without one
+const PROTOCOL_VERSION_3: u32 = 196608;
+const CANCEL_REQUEST_CODE: u32 = 80877102;
+const SSL_REQUEST_CODE: u32 = 80877103;
+const GSSENC_REQUEST_CODE: u32 = 80877104;
+
+/// Returns the name of the message in `frame`, e.g. `"Parse"` or `"RowDescription"`,
+/// or `None` if the message type is unknown.
+pub fn frame_name(direction: Direction, frame: &[u8]) -> Option<&'static str> {
+    let format = *frame.first()?;
+
+    Some(match direction {
+        Direction::Sent => match format {
+            // the startup message and requests sent before it only start with their length,
+            // which is always less than 2^24
+            0 => match frame.get(4..8)?.try_into().map(u32::from_be_bytes).ok()? {
+                PROTOCOL_VERSION_3 => "StartupMessage",
+                CANCEL_REQUEST_CODE => "CancelRequest",
+                SSL_REQUEST_CODE => "SSLRequest",
+                GSSENC_REQUEST_CODE => "GSSENCRequest",
+                _ => return None,
+            },
+            b'B' => "Bind",
+            b'C' => "Close",
+            b'c' => "CopyDone",
+            b'd' => "CopyData",
+            b'D' => "Describe",
+            b'E' => "Execute",
+            b'f' => "CopyFail",
+            b'F' => "FunctionCall",
+            b'H' => "Flush",
+            b'p' => "PasswordMessage",
+            b'P' => "Parse",
+            b'Q' => "Query",
+            b'S' => "Sync",
+            b'X' => "Terminate",
+            _ => return None,
+        },
+
+        Direction::Received => match format {
+            b'1' => "ParseComplete",
+            b'2' => "BindComplete",
+            b'3' => "CloseComplete",
+            b'A' => "NotificationResponse",
+            b'c' => "CopyDone",
+            b'C' => "CommandComplete",
+            b'd' => "CopyData",
+            b'D' => "DataRow",
+            b'E' => "ErrorResponse",
+            b'G' => "CopyInResponse",
+            b'H' => "CopyOutResponse",
+            b'I' => "EmptyQueryResponse",
+            b'K' => "BackendKeyData",
+            b'n' => "NoData",
+            b'N' => "NoticeResponse",
+            b'R' => "Authentication",
+            b's' => "PortalSuspended",
+            b'S' => "ParameterStatus",
+            b't' => "ParameterDescription",
+            b'T' => "RowDescription",
+            b'v' => "NegotiateProtocolVersion",
+            b'V' => "FunctionCallResponse",
+            b'W' => "CopyBothResponse",
+            b'Z' => "ReadyForQuery",
+            _ => return None,
+        },
+    })
+}
+
+/// Formats the message in `frame` for printing, as its direction, name, length,
+/// and a hex dump of its first bytes.
+///
+/// ```text
+/// -> Sync (5 bytes): 53 00 00 00 04
+/// ```
+pub fn format_frame(direction: Direction, frame: &[u8]) -> String {
+    net::format_frame(
+        direction,
+        frame_name(direction, frame).unwrap_or("Unknown"),
+        frame,
+    )
+}
+
+/// Returns the length of the first message in `buf`, including its type and length,
+/// or `None` if `buf` does not contain the whole header.
+pub(crate) fn frame_len(buf: &[u8]) -> Option<usize> {
+    match buf.first()? {
+        // a message without a type, see `frame_name()`
+        0 => buf
+            .get(..4)
+            .map(|len| u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize),
+        _ => buf
+            .get(1..5)
+            .map(|len| u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize + 1),
+    }
+}
+
+/// Passes every message in `buf`, which holds messages that are about to be written,
+/// to `inspector`.
+pub(crate) fn inspect_sent(inspector: &PacketInspector, mut buf: &[u8]) {
+    while !buf.is_empty() {
+        // a message that is cut off is passed as it is, though that never happens
+        // as messages are only written whole
+        let len = frame_len(buf).map_or(buf.len(), |len| len.clamp(1, buf.len()));
+        let (frame, rest) = buf.split_at(len);
+
+        inspector.call(Direction::Sent, frame);
+        buf = rest;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn it_names_and_splits_sent_messages() {
+        let names = Arc::new(Mutex::new(Vec::new()));
+
+        let inspector = PacketInspector::new({
+            let names = names.clone();
+            move |direction, frame| names.lock().unwrap().push(frame_name(direction, frame))
+        });
+
+        let mut buf = Vec::new();
+        // SSLRequest
+        buf.extend_from_slice(&[0, 0, 0, 8, 0x04, 0xd2, 0x16, 0x2f]);
+        // Sync
+        buf.extend_from_slice(&[b'S', 0, 0, 0, 4]);
+        // Execute of the unnamed portal with no row limit
+        buf.extend_from_slice(&[b'E', 0, 0, 0, 9, 0, 0, 0, 0, 0]);
+
+        inspect_sent(&inspector, &buf);
+
+        assert_eq!(
+            *names.lock().unwrap(),
+            [Some("SSLRequest"), Some("Sync"), Some("Execute")]
+        );
+    }
+
+    #[test]
+    fn it_formats_messages() {
+        assert_eq!(
+            format_frame(Direction::Received, &[b'Z', 0, 0, 0, 5, b'I']),
+            "<- ReadyForQuery (6 bytes): 5a 00 00 00 05 49"
+        );
+
+        assert_eq!(
+            format_frame(Direction::Sent, &[b'?', 0, 0, 0, 4]),
+            "-> Unknown (5 bytes): 3f 00 00 00 04"
+        );
+    }
+}
@@ -12,9 +12,12 @@ mod connection;
 mod copy;
 mod database;
 mod error;
+#[cfg(feature = "debug-protocol")]
+pub mod inspect;
 mod io;
 mod listener;
 mod message;
+mod notice;
 mod options;
 mod query_result;
 mod row;
@@ -49,16 +52,17 @@ pub use advisory_lock::{PgAdvisoryLock, PgAdvisoryLockGuard, PgAdvisoryLockKey};
 pub use arguments::{PgArgumentBuffer, PgArguments};
 pub use column::PgColumn;
 pub use connection::PgConnection;
-pub use copy::{PgCopyIn, PgPoolCopyExt};
+pub use copy::{PgCopyIn, PgCopyInRow, PgCopyOutRow, PgPoolCopyExt};
 pub use database::Postgres;
 pub use error::{PgDatabaseError, PgErrorPosition};
 pub use listener::{PgListener, PgNotification};
 pub use message::PgSeverity;
+pub use notice::PgNotice;
 pub use options::{PgConnectOptions, PgSslMode};
 pub use query_result::PgQueryResult;
 pub use row::PgRow;
 pub use statement::PgStatement;
-pub use transaction::PgTransactionManager;
+pub use transaction::{PgTransactionManager, PgTransactionOptions};
 pub use type_info::{PgTypeInfo, PgTypeKind};
 pub use types::PgHasArrayType;
 pub use value::{PgValue, PgValueFormat, PgValueRef};
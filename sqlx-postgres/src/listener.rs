@@ -1,6 +1,7 @@
 use std::fmt::{self, Debug};
 use std::io;
 use std::str::from_utf8;
+use std::time::Duration;
 
 use futures_channel::mpsc;
 use futures_core::future::BoxFuture;
@@ -173,7 +174,8 @@ impl PgListener {
     async fn connect_if_needed(&mut self) -> Result<(), Error> {
         if self.connection.is_none() {
             let mut connection = self.pool.acquire().await?;
-            connection.inner.stream.notifications = self.buffer_tx.take();
+            // cloned so it isn't lost if this is cancelled, e.g. by `recv_timeout()`
+            connection.inner.stream.notifications = self.buffer_tx.clone();
 
             connection
                 .execute(&*build_listen_all_query(&self.channels))
@@ -326,6 +328,87 @@ impl PgListener {
         }
     }
 
+    /// Receives the next notification available from any of the subscribed channels,
+    /// waiting at most `timeout` for one to arrive.
+    ///
+    /// Returns `Ok(None)` if no notification was received before the deadline. Waiting is
+    /// cancel-safe: a notification that arrives just as the deadline passes is kept in the
+    /// buffer, and returned by the next call to this, [`recv()`](Self::recv) or
+    /// [`drain()`](Self::drain).
+    ///
+    /// Like [`recv()`](Self::recv), the connection is transparently re-established if it's lost.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use sqlx::postgres::PgListener;
+    /// #
+    /// # sqlx::__rt::test_block_on(async move {
+    /// # let mut listener = PgListener::connect("postgres:// ...").await?;
+    /// loop {
+    ///     if let Some(notification) = listener.recv_timeout(Duration::from_secs(1)).await? {
+    ///         // handle the notification, and any others that arrived along with it
+    ///         for notification in listener.drain()? {
+    ///             // ...
+    ///         }
+    ///     }
+    ///
+    ///     // do periodic work
+    /// }
+    /// # Result::<(), sqlx::Error>::Ok(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn recv_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<PgNotification>, Error> {
+        match crate::rt::timeout(timeout, self.recv()).await {
+            Ok(res) => res.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Returns all notifications that were already received, without waiting.
+    ///
+    /// This includes notifications buffered while the listener was used to execute queries
+    /// (see [`next_buffered()`](Self::next_buffered)), and those that were read from the
+    /// connection but not returned yet, e.g. because they arrived in the same packet as
+    /// the last notification returned by [`recv()`](Self::recv).
+    /// The connection is never read from, so this does not block.
+    ///
+    /// # Errors
+    /// If a message read from the connection is invalid.
+    pub fn drain(&mut self) -> Result<Vec<PgNotification>, Error> {
+        let mut notifications = Vec::new();
+
+        // these were received before any messages still in the connection's buffer
+        while let Some(notification) = self.next_buffered() {
+            notifications.push(notification);
+        }
+
+        let Some(connection) = &mut self.connection else {
+            return Ok(notifications);
+        };
+
+        while let Some(message) = connection.inner.stream.recv_buffered()? {
+            match message.format {
+                BackendMessageFormat::NotificationResponse => {
+                    notifications.push(PgNotification(message.decode()?));
+                }
+
+                BackendMessageFormat::ReadyForQuery => {
+                    connection.inner.pending_ready_for_query_count -= 1;
+                }
+
+                // ignored, as by `try_recv()`
+                _ => {}
+            }
+        }
+
+        Ok(notifications)
+    }
+
     /// Receives the next notification that already exists in the connection buffer, if any.
     ///
     /// This is similar to `try_recv`, except it will not wait if the connection has not yet received a notification.
@@ -36,6 +36,16 @@ pub enum Authentication {
     /// again using the 4-byte random salt.
     Md5Password(AuthenticationMd5Password),
 
+    /// The frontend must now initiate a GSSAPI negotiation.
+    ///
+    /// This is not supported.
+    Gss,
+
+    /// The frontend must now initiate a SSPI negotiation.
+    ///
+    /// This is not supported.
+    Sspi,
+
     /// The frontend must now initiate a SASL negotiation,
     /// using one of the SASL mechanisms listed in the message.
     ///
@@ -76,6 +86,9 @@ impl BackendMessage for Authentication {
                 Authentication::Md5Password(AuthenticationMd5Password { salt })
             }
 
+            7 => Authentication::Gss,
+            9 => Authentication::Sspi,
+
             10 => Authentication::Sasl(AuthenticationSasl(buf)),
             11 => Authentication::SaslContinue(AuthenticationSaslContinue::decode(buf)?),
             12 => Authentication::SaslFinal(AuthenticationSaslFinal::decode(buf)?),
@@ -191,3 +204,14 @@ impl ProtocolDecode<'_> for AuthenticationSaslFinal {
         Ok(Self { verifier })
     }
 }
+
+#[test]
+fn test_decode_authentication_gss() -> Result<(), Error> {
+    let m = Authentication::decode_body(Bytes::from_static(b"\0\0\0\x07"))?;
+    assert!(matches!(m, Authentication::Gss));
+
+    let m = Authentication::decode_body(Bytes::from_static(b"\0\0\0\x09"))?;
+    assert!(matches!(m, Authentication::Sspi));
+
+    Ok(())
+}
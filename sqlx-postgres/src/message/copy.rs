@@ -17,7 +17,6 @@ pub struct CopyResponseData {
 
 pub struct CopyInResponse(pub CopyResponseData);
 
-#[allow(dead_code)]
 pub struct CopyOutResponse(pub CopyResponseData);
 
 pub struct CopyData<B>(pub B);
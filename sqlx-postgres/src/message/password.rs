@@ -118,6 +118,24 @@ mod tests {
         assert_eq!(buf, EXPECTED);
     }
 
+    #[test]
+    fn test_encode_md5_password_non_ascii() {
+        // Like libpq, the password is hashed as UTF-8 without any normalization.
+        // Computed with `'md5' || md5(md5(password || username) || salt)` in Postgres.
+        const EXPECTED: &[u8] = b"p\0\0\0(md5895a0b62d8292207abdeb9247819a4c8\0";
+
+        let mut buf = Vec::new();
+        let m = Password::Md5 {
+            password: "pass\u{A0}word\u{1F980}",
+            username: "root",
+            salt: [147, 24, 57, 152],
+        };
+
+        m.encode_msg(&mut buf).unwrap();
+
+        assert_eq!(buf, EXPECTED);
+    }
+
     #[cfg(all(test, not(debug_assertions)))]
     #[bench]
     fn bench_encode_clear_password(b: &mut test::Bencher) {
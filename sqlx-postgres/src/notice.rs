@@ -0,0 +1,78 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::message::{Notice, PgSeverity};
+
+/// A notice or warning sent by the server, e.g. from `RAISE NOTICE` in PL/pgSQL.
+///
+/// See [`PgConnectOptions::notice_callback`][crate::PgConnectOptions::notice_callback].
+pub struct PgNotice(pub(crate) Notice);
+
+// Notice message fields are documented:
+// https://www.postgresql.org/docs/current/protocol-error-fields.html
+
+impl PgNotice {
+    #[inline]
+    pub fn severity(&self) -> PgSeverity {
+        self.0.severity()
+    }
+
+    /// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) code for
+    /// this notice.
+    #[inline]
+    pub fn code(&self) -> &str {
+        self.0.code()
+    }
+
+    /// The primary human-readable message. This should be accurate but terse
+    /// (typically one line).
+    #[inline]
+    pub fn message(&self) -> &str {
+        self.0.message()
+    }
+
+    /// An optional secondary message carrying more detail. Might run to multiple lines.
+    #[inline]
+    pub fn detail(&self) -> Option<&str> {
+        self.0.get(b'D')
+    }
+
+    /// An optional suggestion what to do about the reported condition.
+    /// Might run to multiple lines.
+    #[inline]
+    pub fn hint(&self) -> Option<&str> {
+        self.0.get(b'H')
+    }
+}
+
+impl Debug for PgNotice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgNotice")
+            .field("severity", &self.severity())
+            .field("code", &self.code())
+            .field("message", &self.message())
+            .field("detail", &self.detail())
+            .field("hint", &self.hint())
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct NoticeCallback(Arc<dyn Fn(PgNotice) + Send + Sync + 'static>);
+
+impl NoticeCallback {
+    pub(crate) fn new(f: impl Fn(PgNotice) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    #[inline]
+    pub(crate) fn call(&self, notice: PgNotice) {
+        (self.0)(notice)
+    }
+}
+
+impl Debug for NoticeCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NoticeCallback").finish_non_exhaustive()
+    }
+}
@@ -3,7 +3,9 @@ use crate::error::Error;
 use crate::{PgConnectOptions, PgConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
+use sqlx_core::metrics::Metrics;
 use sqlx_core::Url;
+use std::sync::Arc;
 use std::time::Duration;
 
 impl ConnectOptions for PgConnectOptions {
@@ -33,4 +35,9 @@ impl ConnectOptions for PgConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.log_settings.metrics(metrics);
+        self
+    }
 }
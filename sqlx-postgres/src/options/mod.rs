@@ -1,11 +1,18 @@
 use std::borrow::Cow;
 use std::env::var;
-use std::fmt::{Display, Write};
+use std::fmt::{self, Debug, Display, Formatter, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 pub use ssl_mode::PgSslMode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::connection::shared_metadata::SharedStatementMetadata;
+use crate::error::Error;
+use crate::net::{tls::CertificateInput, SocketConnector};
+#[cfg(feature = "debug-protocol")]
+use crate::net::{Direction, PacketInspector};
+use crate::notice::NoticeCallback;
+use crate::{connection::LogSettings, PgNotice};
 
 mod connect;
 mod parse;
@@ -33,6 +40,8 @@ mod ssl_mode;
 /// | `sslmode` | `prefer` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`PgSslMode`]. |
 /// | `sslrootcert` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
+/// | `unnamed-statements` | `false` | Execute queries with arguments using only the unnamed prepared statement. See [`PgConnectOptions::unnamed_statements()`]. |
+/// | `strict-timestamps` | `false` | Refuse to decode `TIMESTAMP` as a date and time with a time zone, and `TIMESTAMPTZ` as one without. See [`PgConnectOptions::strict_timestamps()`]. |
 /// | `host` | `None` | Path to the directory containing a PostgreSQL unix domain socket, which will be used instead of TCP if set. |
 /// | `hostaddr` | `None` | Same as `host`, but only accepts IP addresses. |
 /// | `application-name` | `None` | The name will be displayed in the pg_stat_activity view and included in CSV log entries. |
@@ -86,11 +95,12 @@ mod ssl_mode;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct PgConnectOptions {
     pub(crate) host: String,
     pub(crate) port: u16,
     pub(crate) socket: Option<PathBuf>,
+    pub(crate) socket_connector: Option<Arc<dyn SocketConnector>>,
     pub(crate) username: String,
     pub(crate) password: Option<String>,
     pub(crate) database: Option<String>,
@@ -99,10 +109,16 @@ pub struct PgConnectOptions {
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) shared_metadata: Option<Arc<SharedStatementMetadata>>,
+    pub(crate) unnamed_statements: bool,
+    pub(crate) strict_timestamps: bool,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
     pub(crate) options: Option<String>,
+    pub(crate) notice_callback: Option<NoticeCallback>,
+    #[cfg(feature = "debug-protocol")]
+    pub(crate) packet_inspector: Option<PacketInspector>,
 }
 
 impl Default for PgConnectOptions {
@@ -111,6 +127,39 @@ impl Default for PgConnectOptions {
     }
 }
 
+// The password is redacted so the options can be logged safely.
+impl Debug for PgConnectOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("PgConnectOptions");
+
+        s.field("host", &self.host)
+            .field("port", &self.port)
+            .field("socket", &self.socket)
+            .field("socket_connector", &self.socket_connector)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "********"))
+            .field("database", &self.database)
+            .field("ssl_mode", &self.ssl_mode)
+            .field("ssl_root_cert", &self.ssl_root_cert)
+            .field("ssl_client_cert", &self.ssl_client_cert)
+            .field("ssl_client_key", &self.ssl_client_key)
+            .field("statement_cache_capacity", &self.statement_cache_capacity)
+            .field("shared_metadata", &self.shared_metadata.is_some())
+            .field("unnamed_statements", &self.unnamed_statements)
+            .field("strict_timestamps", &self.strict_timestamps)
+            .field("application_name", &self.application_name)
+            .field("log_settings", &self.log_settings)
+            .field("extra_float_digits", &self.extra_float_digits)
+            .field("options", &self.options)
+            .field("notice_callback", &self.notice_callback);
+
+        #[cfg(feature = "debug-protocol")]
+        s.field("packet_inspector", &self.packet_inspector);
+
+        s.finish()
+    }
+}
+
 impl PgConnectOptions {
     /// Creates a new, default set of options ready for configuration.
     ///
@@ -154,6 +203,7 @@ impl PgConnectOptions {
             port,
             host,
             socket: None,
+            socket_connector: None,
             username,
             password: var("PGPASSWORD").ok(),
             database,
@@ -165,10 +215,16 @@ impl PgConnectOptions {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
             statement_cache_capacity: 100,
+            shared_metadata: None,
+            unnamed_statements: false,
+            strict_timestamps: false,
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("2".into()),
             log_settings: Default::default(),
             options: var("PGOPTIONS").ok(),
+            notice_callback: None,
+            #[cfg(feature = "debug-protocol")]
+            packet_inspector: None,
         }
     }
 
@@ -231,6 +287,31 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets a connector that opens the stream to the server, instead of connecting to
+    /// [`host`][Self::host] and [`port`][Self::port] or a [`socket`][Self::socket].
+    ///
+    /// The connector is called for every new connection, including those opened by a pool.
+    /// TLS is negotiated on the returned stream according to [`ssl_mode`][Self::ssl_mode],
+    /// with `host` used as the server name to verify.
+    ///
+    /// By default set to `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use sqlx::net::StreamSocket;
+    /// use sqlx::postgres::PgConnectOptions;
+    ///
+    /// let options = PgConnectOptions::new().socket_connector(|| async {
+    ///     let stream = open_tunnel().await?;
+    ///     Ok(StreamSocket::from_tokio(stream))
+    /// });
+    /// ```
+    pub fn socket_connector(mut self, connector: impl SocketConnector) -> Self {
+        self.socket_connector = Some(Arc::new(connector));
+        self
+    }
+
     /// Sets the username to connect as.
     ///
     /// Defaults to be the same as the operating system name of
@@ -423,6 +504,95 @@ impl PgConnectOptions {
     /// The default cache capacity is 100 statements.
     pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
         self.statement_cache_capacity = capacity;
+
+        if self.shared_metadata.is_some() {
+            self.shared_metadata = Some(Arc::new(SharedStatementMetadata::new(capacity)));
+        }
+
+        self
+    }
+
+    /// If `true`, the metadata of the statements prepared by each connection opened with
+    /// these options, i.e. by every connection of a [`PgPool`][crate::PgPool], is shared with
+    /// the others: the types of the parameters and the names and types of the columns.
+    ///
+    /// Each connection still prepares each statement on the server, and the server still
+    /// describes it, but if the description matches the shared metadata the connection
+    /// uses it instead of building its own. This saves looking up the user-defined types
+    /// of the statement on every connection, and the memory of a copy of the metadata
+    /// per connection.
+    ///
+    /// If the description doesn't match, e.g. after an `ALTER TABLE`, the shared metadata
+    /// is replaced. The shared cache holds as many statements as
+    /// [`statement_cache_capacity`][Self::statement_cache_capacity].
+    ///
+    /// The default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .share_statement_metadata(true);
+    /// ```
+    pub fn share_statement_metadata(mut self, enabled: bool) -> Self {
+        self.shared_metadata =
+            enabled.then(|| Arc::new(SharedStatementMetadata::new(self.statement_cache_capacity)));
+        self
+    }
+
+    /// If `true`, queries with arguments are parsed into the unnamed prepared statement and
+    /// bound and executed in the same round-trip, so nothing outlives the query on the server.
+    ///
+    /// This is required behind a connection pooler in transaction mode, such as PgBouncer with
+    /// `pool_mode = transaction`, which may hand each round-trip outside a transaction to a
+    /// different server connection: a named prepared statement may not exist on the next one
+    /// (`prepared statement "sqlx_s_1" does not exist`).
+    ///
+    /// The statement cache is bypassed and every query is parsed again on each execution.
+    /// Statements explicitly created with [`Executor::prepare()`][sqlx_core::executor::Executor::prepare]
+    /// are still named, but executing them re-parses their SQL.
+    ///
+    /// Arguments that need their types inferred by the server (e.g. JSON) take an extra
+    /// round-trip to describe the statement. And the first time a query returns a column of a
+    /// user-defined type, its rows are buffered in memory until the type has been looked up.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .unnamed_statements(true);
+    /// ```
+    pub fn unnamed_statements(mut self, enabled: bool) -> Self {
+        self.unnamed_statements = enabled;
+        self
+    }
+
+    /// If `true`, decoding a `TIMESTAMP` as a date and time with a time zone, such as
+    /// `chrono::DateTime<Utc>` or `time::OffsetDateTime`, is an error, as is decoding
+    /// a `TIMESTAMPTZ` as one without, such as `chrono::NaiveDateTime` or
+    /// `time::PrimitiveDateTime`.
+    ///
+    /// `Row::try_get()` already refuses these types, but they are decoded silently by
+    /// `Row::try_get_unchecked()` and by type overrides in the query macros: a `TIMESTAMP`
+    /// is taken to be in UTC, and the time zone of a `TIMESTAMPTZ` is dropped.
+    /// To decode a `TIMESTAMP` that is known to hold UTC, use `sqlx::types::chrono::AssumeUtc`,
+    /// or decode a `time::PrimitiveDateTime` and call `.assume_utc()` on it.
+    ///
+    /// The default is `false`, which will change to `true` in a future release.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .strict_timestamps(true);
+    /// ```
+    pub fn strict_timestamps(mut self, enabled: bool) -> Self {
+        self.strict_timestamps = enabled;
         self
     }
 
@@ -495,6 +665,14 @@ impl PgConnectOptions {
 
     /// Set additional startup options for the connection as a list of key-value pairs.
     ///
+    /// The options are sent in the startup message (as `-c key=value`, like libpq's `options`
+    /// parameter), so they are in effect from the very first query without an extra round trip.
+    /// Whitespace and backslashes in values are escaped as needed.
+    ///
+    /// The `user`, `database` and `client_encoding` parameters are reserved and will cause
+    /// an error when connecting; use [`username`][Self::username] and
+    /// [`database`][Self::database] instead.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -515,11 +693,103 @@ impl PgConnectOptions {
                 options_str.push(' ');
             }
 
-            write!(options_str, "-c {k}={v}").expect("failed to write an option to the string");
+            write!(options_str, "-c {k}=").expect("failed to write an option to the string");
+
+            for c in v.to_string().chars() {
+                if c.is_ascii_whitespace() || c == '\\' {
+                    options_str.push('\\');
+                }
+
+                options_str.push(c);
+            }
         }
         self
     }
 
+    /// Sets a callback to be invoked for each notice or warning sent by the server,
+    /// e.g. from `RAISE NOTICE` in PL/pgSQL.
+    ///
+    /// The callback is called synchronously as the message is read from the connection,
+    /// including while a query is being executed. Notices are still logged to the
+    /// `sqlx::postgres::notice` target as usual.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .notice_callback(|notice| println!("{}: {}", notice.code(), notice.message()));
+    /// ```
+    pub fn notice_callback(mut self, callback: impl Fn(PgNotice) + Send + Sync + 'static) -> Self {
+        self.notice_callback = Some(NoticeCallback::new(callback));
+        self
+    }
+
+    /// Sets a callback to be invoked with every message read from or written to the server,
+    /// for debugging the wire protocol.
+    ///
+    /// Messages are passed as raw bytes, including the message type and length, after TLS
+    /// decryption and before they are parsed. Messages that are written are passed when
+    /// they are flushed. [`inspect::format_frame()`][crate::inspect::format_frame]
+    /// formats a message for printing.
+    ///
+    /// The callback is called synchronously from the connection, so it should return quickly.
+    /// Nothing is done for connections without a callback.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// use sqlx_postgres::inspect::format_frame;
+    ///
+    /// let options = PgConnectOptions::new()
+    ///     .packet_inspector(|direction, frame| eprintln!("{}", format_frame(direction, frame)));
+    /// ```
+    #[cfg(feature = "debug-protocol")]
+    pub fn packet_inspector(
+        mut self,
+        inspector: impl Fn(Direction, &[u8]) + Send + Sync + 'static,
+    ) -> Self {
+        self.packet_inspector = Some(PacketInspector::new(inspector));
+        self
+    }
+
+    /// Returns an error if the startup options try to set a parameter that is reserved
+    /// for the connection itself.
+    pub(crate) fn check_options(&self) -> Result<(), Error> {
+        let Some(options) = &self.options else {
+            return Ok(());
+        };
+
+        let mut args = split_options(options).into_iter();
+
+        while let Some(arg) = args.next() {
+            let setting = match arg.strip_prefix("--") {
+                Some(setting) => setting.to_owned(),
+                None if arg == "-c" => args.next().unwrap_or_default(),
+                None => match arg.strip_prefix("-c") {
+                    Some(setting) => setting.to_owned(),
+                    None => continue,
+                },
+            };
+
+            let key = setting
+                .split('=')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase()
+                .replace('-', "_");
+
+            if RESERVED_OPTIONS.contains(&&*key) {
+                return Err(Error::Configuration(
+                    format!("the `{key}` parameter cannot be set via `options`").into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// We try using a socket if hostname starts with `/` or if socket parameter
     /// is specified.
     pub(crate) fn fetch_socket(&self) -> Option<String> {
@@ -650,6 +920,34 @@ impl PgConnectOptions {
     }
 }
 
+// These are set by the driver in the startup message and must not be overridden.
+const RESERVED_OPTIONS: &[&str] = &["user", "database", "client_encoding"];
+
+// Split a libpq `options` string on unescaped whitespace, removing the escapes.
+fn split_options(options: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut chars = options.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => arg.extend(chars.next()),
+            c if c.is_ascii_whitespace() => {
+                if !arg.is_empty() {
+                    args.push(std::mem::take(&mut arg));
+                }
+            }
+            c => arg.push(c),
+        }
+    }
+
+    if !arg.is_empty() {
+        args.push(arg);
+    }
+
+    args
+}
+
 fn default_host(port: u16) -> String {
     // try to check for the existence of a unix socket and uses that
     let socket = format!(".s.PGSQL.{port}");
@@ -4,6 +4,7 @@ use crate::message::DataRow;
 use crate::statement::PgStatementMetadata;
 use crate::value::PgValueFormat;
 use crate::{PgColumn, PgValueRef, Postgres};
+use sqlx_core::column::resolve_column_name;
 pub(crate) use sqlx_core::row::Row;
 use sqlx_core::type_checking::TypeChecking;
 use sqlx_core::value::ValueRef;
@@ -15,6 +16,8 @@ pub struct PgRow {
     pub(crate) data: DataRow,
     pub(crate) format: PgValueFormat,
     pub(crate) metadata: Arc<PgStatementMetadata>,
+    // see `PgConnectOptions::strict_timestamps()`
+    pub(crate) strict_timestamps: bool,
 }
 
 impl Row for PgRow {
@@ -37,17 +40,26 @@ impl Row for PgRow {
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
+            strict_timestamps: self.strict_timestamps,
         })
     }
 }
 
 impl ColumnIndex<PgRow> for &'_ str {
     fn index(&self, row: &PgRow) -> Result<usize, Error> {
-        row.metadata
-            .column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        if let Some(&index) = row.metadata.column_names.get(*self) {
+            return Ok(index);
+        }
+
+        // fall back to a case-insensitive lookup;
+        // `RowDescription` only gives us table OIDs, so we can't match `table.column`
+        resolve_column_name(
+            self,
+            row.metadata
+                .columns
+                .iter()
+                .map(|column| (column.ordinal, &*column.name, None)),
+        )
     }
 }
 
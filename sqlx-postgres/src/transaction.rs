@@ -1,12 +1,89 @@
+use std::borrow::Cow;
+
 use futures_core::future::BoxFuture;
 
 use crate::error::Error;
 use crate::executor::Executor;
+use crate::message::TransactionStatus;
+use crate::query_scalar::query_scalar;
 
 use crate::{PgConnection, Postgres};
 
+/// The maximum length of a transaction ID for two-phase commit, in bytes.
+const MAX_GID_LEN: usize = 199;
+
 pub(crate) use sqlx_core::transaction::*;
 
+/// Options for beginning a transaction with [`Connection::begin_with()`].
+///
+/// These only apply to a new transaction; they are ignored when establishing a savepoint.
+///
+/// [`Connection::begin_with()`]: crate::connection::Connection::begin_with
+#[derive(Debug, Clone, Default)]
+pub struct PgTransactionOptions {
+    isolation_level: Option<IsolationLevel>,
+    read_only: Option<bool>,
+    deferrable: Option<bool>,
+}
+
+impl PgTransactionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the isolation level of the transaction.
+    ///
+    /// If not set, the `default_transaction_isolation` of the session is used,
+    /// which defaults to `READ COMMITTED`.
+    pub fn isolation_level(mut self, level: IsolationLevel) -> Self {
+        self.isolation_level = Some(level);
+        self
+    }
+
+    /// Sets whether the transaction is `READ ONLY` or `READ WRITE`.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
+    /// Sets whether the transaction is `DEFERRABLE`.
+    ///
+    /// This only has an effect if the transaction is also `SERIALIZABLE` and `READ ONLY`.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = Some(deferrable);
+        self
+    }
+
+    fn begin_sql(&self) -> String {
+        let mut sql = String::from("BEGIN");
+
+        if let Some(level) = self.isolation_level {
+            sql.push_str(" ISOLATION LEVEL ");
+            sql.push_str(level.as_str());
+        }
+
+        match self.read_only {
+            Some(true) => sql.push_str(" READ ONLY"),
+            Some(false) => sql.push_str(" READ WRITE"),
+            None => (),
+        }
+
+        match self.deferrable {
+            Some(true) => sql.push_str(" DEFERRABLE"),
+            Some(false) => sql.push_str(" NOT DEFERRABLE"),
+            None => (),
+        }
+
+        sql
+    }
+}
+
+impl sealed::Sealed for PgTransactionOptions {}
+
+impl TransactionOptions for PgTransactionOptions {
+    type Database = Postgres;
+}
+
 /// Implementation of [`TransactionManager`] for PostgreSQL.
 pub struct PgTransactionManager;
 
@@ -14,15 +91,17 @@ impl TransactionManager for PgTransactionManager {
     type Database = Postgres;
 
     fn begin(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with_options(conn, PgTransactionOptions::default())
+    }
+
+    fn begin_with<O>(conn: &mut PgConnection, options: O) -> BoxFuture<'_, Result<(), Error>>
+    where
+        O: TransactionOptions<Database = Postgres>,
+    {
         Box::pin(async move {
-            let rollback = Rollback::new(conn);
-            let query = begin_ansi_transaction_sql(rollback.conn.inner.transaction_depth);
-            rollback.conn.queue_simple_query(&query)?;
-            rollback.conn.inner.transaction_depth += 1;
-            rollback.conn.wait_until_ready().await?;
-            rollback.defuse();
+            let options = downcast_options(options)?;
 
-            Ok(())
+            Self::begin_with_options(conn, options).await
         })
     }
 
@@ -62,6 +141,107 @@ impl TransactionManager for PgTransactionManager {
             conn.inner.transaction_depth -= 1;
         }
     }
+
+    fn prepare<'c>(conn: &'c mut PgConnection, gid: &'c str) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            let gid = gid_literal(gid)?;
+
+            if conn.inner.transaction_depth != 1 {
+                return Err(Error::InvalidArgument(
+                    "only a transaction, not a savepoint, can be prepared for two-phase commit"
+                        .into(),
+                ));
+            }
+
+            // `PREPARE TRANSACTION` would roll back a failed transaction without an error
+            if matches!(conn.inner.transaction_status, TransactionStatus::Error) {
+                return Err(Error::InvalidArgument(
+                    "a failed transaction can't be prepared for two-phase commit".into(),
+                ));
+            }
+
+            conn.execute(&*format!("PREPARE TRANSACTION {gid}")).await?;
+            conn.inner.transaction_depth -= 1;
+
+            Ok(())
+        })
+    }
+}
+
+impl PgTransactionManager {
+    fn begin_with_options(
+        conn: &mut PgConnection,
+        options: PgTransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            let rollback = Rollback::new(conn);
+            let depth = rollback.conn.inner.transaction_depth;
+            let query = if depth == 0 {
+                Cow::Owned(options.begin_sql())
+            } else {
+                begin_ansi_transaction_sql(depth)
+            };
+            rollback.conn.queue_simple_query(&query)?;
+            rollback.conn.inner.transaction_depth += 1;
+            rollback.conn.wait_until_ready().await?;
+            rollback.defuse();
+
+            Ok(())
+        })
+    }
+}
+
+impl PgConnection {
+    /// Commits the transaction prepared for two-phase commit with the transaction ID `gid`.
+    ///
+    /// This can be called from any connection to the database the transaction was prepared in,
+    /// but not inside a transaction.
+    ///
+    /// See [`Transaction::prepare_two_phase()`].
+    pub async fn commit_prepared(&mut self, gid: &str) -> Result<(), Error> {
+        self.execute(&*format!("COMMIT PREPARED {}", gid_literal(gid)?))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Rolls back the transaction prepared for two-phase commit with the transaction ID `gid`.
+    ///
+    /// This can be called from any connection to the database the transaction was prepared in,
+    /// but not inside a transaction.
+    ///
+    /// See [`Transaction::prepare_two_phase()`].
+    pub async fn rollback_prepared(&mut self, gid: &str) -> Result<(), Error> {
+        self.execute(&*format!("ROLLBACK PREPARED {}", gid_literal(gid)?))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the IDs of the transactions prepared for two-phase commit in the current database,
+    /// oldest first, from `pg_prepared_xacts`.
+    pub async fn list_prepared(&mut self) -> Result<Vec<String>, Error> {
+        query_scalar(
+            "SELECT gid FROM pg_prepared_xacts WHERE database = current_database() ORDER BY prepared",
+        )
+        .fetch_all(self)
+        .await
+    }
+}
+
+/// Quotes a transaction ID for two-phase commit as a string literal.
+fn gid_literal(gid: &str) -> Result<String, Error> {
+    if gid.is_empty() || gid.len() > MAX_GID_LEN || gid.contains('\0') {
+        return Err(Error::InvalidArgument(format!(
+            "invalid transaction ID {gid:?}: must be 1 to {MAX_GID_LEN} bytes without NUL"
+        )));
+    }
+
+    // an escape string literal is quoted the same regardless of `standard_conforming_strings`
+    Ok(format!(
+        "E'{}'",
+        gid.replace('\\', "\\\\").replace('\'', "''")
+    ))
 }
 
 struct Rollback<'c> {
@@ -88,3 +268,30 @@ impl<'c> Rollback<'c> {
         self.defuse = true;
     }
 }
+
+#[test]
+fn test_begin_sql() {
+    assert_eq!(PgTransactionOptions::new().begin_sql(), "BEGIN");
+    assert_eq!(
+        PgTransactionOptions::new()
+            .isolation_level(IsolationLevel::Serializable)
+            .read_only(true)
+            .deferrable(true)
+            .begin_sql(),
+        "BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE"
+    );
+    assert_eq!(
+        PgTransactionOptions::new().read_only(false).begin_sql(),
+        "BEGIN READ WRITE"
+    );
+}
+
+#[test]
+fn test_gid_literal() {
+    assert_eq!(gid_literal("tx-1").unwrap(), "E'tx-1'");
+    assert_eq!(gid_literal(r"it's a\b").unwrap(), r"E'it''s a\\b'");
+    assert!(gid_literal("").is_err());
+    assert!(gid_literal("a\0b").is_err());
+    assert!(gid_literal(&"x".repeat(MAX_GID_LEN)).is_ok());
+    assert!(gid_literal(&"x".repeat(MAX_GID_LEN + 1)).is_err());
+}
@@ -98,6 +98,9 @@ impl_type_checking!(
         sqlx::types::BitVec,
 
         sqlx::postgres::types::PgHstore,
+        sqlx::postgres::types::PgXml,
+        sqlx::postgres::types::PgTsVector,
+        sqlx::postgres::types::PgTsQuery,
         // Arrays
 
         Vec<bool> | &[bool],
@@ -156,6 +159,9 @@ impl_type_checking!(
         Vec<sqlx::types::JsonValue> | &[sqlx::types::JsonValue],
 
         Vec<sqlx::postgres::types::PgHstore> | &[sqlx::postgres::types::PgHstore],
+        Vec<sqlx::postgres::types::PgXml> | &[sqlx::postgres::types::PgXml],
+        Vec<sqlx::postgres::types::PgTsVector> | &[sqlx::postgres::types::PgTsVector],
+        Vec<sqlx::postgres::types::PgTsQuery> | &[sqlx::postgres::types::PgTsQuery],
 
         // Ranges
 
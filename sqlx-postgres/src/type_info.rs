@@ -146,6 +146,12 @@ pub enum PgType {
     JsonpathArray,
     Money,
     MoneyArray,
+    Xml,
+    XmlArray,
+    TsVector,
+    TsVectorArray,
+    TsQuery,
+    TsQueryArray,
 
     // https://www.postgresql.org/docs/9.3/datatype-pseudo.html
     Void,
@@ -203,6 +209,22 @@ impl PgTypeInfo {
         self.0.kind()
     }
 
+    /// If this is a domain, returns the type it was declared over (following nested domains),
+    /// otherwise returns `self`.
+    ///
+    /// A domain has the same representation as its base type, so `Type::compatible()`
+    /// implementations check this to accept domains over the types they decode.
+    pub(crate) fn domain_base(&self) -> &PgTypeInfo {
+        // NOTE: not `self.kind()`, which panics for unresolved type declarations
+        match &self.0 {
+            PgType::Custom(ty) => match &ty.kind {
+                PgTypeKind::Domain(base) => base.domain_base(),
+                _ => self,
+            },
+            _ => self,
+        }
+    }
+
     /// Returns the OID for this type, if available.
     ///
     /// The OID may not be available if SQLx only knows the type by name.
@@ -384,6 +406,12 @@ impl PgType {
             775 => PgType::Macaddr8Array,
             790 => PgType::Money,
             791 => PgType::MoneyArray,
+            142 => PgType::Xml,
+            143 => PgType::XmlArray,
+            3614 => PgType::TsVector,
+            3643 => PgType::TsVectorArray,
+            3615 => PgType::TsQuery,
+            3645 => PgType::TsQueryArray,
             829 => PgType::Macaddr,
             869 => PgType::Inet,
             1000 => PgType::BoolArray,
@@ -492,6 +520,12 @@ impl PgType {
             PgType::Macaddr8Array => Oid(775),
             PgType::Money => Oid(790),
             PgType::MoneyArray => Oid(791),
+            PgType::Xml => Oid(142),
+            PgType::XmlArray => Oid(143),
+            PgType::TsVector => Oid(3614),
+            PgType::TsVectorArray => Oid(3643),
+            PgType::TsQuery => Oid(3615),
+            PgType::TsQueryArray => Oid(3645),
             PgType::Macaddr => Oid(829),
             PgType::Inet => Oid(869),
             PgType::BoolArray => Oid(1000),
@@ -661,6 +695,12 @@ impl PgType {
             PgType::JsonpathArray => "JSONPATH[]",
             PgType::Money => "MONEY",
             PgType::MoneyArray => "MONEY[]",
+            PgType::Xml => "XML",
+            PgType::XmlArray => "XML[]",
+            PgType::TsVector => "TSVECTOR",
+            PgType::TsVectorArray => "TSVECTOR[]",
+            PgType::TsQuery => "TSQUERY",
+            PgType::TsQueryArray => "TSQUERY[]",
             PgType::Void => "VOID",
             PgType::Custom(ty) => &ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -762,6 +802,12 @@ impl PgType {
             PgType::JsonpathArray => "_jsonpath",
             PgType::Money => "money",
             PgType::MoneyArray => "_money",
+            PgType::Xml => "xml",
+            PgType::XmlArray => "_xml",
+            PgType::TsVector => "tsvector",
+            PgType::TsVectorArray => "_tsvector",
+            PgType::TsQuery => "tsquery",
+            PgType::TsQueryArray => "_tsquery",
             PgType::Void => "void",
             PgType::Custom(ty) => &ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -863,6 +909,12 @@ impl PgType {
             PgType::JsonpathArray => &PgTypeKind::Array(PgTypeInfo(PgType::Jsonpath)),
             PgType::Money => &PgTypeKind::Simple,
             PgType::MoneyArray => &PgTypeKind::Array(PgTypeInfo(PgType::Money)),
+            PgType::Xml => &PgTypeKind::Simple,
+            PgType::XmlArray => &PgTypeKind::Array(PgTypeInfo(PgType::Xml)),
+            PgType::TsVector => &PgTypeKind::Simple,
+            PgType::TsVectorArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsVector)),
+            PgType::TsQuery => &PgTypeKind::Simple,
+            PgType::TsQueryArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsQuery)),
 
             PgType::Void => &PgTypeKind::Pseudo,
 
@@ -931,6 +983,12 @@ impl PgType {
             PgType::Macaddr8Array => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr8))),
             PgType::Money => None,
             PgType::MoneyArray => Some(Cow::Owned(PgTypeInfo(PgType::Money))),
+            PgType::Xml => None,
+            PgType::XmlArray => Some(Cow::Owned(PgTypeInfo(PgType::Xml))),
+            PgType::TsVector => None,
+            PgType::TsVectorArray => Some(Cow::Owned(PgTypeInfo(PgType::TsVector))),
+            PgType::TsQuery => None,
+            PgType::TsQueryArray => Some(Cow::Owned(PgTypeInfo(PgType::TsQuery))),
             PgType::Macaddr => None,
             PgType::MacaddrArray => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr))),
             PgType::Inet => None,
@@ -1054,7 +1112,7 @@ impl TypeInfo for PgTypeInfo {
     where
         Self: Sized,
     {
-        self == other
+        self == other || self == other.domain_base()
     }
 }
 
@@ -1095,6 +1153,25 @@ impl PgTypeInfo {
     pub(crate) const JSONPATH: Self = Self(PgType::Jsonpath);
     pub(crate) const JSONPATH_ARRAY: Self = Self(PgType::JsonpathArray);
 
+    //
+    // XML type
+    // https://www.postgresql.org/docs/current/datatype-xml.html
+    //
+
+    pub(crate) const XML: Self = Self(PgType::Xml);
+    pub(crate) const XML_ARRAY: Self = Self(PgType::XmlArray);
+
+    //
+    // text search types
+    // https://www.postgresql.org/docs/current/datatype-textsearch.html
+    //
+
+    pub(crate) const TSVECTOR: Self = Self(PgType::TsVector);
+    pub(crate) const TSVECTOR_ARRAY: Self = Self(PgType::TsVectorArray);
+
+    pub(crate) const TSQUERY: Self = Self(PgType::TsQuery);
+    pub(crate) const TSQUERY_ARRAY: Self = Self(PgType::TsQueryArray);
+
     //
     // network address types
     // https://www.postgresql.org/docs/current/datatype-net-types.html
@@ -1388,3 +1465,34 @@ fn test_name_eq() {
         );
     }
 }
+
+#[test]
+fn test_domain_compatible() {
+    fn domain(oid: u32, name: &str, base: PgTypeInfo) -> PgTypeInfo {
+        PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
+            oid: Oid(oid),
+            name: name.to_string().into(),
+            kind: PgTypeKind::Domain(base),
+        })))
+    }
+
+    let email = domain(100_000, "email", PgTypeInfo::TEXT);
+    let positive = domain(100_001, "positive_int8", PgTypeInfo::INT8);
+    let nested = domain(100_002, "work_email", email.clone());
+
+    // Equality stays exact
+    assert_ne!(email, PgTypeInfo::TEXT);
+    assert_ne!(PgTypeInfo::TEXT, email);
+    assert_ne!(nested, email);
+    assert_eq!(email, PgTypeInfo::with_name("email"));
+
+    assert_eq!(nested.domain_base(), &PgTypeInfo::TEXT);
+    assert!(PgTypeInfo::TEXT.type_compatible(&email));
+    assert!(PgTypeInfo::TEXT.type_compatible(&nested));
+    assert!(PgTypeInfo::INT8.type_compatible(&positive));
+    assert!(PgTypeInfo::with_name("email").type_compatible(&email));
+
+    assert!(!PgTypeInfo::INT4.type_compatible(&positive));
+    assert!(!email.type_compatible(&positive));
+    assert!(!email.type_compatible(&domain(100_003, "username", PgTypeInfo::TEXT)));
+}
@@ -1,6 +1,8 @@
 use sqlx_core::bytes::Buf;
 use sqlx_core::types::Text;
 use std::borrow::Cow;
+use std::iter::Peekable;
+use std::str::CharIndices;
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
@@ -162,7 +164,9 @@ where
             .unwrap_or_else(T::type_info);
 
         buf.extend(&1_i32.to_be_bytes()); // number of dimensions
-        buf.extend(&0_i32.to_be_bytes()); // flags
+
+        let flags_offset = buf.len();
+        buf.extend(&0_i32.to_be_bytes()); // flags, set below if there are `NULL` elements
 
         // element type
         match type_info.0 {
@@ -184,8 +188,19 @@ where
         buf.extend(array_len.to_be_bytes()); // len
         buf.extend(&1_i32.to_be_bytes()); // lower bound
 
+        let mut has_nulls = false;
+
         for element in self.iter() {
+            let element_offset = buf.len();
+
             buf.encode(element)?;
+
+            // `NULL` elements are encoded as just a length of -1
+            has_nulls |= buf[element_offset..] == (-1_i32).to_be_bytes();
+        }
+
+        if has_nulls {
+            buf[flags_offset..flags_offset + 4].copy_from_slice(&1_i32.to_be_bytes());
         }
 
         Ok(IsNull::No)
@@ -262,7 +277,12 @@ where
                 let mut elements = Vec::with_capacity(len);
 
                 for _ in 0..len {
-                    let value_ref = PgValueRef::get(&mut buf, format, element_type_info.clone())?;
+                    let value_ref = PgValueRef::get(
+                        &mut buf,
+                        format,
+                        element_type_info.clone(),
+                        value.strict_timestamps,
+                    )?;
 
                     elements.push(T::decode(value_ref)?);
                 }
@@ -274,83 +294,400 @@ where
                 // no type is provided from the database for the element
                 let element_type_info = T::type_info();
 
-                let s = value.as_str()?;
+                // NOTE: Nearly *all* types use ',' as the sequence delimiter. Yes, there is one
+                //       that does not. The BOX (not PostGIS) type uses ';' as a delimiter.
+                let delimiter = if *element_type_info == PgType::Box {
+                    ';'
+                } else {
+                    ','
+                };
+
+                parse_text_array(value.as_str()?, delimiter)?
+                    .into_iter()
+                    .map(|element| {
+                        T::decode(PgValueRef {
+                            value: element.as_deref().map(str::as_bytes),
+                            row: None,
+                            type_info: element_type_info.clone(),
+                            format,
+                            strict_timestamps: value.strict_timestamps,
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
 
-                // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L718
+/// Parse the text format of a one-dimensional array into its elements, where `None` is `NULL`.
+///
+/// Elements are borrowed from `s` unless they contain escapes. An unquoted `NULL`
+/// (case-insensitive) is `NULL`, while a quoted `"NULL"` is the string `NULL`.
+///
+/// https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L718
+pub(crate) fn parse_text_array(
+    s: &str,
+    delimiter: char,
+) -> Result<Vec<Option<Cow<'_, str>>>, BoxDynError> {
+    let mut chars = s.char_indices().peekable();
+
+    let skip_whitespace = |chars: &mut Peekable<CharIndices<'_>>| {
+        while chars.next_if(|(_, ch)| ch.is_ascii_whitespace()).is_some() {}
+    };
+
+    skip_whitespace(&mut chars);
+
+    // optional dimension decoration, e.g. `[0:2]={1,2,3}`, which is only output
+    // if the lower bound is not one
+    let mut expected_len = None;
+
+    if let Some((open, _)) = chars.next_if(|&(_, ch)| ch == '[') {
+        let close = s[open..]
+            .find(']')
+            .map(|i| open + i)
+            .ok_or("unterminated array dimensions")?;
+
+        let dimensions = &s[open..=close];
+
+        let (lower, upper) = s[open + 1..close]
+            .split_once(':')
+            .ok_or_else(|| format!("invalid array dimensions: {dimensions:?}"))?;
+
+        let parse_bound = |bound: &str| {
+            bound
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| format!("invalid array dimensions: {dimensions:?}"))
+        };
+
+        let lower = parse_bound(lower)?;
+        let upper = parse_bound(upper)?;
+
+        if s[close + 1..].trim_start().starts_with('[') {
+            return Err(
+                "encountered a multi-dimensional array; only one-dimensional arrays are supported"
+                    .into(),
+            );
+        }
+
+        if lower != 1 {
+            return Err(format!("encountered an array with a lower bound of {lower} in the first dimension; only arrays starting at one are supported").into());
+        }
 
-                // trim the wrapping braces
-                let s = &s[1..(s.len() - 1)];
+        expected_len = Some(upper - lower + 1);
 
-                if s.is_empty() {
-                    // short-circuit empty arrays up here
-                    return Ok(Vec::new());
+        while chars.next_if(|&(i, _)| i <= close).is_some() {}
+        skip_whitespace(&mut chars);
+
+        if chars.next_if(|&(_, ch)| ch == '=').is_none() {
+            return Err("expected `=` after array dimensions".into());
+        }
+
+        skip_whitespace(&mut chars);
+    }
+
+    if chars.next_if(|&(_, ch)| ch == '{').is_none() {
+        return Err(format!("array value must start with `{{` or dimensions: {s:?}").into());
+    }
+
+    let mut elements = Vec::new();
+
+    skip_whitespace(&mut chars);
+
+    if chars.next_if(|&(_, ch)| ch == '}').is_none() {
+        loop {
+            skip_whitespace(&mut chars);
+
+            let element = match chars.peek().copied() {
+                None => return Err("unexpected end of array".into()),
+
+                Some((_, '{')) => {
+                    return Err("encountered a multi-dimensional array; only one-dimensional arrays are supported".into());
                 }
 
-                // NOTE: Nearly *all* types use ',' as the sequence delimiter. Yes, there is one
-                //       that does not. The BOX (not PostGIS) type uses ';' as a delimiter.
+                Some((_, ch)) if ch == delimiter || ch == '}' => {
+                    return Err(format!("unexpected `{ch}` in array; expected an element").into());
+                }
 
-                // TODO: When we add support for BOX we need to figure out some way to make the
-                //       delimiter selection
-
-                let delimiter = ',';
-                let mut done = false;
-                let mut in_quotes = false;
-                let mut in_escape = false;
-                let mut value = String::with_capacity(10);
-                let mut chars = s.chars();
-                let mut elements = Vec::with_capacity(4);
-
-                while !done {
-                    loop {
-                        match chars.next() {
-                            Some(ch) => match ch {
-                                _ if in_escape => {
-                                    value.push(ch);
-                                    in_escape = false;
-                                }
+                Some((open, '"')) => {
+                    chars.next();
 
-                                '"' => {
-                                    in_quotes = !in_quotes;
-                                }
+                    // only allocated if the element contains an escape
+                    let mut unescaped: Option<String> = None;
+                    let mut close = None;
 
-                                '\\' => {
-                                    in_escape = true;
-                                }
+                    while let Some((i, ch)) = chars.next() {
+                        match ch {
+                            '"' => {
+                                close = Some(i);
+                                break;
+                            }
 
-                                _ if ch == delimiter && !in_quotes => {
-                                    break;
+                            '\\' => {
+                                let (_, escaped) =
+                                    chars.next().ok_or("unterminated escape in array element")?;
+
+                                unescaped
+                                    .get_or_insert_with(|| s[open + 1..i].to_owned())
+                                    .push(escaped);
+                            }
+
+                            _ => {
+                                if let Some(unescaped) = &mut unescaped {
+                                    unescaped.push(ch);
                                 }
+                            }
+                        }
+                    }
+
+                    let close = close.ok_or("unterminated quoted array element")?;
+
+                    skip_whitespace(&mut chars);
+
+                    Some(unescaped.map_or(Cow::Borrowed(&s[open + 1..close]), Cow::Owned))
+                }
+
+                Some((start, _)) => {
+                    // only allocated if the element contains an escape
+                    let mut unescaped: Option<String> = None;
+                    // trailing whitespace is not part of the element, unless it is escaped
+                    let mut end = start;
+                    let mut unescaped_len = 0;
+
+                    while let Some((i, ch)) = chars.next_if(|&(_, ch)| ch != delimiter && ch != '}')
+                    {
+                        match ch {
+                            '"' | '{' => {
+                                return Err(
+                                    format!("unexpected `{ch}` in unquoted array element").into()
+                                );
+                            }
 
-                                _ => {
-                                    value.push(ch);
+                            '\\' => {
+                                let (_, escaped) =
+                                    chars.next().ok_or("unterminated escape in array element")?;
+
+                                let unescaped =
+                                    unescaped.get_or_insert_with(|| s[start..i].to_owned());
+
+                                unescaped.push(escaped);
+                                unescaped_len = unescaped.len();
+                            }
+
+                            _ => {
+                                let is_whitespace = ch.is_ascii_whitespace();
+
+                                if let Some(unescaped) = &mut unescaped {
+                                    unescaped.push(ch);
+
+                                    if !is_whitespace {
+                                        unescaped_len = unescaped.len();
+                                    }
                                 }
-                            },
 
-                            None => {
-                                done = true;
-                                break;
+                                if !is_whitespace {
+                                    end = i + ch.len_utf8();
+                                }
                             }
                         }
                     }
 
-                    let value_opt = if value == "NULL" {
-                        None
-                    } else {
-                        Some(value.as_bytes())
-                    };
+                    match unescaped {
+                        Some(mut unescaped) => {
+                            unescaped.truncate(unescaped_len);
+                            Some(Cow::Owned(unescaped))
+                        }
 
-                    elements.push(T::decode(PgValueRef {
-                        value: value_opt,
-                        row: None,
-                        type_info: element_type_info.clone(),
-                        format,
-                    })?);
+                        None if s[start..end].eq_ignore_ascii_case("NULL") => None,
 
-                    value.clear();
+                        None => Some(Cow::Borrowed(&s[start..end])),
+                    }
                 }
+            };
 
-                Ok(elements)
+            elements.push(element);
+
+            match chars.next() {
+                Some((_, ch)) if ch == delimiter => {}
+                Some((_, '}')) => break,
+                Some((_, ch)) => {
+                    return Err(format!("unexpected `{ch}` after array element").into());
+                }
+                None => return Err("unexpected end of array".into()),
             }
         }
     }
+
+    skip_whitespace(&mut chars);
+
+    if let Some((i, _)) = chars.next() {
+        return Err(format!("unexpected data after array: {:?}", &s[i..]).into());
+    }
+
+    if let Some(expected_len) = expected_len {
+        if i64::try_from(elements.len()).ok() != Some(expected_len) {
+            return Err(format!(
+                "array has {} elements but its dimensions specify {expected_len}",
+                elements.len()
+            )
+            .into());
+        }
+    }
+
+    Ok(elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_text_array;
+    use crate::encode::{Encode, IsNull};
+    use crate::PgArgumentBuffer;
+
+    #[test]
+    fn encodes_null_elements() {
+        let mut buf = PgArgumentBuffer::default();
+
+        let is_null = [Some(1_i32), None].encode_by_ref(&mut buf).unwrap();
+        assert!(matches!(is_null, IsNull::No));
+
+        #[rustfmt::skip]
+        let expected: &[u8] = &[
+            0, 0, 0, 1, // number of dimensions
+            0, 0, 0, 1, // flags: has `NULL` elements
+            0, 0, 0, 23, // element type: INT4
+            0, 0, 0, 2, // length
+            0, 0, 0, 1, // lower bound
+            0, 0, 0, 4, 0, 0, 0, 1, // 1
+            255, 255, 255, 255, // NULL
+        ];
+
+        assert_eq!(&**buf, expected);
+
+        let mut buf = PgArgumentBuffer::default();
+
+        let is_null = vec!["a", "b"].encode_by_ref(&mut buf).unwrap();
+        assert!(matches!(is_null, IsNull::No));
+
+        assert_eq!(&buf[4..8], [0, 0, 0, 0]); // flags: no `NULL` elements
+        assert_eq!(&buf[8..12], 25_u32.to_be_bytes()); // element type: TEXT
+    }
+
+    fn parse(s: &str) -> Vec<Option<String>> {
+        parse_text_array(s, ',')
+            .unwrap_or_else(|e| panic!("failed to parse {s:?}: {e}"))
+            .into_iter()
+            .map(|element| element.map(Into::into))
+            .collect()
+    }
+
+    fn some(elements: &[&str]) -> Vec<Option<String>> {
+        elements.iter().map(|s| Some(s.to_string())).collect()
+    }
+
+    #[test]
+    fn parses_empty_array() {
+        assert_eq!(parse("{}"), vec![]);
+        assert_eq!(parse(" { } "), vec![]);
+    }
+
+    #[test]
+    fn parses_unquoted_elements() {
+        assert_eq!(parse("{1,2,3}"), some(&["1", "2", "3"]));
+        assert_eq!(parse("{ a , b b ,c }"), some(&["a", "b b", "c"]));
+        assert_eq!(parse("{héllo,wörld}"), some(&["héllo", "wörld"]));
+    }
+
+    #[test]
+    fn parses_null_vs_quoted_null() {
+        assert_eq!(parse("{NULL,null,1}"), vec![None, None, Some("1".into())]);
+        assert_eq!(parse(r#"{"NULL",NULL}"#), vec![Some("NULL".into()), None]);
+        assert_eq!(parse(r"{\NULL,NULLS}"), some(&["NULL", "NULLS"]));
+    }
+
+    #[test]
+    fn parses_empty_strings() {
+        assert_eq!(parse(r#"{"",""}"#), some(&["", ""]));
+        assert_eq!(parse(r#"{""}"#), some(&[""]));
+    }
+
+    #[test]
+    fn parses_escapes_in_quoted_elements() {
+        assert_eq!(
+            parse(r#"{"a \"quoted\" word","back\\slash"}"#),
+            some(&[r#"a "quoted" word"#, r"back\slash"])
+        );
+        assert_eq!(parse(r#"{"\\"}"#), some(&[r"\"]));
+    }
+
+    #[test]
+    fn parses_delimiters_and_braces_in_quoted_elements() {
+        assert_eq!(
+            parse(r#"{"a,b","{c}","}","  padded  "}"#),
+            some(&["a,b", "{c}", "}", "  padded  "])
+        );
+    }
+
+    #[test]
+    fn parses_escapes_in_unquoted_elements() {
+        assert_eq!(parse(r"{a\,b,c\}}"), some(&["a,b", "c}"]));
+        // escaped trailing whitespace is kept
+        assert_eq!(parse(r"{a\ , b}"), some(&["a ", "b"]));
+    }
+
+    #[test]
+    fn parses_custom_delimiter() {
+        let elements = parse_text_array("{(1,1),(0,0);(2,2),(1,1)}", ';').unwrap();
+        assert_eq!(
+            elements,
+            vec![Some("(1,1),(0,0)".into()), Some("(2,2),(1,1)".into())]
+        );
+    }
+
+    #[test]
+    fn parses_dimensions_starting_at_one() {
+        assert_eq!(parse("[1:3]={1,2,3}"), some(&["1", "2", "3"]));
+    }
+
+    #[test]
+    fn borrows_unescaped_elements() {
+        let elements = parse_text_array(r#"{a,"b",c\,d}"#, ',').unwrap();
+
+        assert!(matches!(elements[0], Some(std::borrow::Cow::Borrowed("a"))));
+        assert!(matches!(elements[1], Some(std::borrow::Cow::Borrowed("b"))));
+        assert!(matches!(elements[2], Some(std::borrow::Cow::Owned(_))));
+    }
+
+    #[test]
+    fn rejects_invalid_arrays() {
+        for s in [
+            "",
+            "1,2",
+            "{1,2",
+            "{1,}",
+            "{,1}",
+            r#"{"abc}"#,
+            r#"{"a"b}"#,
+            r#"{a"b"}"#,
+            r"{a\",
+            "{1} 2",
+            "{{1,2},{3,4}}",
+            "[0:2]={1,2,3}",
+            "[1:2][1:2]={{1,2},{3,4}}",
+            "[1:3]={1,2}",
+            "[1:3]{1,2,3}",
+            "[1-3]={1,2,3}",
+        ] {
+            assert!(
+                parse_text_array(s, ',').is_err(),
+                "expected {s:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_lower_bound_other_than_one() {
+        let err = parse_text_array("[0:2]={1,2,3}", ',').unwrap_err();
+
+        assert!(err.to_string().contains("lower bound of 0"), "{err}");
+    }
 }
@@ -5,7 +5,7 @@ use std::cmp;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::types::numeric::{PgNumeric, PgNumericSign};
+use crate::types::numeric::{PgNumeric, PgNumericNaNError, PgNumericSign};
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
@@ -33,22 +33,30 @@ impl TryFrom<&'_ PgNumeric> for BigDecimal {
     type Error = BoxDynError;
 
     fn try_from(numeric: &'_ PgNumeric) -> Result<Self, Self::Error> {
-        let (digits, sign, weight) = match *numeric {
+        let (digits, sign, weight, scale) = match *numeric {
             PgNumeric::Number {
                 ref digits,
                 sign,
                 weight,
-                ..
-            } => (digits, sign, weight),
+                scale,
+            } => (digits, sign, weight, scale),
 
             PgNumeric::NotANumber => {
-                return Err("BigDecimal does not support NaN values".into());
+                return Err(PgNumericNaNError::new("BigDecimal").into());
             }
         };
 
+        // the number of decimal digits after the decimal point, including trailing zeros,
+        // which are not sent as digits
+        let scale = i64::from(scale);
+
+        if scale < 0 {
+            return Err(format!("invalid scale value for Pg NUMERIC: {scale}").into());
+        }
+
         if digits.is_empty() {
             // Postgres returns an empty digit array for 0 but BigInt expects at least one zero
-            return Ok(0u64.into());
+            return Ok(BigDecimal::new(0.into(), scale));
         }
 
         let sign = match sign {
@@ -56,11 +64,12 @@ impl TryFrom<&'_ PgNumeric> for BigDecimal {
             PgNumericSign::Negative => Sign::Minus,
         };
 
-        // weight is 0 if the decimal point falls after the first base-10000 digit
+        // weight is 0 if the decimal point falls after the first base-10000 digit,
+        // so this is the power of 10 of the last digit, which may be negative or past `scale`
         //
         // `Vec` capacity cannot exceed `isize::MAX` bytes, so this cast can't wrap in practice.
         #[allow(clippy::cast_possible_wrap)]
-        let scale = (digits.len() as i64 - weight as i64 - 1) * 4;
+        let digits_scale = (digits.len() as i64 - weight as i64 - 1) * 4;
 
         // no optimized algorithm for base-10 so use base-100 for faster processing
         let mut cents = Vec::with_capacity(digits.len() * 2);
@@ -85,7 +94,8 @@ impl TryFrom<&'_ PgNumeric> for BigDecimal {
         let bigint = BigInt::from_radix_be(sign, &cents, 100)
             .ok_or("PgNumeric contained an out-of-range digit")?;
 
-        Ok(BigDecimal::new(bigint, scale))
+        // the digits past `scale` are zero, so this only adds or removes trailing zeros
+        Ok(BigDecimal::new(bigint, digits_scale).with_scale(scale))
     }
 }
 
@@ -195,12 +205,18 @@ impl Encode<'_, Postgres> for BigDecimal {
 
 /// ### Note: `NaN`
 /// `BigDecimal` has a greater range than `NUMERIC` (see the corresponding `Encode` impl for details)
-/// but cannot represent `NaN`, so decoding may return an error.
+/// but cannot represent `NaN`, so decoding it returns a [`PgNumericNaNError`].
+///
+/// The scale of the `NUMERIC` is kept, including trailing zeros, e.g. `1.50::numeric` is decoded
+/// with a scale of 2 in both the text and binary formats.
 impl Decode<'_, Postgres> for BigDecimal {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         match value.format() {
             PgValueFormat::Binary => PgNumeric::decode(value.as_bytes()?)?.try_into(),
-            PgValueFormat::Text => Ok(value.as_str()?.parse::<BigDecimal>()?),
+            PgValueFormat::Text => match value.as_str()? {
+                "NaN" => Err(PgNumericNaNError::new("BigDecimal").into()),
+                s => Ok(s.parse::<BigDecimal>()?),
+            },
         }
     }
 }
@@ -475,3 +491,190 @@ mod bigdecimal_to_pgnumeric {
         );
     }
 }
+
+#[cfg(test)]
+mod pgnumeric_to_bigdecimal {
+    use super::{BigDecimal, PgNumeric, PgNumericNaNError, PgNumericSign};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::convert::TryFrom;
+
+    /// Decode `numeric` and check that it's `expected`, including its scale.
+    fn assert_decodes_to(numeric: PgNumeric, expected: &str) {
+        let decimal = BigDecimal::try_from(&numeric).unwrap();
+        let expected: BigDecimal = expected.parse().unwrap();
+
+        assert_eq!(
+            decimal.as_bigint_and_exponent(),
+            expected.as_bigint_and_exponent(),
+            "{numeric:?}"
+        );
+    }
+
+    #[test]
+    fn zero_with_scale() {
+        assert_decodes_to(
+            PgNumeric::Number {
+                sign: PgNumericSign::Positive,
+                scale: 3,
+                weight: 0,
+                digits: vec![],
+            },
+            "0.000",
+        );
+    }
+
+    #[test]
+    fn trailing_zeros() {
+        // `1.50000`, the trailing zero digit groups are not sent
+        assert_decodes_to(
+            PgNumeric::Number {
+                sign: PgNumericSign::Positive,
+                scale: 5,
+                weight: 0,
+                digits: vec![1, 5000],
+            },
+            "1.50000",
+        );
+
+        // `1.5` in a digit group of 4 decimal digits
+        assert_decodes_to(
+            PgNumeric::Number {
+                sign: PgNumericSign::Positive,
+                scale: 1,
+                weight: 0,
+                digits: vec![1, 5000],
+            },
+            "1.5",
+        );
+    }
+
+    #[test]
+    fn weight_past_digits() {
+        assert_decodes_to(
+            PgNumeric::Number {
+                sign: PgNumericSign::Negative,
+                scale: 0,
+                weight: 2,
+                digits: vec![12],
+            },
+            "-1200000000",
+        );
+    }
+
+    #[test]
+    fn tiny_magnitude() {
+        assert_decodes_to(
+            PgNumeric::Number {
+                sign: PgNumericSign::Positive,
+                scale: 20,
+                weight: -5,
+                digits: vec![1],
+            },
+            "0.00000000000000000001",
+        );
+
+        assert_decodes_to(
+            PgNumeric::Number {
+                sign: PgNumericSign::Negative,
+                scale: 22,
+                weight: -5,
+                digits: vec![12, 3400],
+            },
+            "-0.0000000000000000001234",
+        );
+    }
+
+    #[test]
+    fn high_precision() {
+        // NUMERIC(60, 20)
+        assert_decodes_to(
+            PgNumeric::Number {
+                sign: PgNumericSign::Positive,
+                scale: 20,
+                weight: 9,
+                digits: vec![
+                    1234, 5678, 9012, 3456, 7890, 1234, 5678, 9012, 3456, 7890, 1234, 5678, 9012,
+                    3456, 7890,
+                ],
+            },
+            "1234567890123456789012345678901234567890.12345678901234567890",
+        );
+    }
+
+    #[test]
+    fn nan() {
+        let err = BigDecimal::try_from(&PgNumeric::NotANumber).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<PgNumericNaNError>().unwrap().type_name(),
+            "BigDecimal"
+        );
+    }
+
+    /// A random decimal in the text format of Postgres, with runs of zeros
+    /// to cover leading and trailing zero digit groups.
+    fn random_decimal(rng: &mut StdRng) -> String {
+        let digits = |rng: &mut StdRng| -> String {
+            let len = rng.gen_range(0..45);
+            let zeros = rng.gen_bool(0.5);
+
+            (0..len)
+                .map(|_| {
+                    if zeros && rng.gen_bool(0.8) {
+                        '0'
+                    } else {
+                        char::from(b'0' + rng.gen_range(0..10))
+                    }
+                })
+                .collect()
+        };
+
+        let integer = digits(rng);
+        let fraction = digits(rng);
+
+        let integer = match integer.trim_start_matches('0') {
+            "" => "0",
+            integer => integer,
+        };
+
+        let mut s = String::new();
+
+        if rng.gen_bool(0.5) && (integer != "0" || fraction.contains(|c| c != '0')) {
+            s.push('-');
+        }
+
+        s.push_str(integer);
+
+        if !fraction.is_empty() {
+            s.push('.');
+            s.push_str(&fraction);
+        }
+
+        s
+    }
+
+    #[test]
+    fn random_round_trip() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+
+        for _ in 0..10_000 {
+            let text = random_decimal(&mut rng);
+            // Postgres parses the text format with the same result
+            let decimal: BigDecimal = text.parse().unwrap();
+
+            let numeric = PgNumeric::try_from(&decimal).unwrap();
+
+            // `Display` formats the digit groups as Postgres would, so this checks their weight
+            assert_eq!(numeric.to_string(), text, "{numeric:?}");
+
+            let decoded = BigDecimal::try_from(&numeric).unwrap();
+
+            assert_eq!(
+                decoded.as_bigint_and_exponent(),
+                decimal.as_bigint_and_exponent(),
+                "{text}"
+            );
+        }
+    }
+}
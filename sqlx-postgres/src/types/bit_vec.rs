@@ -16,6 +16,7 @@ impl Type<Postgres> for BitVec {
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
+        let ty = ty.domain_base();
         *ty == PgTypeInfo::BIT || *ty == PgTypeInfo::VARBIT
     }
 }
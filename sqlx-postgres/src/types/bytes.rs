@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
@@ -100,13 +102,17 @@ impl Decode<'_, Postgres> for Vec<u8> {
 
 impl<const N: usize> Decode<'_, Postgres> for [u8; N] {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        let mut bytes = [0u8; N];
-        match value.format() {
-            PgValueFormat::Binary => {
-                bytes = value.as_bytes()?.try_into()?;
-            }
-            PgValueFormat::Text => hex::decode_to_slice(text_hex_decode_input(value)?, &mut bytes)?,
+        let bytes = match value.format() {
+            PgValueFormat::Binary => Cow::Borrowed(value.as_bytes()?),
+            PgValueFormat::Text => Cow::Owned(hex::decode(text_hex_decode_input(value)?)?),
         };
-        Ok(bytes)
+
+        <[u8; N]>::try_from(&*bytes).map_err(|_| {
+            format!(
+                "expected {N} bytes to decode `[u8; {N}]`, got {} bytes",
+                bytes.len()
+            )
+            .into()
+        })
     }
 }
@@ -1,11 +1,14 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::types::timestamp::{check_finite, check_time_zone};
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use chrono::{
     DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc,
 };
+use sqlx_core::types::chrono::AssumeUtc;
+use std::borrow::Cow;
 use std::mem;
 
 impl Type<Postgres> for NaiveDateTime {
@@ -49,31 +52,46 @@ impl Encode<'_, Postgres> for NaiveDateTime {
 
 impl<'r> Decode<'r, Postgres> for NaiveDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(match value.format() {
-            PgValueFormat::Binary => {
-                // TIMESTAMP is encoded as the microseconds since the epoch
-                let us = Decode::<Postgres>::decode(value)?;
-                postgres_epoch_datetime() + Duration::microseconds(us)
-            }
-
-            PgValueFormat::Text => {
-                let s = value.as_str()?;
-                NaiveDateTime::parse_from_str(
-                    s,
-                    if s.contains('+') {
-                        // Contains a time-zone specifier
-                        // This is given for timestamptz for some reason
-                        // Postgres already guarantees this to always be UTC
-                        "%Y-%m-%d %H:%M:%S%.f%#z"
-                    } else {
-                        "%Y-%m-%d %H:%M:%S%.f"
-                    },
-                )?
-            }
-        })
+        check_finite(&value, "NaiveDateTime")?;
+        check_time_zone(
+            &value,
+            "NaiveDateTime",
+            false,
+            "use `DateTime<Utc>` or `DateTime<FixedOffset>`",
+        )?;
+
+        decode_naive(value)
     }
 }
 
+// decodes `TIMESTAMP` and `TIMESTAMPTZ` alike
+fn decode_naive(value: PgValueRef<'_>) -> Result<NaiveDateTime, BoxDynError> {
+    Ok(match value.format() {
+        PgValueFormat::Binary => {
+            // TIMESTAMP is encoded as the microseconds since the epoch
+            let us = Decode::<Postgres>::decode(value)?;
+            postgres_epoch_datetime()
+                .checked_add_signed(Duration::microseconds(us))
+                .ok_or_else(|| format!("timestamp out of range for NaiveDateTime: {us}us"))?
+        }
+
+        PgValueFormat::Text => {
+            let s = value.as_str()?;
+            NaiveDateTime::parse_from_str(
+                &signed_year(s),
+                if s.contains('+') {
+                    // Contains a time-zone specifier
+                    // This is given for timestamptz for some reason
+                    // Postgres already guarantees this to always be UTC
+                    "%Y-%m-%d %H:%M:%S%.f%#z"
+                } else {
+                    "%Y-%m-%d %H:%M:%S%.f"
+                },
+            )?
+        }
+    })
+}
+
 impl<Tz: TimeZone> Encode<'_, Postgres> for DateTime<Tz> {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
         Encode::<Postgres>::encode(self.naive_utc(), buf)
@@ -100,16 +118,24 @@ impl<'r> Decode<'r, Postgres> for DateTime<Utc> {
 
 impl<'r> Decode<'r, Postgres> for DateTime<FixedOffset> {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        check_finite(&value, "DateTime<Tz>")?;
+        check_time_zone(
+            &value,
+            "DateTime<Tz>",
+            true,
+            "use `NaiveDateTime`, or `AssumeUtc` if it holds UTC",
+        )?;
+
         Ok(match value.format() {
             PgValueFormat::Binary => {
-                let naive = <NaiveDateTime as Decode<Postgres>>::decode(value)?;
+                let naive = decode_naive(value)?;
                 Utc.fix().from_utc_datetime(&naive)
             }
 
             PgValueFormat::Text => {
                 let s = value.as_str()?;
                 DateTime::parse_from_str(
-                    s,
+                    &signed_year(s),
                     if s.contains('+') || s.contains('-') {
                         // Contains a time-zone specifier
                         // This is given for timestamptz for some reason
@@ -124,6 +150,44 @@ impl<'r> Decode<'r, Postgres> for DateTime<FixedOffset> {
     }
 }
 
+impl Type<Postgres> for AssumeUtc {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMP
+    }
+}
+
+impl PgHasArrayType for AssumeUtc {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMP_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for AssumeUtc {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        Encode::<Postgres>::encode(self.naive_utc(), buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for AssumeUtc {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        check_finite(&value, "AssumeUtc")?;
+
+        Ok(decode_naive(value)?.into())
+    }
+}
+
+// chrono only parses years with more than 4 digits if they have a sign
+fn signed_year(s: &str) -> Cow<'_, str> {
+    match s.find('-') {
+        Some(len) if len > 4 => Cow::Owned(format!("+{s}")),
+        _ => Cow::Borrowed(s),
+    }
+}
+
 #[inline]
 fn postgres_epoch_datetime() -> NaiveDateTime {
     NaiveDate::from_ymd_opt(2000, 1, 1)
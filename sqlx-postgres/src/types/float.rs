@@ -3,6 +3,7 @@ use byteorder::{BigEndian, ByteOrder};
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::types::numeric::PgNumeric;
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
@@ -10,6 +11,11 @@ impl Type<Postgres> for f32 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::FLOAT4
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        let ty = ty.domain_base();
+        *ty == PgTypeInfo::FLOAT4 || *ty == PgTypeInfo::NUMERIC
+    }
 }
 
 impl PgHasArrayType for f32 {
@@ -28,6 +34,18 @@ impl Encode<'_, Postgres> for f32 {
 
 impl Decode<'_, Postgres> for f32 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        if value.type_info == PgTypeInfo::NUMERIC {
+            let numeric = numeric_to_string(value)?;
+            let float: f32 = numeric.parse()?;
+
+            // finite values that round to infinity don't fit
+            if float.is_infinite() && !numeric.ends_with("Infinity") {
+                return Err("NUMERIC value is out of range for `f32`".into());
+            }
+
+            return Ok(float);
+        }
+
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_f32(value.as_bytes()?),
             PgValueFormat::Text => value.as_str()?.parse()?,
@@ -39,6 +57,11 @@ impl Type<Postgres> for f64 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::FLOAT8
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        let ty = ty.domain_base();
+        *ty == PgTypeInfo::FLOAT8 || *ty == PgTypeInfo::NUMERIC
+    }
 }
 
 impl PgHasArrayType for f64 {
@@ -57,9 +80,50 @@ impl Encode<'_, Postgres> for f64 {
 
 impl Decode<'_, Postgres> for f64 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        if value.type_info == PgTypeInfo::NUMERIC {
+            let numeric = numeric_to_string(value)?;
+            let float: f64 = numeric.parse()?;
+
+            // finite values that round to infinity don't fit
+            if float.is_infinite() && !numeric.ends_with("Infinity") {
+                return Err("NUMERIC value is out of range for `f64`".into());
+            }
+
+            return Ok(float);
+        }
+
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_f64(value.as_bytes()?),
             PgValueFormat::Text => value.as_str()?.parse()?,
         })
     }
 }
+
+// the sign field of `'Infinity'` and `'-Infinity'`, `NUMERIC_PINF` and `NUMERIC_NINF` in numeric.c
+const NUMERIC_PINF: [u8; 2] = [0xD0, 0x00];
+const NUMERIC_NINF: [u8; 2] = [0xF0, 0x00];
+
+/// The decimal representation of a `NUMERIC` value, which is parsed into a float so that
+/// it's rounded correctly.
+fn numeric_to_string(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+    match value.format() {
+        PgValueFormat::Binary => {
+            let buf = value.as_bytes()?;
+
+            if buf.len() < 8 {
+                return Err(
+                    format!("expected at least 8 bytes for NUMERIC, got {}", buf.len()).into(),
+                );
+            }
+
+            // infinities were added in Postgres 14, and aren't represented by `PgNumeric`
+            Ok(match [buf[4], buf[5]] {
+                NUMERIC_PINF => "Infinity".to_owned(),
+                NUMERIC_NINF => "-Infinity".to_owned(),
+                _ => PgNumeric::decode(buf)?.to_string(),
+            })
+        }
+
+        PgValueFormat::Text => Ok(value.as_str()?.to_owned()),
+    }
+}
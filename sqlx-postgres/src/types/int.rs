@@ -1,5 +1,5 @@
 use byteorder::{BigEndian, ByteOrder};
-use std::num::{NonZeroI16, NonZeroI32, NonZeroI64};
+use std::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8};
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
@@ -109,6 +109,11 @@ impl Type<Postgres> for i32 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT4
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        // `int_decode` handles any width up to 8 bytes, so narrower integers widen losslessly
+        [PgTypeInfo::INT2, PgTypeInfo::INT4].contains(ty.domain_base())
+    }
 }
 
 impl PgHasArrayType for i32 {
@@ -135,6 +140,10 @@ impl Type<Postgres> for i64 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT8
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        [PgTypeInfo::INT2, PgTypeInfo::INT4, PgTypeInfo::INT8].contains(ty.domain_base())
+    }
 }
 
 impl PgHasArrayType for i64 {
@@ -157,6 +166,12 @@ impl Decode<'_, Postgres> for i64 {
     }
 }
 
+impl PgHasArrayType for NonZeroI8 {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::CHAR_ARRAY
+    }
+}
+
 impl PgHasArrayType for NonZeroI16 {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT2_ARRAY
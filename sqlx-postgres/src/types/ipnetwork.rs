@@ -21,6 +21,7 @@ impl Type<Postgres> for IpNetwork {
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
+        let ty = ty.domain_base();
         *ty == PgTypeInfo::CIDR || *ty == PgTypeInfo::INET
     }
 }
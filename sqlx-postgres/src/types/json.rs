@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::value::RawValue as JsonRawValue;
 use serde_json::Value as JsonValue;
 pub(crate) use sqlx_core::types::{Json, Type};
+use std::collections::{BTreeMap, HashMap};
 
 // <https://www.postgresql.org/docs/12/datatype-json.html>
 
@@ -20,6 +21,7 @@ impl<T> Type<Postgres> for Json<T> {
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
+        let ty = ty.domain_base();
         *ty == PgTypeInfo::JSON || *ty == PgTypeInfo::JSONB
     }
 }
@@ -44,6 +46,26 @@ impl PgHasArrayType for JsonValue {
     }
 }
 
+impl PgHasArrayType for BTreeMap<String, JsonValue> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::JSONB_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        array_compatible::<JsonValue>(ty)
+    }
+}
+
+impl<S> PgHasArrayType for HashMap<String, JsonValue, S> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::JSONB_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        array_compatible::<JsonValue>(ty)
+    }
+}
+
 impl PgHasArrayType for JsonRawValue {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::JSONB_ARRAY
@@ -254,15 +254,29 @@ impl FromStr for PgLQueryLevel {
             match bytes[0] {
                 b'*' => {
                     if bytes.len() > 1 {
-                        let parts = s[2..s.len() - 1].split(',').collect::<Vec<_>>();
+                        let bounds = s[1..]
+                            .strip_prefix('{')
+                            .and_then(|s| s.strip_suffix('}'))
+                            .ok_or(PgLQueryParseError::UnexpectedCharacter)?;
+
+                        // either bound may be omitted, e.g. `*{2,}` or `*{,3}`
+                        let parse_bound = |bound: &str| -> Result<Option<u16>, Self::Err> {
+                            Ok(if bound.is_empty() {
+                                None
+                            } else {
+                                Some(bound.parse()?)
+                            })
+                        };
+
+                        let parts = bounds.split(',').collect::<Vec<_>>();
                         match parts.len() {
                             1 => {
                                 let number = parts[0].parse()?;
                                 Ok(PgLQueryLevel::Star(Some(number), Some(number)))
                             }
                             2 => Ok(PgLQueryLevel::Star(
-                                Some(parts[0].parse()?),
-                                Some(parts[1].parse()?),
+                                parse_bound(parts[0])?,
+                                parse_bound(parts[1])?,
                             )),
                             _ => Err(PgLQueryParseError::UnexpectedCharacter),
                         }
@@ -315,7 +329,7 @@ fn write_variants(f: &mut Formatter<'_>, variants: &[PgLQueryVariant], not: bool
     if let Some(variant) = iter.next() {
         write!(f, "{}{}", if not { "!" } else { "" }, variant)?;
         for variant in iter {
-            write!(f, ".{variant}")?;
+            write!(f, "|{variant}")?;
         }
     }
     Ok(())
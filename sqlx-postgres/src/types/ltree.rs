@@ -8,14 +8,24 @@ use std::io::Write;
 use std::ops::Deref;
 use std::str::FromStr;
 
+/// The maximum length of a label, in bytes.
+const MAX_LABEL_LEN: usize = 255;
+
+/// The maximum number of labels in a path.
+const MAX_LABELS: usize = 65535;
+
 /// Represents ltree specific errors
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum PgLTreeParseError {
-    /// LTree labels can only contain [A-Za-z0-9_]
+    /// LTree labels can only contain [A-Za-z0-9_], and must be 1 to 255 bytes long
     #[error("ltree label contains invalid characters")]
     InvalidLtreeLabel,
 
+    /// LTree paths can have at most 65535 labels
+    #[error("ltree has more than 65535 labels")]
+    TooManyLabels,
+
     /// LTree version not supported
     #[error("ltree version not supported")]
     InvalidLtreeVersion,
@@ -30,7 +40,8 @@ impl PgLTreeLabel {
         S: Into<String>,
     {
         let label = label.into();
-        if label.len() <= 256
+        if !label.is_empty()
+            && label.len() <= MAX_LABEL_LEN
             && label
                 .bytes()
                 .all(|c| c.is_ascii_alphabetic() || c.is_ascii_digit() || c == b'_')
@@ -124,7 +135,22 @@ impl PgLTree {
         S: Into<String>,
         I: IntoIterator<Item = S>,
     {
-        labels.into_iter().map(PgLTreeLabel::new).collect()
+        let ltree: Self = labels
+            .into_iter()
+            .map(PgLTreeLabel::new)
+            .collect::<Result<_, _>>()?;
+
+        ltree.check_len()?;
+
+        Ok(ltree)
+    }
+
+    fn check_len(&self) -> Result<(), PgLTreeParseError> {
+        if self.labels.len() > MAX_LABELS {
+            return Err(PgLTreeParseError::TooManyLabels);
+        }
+
+        Ok(())
     }
 
     /// push a label to ltree
@@ -146,6 +172,14 @@ impl FromIterator<PgLTreeLabel> for PgLTree {
     }
 }
 
+impl TryFrom<Vec<String>> for PgLTree {
+    type Error = PgLTreeParseError;
+
+    fn try_from(labels: Vec<String>) -> Result<Self, Self::Error> {
+        Self::try_from_iter(labels)
+    }
+}
+
 impl IntoIterator for PgLTree {
     type Item = PgLTreeLabel;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -159,12 +193,11 @@ impl FromStr for PgLTree {
     type Err = PgLTreeParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            labels: s
-                .split('.')
-                .map(PgLTreeLabel::new)
-                .collect::<Result<Vec<_>, Self::Err>>()?,
-        })
+        if s.is_empty() {
+            return Ok(Self::new());
+        }
+
+        Self::try_from_iter(s.split('.'))
     }
 }
 
@@ -204,6 +237,8 @@ impl PgHasArrayType for PgLTree {
 
 impl Encode<'_, Postgres> for PgLTree {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.check_len()?;
+
         buf.extend(1i8.to_le_bytes());
         write!(buf, "{self}")?;
 
@@ -12,7 +12,7 @@ impl Type<Postgres> for MacAddress {
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
-        *ty == PgTypeInfo::MACADDR
+        *ty.domain_base() == PgTypeInfo::MACADDR
     }
 }
 
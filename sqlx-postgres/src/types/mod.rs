@@ -9,10 +9,11 @@
 //! | `i16`                                 | SMALLINT, SMALLSERIAL, INT2                          |
 //! | `i32`                                 | INT, SERIAL, INT4                                    |
 //! | `i64`                                 | BIGINT, BIGSERIAL, INT8                              |
-//! | `f32`                                 | REAL, FLOAT4                                         |
-//! | `f64`                                 | DOUBLE PRECISION, FLOAT8                             |
+//! | `f32`                                 | REAL, FLOAT4, NUMERIC<sup>2</sup>                    |
+//! | `f64`                                 | DOUBLE PRECISION, FLOAT8, NUMERIC<sup>2</sup>        |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR(N), TEXT, NAME, CITEXT                 |
-//! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
+//! | `char`                                | TEXT, VARCHAR, CHAR(N), "CHAR" (a single character)  |
+//! | `&[u8]`, `Vec<u8>`, `[u8; N]`         | BYTEA                                                |
 //! | `()`                                  | VOID                                                 |
 //! | [`PgInterval`]                        | INTERVAL                                             |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
@@ -29,11 +30,20 @@
 //! | [`PgPolygon`]                         | POLYGON                                              |
 //! | [`PgCircle`]                          | CIRCLE                                               |
 //! | [`PgHstore`]                          | HSTORE                                               |
+//! | [`PgXml`]                             | XML                                                  |
+//! | [`PgTsVector`]                        | TSVECTOR                                             |
+//! | [`PgTsQuery`]                         | TSQUERY                                              |
 //!
 //! <sup>1</sup> SQLx generally considers `CITEXT` to be compatible with `String`, `&str`, etc.,
 //! but this wrapper type is available for edge cases, such as `CITEXT[]` which Postgres
 //! does not consider to be compatible with `TEXT[]`.
 //!
+//! <sup>2</sup> Decoding only, e.g. for the result of `AVG()`. The value is rounded to the
+//! nearest float, so precision may be lost silently. `'NaN'` and `'Infinity'` decode to the
+//! corresponding float values, but finite values too large for the float type are an error.
+//! Floats are always sent as `FLOAT4`/`FLOAT8`; Postgres converts them if they're written to a
+//! `NUMERIC` column. Use `BigDecimal` or `Decimal` to read and write exact values.
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -61,9 +71,11 @@
 //! | `chrono::DateTime<Utc>`               | TIMESTAMPTZ                                          |
 //! | `chrono::DateTime<Local>`             | TIMESTAMPTZ                                          |
 //! | `chrono::NaiveDateTime`               | TIMESTAMP                                            |
+//! | `sqlx::types::chrono::AssumeUtc`      | TIMESTAMP (holding UTC)<sup>4</sup>                  |
 //! | `chrono::NaiveDate`                   | DATE                                                 |
 //! | `chrono::NaiveTime`                   | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
+//! | [`PgTimestamp<T>`](PgTimestamp)       | TIMESTAMP, TIMESTAMPTZ, including `'infinity'`<sup>3</sup> |
 //!
 //! ### [`time`](https://crates.io/crates/time)
 //!
@@ -76,6 +88,18 @@
 //! | `time::Date`                          | DATE                                                 |
 //! | `time::Time`                          | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
+//! | [`PgTimestamp<T>`](PgTimestamp)       | TIMESTAMP, TIMESTAMPTZ, including `'infinity'`<sup>3</sup> |
+//!
+//! <sup>3</sup> Decoding `'infinity'` or `'-infinity'` as a `chrono` or `time` timestamp is
+//! an error, as is a timestamp outside of the range of the type. Wrap the timestamp type
+//! in [`PgTimestamp`] to read and write the infinite timestamps.
+//!
+//! <sup>4</sup> By default, a `TIMESTAMP` can also be decoded as `DateTime<Utc>` or
+//! `OffsetDateTime`, assuming it holds UTC, and a `TIMESTAMPTZ` as `NaiveDateTime` or
+//! `PrimitiveDateTime`, dropping its time zone. With
+//! [`PgConnectOptions::strict_timestamps()`][crate::PgConnectOptions::strict_timestamps]
+//! both are errors, and a `TIMESTAMP` known to hold UTC has to be decoded as `AssumeUtc`
+//! (or as `PrimitiveDateTime`, calling `.assume_utc()`).
 //!
 //! ### [`uuid`](https://crates.io/crates/uuid)
 //!
@@ -125,10 +149,15 @@
 //! | [`Json<T>`]                           | JSON, JSONB                                          |
 //! | `serde_json::Value`                   | JSON, JSONB                                          |
 //! | `&serde_json::value::RawValue`        | JSON, JSONB                                          |
+//! | `HashMap<String, serde_json::Value>`  | JSON, JSONB                                          |
+//! | `BTreeMap<String, serde_json::Value>` | JSON, JSONB                                          |
 //!
 //! `Value` and `RawValue` from `serde_json` can be used for unstructured JSON data with
 //! Postgres.
 //!
+//! `HashMap` and `BTreeMap` with `String` keys can be used for JSON objects; decoding any other
+//! JSON value as one of them is an error.
+//!
 //! [`Json<T>`](crate::types::Json) can be used for structured JSON data with Postgres.
 //!
 //! # [Composite types](https://www.postgresql.org/docs/current/rowtypes.html)
@@ -158,7 +187,10 @@
 //!
 //! # Arrays
 //!
-//! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements `Type`.
+//! One-dimensional arrays are supported as `Vec<T>`, `&[T]` or `[T; N]` where `T` implements `Type`.
+//!
+//! This includes `T = Option<U>` for arrays with `NULL` elements, and references, e.g. `&[&str]`
+//! or `Vec<&String>`. An array can be bound to `= ANY($1)` to compare a value with each element.
 //!
 //! # [Enumerations](https://www.postgresql.org/docs/current/datatype-enum.html)
 //!
@@ -220,8 +252,11 @@ mod range;
 mod record;
 mod str;
 mod text;
+mod text_search;
+mod timestamp;
 mod tuple;
 mod void;
+mod xml;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 mod time_tz;
@@ -233,8 +268,12 @@ mod cube;
 
 mod geometry;
 
-#[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
-mod numeric;
+// also used by `PgValueRef::to_dynamic()` and to decode `NUMERIC` as a float
+#[cfg_attr(
+    not(any(feature = "bigdecimal", feature = "rust_decimal")),
+    allow(dead_code)
+)]
+pub(crate) mod numeric;
 
 #[cfg(feature = "rust_decimal")]
 mod rust_decimal;
@@ -280,8 +319,14 @@ pub use ltree::PgLTree;
 pub use ltree::PgLTreeLabel;
 pub use ltree::PgLTreeParseError;
 pub use money::PgMoney;
+pub use numeric::PgNumericNaNError;
 pub use oid::Oid;
 pub use range::PgRange;
+pub use text_search::{
+    PgTsLexeme, PgTsPosition, PgTsQuery, PgTsQueryItem, PgTsQueryOperator, PgTsVector, PgTsWeight,
+};
+pub use timestamp::PgTimestamp;
+pub use xml::PgXml;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
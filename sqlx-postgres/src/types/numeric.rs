@@ -1,4 +1,5 @@
 use sqlx_core::bytes::Buf;
+use std::fmt::{self, Display, Formatter};
 use std::num::Saturating;
 
 use crate::error::BoxDynError;
@@ -54,6 +55,85 @@ const SIGN_POS: u16 = 0x0000;
 const SIGN_NEG: u16 = 0x4000;
 const SIGN_NAN: u16 = 0xC000; // overflows i16 (C equivalent truncates from integer literal)
 
+/// Formats the value as Postgres does, e.g. `-12.3400` for a `NUMERIC` with a scale of 4.
+impl Display for PgNumeric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let PgNumeric::Number {
+            sign,
+            digits,
+            weight,
+            scale,
+        } = self
+        else {
+            return f.write_str("NaN");
+        };
+
+        // the base-10000 digit at `i`, where digits before or after `digits` are zero
+        let digit = |i: i32| {
+            usize::try_from(i)
+                .ok()
+                .and_then(|i| digits.get(i))
+                .copied()
+                .unwrap_or(0)
+        };
+
+        if *sign == PgNumericSign::Negative {
+            f.write_str("-")?;
+        }
+
+        let weight = i32::from(*weight);
+
+        if weight < 0 {
+            f.write_str("0")?;
+        } else {
+            write!(f, "{}", digit(0))?;
+
+            for i in 1..=weight {
+                write!(f, "{:04}", digit(i))?;
+            }
+        }
+
+        let scale = usize::try_from(*scale).unwrap_or(0);
+
+        if scale > 0 {
+            let mut fraction = String::with_capacity(scale + 3);
+            let mut i = weight + 1;
+
+            while fraction.len() < scale {
+                fraction.push_str(&format!("{:04}", digit(i)));
+                i += 1;
+            }
+
+            fraction.truncate(scale);
+            write!(f, ".{fraction}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The error returned when decoding a `NUMERIC` that is `'NaN'` as a type that can't represent it,
+/// such as `BigDecimal` or `Decimal`.
+///
+/// Decode as `Option<T>` and filter out `NaN` in SQL, e.g. with `NULLIF(x, 'NaN')`,
+/// or decode as `f64` to read it as [`f64::NAN`].
+#[derive(Debug, thiserror::Error)]
+#[error("cannot decode NUMERIC 'NaN' as `{type_name}`, which has no representation for it")]
+pub struct PgNumericNaNError {
+    type_name: &'static str,
+}
+
+impl PgNumericNaNError {
+    pub(crate) fn new(type_name: &'static str) -> Self {
+        PgNumericNaNError { type_name }
+    }
+
+    /// The name of the type the `NaN` could not be decoded as.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
 /// Possible sign values for [PgNumeric].
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u16)]
@@ -349,8 +349,12 @@ where
                 }
 
                 if !flags.contains(RangeFlags::LB_INF) {
-                    let value =
-                        T::decode(PgValueRef::get(&mut buf, value.format, element_ty.clone())?)?;
+                    let value = T::decode(PgValueRef::get(
+                        &mut buf,
+                        value.format,
+                        element_ty.clone(),
+                        value.strict_timestamps,
+                    )?)?;
 
                     start = if flags.contains(RangeFlags::LB_INC) {
                         Bound::Included(value)
@@ -360,8 +364,12 @@ where
                 }
 
                 if !flags.contains(RangeFlags::UB_INF) {
-                    let value =
-                        T::decode(PgValueRef::get(&mut buf, value.format, element_ty.clone())?)?;
+                    let value = T::decode(PgValueRef::get(
+                        &mut buf,
+                        value.format,
+                        element_ty.clone(),
+                        value.strict_timestamps,
+                    )?)?;
 
                     end = if flags.contains(RangeFlags::UB_INC) {
                         Bound::Included(value)
@@ -451,6 +459,7 @@ where
                             format: PgValueFormat::Text,
                             value: Some(element.as_bytes()),
                             row: None,
+                            strict_timestamps: value.strict_timestamps,
                         })?);
 
                         if count == 1 {
@@ -514,7 +523,7 @@ where
 fn range_compatible<E: Type<Postgres>>(ty: &PgTypeInfo) -> bool {
     // we require the declared type to be a _range_ with an
     // element type that is acceptable
-    if let PgTypeKind::Range(element) = &ty.kind() {
+    if let PgTypeKind::Range(element) = &ty.domain_base().kind() {
         return E::compatible(element);
     }
 
@@ -62,13 +62,18 @@ pub struct PgRecordDecoder<'r> {
     buf: &'r [u8],
     typ: PgTypeInfo,
     fmt: PgValueFormat,
+    strict_timestamps: bool,
     ind: usize,
+    // in the text format, a trailing NULL field leaves nothing in `buf`,
+    // so we track whether the last field has been consumed instead
+    text_done: bool,
 }
 
 impl<'r> PgRecordDecoder<'r> {
     #[doc(hidden)]
     pub fn new(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
         let fmt = value.format();
+        let strict_timestamps = value.strict_timestamps;
         let mut buf = value.as_bytes()?;
         let typ = value.type_info;
 
@@ -87,7 +92,9 @@ impl<'r> PgRecordDecoder<'r> {
             buf,
             fmt,
             typ,
+            strict_timestamps,
             ind: 0,
+            text_done: false,
         })
     }
 
@@ -96,7 +103,12 @@ impl<'r> PgRecordDecoder<'r> {
     where
         T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
     {
-        if self.buf.is_empty() {
+        let exhausted = match self.fmt {
+            PgValueFormat::Binary => self.buf.is_empty(),
+            PgValueFormat::Text => self.text_done,
+        };
+
+        if exhausted {
             return Err(format!("no field `{0}` found on record", self.ind).into());
         }
 
@@ -137,42 +149,53 @@ impl<'r> PgRecordDecoder<'r> {
 
                 self.ind += 1;
 
-                T::decode(PgValueRef::get(&mut self.buf, self.fmt, element_type)?)
+                T::decode(PgValueRef::get(
+                    &mut self.buf,
+                    self.fmt,
+                    element_type,
+                    self.strict_timestamps,
+                )?)
             }
 
             PgValueFormat::Text => {
-                let mut element = String::new();
+                // all the special characters are ASCII, so we can scan the bytes directly
+                // without splitting a multi-byte UTF-8 sequence
+                let mut element = Vec::new();
                 let mut quoted = false;
                 let mut in_quotes = false;
                 let mut in_escape = false;
-                let mut prev_ch = '\0';
+                let mut prev_ch = b'\0';
+                let mut separated = false;
 
                 while !self.buf.is_empty() {
-                    let ch = self.buf.get_u8() as char;
+                    let ch = self.buf.get_u8();
                     match ch {
                         _ if in_escape => {
                             element.push(ch);
                             in_escape = false;
                         }
 
-                        '"' if in_quotes => {
+                        b'"' if in_quotes => {
                             in_quotes = false;
                         }
 
-                        '"' => {
+                        b'"' => {
                             in_quotes = true;
                             quoted = true;
 
-                            if prev_ch == '"' {
-                                element.push('"')
+                            if prev_ch == b'"' {
+                                element.push(b'"')
                             }
                         }
 
-                        '\\' if !in_escape => {
+                        b'\\' if !in_escape => {
                             in_escape = true;
                         }
 
-                        ',' if !in_quotes => break,
+                        b',' if !in_quotes => {
+                            separated = true;
+                            break;
+                        }
 
                         _ => {
                             element.push(ch);
@@ -181,11 +204,16 @@ impl<'r> PgRecordDecoder<'r> {
                     prev_ch = ch;
                 }
 
+                // the last field is not followed by a separator
+                self.text_done = !separated;
+
+                self.ind += 1;
+
                 let buf = if element.is_empty() && !quoted {
                     // completely empty input means NULL
                     None
                 } else {
-                    Some(element.as_bytes())
+                    Some(&element[..])
                 };
 
                 // NOTE: we do not call [`accepts`] or give a chance to from a user as
@@ -198,6 +226,7 @@ impl<'r> PgRecordDecoder<'r> {
                     format: self.fmt,
                     value: buf,
                     row: None,
+                    strict_timestamps: self.strict_timestamps,
                 })
             }
         }
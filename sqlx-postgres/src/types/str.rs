@@ -3,7 +3,7 @@ use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::types::array_compatible;
 use crate::types::Type;
-use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use std::borrow::Cow;
 
 impl Type<Postgres> for str {
@@ -20,7 +20,7 @@ impl Type<Postgres> for str {
             PgTypeInfo::UNKNOWN,
             PgTypeInfo::with_name("citext"),
         ]
-        .contains(ty)
+        .contains(ty.domain_base())
     }
 }
 
@@ -146,3 +146,54 @@ impl Decode<'_, Postgres> for String {
         Ok(value.as_str()?.to_owned())
     }
 }
+
+impl Type<Postgres> for char {
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty.domain_base() == PgTypeInfo::CHAR || <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for char {
+    fn array_type_info() -> PgTypeInfo {
+        <&str as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <&str as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for char {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<Postgres>>::encode(self.encode_utf8(&mut [0; 4]), buf)
+    }
+}
+
+impl Decode<'_, Postgres> for char {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        // `"char"` is a single byte in the binary format, which we only accept if it is ASCII
+        if value.type_info == PgTypeInfo::CHAR && value.format() == PgValueFormat::Binary {
+            return match value.as_bytes()? {
+                [byte] if byte.is_ascii() => Ok(char::from(*byte)),
+                [byte] => Err(format!("\"char\" value {byte:#x} is not ASCII").into()),
+                bytes => Err(format!("expected 1 byte for \"char\", got {}", bytes.len()).into()),
+            };
+        }
+
+        let s = value.as_str()?;
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(format!(
+                "expected a single character, got {} characters",
+                s.chars().count()
+            )
+            .into()),
+        }
+    }
+}
@@ -0,0 +1,659 @@
+use std::fmt::{self, Display, Formatter, Write};
+use std::str::FromStr;
+
+use sqlx_core::bytes::Buf;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+// Bits of a `WordEntryPos` holding the position; the remaining 2 bits hold the weight.
+const POSITION_MASK: u16 = 0x3FFF;
+
+// `QueryItem` type tags in the binary format of `tsquery`
+const QI_VAL: u8 = 1;
+const QI_OPR: u8 = 2;
+
+// `QueryOperator` operator tags
+const OP_NOT: u8 = 1;
+const OP_AND: u8 = 2;
+const OP_OR: u8 = 3;
+const OP_PHRASE: u8 = 4;
+
+/// Full-text search document (`tsvector`) support for Postgres.
+///
+/// A `tsvector` is a sorted list of distinct lexemes, each with the positions (if any)
+/// where it occurred in the original document. It is typically produced by `to_tsvector()`.
+///
+/// See [the Postgres manual, Section 8.11][PG.8.11] for details on using `tsvector`.
+///
+/// [PG.8.11]: https://www.postgresql.org/docs/current/datatype-textsearch.html
+///
+/// ```rust,ignore
+/// let document: PgTsVector = sqlx::query_scalar("SELECT to_tsvector('english', 'The cats sat')")
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// assert_eq!(document.lexemes().collect::<Vec<_>>(), ["cat", "sat"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgTsVector {
+    lexemes: Vec<PgTsLexeme>,
+}
+
+/// A single lexeme in a [`PgTsVector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PgTsLexeme {
+    /// The normalized word.
+    pub lexeme: String,
+
+    /// Where the word occurred in the document; empty if the positions were stripped.
+    pub positions: Vec<PgTsPosition>,
+}
+
+/// The position of a [`PgTsLexeme`] in a document, with its weight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PgTsPosition {
+    /// The 1-based position of the word; Postgres limits positions to 16383.
+    pub position: u16,
+
+    /// The weight assigned with `setweight()`.
+    pub weight: PgTsWeight,
+}
+
+/// The weight of a lexeme position in a [`PgTsVector`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PgTsWeight {
+    A,
+    B,
+    C,
+    /// The default weight, which is not shown in the text format.
+    #[default]
+    D,
+}
+
+/// Full-text search query (`tsquery`) support for Postgres.
+///
+/// SQLx does not parse the query syntax; construct queries on the server with
+/// `to_tsquery()`, `plainto_tsquery()`, etc. A decoded `tsquery` can be bound again unchanged.
+///
+/// Only the binary format is supported, so this type cannot be decoded from queries
+/// that use the simple query protocol, such as [`raw_sql()`][sqlx_core::raw_sql::raw_sql].
+///
+/// See [the Postgres manual, Section 8.11][PG.8.11] for details on using `tsquery`.
+///
+/// [PG.8.11]: https://www.postgresql.org/docs/current/datatype-textsearch.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgTsQuery {
+    items: Vec<PgTsQueryItem>,
+}
+
+/// An item of a [`PgTsQuery`], which are stored in prefix (Polish) notation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PgTsQueryItem {
+    /// A lexeme to match.
+    Operand {
+        value: String,
+        /// Bitmask of the weights to match (`A` = 8, `B` = 4, `C` = 2, `D` = 1), or 0 for any.
+        weights: u8,
+        /// `true` if this is a prefix match (`value:*`).
+        prefix: bool,
+    },
+    /// An operator applying to the following one (`!`) or two items.
+    Operator(PgTsQueryOperator),
+}
+
+/// An operator in a [`PgTsQuery`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PgTsQueryOperator {
+    /// `!`
+    Not,
+    /// `&`
+    And,
+    /// `|`
+    Or,
+    /// `<->`, or `<N>` where `N` is the distance.
+    Phrase { distance: u16 },
+}
+
+impl PgTsVector {
+    /// Returns the number of distinct lexemes.
+    pub fn len(&self) -> usize {
+        self.lexemes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lexemes.is_empty()
+    }
+
+    /// Iterate over the lexemes along with their positions.
+    pub fn iter(&self) -> std::slice::Iter<'_, PgTsLexeme> {
+        self.lexemes.iter()
+    }
+
+    /// Iterate over just the lexemes.
+    pub fn lexemes(&self) -> impl Iterator<Item = &str> + '_ {
+        self.lexemes.iter().map(|lexeme| lexeme.lexeme.as_str())
+    }
+
+    fn from_bytes(mut buf: &[u8]) -> Result<Self, BoxDynError> {
+        let len = read_i32(&mut buf)?;
+        let len =
+            usize::try_from(len).map_err(|_| format!("PgTsVector: length out of range: {len}"))?;
+
+        let mut lexemes = Vec::with_capacity(std::cmp::min(len, buf.len()));
+
+        for _ in 0..len {
+            let lexeme = read_cstring(&mut buf)?;
+
+            let npos = read_u16(&mut buf)?;
+            let positions = (0..npos)
+                .map(|_| read_u16(&mut buf).map(PgTsPosition::from_bits))
+                .collect::<Result<_, _>>()?;
+
+            lexemes.push(PgTsLexeme { lexeme, positions });
+        }
+
+        if !buf.is_empty() {
+            tracing::warn!("{} unread bytes at the end of TSVECTOR value", buf.len());
+        }
+
+        Ok(Self { lexemes })
+    }
+}
+
+impl PgTsPosition {
+    fn from_bits(bits: u16) -> Self {
+        let weight = match bits >> 14 {
+            3 => PgTsWeight::A,
+            2 => PgTsWeight::B,
+            1 => PgTsWeight::C,
+            _ => PgTsWeight::D,
+        };
+
+        Self {
+            position: bits & POSITION_MASK,
+            weight,
+        }
+    }
+
+    fn to_bits(self) -> Result<u16, BoxDynError> {
+        if self.position > POSITION_MASK {
+            return Err(format!("PgTsVector: position out of range: {}", self.position).into());
+        }
+
+        let weight: u16 = match self.weight {
+            PgTsWeight::A => 3,
+            PgTsWeight::B => 2,
+            PgTsWeight::C => 1,
+            PgTsWeight::D => 0,
+        };
+
+        Ok(weight << 14 | self.position)
+    }
+}
+
+impl PgTsQuery {
+    /// Returns the items of this query in prefix (Polish) notation.
+    pub fn items(&self) -> &[PgTsQueryItem] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterate over the lexemes this query matches against.
+    pub fn operands(&self) -> impl Iterator<Item = &str> + '_ {
+        self.items.iter().filter_map(|item| match item {
+            PgTsQueryItem::Operand { value, .. } => Some(value.as_str()),
+            PgTsQueryItem::Operator(_) => None,
+        })
+    }
+
+    fn from_bytes(mut buf: &[u8]) -> Result<Self, BoxDynError> {
+        let len = read_i32(&mut buf)?;
+        let len =
+            usize::try_from(len).map_err(|_| format!("PgTsQuery: length out of range: {len}"))?;
+
+        let mut items = Vec::with_capacity(std::cmp::min(len, buf.len()));
+
+        for _ in 0..len {
+            let item = match read_u8(&mut buf)? {
+                QI_VAL => {
+                    let weights = read_u8(&mut buf)?;
+                    let prefix = read_u8(&mut buf)? != 0;
+                    let value = read_cstring(&mut buf)?;
+
+                    PgTsQueryItem::Operand {
+                        value,
+                        weights,
+                        prefix,
+                    }
+                }
+
+                QI_OPR => PgTsQueryItem::Operator(match read_u8(&mut buf)? {
+                    OP_NOT => PgTsQueryOperator::Not,
+                    OP_AND => PgTsQueryOperator::And,
+                    OP_OR => PgTsQueryOperator::Or,
+                    OP_PHRASE => PgTsQueryOperator::Phrase {
+                        distance: read_u16(&mut buf)?,
+                    },
+                    other => return Err(format!("PgTsQuery: unknown operator {other}").into()),
+                }),
+
+                other => return Err(format!("PgTsQuery: unknown item type {other}").into()),
+            };
+
+            items.push(item);
+        }
+
+        if !buf.is_empty() {
+            tracing::warn!("{} unread bytes at the end of TSQUERY value", buf.len());
+        }
+
+        Ok(Self { items })
+    }
+}
+
+impl<'a> IntoIterator for &'a PgTsVector {
+    type Item = &'a PgTsLexeme;
+    type IntoIter = std::slice::Iter<'a, PgTsLexeme>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lexemes.iter()
+    }
+}
+
+impl IntoIterator for PgTsVector {
+    type Item = PgTsLexeme;
+    type IntoIter = std::vec::IntoIter<PgTsLexeme>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lexemes.into_iter()
+    }
+}
+
+impl FromIterator<PgTsLexeme> for PgTsVector {
+    /// Postgres sorts the lexemes and merges duplicates when the value is bound.
+    fn from_iter<T: IntoIterator<Item = PgTsLexeme>>(iter: T) -> Self {
+        Self {
+            lexemes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl FromStr for PgTsVector {
+    type Err = BoxDynError;
+
+    /// Parse the text format of `tsvector`, e.g. `'cat':2A 'sat':3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars().peekable();
+        let mut lexemes = Vec::new();
+
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+            let Some(&first) = chars.peek() else {
+                break;
+            };
+
+            let mut lexeme = String::new();
+
+            if first == '\'' {
+                chars.next();
+
+                loop {
+                    match chars.next() {
+                        Some('\'') if chars.next_if_eq(&'\'').is_some() => lexeme.push('\''),
+                        Some('\'') => break,
+                        Some('\\') => lexeme.push(chars.next().ok_or("unexpected end after `\\`")?),
+                        Some(c) => lexeme.push(c),
+                        None => return Err(format!("unterminated lexeme: {lexeme:?}").into()),
+                    }
+                }
+            } else {
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ':') {
+                    if c == '\\' {
+                        lexeme.push(chars.next().ok_or("unexpected end after `\\`")?);
+                    } else {
+                        lexeme.push(c);
+                    }
+                }
+            }
+
+            let mut positions = Vec::new();
+
+            if chars.next_if_eq(&':').is_some() {
+                loop {
+                    let mut digits = String::new();
+
+                    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                        digits.push(c);
+                    }
+
+                    let position = digits.parse().map_err(|_| {
+                        format!("invalid position {digits:?} for lexeme {lexeme:?}")
+                    })?;
+
+                    let weight = match chars.next_if(|c| matches!(c, 'A'..='D' | 'a'..='d')) {
+                        Some('A' | 'a') => PgTsWeight::A,
+                        Some('B' | 'b') => PgTsWeight::B,
+                        Some('C' | 'c') => PgTsWeight::C,
+                        _ => PgTsWeight::D,
+                    };
+
+                    positions.push(PgTsPosition { position, weight });
+
+                    if chars.next_if_eq(&',').is_none() {
+                        break;
+                    }
+                }
+            }
+
+            lexemes.push(PgTsLexeme { lexeme, positions });
+        }
+
+        Ok(Self { lexemes })
+    }
+}
+
+impl Display for PgTsVector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, lexeme) in self.lexemes.iter().enumerate() {
+            if i > 0 {
+                f.write_char(' ')?;
+            }
+
+            write!(f, "{lexeme}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for PgTsLexeme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_char('\'')?;
+
+        for c in self.lexeme.chars() {
+            match c {
+                '\'' => f.write_str("''")?,
+                '\\' => f.write_str("\\\\")?,
+                c => f.write_char(c)?,
+            }
+        }
+
+        f.write_char('\'')?;
+
+        for (i, position) in self.positions.iter().enumerate() {
+            f.write_char(if i == 0 { ':' } else { ',' })?;
+            write!(f, "{}", position.position)?;
+
+            match position.weight {
+                PgTsWeight::A => f.write_char('A')?,
+                PgTsWeight::B => f.write_char('B')?,
+                PgTsWeight::C => f.write_char('C')?,
+                PgTsWeight::D => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Type<Postgres> for PgTsVector {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TSVECTOR
+    }
+}
+
+impl PgHasArrayType for PgTsVector {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TSVECTOR_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgTsVector {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let len = i32::try_from(self.lexemes.len())
+            .map_err(|_| format!("PgTsVector length out of range: {}", self.lexemes.len()))?;
+
+        buf.extend_from_slice(&len.to_be_bytes());
+
+        for lexeme in &self.lexemes {
+            write_cstring(buf, &lexeme.lexeme)?;
+
+            let npos = u16::try_from(lexeme.positions.len()).map_err(|_| {
+                format!(
+                    "PgTsVector: too many positions for lexeme {:?}: {}",
+                    lexeme.lexeme,
+                    lexeme.positions.len()
+                )
+            })?;
+
+            buf.extend_from_slice(&npos.to_be_bytes());
+
+            for position in &lexeme.positions {
+                buf.extend_from_slice(&position.to_bits()?.to_be_bytes());
+            }
+        }
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgTsVector {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Text => value.as_str()?.parse(),
+            PgValueFormat::Binary => Self::from_bytes(value.as_bytes()?),
+        }
+    }
+}
+
+impl Type<Postgres> for PgTsQuery {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TSQUERY
+    }
+}
+
+impl PgHasArrayType for PgTsQuery {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TSQUERY_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgTsQuery {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let len = i32::try_from(self.items.len())
+            .map_err(|_| format!("PgTsQuery length out of range: {}", self.items.len()))?;
+
+        buf.extend_from_slice(&len.to_be_bytes());
+
+        for item in &self.items {
+            match item {
+                PgTsQueryItem::Operand {
+                    value,
+                    weights,
+                    prefix,
+                } => {
+                    buf.extend_from_slice(&[QI_VAL, *weights, u8::from(*prefix)]);
+                    write_cstring(buf, value)?;
+                }
+
+                PgTsQueryItem::Operator(operator) => {
+                    let tag = match operator {
+                        PgTsQueryOperator::Not => OP_NOT,
+                        PgTsQueryOperator::And => OP_AND,
+                        PgTsQueryOperator::Or => OP_OR,
+                        PgTsQueryOperator::Phrase { .. } => OP_PHRASE,
+                    };
+
+                    buf.extend_from_slice(&[QI_OPR, tag]);
+
+                    if let PgTsQueryOperator::Phrase { distance } = operator {
+                        buf.extend_from_slice(&distance.to_be_bytes());
+                    }
+                }
+            }
+        }
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgTsQuery {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Text => {
+                Err("PgTsQuery can only be decoded from the binary format; use `query()` instead of `raw_sql()`".into())
+            }
+            PgValueFormat::Binary => Self::from_bytes(value.as_bytes()?),
+        }
+    }
+}
+
+fn ensure_remaining(buf: &[u8], len: usize) -> Result<(), BoxDynError> {
+    if buf.len() < len {
+        return Err(format!("expected {len} bytes, got {}", buf.len()).into());
+    }
+
+    Ok(())
+}
+
+fn read_u8(buf: &mut &[u8]) -> Result<u8, BoxDynError> {
+    ensure_remaining(buf, 1)?;
+    Ok(buf.get_u8())
+}
+
+fn read_u16(buf: &mut &[u8]) -> Result<u16, BoxDynError> {
+    ensure_remaining(buf, 2)?;
+    Ok(buf.get_u16())
+}
+
+fn read_i32(buf: &mut &[u8]) -> Result<i32, BoxDynError> {
+    ensure_remaining(buf, 4)?;
+    Ok(buf.get_i32())
+}
+
+fn read_cstring(buf: &mut &[u8]) -> Result<String, BoxDynError> {
+    let end = buf
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or("expected a NUL-terminated string")?;
+
+    let s = std::str::from_utf8(&buf[..end])?.to_owned();
+    buf.advance(end + 1);
+
+    Ok(s)
+}
+
+fn write_cstring(buf: &mut PgArgumentBuffer, s: &str) -> Result<(), BoxDynError> {
+    if s.contains('\0') {
+        return Err(format!("text search lexeme {s:?} contains a NUL byte").into());
+    }
+
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // to_tsvector('english', 'The cats sat') = 'cat':2 'sat':3
+    const CATS_SAT: &[u8] = b"\x00\x00\x00\x02cat\x00\x00\x01\x00\x02sat\x00\x00\x01\x00\x03";
+
+    // 'fat' & 'rat':*B
+    const FAT_AND_RAT: &[u8] = b"\x00\x00\x00\x03\x02\x02\x01\x04\x01rat\x00\x01\x00\x00fat\x00";
+
+    fn encode<'q, T: Encode<'q, Postgres>>(value: &T) -> Vec<u8> {
+        let mut buf = PgArgumentBuffer::default();
+        let _ = value.encode_by_ref(&mut buf).unwrap();
+        buf.to_vec()
+    }
+
+    #[test]
+    fn tsvector_decodes_binary() {
+        let vector = PgTsVector::from_bytes(CATS_SAT).unwrap();
+
+        assert_eq!(vector.lexemes().collect::<Vec<_>>(), ["cat", "sat"]);
+        assert_eq!(
+            vector.iter().next().unwrap().positions,
+            [PgTsPosition {
+                position: 2,
+                weight: PgTsWeight::D
+            }]
+        );
+        assert_eq!(encode(&vector), CATS_SAT);
+    }
+
+    #[test]
+    fn tsvector_round_trips_weights() {
+        let vector: PgTsVector = "'a':1A,2B 'b':3C,16383 'c'".parse().unwrap();
+
+        assert_eq!(PgTsVector::from_bytes(&encode(&vector)).unwrap(), vector);
+        assert_eq!(vector.to_string(), "'a':1A,2B 'b':3C,16383 'c'");
+    }
+
+    #[test]
+    fn tsvector_parses_text() {
+        let vector: PgTsVector = r"'it''s' 'back\\slash':4 plain:5b".parse().unwrap();
+
+        assert_eq!(
+            vector.lexemes().collect::<Vec<_>>(),
+            ["it's", "back\\slash", "plain"]
+        );
+        assert_eq!(vector.to_string(), r"'it''s' 'back\\slash':4 'plain':5B");
+
+        assert!("'unterminated".parse::<PgTsVector>().is_err());
+        assert!("'a':x".parse::<PgTsVector>().is_err());
+    }
+
+    #[test]
+    fn tsvector_rejects_truncated_binary() {
+        assert!(PgTsVector::from_bytes(&CATS_SAT[..CATS_SAT.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn tsquery_round_trips_binary() {
+        let query = PgTsQuery::from_bytes(FAT_AND_RAT).unwrap();
+
+        assert_eq!(
+            query.items(),
+            [
+                PgTsQueryItem::Operator(PgTsQueryOperator::And),
+                PgTsQueryItem::Operand {
+                    value: "rat".into(),
+                    weights: 4,
+                    prefix: true
+                },
+                PgTsQueryItem::Operand {
+                    value: "fat".into(),
+                    weights: 0,
+                    prefix: false
+                },
+            ]
+        );
+        assert_eq!(query.operands().collect::<Vec<_>>(), ["rat", "fat"]);
+        assert_eq!(encode(&query), FAT_AND_RAT);
+    }
+
+    #[test]
+    fn tsquery_round_trips_phrase() {
+        // 'a' <2> 'b'
+        let bytes = b"\x00\x00\x00\x03\x02\x04\x00\x02\x01\x00\x00b\x00\x01\x00\x00a\x00";
+        let query = PgTsQuery::from_bytes(bytes).unwrap();
+
+        assert_eq!(
+            query.items()[0],
+            PgTsQueryItem::Operator(PgTsQueryOperator::Phrase { distance: 2 })
+        );
+        assert_eq!(encode(&query), bytes);
+    }
+}
@@ -2,6 +2,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::types::time::PG_EPOCH;
+use crate::types::timestamp::{check_finite, check_time_zone};
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use std::borrow::Cow;
@@ -53,41 +54,57 @@ impl Encode<'_, Postgres> for PrimitiveDateTime {
 
 impl<'r> Decode<'r, Postgres> for PrimitiveDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(match value.format() {
-            PgValueFormat::Binary => {
-                // TIMESTAMP is encoded as the microseconds since the epoch
-                let us = Decode::<Postgres>::decode(value)?;
-                PG_EPOCH.midnight() + Duration::microseconds(us)
-            }
+        check_finite(&value, "PrimitiveDateTime")?;
+        check_time_zone(&value, "PrimitiveDateTime", false, "use `OffsetDateTime`")?;
 
-            PgValueFormat::Text => {
-                let s = value.as_str()?;
-
-                // If there is no decimal point we need to add one.
-                let s = if s.contains('.') {
-                    Cow::Borrowed(s)
-                } else {
-                    Cow::Owned(format!("{s}.0"))
-                };
-
-                // Contains a time-zone specifier
-                // This is given for timestamptz for some reason
-                // Postgres already guarantees this to always be UTC
-                if s.contains('+') {
-                    PrimitiveDateTime::parse(&s, &format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond][offset_hour]"))?
-                } else {
-                    PrimitiveDateTime::parse(
-                        &s,
-                        &format_description!(
-                            "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
-                        ),
-                    )?
-                }
-            }
-        })
+        decode_primitive(value)
     }
 }
 
+// decodes `TIMESTAMP` and `TIMESTAMPTZ` alike
+fn decode_primitive(value: PgValueRef<'_>) -> Result<PrimitiveDateTime, BoxDynError> {
+    Ok(match value.format() {
+        PgValueFormat::Binary => {
+            // TIMESTAMP is encoded as the microseconds since the epoch
+            let us = Decode::<Postgres>::decode(value)?;
+            PG_EPOCH
+                .midnight()
+                .checked_add(Duration::microseconds(us))
+                .ok_or_else(|| format!("timestamp out of range for PrimitiveDateTime: {us}us"))?
+        }
+
+        PgValueFormat::Text => {
+            let s = value.as_str()?;
+
+            // If there is no decimal point we need to add one.
+            let s = if s.contains('.') {
+                Cow::Borrowed(s)
+            } else {
+                Cow::Owned(format!("{s}.0"))
+            };
+
+            // Contains a time-zone specifier
+            // This is given for timestamptz for some reason
+            // Postgres already guarantees this to always be UTC
+            if s.contains('+') {
+                PrimitiveDateTime::parse(
+                    &s,
+                    &format_description!(
+                        "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond][offset_hour]"
+                    ),
+                )?
+            } else {
+                PrimitiveDateTime::parse(
+                    &s,
+                    &format_description!(
+                        "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
+                    ),
+                )?
+            }
+        }
+    })
+}
+
 impl Encode<'_, Postgres> for OffsetDateTime {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
         let utc = self.to_offset(offset!(UTC));
@@ -103,6 +120,14 @@ impl Encode<'_, Postgres> for OffsetDateTime {
 
 impl<'r> Decode<'r, Postgres> for OffsetDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(<PrimitiveDateTime as Decode<Postgres>>::decode(value)?.assume_utc())
+        check_finite(&value, "OffsetDateTime")?;
+        check_time_zone(
+            &value,
+            "OffsetDateTime",
+            true,
+            "use `PrimitiveDateTime`, and `.assume_utc()` if it holds UTC",
+        )?;
+
+        Ok(decode_primitive(value)?.assume_utc())
     }
 }
@@ -0,0 +1,153 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use std::mem;
+
+/// A `TIMESTAMP` or `TIMESTAMPTZ` that may be `'infinity'` or `'-infinity'`.
+///
+/// The timestamp types of `chrono` and `time` can't represent the infinite timestamps,
+/// so decoding one as them is an error. Wrap them in this type to read and write
+/// the infinite timestamps as well, e.g. `PgTimestamp<DateTime<Utc>>` for `TIMESTAMPTZ`.
+///
+/// The variants are ordered the same as the timestamps in Postgres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PgTimestamp<T> {
+    /// `'-infinity'`, earlier than all other timestamps.
+    NegInfinity,
+    /// A finite timestamp.
+    Value(T),
+    /// `'infinity'`, later than all other timestamps.
+    Infinity,
+}
+
+impl<T> PgTimestamp<T> {
+    /// Returns the timestamp if it's finite.
+    pub fn value(self) -> Option<T> {
+        match self {
+            PgTimestamp::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<T> for PgTimestamp<T> {
+    fn from(value: T) -> Self {
+        PgTimestamp::Value(value)
+    }
+}
+
+impl<T: Type<Postgres>> Type<Postgres> for PgTimestamp<T> {
+    fn type_info() -> PgTypeInfo {
+        T::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+impl<T: PgHasArrayType> PgHasArrayType for PgTimestamp<T> {
+    fn array_type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        T::array_compatible(ty)
+    }
+}
+
+impl<'q, T: Encode<'q, Postgres>> Encode<'q, Postgres> for PgTimestamp<T> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        match self {
+            PgTimestamp::NegInfinity => Encode::<Postgres>::encode(NEG_INFINITY, buf),
+            PgTimestamp::Value(value) => value.encode_by_ref(buf),
+            PgTimestamp::Infinity => Encode::<Postgres>::encode(INFINITY, buf),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        match self {
+            PgTimestamp::Value(value) => value.size_hint(),
+            _ => mem::size_of::<i64>(),
+        }
+    }
+}
+
+impl<'r, T: Decode<'r, Postgres>> Decode<'r, Postgres> for PgTimestamp<T> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match infinity(&value)? {
+            Some(false) => PgTimestamp::NegInfinity,
+            Some(true) => PgTimestamp::Infinity,
+            None => PgTimestamp::Value(T::decode(value)?),
+        })
+    }
+}
+
+// `TIMESTAMP` and `TIMESTAMPTZ` are encoded as the microseconds since the epoch,
+// with the extremes standing for the infinite timestamps, `DT_NOBEGIN` and `DT_NOEND` in
+// timestamp.h
+const NEG_INFINITY: i64 = i64::MIN;
+const INFINITY: i64 = i64::MAX;
+
+/// Returns `Some(true)` if `value` is `'infinity'`, `Some(false)` if it's `'-infinity'`,
+/// or `None` if it's finite.
+fn infinity(value: &PgValueRef<'_>) -> Result<Option<bool>, BoxDynError> {
+    Ok(match value.format() {
+        PgValueFormat::Binary => match value.as_bytes()?.try_into().map(i64::from_be_bytes) {
+            Ok(NEG_INFINITY) => Some(false),
+            Ok(INFINITY) => Some(true),
+            _ => None,
+        },
+
+        PgValueFormat::Text => match value.as_str()? {
+            "-infinity" => Some(false),
+            "infinity" => Some(true),
+            _ => None,
+        },
+    })
+}
+
+/// Returns an error naming the timestamp if `value` is infinite,
+/// as `type_name` can't represent it.
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) fn check_finite(value: &PgValueRef<'_>, type_name: &str) -> Result<(), BoxDynError> {
+    match infinity(value)? {
+        Some(infinite) => Err(format!(
+            "cannot decode '{}' as `{type_name}`; use `PgTimestamp<{type_name}>` \
+             to decode infinite timestamps",
+            if infinite { "infinity" } else { "-infinity" },
+        )
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// With [`PgConnectOptions::strict_timestamps()`][crate::PgConnectOptions::strict_timestamps],
+/// returns an error if `value` is a `TIMESTAMP` and `type_name` has a time zone, or if it is
+/// a `TIMESTAMPTZ` and `type_name` doesn't; `suggestion` names the types to use instead.
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) fn check_time_zone(
+    value: &PgValueRef<'_>,
+    type_name: &str,
+    with_time_zone: bool,
+    suggestion: &str,
+) -> Result<(), BoxDynError> {
+    if !value.strict_timestamps {
+        return Ok(());
+    }
+
+    match (&value.type_info.0, with_time_zone) {
+        (crate::type_info::PgType::Timestamp, true) => Err(format!(
+            "cannot decode TIMESTAMP as `{type_name}` because it has no time zone; {suggestion}"
+        )
+        .into()),
+        (crate::type_info::PgType::Timestamptz, false) => Err(format!(
+            "cannot decode TIMESTAMPTZ as `{type_name}` without dropping its time zone; \
+             {suggestion}"
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
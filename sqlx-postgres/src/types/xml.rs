@@ -0,0 +1,82 @@
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// XML document or content fragment (`xml`) support for Postgres.
+///
+/// The value is transferred as text; it is not parsed or validated on the client.
+/// Postgres will reject malformed XML when it is bound as a parameter.
+///
+/// See [the Postgres manual, Section 8.13][PG.8.13] for details on using `xml`.
+///
+/// [PG.8.13]: https://www.postgresql.org/docs/current/datatype-xml.html
+///
+/// ### Note: Server Support Required
+/// Postgres must be built with `libxml` (`--with-libxml`) for the `xml` type to be usable.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgXml(pub String);
+
+impl Type<Postgres> for PgXml {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::XML
+    }
+}
+
+impl Deref for PgXml {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_str()
+    }
+}
+
+impl From<String> for PgXml {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PgXml> for String {
+    fn from(value: PgXml) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for PgXml {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PgXml(s.parse()?))
+    }
+}
+
+impl Display for PgXml {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PgHasArrayType for PgXml {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::XML_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgXml {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        // the binary format of `xml` is the text of the document in the client encoding
+        <&str as Encode<Postgres>>::encode(&**self, buf)
+    }
+}
+
+impl Decode<'_, Postgres> for PgXml {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(PgXml(value.as_str()?.to_owned()))
+    }
+}
@@ -1,6 +1,11 @@
+use crate::decode::Decode;
 use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::type_info::PgType;
+use crate::types::numeric::PgNumeric;
+use crate::types::Oid;
 use crate::{PgTypeInfo, Postgres};
 use sqlx_core::bytes::{Buf, Bytes};
+use sqlx_core::value::{DynamicValue, ToDynamicValue};
 pub(crate) use sqlx_core::value::{Value, ValueRef};
 use std::borrow::Cow;
 use std::str::from_utf8;
@@ -19,6 +24,8 @@ pub struct PgValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: PgTypeInfo,
     pub(crate) format: PgValueFormat,
+    // see `PgConnectOptions::strict_timestamps()`
+    pub(crate) strict_timestamps: bool,
 }
 
 /// Implementation of [`Value`] for PostgreSQL.
@@ -27,6 +34,7 @@ pub struct PgValue {
     pub(crate) value: Option<Bytes>,
     pub(crate) type_info: PgTypeInfo,
     pub(crate) format: PgValueFormat,
+    pub(crate) strict_timestamps: bool,
 }
 
 impl<'r> PgValueRef<'r> {
@@ -34,6 +42,7 @@ impl<'r> PgValueRef<'r> {
         buf: &mut &'r [u8],
         format: PgValueFormat,
         ty: PgTypeInfo,
+        strict_timestamps: bool,
     ) -> Result<Self, String> {
         let element_len = buf.get_i32();
 
@@ -54,6 +63,7 @@ impl<'r> PgValueRef<'r> {
             row: None,
             type_info: ty,
             format,
+            strict_timestamps,
         })
     }
 
@@ -83,6 +93,7 @@ impl Value for PgValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            strict_timestamps: self.strict_timestamps,
         }
     }
 
@@ -111,6 +122,7 @@ impl<'r> ValueRef<'r> for PgValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            strict_timestamps: self.strict_timestamps,
         }
     }
 
@@ -122,3 +134,95 @@ impl<'r> ValueRef<'r> for PgValueRef<'r> {
         self.value.is_none()
     }
 }
+
+/// Integers, `OID`, floating-point numbers, `BOOL`, `BYTEA` and string types are converted to
+/// the matching variant, and with the `chrono` feature, `DATE`, `TIME`, `TIMESTAMP` and
+/// `TIMESTAMPTZ` as well.
+///
+/// `NUMERIC`, `UUID`, `JSON` and `JSONB` are converted to [`DynamicValue::Text`], as are values
+/// of all other types that are received in the text format, e.g. from
+/// [`raw_sql()`][sqlx_core::raw_sql::raw_sql]. Other values in the binary format can't be
+/// converted.
+impl ToDynamicValue for PgValueRef<'_> {
+    fn to_dynamic(&self) -> Result<DynamicValue, BoxDynError> {
+        if self.is_null() {
+            return Ok(DynamicValue::Null);
+        }
+
+        let value = self.clone();
+
+        Ok(match self.type_info.0 {
+            PgType::Bool => DynamicValue::Bool(decode(value)?),
+            PgType::Char => DynamicValue::Int(decode::<i8>(value)?.into()),
+            PgType::Int2 => DynamicValue::Int(decode::<i16>(value)?.into()),
+            PgType::Int4 => DynamicValue::Int(decode::<i32>(value)?.into()),
+            PgType::Int8 => DynamicValue::Int(decode(value)?),
+            PgType::Oid => DynamicValue::UInt(decode::<Oid>(value)?.0.into()),
+            PgType::Float4 => DynamicValue::Double(decode::<f32>(value)?.into()),
+            PgType::Float8 => DynamicValue::Double(decode(value)?),
+            PgType::Bytea => DynamicValue::Bytes(decode(value)?),
+
+            #[cfg(feature = "chrono")]
+            PgType::Date => DynamicValue::Date(decode(value)?),
+            #[cfg(feature = "chrono")]
+            PgType::Time => DynamicValue::Time(decode(value)?),
+            #[cfg(feature = "chrono")]
+            PgType::Timestamp => DynamicValue::DateTime(decode(value)?),
+            #[cfg(feature = "chrono")]
+            PgType::Timestamptz => DynamicValue::DateTimeUtc(decode(value)?),
+
+            _ if self.format == PgValueFormat::Text => {
+                DynamicValue::Text(self.as_str()?.to_owned())
+            }
+
+            PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name | PgType::Unknown => {
+                DynamicValue::Text(self.as_str()?.to_owned())
+            }
+            PgType::Numeric => DynamicValue::Text(PgNumeric::decode(self.as_bytes()?)?.to_string()),
+            PgType::Uuid => {
+                let bytes = self.as_bytes()?;
+
+                if bytes.len() != 16 {
+                    return Err(format!("expected 16 bytes for a UUID, got {}", bytes.len()).into());
+                }
+
+                let hex =
+                    |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+
+                DynamicValue::Text(format!(
+                    "{}-{}-{}-{}-{}",
+                    hex(&bytes[..4]),
+                    hex(&bytes[4..6]),
+                    hex(&bytes[6..8]),
+                    hex(&bytes[8..10]),
+                    hex(&bytes[10..])
+                ))
+            }
+            PgType::Json => DynamicValue::Text(self.as_str()?.to_owned()),
+            PgType::Jsonb => {
+                // the binary format of JSONB is a version number followed by the text
+                let bytes = self.as_bytes()?;
+
+                match bytes.split_first() {
+                    Some((1, text)) => DynamicValue::Text(from_utf8(text)?.to_owned()),
+                    _ => return Err("unsupported JSONB format version".into()),
+                }
+            }
+
+            _ => {
+                return Err(format!(
+                    "values of type {} in the binary format can't be converted to a \
+                     `DynamicValue`; use `raw_sql()` to receive them in the text format",
+                    self.type_info
+                )
+                .into());
+            }
+        })
+    }
+}
+
+/// [`Decode::decode()`] for a value of this driver, whose database can't be inferred
+/// from the value type alone.
+fn decode<'r, T: Decode<'r, Postgres>>(value: PgValueRef<'r>) -> Result<T, BoxDynError> {
+    T::decode(value)
+}
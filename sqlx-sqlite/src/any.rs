@@ -16,6 +16,7 @@ use sqlx_core::connection::{ConnectOptions, Connection};
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
+use sqlx_core::placeholders::PlaceholderSyntax;
 use sqlx_core::transaction::TransactionManager;
 use std::pin::pin;
 
@@ -26,6 +27,10 @@ impl AnyConnectionBackend for SqliteConnection {
         <Sqlite as Database>::NAME
     }
 
+    fn placeholder_syntax(&self) -> PlaceholderSyntax {
+        <Sqlite as Database>::PLACEHOLDER_SYNTAX
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -84,7 +89,7 @@ impl AnyConnectionBackend for SqliteConnection {
 
         Box::pin(
             self.worker
-                .execute(query, args, self.row_channel_size, persistent, None)
+                .execute(query, args, self.row_channel_size, persistent, None, true)
                 .map_ok(flume::Receiver::into_stream)
                 .try_flatten_stream()
                 .map(
@@ -108,7 +113,14 @@ impl AnyConnectionBackend for SqliteConnection {
         Box::pin(async move {
             let mut stream = pin!(
                 self.worker
-                    .execute(query, args, self.row_channel_size, persistent, Some(1))
+                    .execute(
+                        query,
+                        args,
+                        self.row_channel_size,
+                        persistent,
+                        Some(1),
+                        true
+                    )
                     .map_ok(flume::Receiver::into_stream)
                     .await?
             );
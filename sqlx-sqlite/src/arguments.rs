@@ -4,7 +4,9 @@ use crate::statement::StatementHandle;
 use crate::Sqlite;
 use atoi::atoi;
 use libsqlite3_sys::SQLITE_OK;
+use sqlx_core::arguments::{ArgumentSummary, ArgumentsSummary};
 use std::borrow::Cow;
+use std::cmp;
 
 pub(crate) use sqlx_core::arguments::*;
 use sqlx_core::error::BoxDynError;
@@ -72,51 +74,98 @@ impl<'q> Arguments<'q> for SqliteArguments<'q> {
     fn len(&self) -> usize {
         self.values.len()
     }
+
+    fn summary(&self) -> ArgumentsSummary {
+        let arguments = self
+            .values
+            .iter()
+            .map(|value| {
+                let (type_name, size) = match value {
+                    SqliteArgumentValue::Null => ("NULL", None),
+                    SqliteArgumentValue::Text(text) => ("TEXT", Some(text.len())),
+                    SqliteArgumentValue::Blob(blob) => ("BLOB", Some(blob.len())),
+                    SqliteArgumentValue::Double(_) => ("REAL", Some(8)),
+                    SqliteArgumentValue::Int(_) => ("INTEGER", Some(4)),
+                    SqliteArgumentValue::Int64(_) => ("INTEGER", Some(8)),
+                };
+
+                ArgumentSummary {
+                    type_name: type_name.to_owned(),
+                    size,
+                }
+            })
+            .collect();
+
+        ArgumentsSummary::new::<Sqlite>(arguments)
+    }
 }
 
 impl SqliteArguments<'_> {
-    pub(super) fn bind(&self, handle: &mut StatementHandle, offset: usize) -> Result<usize, Error> {
+    /// Bind the parameters of `handle`, where the first anonymous (`?`) parameter
+    /// is the argument at `offset`.
+    ///
+    /// Returns the number of anonymous parameters and the highest (1-based) index of
+    /// an argument that was bound.
+    pub(super) fn bind(
+        &self,
+        handle: &mut StatementHandle,
+        offset: usize,
+    ) -> Result<(usize, usize), Error> {
+        let cnt = handle.bind_parameter_count();
+
+        // check that there are enough arguments before binding any of them
         let mut arg_i = offset;
-        // for handle in &statement.handles {
+        let mut highest = 0;
 
-        let cnt = handle.bind_parameter_count();
+        for param_i in 1..=cnt {
+            highest = cmp::max(highest, parameter_index(handle, param_i, &mut arg_i)?);
+        }
+
+        if highest > self.values.len() {
+            return Err(Error::ParameterCountMismatch {
+                expected: highest,
+                got: self.values.len(),
+            });
+        }
+
+        let mut arg_i = offset;
 
         for param_i in 1..=cnt {
-            // figure out the index of this bind parameter into our argument tuple
-            let n: usize = if let Some(name) = handle.bind_parameter_name(param_i) {
-                if let Some(name) = name.strip_prefix('?') {
-                    // parameter should have the form ?NNN
-                    atoi(name.as_bytes()).expect("parameter of the form ?NNN")
-                } else if let Some(name) = name.strip_prefix('$') {
-                    // parameter should have the form $NNN
-                    atoi(name.as_bytes()).ok_or_else(|| {
-                        err_protocol!(
-                            "parameters with non-integer names are not currently supported: {}",
-                            name
-                        )
-                    })?
-                } else {
-                    return Err(err_protocol!("unsupported SQL parameter format: {}", name));
-                }
-            } else {
-                arg_i += 1;
-                arg_i
-            };
-
-            if n > self.values.len() {
-                // SQLite treats unbound variables as NULL
-                // we reproduce this here
-                // If you are reading this and think this should be an error, open an issue and we can
-                // discuss configuring this somehow
-                // Note that the query macros have a different way of enforcing
-                // argument arity
-                break;
-            }
+            let n = parameter_index(handle, param_i, &mut arg_i)?;
 
             self.values[n - 1].bind(handle, param_i)?;
         }
 
-        Ok(arg_i - offset)
+        Ok((arg_i - offset, highest))
+    }
+}
+
+/// Figure out the index of a bind parameter into our argument tuple.
+///
+/// `arg_i` is the index of the last anonymous parameter, and is incremented if this is one.
+fn parameter_index(
+    handle: &StatementHandle,
+    param_i: usize,
+    arg_i: &mut usize,
+) -> Result<usize, Error> {
+    let Some(name) = handle.bind_parameter_name(param_i) else {
+        *arg_i += 1;
+        return Ok(*arg_i);
+    };
+
+    if let Some(name) = name.strip_prefix('?') {
+        // parameter should have the form ?NNN
+        Ok(atoi(name.as_bytes()).expect("parameter of the form ?NNN"))
+    } else if let Some(name) = name.strip_prefix('$') {
+        // parameter should have the form $NNN
+        atoi(name.as_bytes()).ok_or_else(|| {
+            err_protocol!(
+                "parameters with non-integer names are not currently supported: {}",
+                name
+            )
+        })
+    } else {
+        Err(err_protocol!("unsupported SQL parameter format: {}", name))
     }
 }
 
@@ -0,0 +1,301 @@
+use super::handle::ConnectionHandle;
+use super::worker::WorkerLocker;
+use super::ConnectionState;
+use crate::{error::Error, SqliteConnection, SqliteError};
+use libsqlite3_sys::{
+    sqlite3, sqlite3_backup_finish, sqlite3_backup_init, sqlite3_backup_pagecount,
+    sqlite3_backup_remaining, sqlite3_backup_step, sqlite3_open_v2, SQLITE_BUSY, SQLITE_DONE,
+    SQLITE_LOCKED, SQLITE_OK, SQLITE_OPEN_CREATE, SQLITE_OPEN_NOMUTEX, SQLITE_OPEN_READWRITE,
+};
+use std::ffi::CString;
+use std::fmt::{self, Debug, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait before retrying a step that failed because the source was locked.
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+impl SqliteConnection {
+    /// Copy the `main` database of this connection to the database file at `destination`,
+    /// using the [online backup API].
+    ///
+    /// The file is created if it does not exist, and overwritten if it does.
+    ///
+    /// The backup is copied on the connection's worker thread, a few pages at a time
+    /// (see [`SqliteBackup::pages_per_step()`]). Other connections may write to the database
+    /// between steps, in which case SQLite restarts the backup on the next step, so the result
+    /// is always a consistent snapshot. Writes through this connection can't happen while the
+    /// backup runs, as it is borrowed mutably.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::SqliteConnection) -> sqlx::Result<()> {
+    /// conn.backup("backup.db")
+    ///     .pages_per_step(100)
+    ///     .progress(|remaining, total| println!("{remaining} of {total} pages left"))
+    ///     .run()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [online backup API]: https://www.sqlite.org/backup.html
+    pub fn backup(&mut self, destination: impl AsRef<Path>) -> SqliteBackup<'_> {
+        SqliteBackup::new(self, Destination::File(destination.as_ref().to_owned()))
+    }
+
+    /// Copy the `main` database of this connection to the `main` database of `destination`,
+    /// replacing its contents.
+    ///
+    /// `destination` can't be used while the backup runs. See [`Self::backup()`] for details.
+    pub fn backup_to_connection<'c>(
+        &'c mut self,
+        destination: &'c mut SqliteConnection,
+    ) -> SqliteBackup<'c> {
+        SqliteBackup::new(self, Destination::Connection(destination))
+    }
+}
+
+/// An online backup of a database, created by [`SqliteConnection::backup()`]
+/// or [`SqliteConnection::backup_to_connection()`].
+#[must_use = "the backup does not start until `.run()` is awaited"]
+pub struct SqliteBackup<'c> {
+    conn: &'c mut SqliteConnection,
+    destination: Destination<'c>,
+    params: BackupParams,
+}
+
+enum Destination<'c> {
+    File(PathBuf),
+    Connection(&'c mut SqliteConnection),
+}
+
+pub(crate) enum BackupDestination {
+    File(CString),
+    Connection(WorkerLocker),
+}
+
+pub(crate) struct BackupParams {
+    pages_per_step: i32,
+    busy_timeout: Duration,
+    progress: Option<Box<dyn FnMut(u32, u32) + Send>>,
+}
+
+impl<'c> SqliteBackup<'c> {
+    fn new(conn: &'c mut SqliteConnection, destination: Destination<'c>) -> Self {
+        SqliteBackup {
+            conn,
+            destination,
+            params: BackupParams {
+                pages_per_step: 100,
+                busy_timeout: Duration::from_secs(5),
+                progress: None,
+            },
+        }
+    }
+
+    /// Set the number of pages to copy in each step. Defaults to 100.
+    ///
+    /// The source database is locked while a step runs, so smaller steps let other
+    /// connections write sooner. Passing `0` copies the whole database in one step.
+    pub fn pages_per_step(mut self, pages: u32) -> Self {
+        self.params.pages_per_step = match pages {
+            0 => -1,
+            pages => i32::try_from(pages).unwrap_or(i32::MAX),
+        };
+        self
+    }
+
+    /// Set how long to keep retrying a step while the source database is locked
+    /// by another connection. Defaults to 5 seconds.
+    ///
+    /// The backup fails with `SQLITE_BUSY` or `SQLITE_LOCKED` once this elapses.
+    pub fn busy_timeout(mut self, timeout: Duration) -> Self {
+        self.params.busy_timeout = timeout;
+        self
+    }
+
+    /// Call `callback` with the number of pages remaining and the total number of pages
+    /// after every step.
+    ///
+    /// The callback is called on the connection's worker thread, so it should return quickly.
+    pub fn progress<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(u32, u32) + Send + 'static,
+    {
+        self.params.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Run the backup to completion.
+    ///
+    /// # Errors
+    /// * [`Error::Io`] if the destination path is not valid UTF-8 or contains a zero byte.
+    /// * [`Error::Database`] if the destination can't be opened or written to,
+    ///   or the source stays locked for longer than the [busy timeout][Self::busy_timeout].
+    pub async fn run(self) -> Result<(), Error> {
+        let destination = match self.destination {
+            Destination::File(path) => BackupDestination::File(path_to_cstring(&path)?),
+            Destination::Connection(conn) => BackupDestination::Connection(conn.worker.locker()),
+        };
+
+        self.conn.worker.backup(destination, self.params).await
+    }
+}
+
+impl Debug for SqliteBackup<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let destination: &dyn Debug = match &self.destination {
+            Destination::File(path) => path,
+            Destination::Connection(conn) => conn,
+        };
+
+        f.debug_struct("SqliteBackup")
+            .field("destination", destination)
+            .field("pages_per_step", &self.params.pages_per_step)
+            .field("busy_timeout", &self.params.busy_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+fn path_to_cstring(path: &Path) -> Result<CString, Error> {
+    let path = path.to_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "backup destination path must be valid UTF-8",
+        )
+    })?;
+
+    CString::new(path).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "backup destination path must not contain nul bytes",
+        )
+        .into()
+    })
+}
+
+pub(crate) fn backup(
+    conn: &mut ConnectionState,
+    destination: BackupDestination,
+    params: BackupParams,
+) -> Result<(), Error> {
+    match destination {
+        BackupDestination::File(path) => {
+            let dest = open(&path)?;
+
+            // SAFETY: we have exclusive access to both database handles
+            unsafe { run(conn.handle.as_ptr(), dest.as_ptr(), params) }
+        }
+
+        BackupDestination::Connection(locker) => {
+            let dest = locker.lock()?;
+
+            // SAFETY: the destination's worker thread is waiting for us to release its lock
+            unsafe { run(conn.handle.as_ptr(), dest.handle.as_ptr(), params) }
+        }
+    }
+}
+
+fn open(path: &CString) -> Result<ConnectionHandle, Error> {
+    let mut handle = ptr::null_mut();
+
+    // <https://www.sqlite.org/c3ref/open.html>
+    let status = unsafe {
+        sqlite3_open_v2(
+            path.as_ptr(),
+            &mut handle,
+            SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE | SQLITE_OPEN_NOMUTEX,
+            ptr::null(),
+        )
+    };
+
+    if handle.is_null() {
+        return Err(Error::Io(io::Error::new(
+            io::ErrorKind::OutOfMemory,
+            "SQLite is unable to allocate memory to hold the sqlite3 object",
+        )));
+    }
+
+    // SAFE: tested for NULL just above; the handle is closed on drop
+    let mut handle = unsafe { ConnectionHandle::new(handle) };
+
+    if status != SQLITE_OK {
+        return Err(Error::Database(Box::new(handle.expect_error())));
+    }
+
+    Ok(handle)
+}
+
+/// Copy `src` to `dest` with `sqlite3_backup_*()`.
+///
+/// <https://www.sqlite.org/c3ref/backup_finish.html>
+///
+/// # Safety
+/// Both handles must be valid and not in use by any other thread.
+unsafe fn run(
+    src: *mut sqlite3,
+    dest: *mut sqlite3,
+    mut params: BackupParams,
+) -> Result<(), Error> {
+    let backup = sqlite3_backup_init(dest, c"main".as_ptr(), src, c"main".as_ptr());
+
+    if backup.is_null() {
+        return Err(Error::Database(Box::new(SqliteError::new(dest))));
+    }
+
+    let mut busy_since = None;
+
+    let status = loop {
+        let status = sqlite3_backup_step(backup, params.pages_per_step);
+
+        match status & 0xFF {
+            SQLITE_OK | SQLITE_DONE => {
+                busy_since = None;
+
+                if let Some(progress) = &mut params.progress {
+                    let remaining = sqlite3_backup_remaining(backup);
+                    let total = sqlite3_backup_pagecount(backup);
+
+                    progress(
+                        u32::try_from(remaining).unwrap_or(0),
+                        u32::try_from(total).unwrap_or(0),
+                    );
+                }
+
+                if status == SQLITE_DONE {
+                    break status;
+                }
+            }
+
+            // the source (or, for `SQLITE_BUSY`, the destination) is locked by another
+            // connection; the step can be retried later
+            SQLITE_BUSY | SQLITE_LOCKED => {
+                let busy_since = *busy_since.get_or_insert_with(Instant::now);
+
+                if busy_since.elapsed() >= params.busy_timeout {
+                    break status;
+                }
+
+                thread::sleep(BUSY_RETRY_DELAY);
+            }
+
+            _ => break status,
+        }
+    };
+
+    // releases the locks held by the backup, and sets the error on `dest` if a step failed
+    sqlite3_backup_finish(backup);
+
+    match status & 0xFF {
+        SQLITE_DONE => Ok(()),
+        SQLITE_BUSY | SQLITE_LOCKED => {
+            Err(Error::Database(Box::new(SqliteError::from_code(status))))
+        }
+        _ => Err(Error::Database(Box::new(
+            SqliteError::try_new(dest).unwrap_or_else(|| SqliteError::from_code(status)),
+        ))),
+    }
+}
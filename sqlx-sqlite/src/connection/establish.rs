@@ -1,7 +1,9 @@
 use crate::connection::handle::ConnectionHandle;
+use crate::connection::worker::SharedWorkers;
+use crate::connection::write_lock::{SharedWriteLock, WriteLock};
 use crate::connection::LogSettings;
 use crate::connection::{ConnectionState, Statements};
-use crate::error::Error;
+use crate::error::{DatabaseError, Error};
 use crate::{SqliteConnectOptions, SqliteError};
 use libsqlite3_sys::{
     sqlite3, sqlite3_busy_timeout, sqlite3_db_config, sqlite3_extended_result_codes, sqlite3_free,
@@ -17,6 +19,7 @@ use std::io;
 use std::os::raw::c_int;
 use std::ptr::{addr_of_mut, null, null_mut};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 // This was originally `AtomicU64` but that's not supported on MIPS (or PowerPC):
@@ -50,12 +53,22 @@ pub struct EstablishParams {
     extensions: IndexMap<CString, Option<CString>>,
     pub(crate) thread_name: String,
     pub(crate) command_channel_size: usize,
+    pub(crate) shared_workers: Option<Arc<SharedWorkers>>,
+    write_lock: Option<Arc<SharedWriteLock>>,
     #[cfg(feature = "regexp")]
     register_regexp_function: bool,
 }
 
 impl EstablishParams {
     pub fn from_options(options: &SqliteConnectOptions) -> Result<Self, Error> {
+        if options.shared_workers.is_some() && options.shared_cache {
+            // a connection waiting for a table lock held by another connection on its thread
+            // would wait forever, as shared-cache locks don't honor the busy timeout
+            return Err(Error::Configuration(
+                "`shared_worker_threads` cannot be used with `shared_cache`".into(),
+            ));
+        }
+
         let mut filename = options
             .filename
             .to_str()
@@ -159,6 +172,8 @@ impl EstablishParams {
             extensions,
             thread_name: (options.thread_name)(thread_id as u64),
             command_channel_size: options.command_channel_size,
+            shared_workers: options.shared_workers.clone(),
+            write_lock: options.write_lock.clone(),
             #[cfg(feature = "regexp")]
             register_regexp_function: options.register_regexp_function,
         })
@@ -237,17 +252,27 @@ impl EstablishParams {
                 };
 
                 if status != SQLITE_OK {
-                    let mut e = handle.expect_error();
+                    // the error is not recorded on the connection, so `expect_error()` would panic
+                    let e = SqliteError::from_code(status);
 
                     // SAFETY: We become responsible for any memory allocation at `&error`, so test
                     // for null and take an RAII version for returns
-                    if !error_msg.is_null() {
-                        e = e.with_message(unsafe {
-                            let msg = CStr::from_ptr(error_msg).to_string_lossy().into();
+                    let msg = if !error_msg.is_null() {
+                        unsafe {
+                            let msg = CStr::from_ptr(error_msg).to_string_lossy().into_owned();
                             sqlite3_free(error_msg as *mut c_void);
                             msg
-                        });
-                    }
+                        }
+                    } else {
+                        e.message().to_owned()
+                    };
+
+                    // include the extension in the message, as several may be loaded
+                    let e = e.with_message(format!(
+                        "failed to load extension {:?}: {msg}",
+                        ext.0.to_string_lossy()
+                    ));
+
                     return Err(Error::Database(Box::new(e)));
                 }
             } // Preempt any hypothetical security issues arising from leaving ENABLE_LOAD_EXTENSION
@@ -296,6 +321,10 @@ impl EstablishParams {
             preupdate_hook_callback: None,
             commit_hook_callback: None,
             rollback_hook_callback: None,
+            write_lock: self
+                .write_lock
+                .clone()
+                .map(|shared| WriteLock::new(shared, self.busy_timeout)),
         })
     }
 }
@@ -1,31 +1,60 @@
+use crate::connection::write_lock::WriteLock;
 use crate::connection::{ConnectionHandle, ConnectionState};
 use crate::error::Error;
 use crate::logger::QueryLogger;
-use crate::statement::{StatementHandle, VirtualStatement};
+use crate::metrics::QueryMetrics;
+use crate::statement::VirtualStatement;
 use crate::{SqliteArguments, SqliteQueryResult, SqliteRow};
 use sqlx_core::Either;
+use std::cmp;
 
 pub struct ExecuteIter<'a> {
     handle: &'a mut ConnectionHandle,
     statement: &'a mut VirtualStatement,
     logger: QueryLogger<'a>,
+    metrics: Option<QueryMetrics>,
+    write_lock: Option<&'a mut WriteLock>,
     args: Option<SqliteArguments<'a>>,
 
     /// since a `VirtualStatement` can encompass multiple actual statements,
     /// this keeps track of the number of arguments so far
     args_used: usize,
 
+    /// the highest index of an argument that was bound so far, including `?NNN` parameters
+    args_highest: usize,
+
     goto_next: bool,
 }
 
+/// Execute `query`, reporting it to the [`Metrics`][sqlx_core::metrics::Metrics]
+/// of the connection if `metered`.
+///
+/// Queries issued by the driver itself, such as the ones describing a statement,
+/// are not `metered`.
 pub(crate) fn iter<'a>(
     conn: &'a mut ConnectionState,
     query: &'a str,
     args: Option<SqliteArguments<'a>>,
     persistent: bool,
+    metered: bool,
 ) -> Result<ExecuteIter<'a>, Error> {
+    let metrics = if metered {
+        QueryMetrics::start(&conn.log_settings)
+    } else {
+        None
+    };
+
     // fetch the cached statement or allocate a new one
-    let statement = conn.statements.get(query, persistent)?;
+    let statement = match conn.statements.get(query, persistent) {
+        Ok(statement) => statement,
+        Err(e) => {
+            if let Some(metrics) = metrics {
+                metrics.finish(Err(&e));
+            }
+
+            return Err(e);
+        }
+    };
 
     let logger = QueryLogger::new(query, conn.log_settings.clone());
 
@@ -33,26 +62,15 @@ pub(crate) fn iter<'a>(
         handle: &mut conn.handle,
         statement,
         logger,
+        metrics,
+        write_lock: conn.write_lock.as_mut(),
         args,
         args_used: 0,
+        args_highest: 0,
         goto_next: true,
     })
 }
 
-fn bind(
-    statement: &mut StatementHandle,
-    arguments: &Option<SqliteArguments<'_>>,
-    offset: usize,
-) -> Result<usize, Error> {
-    let mut n = 0;
-
-    if let Some(arguments) = arguments {
-        n = arguments.bind(statement, offset)?;
-    }
-
-    Ok(n)
-}
-
 impl ExecuteIter<'_> {
     pub fn finish(&mut self) -> Result<(), Error> {
         for res in self {
@@ -61,21 +79,85 @@ impl ExecuteIter<'_> {
 
         Ok(())
     }
-}
 
-impl Iterator for ExecuteIter<'_> {
-    type Item = Result<Either<SqliteQueryResult, SqliteRow>, Error>;
+    /// Skip any remaining rows of the current statement and move on to the next one.
+    pub fn skip_rows(&mut self) {
+        if let Some(statement) = self.statement.current() {
+            // the last step returned a row, so this cannot fail
+            statement.handle.reset().ok();
+        }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let statement = if self.goto_next {
+        self.goto_next = true;
+    }
+
+    /// Bind the arguments for the current statement.
+    fn bind(&mut self) -> Result<(), Error> {
+        let Some(arguments) = &self.args else {
+            return Ok(());
+        };
+
+        let is_last = self.statement.is_last();
+
+        let Some(statement) = self.statement.current() else {
+            return Ok(());
+        };
+
+        let (args_used, highest) = arguments.bind(statement.handle, self.args_used)?;
+
+        self.args_used += args_used;
+        self.args_highest = cmp::max(self.args_highest, highest);
+
+        if is_last {
+            self.check_unused_arguments()?;
+        }
+
+        Ok(())
+    }
+
+    /// Return an error if there are more arguments than parameters in the statements.
+    ///
+    /// The statements are prepared one at a time, as one may depend on the ones before it,
+    /// so this can't be checked before the last one, when the others have already run.
+    fn check_unused_arguments(&self) -> Result<(), Error> {
+        let Some(arguments) = &self.args else {
+            return Ok(());
+        };
+
+        let expected = cmp::max(self.args_used, self.args_highest);
+
+        if expected < arguments.values.len() {
+            return Err(Error::ParameterCountMismatch {
+                expected,
+                got: arguments.values.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn step(&mut self) -> Option<Result<Either<SqliteQueryResult, SqliteRow>, Error>> {
+        if self.goto_next {
             let statement = match self.statement.prepare_next(self.handle) {
                 Ok(Some(statement)) => statement,
-                Ok(None) => return None,
+                // this is also checked before executing the last statement, if we could tell
+                // which one that is
+                Ok(None) => return self.check_unused_arguments().err().map(Err),
                 Err(e) => return Some(Err(e)),
             };
 
             self.goto_next = false;
 
+            // hold the write lock until the statement is done, or the transaction if one is open;
+            // unlike `BEGIN`, `BEGIN IMMEDIATE` and `BEGIN EXCLUSIVE` aren't read-only
+            // as they start a write transaction, so they take the lock as well
+            if let Some(write_lock) = &mut self.write_lock {
+                if !statement.handle.read_only() {
+                    if let Err(e) = write_lock.acquire() {
+                        return Some(Err(e));
+                    }
+                }
+            }
+
             // sanity check: ensure the VM is reset and the bindings are cleared
             if let Err(e) = statement.handle.reset() {
                 return Some(Err(e.into()));
@@ -83,15 +165,12 @@ impl Iterator for ExecuteIter<'_> {
 
             statement.handle.clear_bindings();
 
-            match bind(statement.handle, &self.args, self.args_used) {
-                Ok(args_used) => self.args_used += args_used,
-                Err(e) => return Some(Err(e)),
+            if let Err(e) = self.bind() {
+                return Some(Err(e));
             }
+        }
 
-            statement
-        } else {
-            self.statement.current()?
-        };
+        let statement = self.statement.current()?;
 
         match statement.handle.step() {
             Ok(true) => {
@@ -123,6 +202,35 @@ impl Iterator for ExecuteIter<'_> {
     }
 }
 
+impl Iterator for ExecuteIter<'_> {
+    type Item = Result<Either<SqliteQueryResult, SqliteRow>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.step();
+
+        match &item {
+            Some(Ok(Either::Right(_))) => {
+                if let Some(metrics) = &mut self.metrics {
+                    metrics.increment_rows_returned();
+                }
+            }
+            Some(Ok(Either::Left(_))) => {}
+            Some(Err(e)) => {
+                if let Some(metrics) = self.metrics.take() {
+                    metrics.finish(Err(e));
+                }
+            }
+            None => {
+                if let Some(metrics) = self.metrics.take() {
+                    metrics.finish(Ok(()));
+                }
+            }
+        }
+
+        item
+    }
+}
+
 impl Drop for ExecuteIter<'_> {
     fn drop(&mut self) {
         self.statement.reset().ok();
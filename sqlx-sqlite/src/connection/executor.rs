@@ -10,45 +10,61 @@ use sqlx_core::executor::{Execute, Executor};
 use sqlx_core::Either;
 use std::{future, pin::pin};
 
-impl<'c> Executor<'c> for &'c mut SqliteConnection {
-    type Database = Sqlite;
+impl SqliteConnection {
+    /// Queries issued with the returned executor are not reported to the
+    /// [`Metrics`][sqlx_core::metrics::Metrics] of the connection.
+    ///
+    /// This is used for the queries the driver issues itself, such as the `PRAGMA`s
+    /// executed on connect.
+    pub(crate) fn unmetered(&mut self) -> Unmetered<'_> {
+        Unmetered(self)
+    }
 
-    fn fetch_many<'e, 'q, E>(
-        self,
+    fn fetch_many_with<'e, 'q, E>(
+        &'e mut self,
         mut query: E,
+        metered: bool,
     ) -> BoxStream<'e, Result<Either<SqliteQueryResult, SqliteRow>, Error>>
     where
-        'c: 'e,
-        E: Execute<'q, Self::Database>,
+        E: Execute<'q, Sqlite>,
         'q: 'e,
         E: 'q,
     {
-        let sql = query.sql();
         let arguments = match query.take_arguments().map_err(Error::Encode) {
             Ok(arguments) => arguments,
             Err(error) => return stream::once(future::ready(Err(error))).boxed(),
         };
         let persistent = query.persistent() && arguments.is_some();
 
+        // the future owns the query, as the SQL may be borrowed from it
         Box::pin(
-            self.worker
-                .execute(sql, arguments, self.row_channel_size, persistent, None)
-                .map_ok(flume::Receiver::into_stream)
-                .try_flatten_stream(),
+            async move {
+                self.worker
+                    .execute(
+                        query.sql(),
+                        arguments,
+                        self.row_channel_size,
+                        persistent,
+                        None,
+                        metered,
+                    )
+                    .await
+            }
+            .map_ok(flume::Receiver::into_stream)
+            .try_flatten_stream(),
         )
     }
 
-    fn fetch_optional<'e, 'q, E>(
-        self,
+    fn fetch_optional_with<'e, 'q, E>(
+        &'e mut self,
         mut query: E,
+        metered: bool,
     ) -> BoxFuture<'e, Result<Option<SqliteRow>, Error>>
     where
-        'c: 'e,
-        E: Execute<'q, Self::Database>,
+        E: Execute<'q, Sqlite>,
         'q: 'e,
         E: 'q,
     {
-        let sql = query.sql();
         let arguments = match query.take_arguments().map_err(Error::Encode) {
             Ok(arguments) => arguments,
             Err(error) => return future::ready(Err(error)).boxed(),
@@ -58,19 +74,59 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         Box::pin(async move {
             let mut stream = pin!(self
                 .worker
-                .execute(sql, arguments, self.row_channel_size, persistent, Some(1))
+                .execute(
+                    query.sql(),
+                    arguments,
+                    self.row_channel_size,
+                    persistent,
+                    Some(1),
+                    metered,
+                )
                 .map_ok(flume::Receiver::into_stream)
                 .try_flatten_stream());
 
+            // the remaining statements are still executed, and may fail
+            let mut ret = None;
             while let Some(res) = stream.try_next().await? {
                 if let Either::Right(row) = res {
-                    return Ok(Some(row));
+                    ret.get_or_insert(row);
                 }
             }
 
-            Ok(None)
+            Ok(ret)
         })
     }
+}
+
+/// See [`SqliteConnection::unmetered()`].
+#[derive(Debug)]
+pub(crate) struct Unmetered<'c>(&'c mut SqliteConnection);
+
+impl<'c> Executor<'c> for &'c mut SqliteConnection {
+    type Database = Sqlite;
+
+    fn fetch_many<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<SqliteQueryResult, SqliteRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_many_with(query, true)
+    }
+
+    fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<SqliteRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_optional_with(query, true)
+    }
 
     fn prepare_with<'e, 'q: 'e>(
         self,
@@ -98,3 +154,49 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         Box::pin(self.worker.describe(sql))
     }
 }
+
+impl<'c> Executor<'c> for Unmetered<'c> {
+    type Database = Sqlite;
+
+    fn fetch_many<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<SqliteQueryResult, SqliteRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.0.fetch_many_with(query, false)
+    }
+
+    fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<SqliteRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.0.fetch_optional_with(query, false)
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [SqliteTypeInfo],
+    ) -> BoxFuture<'e, Result<SqliteStatement<'q>, Error>>
+    where
+        'c: 'e,
+    {
+        self.0.prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<Sqlite>, Error>>
+    where
+        'c: 'e,
+    {
+        self.0.describe(sql)
+    }
+}
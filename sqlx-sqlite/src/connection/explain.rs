@@ -390,6 +390,7 @@ fn root_block_columns(
            WHERE s.type = 'index'",
         None,
         false,
+        false,
     )?
     .filter_map(|res| res.map(|either| either.right()).transpose())
     .map(|row| FromRow::from_row(&row?))
@@ -567,7 +568,7 @@ pub(super) fn explain(
 ) -> Result<(Vec<SqliteTypeInfo>, Vec<Option<bool>>), Error> {
     let root_block_cols = root_block_columns(conn)?;
     let program: Vec<(i64, String, i64, i64, i64, Vec<u8>)> =
-        execute::iter(conn, &format!("EXPLAIN {query}"), None, false)?
+        execute::iter(conn, &format!("EXPLAIN {query}"), None, false, false)?
             .filter_map(|res| res.map(|either| either.right()).transpose())
             .map(|row| FromRow::from_row(&row?))
             .collect::<Result<Vec<_>, Error>>()?;
@@ -1544,21 +1545,27 @@ fn test_root_block_columns_has_types() {
         &mut conn,
         r"CREATE TABLE t(a INTEGER PRIMARY KEY, b_null TEXT NULL, b TEXT NOT NULL);",
         None,
+        false,
+        false
+    )
+    .unwrap()
+    .next()
+    .is_some());
+    assert!(execute::iter(
+        &mut conn,
+        r"CREATE INDEX i1 on t (a,b_null);",
+        None,
+        false,
         false
     )
     .unwrap()
     .next()
     .is_some());
-    assert!(
-        execute::iter(&mut conn, r"CREATE INDEX i1 on t (a,b_null);", None, false)
-            .unwrap()
-            .next()
-            .is_some()
-    );
     assert!(execute::iter(
         &mut conn,
         r"CREATE UNIQUE INDEX i2 on t (a,b_null);",
         None,
+        false,
         false
     )
     .unwrap()
@@ -1568,6 +1575,7 @@ fn test_root_block_columns_has_types() {
         &mut conn,
         r"CREATE TABLE t2(a INTEGER NOT NULL, b_null NUMERIC NULL, b NUMERIC NOT NULL);",
         None,
+        false,
         false
     )
     .unwrap()
@@ -1577,6 +1585,7 @@ fn test_root_block_columns_has_types() {
         &mut conn,
         r"CREATE INDEX t2i1 on t2 (a,b_null);",
         None,
+        false,
         false
     )
     .unwrap()
@@ -1586,6 +1595,7 @@ fn test_root_block_columns_has_types() {
         &mut conn,
         r"CREATE UNIQUE INDEX t2i2 on t2 (a,b);",
         None,
+        false,
         false
     )
     .unwrap()
@@ -1596,6 +1606,7 @@ fn test_root_block_columns_has_types() {
         &mut conn,
         r"CREATE TEMPORARY TABLE t3(a TEXT PRIMARY KEY, b REAL NOT NULL, b_null REAL NULL);",
         None,
+        false,
         false
     )
     .unwrap()
@@ -1607,6 +1618,7 @@ fn test_root_block_columns_has_types() {
         r"select name, 0 db_seq, rootpage from main.sqlite_schema UNION ALL select name, 1 db_seq, rootpage from temp.sqlite_schema",
         None,
         false,
+        false
     )
     .unwrap()
     .filter_map(|res| res.map(|either| either.right()).transpose())
@@ -4,6 +4,7 @@ use std::fmt::Write;
 use std::fmt::{self, Debug, Formatter};
 use std::os::raw::{c_char, c_int, c_void};
 use std::panic::catch_unwind;
+use std::path::PathBuf;
 use std::ptr;
 use std::ptr::NonNull;
 
@@ -11,8 +12,9 @@ use futures_core::future::BoxFuture;
 use futures_intrusive::sync::MutexGuard;
 use futures_util::future;
 use libsqlite3_sys::{
-    sqlite3, sqlite3_commit_hook, sqlite3_progress_handler, sqlite3_rollback_hook,
-    sqlite3_update_hook, SQLITE_DELETE, SQLITE_INSERT, SQLITE_UPDATE,
+    sqlite3, sqlite3_commit_hook, sqlite3_db_filename, sqlite3_file_control, sqlite3_free,
+    sqlite3_progress_handler, sqlite3_rollback_hook, sqlite3_update_hook, SQLITE_DELETE,
+    SQLITE_FCNTL_VFSNAME, SQLITE_INSERT, SQLITE_OK, SQLITE_UPDATE,
 };
 #[cfg(feature = "preupdate-hook")]
 pub use preupdate_hook::*;
@@ -26,10 +28,12 @@ use sqlx_core::transaction::Transaction;
 
 use crate::connection::establish::EstablishParams;
 use crate::connection::worker::ConnectionWorker;
+use crate::connection::write_lock::WriteLock;
 use crate::options::OptimizeOnClose;
 use crate::statement::VirtualStatement;
 use crate::{Sqlite, SqliteConnectOptions, SqliteError};
 
+pub(crate) mod backup;
 pub(crate) mod collation;
 pub(crate) mod describe;
 pub(crate) mod establish;
@@ -42,7 +46,8 @@ pub(crate) mod intmap;
 mod preupdate_hook;
 pub(crate) mod serialize;
 
-mod worker;
+pub(crate) mod worker;
+pub(crate) mod write_lock;
 
 /// A connection to an open [Sqlite] database.
 ///
@@ -124,9 +129,21 @@ pub(crate) struct ConnectionState {
     commit_hook_callback: Option<CommitHookHandler>,
 
     rollback_hook_callback: Option<RollbackHookHandler>,
+
+    /// Set if the writes of the connections opened with the same options are serialized.
+    pub(crate) write_lock: Option<WriteLock>,
 }
 
 impl ConnectionState {
+    /// Release the write lock, if writes are serialized, unless a transaction is still open.
+    pub(crate) fn release_write_lock_if_autocommit(&mut self) {
+        if let Some(write_lock) = &mut self.write_lock {
+            if self.handle.is_autocommit() {
+                write_lock.release();
+            }
+        }
+    }
+
     /// Drops the `progress_handler_callback` if it exists.
     pub(crate) fn remove_progress_handler(&mut self) {
         if let Some(mut handler) = self.progress_handler_callback.take() {
@@ -196,12 +213,73 @@ impl SqliteConnection {
     /// Lock the SQLite database handle out from the worker thread so direct SQLite API calls can
     /// be made safely.
     ///
+    /// The worker thread is parked until the returned [`LockedSqliteHandle`] is dropped, so no
+    /// statements run on this connection in the meantime, and then resumes with the next command.
+    /// While the handle is locked:
+    ///
+    /// * the `sqlite3` handle must not be closed, nor any statements of SQLx finalized;
+    /// * the handle should be held only briefly, as any query on this connection waits for it,
+    ///   and it should not be held across long blocking work in an async context.
+    ///
+    /// See [`.vfs_name()`][Self::vfs_name] and [`.filename()`][Self::filename] for examples.
+    ///
     /// Returns an error if the worker thread crashed.
     pub async fn lock_handle(&mut self) -> Result<LockedSqliteHandle<'_>, Error> {
         let guard = self.worker.unlock_db().await?;
 
         Ok(LockedSqliteHandle { guard })
     }
+
+    /// Returns the name of the VFS used for the main database, e.g. `"unix"`.
+    pub async fn vfs_name(&mut self) -> Result<String, Error> {
+        let mut handle = self.lock_handle().await?;
+        let mut name: *mut c_char = ptr::null_mut();
+
+        // SAFETY: the handle is locked, and `SQLITE_FCNTL_VFSNAME` only writes a pointer
+        // to a string allocated by SQLite to `name`
+        let status = unsafe {
+            sqlite3_file_control(
+                handle.as_raw_handle().as_ptr(),
+                c"main".as_ptr(),
+                SQLITE_FCNTL_VFSNAME,
+                ptr::addr_of_mut!(name).cast(),
+            )
+        };
+
+        if status != SQLITE_OK {
+            return Err(SqliteError::from_code(status).into());
+        }
+
+        if name.is_null() {
+            return Err(SqliteError::generic("VFS did not report its name").into());
+        }
+
+        // SAFETY: `name` is a NUL-terminated string we own and have to free
+        unsafe {
+            let vfs_name = CStr::from_ptr(name).to_string_lossy().into_owned();
+            sqlite3_free(name.cast());
+            Ok(vfs_name)
+        }
+    }
+
+    /// Returns the path of the file of the main database, or `None` if it is a temporary or
+    /// in-memory database.
+    pub async fn filename(&mut self) -> Result<Option<PathBuf>, Error> {
+        let mut handle = self.lock_handle().await?;
+
+        // SAFETY: the handle is locked, and the returned string lives as long as the connection
+        unsafe {
+            let filename = sqlite3_db_filename(handle.as_raw_handle().as_ptr(), c"main".as_ptr());
+
+            if filename.is_null() || *filename == 0 {
+                return Ok(None);
+            }
+
+            Ok(Some(PathBuf::from(
+                CStr::from_ptr(filename).to_string_lossy().into_owned(),
+            )))
+        }
+    }
 }
 
 impl Debug for SqliteConnection {
@@ -228,6 +306,8 @@ impl Connection for SqliteConnection {
                 pragma_string.push_str("PRAGMA optimize;");
                 self.execute(&*pragma_string).await?;
             }
+            // The worker runs the commands in order, so the commands still queued, like
+            // the rollback of a dropped transaction, are finished before it shuts down.
             let shutdown = self.worker.shutdown();
             // Drop the statement worker, which should
             // cover all references to the connection handle outside of the worker thread
@@ -599,3 +679,38 @@ impl Statements {
         self.temp = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use libsqlite3_sys::sqlite3_db_readonly;
+    use sqlx::{ConnectOptions, Row};
+    use std::str::FromStr;
+
+    use crate::SqliteConnectOptions;
+
+    #[sqlx::test]
+    async fn test_lock_handle_resumes_worker() {
+        let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+
+        {
+            let mut handle = conn.lock_handle().await.unwrap();
+
+            // SAFETY: the handle is locked until the end of this block
+            let readonly =
+                unsafe { sqlite3_db_readonly(handle.as_raw_handle().as_ptr(), c"main".as_ptr()) };
+
+            assert_eq!(readonly, 0);
+        }
+
+        let row = sqlx::query("SELECT 1 + 1")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+
+        assert_eq!(row.get::<i32, _>(0), 2);
+    }
+}
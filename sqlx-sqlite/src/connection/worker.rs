@@ -1,8 +1,11 @@
 use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
 use std::future::Future;
+use std::ops::ControlFlow;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError};
 use std::thread;
+use std::time::Duration;
 
 use futures_channel::oneshot;
 use futures_intrusive::sync::{Mutex, MutexGuard};
@@ -19,20 +22,91 @@ use crate::connection::describe::describe;
 use crate::connection::establish::EstablishParams;
 use crate::connection::execute;
 use crate::connection::ConnectionState;
-use crate::{Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStatement};
+use crate::{
+    Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStatement,
+    SqliteTransactionBehavior,
+};
 
+use super::backup::{backup, BackupDestination, BackupParams};
 use super::serialize::{deserialize, serialize, SchemaName, SqliteOwnedBuf};
 
-// Each SQLite connection has a dedicated thread.
+// Each SQLite connection has a dedicated thread, unless the connections opened with the same
+// options share a few threads; see `SqliteConnectOptions::shared_worker_threads()`.
+//
+// A shared thread runs the commands of its connections in the order they are sent. While the
+// rows of a query wait to be fetched, it runs the commands of its other connections in the
+// meantime, but a connection waiting for a lock blocks the other connections on its thread.
+
+/// How long a shared worker thread waits for room for the rows of a query before checking for
+/// the commands of its other connections again.
+const SHARED_THREAD_POLL_INTERVAL: Duration = Duration::from_millis(5);
 
-// TODO: Tweak this so that we can use a thread pool per pool of SQLite3 connections to reduce
-//       OS resource usage. Low priority because a high concurrent load for SQLite3 is very
-//       unlikely.
+/// A command for the connection with the given routing ID.
+///
+/// The ID is only used by shared worker threads; it's always 0 for a dedicated thread.
+type Message = (usize, Command, Span);
 
 pub(crate) struct ConnectionWorker {
-    command_tx: flume::Sender<(Command, tracing::Span)>,
+    command_tx: CommandSender,
     /// Mutex for locking access to the database.
     pub(crate) shared: Arc<WorkerSharedState>,
+    /// The dedicated worker thread, taken by [`Self::shutdown()`] to wait for it to exit.
+    ///
+    /// `None` for a connection on a shared worker thread, which outlives the connection.
+    thread: Option<DedicatedThread>,
+}
+
+struct DedicatedThread {
+    handle: thread::JoinHandle<()>,
+    /// Completes once the thread has dropped everything it owns and is about to exit.
+    exited: oneshot::Receiver<()>,
+}
+
+/// Sends commands to the worker thread of a connection.
+#[derive(Clone)]
+struct CommandSender {
+    tx: flume::Sender<Message>,
+    /// The ID of the connection on a shared worker thread, or `None` on a dedicated thread.
+    routing_id: Option<usize>,
+}
+
+impl CommandSender {
+    fn send(&self, command: Command) -> Result<(), Error> {
+        self.tx
+            .send((self.routing_id.unwrap_or(0), command, Span::current()))
+            .map_err(|_| Error::WorkerCrashed)
+    }
+
+    async fn send_async(&self, command: Command) -> Result<(), Error> {
+        self.tx
+            .send_async((self.routing_id.unwrap_or(0), command, Span::current()))
+            .await
+            .map_err(|_| Error::WorkerCrashed)
+    }
+}
+
+/// Locks the database of a worker from another thread, like [`ConnectionWorker::unlock_db()`].
+///
+/// Unlike a `MutexGuard` borrowed from the worker, this can be moved to another worker thread,
+/// so that the database stays locked until that thread is done with it.
+pub(crate) struct WorkerLocker {
+    command_tx: CommandSender,
+    shared: Arc<WorkerSharedState>,
+}
+
+impl WorkerLocker {
+    /// Block the current thread until the worker has released the database.
+    pub(crate) fn lock(&self) -> Result<MutexGuard<'_, ConnectionState>, Error> {
+        let (guard, res) = futures_executor::block_on(futures_util::future::join(
+            // we need to join the wait queue for the lock before we send the message
+            self.shared.conn.lock(),
+            self.command_tx.send_async(Command::UnlockDb),
+        ));
+
+        res?;
+
+        Ok(guard)
+    }
 }
 
 pub(crate) struct WorkerSharedState {
@@ -40,7 +114,23 @@ pub(crate) struct WorkerSharedState {
     pub(crate) conn: Mutex<ConnectionState>,
 }
 
+impl WorkerSharedState {
+    fn new(conn: ConnectionState) -> Arc<Self> {
+        Arc::new(WorkerSharedState {
+            cached_statements_size: AtomicUsize::new(0),
+            // note: must be fair because in `Command::UnlockDb` we unlock the mutex
+            // and then immediately try to relock it; an unfair mutex would immediately
+            // grant us the lock even if another task is waiting.
+            conn: Mutex::new(conn, true),
+        })
+    }
+}
+
 enum Command {
+    Establish {
+        params: Box<EstablishParams>,
+        tx: oneshot::Sender<Result<Arc<WorkerSharedState>, Error>>,
+    },
     Prepare {
         query: Box<str>,
         tx: oneshot::Sender<Result<SqliteStatement<'static>, Error>>,
@@ -55,6 +145,7 @@ enum Command {
         persistent: bool,
         tx: flume::Sender<Result<Either<SqliteQueryResult, SqliteRow>, Error>>,
         limit: Option<usize>,
+        metered: bool,
     },
     Serialize {
         schema: Option<SchemaName>,
@@ -66,7 +157,13 @@ enum Command {
         read_only: bool,
         tx: oneshot::Sender<Result<(), Error>>,
     },
+    Backup {
+        destination: BackupDestination,
+        params: BackupParams,
+        tx: oneshot::Sender<Result<(), Error>>,
+    },
     Begin {
+        behavior: SqliteTransactionBehavior,
         tx: rendezvous_oneshot::Sender<Result<(), Error>>,
     },
     Commit {
@@ -83,229 +180,262 @@ enum Command {
         tx: oneshot::Sender<()>,
     },
     Shutdown {
-        tx: oneshot::Sender<()>,
+        tx: Option<oneshot::Sender<()>>,
     },
 }
 
-impl ConnectionWorker {
-    pub(crate) async fn establish(params: EstablishParams) -> Result<Self, Error> {
-        let (establish_tx, establish_rx) = oneshot::channel();
+/// The state of a connection that is kept by its worker thread.
+struct WorkerConnection {
+    shared: Arc<WorkerSharedState>,
+    // If COMMIT or ROLLBACK is processed but not acknowledged, there would be another
+    // ROLLBACK sent when the `Transaction` drops. We need to ignore it otherwise we
+    // would rollback an already completed transaction.
+    ignore_next_start_rollback: bool,
+}
 
-        thread::Builder::new()
-            .name(params.thread_name.clone())
-            .spawn(move || {
-                let (command_tx, command_rx) = flume::bounded(params.command_channel_size);
+impl WorkerConnection {
+    fn new(shared: Arc<WorkerSharedState>) -> Self {
+        WorkerConnection {
+            shared,
+            ignore_next_start_rollback: false,
+        }
+    }
 
-                let conn = match params.establish() {
-                    Ok(conn) => conn,
+    /// Run a command on the locked connection.
+    ///
+    /// `Establish`, `UnlockDb` and `Shutdown` depend on the kind of the thread and are handled
+    /// by its loop instead. Returns `Break` if the connection has to be shut down.
+    ///
+    /// `thread` is the shared worker thread the connection is on, if any, which runs the commands
+    /// of its other connections while the rows of a query wait to be fetched.
+    fn run(
+        &mut self,
+        conn: &mut ConnectionState,
+        cmd: Command,
+        mut thread: Option<&mut SharedWorkerThread>,
+    ) -> ControlFlow<()> {
+        let shared = &self.shared;
+
+        match cmd {
+            Command::Prepare { query, tx } => {
+                tx.send(prepare(conn, &query).map(|prepared| {
+                    update_cached_statements_size(conn, &shared.cached_statements_size);
+                    prepared
+                }))
+                .ok();
+            }
+            Command::Describe { query, tx } => {
+                tx.send(describe(conn, &query)).ok();
+            }
+            Command::Execute {
+                query,
+                arguments,
+                persistent,
+                tx,
+                limit,
+                metered,
+            } => {
+                let iter = match execute::iter(conn, &query, arguments, persistent, metered) {
+                    Ok(iter) => iter,
                     Err(e) => {
-                        establish_tx.send(Err(e)).ok();
-                        return;
+                        send_result(&tx, Err(e), thread);
+                        return ControlFlow::Continue(());
                     }
                 };
 
-                let shared = Arc::new(WorkerSharedState {
-                    cached_statements_size: AtomicUsize::new(0),
-                    // note: must be fair because in `Command::UnlockDb` we unlock the mutex
-                    // and then immediately try to relock it; an unfair mutex would immediately
-                    // grant us the lock even if another task is waiting.
-                    conn: Mutex::new(conn, true),
-                });
-                let mut conn = shared.conn.try_lock().unwrap();
-
-                if establish_tx
-                    .send(Ok(Self {
-                        command_tx,
-                        shared: Arc::clone(&shared),
-                    }))
-                    .is_err()
-                {
-                    return;
-                }
-
-                // If COMMIT or ROLLBACK is processed but not acknowledged, there would be another
-                // ROLLBACK sent when the `Transaction` drops. We need to ignore it otherwise we
-                // would rollback an already completed transaction.
-                let mut ignore_next_start_rollback = false;
-
-                for (cmd, span) in command_rx {
-                    let _guard = span.enter();
-                    match cmd {
-                        Command::Prepare { query, tx } => {
-                            tx.send(prepare(&mut conn, &query).map(|prepared| {
-                                update_cached_statements_size(
-                                    &conn,
-                                    &shared.cached_statements_size,
-                                );
-                                prepared
-                            }))
-                            .ok();
-                        }
-                        Command::Describe { query, tx } => {
-                            tx.send(describe(&mut conn, &query)).ok();
+                match limit {
+                    None => {
+                        for res in iter {
+                            let has_error = res.is_err();
+                            if !send_result(&tx, res, thread.as_deref_mut()) || has_error {
+                                break;
+                            }
                         }
-                        Command::Execute {
-                            query,
-                            arguments,
-                            persistent,
-                            tx,
-                            limit
-                        } => {
-                            let iter = match execute::iter(&mut conn, &query, arguments, persistent)
-                            {
-                                Ok(iter) => iter,
-                                Err(e) => {
-                                    tx.send(Err(e)).ok();
+                    }
+                    Some(limit) => {
+                        let mut iter = iter;
+                        let mut rows_returned = 0;
+
+                        while let Some(res) = iter.next() {
+                            if let Ok(Either::Right(_)) = &res {
+                                if rows_returned >= limit {
+                                    // past the limit, the remaining statements
+                                    // are still executed but not stepped for rows
+                                    iter.skip_rows();
                                     continue;
                                 }
-                            };
-
-                            match limit {
-                                None => {
-                                    for res in iter {
-                                        let has_error = res.is_err();
-                                        if tx.send(res).is_err() || has_error {
-                                            break;
-                                        }
-                                    }
-                                },
-                                Some(limit) => {
-                                    let mut iter = iter;
-                                    let mut rows_returned = 0;
-
-                                    while let Some(res) = iter.next() {
-                                        if let Ok(ok) = &res {
-                                            if ok.is_right() {
-                                                rows_returned += 1;
-                                                if rows_returned >= limit {
-                                                    drop(iter);
-                                                    let _ = tx.send(res);
-                                                    break;
-                                                }
-                                            }
-                                        }
-                                        let has_error = res.is_err();
-                                        if tx.send(res).is_err() || has_error {
-                                            break;
-                                        }
-                                    }
-                                },
-                            }
 
-                            update_cached_statements_size(&conn, &shared.cached_statements_size);
-                        }
-                        Command::Begin { tx } => {
-                            let depth = conn.transaction_depth;
-                            let res =
-                                conn.handle
-                                    .exec(begin_ansi_transaction_sql(depth))
-                                    .map(|_| {
-                                        conn.transaction_depth += 1;
-                                    });
-                            let res_ok = res.is_ok();
-
-                            if tx.blocking_send(res).is_err() && res_ok {
-                                // The BEGIN was processed but not acknowledged. This means no
-                                // `Transaction` was created and so there is no way to commit /
-                                // rollback this transaction. We need to roll it back
-                                // immediately otherwise it would remain started forever.
-                                if let Err(error) = conn
-                                    .handle
-                                    .exec(rollback_ansi_transaction_sql(depth + 1))
-                                    .map(|_| {
-                                        conn.transaction_depth -= 1;
-                                    })
-                                {
-                                    // The rollback failed. To prevent leaving the connection
-                                    // in an inconsistent state we shutdown this worker which
-                                    // causes any subsequent operation on the connection to fail.
-                                    tracing::error!(%error, "failed to rollback cancelled transaction");
-                                    break;
-                                }
-                            }
-                        }
-                        Command::Commit { tx } => {
-                            let depth = conn.transaction_depth;
-
-                            let res = if depth > 0 {
-                                conn.handle
-                                    .exec(commit_ansi_transaction_sql(depth))
-                                    .map(|_| {
-                                        conn.transaction_depth -= 1;
-                                    })
-                            } else {
-                                Ok(())
-                            };
-                            let res_ok = res.is_ok();
-
-                            if tx.blocking_send(res).is_err() && res_ok {
-                                // The COMMIT was processed but not acknowledged. This means that
-                                // the `Transaction` doesn't know it was committed and will try to
-                                // rollback on drop. We need to ignore that rollback.
-                                ignore_next_start_rollback = true;
-                            }
-                        }
-                        Command::Rollback { tx } => {
-                            if ignore_next_start_rollback && tx.is_none() {
-                                ignore_next_start_rollback = false;
-                                continue;
+                                rows_returned += 1;
                             }
 
-                            let depth = conn.transaction_depth;
-
-                            let res = if depth > 0 {
-                                conn.handle
-                                    .exec(rollback_ansi_transaction_sql(depth))
-                                    .map(|_| {
-                                        conn.transaction_depth -= 1;
-                                    })
-                            } else {
-                                Ok(())
-                            };
-
-                            let res_ok = res.is_ok();
-
-                            if let Some(tx) = tx {
-                                if tx.blocking_send(res).is_err() && res_ok {
-                                    // The ROLLBACK was processed but not acknowledged. This means
-                                    // that the `Transaction` doesn't know it was rolled back and
-                                    // will try to rollback again on drop. We need to ignore that
-                                    // rollback.
-                                    ignore_next_start_rollback = true;
-                                }
+                            let has_error = res.is_err();
+                            if !send_result(&tx, res, thread.as_deref_mut()) || has_error {
+                                break;
                             }
                         }
-                        Command::Serialize { schema, tx } => {
-                            tx.send(serialize(&mut conn, schema)).ok();
-                        }
-                        Command::Deserialize { schema, data, read_only, tx } => {
-                            tx.send(deserialize(&mut conn, schema, data, read_only)).ok();
-                        }
-                        Command::ClearCache { tx } => {
-                            conn.statements.clear();
-                            update_cached_statements_size(&conn, &shared.cached_statements_size);
-                            tx.send(()).ok();
-                        }
-                        Command::UnlockDb => {
-                            drop(conn);
-                            conn = futures_executor::block_on(shared.conn.lock());
-                        }
-                        Command::Ping { tx } => {
-                            tx.send(()).ok();
-                        }
-                        Command::Shutdown { tx } => {
-                            // drop the connection references before sending confirmation
-                            // and ending the command loop
-                            drop(conn);
-                            drop(shared);
-                            let _ = tx.send(());
-                            return;
-                        }
                     }
                 }
+
+                conn.release_write_lock_if_autocommit();
+                update_cached_statements_size(conn, &shared.cached_statements_size);
+            }
+            Command::Begin { behavior, tx } => {
+                let depth = conn.transaction_depth;
+                let sql = if depth == 0 {
+                    Cow::Borrowed(behavior.begin_sql())
+                } else {
+                    begin_ansi_transaction_sql(depth)
+                };
+                let res = begin_write_lock(conn, depth, behavior)
+                    .and_then(|_| conn.handle.exec(sql))
+                    .map(|_| {
+                        conn.transaction_depth += 1;
+                    });
+                let res_ok = res.is_ok();
+
+                if !res_ok {
+                    conn.release_write_lock_if_autocommit();
+                }
+
+                if tx.blocking_send(res).is_err() && res_ok {
+                    // The BEGIN was processed but not acknowledged. This means no
+                    // `Transaction` was created and so there is no way to commit /
+                    // rollback this transaction. We need to roll it back
+                    // immediately otherwise it would remain started forever.
+                    if let Err(error) = conn
+                        .handle
+                        .exec(rollback_ansi_transaction_sql(depth + 1))
+                        .map(|_| {
+                            conn.transaction_depth -= 1;
+                            conn.release_write_lock_if_autocommit();
+                        })
+                    {
+                        // The rollback failed. To prevent leaving the connection
+                        // in an inconsistent state we shutdown this worker which
+                        // causes any subsequent operation on the connection to fail.
+                        tracing::error!(%error, "failed to rollback cancelled transaction");
+                        return ControlFlow::Break(());
+                    }
+                }
+            }
+            Command::Commit { tx } => {
+                let depth = conn.transaction_depth;
+
+                let res = if depth > 0 {
+                    conn.handle
+                        .exec(commit_ansi_transaction_sql(depth))
+                        .map(|_| {
+                            conn.transaction_depth -= 1;
+                        })
+                } else {
+                    Ok(())
+                };
+                let res_ok = res.is_ok();
+
+                conn.release_write_lock_if_autocommit();
+
+                if tx.blocking_send(res).is_err() && res_ok {
+                    // The COMMIT was processed but not acknowledged. This means that
+                    // the `Transaction` doesn't know it was committed and will try to
+                    // rollback on drop. We need to ignore that rollback.
+                    self.ignore_next_start_rollback = true;
+                }
+            }
+            Command::Rollback { tx } => {
+                if self.ignore_next_start_rollback && tx.is_none() {
+                    self.ignore_next_start_rollback = false;
+                    return ControlFlow::Continue(());
+                }
+
+                let depth = conn.transaction_depth;
+
+                let res = if depth > 0 {
+                    conn.handle
+                        .exec(rollback_ansi_transaction_sql(depth))
+                        .map(|_| {
+                            conn.transaction_depth -= 1;
+                        })
+                } else {
+                    Ok(())
+                };
+
+                let res_ok = res.is_ok();
+
+                conn.release_write_lock_if_autocommit();
+
+                if let Some(tx) = tx {
+                    if tx.blocking_send(res).is_err() && res_ok {
+                        // The ROLLBACK was processed but not acknowledged. This means
+                        // that the `Transaction` doesn't know it was rolled back and
+                        // will try to rollback again on drop. We need to ignore that
+                        // rollback.
+                        self.ignore_next_start_rollback = true;
+                    }
+                }
+            }
+            Command::Serialize { schema, tx } => {
+                tx.send(serialize(conn, schema)).ok();
+            }
+            Command::Deserialize {
+                schema,
+                data,
+                read_only,
+                tx,
+            } => {
+                tx.send(deserialize(conn, schema, data, read_only)).ok();
+            }
+            Command::Backup {
+                destination,
+                params,
+                tx,
+            } => {
+                tx.send(backup(conn, destination, params)).ok();
+            }
+            Command::ClearCache { tx } => {
+                conn.statements.clear();
+                update_cached_statements_size(conn, &shared.cached_statements_size);
+                tx.send(()).ok();
+            }
+            Command::Ping { tx } => {
+                tx.send(()).ok();
+            }
+            Command::Establish { .. } | Command::UnlockDb | Command::Shutdown { .. } => {
+                unreachable!("BUG: command must be handled by the worker thread")
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+impl ConnectionWorker {
+    pub(crate) async fn establish(params: EstablishParams) -> Result<Self, Error> {
+        match params.shared_workers.clone() {
+            Some(workers) => workers.establish(params).await,
+            None => Self::establish_dedicated(params).await,
+        }
+    }
+
+    async fn establish_dedicated(params: EstablishParams) -> Result<Self, Error> {
+        let (establish_tx, establish_rx) = oneshot::channel();
+        let (exited_tx, exited_rx) = oneshot::channel::<()>();
+
+        let handle = thread::Builder::new()
+            .name(params.thread_name.clone())
+            .spawn(move || {
+                run_dedicated_thread(params, establish_tx);
+
+                // everything the thread owned has been dropped by now
+                drop(exited_tx);
             })?;
 
-        establish_rx.await.map_err(|_| Error::WorkerCrashed)?
+        let mut worker = establish_rx.await.map_err(|_| Error::WorkerCrashed)??;
+        worker.thread = Some(DedicatedThread {
+            handle,
+            exited: exited_rx,
+        });
+
+        Ok(worker)
     }
 
     pub(crate) async fn prepare(&mut self, query: &str) -> Result<SqliteStatement<'static>, Error> {
@@ -331,28 +461,26 @@ impl ConnectionWorker {
         chan_size: usize,
         persistent: bool,
         limit: Option<usize>,
+        metered: bool,
     ) -> Result<flume::Receiver<Result<Either<SqliteQueryResult, SqliteRow>, Error>>, Error> {
         let (tx, rx) = flume::bounded(chan_size);
 
         self.command_tx
-            .send_async((
-                Command::Execute {
-                    query: query.into(),
-                    arguments: args.map(SqliteArguments::into_static),
-                    persistent,
-                    tx,
-                    limit,
-                },
-                Span::current(),
-            ))
-            .await
-            .map_err(|_| Error::WorkerCrashed)?;
+            .send_async(Command::Execute {
+                query: query.into(),
+                arguments: args.map(SqliteArguments::into_static),
+                persistent,
+                tx,
+                limit,
+                metered,
+            })
+            .await?;
 
         Ok(rx)
     }
 
-    pub(crate) async fn begin(&mut self) -> Result<(), Error> {
-        self.oneshot_cmd_with_ack(|tx| Command::Begin { tx })
+    pub(crate) async fn begin(&mut self, behavior: SqliteTransactionBehavior) -> Result<(), Error> {
+        self.oneshot_cmd_with_ack(|tx| Command::Begin { behavior, tx })
             .await?
     }
 
@@ -367,9 +495,7 @@ impl ConnectionWorker {
     }
 
     pub(crate) fn start_rollback(&mut self) -> Result<(), Error> {
-        self.command_tx
-            .send((Command::Rollback { tx: None }, Span::current()))
-            .map_err(|_| Error::WorkerCrashed)
+        self.command_tx.send(Command::Rollback { tx: None })
     }
 
     pub(crate) async fn ping(&mut self) -> Result<(), Error> {
@@ -399,16 +525,34 @@ impl ConnectionWorker {
             .await?
     }
 
+    pub(crate) async fn backup(
+        &mut self,
+        destination: BackupDestination,
+        params: BackupParams,
+    ) -> Result<(), Error> {
+        self.oneshot_cmd(|tx| Command::Backup {
+            destination,
+            params,
+            tx,
+        })
+        .await?
+    }
+
+    /// Get a [`WorkerLocker`] for locking the database from another worker thread.
+    pub(crate) fn locker(&self) -> WorkerLocker {
+        WorkerLocker {
+            command_tx: self.command_tx.clone(),
+            shared: Arc::clone(&self.shared),
+        }
+    }
+
     async fn oneshot_cmd<F, T>(&mut self, command: F) -> Result<T, Error>
     where
         F: FnOnce(oneshot::Sender<T>) -> Command,
     {
         let (tx, rx) = oneshot::channel();
 
-        self.command_tx
-            .send_async((command(tx), Span::current()))
-            .await
-            .map_err(|_| Error::WorkerCrashed)?;
+        self.command_tx.send_async(command(tx)).await?;
 
         rx.await.map_err(|_| Error::WorkerCrashed)
     }
@@ -419,10 +563,7 @@ impl ConnectionWorker {
     {
         let (tx, rx) = rendezvous_oneshot::channel();
 
-        self.command_tx
-            .send_async((command(tx), Span::current()))
-            .await
-            .map_err(|_| Error::WorkerCrashed)?;
+        self.command_tx.send_async(command(tx)).await?;
 
         rx.recv().await.map_err(|_| Error::WorkerCrashed)
     }
@@ -435,34 +576,362 @@ impl ConnectionWorker {
         let (guard, res) = futures_util::future::join(
             // we need to join the wait queue for the lock before we send the message
             self.shared.conn.lock(),
-            self.command_tx
-                .send_async((Command::UnlockDb, Span::current())),
+            self.command_tx.send_async(Command::UnlockDb),
         )
         .await;
 
-        res.map_err(|_| Error::WorkerCrashed)?;
+        res?;
 
         Ok(guard)
     }
 
-    /// Send a command to the worker to shut down the processing thread.
+    /// Send a command to the worker to shut down the connection.
+    ///
+    /// The connection is closed once the worker acknowledges the command. A dedicated thread
+    /// exits right after, and the returned future resolves once it did; no thread is blocked
+    /// waiting for it.
     ///
     /// A `WorkerCrashed` error may be returned if the thread has already stopped.
     pub(crate) fn shutdown(&mut self) -> impl Future<Output = Result<(), Error>> {
         let (tx, rx) = oneshot::channel();
 
-        let send_res = self
-            .command_tx
-            .send((Command::Shutdown { tx }, Span::current()))
-            .map_err(|_| Error::WorkerCrashed);
+        let send_res = self.command_tx.send(Command::Shutdown { tx: Some(tx) });
+
+        let thread = self.thread.take();
 
         async move {
             send_res?;
 
             // wait for the response
-            rx.await.map_err(|_| Error::WorkerCrashed)
+            rx.await.map_err(|_| Error::WorkerCrashed)?;
+
+            if let Some(thread) = thread {
+                // the sender is only ever dropped, when the thread is done
+                let _ = thread.exited.await;
+
+                // the thread has nothing left to do, so this doesn't block; otherwise it's
+                // detached, as its exit is only a matter of time
+                if thread.handle.is_finished() {
+                    thread.handle.join().map_err(|_| Error::WorkerCrashed)?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+impl Drop for ConnectionWorker {
+    fn drop(&mut self) {
+        // a dedicated thread exits once every sender of its channel is dropped, but a shared
+        // thread has to be told to drop the connection; it's ignored if it was shut down already
+        if self.command_tx.routing_id.is_some() {
+            let _ = self.command_tx.send(Command::Shutdown { tx: None });
+        }
+    }
+}
+
+fn run_dedicated_thread(
+    params: EstablishParams,
+    establish_tx: oneshot::Sender<Result<ConnectionWorker, Error>>,
+) {
+    let (command_tx, command_rx) = flume::bounded(params.command_channel_size);
+
+    let conn = match params.establish() {
+        Ok(conn) => conn,
+        Err(e) => {
+            establish_tx.send(Err(e)).ok();
+            return;
+        }
+    };
+
+    let shared = WorkerSharedState::new(conn);
+    let mut conn = shared.conn.try_lock().unwrap();
+
+    if establish_tx
+        .send(Ok(ConnectionWorker {
+            command_tx: CommandSender {
+                tx: command_tx,
+                routing_id: None,
+            },
+            shared: Arc::clone(&shared),
+            thread: None,
+        }))
+        .is_err()
+    {
+        return;
+    }
+
+    let mut worker = WorkerConnection::new(Arc::clone(&shared));
+
+    for (_, cmd, span) in command_rx {
+        let _guard = span.enter();
+        match cmd {
+            Command::UnlockDb => {
+                drop(conn);
+                conn = futures_executor::block_on(shared.conn.lock());
+            }
+            Command::Shutdown { tx } => {
+                // drop the connection references before sending confirmation
+                // and ending the command loop
+                drop(conn);
+                drop(worker);
+                drop(shared);
+                if let Some(tx) = tx {
+                    let _ = tx.send(());
+                }
+                return;
+            }
+            cmd => {
+                if worker.run(&mut conn, cmd, None).is_break() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// The worker threads shared by the connections opened with the same
+/// [`SqliteConnectOptions`][crate::SqliteConnectOptions].
+///
+/// See [`SqliteConnectOptions::shared_worker_threads()`][crate::SqliteConnectOptions::shared_worker_threads].
+#[derive(Debug)]
+pub(crate) struct SharedWorkers {
+    max_threads: usize,
+    threads: std::sync::Mutex<Vec<SharedThread>>,
+    next_routing_id: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct SharedThread {
+    command_tx: flume::Sender<Message>,
+    /// The number of connections on the thread.
+    connections: Arc<AtomicUsize>,
+}
+
+impl SharedWorkers {
+    pub(crate) fn new(max_threads: usize) -> Self {
+        SharedWorkers {
+            max_threads,
+            threads: Default::default(),
+            // 0 is the routing ID of a dedicated thread
+            next_routing_id: AtomicUsize::new(1),
+        }
+    }
+
+    async fn establish(&self, params: EstablishParams) -> Result<ConnectionWorker, Error> {
+        let command_tx = CommandSender {
+            tx: self.assign_thread(&params)?,
+            routing_id: Some(self.next_routing_id.fetch_add(1, Ordering::Relaxed)),
+        };
+
+        let (tx, rx) = oneshot::channel();
+
+        command_tx
+            .send_async(Command::Establish {
+                params: Box::new(params),
+                tx,
+            })
+            .await?;
+
+        let shared = rx.await.map_err(|_| Error::WorkerCrashed)??;
+
+        Ok(ConnectionWorker {
+            command_tx,
+            shared,
+            thread: None,
+        })
+    }
+
+    /// Choose a thread for a new connection: one without connections, a new one if there are
+    /// fewer than the maximum, or else the one with the fewest connections.
+    fn assign_thread(&self, params: &EstablishParams) -> Result<flume::Sender<Message>, Error> {
+        // the threads can't be left inconsistent by a panic
+        let mut threads = self.threads.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let idle = threads
+            .iter()
+            .position(|thread| thread.connections.load(Ordering::Acquire) == 0);
+
+        let index = match idle {
+            Some(index) => index,
+            None if threads.len() < self.max_threads => {
+                // unbounded, so that a thread can send to itself, e.g. to lock the destination
+                // of a backup, and a connection dropped on it never blocks
+                let (command_tx, command_rx) = flume::unbounded();
+                let connections = Arc::new(AtomicUsize::new(0));
+
+                thread::Builder::new()
+                    .name(params.thread_name.clone())
+                    .spawn({
+                        let connections = Arc::clone(&connections);
+                        move || run_shared_thread(command_rx, connections)
+                    })?;
+
+                threads.push(SharedThread {
+                    command_tx,
+                    connections,
+                });
+
+                threads.len() - 1
+            }
+            None => threads
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, thread)| thread.connections.load(Ordering::Acquire))
+                .map(|(index, _)| index)
+                .expect("BUG: there must be at least one shared worker thread"),
+        };
+
+        let thread = &threads[index];
+        thread.connections.fetch_add(1, Ordering::AcqRel);
+
+        Ok(thread.command_tx.clone())
+    }
+}
+
+/// Run the commands of the connections on a shared worker thread until the
+/// [`SharedWorkers`] and all of its connections are dropped.
+fn run_shared_thread(command_rx: flume::Receiver<Message>, connections: Arc<AtomicUsize>) {
+    let mut thread = SharedWorkerThread {
+        command_rx,
+        connections,
+        workers: HashMap::new(),
+        running: HashMap::new(),
+    };
+
+    while let Ok(message) = thread.command_rx.recv() {
+        thread.dispatch(message);
+    }
+}
+
+/// The connections on a shared worker thread.
+struct SharedWorkerThread {
+    command_rx: flume::Receiver<Message>,
+    connections: Arc<AtomicUsize>,
+    /// The connections that aren't running a command.
+    workers: HashMap<usize, WorkerConnection>,
+    /// The commands sent to the connections that are running a command, which are run after it.
+    ///
+    /// A command waiting for the rows of a query to be fetched runs the commands of the other
+    /// connections in the meantime, so there may be several.
+    running: HashMap<usize, VecDeque<(Command, Span)>>,
+}
+
+impl SharedWorkerThread {
+    fn dispatch(&mut self, (routing_id, cmd, span): Message) {
+        if let Some(queued) = self.running.get_mut(&routing_id) {
+            queued.push_back((cmd, span));
+            return;
+        }
+
+        match cmd {
+            Command::Establish { params, tx } => {
+                let _guard = span.enter();
+
+                match params.establish() {
+                    Ok(conn) => {
+                        let shared = WorkerSharedState::new(conn);
+                        self.workers
+                            .insert(routing_id, WorkerConnection::new(Arc::clone(&shared)));
+
+                        if tx.send(Ok(shared)).is_err() {
+                            self.workers.remove(&routing_id);
+                            self.connections.fetch_sub(1, Ordering::AcqRel);
+                        }
+                    }
+                    Err(e) => {
+                        // the connection was counted when the thread was assigned
+                        self.connections.fetch_sub(1, Ordering::AcqRel);
+                        tx.send(Err(e)).ok();
+                    }
+                }
+            }
+            cmd => self.run(routing_id, cmd, span),
         }
     }
+
+    /// Run a command of a connection, and then the commands sent to it in the meantime.
+    fn run(&mut self, routing_id: usize, cmd: Command, span: Span) {
+        // the commands of a connection that was shut down are dropped,
+        // which fails them with `WorkerCrashed`
+        let mut worker = self.workers.remove(&routing_id);
+
+        self.running.insert(routing_id, VecDeque::new());
+
+        let mut next = Some((cmd, span));
+
+        while let Some((cmd, span)) = next {
+            let _guard = span.enter();
+
+            match (cmd, worker.as_mut()) {
+                (Command::Shutdown { tx }, _) => {
+                    if worker.take().is_some() {
+                        self.connections.fetch_sub(1, Ordering::AcqRel);
+                    }
+
+                    if let Some(tx) = tx {
+                        let _ = tx.send(());
+                    }
+                }
+                // the connection is only locked while a command runs on it
+                (Command::UnlockDb, _) | (_, None) => {}
+                (cmd, Some(running)) => {
+                    let shared = Arc::clone(&running.shared);
+                    let mut conn = futures_executor::block_on(shared.conn.lock());
+
+                    if running.run(&mut conn, cmd, Some(self)).is_break() {
+                        drop(conn);
+                        worker = None;
+                        self.connections.fetch_sub(1, Ordering::AcqRel);
+                    }
+                }
+            }
+
+            next = self
+                .running
+                .get_mut(&routing_id)
+                .and_then(VecDeque::pop_front);
+        }
+
+        self.running.remove(&routing_id);
+
+        if let Some(worker) = worker {
+            self.workers.insert(routing_id, worker);
+        }
+    }
+
+    /// Send a result of a query, running the commands of the other connections until
+    /// there is room for it.
+    ///
+    /// Returns `false` if the receiver was dropped.
+    fn send_yielding<T>(&mut self, tx: &flume::Sender<T>, mut value: T) -> bool {
+        loop {
+            while let Ok(message) = self.command_rx.try_recv() {
+                self.dispatch(message);
+            }
+
+            value = match tx.send_timeout(value, SHARED_THREAD_POLL_INTERVAL) {
+                Ok(()) => return true,
+                Err(flume::SendTimeoutError::Disconnected(_)) => return false,
+                Err(flume::SendTimeoutError::Timeout(value)) => value,
+            };
+        }
+    }
+}
+
+/// Send a result of a query, waiting until there is room for it.
+///
+/// Returns `false` if the receiver was dropped.
+fn send_result<T>(
+    tx: &flume::Sender<T>,
+    value: T,
+    thread: Option<&mut SharedWorkerThread>,
+) -> bool {
+    match thread {
+        Some(thread) => thread.send_yielding(tx, value),
+        None => tx.send(value).is_ok(),
+    }
 }
 
 fn prepare(conn: &mut ConnectionState, query: &str) -> Result<SqliteStatement<'static>, Error> {
@@ -491,6 +960,21 @@ fn prepare(conn: &mut ConnectionState, query: &str) -> Result<SqliteStatement<'s
     })
 }
 
+/// Acquire the write lock before beginning an `IMMEDIATE` or `EXCLUSIVE` transaction,
+/// if writes are serialized.
+fn begin_write_lock(
+    conn: &mut ConnectionState,
+    depth: usize,
+    behavior: SqliteTransactionBehavior,
+) -> Result<(), Error> {
+    match &mut conn.write_lock {
+        Some(write_lock) if depth == 0 && behavior != SqliteTransactionBehavior::Deferred => {
+            write_lock.acquire()
+        }
+        _ => Ok(()),
+    }
+}
+
 fn update_cached_statements_size(conn: &ConnectionState, size: &AtomicUsize) {
     size.store(conn.statements.len(), Ordering::Release);
 }
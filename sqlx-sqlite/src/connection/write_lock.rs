@@ -0,0 +1,90 @@
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use libsqlite3_sys::SQLITE_BUSY;
+
+use crate::error::Error;
+use crate::SqliteError;
+
+/// The lock shared by all connections opened with the same
+/// [`SqliteConnectOptions`][crate::SqliteConnectOptions] to serialize their writes.
+///
+/// See [`SqliteConnectOptions::serialized_writes()`][crate::SqliteConnectOptions::serialized_writes].
+#[derive(Debug, Default)]
+pub(crate) struct SharedWriteLock {
+    locked: Mutex<bool>,
+    unlocked: Condvar,
+}
+
+impl SharedWriteLock {
+    fn locked(&self) -> MutexGuard<'_, bool> {
+        // the flag can't be left inconsistent by a panic
+        self.locked.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The handle of a connection to its [`SharedWriteLock`], which remembers whether
+/// the connection holds it.
+///
+/// The lock is waited for by blocking the worker thread of the connection, like SQLite itself
+/// does with the busy timeout.
+#[derive(Debug)]
+pub(crate) struct WriteLock {
+    shared: Arc<SharedWriteLock>,
+    timeout: Duration,
+    held: bool,
+}
+
+impl WriteLock {
+    pub(crate) fn new(shared: Arc<SharedWriteLock>, timeout: Duration) -> Self {
+        Self {
+            shared,
+            timeout,
+            held: false,
+        }
+    }
+
+    /// Acquire the lock, if this connection doesn't hold it already.
+    ///
+    /// Waits for up to the busy timeout, and then fails with `SQLITE_BUSY` as SQLite would.
+    pub(crate) fn acquire(&mut self) -> Result<(), Error> {
+        if self.held {
+            return Ok(());
+        }
+
+        let locked = self.shared.locked();
+
+        let (mut locked, _) = self
+            .shared
+            .unlocked
+            .wait_timeout_while(locked, self.timeout, |locked| *locked)
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if *locked {
+            return Err(SqliteError::from_code(SQLITE_BUSY).into());
+        }
+
+        *locked = true;
+        self.held = true;
+
+        Ok(())
+    }
+
+    /// Release the lock, if this connection holds it.
+    pub(crate) fn release(&mut self) {
+        if !self.held {
+            return;
+        }
+
+        *self.shared.locked() = false;
+        self.held = false;
+
+        self.shared.unlocked.notify_one();
+    }
+}
+
+impl Drop for WriteLock {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
@@ -1,4 +1,5 @@
 pub(crate) use sqlx_core::database::{Database, HasStatementCache};
+use sqlx_core::placeholders::PlaceholderSyntax;
 
 use crate::{
     SqliteArgumentValue, SqliteArguments, SqliteColumn, SqliteConnection, SqliteQueryResult,
@@ -34,6 +35,8 @@ impl Database for Sqlite {
     const NAME: &'static str = "SQLite";
 
     const URL_SCHEMES: &'static [&'static str] = &["sqlite"];
+
+    const PLACEHOLDER_SYNTAX: PlaceholderSyntax = PlaceholderSyntax::Dollar;
 }
 
 impl HasStatementCache for Sqlite {}
@@ -46,6 +46,7 @@ use std::sync::atomic::AtomicBool;
 
 pub use arguments::{SqliteArgumentValue, SqliteArguments};
 pub use column::SqliteColumn;
+pub use connection::backup::SqliteBackup;
 pub use connection::serialize::SqliteOwnedBuf;
 #[cfg(feature = "preupdate-hook")]
 pub use connection::PreupdateHookResult;
@@ -58,7 +59,9 @@ pub use options::{
 pub use query_result::SqliteQueryResult;
 pub use row::SqliteRow;
 pub use statement::SqliteStatement;
-pub use transaction::SqliteTransactionManager;
+pub use transaction::{
+    SqliteTransactionBehavior, SqliteTransactionManager, SqliteTransactionOptions,
+};
 pub use type_info::SqliteTypeInfo;
 pub use value::{SqliteValue, SqliteValueRef};
 
@@ -132,7 +135,7 @@ pub fn describe_blocking(query: &str, database_url: &str) -> Result<Describe<Sql
     let mut conn = params.establish()?;
 
     // Execute any ancillary `PRAGMA`s
-    connection::execute::iter(&mut conn, &opts.pragma_string(), None, false)?.finish()?;
+    connection::execute::iter(&mut conn, &opts.pragma_string(), None, false, false)?.finish()?;
 
     connection::describe::describe(&mut conn, query)
 
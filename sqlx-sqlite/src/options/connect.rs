@@ -4,8 +4,10 @@ use log::LevelFilter;
 use sqlx_core::connection::ConnectOptions;
 use sqlx_core::error::Error;
 use sqlx_core::executor::Executor;
+use sqlx_core::metrics::Metrics;
 use std::fmt::Write;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -36,7 +38,7 @@ impl ConnectOptions for SqliteConnectOptions {
             let mut conn = SqliteConnection::establish(self).await?;
 
             // Execute PRAGMAs
-            conn.execute(&*self.pragma_string()).await?;
+            conn.unmetered().execute(&*self.pragma_string()).await?;
 
             if !self.collations.is_empty() {
                 let mut locked = conn.lock_handle().await?;
@@ -59,6 +61,11 @@ impl ConnectOptions for SqliteConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.log_settings.metrics(metrics);
+        self
+    }
 }
 
 impl SqliteConnectOptions {
@@ -18,6 +18,8 @@ pub use synchronous::SqliteSynchronous;
 
 use crate::common::DebugFn;
 use crate::connection::collation::Collation;
+use crate::connection::worker::SharedWorkers;
+use crate::connection::write_lock::SharedWriteLock;
 use sqlx_core::IndexMap;
 
 /// Options and flags which can be used to configure a SQLite connection.
@@ -85,6 +87,9 @@ pub struct SqliteConnectOptions {
 
     pub(crate) serialized: bool,
     pub(crate) thread_name: Arc<DebugFn<dyn Fn(u64) -> String + Send + Sync + 'static>>,
+    pub(crate) shared_workers: Option<Arc<SharedWorkers>>,
+
+    pub(crate) write_lock: Option<Arc<SharedWriteLock>>,
 
     pub(crate) optimize_on_close: OptimizeOnClose,
 
@@ -207,6 +212,8 @@ impl SqliteConnectOptions {
             collations: Default::default(),
             serialized: false,
             thread_name: Arc::new(DebugFn(|id| format!("sqlx-sqlite-worker-{id}"))),
+            shared_workers: None,
+            write_lock: None,
             command_channel_size: 50,
             row_channel_size: 50,
             optimize_on_close: OptimizeOnClose::Disabled,
@@ -425,6 +432,34 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Serialize the writes of all connections opened with these options, e.g. by a pool,
+    /// so that they don't fail with `SQLITE_BUSY` when contending for the database's write lock.
+    ///
+    /// The default setting is `false`, leaving it to SQLite to arbitrate between writers with the
+    /// [busy timeout][Self::busy_timeout], which a writer may exceed under heavy contention.
+    ///
+    /// If set to `true`, a lock shared by these options (and their clones) is held by a connection
+    /// while it may be writing: for the duration of a statement that isn't read-only, or from
+    /// such a statement or the start of an `IMMEDIATE` or `EXCLUSIVE` transaction until
+    /// the transaction ends. This includes transactions begun with a `BEGIN IMMEDIATE` or
+    /// `BEGIN EXCLUSIVE` statement. Other connections wait for the lock before writing, for up
+    /// to the busy timeout. Read-only statements don't take the lock, so with
+    /// [`SqliteJournalMode::Wal`] they still run concurrently with the writer.
+    ///
+    /// ### Note
+    /// A deferred transaction which reads and then writes may still fail with `SQLITE_BUSY`
+    /// if another connection wrote to the database in the meantime;
+    /// begin the transaction with [`SqliteTransactionBehavior::Immediate`] instead.
+    ///
+    /// A task which holds the lock in a transaction and then writes with another connection
+    /// from the same options will wait for itself until the busy timeout elapses.
+    ///
+    /// [`SqliteTransactionBehavior::Immediate`]: crate::SqliteTransactionBehavior::Immediate
+    pub fn serialized_writes(mut self, serialized_writes: bool) -> Self {
+        self.write_lock = serialized_writes.then(Default::default);
+        self
+    }
+
     /// Provide a callback to generate the name of the background worker thread.
     ///
     /// The value passed to the callback is an auto-incremented integer for use as the thread ID.
@@ -436,6 +471,36 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Share `threads` worker threads between all connections opened with these options
+    /// (and their clones), e.g. by a pool, instead of running each connection on its own thread.
+    ///
+    /// The default of `0` gives each connection a dedicated thread. Threads are started as
+    /// connections are opened, up to `threads`; a new connection goes to a thread without
+    /// connections if there is one, or else to the thread with the fewest. They exit once these
+    /// options and every connection opened with them are dropped.
+    ///
+    /// Connecting fails if [`shared_cache()`][Self::shared_cache] is enabled, which it is for
+    /// `sqlite::memory:`. Waiting for a lock of the shared cache never times out, so it would
+    /// block a thread forever if the lock is held by another connection on it.
+    ///
+    /// ### Note
+    /// A thread runs the commands of its connections one after another. Once
+    /// [`row_buffer_size()`][Self::row_buffer_size] rows of a query are waiting to be fetched,
+    /// it runs the commands of its other connections until they are; the query continues once
+    /// those commands are done.
+    ///
+    /// A connection that waits for a lock held by another connection, e.g. for the
+    /// [busy timeout][Self::busy_timeout], blocks the other connections on its thread. If the
+    /// other connection is on the same thread, it can't release the lock until the wait times
+    /// out. Avoid writing with several connections in transactions at the same time.
+    ///
+    /// This suits pools with many connections that mostly read, such as one per request with
+    /// [`SqliteJournalMode::Wal`].
+    pub fn shared_worker_threads(mut self, threads: usize) -> Self {
+        self.shared_workers = (threads > 0).then(|| Arc::new(SharedWorkers::new(threads)));
+        self
+    }
+
     /// Set the maximum number of commands to buffer for the worker thread before backpressure is
     /// applied.
     ///
@@ -117,15 +117,27 @@ impl SqliteConnectOptions {
 
     pub(crate) fn build_url(&self) -> Url {
         // https://url.spec.whatwg.org/#path-percent-encode-set
+        //
+        // A relative filename ends up in the host of the URL, so the characters that would
+        // change how the authority is parsed (such as the `:` of a port) are encoded as well,
+        // as is `%` itself so the filename is decoded back to what it was.
         static PATH_ENCODE_SET: AsciiSet = percent_encoding::CONTROLS
             .add(b' ')
             .add(b'"')
             .add(b'#')
+            .add(b'%')
+            .add(b':')
             .add(b'<')
             .add(b'>')
             .add(b'?')
+            .add(b'@')
+            .add(b'[')
+            .add(b'\\')
+            .add(b']')
+            .add(b'^')
             .add(b'`')
             .add(b'{')
+            .add(b'|')
             .add(b'}');
 
         let filename_encoded = percent_encode(
@@ -229,3 +241,27 @@ fn it_returns_the_parsed_url() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn it_round_trips_through_the_built_url() -> Result<(), Error> {
+    for url in [
+        "sqlite::memory:",
+        "sqlite://test.db?mode=ro",
+        "sqlite:///var/lib/data/test.db?mode=rwc&cache=private",
+        "sqlite://dir/100%25%20done%3F%23.db",
+        "sqlite://C:/data/test.db",
+        "sqlite://data%5B1%5D.db?immutable=true&vfs=unix-none",
+    ] {
+        let options: SqliteConnectOptions = url.parse()?;
+        let built = options.build_url();
+        let parsed: SqliteConnectOptions = built.as_str().parse()?;
+
+        assert_eq!(
+            format!("{parsed:?}"),
+            format!("{options:?}"),
+            "{url} -> {built}"
+        );
+    }
+
+    Ok(())
+}
@@ -118,6 +118,14 @@ impl VirtualStatement {
         Ok(self.current())
     }
 
+    /// Whether the current statement is the last one in the query.
+    ///
+    /// This may return `false` for the last statement if it is followed by something
+    /// other than whitespace, e.g. a comment.
+    pub fn is_last(&self) -> bool {
+        self.tail.is_empty() && self.index.is_some_and(|idx| idx + 1 == self.handles.len())
+    }
+
     pub fn current(&mut self) -> Option<PreparedStatement<'_>> {
         self.index
             .filter(|&idx| idx < self.handles.len())
@@ -2,7 +2,65 @@ use futures_core::future::BoxFuture;
 
 use crate::{Sqlite, SqliteConnection};
 use sqlx_core::error::Error;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{downcast_options, sealed, TransactionManager, TransactionOptions};
+
+/// Options for beginning a transaction with [`Connection::begin_with()`].
+///
+/// These only apply to a new transaction; they are ignored when establishing a savepoint.
+///
+/// [`Connection::begin_with()`]: sqlx_core::connection::Connection::begin_with
+#[derive(Debug, Clone, Default)]
+pub struct SqliteTransactionOptions {
+    behavior: SqliteTransactionBehavior,
+}
+
+impl SqliteTransactionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets when the transaction acquires its locks on the database.
+    pub fn behavior(mut self, behavior: SqliteTransactionBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+}
+
+/// When a transaction acquires its locks on the database.
+///
+/// See <https://www.sqlite.org/lang_transaction.html> for details.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum SqliteTransactionBehavior {
+    /// `BEGIN DEFERRED`: no lock is acquired until the database is first read or written.
+    ///
+    /// A transaction which reads and then writes may fail with `SQLITE_BUSY` if another
+    /// connection wrote to the database in the meantime.
+    #[default]
+    Deferred,
+
+    /// `BEGIN IMMEDIATE`: the write lock is acquired as soon as the transaction begins.
+    Immediate,
+
+    /// `BEGIN EXCLUSIVE`: like `Immediate`, but also prevents other connections from reading
+    /// the database outside of WAL mode.
+    Exclusive,
+}
+
+impl SqliteTransactionBehavior {
+    pub(crate) fn begin_sql(&self) -> &'static str {
+        match self {
+            SqliteTransactionBehavior::Deferred => "BEGIN DEFERRED",
+            SqliteTransactionBehavior::Immediate => "BEGIN IMMEDIATE",
+            SqliteTransactionBehavior::Exclusive => "BEGIN EXCLUSIVE",
+        }
+    }
+}
+
+impl sealed::Sealed for SqliteTransactionOptions {}
+
+impl TransactionOptions for SqliteTransactionOptions {
+    type Database = Sqlite;
+}
 
 /// Implementation of [`TransactionManager`] for SQLite.
 pub struct SqliteTransactionManager;
@@ -11,7 +69,18 @@ impl TransactionManager for SqliteTransactionManager {
     type Database = Sqlite;
 
     fn begin(conn: &mut SqliteConnection) -> BoxFuture<'_, Result<(), Error>> {
-        Box::pin(conn.worker.begin())
+        Box::pin(conn.worker.begin(SqliteTransactionBehavior::default()))
+    }
+
+    fn begin_with<O>(conn: &mut SqliteConnection, options: O) -> BoxFuture<'_, Result<(), Error>>
+    where
+        O: TransactionOptions<Database = Sqlite>,
+    {
+        Box::pin(async move {
+            let options: SqliteTransactionOptions = downcast_options(options)?;
+
+            conn.worker.begin(options.behavior).await
+        })
     }
 
     fn commit(conn: &mut SqliteConnection) -> BoxFuture<'_, Result<(), Error>> {
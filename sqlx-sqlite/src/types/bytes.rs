@@ -69,6 +69,41 @@ impl Decode<'_, Sqlite> for Box<[u8]> {
     }
 }
 
+impl<const N: usize> Type<Sqlite> for [u8; N] {
+    fn type_info() -> SqliteTypeInfo {
+        <&[u8] as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <&[u8] as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<const N: usize> Encode<'_, Sqlite> for [u8; N] {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Blob(Cow::Owned(self.to_vec())));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<const N: usize> Decode<'_, Sqlite> for [u8; N] {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let bytes = value.blob();
+
+        bytes.try_into().map_err(|_| {
+            format!(
+                "expected {N} bytes to decode `[u8; {N}]`, got {} bytes",
+                bytes.len()
+            )
+            .into()
+        })
+    }
+}
+
 impl Type<Sqlite> for Vec<u8> {
     fn type_info() -> SqliteTypeInfo {
         <&[u8] as Type<Sqlite>>::type_info()
@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use crate::types::datetime::{SqliteDateTime, UnixTimeUnit};
 use crate::value::ValueRef;
 use crate::{
     decode::Decode,
@@ -109,7 +110,10 @@ impl<'r> Decode<'r, Sqlite> for DateTime<FixedOffset> {
 fn decode_datetime(value: SqliteValueRef<'_>) -> Result<DateTime<FixedOffset>, BoxDynError> {
     let dt = match value.type_info().0 {
         DataType::Text => decode_datetime_from_text(value.text()?),
-        DataType::Int4 | DataType::Integer => decode_datetime_from_int(value.int64()),
+        DataType::Int4 | DataType::Integer => {
+            let timestamp = value.int64();
+            decode_datetime_from_int(timestamp, UnixTimeUnit::infer(timestamp))
+        }
         DataType::Float => decode_datetime_from_float(value.double()),
 
         _ => None,
@@ -159,8 +163,58 @@ fn decode_datetime_from_text(value: &str) -> Option<DateTime<FixedOffset>> {
     None
 }
 
-fn decode_datetime_from_int(value: i64) -> Option<DateTime<FixedOffset>> {
-    Utc.fix().timestamp_opt(value, 0).single()
+fn decode_datetime_from_int(value: i64, unit: UnixTimeUnit) -> Option<DateTime<FixedOffset>> {
+    match unit {
+        UnixTimeUnit::Seconds => Utc.fix().timestamp_opt(value, 0).single(),
+        UnixTimeUnit::Millis => Utc.fix().timestamp_millis_opt(value).single(),
+    }
+}
+
+fn to_unix_timestamp(dt: &NaiveDateTime, unit: UnixTimeUnit) -> i64 {
+    match unit {
+        UnixTimeUnit::Seconds => dt.and_utc().timestamp(),
+        UnixTimeUnit::Millis => dt.and_utc().timestamp_millis(),
+    }
+}
+
+impl SqliteDateTime for NaiveDateTime {
+    fn from_unix_timestamp(timestamp: i64, unit: UnixTimeUnit) -> Option<Self> {
+        Some(decode_datetime_from_int(timestamp, unit)?.naive_utc())
+    }
+
+    fn to_unix_timestamp(&self, unit: UnixTimeUnit) -> Option<i64> {
+        Some(to_unix_timestamp(self, unit))
+    }
+}
+
+impl SqliteDateTime for DateTime<Utc> {
+    fn from_unix_timestamp(timestamp: i64, unit: UnixTimeUnit) -> Option<Self> {
+        Some(decode_datetime_from_int(timestamp, unit)?.to_utc())
+    }
+
+    fn to_unix_timestamp(&self, unit: UnixTimeUnit) -> Option<i64> {
+        Some(to_unix_timestamp(&self.naive_utc(), unit))
+    }
+}
+
+impl SqliteDateTime for DateTime<Local> {
+    fn from_unix_timestamp(timestamp: i64, unit: UnixTimeUnit) -> Option<Self> {
+        Some(Local.from_utc_datetime(&decode_datetime_from_int(timestamp, unit)?.naive_utc()))
+    }
+
+    fn to_unix_timestamp(&self, unit: UnixTimeUnit) -> Option<i64> {
+        Some(to_unix_timestamp(&self.naive_utc(), unit))
+    }
+}
+
+impl SqliteDateTime for DateTime<FixedOffset> {
+    fn from_unix_timestamp(timestamp: i64, unit: UnixTimeUnit) -> Option<Self> {
+        decode_datetime_from_int(timestamp, unit)
+    }
+
+    fn to_unix_timestamp(&self, unit: UnixTimeUnit) -> Option<i64> {
+        Some(to_unix_timestamp(&self.naive_utc(), unit))
+    }
 }
 
 fn decode_datetime_from_float(value: f64) -> Option<DateTime<FixedOffset>> {
@@ -0,0 +1,163 @@
+//! Wrappers that choose how a date-time is stored in SQLite.
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::value::ValueRef;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+/// The smallest magnitude of an `INTEGER` date-time that is taken to be in milliseconds.
+///
+/// As seconds, this is in the year 5138; as milliseconds, it is in March 1973.
+const MIN_UNIX_MILLIS: u64 = 100_000_000_000;
+
+mod sealed {
+    /// The unit of a Unix timestamp.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum UnixTimeUnit {
+        Seconds,
+        Millis,
+    }
+
+    /// The date-time types that can be wrapped by [`SqliteDateTimeText`][super::SqliteDateTimeText],
+    /// [`SqliteDateTimeUnixSeconds`][super::SqliteDateTimeUnixSeconds] and
+    /// [`SqliteDateTimeUnixMillis`][super::SqliteDateTimeUnixMillis].
+    pub trait SqliteDateTime: Sized {
+        fn from_unix_timestamp(timestamp: i64, unit: UnixTimeUnit) -> Option<Self>;
+
+        fn to_unix_timestamp(&self, unit: UnixTimeUnit) -> Option<i64>;
+    }
+}
+
+pub(crate) use sealed::{SqliteDateTime, UnixTimeUnit};
+
+impl UnixTimeUnit {
+    /// Infer the unit of a timestamp from its magnitude.
+    pub(crate) fn infer(timestamp: i64) -> Self {
+        if timestamp.unsigned_abs() >= MIN_UNIX_MILLIS {
+            UnixTimeUnit::Millis
+        } else {
+            UnixTimeUnit::Seconds
+        }
+    }
+}
+
+/// Store a date-time as a `TEXT` value.
+///
+/// This is how date-times are stored without a wrapper; see [the module documentation][super]
+/// for the format. The wrapper only makes the choice explicit.
+///
+/// Supported for the `chrono` types `NaiveDateTime`, `DateTime<Utc>`, `DateTime<Local>` and
+/// `DateTime<FixedOffset>`, and the `time` types `PrimitiveDateTime` and `OffsetDateTime`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SqliteDateTimeText<T>(pub T);
+
+/// Store a date-time as an `INTEGER` of seconds since January 1, 1970 00:00 UTC,
+/// as if returned from the `unixepoch()` function.
+///
+/// Fractional seconds are truncated towards the past, and naive date-times are taken to be in UTC.
+/// An `INTEGER` is always decoded as seconds, regardless of its magnitude.
+///
+/// Supported for the same types as [`SqliteDateTimeText`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SqliteDateTimeUnixSeconds<T>(pub T);
+
+/// Store a date-time as an `INTEGER` of milliseconds since January 1, 1970 00:00 UTC,
+/// as used by JavaScript's `Date.now()` and Java's `System.currentTimeMillis()`.
+///
+/// Fractional milliseconds are truncated towards the past, and naive date-times are taken
+/// to be in UTC. An `INTEGER` is always decoded as milliseconds, regardless of its magnitude.
+///
+/// Supported for the same types as [`SqliteDateTimeText`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SqliteDateTimeUnixMillis<T>(pub T);
+
+impl<T> Type<Sqlite> for SqliteDateTimeText<T>
+where
+    T: SqliteDateTime + Type<Sqlite>,
+{
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Text)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+impl<'q, T> Encode<'q, Sqlite> for SqliteDateTimeText<T>
+where
+    T: SqliteDateTime + Encode<'q, Sqlite>,
+{
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> Result<IsNull, BoxDynError> {
+        self.0.encode_by_ref(buf)
+    }
+}
+
+impl<'r, T> Decode<'r, Sqlite> for SqliteDateTimeText<T>
+where
+    T: SqliteDateTime + Decode<'r, Sqlite>,
+{
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        T::decode(value).map(Self)
+    }
+}
+
+macro_rules! impl_unix_timestamp {
+    ($name:ident, $unit:expr) => {
+        impl<T> Type<Sqlite> for $name<T>
+        where
+            T: SqliteDateTime + Type<Sqlite>,
+        {
+            fn type_info() -> SqliteTypeInfo {
+                SqliteTypeInfo(DataType::Integer)
+            }
+
+            fn compatible(ty: &SqliteTypeInfo) -> bool {
+                T::compatible(ty)
+            }
+        }
+
+        impl<T> Encode<'_, Sqlite> for $name<T>
+        where
+            T: SqliteDateTime,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut Vec<SqliteArgumentValue<'_>>,
+            ) -> Result<IsNull, BoxDynError> {
+                let timestamp = self
+                    .0
+                    .to_unix_timestamp($unit)
+                    .ok_or("date-time out of range for a Unix timestamp")?;
+
+                Encode::<Sqlite>::encode(timestamp, buf)
+            }
+        }
+
+        impl<'r, T> Decode<'r, Sqlite> for $name<T>
+        where
+            T: SqliteDateTime + Decode<'r, Sqlite>,
+        {
+            fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+                match value.type_info().0 {
+                    DataType::Int4 | DataType::Integer => {
+                        let timestamp = value.int64();
+
+                        T::from_unix_timestamp(timestamp, $unit)
+                            .map(Self)
+                            .ok_or_else(|| format!("invalid Unix timestamp: {timestamp}").into())
+                    }
+
+                    // date-times stored in another format are still decoded as usual
+                    _ => T::decode(value).map(Self),
+                }
+            }
+        }
+    };
+}
+
+impl_unix_timestamp!(SqliteDateTimeUnixSeconds, UnixTimeUnit::Seconds);
+impl_unix_timestamp!(SqliteDateTimeUnixMillis, UnixTimeUnit::Millis);
@@ -16,7 +16,8 @@
 //! | `f32`                                 | REAL                                                 |
 //! | `f64`                                 | REAL                                                 |
 //! | `&str`, [`String`]                    | TEXT                                                 |
-//! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
+//! | `char`                                | TEXT (a single character)                            |
+//! | `&[u8]`, `Vec<u8>`, `[u8; N]`         | BLOB                                                 |
 //!
 //! #### Note: Unsigned Integers
 //! Decoding of unsigned integer types simply performs a checked conversion
@@ -60,13 +61,26 @@
 //!   A number of possible formats are tried; see `sqlx-sqlite/src/types/chrono.rs` for the current
 //!   set of formats.
 //! * If `INTEGER`, it is expected to be the number of seconds since January 1, 1970 00:00 UTC,
-//!   as if returned from the `unixepoch()` function (without the `subsec` modifier).
+//!   as if returned from the `unixepoch()` function (without the `subsec` modifier),
+//!   or the number of milliseconds if its magnitude is at least 100,000,000,000
+//!   (which is in the year 5138 as seconds, and in 1973 as milliseconds).
 //! * If `REAL`, it is expected to be the (possibly fractional) number of days since the Julian epoch,
 //!   November 24, 4714 BCE 12:00 UTC, as if returned from the `julianday()` function.
 //!
 //! These types will always encode to a datetime string, either
 //! with a timezone offset (`DateTime<Tz>` for any `Tz: TimeZone`) or without (`NaiveDateTime`).
 //!
+//! ##### NOTE: Choosing the storage format
+//! To store `NaiveDateTime`, `DateTime<Utc>`, `DateTime<Local>` or `DateTime<FixedOffset>`
+//! in another format, wrap it in one of:
+//!
+//! * [`SqliteDateTimeText`]: a datetime string, as without a wrapper.
+//! * [`SqliteDateTimeUnixSeconds`]: an `INTEGER` of seconds since the Unix epoch.
+//! * [`SqliteDateTimeUnixMillis`]: an `INTEGER` of milliseconds since the Unix epoch.
+//!
+//! The Unix timestamp wrappers also decode an `INTEGER` in their unit regardless of its magnitude,
+//! which is needed for millisecond timestamps before 1973.
+//!
 //! ##### NOTE: `CURRENT_TIMESTAMP` and comparison/interoperability of `DATETIME` values
 //! As stated previously, `DateTime<Tz>` always encodes to a date-time string
 //! _with_ a timezone offset,
@@ -102,17 +116,17 @@
 //!
 //! | Rust type                             | Sqlite type(s)                                       |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `time::PrimitiveDateTime`             | DATETIME (TEXT, INTEGER)                             |
-//! | `time::OffsetDateTime`                | DATETIME (TEXT, INTEGER)                             |
+//! | `time::PrimitiveDateTime`             | DATETIME (TEXT, INTEGER, REAL)                       |
+//! | `time::OffsetDateTime`                | DATETIME (TEXT, INTEGER, REAL)                       |
 //! | `time::Date`                          | DATE (TEXT only)                                     |
 //! | `time::Time`                          | TIME (TEXT only)                                     |
 //!
 //! ##### NOTE: `DATETIME` conversions
-//! The behavior here is identical to the corresponding `chrono` types, minus the support for `REAL`
-//! values as Julian days (it's just not implemented).
+//! The behavior here is identical to the corresponding `chrono` types.
 //!
 //! `PrimitiveDateTime` and `OffsetDateTime` will always encode to a datetime string, either
-//! with a timezone offset (`OffsetDateTime`) or without (`PrimitiveDateTime`).
+//! with a timezone offset (`OffsetDateTime`) or without (`PrimitiveDateTime`),
+//! unless wrapped in [`SqliteDateTimeUnixSeconds`] or [`SqliteDateTimeUnixMillis`].
 //!
 //! ##### NOTE: `CURRENT_TIMESTAMP` and comparison/interoperability of `DATETIME` values
 //! As stated previously, `OffsetDateTime` always encodes to a datetime string _with_ a timezone offset,
@@ -161,6 +175,8 @@
 //! | [`Json<T>`]                           | TEXT                                                 |
 //! | `serde_json::JsonValue`               | TEXT                                                 |
 //! | `&serde_json::value::RawValue`        | TEXT                                                 |
+//! | `HashMap<String, serde_json::Value>`  | TEXT                                                 |
+//! | `BTreeMap<String, serde_json::Value>` | TEXT                                                 |
 //!
 //! # Nullable
 //!
@@ -227,6 +243,8 @@ mod bool;
 mod bytes;
 #[cfg(feature = "chrono")]
 mod chrono;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
 mod float;
 mod int;
 #[cfg(feature = "json")]
@@ -238,3 +256,6 @@ mod time;
 mod uint;
 #[cfg(feature = "uuid")]
 mod uuid;
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use datetime::{SqliteDateTimeText, SqliteDateTimeUnixMillis, SqliteDateTimeUnixSeconds};
@@ -122,3 +122,36 @@ impl<'r> Decode<'r, Sqlite> for Cow<'r, str> {
         value.text().map(Cow::Borrowed)
     }
 }
+
+impl Type<Sqlite> for char {
+    fn type_info() -> SqliteTypeInfo {
+        <&str as Type<Sqlite>>::type_info()
+    }
+}
+
+impl Encode<'_, Sqlite> for char {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Text(Cow::Owned(self.to_string())));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Sqlite> for char {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let s = value.text()?;
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(format!(
+                "expected a single character, got {} characters",
+                s.chars().count()
+            )
+            .into()),
+        }
+    }
+}
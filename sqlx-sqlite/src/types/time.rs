@@ -1,3 +1,4 @@
+use crate::types::datetime::{SqliteDateTime, UnixTimeUnit};
 use crate::value::ValueRef;
 use crate::{
     decode::Decode,
@@ -29,7 +30,11 @@ impl Type<Sqlite> for PrimitiveDateTime {
     fn compatible(ty: &SqliteTypeInfo) -> bool {
         matches!(
             ty.0,
-            DataType::Datetime | DataType::Text | DataType::Integer | DataType::Int4
+            DataType::Datetime
+                | DataType::Text
+                | DataType::Integer
+                | DataType::Int4
+                | DataType::Float
         )
     }
 }
@@ -123,8 +128,10 @@ fn decode_offset_datetime(value: SqliteValueRef<'_>) -> Result<OffsetDateTime, B
     let dt = match value.type_info().0 {
         DataType::Text => decode_offset_datetime_from_text(value.text()?),
         DataType::Int4 | DataType::Integer => {
-            Some(OffsetDateTime::from_unix_timestamp(value.int64())?)
+            let timestamp = value.int64();
+            decode_offset_datetime_from_int(timestamp, UnixTimeUnit::infer(timestamp))
         }
+        DataType::Float => decode_offset_datetime_from_float(value.double()),
 
         _ => None,
     };
@@ -152,13 +159,74 @@ fn decode_offset_datetime_from_text(value: &str) -> Option<OffsetDateTime> {
     None
 }
 
+fn decode_offset_datetime_from_int(value: i64, unit: UnixTimeUnit) -> Option<OffsetDateTime> {
+    match unit {
+        UnixTimeUnit::Seconds => OffsetDateTime::from_unix_timestamp(value).ok(),
+        UnixTimeUnit::Millis => {
+            OffsetDateTime::from_unix_timestamp_nanos(i128::from(value) * 1_000_000).ok()
+        }
+    }
+}
+
+fn decode_offset_datetime_from_float(value: f64) -> Option<OffsetDateTime> {
+    let epoch_in_julian_days = 2_440_587.5;
+    let seconds_in_day = 86400.0;
+    let timestamp = (value - epoch_in_julian_days) * seconds_in_day;
+
+    if !timestamp.is_finite() {
+        return None;
+    }
+
+    // We checked above if the value is infinite or NaN, and out-of-range values saturate
+    // and are then rejected by `from_unix_timestamp_nanos()`
+    #[allow(clippy::cast_possible_truncation)]
+    let nanos = (timestamp * 1E9) as i128;
+
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+}
+
+fn to_unix_timestamp(dt: OffsetDateTime, unit: UnixTimeUnit) -> Option<i64> {
+    match unit {
+        UnixTimeUnit::Seconds => Some(dt.unix_timestamp()),
+        UnixTimeUnit::Millis => dt
+            .unix_timestamp_nanos()
+            .div_euclid(1_000_000)
+            .try_into()
+            .ok(),
+    }
+}
+
+impl SqliteDateTime for OffsetDateTime {
+    fn from_unix_timestamp(timestamp: i64, unit: UnixTimeUnit) -> Option<Self> {
+        decode_offset_datetime_from_int(timestamp, unit)
+    }
+
+    fn to_unix_timestamp(&self, unit: UnixTimeUnit) -> Option<i64> {
+        to_unix_timestamp(*self, unit)
+    }
+}
+
+impl SqliteDateTime for PrimitiveDateTime {
+    fn from_unix_timestamp(timestamp: i64, unit: UnixTimeUnit) -> Option<Self> {
+        decode_offset_datetime_from_int(timestamp, unit)
+            .map(|dt| PrimitiveDateTime::new(dt.date(), dt.time()))
+    }
+
+    fn to_unix_timestamp(&self, unit: UnixTimeUnit) -> Option<i64> {
+        to_unix_timestamp(self.assume_utc(), unit)
+    }
+}
+
 fn decode_datetime(value: SqliteValueRef<'_>) -> Result<PrimitiveDateTime, BoxDynError> {
     let dt = match value.type_info().0 {
         DataType::Text => decode_datetime_from_text(value.text()?),
         DataType::Int4 | DataType::Integer => {
-            let parsed = OffsetDateTime::from_unix_timestamp(value.int64()).unwrap();
-            Some(PrimitiveDateTime::new(parsed.date(), parsed.time()))
+            let timestamp = value.int64();
+            decode_offset_datetime_from_int(timestamp, UnixTimeUnit::infer(timestamp))
+                .map(|dt| PrimitiveDateTime::new(dt.date(), dt.time()))
         }
+        DataType::Float => decode_offset_datetime_from_float(value.double())
+            .map(|dt| PrimitiveDateTime::new(dt.date(), dt.time())),
 
         _ => None,
     };
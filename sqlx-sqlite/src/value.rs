@@ -10,6 +10,7 @@ use libsqlite3_sys::{
     sqlite3_value_dup, sqlite3_value_free, sqlite3_value_int64, sqlite3_value_type, SQLITE_NULL,
 };
 
+use sqlx_core::value::{DynamicValue, ToDynamicValue};
 pub(crate) use sqlx_core::value::{Value, ValueRef};
 
 use crate::error::BoxDynError;
@@ -97,6 +98,29 @@ impl<'r> ValueRef<'r> for SqliteValueRef<'r> {
     }
 }
 
+/// Values are converted by their storage class, as SQLite columns have no fixed type:
+/// `INTEGER` to [`DynamicValue::Int`], `REAL` to [`DynamicValue::Double`], `TEXT` to
+/// [`DynamicValue::Text`] and `BLOB` to [`DynamicValue::Bytes`].
+impl ToDynamicValue for SqliteValueRef<'_> {
+    fn to_dynamic(&self) -> Result<DynamicValue, BoxDynError> {
+        if self.is_null() {
+            return Ok(DynamicValue::Null);
+        }
+
+        Ok(match self.type_info().0 {
+            DataType::Null => DynamicValue::Null,
+            DataType::Integer | DataType::Int4 | DataType::Bool => DynamicValue::Int(self.int64()),
+            DataType::Float => DynamicValue::Double(self.double()),
+            DataType::Blob => DynamicValue::Bytes(self.blob().to_vec()),
+            DataType::Text
+            | DataType::Numeric
+            | DataType::Date
+            | DataType::Time
+            | DataType::Datetime => DynamicValue::Text(self.text()?.to_owned()),
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct SqliteValue(Arc<ValueHandle<'static>>);
 
@@ -1,6 +1,13 @@
+use anyhow::Context;
+use futures_util::TryStreamExt;
 use sqlx::pool::PoolOptions;
-use sqlx::{Connection, Database, Pool};
+use sqlx::{
+    ColumnIndex, Connection, Database, Decode, Executor, Pool, Row, TransactionManager, Type,
+};
 use std::env;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
 
 pub fn setup_if_needed() {
     let _ = dotenvy::dotenv();
@@ -36,6 +43,326 @@ where
     Ok(pool)
 }
 
+// A connection with fixtures applied, from `with_fixtures()` or `with_committed_fixtures()`
+pub struct FixtureConnection<DB: Database> {
+    conn: DB::Connection,
+    // whether the fixtures and everything after them are in a transaction to roll back
+    rollback: bool,
+}
+
+impl<DB: Database> Deref for FixtureConnection<DB> {
+    type Target = DB::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.conn
+    }
+}
+
+impl<DB: Database> DerefMut for FixtureConnection<DB> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.conn
+    }
+}
+
+impl<DB: Database> Drop for FixtureConnection<DB> {
+    fn drop(&mut self) {
+        if self.rollback {
+            // the server rolls back the transaction anyway when the connection is closed
+            DB::TransactionManager::start_rollback(&mut self.conn);
+        }
+    }
+}
+
+// Make a new connection and run `tests/fixtures/{name}.sql` for each of `fixtures` in order,
+// in a transaction that is rolled back when the connection is dropped
+//
+// Note that MySQL commits implicitly after most DDL statements, except for temporary tables
+pub async fn with_fixtures<DB>(fixtures: &[&str]) -> anyhow::Result<FixtureConnection<DB>>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    connect_with_fixtures(fixtures, true).await
+}
+
+// Make a new connection and run `tests/fixtures/{name}.sql` for each of `fixtures` in order,
+// for tests that need to commit
+pub async fn with_committed_fixtures<DB>(fixtures: &[&str]) -> anyhow::Result<FixtureConnection<DB>>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    connect_with_fixtures(fixtures, false).await
+}
+
+async fn connect_with_fixtures<DB>(
+    fixtures: &[&str],
+    rollback: bool,
+) -> anyhow::Result<FixtureConnection<DB>>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    let mut conn = new::<DB>().await?;
+
+    if rollback {
+        DB::TransactionManager::begin(&mut conn).await?;
+    }
+
+    // from here on, the transaction is rolled back if a fixture fails
+    let mut conn = FixtureConnection { conn, rollback };
+
+    for name in fixtures {
+        run_fixture(&mut conn.conn, name).await?;
+    }
+
+    Ok(conn)
+}
+
+async fn run_fixture<DB>(conn: &mut DB::Connection, name: &str) -> anyhow::Result<()>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    let path = env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join("tests/fixtures")
+        .join(format!("{name}.sql"));
+
+    let sql = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read fixture {}", path.display()))?;
+
+    // each statement returns one result, so the failing one is the one after the last result
+    let mut results = sqlx::raw_sql(&sql).execute_many(&mut *conn);
+    let mut statement = 1;
+
+    while results
+        .try_next()
+        .await
+        .with_context(|| {
+            format!(
+                "statement #{statement} of fixture {} failed",
+                path.display()
+            )
+        })?
+        .is_some()
+    {
+        statement += 1;
+    }
+
+    Ok(())
+}
+
+// Test that `fetch_optional()` returns the first row of the first result set that has one,
+// and still executes the statements after it
+pub async fn fetch_optional_from_multiple_statements<DB>() -> anyhow::Result<()>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    i32: Type<DB> + for<'r> Decode<'r, DB>,
+    i64: Type<DB> + for<'r> Decode<'r, DB>,
+    usize: ColumnIndex<DB::Row>,
+{
+    let mut conn = new::<DB>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE fetch_optional_multi (id INT)")
+        .await?;
+
+    // statements without rows are skipped over
+    let row = sqlx::raw_sql("UPDATE fetch_optional_multi SET id = id + 1; SELECT 1 AS n;")
+        .fetch_optional(&mut conn)
+        .await?;
+
+    assert_eq!(row.map(|row| row.get::<i32, _>(0)), Some(1));
+
+    // as are result sets without rows
+    let row = sqlx::raw_sql("SELECT id FROM fetch_optional_multi; SELECT 2 AS n; SELECT 3 AS n;")
+        .fetch_optional(&mut conn)
+        .await?;
+
+    assert_eq!(row.map(|row| row.get::<i32, _>(0)), Some(2));
+
+    // statements after the first row are still executed
+    let row = sqlx::raw_sql("SELECT 1 AS n; INSERT INTO fetch_optional_multi VALUES (10);")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i32, _>(0), 1);
+
+    let row = sqlx::raw_sql("SELECT COUNT(*) FROM fetch_optional_multi")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 1);
+
+    // and their errors are returned
+    let res = sqlx::raw_sql("SELECT 1 AS n; SELECT * FROM fetch_optional_missing;")
+        .fetch_optional(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    // without affecting the next query
+    let row = sqlx::raw_sql("SELECT 4").fetch_one(&mut conn).await?;
+
+    assert_eq!(row.get::<i32, _>(0), 4);
+
+    Ok(())
+}
+
+// Test that closing a connection rolls back a transaction that was dropped on it first,
+// by checking that `other` can write to the table right after the connection is closed
+//
+// `connect` opens the connection to close. `check` is run by `other` in a transaction and must
+// fail instead of waiting if the table is still locked by the dropped transaction.
+pub async fn rolls_back_dropped_transaction_before_close<DB, F, Fut>(
+    mut other: DB::Connection,
+    mut connect: F,
+    check: &str,
+) -> anyhow::Result<()>
+where
+    DB: Database,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<DB::Connection>>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    i64: Type<DB> + for<'r> Decode<'r, DB>,
+    usize: ColumnIndex<DB::Row>,
+{
+    // visible to both connections, so it can't be a temporary table
+    other.execute("DROP TABLE IF EXISTS close_test").await?;
+    other
+        .execute("CREATE TABLE close_test (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    for _ in 0..10 {
+        let mut conn = connect().await?;
+
+        let mut tx = conn.begin().await?;
+        tx.execute("INSERT INTO close_test (id) VALUES (1)").await?;
+        drop(tx);
+
+        conn.close().await?;
+
+        let mut tx = other.begin().await?;
+        tx.execute(check).await?;
+        tx.rollback().await?;
+
+        let row = sqlx::raw_sql("SELECT COUNT(*) FROM close_test")
+            .fetch_one(&mut other)
+            .await?;
+        assert_eq!(row.get::<i64, _>(0), 0);
+    }
+
+    other.execute("DROP TABLE close_test").await?;
+
+    Ok(())
+}
+
+// Test the error variants returned for failures common to all drivers
+pub async fn structured_errors<DB>() -> anyhow::Result<()>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    i32: Type<DB> + for<'r> Decode<'r, DB>,
+    String: Type<DB> + for<'r> Decode<'r, DB>,
+    usize: ColumnIndex<DB::Row>,
+    for<'s> &'s str: ColumnIndex<DB::Row>,
+{
+    let mut conn = new::<DB>().await?;
+
+    let err = sqlx::raw_sql("SELECT 1 AS id WHERE 1 = 0")
+        .fetch_one(&mut conn)
+        .await
+        .err()
+        .expect("query should return no rows");
+    assert!(matches!(err, sqlx::Error::RowNotFound), "{err:?}");
+
+    let row = sqlx::raw_sql("SELECT 1 AS id").fetch_one(&mut conn).await?;
+
+    let err = row.try_get::<i32, _>("name").unwrap_err();
+    assert!(
+        matches!(&err, sqlx::Error::ColumnNotFound(name) if name == "name"),
+        "{err:?}"
+    );
+
+    let err = row.try_get::<i32, _>(1).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            sqlx::Error::ColumnIndexOutOfBounds { index: 1, len: 1 }
+        ),
+        "{err:?}"
+    );
+
+    let err = row.try_get::<String, _>("id").unwrap_err();
+    assert!(
+        matches!(&err, sqlx::Error::ColumnDecode { index, .. } if index == "\"id\""),
+        "{err:?}"
+    );
+
+    let err = sqlx::raw_sql("SELECT * FROM this_table_does_not_exist")
+        .fetch_all(&mut conn)
+        .await
+        .err()
+        .expect("query should fail");
+    assert!(err.as_database_error().is_some(), "{err:?}");
+    assert!(err.into_database_error().is_some());
+
+    Ok(())
+}
+
+// Test that `Upsert` inserts new rows and updates the ones they conflict with
+//
+// This is a macro as the query built by `Upsert` can't be executed from a function
+// that is generic over the database.
+#[macro_export]
+macro_rules! test_upsert_conflicting_rows {
+    ($name:ident($db:ident)) => {
+        #[sqlx_macros::test]
+        async fn $name() -> anyhow::Result<()> {
+            use sqlx::Executor;
+
+            let mut conn = sqlx_test::new::<$db>().await?;
+
+            conn.execute(
+                "CREATE TEMPORARY TABLE upsert_users (id INT PRIMARY KEY, name TEXT NOT NULL)",
+            )
+            .await?;
+
+            for rows in [[(1, "Alice"), (2, "Bob")], [(2, "Robert"), (3, "Carol")]] {
+                sqlx::Upsert::<$db>::into("upsert_users")
+                    .columns(&["id", "name"])
+                    .values(rows, |mut b, (id, name)| {
+                        b.push_bind(id).push_bind(name);
+                    })
+                    .conflict(&["id"])
+                    .update(&["name"])
+                    .into_query_builder()
+                    .build()
+                    .execute(&mut conn)
+                    .await?;
+            }
+
+            let users: Vec<(i32, String)> =
+                sqlx::query_as("SELECT id, name FROM upsert_users ORDER BY id")
+                    .fetch_all(&mut conn)
+                    .await?;
+
+            assert_eq!(
+                users,
+                [
+                    (1, "Alice".to_owned()),
+                    (2, "Robert".to_owned()),
+                    (3, "Carol".to_owned())
+                ]
+            );
+
+            Ok(())
+        }
+    };
+}
+
 // Test type encoding and decoding
 #[macro_export]
 macro_rules! test_type {
@@ -51,6 +378,15 @@ macro_rules! test_type {
         }
     };
 
+    // For types that can't be compared with the default expression, e.g. `{0} <=> ?` for MySQL,
+    // `compare` replaces it with an expression of `{0}` and the first parameter.
+    ($name:ident<$ty:ty>($db:ident, compare = $compare:literal, $($text:literal == $value:expr),+ $(,)?)) => {
+        paste::item! {
+            $crate::__test_prepared_type!($name<$ty>($db, $crate::[< $db _query_for_test_prepared_type_compare >]!($compare), $($text == $value),+));
+            $crate::test_unprepared_type!($name<$ty>($db, $($text == $value),+));
+        }
+    };
+
     ($name:ident<$ty:ty>($db:ident, $($text:literal ~= $value:expr),+ $(,)?)) => {
         paste::item! {
             $crate::__test_prepared_type!($name<$ty>($db, $crate::[< $db _query_for_test_prepared_geometric_type >]!(), $($text == $value),+));
@@ -68,6 +404,69 @@ macro_rules! test_type {
     };
 }
 
+// Test that `NULL` decodes as `None`, and that `None` is encoded as `NULL`
+//
+// Every `$text` must be an SQL expression that evaluates to `NULL` of the type under test.
+#[macro_export]
+macro_rules! test_null_type {
+    ($name:ident<$ty:ty>($db:ident, $($text:literal),+ $(,)?)) => {
+        paste::item! {
+            #[sqlx_macros::test]
+            async fn [< test_null_type_ $name >] () -> anyhow::Result<()> {
+                use sqlx::prelude::*;
+                use futures::TryStreamExt;
+
+                let mut conn = sqlx_test::new::<$db>().await?;
+
+                $(
+                    let query = format!($crate::[< $db _query_for_test_null_type >]!(), $text);
+                    println!("{query}");
+
+                    let row = sqlx::query(&query)
+                        .bind(None::<$ty>)
+                        .bind(None::<$ty>)
+                        .fetch_one(&mut conn)
+                        .await?;
+
+                    let matches: i32 = row.try_get(0)?;
+                    let returned: Option<$ty> = row.try_get(1)?;
+                    let round_trip: Option<$ty> = row.try_get(2)?;
+
+                    assert!(matches != 0,
+                            "[1] expected NULL; query: {}\n\
+                             as returned: {:?}\n\
+                             round-trip: {:?}",
+                            query, returned, round_trip);
+
+                    assert!(returned.is_none(),
+                            "[2] expected NULL to decode as None; query: {}\n\
+                             as returned: {:?}",
+                            query, returned);
+
+                    assert!(round_trip.is_none(),
+                            "[3] expected None to be bound as NULL; query: {}\n\
+                             round-trip: {:?}",
+                            query, round_trip);
+
+                    let query = format!("SELECT {}", $text);
+                    let mut s = conn.fetch(&*query);
+                    let row = s.try_next().await?.unwrap();
+                    let rec = row.try_get::<Option<$ty>, _>(0)?;
+
+                    assert!(rec.is_none(),
+                            "[4] expected NULL to decode as None; query: {}\n\
+                             as returned: {:?}",
+                            query, rec);
+
+                    drop(s);
+                )+
+
+                Ok(())
+            }
+        }
+    };
+}
+
 // Test type decoding only
 #[macro_export]
 macro_rules! test_decode_type {
@@ -118,7 +517,7 @@ macro_rules! test_unprepared_type {
                     let row = s.try_next().await?.unwrap();
                     let rec = row.try_get::<$ty, _>(0)?;
 
-                    assert_eq!($value, rec);
+                    assert_eq!($value, rec, "DB value mismatch; query: {}", query);
 
                     drop(s);
                 )+
@@ -149,7 +548,7 @@ macro_rules! __test_prepared_decode_type {
 
                     let rec: $ty = row.try_get(0)?;
 
-                    assert_eq!($value, rec);
+                    assert_eq!($value, rec, "DB value mismatch; query: {}", query);
                 )+
 
                 Ok(())
@@ -184,22 +583,25 @@ macro_rules! __test_prepared_type {
                     let round_trip: $ty = row.try_get(2)?;
 
                     assert!(matches != 0,
-                            "[1] DB value mismatch; given value: {:?}\n\
+                            "[1] DB value mismatch; query: {}\n\
+                             given value: {:?}\n\
                              as returned: {:?}\n\
                              round-trip: {:?}",
-                            $value, returned, round_trip);
+                            query, $value, returned, round_trip);
 
                     assert_eq!($value, returned,
-                            "[2] DB value mismatch; given value: {:?}\n\
+                            "[2] DB value mismatch; query: {}\n\
+                                     given value: {:?}\n\
                                      as returned: {:?}\n\
                                      round-trip: {:?}",
-                                    $value, returned, round_trip);
+                                    query, $value, returned, round_trip);
 
                     assert_eq!($value, round_trip,
-                            "[3] DB value mismatch; given value: {:?}\n\
+                            "[3] DB value mismatch; query: {}\n\
+                                     given value: {:?}\n\
                                      as returned: {:?}\n\
                                      round-trip: {:?}",
-                                    $value, returned, round_trip);
+                                    query, $value, returned, round_trip);
                 )+
 
                 Ok(())
@@ -216,6 +618,20 @@ macro_rules! MySql_query_for_test_prepared_type {
     };
 }
 
+#[macro_export]
+macro_rules! MySql_query_for_test_prepared_type_compare {
+    ($compare:literal) => {
+        concat!("SELECT CAST(", $compare, " AS SIGNED INTEGER), {0}, ?")
+    };
+}
+
+#[macro_export]
+macro_rules! MySql_query_for_test_null_type {
+    () => {
+        "SELECT CAST({0} IS NULL AND ? IS NULL AS SIGNED INTEGER), {0}, ?"
+    };
+}
+
 #[macro_export]
 macro_rules! Mssql_query_for_test_prepared_type {
     () => {
@@ -230,6 +646,20 @@ macro_rules! Sqlite_query_for_test_prepared_type {
     };
 }
 
+#[macro_export]
+macro_rules! Sqlite_query_for_test_prepared_type_compare {
+    ($compare:literal) => {
+        concat!("SELECT ", $compare, ", {0}, ?")
+    };
+}
+
+#[macro_export]
+macro_rules! Sqlite_query_for_test_null_type {
+    () => {
+        "SELECT {0} IS NULL AND ? IS NULL, {0}, ?"
+    };
+}
+
 #[macro_export]
 macro_rules! Postgres_query_for_test_prepared_type {
     () => {
@@ -237,6 +667,20 @@ macro_rules! Postgres_query_for_test_prepared_type {
     };
 }
 
+#[macro_export]
+macro_rules! Postgres_query_for_test_prepared_type_compare {
+    ($compare:literal) => {
+        concat!("SELECT (", $compare, ")::int4, {0}, $2")
+    };
+}
+
+#[macro_export]
+macro_rules! Postgres_query_for_test_null_type {
+    () => {
+        "SELECT ({0} IS NULL AND $1 IS NULL)::int4, {0}, $2"
+    };
+}
+
 #[macro_export]
 macro_rules! Postgres_query_for_test_prepared_geometric_type {
     () => {
@@ -13,6 +13,10 @@ pub use sqlx_core::any::{
 #[allow(deprecated)]
 pub use sqlx_core::any::AnyKind;
 
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub use sqlx_core::any::mock;
+
 pub(crate) mod reexports {
     /// **SEE DOCUMENTATION BEFORE USE**. Type alias for `Pool<Any>`.
     #[doc = include_str!("install_drivers_note.md")]
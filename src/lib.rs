@@ -11,34 +11,48 @@ compile_error!(
 
 pub use sqlx_core::acquire::Acquire;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
+#[cfg(feature = "blocking")]
+pub use sqlx_core::blocking;
+pub use sqlx_core::check;
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
 pub use sqlx_core::connection::{ConnectOptions, Connection};
 pub use sqlx_core::database::{self, Database};
 pub use sqlx_core::describe::Describe;
 pub use sqlx_core::executor::{Execute, Executor};
+#[doc(hidden)]
+pub use sqlx_core::from_row::ColumnOrdinal as __ColumnOrdinal;
 pub use sqlx_core::from_row::FromRow;
+pub use sqlx_core::metrics;
+pub use sqlx_core::placeholders::{self, translate_placeholders};
 pub use sqlx_core::pool::{self, Pool};
 #[doc(hidden)]
 pub use sqlx_core::query::query_with_result as __query_with_result;
-pub use sqlx_core::query::{query, query_with};
-pub use sqlx_core::query_as::{query_as, query_as_with};
-pub use sqlx_core::query_builder::{self, QueryBuilder};
+pub use sqlx_core::query::{query, query_owned, query_with};
+pub use sqlx_core::query_as::{query_as, query_as_owned, query_as_with};
+pub use sqlx_core::query_builder::{self, QueryBuilder, Upsert};
 #[doc(hidden)]
 pub use sqlx_core::query_scalar::query_scalar_with_result as __query_scalar_with_result;
-pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
+pub use sqlx_core::query_scalar::{query_scalar, query_scalar_owned, query_scalar_with};
 pub use sqlx_core::raw_sql::{raw_sql, RawSql};
-pub use sqlx_core::row::Row;
+pub use sqlx_core::row::{Row, RowSliceExt};
 pub use sqlx_core::statement::Statement;
-pub use sqlx_core::transaction::{Transaction, TransactionManager};
+pub use sqlx_core::transaction::{
+    IsolationLevel, PreparedTransaction, Transaction, TransactionManager, TransactionOptions,
+};
 pub use sqlx_core::type_info::TypeInfo;
 pub use sqlx_core::types::Type;
-pub use sqlx_core::value::{Value, ValueRef};
+pub use sqlx_core::value::{DynamicValue, ToDynamicValue, Value, ValueRef};
 pub use sqlx_core::Either;
 
 #[doc(inline)]
 pub use sqlx_core::error::{self, Error, Result};
 
+/// Opening the streams that connections run over.
+pub mod net {
+    pub use sqlx_core::net::{SocketConnector, StreamSocket};
+}
+
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
@@ -169,6 +183,8 @@ pub mod prelude {
     pub use super::FromRow;
     pub use super::IntoArguments;
     pub use super::Row;
+    pub use super::RowSliceExt;
     pub use super::Statement;
+    pub use super::ToDynamicValue;
     pub use super::Type;
 }
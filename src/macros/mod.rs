@@ -110,6 +110,18 @@
 /// * Postgres: `$N` where `N` is the 1-based positional argument index
 /// * MySQL/SQLite: `?` which matches arguments in order that it appears in the query
 ///
+/// MySQL and SQLite convert bind arguments to the types they need, so the types of arguments are
+/// not checked as strictly as in Postgres. MySQL 8.0.22 and later report the types of parameters
+/// though, and text bound to a parameter that is expected to be a number is a compile error.
+///
+/// In Postgres, a slice or `Vec` of a supported type (e.g. `&[i64]` or `Vec<String>`) can be bound
+/// to an array parameter, for example `WHERE id = ANY($1)`. MySQL and SQLite have no array
+/// parameters, so a slice or `Vec` (other than bytes, i.e. `&[u8]`/`Vec<u8>`) passed to a
+/// single parameter is reported as a mismatched types error such as
+/// ``expected `i64`, found `Vec<i64>` `` and not as a dedicated diagnostic.
+/// To match a column against a list of values there, build the `IN (...)` list with
+/// [`QueryBuilder`][crate::QueryBuilder] and one bind parameter per element.
+///
 /// ## Nullability: Bind Parameters
 /// For a given expected type `T`, both `T` and `Option<T>` are allowed (as well as either
 /// behind references). `Option::None` will be bound as `NULL`, so if binding a type behind `Option`
@@ -305,6 +317,30 @@
 ///
 /// See [the README for `sqlx-cli`](https://crates.io/crates/sqlx-cli) for more information.
 ///
+/// ## Multiple Databases
+/// To check some queries against a different database than `DATABASE_URL`, give that database
+/// a name with `database = "<name>"` as the first argument of any of the query macros. The query is
+/// then checked against the URL in `DATABASE_URL_<NAME>`, with the name uppercased and `-`
+/// replaced with `_`:
+///
+/// ```rust,ignore
+/// // checked against `DATABASE_URL`
+/// let user = sqlx::query!("SELECT name FROM users WHERE id = $1", id)
+///     .fetch_one(&pg_pool)
+///     .await?;
+///
+/// // checked against `DATABASE_URL_LOCAL_CACHE`
+/// let entry = sqlx::query_as!(database = "local-cache", Entry, "SELECT * FROM entries")
+///     .fetch_all(&sqlite_pool)
+///     .await?;
+/// ```
+///
+/// The databases may be of different types. Names may only contain ASCII letters, digits,
+/// `_` and `-`.
+///
+/// `cargo sqlx prepare` saves the data of these queries as `query-<name>-<hash>.json`,
+/// so the same query can be cached for more than one database.
+///
 /// ## See Also
 /// * [`query_as!`][`crate::query_as!`] if you want to use a struct you can name,
 /// * [`query_file!`][`crate::query_file!`] if you want to define the SQL query out-of-line,
@@ -313,6 +349,9 @@
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query (
     // in Rust 1.45 we can now invoke proc macros in expression position
+    (database = $database:literal, $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(database = $database, source = $query)
+    });
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query)
     });
@@ -324,6 +363,9 @@ macro_rules! query (
     // experience, at least not with IntelliJ-Rust at the time of writing (version 0.3.126.3220-201)
     // so really the only benefit is making the macros _slightly_ self-documenting, but it's
     // not like it makes them magically understandable at-a-glance.
+    (database = $database:literal, $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(database = $database, source = $query, args = [$($args)*])
+    });
     ($query:expr, $($args:tt)*) => ({
         $crate::sqlx_macros::expand_query!(source = $query, args = [$($args)*])
     })
@@ -334,9 +376,15 @@ macro_rules! query (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_unchecked (
+    (database = $database:literal, $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(database = $database, source = $query, checked = false)
+    });
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query, checked = false)
     });
+    (database = $database:literal, $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(database = $database, source = $query, args = [$($args)*], checked = false)
+    });
     ($query:expr, $($args:tt)*) => ({
         $crate::sqlx_macros::expand_query!(source = $query, args = [$($args)*], checked = false)
     })
@@ -387,9 +435,15 @@ macro_rules! query_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file (
+    (database = $database:literal, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(database = $database, source_file = $path)
+    });
     ($path:literal) => ({
         $crate::sqlx_macros::expand_query!(source_file = $path)
     });
+    (database = $database:literal, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(database = $database, source_file = $path, args = [$($args)*])
+    });
     ($path:literal, $($args:tt)*) => ({
         $crate::sqlx_macros::expand_query!(source_file = $path, args = [$($args)*])
     })
@@ -401,9 +455,15 @@ macro_rules! query_file (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_unchecked (
+    (database = $database:literal, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(database = $database, source_file = $path, checked = false)
+    });
     ($path:literal) => ({
         $crate::sqlx_macros::expand_query!(source_file = $path, checked = false)
     });
+    (database = $database:literal, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(database = $database, source_file = $path, args = [$($args)*], checked = false)
+    });
     ($path:literal, $($args:tt)*) => ({
         $crate::sqlx_macros::expand_query!(source_file = $path, args = [$($args)*], checked = false)
     })
@@ -515,6 +575,65 @@ macro_rules! query_file_unchecked (
 /// assert_eq!(record.id, MyInt4(1));
 /// ```
 ///
+/// ### Optional Records
+/// If the record type is given as `Option<T>`, a row where every column is `NULL` is returned
+/// as `None` instead of being decoded into `T`; any other row is decoded as `Some(T)` exactly as
+/// above. This is useful for the unmatched side of a `LEFT JOIN`, where the row is semantically
+/// absent:
+///
+/// ```rust,ignore
+/// struct Account {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// // Postgres/SQLite; the columns are nullable because of the `LEFT JOIN`,
+/// // so they are forced to be non-null to decode into `Account`
+/// let account: Option<Account> = sqlx::query_as!(
+///     Option<Account>,
+///     r#"SELECT account.id as "id!", account.name as "name!"
+///        FROM tweet LEFT JOIN account ON account.id = tweet.owner_id
+///        WHERE tweet.id = $1"#,
+///     tweet_id
+/// )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// Note that a row which is only partially `NULL` is still an error if a `NULL` column is decoded
+/// into a non-`Option` field.
+///
+/// ### Renamed Columns
+/// If the columns are named in a different case than the fields, give the case of the columns
+/// with `rename_all = ".."` after the record type, as with
+/// [`#[sqlx(rename_all = "..")]`][crate::FromRow#rename_all] on `#[derive(FromRow)]`.
+/// Each column is then matched to the `snake_case` field that would be renamed to it,
+/// and a column name that isn't in the given case is an error:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(rename_all = "camelCase")]
+/// struct UserProfile {
+///     // "userId"
+///     user_id: i32,
+///     // "displayName"
+///     display_name: String,
+/// }
+///
+/// // Postgres/SQLite
+/// let profile = sqlx::query_as!(
+///     UserProfile,
+///     rename_all = "camelCase",
+///     r#"SELECT "userId", "displayName" FROM user_profiles WHERE "userId" = $1"#,
+///     user_id
+/// )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// The macro can't see the attributes of the record type, so a column for a field with its own
+/// `#[sqlx(rename = "..")]` has to be aliased to a name that `rename_all` maps to the field.
+///
 /// ### Troubleshooting: "error: mismatched types"
 /// If you get a "mismatched types" error from an invocation of this macro and the error
 /// isn't pointing specifically at a parameter.
@@ -569,9 +688,27 @@ macro_rules! query_file_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as (
+    (database = $database:literal, $out_struct:path, rename_all = $rename_all:literal, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, rename_all = $rename_all, source = $query)
+    });
+    ($out_struct:path, rename_all = $rename_all:literal, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, rename_all = $rename_all, source = $query)
+    });
+    (database = $database:literal, $out_struct:path, rename_all = $rename_all:literal, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, rename_all = $rename_all, source = $query, args = [$($args)*])
+    });
+    ($out_struct:path, rename_all = $rename_all:literal, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, rename_all = $rename_all, source = $query, args = [$($args)*])
+    });
+    (database = $database:literal, $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, source = $query)
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query)
     });
+    (database = $database:literal, $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, source = $query, args = [$($args)*])
+    });
     ($out_struct:path, $query:expr, $($args:tt)*) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*])
     })
@@ -613,9 +750,27 @@ macro_rules! query_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as (
+    (database = $database:literal, $out_struct:path, rename_all = $rename_all:literal, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, rename_all = $rename_all, source_file = $path)
+    });
+    ($out_struct:path, rename_all = $rename_all:literal, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, rename_all = $rename_all, source_file = $path)
+    });
+    (database = $database:literal, $out_struct:path, rename_all = $rename_all:literal, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, rename_all = $rename_all, source_file = $path, args = [$($args)*])
+    });
+    ($out_struct:path, rename_all = $rename_all:literal, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, rename_all = $rename_all, source_file = $path, args = [$($args)*])
+    });
+    (database = $database:literal, $out_struct:path, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, source_file = $path)
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path)
     });
+    (database = $database:literal, $out_struct:path, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, source_file = $path, args = [$($args)*])
+    });
     ($out_struct:path, $path:literal, $($args:tt)*) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, args = [$($args)*])
     })
@@ -626,10 +781,28 @@ macro_rules! query_file_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as_unchecked (
+    (database = $database:literal, $out_struct:path, rename_all = $rename_all:literal, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, rename_all = $rename_all, source = $query, checked = false)
+    });
+    ($out_struct:path, rename_all = $rename_all:literal, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, rename_all = $rename_all, source = $query, checked = false)
+    });
+    (database = $database:literal, $out_struct:path, rename_all = $rename_all:literal, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, rename_all = $rename_all, source = $query, args = [$($args)*], checked = false)
+    });
+    ($out_struct:path, rename_all = $rename_all:literal, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, rename_all = $rename_all, source = $query, args = [$($args)*], checked = false)
+    });
+    (database = $database:literal, $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, source = $query, checked = false)
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, checked = false)
     });
 
+    (database = $database:literal, $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, source = $query, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $query:expr, $($args:tt)*) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], checked = false)
     })
@@ -641,10 +814,28 @@ macro_rules! query_as_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as_unchecked (
+    (database = $database:literal, $out_struct:path, rename_all = $rename_all:literal, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, rename_all = $rename_all, source_file = $path, checked = false)
+    });
+    ($out_struct:path, rename_all = $rename_all:literal, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, rename_all = $rename_all, source_file = $path, checked = false)
+    });
+    (database = $database:literal, $out_struct:path, rename_all = $rename_all:literal, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, rename_all = $rename_all, source_file = $path, args = [$($args)*], checked = false)
+    });
+    ($out_struct:path, rename_all = $rename_all:literal, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, rename_all = $rename_all, source_file = $path, args = [$($args)*], checked = false)
+    });
+    (database = $database:literal, $out_struct:path, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, source_file = $path, checked = false)
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, checked = false)
     });
 
+    (database = $database:literal, $out_struct:path, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(database = $database, record = $out_struct, source_file = $path, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $path:literal, $($args:tt)*) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, args = [$($args)*], checked = false)
     })
@@ -667,9 +858,15 @@ macro_rules! query_file_as_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_scalar (
+    (database = $database:literal, $query:expr) => (
+        $crate::sqlx_macros::expand_query!(database = $database, scalar = _, source = $query)
+    );
     ($query:expr) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source = $query)
     );
+    (database = $database:literal, $query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(database = $database, scalar = _, source = $query, args = [$($args)*])
+    );
     ($query:expr, $($args:tt)*) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source = $query, args = [$($args)*])
     )
@@ -680,9 +877,15 @@ macro_rules! query_scalar (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_scalar (
+    (database = $database:literal, $path:literal) => (
+        $crate::sqlx_macros::expand_query!(database = $database, scalar = _, source_file = $path)
+    );
     ($path:literal) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path)
     );
+    (database = $database:literal, $path:literal, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(database = $database, scalar = _, source_file = $path, args = [$($args)*])
+    );
     ($path:literal, $($args:tt)*) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path, args = [$($args)*])
     )
@@ -698,9 +901,15 @@ macro_rules! query_file_scalar (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_scalar_unchecked (
+    (database = $database:literal, $query:expr) => (
+        $crate::sqlx_macros::expand_query!(database = $database, scalar = _, source = $query, checked = false)
+    );
     ($query:expr) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source = $query, checked = false)
     );
+    (database = $database:literal, $query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(database = $database, scalar = _, source = $query, args = [$($args)*], checked = false)
+    );
     ($query:expr, $($args:tt)*) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source = $query, args = [$($args)*], checked = false)
     )
@@ -716,9 +925,15 @@ macro_rules! query_scalar_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_scalar_unchecked (
+    (database = $database:literal, $path:literal) => (
+        $crate::sqlx_macros::expand_query!(database = $database, scalar = _, source_file = $path, checked = false)
+    );
     ($path:literal) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path, checked = false)
     );
+    (database = $database:literal, $path:literal, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(database = $database, scalar = _, source_file = $path, args = [$($args)*], checked = false)
+    );
     ($path:literal, $($args:tt)*) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path, args = [$($args)*], checked = false)
     )
@@ -38,6 +38,11 @@ impl<T, U> WrapSameExt for WrapSame<T, Option<U>> {
     type Wrapped = Option<T>;
 }
 
+// e.g. `&Option<String>`, which would otherwise be compared to the bare expected type
+impl<T, U> WrapSameExt for WrapSame<T, &'_ Option<U>> {
+    type Wrapped = Option<T>;
+}
+
 impl<T, U> WrapSameExt for &'_ WrapSame<T, U> {
     type Wrapped = T;
 }
@@ -62,24 +67,37 @@ impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, Option<String>> {
     type Matched = Option<&'a str>;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a [u8]>, Option<Vec<u8>>> {
-    type Matched = Option<&'a [u8]>;
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<Vec<T>>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, Option<&'a String>> {
     type Matched = Option<&'a str>;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a [u8]>, Option<&'a Vec<u8>>> {
-    type Matched = Option<&'a [u8]>;
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<&'a Vec<T>>> {
+    type Matched = Option<&'a [T]>;
+}
+
+impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, &'a Option<String>> {
+    type Matched = Option<&'a str>;
+}
+
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, &'a Option<Vec<T>>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<'a> MatchBorrowExt for MatchBorrow<&'a str, String> {
     type Matched = &'a str;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<&'a [u8], Vec<u8>> {
-    type Matched = &'a [u8];
+// e.g. `Vec<i64>` for a Postgres `INT8[]` parameter, which expects `&[i64]`
+impl<'a, T> MatchBorrowExt for MatchBorrow<&'a [T], Vec<T>> {
+    type Matched = &'a [T];
+}
+
+impl<'a, T> MatchBorrowExt for MatchBorrow<&'a [T], &'a Vec<T>> {
+    type Matched = &'a [T];
 }
 
 impl<T> MatchBorrowExt for MatchBorrow<&'_ T, T> {
@@ -114,10 +132,96 @@ impl<T> MatchBorrowExt for MatchBorrow<Option<T>, Option<&'_ &'_ T>> {
     type Matched = Option<T>;
 }
 
+// `IpAddr` is accepted where an `IpNetwork` is expected, e.g. for a Postgres `INET` parameter
+#[cfg(feature = "ipnetwork")]
+mod ip_addr {
+    use super::{MatchBorrow, MatchBorrowExt};
+    use sqlx_core::types::ipnetwork::IpNetwork;
+    use std::net::IpAddr;
+
+    impl MatchBorrowExt for MatchBorrow<IpNetwork, IpAddr> {
+        type Matched = IpNetwork;
+    }
+
+    impl MatchBorrowExt for MatchBorrow<IpNetwork, &'_ IpAddr> {
+        type Matched = IpNetwork;
+    }
+
+    impl MatchBorrowExt for MatchBorrow<Option<IpNetwork>, Option<IpAddr>> {
+        type Matched = Option<IpNetwork>;
+    }
+
+    impl MatchBorrowExt for MatchBorrow<Option<IpNetwork>, Option<&'_ IpAddr>> {
+        type Matched = Option<IpNetwork>;
+    }
+
+    impl<'a> MatchBorrowExt for MatchBorrow<&'a [IpNetwork], Vec<IpAddr>> {
+        type Matched = &'a [IpNetwork];
+    }
+
+    impl<'a> MatchBorrowExt for MatchBorrow<&'a [IpNetwork], &'_ [IpAddr]> {
+        type Matched = &'a [IpNetwork];
+    }
+}
+
 impl<T, U> MatchBorrowExt for &'_ MatchBorrow<T, U> {
     type Matched = U;
 }
 
+// For databases that convert arguments to the types of their parameters themselves, like MySQL,
+// the expected type of a parameter is only a hint, so the types of arguments aren't compared to it.
+// We can still reject text bound to a numeric parameter, using the same autoref trick:
+// `CheckNumeric<T>` resolves to `T` for text and to `()` for anything else, and only `()`
+// implements `NumericParam`.
+
+pub trait IsText {}
+
+impl IsText for str {}
+
+impl IsText for String {}
+
+impl IsText for Box<str> {}
+
+impl IsText for std::borrow::Cow<'_, str> {}
+
+impl<T: IsText + ?Sized> IsText for &'_ T {}
+
+impl<T: IsText> IsText for Option<T> {}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be bound to numeric parameter #{PARAM}",
+    label = "expected a number"
+)]
+pub trait NumericParam<const PARAM: usize> {}
+
+impl<const PARAM: usize> NumericParam<PARAM> for () {}
+
+pub struct CheckNumeric<T>(PhantomData<T>);
+
+impl<T> CheckNumeric<T> {
+    pub fn new(_arg: &T) -> Self {
+        CheckNumeric(PhantomData)
+    }
+}
+
+pub trait CheckNumericExt: Sized {
+    type Checked;
+
+    fn check_numeric(self) -> Self::Checked {
+        panic!("only for type resolution")
+    }
+}
+
+impl<T: IsText> CheckNumericExt for CheckNumeric<T> {
+    type Checked = T;
+}
+
+impl<T> CheckNumericExt for &'_ CheckNumeric<T> {
+    type Checked = ();
+}
+
+pub fn numeric_param<const PARAM: usize, T: NumericParam<PARAM>>(_checked: T) {}
+
 pub fn conjure_value<T>() -> T {
     panic!()
 }
@@ -143,6 +247,7 @@ fn test_wrap_same() {
         let _: i32 = WrapSame::<i32, _>::new(&0i32).wrap_same();
         let _: i32 = WrapSame::<i32, _>::new(&"hello, world!").wrap_same();
         let _: Option<i32> = WrapSame::<i32, _>::new(&Some(String::new())).wrap_same();
+        let _: Option<i32> = WrapSame::<i32, _>::new(&&Some(String::new())).wrap_same();
     }
 }
 
@@ -160,5 +265,66 @@ fn test_match_borrow() {
 
         let (_, match_borrow) = MatchBorrow::new(0i64, &0i64);
         let _: i64 = match_borrow.match_borrow();
+
+        let ids = vec![0i64];
+
+        let (_, match_borrow) = MatchBorrow::new(&[0i64][..], &vec![0i64]);
+        let _: &[i64] = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(&[0i64][..], &&ids);
+        let _: &[i64] = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(&[0i64][..], &&[0i64][..]);
+        let _: &[i64] = match_borrow.match_borrow();
+
+        let names = [String::new()];
+
+        let (_, match_borrow) = MatchBorrow::new(Some(&names[..]), &Some(vec![]));
+        let _: Option<&[String]> = match_borrow.match_borrow();
+
+        let name = Some(String::new());
+
+        let (_, match_borrow) = MatchBorrow::new(Some(""), &&name);
+        let _: Option<&str> = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(Some(0i64), &&Some(0i64));
+        let _: Option<i64> = match_borrow.match_borrow();
+    }
+}
+
+#[test]
+fn test_check_numeric() {
+    if false {
+        let _: String = CheckNumeric::new(&String::new()).check_numeric();
+        let _: Option<&str> = CheckNumeric::new(&Some("")).check_numeric();
+        let _: () = CheckNumeric::new(&0i64).check_numeric();
+        let _: () = CheckNumeric::new(&Some(0i64)).check_numeric();
+
+        numeric_param::<1, _>(CheckNumeric::new(&&0u32).check_numeric());
+    }
+}
+
+#[cfg(feature = "ipnetwork")]
+#[test]
+fn test_match_borrow_ip_addr() {
+    use sqlx_core::types::ipnetwork::IpNetwork;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    if false {
+        let addr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        let (_, match_borrow) = MatchBorrow::new(IpNetwork::from(addr), &addr);
+        let _: IpNetwork = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(IpNetwork::from(addr), &&addr);
+        let _: IpNetwork = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(Some(IpNetwork::from(addr)), &Some(addr));
+        let _: Option<IpNetwork> = match_borrow.match_borrow();
+
+        let networks = [IpNetwork::from(addr)];
+
+        let (_, match_borrow) = MatchBorrow::new(&networks[..], &vec![addr]);
+        let _: &[IpNetwork] = match_borrow.match_borrow();
     }
 }
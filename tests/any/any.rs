@@ -141,3 +141,26 @@ async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_translates_placeholders_for_the_backend() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let mut conn = new::<Any>().await?;
+
+    assert!(sqlx::translate_placeholders::<Any>("SELECT $1").is_err());
+
+    let sql = conn
+        .placeholder_syntax()
+        .translate("SELECT CAST($1 AS INTEGER) + CAST($2 AS INTEGER)")?;
+
+    let value: i32 = sqlx::query_scalar(&sql)
+        .bind(1_i32)
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 3);
+
+    Ok(())
+}
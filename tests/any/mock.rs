@@ -0,0 +1,172 @@
+use sqlx::any::mock::{MockConnection, MockRow};
+use sqlx::{AnyConnection, Connection, Error, FromRow};
+
+#[derive(Debug, PartialEq, FromRow)]
+struct User {
+    id: i64,
+    name: String,
+}
+
+/// Creates the user if it doesn't exist yet.
+async fn ensure_user(conn: &mut AnyConnection, name: &str) -> sqlx::Result<User> {
+    let mut tx = conn.begin().await?;
+
+    let existing: Option<User> = sqlx::query_as("SELECT id, name FROM users WHERE name = ?")
+        .bind(name)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    let user = match existing {
+        Some(user) => user,
+        None => {
+            sqlx::query_as("INSERT INTO users (name) VALUES (?) RETURNING id, name")
+                .bind(name)
+                .fetch_one(&mut *tx)
+                .await?
+        }
+    };
+
+    tx.commit().await?;
+
+    Ok(user)
+}
+
+fn user_row(id: i64, name: &str) -> MockRow {
+    MockRow::new()
+        .column("id", id)
+        .column("name", name.to_owned())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_existing_user_from_mock() -> anyhow::Result<()> {
+    let mut mock = MockConnection::new();
+
+    mock.expect_begin();
+    mock.expect_query("SELECT id, name FROM users WHERE name = ?")
+        .bind("ferris")
+        .returning([user_row(1, "ferris")]);
+    mock.expect_commit();
+
+    let mut conn = AnyConnection::from(mock);
+
+    let user = ensure_user(&mut conn, "ferris").await?;
+
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            name: "ferris".into()
+        }
+    );
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_inserts_missing_user_with_mock() -> anyhow::Result<()> {
+    let mut mock = MockConnection::new();
+
+    mock.expect_begin();
+    mock.expect_query(
+        "SELECT id, name
+         FROM users
+         WHERE name = ?",
+    )
+    .bind("ferris")
+    .returning([]);
+    mock.expect_query_matching(r"^INSERT INTO users\b")
+        .bind("ferris")
+        .returning([user_row(2, "ferris")])
+        .rows_affected(1);
+    mock.expect_commit();
+
+    let mut conn = AnyConnection::from(mock);
+
+    let user = ensure_user(&mut conn, "ferris").await?;
+
+    assert_eq!(user.id, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_on_mock_error() -> anyhow::Result<()> {
+    let mut mock = MockConnection::new();
+
+    mock.expect_begin();
+    mock.expect_query("SELECT id, name FROM users WHERE name = ?")
+        .returning_error(Error::RowNotFound);
+    mock.expect_rollback();
+
+    let mut conn = AnyConnection::from(mock);
+
+    let res = ensure_user(&mut conn, "ferris").await;
+
+    assert!(matches!(res, Err(Error::RowNotFound)));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_rows_affected_from_mock() -> anyhow::Result<()> {
+    let mut mock = MockConnection::new();
+
+    mock.expect_query("DELETE FROM users WHERE id = ?")
+        .bind(1_i64)
+        .rows_affected(1);
+
+    let mut conn = AnyConnection::from(mock);
+
+    let res = sqlx::query("DELETE FROM users WHERE id = ?")
+        .bind(1_i64)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(res.rows_affected(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+#[should_panic(expected = "unexpected bind values")]
+async fn it_panics_on_mismatched_bind_values() {
+    let mut mock = MockConnection::new();
+
+    mock.expect_query("DELETE FROM users WHERE id = ?")
+        .bind(1_i64);
+
+    let mut conn = AnyConnection::from(mock);
+
+    let _ = sqlx::query("DELETE FROM users WHERE id = ?")
+        .bind(2_i64)
+        .execute(&mut conn)
+        .await;
+}
+
+#[sqlx_macros::test]
+#[should_panic(expected = "expected Commit, got ROLLBACK")]
+async fn it_panics_on_unexpected_rollback() {
+    let mut mock = MockConnection::new();
+
+    mock.expect_begin();
+    mock.expect_commit();
+
+    let mut conn = AnyConnection::from(mock);
+
+    let tx = conn.begin().await.unwrap();
+
+    // committing is forgotten
+    drop(tx);
+}
+
+#[test]
+#[should_panic(expected = "unmet expectations")]
+fn it_panics_on_unmet_expectations() {
+    let mut mock = MockConnection::new();
+
+    mock.expect_query("SELECT 1");
+
+    drop(AnyConnection::from(mock));
+}
@@ -1,10 +1,10 @@
 use sqlx::any::{AnyConnectOptions, AnyPoolOptions};
-use sqlx::Executor;
+use sqlx::{Connection, Executor};
 use std::sync::{
     atomic::{AtomicI32, AtomicUsize, Ordering},
     Arc, Mutex,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[sqlx_macros::test]
 async fn pool_should_invoke_after_connect() -> anyhow::Result<()> {
@@ -70,6 +70,224 @@ async fn pool_should_be_returned_failed_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn pool_should_serve_waiters_in_fifo_order() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    const TASKS: usize = 100;
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .acquire_timeout(Duration::from_secs(30))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    // Hold both connections so every task has to queue.
+    let held = vec![pool.acquire().await?, pool.acquire().await?];
+
+    let order = Arc::new(Mutex::new(Vec::with_capacity(TASKS)));
+    let mut tasks = Vec::with_capacity(TASKS);
+
+    for i in 0..TASKS {
+        let task_pool = pool.clone();
+        let order = order.clone();
+
+        tasks.push(sqlx_core::rt::spawn(async move {
+            let started_at = std::time::Instant::now();
+            let mut conn = task_pool.acquire().await?;
+            let waited = started_at.elapsed();
+
+            order.lock().unwrap().push(i);
+            conn.execute("SELECT 1").await?;
+
+            // Hold the connection long enough that scheduling jitter doesn't reorder tasks.
+            sqlx_core::rt::sleep(Duration::from_millis(5)).await;
+
+            Ok::<_, sqlx::Error>(waited)
+        }));
+
+        // Make sure the tasks are queued in the order they were spawned.
+        while pool.num_waiting() <= i {
+            sqlx_core::rt::yield_now().await;
+        }
+    }
+
+    assert_eq!(pool.num_waiting(), TASKS);
+
+    drop(held);
+
+    let mut max_wait = Duration::ZERO;
+
+    for task in tasks {
+        max_wait = std::cmp::max(max_wait, task.await?);
+    }
+
+    let order = order.lock().unwrap();
+
+    // Two connections are handed out concurrently, so neighbors may swap places.
+    for (position, &i) in order.iter().enumerate() {
+        assert!(
+            position.abs_diff(i) <= 4,
+            "task {i} acquired at {position}: {order:?}"
+        );
+    }
+
+    assert!(max_wait < Duration::from_secs(10), "max wait: {max_wait:?}");
+    assert_eq!(pool.num_waiting(), 0);
+    assert!(pool.acquire_wait_time() >= max_wait);
+
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_not_leak_timed_out_waiters() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(100))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    for _ in 0..10 {
+        assert!(matches!(
+            pool.acquire().await,
+            Err(sqlx::Error::PoolTimedOut)
+        ));
+    }
+
+    assert_eq!(pool.num_waiting(), 0);
+    assert!(pool.acquire_wait_time() >= Duration::from_secs(1));
+
+    drop(conn);
+
+    // The only connection must still be available to the next waiter.
+    let _conn = pool.acquire().await?;
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_honor_per_call_acquire_timeout() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(30))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    assert!(pool.try_acquire().is_none());
+
+    let started_at = Instant::now();
+    assert!(matches!(
+        pool.acquire_timeout(Duration::from_millis(100)).await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+    let elapsed = started_at.elapsed();
+    assert!(elapsed >= Duration::from_millis(100), "{elapsed:?}");
+    assert!(elapsed < Duration::from_secs(5), "{elapsed:?}");
+
+    assert_eq!(pool.num_waiting(), 0);
+
+    drop(conn);
+
+    let _conn = pool.acquire_timeout(Duration::from_secs(5)).await?;
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_replace_detached_connections() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(5))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let mut detached = pool.acquire_detached().await?;
+    assert_eq!(pool.size(), 0);
+
+    // the pool opens a replacement while the detached connection is still usable
+    let mut conn = pool.acquire().await?;
+    assert_eq!(pool.size(), 1);
+
+    conn.execute("SELECT 1").await?;
+    detached.execute("SELECT 1").await?;
+
+    drop(conn);
+    assert_eq!(pool.size(), 1);
+
+    detached.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_reject_attach_beyond_max_connections() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let url = dotenvy::var("DATABASE_URL")?;
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .min_connections(0)
+        .connect_lazy(&url)?;
+
+    assert_eq!(pool.size(), 0);
+
+    for _ in 0..2 {
+        let conn = sqlx::AnyConnection::connect(&url).await?;
+        assert!(pool.attach(conn).is_ok());
+    }
+
+    assert_eq!(pool.size(), 2);
+    assert_eq!(pool.num_idle(), 2);
+
+    let conn = sqlx::AnyConnection::connect(&url).await?;
+    let conn = pool
+        .attach(conn)
+        .expect_err("attach should fail at max_connections");
+    conn.close().await?;
+
+    assert_eq!(pool.size(), 2);
+
+    // a detached connection frees a slot for one to be attached again
+    let detached = pool.acquire_detached().await?;
+    assert_eq!(pool.size(), 1);
+
+    assert!(pool.attach(detached).is_ok());
+    assert_eq!(pool.size(), 2);
+
+    // attached connections are handed out like any other
+    let mut a = pool.acquire().await?;
+    let mut b = pool.acquire().await?;
+    a.execute("SELECT 1").await?;
+    b.execute("SELECT 1").await?;
+    assert_eq!(pool.size(), 2);
+    drop((a, b));
+
+    pool.close().await;
+
+    let conn = sqlx::AnyConnection::connect(&url).await?;
+    let conn = pool
+        .attach(conn)
+        .expect_err("attach should fail after the pool is closed");
+    conn.close().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_pool_callbacks() -> anyhow::Result<()> {
     sqlx::any::install_default_drivers();
@@ -0,0 +1,3 @@
+CREATE TEMPORARY TABLE fixture_errors (id INTEGER NOT NULL);
+
+INSERT INTO fixture_does_not_exist (id) VALUES (1);
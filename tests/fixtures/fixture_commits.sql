@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS fixture_commits;
+
+CREATE TABLE fixture_commits (id INTEGER NOT NULL);
+
+INSERT INTO fixture_commits (id) VALUES (1);
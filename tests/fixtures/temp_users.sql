@@ -0,0 +1 @@
+CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
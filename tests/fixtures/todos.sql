@@ -0,0 +1,10 @@
+CREATE TABLE todos
+(
+    id          BIGINT PRIMARY KEY NOT NULL,
+    description TEXT    NOT NULL,
+    done        BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+INSERT INTO todos (id, description, done)
+VALUES (1, 'write the fixtures', TRUE),
+       (2, 'use the fixtures in tests', FALSE);
@@ -1,3 +1,4 @@
+use sqlx::{FromRow, Row, RowSliceExt};
 use sqlx_mysql::MySql;
 use sqlx_test::new;
 
@@ -300,3 +301,116 @@ async fn test_derive_weak_enum() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx::test]
+async fn test_row_try_into_struct() -> anyhow::Result<()> {
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct Name {
+        id: i64,
+        name: String,
+    }
+
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct NameRef<'r> {
+        id: i64,
+        name: &'r str,
+    }
+
+    let mut conn = new::<MySql>().await?;
+
+    let rows = sqlx::query("SELECT 1 AS id, 'foo' AS name UNION ALL SELECT 2, 'bar'")
+        .fetch_all(&mut conn)
+        .await?;
+
+    let first: Name = rows[0].try_into_struct()?;
+    assert_eq!(
+        first,
+        Name {
+            id: 1,
+            name: "foo".to_owned()
+        }
+    );
+
+    let first: NameRef<'_> = rows[0].try_into_struct()?;
+    assert_eq!(first, NameRef { id: 1, name: "foo" });
+
+    let owned: Vec<Name> = rows.try_into_structs()?;
+    assert_eq!(owned.len(), 2);
+    assert_eq!(owned[1].name, "bar");
+
+    let borrowed: Vec<NameRef<'_>> = rows.try_into_structs()?;
+    assert_eq!(
+        borrowed,
+        [
+            NameRef { id: 1, name: "foo" },
+            NameRef { id: 2, name: "bar" }
+        ]
+    );
+
+    let err = rows[0]
+        .try_into_struct::<(i64, i64)>()
+        .expect_err("expected a decode error");
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{err:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_from_row_with_changing_column_order() -> anyhow::Result<()> {
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct Item {
+        id: i32,
+        name: String,
+        #[sqlx(default)]
+        note: Option<String>,
+    }
+
+    let mut conn = new::<MySql>().await?;
+
+    let queries = [
+        "SELECT 1 AS id, 'one' AS name",
+        "SELECT 'one' AS name, 1 AS id",
+        "SELECT 'one' AS name, 'note' AS note, 1 AS id",
+        "SELECT 1 AS id, 'one' AS name",
+    ];
+
+    let mut rows = Vec::new();
+
+    for sql in queries {
+        rows.push(sqlx::query(sql).fetch_one(&mut conn).await?);
+    }
+
+    // decode rows of the different queries interleaved
+    for _ in 0..2 {
+        for (i, row) in rows.iter().enumerate() {
+            let item = Item::from_row(row)?;
+
+            assert_eq!(item.id, 1);
+            assert_eq!(item.name, "one");
+            assert_eq!(item.note.is_some(), i == 2);
+        }
+    }
+
+    let row = sqlx::query("SELECT 'one' AS id, 'two' AS name")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = Item::from_row(&row).expect_err("expected a decode error");
+    let expected = row
+        .try_get::<i32, _>("id")
+        .expect_err("expected a decode error");
+    assert_eq!(err.to_string(), expected.to_string());
+    assert!(err.to_string().contains("\"id\""), "{err}");
+
+    let row = sqlx::query("SELECT 'two' AS name")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = Item::from_row(&row).expect_err("expected a missing column");
+    assert!(
+        matches!(err, sqlx::Error::ColumnNotFound(ref name) if name == "id"),
+        "{err:?}"
+    );
+
+    Ok(())
+}
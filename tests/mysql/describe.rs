@@ -69,3 +69,23 @@ async fn uses_alias_name() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let d = conn
+        .describe("SELECT * FROM tweet WHERE id = ? AND text = ?")
+        .await?;
+
+    let parameters = d.parameters().unwrap().left().unwrap();
+
+    assert_eq!(parameters.len(), 2);
+
+    // servers before MySQL 8.0.22 report every parameter as `VAR_STRING`
+    if parameters[0].name() != "VARCHAR" {
+        assert_eq!(parameters[0].name(), "BIGINT");
+    }
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
-use sqlx::{error::ErrorKind, mysql::MySql, Connection};
+use sqlx::mysql::{MySql, MySqlConnectOptions, MySqlConnection};
+use sqlx::{error::ErrorKind, Connection};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -74,3 +75,21 @@ async fn it_fails_with_check_violation() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_returns_structured_errors() -> anyhow::Result<()> {
+    sqlx_test::structured_errors::<MySql>().await?;
+
+    // and the ones specific to this driver
+    let err = "mysql://localhost/db?ssl-mode=sometimes"
+        .parse::<MySqlConnectOptions>()
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)), "{err:?}");
+
+    // nothing listens on port 1
+    let err = MySqlConnection::connect("mysql://root@127.0.0.1:1/sqlx")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Io(_)), "{err:?}");
+    Ok(())
+}
@@ -58,6 +58,32 @@ async fn test_query_as_raw() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_as_optional() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let accounts = sqlx::query_as!(
+        Option<RawAccount>,
+        "SELECT accounts.type as `type!`, accounts.name \
+         FROM (select 1 as id union all select 2) ids \
+         LEFT JOIN (select 1 as type, 'Herp Derpinson' as name) accounts \
+         ON accounts.type = ids.id \
+         ORDER BY ids.id"
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(accounts.len(), 2);
+
+    let account = accounts[0].as_ref().expect("row 1 should be present");
+    assert_eq!(account.r#type, 1);
+    assert_eq!(account.name.as_deref(), Some("Herp Derpinson"));
+
+    assert!(accounts[1].is_none());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_query_scalar() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -555,4 +581,84 @@ async fn test_from_row_json_try_from_attr() -> anyhow::Result<()> {
     Ok(())
 }
 
-// we don't emit bind parameter type-checks for MySQL so testing the overrides is redundant
+#[sqlx_macros::test]
+async fn test_bind_numeric_params() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let (mut conn, id) = with_test_row(&mut conn).await?;
+
+    // MySQL converts the arguments itself, so any number is accepted for a `BIGINT` parameter;
+    // only text is rejected (see `tests/ui/mysql/wrong_param_type.rs`)
+    let owner_id: Option<i32> = Some(1);
+
+    let record = sqlx::query!(
+        "SELECT text FROM tweet WHERE id = ? AND owner_id = ? AND text = ?",
+        id.0,
+        owner_id,
+        "#sqlx is pretty cool!".to_owned(),
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    assert_eq!(record.text, "#sqlx is pretty cool!");
+
+    let record = sqlx::query!("SELECT id FROM tweet WHERE id = ?", id.0 as u32)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    assert_eq!(record.id, id.0);
+
+    Ok(())
+}
+
+// we only reject text bound to numeric parameters for MySQL so testing the overrides is redundant
+
+#[derive(Debug, PartialEq, sqlx::FromRow)]
+#[sqlx(rename_all = "camelCase")]
+struct UserProfile {
+    user_id: i32,
+    display_name: String,
+    #[sqlx(rename = "emailAddress")]
+    email: Option<String>,
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_rename_all() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let mut tx = conn.begin().await?;
+
+    sqlx::query!(
+        "INSERT INTO user_profiles (userId, displayName, emailAddress) \
+         VALUES (1, 'Herp Derpinson', 'herp@example.com')"
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let expected = UserProfile {
+        user_id: 1,
+        display_name: "Herp Derpinson".into(),
+        email: Some("herp@example.com".into()),
+    };
+
+    let derived: UserProfile = sqlx::query_as("SELECT * FROM user_profiles WHERE userId = ?")
+        .bind(1_i32)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(derived, expected);
+
+    // the macro can't see `#[sqlx(rename)]`, so that column is aliased instead
+    let checked = sqlx::query_as!(
+        UserProfile,
+        rename_all = "camelCase",
+        "SELECT userId, displayName, emailAddress AS email FROM user_profiles WHERE userId = ?",
+        1_i32
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    assert_eq!(checked, expected);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use anyhow::Context;
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
+use sqlx::mysql::{
+    MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow, MySqlStatusFlags,
+    MySqlTransactionOptions,
+};
+use sqlx::{Column, Connection, Executor, IsolationLevel, Row, Statement, TypeInfo, Upsert};
 use sqlx_core::connection::ConnectOptions;
 use sqlx_mysql::MySqlConnectOptions;
 use sqlx_test::{new, setup_if_needed};
@@ -143,6 +146,120 @@ async fn it_works_with_cache_disabled() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_checks_the_number_of_bound_arguments() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    for persistent in [true, false] {
+        let err = sqlx::query_scalar::<_, i64>("SELECT CAST(? AS SIGNED) + CAST(? AS SIGNED)")
+            .bind(1_i32)
+            .persistent(persistent)
+            .fetch_one(&mut conn)
+            .await
+            .expect_err("binding too few arguments should fail");
+
+        assert!(
+            matches!(
+                err,
+                sqlx::Error::ParameterCountMismatch {
+                    expected: 2,
+                    got: 1
+                }
+            ),
+            "{err:?}"
+        );
+
+        let err = sqlx::query_scalar::<_, i64>("SELECT CAST(? AS SIGNED)")
+            .bind(1_i32)
+            .bind(2_i32)
+            .persistent(persistent)
+            .fetch_one(&mut conn)
+            .await
+            .expect_err("binding too many arguments should fail");
+
+        assert!(
+            matches!(
+                err,
+                sqlx::Error::ParameterCountMismatch {
+                    expected: 1,
+                    got: 2
+                }
+            ),
+            "{err:?}"
+        );
+    }
+
+    // the connection is still usable
+    let v: i64 = sqlx::query_scalar("SELECT CAST(? AS SIGNED)")
+        .bind(10_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(v, 10);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_rows_with_a_cursor() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let mut url = url::Url::parse(&env::var("DATABASE_URL")?)?;
+    url.query_pairs_mut().append_pair("fetch-size", "100");
+
+    let mut conn = MySqlConnection::connect(url.as_ref()).await?;
+
+    // 10,000 rows, numbered from 0
+    let sql = "SELECT a.n + 10 * b.n + 100 * c.n + 1000 * d.n AS n
+        FROM (SELECT 0 AS n UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4
+              UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 UNION ALL SELECT 8 UNION ALL SELECT 9) a,
+             (SELECT 0 AS n UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4
+              UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 UNION ALL SELECT 8 UNION ALL SELECT 9) b,
+             (SELECT 0 AS n UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4
+              UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 UNION ALL SELECT 8 UNION ALL SELECT 9) c,
+             (SELECT 0 AS n UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4
+              UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 UNION ALL SELECT 8 UNION ALL SELECT 9) d
+        WHERE a.n + 10 * b.n + 100 * c.n + 1000 * d.n >= ?
+        ORDER BY n";
+
+    // stop reading in the middle of the second batch of rows
+    {
+        let mut rows = sqlx::query_scalar::<_, i64>(sql)
+            .bind(0_i32)
+            .fetch(&mut conn);
+
+        for expected in 0..150 {
+            assert_eq!(rows.try_next().await?, Some(expected));
+        }
+    }
+
+    // the connection can be used after the stream is dropped
+    let n: i64 = sqlx::query_scalar("SELECT ? + 1")
+        .bind(1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(n, 2);
+
+    // and executing the statement again reads all rows
+    let rows: Vec<i64> = sqlx::query_scalar(sql)
+        .bind(0_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows, (0..10_000).collect::<Vec<_>>());
+
+    // as does a result that fits in one batch
+    let rows: Vec<i64> = sqlx::query_scalar(sql)
+        .bind(9_990_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows, (9_990..10_000).collect::<Vec<_>>());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_drops_results_in_affected_rows() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -262,6 +379,25 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_does_not_cache_non_persistent_queries() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    for i in 0..100 {
+        let val: i64 = sqlx::query_scalar(&format!("SELECT CAST({i} AS SIGNED) + ?"))
+            .bind(1_i64)
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(i + 1, val);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_closes_statements_with_persistent_disabled() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -411,6 +547,357 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fetches_returning_rows_with_rows_affected() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // `RETURNING` is only supported by MariaDB 10.5+
+    let version: String = sqlx::query_scalar("SELECT VERSION()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    if !version.contains("MariaDB") || conn.server_version() < (10, 5, 0) {
+        return Ok(());
+    }
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE returning_test (id INT AUTO_INCREMENT PRIMARY KEY, flag BOOLEAN)",
+    )
+    .await?;
+
+    let (ids, result) = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO returning_test (flag) VALUES (false), (false), (true) RETURNING id",
+    )
+    .fetch_all_with_result(&mut conn)
+    .await?;
+
+    assert_eq!(ids, [1, 2, 3]);
+    assert_eq!(result.rows_affected(), 3);
+
+    let (rows, result) = sqlx::query("DELETE FROM returning_test WHERE flag = false RETURNING id")
+        .fetch_all_with_result(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(result.rows_affected(), 2);
+
+    // more than 250 rows affected makes the terminating OK packet longer than an EOF packet
+    let (ids, result) = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO returning_test (flag) SELECT seq % 2 FROM seq_1_to_300 RETURNING id",
+    )
+    .fetch_all_with_result(&mut conn)
+    .await?;
+
+    assert_eq!(ids.len(), 300);
+    assert_eq!(result.rows_affected(), 300);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_combines_results_of_multiple_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE multi_statement (id INT)")
+        .await?;
+
+    // `execute()` sums the rows affected by each statement
+    let res = sqlx::raw_sql(
+        "INSERT INTO multi_statement VALUES (1), (2); INSERT INTO multi_statement VALUES (3);",
+    )
+    .execute(&mut conn)
+    .await?;
+
+    assert_eq!(res.rows_affected(), 3);
+
+    let sql = "SELECT id FROM multi_statement WHERE id < 3 ORDER BY id; \
+               SELECT id FROM multi_statement WHERE id = 3;";
+
+    // `fetch_all()` returns the rows of every result set, in order
+    let ids: Vec<i32> = sqlx::raw_sql(sql)
+        .fetch_all(&mut conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    assert_eq!(ids, [1, 2, 3]);
+
+    // `fetch_one()` returns the first row of the first result set
+    let row = sqlx::raw_sql(sql).fetch_one(&mut conn).await?;
+
+    assert_eq!(row.get::<i32, _>(0), 1);
+
+    // and the connection is left ready for the next query
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM multi_statement")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_optional_row_from_multiple_statements() -> anyhow::Result<()> {
+    sqlx_test::fetch_optional_from_multiple_statements::<MySql>().await
+}
+
+#[sqlx_macros::test]
+async fn it_calls_procedure_with_result_sets_and_out_params() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("DROP PROCEDURE IF EXISTS sqlx_test_call")
+        .await?;
+    conn.execute(
+        r#"
+CREATE PROCEDURE sqlx_test_call(IN n INT, OUT total BIGINT)
+BEGIN
+    SELECT n AS a;
+    SELECT n + 1 AS b UNION ALL SELECT n + 2;
+    SET total = n * 10;
+END
+        "#,
+    )
+    .await?;
+
+    let res = conn
+        .call("sqlx_test_call")
+        .bind(5_i32)
+        .out_param("@sqlx_total")
+        .execute()
+        .await?;
+
+    let result_sets: Vec<Vec<i64>> = res
+        .result_sets()
+        .iter()
+        .map(|rows| rows.iter().map(|row| row.get(0)).collect())
+        .collect();
+
+    assert_eq!(result_sets, [vec![5], vec![6, 7]]);
+
+    let total: i64 = res.out_params().unwrap().try_get("@sqlx_total")?;
+    assert_eq!(total, 50);
+
+    // a plain `CALL` also yields every result set and leaves the connection usable
+    let row = sqlx::query("CALL sqlx_test_call(?, @sqlx_total)")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i64, _>(0), 1);
+
+    let rows = sqlx::query("CALL sqlx_test_call(?, @sqlx_total)")
+        .bind(1_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 3);
+
+    let total: i64 = sqlx::query_scalar("SELECT @sqlx_total")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(total, 10);
+
+    conn.execute("DROP PROCEDURE sqlx_test_call").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_drop_fetch_stream_mid_result() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // 10^5 rows without relying on recursive CTEs
+    let digits = "(SELECT 0 d UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 \
+                  UNION ALL SELECT 4 UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 \
+                  UNION ALL SELECT 8 UNION ALL SELECT 9)";
+    let query = format!(
+        "SELECT a.d + b.d * 10 + c.d * 100 + d.d * 1000 + e.d * 10000 AS i \
+         FROM {digits} a, {digits} b, {digits} c, {digits} d, {digits} e"
+    );
+
+    // both the binary and text protocols
+    {
+        let mut rows = sqlx::query(&query).fetch(&mut conn);
+        rows.try_next().await?.expect("at least one row");
+    }
+
+    let value: i64 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(value, 42);
+
+    {
+        let mut rows = sqlx::raw_sql(&query).fetch(&mut conn);
+        rows.try_next().await?.expect("at least one row");
+    }
+
+    let value: i64 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(value, 42);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_non_utf8_text_as_bytes_or_lossy() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // stop the server from converting latin1 text to the connection's utf8mb4
+    conn.execute("SET character_set_results = NULL").await?;
+    conn.execute(
+        "CREATE TEMPORARY TABLE legacy_latin1 (b BLOB NOT NULL, t TEXT CHARACTER SET latin1 NOT NULL)",
+    )
+    .await?;
+    conn.execute("INSERT INTO legacy_latin1 (b, t) VALUES (X'E9', X'E9')")
+        .await?;
+
+    for row in [
+        sqlx::query("SELECT b, t FROM legacy_latin1")
+            .fetch_one(&mut conn)
+            .await?,
+        sqlx::raw_sql("SELECT b, t FROM legacy_latin1")
+            .fetch_one(&mut conn)
+            .await?,
+    ] {
+        assert_eq!(row.try_get::<Vec<u8>, _>("b")?, [0xE9]);
+        assert_eq!(row.try_get::<&[u8], _>("t")?, [0xE9]);
+
+        let err = row.try_get::<String, _>("t").unwrap_err().to_string();
+        assert!(err.contains("collation ID 8"), "{err}");
+
+        assert_eq!(row.try_get_lossy("b")?, "\u{FFFD}");
+        assert_eq!(row.try_get_lossy("t")?, "\u{FFFD}");
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_verifies_connection_charset() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    // without `SET NAMES`, this relies on the charset from the handshake, or on the correction
+    // with a server that ignores it (e.g. `--skip-character-set-client-handshake` with
+    // `--character-set-server=latin1`)
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .set_names(false)
+        .verify_charset(true);
+
+    let mut conn = options.connect().await?;
+
+    assert_eq!(conn.charset(), "utf8mb4");
+    assert_eq!(conn.collation(), "utf8mb4_unicode_ci");
+
+    let charsets: (String, String, String) = sqlx::query_as(
+        "SELECT @@character_set_client, @@character_set_connection, @@character_set_results",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(
+        charsets,
+        ("utf8mb4".into(), "utf8mb4".into(), "utf8mb4".into())
+    );
+
+    conn.execute("SET character_set_results = latin1").await?;
+    conn.verify_charset().await?;
+
+    let charsets: (String, String, String) = sqlx::query_as(
+        "SELECT @@character_set_client, @@character_set_connection, @@character_set_results",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(
+        charsets,
+        ("utf8mb4".into(), "utf8mb4".into(), "utf8mb4".into())
+    );
+
+    conn.execute("CREATE TEMPORARY TABLE emoji (t TEXT CHARACTER SET utf8mb4 NOT NULL)")
+        .await?;
+
+    sqlx::query("INSERT INTO emoji (t) VALUES (?)")
+        .bind("caf\u{e9} \u{1F600}")
+        .execute(&mut conn)
+        .await?;
+
+    let t: String = sqlx::query_scalar("SELECT t FROM emoji")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(t, "caf\u{e9} \u{1F600}");
+
+    // `utf8` only has the characters of up to 3 bytes
+    let mut conn = options.charset("utf8").connect().await?;
+
+    assert_eq!(conn.charset(), "utf8");
+
+    let err = sqlx::query("SELECT ?")
+        .bind("\u{1F600}")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::Encode(_)), "{err}");
+
+    let value: String = sqlx::query_scalar("SELECT ?")
+        .bind("caf\u{e9}")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, "caf\u{e9}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resets_session_state_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .reset_on_release(true)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    conn.execute("SET @sqlx_reset_test = 1, time_zone = '+01:00'")
+        .await?;
+    conn.execute("CREATE TEMPORARY TABLE sqlx_reset_test (id INT)")
+        .await?;
+
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+
+    // the same connection, but with a clean session
+    let (same_id, var_is_null, time_zone): (u64, i64, String) =
+        sqlx::query_as("SELECT CONNECTION_ID(), @sqlx_reset_test IS NULL, @@time_zone")
+            .fetch_one(&mut *conn)
+            .await?;
+
+    assert_eq!(same_id, id);
+    assert_eq!(var_is_null, 1);
+
+    // the `time_zone` from the connect options is applied again
+    assert_eq!(time_zone, "+00:00");
+
+    assert!(conn.execute("SELECT * FROM sqlx_reset_test").await.is_err());
+
+    // prepared statements were deallocated by the server, so this has to prepare them again
+    let _: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
 // repro is more reliable with the basic scheduler used by `#[tokio::test]`
 #[cfg(feature = "_rt-tokio")]
 #[tokio::test]
@@ -465,6 +952,25 @@ async fn test_issue_622() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_server_version() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let version: String = sqlx::query_scalar("SELECT VERSION()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let (major, minor, patch) = conn.server_version();
+
+    assert!(major > 0, "{version}");
+    assert!(
+        version.starts_with(&format!("{major}.{minor}.{patch}")),
+        "{version}"
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -525,6 +1031,49 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_begin_with_options() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_1876 (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn
+        .begin_with(
+            MySqlTransactionOptions::new()
+                .isolation_level(IsolationLevel::Serializable)
+                .consistent_snapshot(true)
+                .read_only(true),
+        )
+        .await?;
+
+    // options are ignored for savepoints
+    let mut savepoint = tx
+        .begin_with(MySqlTransactionOptions::new().read_only(false))
+        .await?;
+
+    let err = sqlx::query("INSERT INTO _sqlx_users_1876 (id) VALUES (?)")
+        .bind(1_i32)
+        .execute(&mut *savepoint)
+        .await
+        .expect_err("expected a read-only transaction");
+
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("25006")
+    );
+
+    savepoint.rollback().await?;
+    tx.rollback().await?;
+
+    // the isolation level only applied to that transaction
+    let level: String = sqlx::query_scalar("SELECT @@transaction_isolation")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_ne!(level, "SERIALIZABLE");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_handle_split_packets() -> anyhow::Result<()> {
     // This will only take effect on new connections
@@ -589,6 +1138,153 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_returns_unsigned_last_insert_id_above_i64_max() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE big_auto_increment (id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY, n INT) \
+         AUTO_INCREMENT = 9223372036854775900",
+    )
+    .await?;
+
+    let res = sqlx::query("INSERT INTO big_auto_increment (n) VALUES (?)")
+        .bind(1)
+        .execute(&mut conn)
+        .await?;
+
+    let id = res.last_insert_id();
+    assert_eq!(id, 9223372036854775900);
+
+    let stored: u64 = sqlx::query_scalar("SELECT id FROM big_auto_increment")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(stored, id);
+
+    // `AnyQueryResult` can't represent it, rather than wrapping around
+    let any: sqlx::any::AnyQueryResult = res.into();
+    assert_eq!(any.last_insert_id(), None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_qualified_and_case_insensitive_column_names() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let mut tx = conn.begin().await?;
+
+    let id: u64 = sqlx::query("INSERT INTO tweet (text) VALUES ('Hello')")
+        .execute(&mut *tx)
+        .await?
+        .last_insert_id();
+
+    let row = sqlx::query(
+        "SELECT a.id, b.id, a.text AS Text FROM tweet a JOIN tweet b ON b.id = a.id WHERE a.id = ?",
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // the first column with a matching name
+    assert_eq!(row.try_get::<u64, _>("id")?, id);
+    assert_eq!(row.try_get::<u64, _>("a.id")?, id);
+    assert_eq!(row.try_get::<u64, _>("B.ID")?, id);
+    assert_eq!(row.try_get::<String, _>("text")?, "Hello");
+
+    match row.try_get::<u64, _>("tweet.id") {
+        Err(sqlx::Error::ColumnAmbiguous { name, candidates }) => {
+            assert_eq!(name, "tweet.id");
+            assert_eq!(candidates, ["a.id", "b.id"]);
+        }
+        other => panic!("expected `Error::ColumnAmbiguous`, got {other:?}"),
+    }
+
+    assert!(matches!(
+        row.try_get::<u64, _>("c.id"),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "mysql-compression-zlib")]
+#[sqlx_macros::test]
+async fn it_fetches_large_result_with_zlib_compression() -> anyhow::Result<()> {
+    use sqlx::mysql::MySqlCompression;
+
+    setup_if_needed();
+
+    let mut conn = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .compression(MySqlCompression::Zlib)
+        .connect()
+        .await?;
+
+    let (_, compression): (String, String) =
+        sqlx::query_as("SHOW SESSION STATUS LIKE 'Compression'")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(compression, "ON");
+
+    // large enough to span many compressed frames in both directions
+    let data = "sqlx".repeat(256 * 1024);
+
+    let ret: String = sqlx::query_scalar("SELECT CONCAT(?, REPEAT('x', 1048576))")
+        .bind(&data)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ret.len(), data.len() + 1048576);
+    assert!(ret.starts_with(&data));
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_round_trips_blob_larger_than_max_packet_len() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // larger than one packet (16 MiB), so it is split when sent and when received
+    let data: Vec<u8> = (0..20 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+
+    let max_allowed_packet: u64 =
+        sqlx::query_scalar("SELECT CAST(@@max_allowed_packet AS UNSIGNED)")
+            .fetch_one(&mut conn)
+            .await?;
+
+    conn.execute("CREATE TEMPORARY TABLE blobs (id INT PRIMARY KEY, data LONGBLOB NOT NULL)")
+        .await?;
+
+    let res = sqlx::query("INSERT INTO blobs (id, data) VALUES (1, ?)")
+        .bind(&data)
+        .execute(&mut conn)
+        .await;
+
+    if max_allowed_packet < 21 * 1024 * 1024 {
+        // rejected before sending, so the connection is still usable
+        let err = res.expect_err("packet should exceed max_allowed_packet");
+        assert!(err.to_string().contains("max_allowed_packet"), "{err}");
+
+        conn.ping().await?;
+
+        return Ok(());
+    }
+
+    res?;
+
+    let ret: Vec<u8> = sqlx::query_scalar("SELECT data FROM blobs WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ret.len(), data.len());
+    assert!(ret == data);
+
+    Ok(())
+}
+
 async fn select_statement_count(conn: &mut MySqlConnection) -> Result<i64, sqlx::Error> {
     // Fails if performance schema does not exist
     sqlx::query_scalar(
@@ -603,3 +1299,376 @@ async fn select_statement_count(conn: &mut MySqlConnection) -> Result<i64, sqlx:
     .fetch_one(conn)
     .await
 }
+
+#[cfg(feature = "_rt-tokio")]
+#[sqlx_macros::test]
+async fn it_connects_through_a_socket_connector() -> anyhow::Result<()> {
+    use sqlx::net::StreamSocket;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    sqlx_test::setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let host = options.get_host().to_owned();
+    let port = options.get_port();
+
+    let connects = Arc::new(AtomicUsize::new(0));
+
+    // a "tunnel" to the server: an in-process pipe whose other end is forwarded over TCP
+    let options = options.socket_connector({
+        let connects = connects.clone();
+
+        move || {
+            let host = host.clone();
+            let connects = connects.clone();
+
+            async move {
+                connects.fetch_add(1, Ordering::SeqCst);
+
+                let (client, mut server) = tokio::io::duplex(8192);
+                let mut upstream = tokio::net::TcpStream::connect((&*host, port)).await?;
+
+                tokio::spawn(async move {
+                    let _ = tokio::io::copy_bidirectional(&mut server, &mut upstream).await;
+                });
+
+                Ok(StreamSocket::from_tokio(client))
+            }
+        }
+    });
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let value: i64 = sqlx::query_scalar("SELECT 1 + 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 2);
+
+    conn.close().await?;
+    assert_eq!(connects.load(Ordering::SeqCst), 1);
+
+    // every connection opened by the pool goes through the connector
+    let pool = MySqlPoolOptions::new()
+        .max_connections(3)
+        .connect_with(options)
+        .await?;
+
+    let queries = (0..3).map(|_| sqlx::query("SELECT SLEEP(0.1)").execute(&pool));
+    for res in futures::future::join_all(queries).await {
+        res?;
+    }
+
+    assert_eq!(pool.size(), 3);
+    assert_eq!(connects.load(Ordering::SeqCst), 4);
+
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_rows_as_dynamic_values() -> anyhow::Result<()> {
+    use sqlx::{DynamicValue, FromRow};
+    use std::collections::HashMap;
+
+    let mut conn = new::<MySql>().await?;
+
+    let sql = "SELECT NULL AS `null`, TRUE AS `bool`, -3 AS `int`, \
+               CAST(4 AS UNSIGNED) AS `uint`, CAST(1.5 AS DOUBLE) AS `double`, \
+               'text' AS `text`, X'deadbeef' AS `bytes`, CAST(-12.34 AS DECIMAL(10, 4)) AS `decimal`, \
+               1 AS `dup`, 2 AS `dup`";
+
+    let expected = vec![
+        ("null", DynamicValue::Null),
+        ("bool", DynamicValue::Int(1)),
+        ("int", DynamicValue::Int(-3)),
+        ("uint", DynamicValue::UInt(4)),
+        ("double", DynamicValue::Double(1.5)),
+        ("text", DynamicValue::from("text")),
+        ("bytes", DynamicValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef])),
+        ("decimal", DynamicValue::from("-12.3400")),
+        ("dup", DynamicValue::Int(1)),
+        ("dup", DynamicValue::Int(2)),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_owned(), value))
+    .collect::<Vec<_>>();
+
+    // the binary format
+    let row: Vec<(String, DynamicValue)> = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+    assert_eq!(row, expected);
+
+    // the text format
+    let row = sqlx::raw_sql(sql).fetch_one(&mut conn).await?;
+    assert_eq!(Vec::<(String, DynamicValue)>::from_row(&row)?, expected);
+
+    let map: HashMap<String, DynamicValue> = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+    assert_eq!(map.len(), 9);
+    assert_eq!(map["dup"], DynamicValue::Int(2));
+
+    // a `TIME` outside the range of a time of day
+    let map: HashMap<String, DynamicValue> =
+        sqlx::query_as("SELECT CAST('-838:59:59' AS TIME) AS `time`")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(map["time"], DynamicValue::from("-838:59:59"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_warnings_count() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("SET SESSION sql_mode = ''").await?;
+    conn.execute("CREATE TEMPORARY TABLE small_ints (n TINYINT)")
+        .await?;
+
+    let res = sqlx::query("INSERT INTO small_ints (n) VALUES (?)")
+        .bind(1000_i32)
+        .execute(&mut conn)
+        .await?;
+
+    // the value is clamped to 127 with a warning, rather than rejected
+    assert_eq!(res.warnings(), 1);
+    assert_eq!(conn.warnings(), 1);
+
+    conn.execute("SELECT 1").await?;
+    assert_eq!(conn.warnings(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_tracks_server_status_across_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    assert!(!conn
+        .server_status()
+        .contains(MySqlStatusFlags::SERVER_STATUS_IN_TRANS));
+
+    let tx = conn.begin().await?;
+    assert!(tx
+        .server_status()
+        .contains(MySqlStatusFlags::SERVER_STATUS_IN_TRANS));
+    tx.commit().await?;
+
+    assert!(!conn
+        .server_status()
+        .contains(MySqlStatusFlags::SERVER_STATUS_IN_TRANS));
+    assert!(conn
+        .server_status()
+        .contains(MySqlStatusFlags::SERVER_STATUS_AUTOCOMMIT));
+
+    Ok(())
+}
+
+#[test]
+fn it_generates_upserts() {
+    let qb = Upsert::<MySql>::into("db.user`s")
+        .columns(&["id", "name"])
+        .values(0..2, |mut b, i| {
+            b.push_bind(i).push_bind("name");
+        })
+        .conflict(&["id"])
+        .update(&["name"])
+        .into_query_builder();
+
+    assert_eq!(
+        qb.sql(),
+        r#"INSERT INTO `db`.`user``s` (`id`, `name`) VALUES (?, ?), (?, ?) ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"#
+    );
+}
+
+sqlx_test::test_upsert_conflicting_rows!(it_upserts_conflicting_rows(MySql));
+
+#[sqlx_macros::test]
+async fn it_can_stream_queries_with_owned_sql() -> anyhow::Result<()> {
+    use futures::stream::BoxStream;
+    use futures::Stream;
+
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    struct User {
+        id: i32,
+        name: String,
+    }
+
+    // the SQL is built here, but the stream outlives it and the borrow of the pool
+    fn stream_users(
+        pool: &MySqlPool,
+        min_id: i32,
+        order_by: &str,
+    ) -> impl Stream<Item = sqlx::Result<User>> + Send + 'static {
+        let sql = format!(
+            "SELECT id, name FROM (SELECT 1 AS id, 'alice' AS name UNION ALL SELECT 2, 'bob' \
+             UNION ALL SELECT 3, 'carol') AS users WHERE id >= ? ORDER BY {order_by}"
+        );
+
+        sqlx::query_as_owned(sql).bind(min_id).fetch(pool)
+    }
+
+    fn stream_rows(pool: &MySqlPool) -> BoxStream<'static, sqlx::Result<MySqlRow>> {
+        sqlx::query_owned(String::from("SELECT 1")).fetch(pool)
+    }
+
+    let pool = MySqlPoolOptions::new()
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let users = sqlx_core::rt::spawn(stream_users(&pool, 2, "id").try_collect::<Vec<_>>());
+
+    assert_eq!(
+        users.await?,
+        [
+            User {
+                id: 2,
+                name: "bob".into()
+            },
+            User {
+                id: 3,
+                name: "carol".into()
+            },
+        ]
+    );
+
+    let rows: Vec<MySqlRow> = stream_rows(&pool).try_collect().await?;
+    assert_eq!(rows.len(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_dropped_transaction_before_close() -> anyhow::Result<()> {
+    let mut other = new::<MySql>().await?;
+
+    // fail instead of waiting if the row is still locked
+    other
+        .execute("SET SESSION innodb_lock_wait_timeout = 1")
+        .await?;
+
+    sqlx_test::rolls_back_dropped_transaction_before_close::<MySql, _, _>(
+        other,
+        new::<MySql>,
+        "INSERT INTO close_test (id) VALUES (1)",
+    )
+    .await
+}
+
+#[cfg(feature = "chrono")]
+#[sqlx_macros::test]
+async fn it_decodes_timestamps_strictly() -> anyhow::Result<()> {
+    use sqlx::types::chrono::{AssumeUtc, DateTime, NaiveDate, NaiveDateTime, Utc};
+
+    setup_if_needed();
+
+    let naive = NaiveDate::from_ymd_opt(2024, 2, 29)
+        .unwrap()
+        .and_hms_opt(12, 34, 56)
+        .unwrap();
+
+    let options = env::var("DATABASE_URL")?.parse::<MySqlConnectOptions>()?;
+
+    for strict in [false, true] {
+        let mut conn = options.clone().strict_timestamps(strict).connect().await?;
+
+        conn.execute(
+            "SET time_zone = '+00:00'; \
+             CREATE TEMPORARY TABLE strict_timestamps (dt DATETIME, ts TIMESTAMP); \
+             INSERT INTO strict_timestamps VALUES ('2024-02-29 12:34:56', '2024-02-29 12:34:56')",
+        )
+        .await?;
+
+        let row = sqlx::query("SELECT dt, ts FROM strict_timestamps")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(row.try_get::<NaiveDateTime, _>(0)?, naive);
+        assert_eq!(row.try_get::<AssumeUtc, _>(0)?, AssumeUtc(naive.and_utc()));
+        assert_eq!(row.try_get::<DateTime<Utc>, _>(1)?, naive.and_utc());
+
+        let datetime_as_utc = row.try_get_unchecked::<DateTime<Utc>, _>(0);
+        let timestamp_as_naive = row.try_get_unchecked::<NaiveDateTime, _>(1);
+
+        if strict {
+            let err = datetime_as_utc.unwrap_err().to_string();
+            assert!(
+                err.contains("cannot decode DATETIME as `DateTime<Tz>`"),
+                "{err}"
+            );
+
+            let err = timestamp_as_naive.unwrap_err().to_string();
+            assert!(
+                err.contains("cannot decode TIMESTAMP as `NaiveDateTime`"),
+                "{err}"
+            );
+        } else {
+            assert_eq!(datetime_as_utc?, naive.and_utc());
+            assert_eq!(timestamp_as_naive?, naive);
+        }
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_tracks_session_state() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let database = conn.database().map(ToOwned::to_owned);
+    assert!(database.is_some());
+
+    // a failed run may have left the database behind
+    conn.execute(
+        "DROP DATABASE IF EXISTS sqlx_session_state; \
+         CREATE DATABASE sqlx_session_state; \
+         CREATE TEMPORARY TABLE session_track (v INT); \
+         INSERT INTO session_track VALUES (1)",
+    )
+    .await?;
+
+    let query = "SELECT v FROM session_track WHERE v > ?";
+
+    let v: i32 = sqlx::query_scalar(query)
+        .bind(0)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(v, 1);
+
+    conn.execute("USE sqlx_session_state").await?;
+
+    assert_eq!(conn.database(), Some("sqlx_session_state"));
+    assert_eq!(
+        conn.last_session_state().schema(),
+        Some("sqlx_session_state")
+    );
+
+    conn.execute("SET SESSION time_zone = '+02:00'").await?;
+
+    assert_eq!(
+        conn.last_session_state().system_variable("time_zone"),
+        Some("+02:00")
+    );
+    assert_eq!(conn.database(), Some("sqlx_session_state"));
+
+    // the statement prepared before `USE` would still read the table of the other database
+    conn.execute(
+        "CREATE TEMPORARY TABLE session_track (v INT); \
+         INSERT INTO session_track VALUES (2)",
+    )
+    .await?;
+
+    let v: i32 = sqlx::query_scalar(query)
+        .bind(0)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(v, 2);
+
+    conn.execute(&*format!("USE {}", database.as_deref().unwrap()))
+        .await?;
+    conn.execute("DROP DATABASE sqlx_session_state").await?;
+
+    assert_eq!(conn.database(), database.as_deref());
+
+    Ok(())
+}
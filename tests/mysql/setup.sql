@@ -17,6 +17,13 @@ CREATE TABLE tweet_reply
     CONSTRAINT tweet_id_fk FOREIGN KEY (tweet_id) REFERENCES tweet(id)
 );
 
+-- legacy camelCase columns, see `#[sqlx(rename_all = "camelCase")]`
+CREATE TABLE user_profiles (
+    userId INTEGER PRIMARY KEY,
+    displayName TEXT NOT NULL,
+    emailAddress TEXT
+);
+
 CREATE TABLE products (
     product_no INTEGER,
     name TEXT,
@@ -9,10 +9,14 @@ use sqlx::{Executor, Row};
 
 use sqlx::types::Text;
 
-use sqlx::mysql::types::MySqlTime;
+use sqlx::mysql::types::{MySqlSet, MySqlTime};
 use sqlx_mysql::types::MySqlTimeSign;
 
-use sqlx_test::{new, test_type};
+use sqlx_test::{new, test_null_type, test_type};
+
+test_null_type!(option_i32<i32>(MySql, "NULL", "CAST(NULL AS SIGNED)"));
+test_null_type!(option_string<String>(MySql, "NULL", "CAST(NULL AS CHAR)"));
+test_null_type!(option_bytes<Vec<u8>>(MySql, "NULL", "CAST(NULL AS BINARY)"));
 
 test_type!(bool(MySql, "false" == false, "true" == true));
 
@@ -40,6 +44,8 @@ test_type!(string<String>(MySql,
     "''" == ""
 ));
 
+test_type!(char(MySql, "'a'" == 'a', "'é'" == 'é', "'🦀'" == '🦀',));
+
 test_type!(bytes<Vec<u8>>(MySql,
     "X'DEADBEEF'"
         == vec![0xDE_u8, 0xAD, 0xBE, 0xEF],
@@ -49,6 +55,38 @@ test_type!(bytes<Vec<u8>>(MySql,
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+test_type!(byte_array_16<[u8; 16]>(MySql,
+    "X'b731678f636f4135bc6f19440c13bd19'"
+        == [0xb7_u8, 0x31, 0x67, 0x8f, 0x63, 0x6f, 0x41, 0x35, 0xbc, 0x6f, 0x19, 0x44, 0x0c, 0x13, 0xbd, 0x19],
+));
+
+test_type!(byte_array_32<[u8; 32]>(MySql,
+    "X'abababababababababababababababababababababababababababababababab'" == [0xAB_u8; 32],
+));
+
+test_type!(byte_array_option<Option<[u8; 4]>>(MySql,
+    "X'DEADBEEF'" == Some([0xDE_u8, 0xAD, 0xBE, 0xEF]),
+    "NULL" == None::<[u8; 4]>,
+));
+
+#[sqlx_macros::test]
+async fn test_byte_array_rejects_wrong_length() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let err = sqlx::query_scalar::<_, [u8; 16]>("SELECT X'DEADBEEF'")
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("expected an error decoding 4 bytes as [u8; 16]");
+
+    assert!(
+        err.to_string()
+            .contains("expected 16 bytes to decode `[u8; 16]`, got 4 bytes"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "uuid")]
 test_type!(uuid<sqlx::types::Uuid>(MySql,
     "x'b731678f636f4135bc6f19440c13bd19'"
@@ -151,6 +189,30 @@ mod chrono {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_type_chrono_date_out_of_range() -> anyhow::Result<()> {
+        let mut conn = sqlx_test::new::<MySql>().await?;
+
+        let date = NaiveDate::from_ymd_opt(-50000, 1, 1).unwrap();
+
+        let err = sqlx::query("SELECT ?, ?")
+            .bind(1)
+            .bind(date)
+            .fetch_one(&mut conn)
+            .await
+            .expect_err("expected the date to fail to encode");
+
+        let sqlx::Error::Encode(err) = err else {
+            panic!("expected an encode error, got {err:?}");
+        };
+
+        let message = err.to_string();
+        assert!(message.contains("argument $2"), "{message}");
+        assert!(message.contains("out of range for MySQL"), "{message}");
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
@@ -258,6 +320,8 @@ test_type!(decimal<sqlx::types::Decimal>(MySql,
 mod json_tests {
     use serde_json::{json, Value as JsonValue};
 
+    use std::collections::{BTreeMap, HashMap};
+
     use sqlx::types::Json;
     use sqlx_test::test_type;
 
@@ -273,6 +337,53 @@ mod json_tests {
         "'[\"Hello\",\"World!\"]'" == json!(["Hello", "World!"])
     ));
 
+    test_type!(json_btree_map<BTreeMap<String, JsonValue>>(
+        MySql,
+        // MySQL 8.0.27 changed `<=>` to return an unsigned integer
+        "SELECT CAST(CAST({0} AS BINARY) <=> CAST(? AS BINARY) AS SIGNED INTEGER), CAST({0} AS BINARY) as _2, ? as _3",
+        "'{\"friend\":{\"name\":\"Bob\"},\"name\":\"Joe\",\"名前\":\"😎\"}'"
+            == serde_json::from_value::<BTreeMap<String, JsonValue>>(json!({
+                "name": "Joe",
+                "friend": { "name": "Bob" },
+                "名前": "😎",
+            })).unwrap()
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_json_as_bytes() -> anyhow::Result<()> {
+        #[derive(sqlx::Type, Debug, PartialEq)]
+        #[sqlx(transparent)]
+        struct RawJson(Vec<u8>);
+
+        let mut conn = new::<MySql>().await?;
+
+        let value: RawJson = sqlx::query_scalar("SELECT JSON_OBJECT('a', 1)")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, RawJson(br#"{"a": 1}"#.to_vec()));
+
+        Ok(())
+    }
+
+    #[sqlx_macros::test]
+    async fn test_json_map_rejects_non_object() -> anyhow::Result<()> {
+        let mut conn = new::<MySql>().await?;
+
+        let err = sqlx::query_scalar::<_, HashMap<String, JsonValue>>("SELECT JSON_ARRAY(1, 2)")
+            .fetch_one(&mut conn)
+            .await
+            .expect_err("expected an error decoding an array as a map");
+
+        assert!(
+            err.to_string()
+                .contains("expected a JSON object, found an array"),
+            "{err}"
+        );
+
+        Ok(())
+    }
+
     #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
     struct Friend {
         name: String,
@@ -281,8 +392,7 @@ mod json_tests {
 
     test_type!(json_struct<Json<Friend>>(
         MySql,
-        // MySQL 8.0.27 changed `<=>` to return an unsigned integer
-        "SELECT CAST(CAST({0} AS BINARY) <=> CAST(? AS BINARY) AS SIGNED INTEGER), CAST({0} AS BINARY) as _2, ? as _3",
+        compare = "CAST({0} AS BINARY) <=> CAST(? AS BINARY)",
         "\'{\"name\":\"Joe\",\"age\":33}\'" == Json(Friend { name: "Joe".to_string(), age: 33 })
     ));
 
@@ -342,6 +452,76 @@ CREATE TEMPORARY TABLE with_bits (
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_year_bit_and_set() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE with_year_bit_set (
+    id INT PRIMARY KEY,
+    year YEAR NOT NULL,
+    bit_1 BIT(1) NOT NULL,
+    bit_64 BIT(64) NOT NULL,
+    flags SET('a', 'b', 'c', 'd') NOT NULL
+);
+    "#,
+    )
+    .await?;
+
+    let rows = [
+        (1, 1901_u16, 0_u8, 0_u64, MySqlSet::default()),
+        (
+            2,
+            2155,
+            1,
+            u64::MAX,
+            MySqlSet::from_iter(["a", "b", "c", "d"]),
+        ),
+        (3, 0, 1, 0x0102_0304_0506_0708, MySqlSet::from_iter(["b"])),
+    ];
+
+    for (id, year, bit_1, bit_64, flags) in &rows {
+        sqlx::query("INSERT INTO with_year_bit_set VALUES (?, ?, ?, ?, ?)")
+            .bind(id)
+            .bind(year)
+            .bind(bit_1)
+            .bind(bit_64)
+            .bind(flags)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let sql = "SELECT year, bit_1, bit_64, flags FROM with_year_bit_set WHERE id = ?";
+
+    for (id, year, bit_1, bit_64, flags) in rows {
+        for binary in [false, true] {
+            let row = if binary {
+                sqlx::query(sql).bind(id).fetch_one(&mut conn).await?
+            } else {
+                conn.fetch_one(&*sql.replace('?', &id.to_string())).await?
+            };
+
+            assert_eq!(row.try_get::<u16, _>("year")?, year);
+            assert_eq!(row.try_get::<i32, _>("year")?, i32::from(year));
+            assert_eq!(row.try_get::<u8, _>("bit_1")?, bit_1);
+            assert_eq!(row.try_get::<u64, _>("bit_64")?, bit_64);
+            assert_eq!(
+                row.try_get::<Vec<u8>, _>("bit_64")?,
+                bit_64.to_be_bytes().to_vec()
+            );
+            assert_eq!(row.try_get::<MySqlSet, _>("flags")?, flags);
+            assert_eq!(row.try_get::<String, _>("flags")?, flags.join(","));
+
+            // YEAR and wide BIT columns don't fit in a `u8`
+            assert!(row.try_get::<u8, _>("year").is_err());
+            assert!(row.try_get::<u32, _>("bit_64").is_err());
+        }
+    }
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_text_adapter() -> anyhow::Result<()> {
     #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
@@ -384,3 +564,103 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+// Checks that `$col` decodes as each of the `ok` types, and is out of range for the `err` types.
+macro_rules! check_int_column {
+    ($row:expr, $col:tt == $value:literal, ok: [$($ok:ty),*], err: [$($err:ty),*]) => {
+        $(
+            assert_eq!(
+                $row.try_get::<$ok, _>($col)?,
+                $value,
+                "{} as {}",
+                $col,
+                stringify!($ok)
+            );
+        )*
+
+        $(
+            let err = $row.try_get::<$err, _>($col).unwrap_err();
+            assert!(
+                err.to_string().contains(concat!(
+                    "value ",
+                    stringify!($value),
+                    " is out of range for `",
+                    stringify!($err),
+                    "`"
+                )),
+                "{} as {}: {err}",
+                $col,
+                stringify!($err)
+            );
+        )*
+    };
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_integers_across_sizes_and_signedness() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE int_matrix (
+    id INT PRIMARY KEY,
+    ti TINYINT, si SMALLINT, i INT, bi BIGINT,
+    tu TINYINT UNSIGNED, su SMALLINT UNSIGNED, iu INT UNSIGNED, bu BIGINT UNSIGNED
+);
+
+INSERT INTO int_matrix VALUES
+    (1, 42, 42, 42, 42, 42, 42, 42, 42),
+    (2, -128, -32768, -2147483648, -9223372036854775808,
+        255, 65535, 4294967295, 18446744073709551615);
+    "#,
+    )
+    .await?;
+
+    let sql = "SELECT * FROM int_matrix WHERE id = ?";
+
+    for binary in [false, true] {
+        let (small, extreme) = if binary {
+            (
+                sqlx::query(sql).bind(1).fetch_one(&mut conn).await?,
+                sqlx::query(sql).bind(2).fetch_one(&mut conn).await?,
+            )
+        } else {
+            (
+                conn.fetch_one(&*sql.replace('?', "1")).await?,
+                conn.fetch_one(&*sql.replace('?', "2")).await?,
+            )
+        };
+
+        for col in ["ti", "si", "i", "bi", "tu", "su", "iu", "bu"] {
+            check_int_column!(small, col == 42, ok: [i8, i16, i32, i64, u8, u16, u32, u64], err: []);
+        }
+
+        check_int_column!(extreme, "ti" == -128,
+            ok: [i8, i16, i32, i64],
+            err: [u8, u16, u32, u64]);
+        check_int_column!(extreme, "si" == -32768,
+            ok: [i16, i32, i64],
+            err: [i8, u8, u16, u32, u64]);
+        check_int_column!(extreme, "i" == -2147483648,
+            ok: [i32, i64],
+            err: [i8, i16, u8, u16, u32, u64]);
+        check_int_column!(extreme, "bi" == -9223372036854775808,
+            ok: [i64],
+            err: [i8, i16, i32, u8, u16, u32, u64]);
+
+        check_int_column!(extreme, "tu" == 255,
+            ok: [u8, u16, u32, u64, i16, i32, i64],
+            err: [i8]);
+        check_int_column!(extreme, "su" == 65535,
+            ok: [u16, u32, u64, i32, i64],
+            err: [u8, i8, i16]);
+        check_int_column!(extreme, "iu" == 4294967295,
+            ok: [u32, u64, i64],
+            err: [u8, u16, i8, i16, i32]);
+        check_int_column!(extreme, "bu" == 18446744073709551615,
+            ok: [u64],
+            err: [u8, u16, u32, i8, i16, i32, i64]);
+    }
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use futures::TryStreamExt;
 use sqlx::postgres::types::PgRange;
-use sqlx::{Connection, Executor, FromRow, Postgres};
+use sqlx::{Connection, Executor, FromRow, Postgres, Row, RowSliceExt};
 use sqlx_postgres::PgHasArrayType;
 use sqlx_test::{new, test_type};
 use std::fmt::Debug;
+use std::num::NonZeroI64;
 use std::ops::Bound;
 
 // Transparent types are rust-side wrappers over DB types
@@ -18,6 +19,10 @@ struct Transparent(i32);
 #[sqlx(transparent, no_pg_array)]
 struct TransparentArray(Vec<i64>);
 
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct UserId(NonZeroI64);
+
 #[sqlx_macros::test]
 async fn test_transparent_slice_to_array() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -152,6 +157,23 @@ test_type!(transparent_array<TransparentArray>(Postgres,
     "'{ 23523, 123456, 789 }'::int8[]" == TransparentArray(vec![23523, 123456, 789])
 ));
 
+test_type!(transparent_non_zero<UserId>(Postgres,
+    "42::int8" == UserId(NonZeroI64::new(42).unwrap())
+));
+
+test_type!(transparent_non_zero_vec<Vec<UserId>>(Postgres,
+    "'{1, 2, 3}'::int8[]" == vec![
+        UserId(NonZeroI64::new(1).unwrap()),
+        UserId(NonZeroI64::new(2).unwrap()),
+        UserId(NonZeroI64::new(3).unwrap()),
+    ]
+));
+
+test_type!(transparent_non_zero_option<Option<UserId>>(Postgres,
+    "NULL::int8" == None::<UserId>,
+    "7::int8" == Some(UserId(NonZeroI64::new(7).unwrap()))
+));
+
 test_type!(weak_enum<Weak>(Postgres,
     "0::int4" == Weak::One,
     "2::int4" == Weak::Two,
@@ -876,3 +898,197 @@ VALUES ($1)
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn test_from_row_verify() -> anyhow::Result<()> {
+    use sqlx::check::Mismatch;
+
+    let mut conn = new::<Postgres>().await?;
+
+    #[allow(dead_code)]
+    #[derive(FromRow)]
+    struct Owner {
+        owner_id: Option<i64>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(FromRow)]
+    struct Tweet {
+        id: i64,
+        text: String,
+        #[sqlx(flatten)]
+        _owner: Owner,
+        #[sqlx(default)]
+        _likes: i64,
+    }
+
+    let report =
+        sqlx::check::verify::<Tweet, _>(&mut conn, "SELECT id, text, owner_id FROM tweet").await?;
+
+    assert!(report.is_ok(), "{report}");
+
+    #[allow(dead_code)]
+    #[derive(FromRow)]
+    struct MismatchedTweet {
+        _id: i64,
+        text: i32,
+        owner_id: i64,
+    }
+
+    let report = sqlx::check::verify::<MismatchedTweet, _>(
+        &mut conn,
+        "SELECT id AS _id, text, owner_id FROM tweet",
+    )
+    .await?;
+
+    assert_eq!(
+        report.mismatches,
+        [
+            Mismatch::Type {
+                column: "`text`".into(),
+                rust_type: "i32",
+                sql_type: "TEXT".into(),
+            },
+            Mismatch::Nullability {
+                column: "`owner_id`".into(),
+                rust_type: "i64",
+            },
+        ]
+    );
+
+    #[allow(dead_code)]
+    #[derive(FromRow)]
+    struct TweetId(i64, String);
+
+    let report = sqlx::check::verify::<TweetId, _>(&mut conn, "SELECT id FROM tweet").await?;
+
+    assert_eq!(
+        report.mismatches,
+        [Mismatch::ColumnCount {
+            expected: 2,
+            found: 1
+        }]
+    );
+
+    let report =
+        sqlx::check::verify::<MismatchedTweet, _>(&mut conn, "SELECT id FROM tweet").await?;
+
+    assert!(report.mismatches.contains(&Mismatch::MissingColumn {
+        column: "`text`".into()
+    }));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_row_try_into_struct() -> anyhow::Result<()> {
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct Name {
+        id: i32,
+        name: String,
+    }
+
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct NameRef<'r> {
+        id: i32,
+        name: &'r str,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let rows = sqlx::query("SELECT * FROM (VALUES (1, 'foo'), (2, 'bar')) AS t(id, name)")
+        .fetch_all(&mut conn)
+        .await?;
+
+    let first: Name = rows[0].try_into_struct()?;
+    assert_eq!(
+        first,
+        Name {
+            id: 1,
+            name: "foo".to_owned()
+        }
+    );
+
+    let first: NameRef<'_> = rows[0].try_into_struct()?;
+    assert_eq!(first, NameRef { id: 1, name: "foo" });
+
+    let owned: Vec<Name> = rows.try_into_structs()?;
+    assert_eq!(owned.len(), 2);
+    assert_eq!(owned[1].name, "bar");
+
+    let borrowed: Vec<NameRef<'_>> = rows.try_into_structs()?;
+    assert_eq!(
+        borrowed,
+        [
+            NameRef { id: 1, name: "foo" },
+            NameRef { id: 2, name: "bar" }
+        ]
+    );
+
+    let err = rows[0]
+        .try_into_struct::<(i32, i32)>()
+        .expect_err("expected a decode error");
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{err:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_from_row_with_changing_column_order() -> anyhow::Result<()> {
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct Item {
+        id: i32,
+        name: String,
+        #[sqlx(default)]
+        note: Option<String>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let queries = [
+        "SELECT 1 AS id, 'one' AS name",
+        "SELECT 'one' AS name, 1 AS id",
+        "SELECT 'one' AS name, 'note' AS note, 1 AS id",
+        "SELECT 1 AS id, 'one' AS name",
+    ];
+
+    let mut rows = Vec::new();
+
+    for sql in queries {
+        rows.push(sqlx::query(sql).fetch_one(&mut conn).await?);
+    }
+
+    // decode rows of the different queries interleaved
+    for _ in 0..2 {
+        for (i, row) in rows.iter().enumerate() {
+            let item = Item::from_row(row)?;
+
+            assert_eq!(item.id, 1);
+            assert_eq!(item.name, "one");
+            assert_eq!(item.note.is_some(), i == 2);
+        }
+    }
+
+    let row = sqlx::query("SELECT 'one' AS id, 'two' AS name")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = Item::from_row(&row).expect_err("expected a decode error");
+    let expected = row
+        .try_get::<i32, _>("id")
+        .expect_err("expected a decode error");
+    assert_eq!(err.to_string(), expected.to_string());
+    assert!(err.to_string().contains("\"id\""), "{err}");
+
+    let row = sqlx::query("SELECT 'two' AS name")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = Item::from_row(&row).expect_err("expected a missing column");
+    assert!(
+        matches!(err, sqlx::Error::ColumnNotFound(ref name) if name == "id"),
+        "{err:?}"
+    );
+
+    Ok(())
+}
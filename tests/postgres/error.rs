@@ -1,4 +1,5 @@
-use sqlx::{error::ErrorKind, postgres::Postgres, Connection};
+use sqlx::postgres::{PgConnectOptions, PgConnection, Postgres};
+use sqlx::{error::ErrorKind, Connection};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -74,3 +75,21 @@ async fn it_fails_with_check_violation() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_returns_structured_errors() -> anyhow::Result<()> {
+    sqlx_test::structured_errors::<Postgres>().await?;
+
+    // and the ones specific to this driver
+    let err = "postgres://localhost/db?sslmode=sometimes"
+        .parse::<PgConnectOptions>()
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)), "{err:?}");
+
+    // nothing listens on port 1
+    let err = PgConnection::connect("postgres://postgres@127.0.0.1:1/sqlx")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Io(_)), "{err:?}");
+    Ok(())
+}
@@ -152,6 +152,37 @@ async fn test_query_as() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_array_params() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let ids = vec![1i64, 3];
+
+    let rows = sqlx::query!(
+        r#"SELECT id "id!" from (VALUES (1::int8), (2), (3)) accounts(id) where id = ANY($1)"#,
+        &ids[..]
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(rows.iter().map(|row| row.id).collect::<Vec<_>>(), [1, 3]);
+
+    let names = vec!["Herp Derpinson".to_string()];
+
+    let accounts = sqlx::query_as!(
+        Account,
+        r#"SELECT id "id!", name from (VALUES (1, 'Herp Derpinson'), (2, 'Derp Herpinson')) accounts(id, name) where name = ANY($1)"#,
+        names
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].id, 1);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     r#type: i32,
@@ -284,6 +315,81 @@ async fn query_by_string() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn query_by_option_references() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let name = String::from("Ferris");
+    let some_name = Some(name.clone());
+    let none_name: Option<String> = None;
+    let id = 1_i32;
+
+    let result = sqlx::query!(
+        r#"SELECT $1::text as "a", $2::text as "b", $3::text as "c", $4::int4 as "d", $5::text as "e""#,
+        Some(&name),
+        &some_name,
+        some_name.as_ref(),
+        Some(&id),
+        &none_name,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(result.a.as_deref(), Some("Ferris"));
+    assert_eq!(result.b.as_deref(), Some("Ferris"));
+    assert_eq!(result.c.as_deref(), Some("Ferris"));
+    assert_eq!(result.d, Some(1));
+    assert_eq!(result.e, None);
+
+    let row: (Option<String>, Option<String>, Option<String>, Option<i32>) =
+        sqlx::query_as("SELECT $1::text, $2::text, $3::text, $4::int4")
+            .bind(Some(&name))
+            .bind(&some_name)
+            .bind(Some("Ferris"))
+            .bind(None::<&i32>)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(
+        row,
+        (
+            Some(name.clone()),
+            Some(name.clone()),
+            Some(name.clone()),
+            None
+        )
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+#[cfg(feature = "ipnetwork")]
+async fn query_by_ip_addr() -> anyhow::Result<()> {
+    use std::net::IpAddr;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let addr: IpAddr = "192.168.0.1".parse()?;
+    let addrs = vec![addr];
+
+    // `IpAddr` is accepted for `INET` parameters, which otherwise expect `IpNetwork`
+    let result = sqlx::query!(
+        r#"SELECT $1::inet as "addr!: IpAddr", $2::inet = $1 as "same!", $3::inet[] as "addrs!""#,
+        addr,
+        Some(&addr),
+        &addrs[..]
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(result.addr, addr);
+    assert!(result.same);
+    assert_eq!(result.addrs, [addr.into()]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 #[cfg(feature = "bigdecimal")]
 async fn query_by_bigdecimal() -> anyhow::Result<()> {
@@ -342,6 +448,53 @@ async fn test_nullable_err() -> anyhow::Result<()> {
     panic!("expected `UnexpectedNullError`, got {err}")
 }
 
+#[sqlx_macros::test]
+async fn test_query_as_optional() -> anyhow::Result<()> {
+    #[derive(Debug)]
+    struct Account {
+        id: i32,
+        name: Option<String>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let accounts = sqlx::query_as!(
+        Option<Account>,
+        r#"SELECT accounts.id "id!", accounts.name
+           FROM (VALUES (1), (2), (3)) ids(id)
+           LEFT JOIN (VALUES (1, 'Herp Derpinson'), (2, null)) accounts(id, name)
+           ON accounts.id = ids.id
+           ORDER BY ids.id"#
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(accounts.len(), 3);
+
+    let account = accounts[0].as_ref().expect("row 1 should be present");
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name.as_deref(), Some("Herp Derpinson"));
+
+    // a partially `NULL` row is still present
+    let account = accounts[1].as_ref().expect("row 2 should be present");
+    assert_eq!(account.id, 2);
+    assert_eq!(account.name, None);
+
+    // an all-`NULL` row is `None` instead of failing to decode `id`
+    assert!(accounts[2].is_none());
+
+    let account = sqlx::query_as!(
+        std::option::Option<Account>,
+        r#"SELECT null::int4 "id!", null::text "name""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(account.is_none());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_many_args() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -659,3 +812,55 @@ async fn pghstore_tests() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[derive(Debug, PartialEq, sqlx::FromRow)]
+#[sqlx(rename_all = "camelCase")]
+struct UserProfile {
+    user_id: i32,
+    display_name: String,
+    #[sqlx(rename = "emailAddress")]
+    email: Option<String>,
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_rename_all() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    sqlx::query!(
+        r#"INSERT INTO user_profiles ("userId", "displayName", "emailAddress")
+           VALUES (1, 'Herp Derpinson', 'herp@example.com')"#
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let expected = UserProfile {
+        user_id: 1,
+        display_name: "Herp Derpinson".into(),
+        email: Some("herp@example.com".into()),
+    };
+
+    let derived: UserProfile = sqlx::query_as(r#"SELECT * FROM user_profiles WHERE "userId" = $1"#)
+        .bind(1_i32)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(derived, expected);
+
+    // the macro can't see `#[sqlx(rename)]`, so that column is aliased instead
+    let checked = sqlx::query_as!(
+        UserProfile,
+        rename_all = "camelCase",
+        r#"SELECT "userId", "displayName", "emailAddress" AS email
+           FROM user_profiles WHERE "userId" = $1"#,
+        1_i32
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    assert_eq!(checked, expected);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
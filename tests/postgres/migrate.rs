@@ -1,8 +1,8 @@
 use sqlx::migrate::Migrator;
 use sqlx::pool::PoolConnection;
 use sqlx::postgres::{PgConnection, Postgres};
-use sqlx::Executor;
 use sqlx::Row;
+use sqlx::{Connection, Executor};
 use std::path::Path;
 
 #[sqlx::test(migrations = false)]
@@ -85,6 +85,62 @@ async fn no_tx(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn no_tx_concurrent_index(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    let migrator = Migrator::new(Path::new("tests/postgres/migrations_no_tx_index")).await?;
+
+    assert!(migrator.iter().any(|migration| migration.no_tx));
+
+    // run migration
+    migrator.run(&mut conn).await?;
+
+    // check outcome
+    let res: String = conn
+        .fetch_one(
+            "SELECT indexname FROM pg_indexes WHERE indexname = 'migrations_no_tx_index_test_name'",
+        )
+        .await?
+        .get(0);
+    assert_eq!(res, "migrations_no_tx_index_test_name");
+
+    let versions: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success ORDER BY version")
+            .fetch_all(&mut *conn)
+            .await?;
+    assert_eq!(versions, [20240101120000, 20240102120000]);
+
+    // running it a 2nd time should still work
+    migrator.run(&mut conn).await?;
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn create_and_drop_database_with_unusual_name() -> anyhow::Result<()> {
+    use sqlx::migrate::MigrateDatabase;
+
+    let mut url = url::Url::parse(&dotenvy::var("DATABASE_URL")?)?;
+    url.set_path("/_sqlx-unusual \"name\"/");
+    url.query_pairs_mut()
+        .append_pair("application_name", "sqlx-unusual-name-test");
+    let url = url.as_str();
+
+    Postgres::drop_database(url).await?;
+    assert!(!Postgres::database_exists(url).await?);
+
+    Postgres::create_database(url).await?;
+    assert!(Postgres::database_exists(url).await?);
+
+    // a connection to the database that must be terminated to drop it
+    let _conn = PgConnection::connect(url).await?;
+
+    Postgres::force_drop_database(url).await?;
+    assert!(!Postgres::database_exists(url).await?);
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut PgConnection) -> anyhow::Result<()> {
     conn.execute("DROP DATABASE IF EXISTS test_db").await.ok();
@@ -92,6 +148,9 @@ async fn clean_up(conn: &mut PgConnection) -> anyhow::Result<()> {
     conn.execute("DROP TABLE migrations_reversible_test")
         .await
         .ok();
+    conn.execute("DROP TABLE migrations_no_tx_index_test")
+        .await
+        .ok();
     conn.execute("DROP TABLE _sqlx_migrations").await.ok();
 
     Ok(())
@@ -0,0 +1,4 @@
+CREATE TABLE migrations_no_tx_index_test (
+    id BIGINT PRIMARY KEY,
+    name TEXT NOT NULL
+);
@@ -0,0 +1,4 @@
+-- no-transaction
+
+-- fails inside a transaction block
+CREATE INDEX CONCURRENTLY migrations_no_tx_index_test_name ON migrations_no_tx_index_test (name);
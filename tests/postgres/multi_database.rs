@@ -0,0 +1,70 @@
+//! Queries checked against Postgres at `DATABASE_URL` and SQLite at `DATABASE_URL_SQLITE`
+//! in the same crate.
+use sqlx::{Connection, Postgres, SqliteConnection};
+use sqlx_test::new;
+
+#[sqlx_macros::test]
+async fn it_checks_queries_against_named_databases() -> anyhow::Result<()> {
+    let mut pg_conn = new::<Postgres>().await?;
+    let mut sqlite_conn = SqliteConnection::connect(&dotenvy::var("DATABASE_URL_SQLITE")?).await?;
+
+    let row = sqlx::query!(r#"SELECT $1::int4 AS "id!""#, 1_i32)
+        .fetch_one(&mut pg_conn)
+        .await?;
+
+    assert_eq!(row.id, 1);
+
+    // `?1` is not a valid placeholder in Postgres
+    let tweet = sqlx::query!(
+        database = "sqlite",
+        "SELECT id, text FROM tweet WHERE id = ?1",
+        row.id
+    )
+    .fetch_one(&mut sqlite_conn)
+    .await?;
+
+    assert_eq!(tweet.id, 1);
+    assert_eq!(tweet.text, "#sqlx is pretty cool!");
+
+    let text: String = sqlx::query_scalar!(
+        database = "sqlite",
+        "SELECT text FROM tweet WHERE id = ?1",
+        tweet.id
+    )
+    .fetch_one(&mut sqlite_conn)
+    .await?;
+
+    assert_eq!(text, tweet.text);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_query_as_against_named_databases() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq)]
+    struct Account {
+        id: i64,
+        name: String,
+    }
+
+    let mut sqlite_conn = SqliteConnection::connect(&dotenvy::var("DATABASE_URL_SQLITE")?).await?;
+
+    let account = sqlx::query_as!(
+        database = "sqlite",
+        Account,
+        "SELECT id, name FROM accounts WHERE id = ?1",
+        1_i64
+    )
+    .fetch_one(&mut sqlite_conn)
+    .await?;
+
+    assert_eq!(
+        account,
+        Account {
+            id: 1,
+            name: "Herp Derpinson".into()
+        }
+    );
+
+    Ok(())
+}
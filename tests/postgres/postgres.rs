@@ -1,13 +1,15 @@
+use futures::future::BoxFuture;
 use futures::{Stream, StreamExt, TryStreamExt};
 
 use sqlx::postgres::types::Oid;
 use sqlx::postgres::{
     PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
-    PgPoolOptions, PgRow, PgSeverity, Postgres, PG_COPY_MAX_DATA_LEN,
+    PgPool, PgPoolOptions, PgRow, PgSeverity, PgTransactionOptions, PgTypeInfo, PgTypeKind,
+    Postgres, PG_COPY_MAX_DATA_LEN,
 };
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
+use sqlx::{Column, Connection, Executor, IsolationLevel, Row, Statement, TypeInfo};
 use sqlx_core::{bytes::Bytes, error::BoxDynError};
-use sqlx_test::{new, pool, setup_if_needed};
+use sqlx_test::{new, pool, setup_if_needed, with_committed_fixtures, with_fixtures};
 use std::env;
 use std::pin::{pin, Pin};
 use std::sync::Arc;
@@ -27,6 +29,124 @@ async fn it_connects() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_tracks_parameter_status() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let version_num: String = sqlx::query_scalar("SHOW server_version_num")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(conn.server_version_num(), Some(version_num.parse()?));
+    assert!(conn.parameter("server_version").is_some());
+    assert_eq!(conn.parameter("standard_conforming_strings"), Some("on"));
+    assert_eq!(conn.parameter("no_such_parameter"), None);
+
+    conn.execute("SET TIME ZONE 'America/Los_Angeles'").await?;
+    assert_eq!(conn.parameter("TimeZone"), Some("America/Los_Angeles"));
+
+    conn.execute("SET TIME ZONE 'UTC'").await?;
+    assert_eq!(conn.parameter("TimeZone"), Some("UTC"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_summarizes_bound_arguments() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let query = sqlx::query("SELECT $1::int8, $2::text, $3::text")
+        .bind(1_i64)
+        .bind("hello world")
+        .bind(None::<&str>);
+
+    let summary = query.arguments_summary().unwrap();
+
+    assert_eq!(summary.arguments().len(), 3);
+    assert_eq!(
+        summary.to_string(),
+        "[$1: INT8 (8 bytes), $2: TEXT (11 bytes), $3: TEXT NULL]"
+    );
+
+    // the summary does not consume the arguments
+    query.execute(&mut conn).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_utility_statements_as_prepared_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // these can't run inside a transaction block, but a lone prepared statement isn't in one;
+    // `.simple()` is only needed when running them alongside other statements
+    sqlx::query!(
+        "CREATE INDEX CONCURRENTLY IF NOT EXISTS tweet_owner_id_concurrently ON tweet (owner_id)"
+    )
+    .execute(&mut conn)
+    .await?;
+
+    sqlx::query!("DROP INDEX CONCURRENTLY tweet_owner_id_concurrently")
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query!("VACUUM tweet").execute(&mut conn).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_simple_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    sqlx::query!(
+        "CREATE INDEX CONCURRENTLY IF NOT EXISTS tweet_owner_id_concurrently ON tweet (owner_id)"
+    )
+    .simple()
+    .execute(&mut conn)
+    .await?;
+
+    sqlx::query!("DROP INDEX CONCURRENTLY tweet_owner_id_concurrently")
+        .simple()
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query!("VACUUM tweet")
+        .simple()
+        .execute(&mut conn)
+        .await?;
+
+    let n = sqlx::query_scalar!(r#"SELECT 1 AS "n!""#)
+        .simple()
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(n, 1);
+
+    // more than one statement can't be prepared
+    let res = sqlx::query("SELECT 1; SELECT 2").fetch_all(&mut conn).await;
+
+    assert!(res.is_err());
+
+    let rows = sqlx::query("SELECT 1; SELECT 2")
+        .simple()
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+
+    // and the simple query protocol can't have parameters
+    let res = sqlx::query("SELECT $1::int4")
+        .bind(1_i32)
+        .simple()
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Encode(_))), "{res:?}");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_select_void() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -159,21 +279,100 @@ async fn it_can_inspect_constraint_errors() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_executes() -> anyhow::Result<()> {
+async fn it_can_inspect_syntax_error_positions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    let _ = conn
-        .execute(
-            r#"
-CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY);
-            "#,
-        )
+    let sql = "SELECT 'ünïcödé', FROM pg_class";
+    let err = sqlx::query(sql).execute(&mut conn).await.unwrap_err();
+    let err = err
+        .into_database_error()
+        .unwrap()
+        .downcast::<PgDatabaseError>();
+
+    assert_eq!(err.severity(), PgSeverity::Error);
+    assert_eq!(err.code(), "42601");
+
+    let encoding: String = sqlx::query_scalar("SHOW server_encoding")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // the position is in characters, so it is before the byte offset of `FROM`;
+    // with `SQL_ASCII`, the server counts every byte as a character instead
+    let position = err.position().unwrap();
+
+    if encoding == "SQL_ASCII" {
+        assert_eq!(position, PgErrorPosition::Original(23));
+    } else {
+        assert_eq!(position, PgErrorPosition::Original(19));
+        assert_eq!(&sql[position.byte_offset(sql).unwrap()..], "FROM pg_class");
+    }
+
+    assert_eq!(err.internal_position(), None);
+    assert_eq!(err.internal_query(), None);
+
+    let err = sqlx::query("DO $$ BEGIN EXECUTE 'SELECT 1 +'; END $$")
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+    let err = err
+        .into_database_error()
+        .unwrap()
+        .downcast::<PgDatabaseError>();
+
+    assert_eq!(err.code(), "42601");
+    assert_eq!(err.internal_query(), Some("SELECT 1 +"));
+    assert_eq!(err.internal_position(), Some(11));
+    assert_eq!(
+        err.position(),
+        Some(PgErrorPosition::Internal {
+            position: 11,
+            query: "SELECT 1 +"
+        })
+    );
+    assert!(err.r#where().is_some_and(|w| w.contains("PL/pgSQL")));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_inspect_not_null_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE not_null_errors (id INT4 NOT NULL)")
         .await?;
 
+    let err = sqlx::query("INSERT INTO not_null_errors (id) VALUES (NULL)")
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+    let err = err
+        .into_database_error()
+        .unwrap()
+        .downcast::<PgDatabaseError>();
+
+    assert_eq!(err.severity(), PgSeverity::Error);
+    assert_eq!(err.code(), "23502");
+    assert_eq!(err.position(), None);
+    assert!(err.schema().is_some_and(|s| s.starts_with("pg_temp")));
+    assert_eq!(err.table(), Some("not_null_errors"));
+    assert_eq!(err.column(), Some("id"));
+    assert_eq!(err.data_type(), None);
+    assert_eq!(err.constraint(), None);
+    assert!(err.file().is_some());
+    assert!(err.line().is_some());
+    assert!(err.routine().is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes() -> anyhow::Result<()> {
+    let mut conn = with_fixtures::<Postgres>(&["temp_users"]).await?;
+
     for index in 1..=10_i32 {
         let done = sqlx::query("INSERT INTO users (id) VALUES ($1)")
             .bind(index)
-            .execute(&mut conn)
+            .execute(&mut *conn)
             .await?;
 
         assert_eq!(done.rows_affected(), 1);
@@ -181,7 +380,7 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY);
 
     let sum: i32 = sqlx::query("SELECT id FROM users")
         .try_map(|row: PgRow| row.try_get::<i32, _>(0))
-        .fetch(&mut conn)
+        .fetch(&mut *conn)
         .try_fold(0_i32, |acc, x| async move { Ok(acc + x) })
         .await?;
 
@@ -252,6 +451,81 @@ async fn it_works_with_cache_disabled() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_checks_the_number_of_bound_arguments() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for persistent in [true, false] {
+        let err = sqlx::query_scalar::<_, i32>("SELECT $1::int4 + $2::int4")
+            .bind(1_i32)
+            .persistent(persistent)
+            .fetch_one(&mut conn)
+            .await
+            .expect_err("binding too few arguments should fail");
+
+        assert!(
+            matches!(
+                err,
+                sqlx::Error::ParameterCountMismatch {
+                    expected: 2,
+                    got: 1
+                }
+            ),
+            "{err:?}"
+        );
+
+        // the server counts a parameter for every argument, used or not
+        let v = sqlx::query_scalar::<_, i32>("SELECT $1::int4")
+            .bind(1_i32)
+            .bind(2_i32)
+            .persistent(persistent)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(v, 1);
+    }
+
+    // the connection is still usable
+    let v: i32 = sqlx::query_scalar("SELECT $1::int4 + 0")
+        .bind(10_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(v, 10);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_functions_with_sql_standard_bodies() -> anyhow::Result<()> {
+    // Only supported in Postgres 14+
+    let mut conn = new::<Postgres>().await?;
+    if matches!(conn.server_version_num(), Some(version) if version < 140000) {
+        return Ok(());
+    }
+
+    // `$1` is the argument of the function, not a bind parameter
+    sqlx::query("CREATE FUNCTION pg_temp.add_one(a int4) RETURNS int4 LANGUAGE sql RETURN $1 + 1")
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query(
+        "CREATE FUNCTION pg_temp.add_two(a int4) RETURNS int4 LANGUAGE sql \
+         BEGIN ATOMIC SELECT $1 + 2; END",
+    )
+    .execute(&mut conn)
+    .await?;
+
+    let v: i32 = sqlx::query_scalar("SELECT pg_temp.add_one(pg_temp.add_two($1))")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(v, 4);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_executes_with_pool() -> anyhow::Result<()> {
     let pool = sqlx_test::pool::<Postgres>().await?;
@@ -512,6 +786,65 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_commit_and_roll_back_prepared_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_two_phase (id INTEGER PRIMARY KEY)")
+        .await?;
+    conn.execute("TRUNCATE _sqlx_two_phase").await?;
+
+    for (id, gid) in [
+        (1_i32, "_sqlx_two_phase_commit"),
+        (2, "_sqlx_two_phase_'rollback\\"),
+    ] {
+        let mut tx = conn.begin().await?;
+
+        sqlx::query("INSERT INTO _sqlx_two_phase (id) VALUES ($1)")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        let prepared = tx.prepare_two_phase(gid).await?;
+        assert_eq!(prepared.gid(), gid);
+    }
+
+    // the prepared transactions survive the connection closing
+    conn.close().await?;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_two_phase")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    let gids = conn.list_prepared().await?;
+    assert!(gids.iter().any(|gid| gid == "_sqlx_two_phase_commit"));
+    assert!(gids.iter().any(|gid| gid == "_sqlx_two_phase_'rollback\\"));
+
+    conn.commit_prepared("_sqlx_two_phase_commit").await?;
+    conn.rollback_prepared("_sqlx_two_phase_'rollback\\")
+        .await?;
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM _sqlx_two_phase")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(ids, [1]);
+    assert!(conn.list_prepared().await?.is_empty());
+
+    // a savepoint can't be prepared
+    let mut tx = conn.begin().await?;
+    let savepoint = tx.begin().await?;
+    assert!(savepoint
+        .prepare_two_phase("_sqlx_two_phase_savepoint")
+        .await
+        .is_err());
+    tx.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -595,86 +928,403 @@ async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
-// run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
-#[ignore]
 #[sqlx_macros::test]
-async fn pool_smoke_test() -> anyhow::Result<()> {
-    use futures::{future, task::Poll, Future};
+async fn it_can_stream_queries_with_owned_sql() -> anyhow::Result<()> {
+    use futures::stream::BoxStream;
 
-    eprintln!("starting pool");
-
-    let pool = PgPoolOptions::new()
-        .acquire_timeout(Duration::from_secs(5))
-        .min_connections(1)
-        .max_connections(1)
-        .connect(&dotenvy::var("DATABASE_URL")?)
-        .await?;
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    struct User {
+        id: i32,
+        name: String,
+    }
 
-    // spin up more tasks than connections available, and ensure we don't deadlock
-    for i in 0..200 {
-        let pool = pool.clone();
-        sqlx_core::rt::spawn(async move {
-            for j in 0.. {
-                if let Err(e) = sqlx::query("select 1 + 1").execute(&pool).await {
-                    // normal error at termination of the test
-                    if matches!(e, sqlx::Error::PoolClosed) {
-                        eprintln!("pool task {i} exiting normally after {j} iterations");
-                    } else {
-                        eprintln!("pool task {i} dying due to {e} after {j} iterations");
-                    }
-                    break;
-                }
+    // the SQL is built here, but the stream outlives it and the borrow of the pool
+    fn stream_users(
+        pool: &PgPool,
+        min_id: i32,
+        order_by: &str,
+    ) -> impl Stream<Item = sqlx::Result<User>> + Send + 'static {
+        let sql = format!(
+            "SELECT id, name FROM (VALUES (1, 'alice'), (2, 'bob'), (3, 'carol')) AS users (id, name) \
+             WHERE id >= $1 ORDER BY {order_by}"
+        );
 
-                // shouldn't be necessary if the pool is fair
-                // sqlx_core::rt::yield_now().await;
-            }
-        });
+        sqlx::query_as_owned(sql).bind(min_id).fetch(pool)
     }
 
-    // spawn a bunch of tasks that attempt to acquire but give up to ensure correct handling
-    // of cancellations
-    for _ in 0..50 {
-        let pool = pool.clone();
-        sqlx_core::rt::spawn(async move {
-            while !pool.is_closed() {
-                let mut acquire = pin!(pool.acquire());
-
-                // poll the acquire future once to put the waiter in the queue
-                future::poll_fn(move |cx| {
-                    let _ = acquire.as_mut().poll(cx);
-                    Poll::Ready(())
-                })
-                .await;
+    fn stream_ids(pool: &PgPool) -> BoxStream<'static, sqlx::Result<i32>> {
+        sqlx::query_scalar_owned(String::from("SELECT generate_series(1, 3)")).fetch(pool)
+    }
 
-                // this one is necessary since this is a hot loop,
-                // otherwise this task will never be descheduled
-                sqlx_core::rt::yield_now().await;
-            }
-        });
+    fn stream_rows(pool: &PgPool) -> BoxStream<'static, sqlx::Result<PgRow>> {
+        sqlx::query_owned(String::from("SELECT 1")).fetch(pool)
     }
 
-    eprintln!("sleeping for 30 seconds");
+    let pool = pool::<Postgres>().await?;
 
-    sqlx_core::rt::sleep(Duration::from_secs(30)).await;
+    let users = sqlx_core::rt::spawn(stream_users(&pool, 2, "id").try_collect::<Vec<_>>());
 
-    // assert_eq!(pool.size(), 10);
+    assert_eq!(
+        users.await?,
+        [
+            User {
+                id: 2,
+                name: "bob".into()
+            },
+            User {
+                id: 3,
+                name: "carol".into()
+            },
+        ]
+    );
 
-    eprintln!("closing pool");
+    let ids: Vec<i32> = stream_ids(&pool).try_collect().await?;
+    assert_eq!(ids, [1, 2, 3]);
 
-    sqlx_core::rt::timeout(Duration::from_secs(30), pool.close()).await?;
+    let rows: Vec<PgRow> = stream_rows(&pool).try_collect().await?;
+    assert_eq!(rows.len(), 1);
 
-    eprintln!("pool closed successfully");
+    // owned SQL with borrowed arguments, executed on a connection
+    let mut conn = new::<Postgres>().await?;
+    let name = String::from("dave");
+
+    let greeting: String = sqlx::query_scalar_owned(String::from("SELECT 'hello, ' || $1"))
+        .bind(&name)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(greeting, "hello, dave");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_invalid_query() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_can_own_pooled_transactions() -> anyhow::Result<()> {
+    struct Job {
+        tx: sqlx::Transaction<'static, Postgres>,
+    }
 
-    conn.execute("definitely not a correct query")
-        .await
-        .unwrap_err();
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    // the pool's only connection is used throughout, so it sees its temporary table
+    pool.execute("CREATE TEMPORARY TABLE owned_jobs (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    let mut job = Job {
+        tx: pool.begin().await?,
+    };
+
+    sqlx::query("INSERT INTO owned_jobs (id) VALUES (1)")
+        .execute(&mut *job.tx)
+        .await?;
+
+    // the only connection is owned by the transaction
+    assert!(pool.try_begin().await?.is_none());
+
+    sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+
+    // a savepoint within the owned transaction
+    {
+        let mut savepoint = job.tx.begin().await?;
+
+        sqlx::query("INSERT INTO owned_jobs (id) VALUES (2)")
+            .execute(&mut *savepoint)
+            .await?;
+    }
+
+    job.tx.commit().await?;
+
+    // the connection is returned to the pool by the commit
+    let mut tx = pool
+        .try_begin()
+        .await?
+        .expect("expected the connection to be returned to the pool");
+
+    let tx_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(tx_pid, pid);
+
+    sqlx::query("INSERT INTO owned_jobs (id) VALUES (3)")
+        .execute(&mut *tx)
+        .await?;
+
+    // dropping the transaction rolls it back and returns the connection to the pool
+    drop(tx);
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM owned_jobs ORDER BY id")
+        .fetch_all(&pool)
+        .await?;
+    assert_eq!(ids, [1]);
+
+    let reused_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(reused_pid, pid);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_with_isolation_level() -> anyhow::Result<()> {
+    // Two doctors who are both on call each check that the other is still on call before going
+    // off call themselves: a classic write skew, which only `SERIALIZABLE` prevents.
+    async fn go_off_call(level: IsolationLevel) -> anyhow::Result<Result<(), sqlx::Error>> {
+        let mut conn1 = new::<Postgres>().await?;
+        let mut conn2 = new::<Postgres>().await?;
+
+        conn1
+            .execute(
+                "CREATE TABLE IF NOT EXISTS _sqlx_on_call_1876 (id INTEGER PRIMARY KEY, on_call BOOLEAN NOT NULL)",
+            )
+            .await?;
+
+        conn1
+            .execute(
+                "TRUNCATE _sqlx_on_call_1876; INSERT INTO _sqlx_on_call_1876 VALUES (1, TRUE), (2, TRUE)",
+            )
+            .await?;
+
+        let options = PgTransactionOptions::new().isolation_level(level);
+        let mut tx1 = conn1.begin_with(options.clone()).await?;
+        let mut tx2 = conn2.begin_with(options).await?;
+
+        for tx in [&mut tx1, &mut tx2] {
+            let (on_call,): (i64,) =
+                sqlx::query_as("SELECT COUNT(*) FROM _sqlx_on_call_1876 WHERE on_call")
+                    .fetch_one(&mut **tx)
+                    .await?;
+
+            assert_eq!(on_call, 2);
+        }
+
+        sqlx::query("UPDATE _sqlx_on_call_1876 SET on_call = FALSE WHERE id = 1")
+            .execute(&mut *tx1)
+            .await?;
+
+        sqlx::query("UPDATE _sqlx_on_call_1876 SET on_call = FALSE WHERE id = 2")
+            .execute(&mut *tx2)
+            .await?;
+
+        tx1.commit().await?;
+
+        Ok(tx2.commit().await)
+    }
+
+    go_off_call(IsolationLevel::ReadCommitted).await??;
+
+    let err = go_off_call(IsolationLevel::Serializable)
+        .await?
+        .expect_err("expected a serialization failure");
+
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("40001")
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_read_only() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut tx = conn
+        .begin_with(
+            PgTransactionOptions::new()
+                .isolation_level(IsolationLevel::Serializable)
+                .read_only(true)
+                .deferrable(true),
+        )
+        .await?;
+
+    let (isolation, read_only): (String, String) = sqlx::query_as(
+        "SELECT current_setting('transaction_isolation'), current_setting('transaction_read_only')",
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    assert_eq!(isolation, "serializable");
+    assert_eq!(read_only, "on");
+
+    // options are ignored for savepoints
+    let mut savepoint = tx
+        .begin_with(PgTransactionOptions::new().read_only(false))
+        .await?;
+
+    let err = savepoint
+        .execute("CREATE TEMPORARY TABLE _sqlx_read_only_1876 (id INTEGER)")
+        .await
+        .expect_err("expected a read-only transaction");
+
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("25006")
+    );
+
+    savepoint.rollback().await?;
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_retry_cloned_queries_on_serialization_failure() -> anyhow::Result<()> {
+    /// Runs `f` in a `SERIALIZABLE` transaction, retrying it as long as it fails with
+    /// a serialization failure (SQLSTATE 40001).
+    async fn serializable<T, F>(conn: &mut PgConnection, mut f: F) -> Result<T, sqlx::Error>
+    where
+        F: for<'c> FnMut(&'c mut PgConnection) -> BoxFuture<'c, Result<T, sqlx::Error>>,
+    {
+        loop {
+            let options = PgTransactionOptions::new().isolation_level(IsolationLevel::Serializable);
+            let mut tx = conn.begin_with(options).await?;
+
+            let res = match f(&mut tx).await {
+                Ok(value) => tx.commit().await.map(|_| value),
+                Err(e) => Err(e),
+            };
+
+            match res {
+                Err(e)
+                    if e.as_database_error().and_then(|e| e.code()).as_deref() == Some("40001") =>
+                {
+                    continue
+                }
+                res => return res,
+            }
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE _sqlx_retry_1877 (id INTEGER PRIMARY KEY, name TEXT)")
+        .await?;
+
+    // bind once, then clone the query for every attempt
+    let insert = sqlx::query("INSERT INTO _sqlx_retry_1877 (id, name) VALUES ($1, $2)")
+        .bind(1_i32)
+        .bind("Alice");
+
+    let mut attempts = 0;
+
+    serializable(&mut conn, |conn| {
+        attempts += 1;
+        let attempt = attempts;
+        let insert = insert.clone();
+
+        Box::pin(async move {
+            insert.execute(&mut *conn).await?;
+
+            if attempt == 1 {
+                conn.execute(
+                    "DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = 'serialization_failure'; END $$",
+                )
+                .await?;
+            }
+
+            Ok(())
+        })
+    })
+    .await?;
+
+    assert_eq!(attempts, 2);
+
+    let name: String = sqlx::query_scalar("SELECT name FROM _sqlx_retry_1877 WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(name, "Alice");
+
+    Ok(())
+}
+
+// run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
+#[ignore]
+#[sqlx_macros::test]
+async fn pool_smoke_test() -> anyhow::Result<()> {
+    use futures::{future, task::Poll, Future};
+
+    eprintln!("starting pool");
+
+    let pool = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(5))
+        .min_connections(1)
+        .max_connections(1)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    // spin up more tasks than connections available, and ensure we don't deadlock
+    for i in 0..200 {
+        let pool = pool.clone();
+        sqlx_core::rt::spawn(async move {
+            for j in 0.. {
+                if let Err(e) = sqlx::query("select 1 + 1").execute(&pool).await {
+                    // normal error at termination of the test
+                    if matches!(e, sqlx::Error::PoolClosed) {
+                        eprintln!("pool task {i} exiting normally after {j} iterations");
+                    } else {
+                        eprintln!("pool task {i} dying due to {e} after {j} iterations");
+                    }
+                    break;
+                }
+
+                // shouldn't be necessary if the pool is fair
+                // sqlx_core::rt::yield_now().await;
+            }
+        });
+    }
+
+    // spawn a bunch of tasks that attempt to acquire but give up to ensure correct handling
+    // of cancellations
+    for _ in 0..50 {
+        let pool = pool.clone();
+        sqlx_core::rt::spawn(async move {
+            while !pool.is_closed() {
+                let mut acquire = pin!(pool.acquire());
+
+                // poll the acquire future once to put the waiter in the queue
+                future::poll_fn(move |cx| {
+                    let _ = acquire.as_mut().poll(cx);
+                    Poll::Ready(())
+                })
+                .await;
+
+                // this one is necessary since this is a hot loop,
+                // otherwise this task will never be descheduled
+                sqlx_core::rt::yield_now().await;
+            }
+        });
+    }
+
+    eprintln!("sleeping for 30 seconds");
+
+    sqlx_core::rt::sleep(Duration::from_secs(30)).await;
+
+    // assert_eq!(pool.size(), 10);
+
+    eprintln!("closing pool");
+
+    sqlx_core::rt::timeout(Duration::from_secs(30), pool.close()).await?;
+
+    eprintln!("pool closed successfully");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_invalid_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("definitely not a correct query")
+        .await
+        .unwrap_err();
 
     let mut s = conn.fetch("select 1");
     let row = s.try_next().await?.unwrap();
@@ -761,73 +1411,907 @@ async fn it_caches_statements() -> anyhow::Result<()> {
 
         let val: Oid = row.get("val");
 
-        assert_eq!(Oid(i), val);
-    }
+        assert_eq!(Oid(i), val);
+    }
+
+    assert_eq!(1, conn.cached_statements_size());
+    conn.clear_cached_statements().await?;
+    assert_eq!(0, conn.cached_statements_size());
+
+    for i in 0..2 {
+        let row = sqlx::query("SELECT $1 AS val")
+            .bind(Oid(i))
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: Oid = row.get("val");
+
+        assert_eq!(Oid(i), val);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_does_not_cache_non_persistent_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for i in 0..100 {
+        let val: i64 = sqlx::query_scalar(&format!("SELECT {i}::int8 + $1"))
+            .bind(1_i64)
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(i + 1, val);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepares_named_statements_without_a_cache() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let options = options.statement_cache_capacity(0);
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let statement = conn.prepare("SELECT $1::int4 AS val").await?;
+    assert_eq!(statement.columns().len(), 1);
+
+    let describe = conn.describe("SELECT $1::int4 AS val").await?;
+    assert_eq!(describe.columns().len(), 1);
+
+    // the type of the column is looked up on a new connection
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let status: String = sqlx::query_scalar("SELECT $1::status::text")
+        .bind("open")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(status, "open");
+
+    let rows = sqlx::query("SELECT $1::status AS status, $2::int4 AS id")
+        .bind("closed")
+        .bind(1_i32)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].try_get::<i32, _>("id")?, 1);
+    assert_eq!(rows[0].column(0).type_info().name(), "status");
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_invokes_notice_callback_in_order() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let notices = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let options = options.notice_callback({
+        let notices = notices.clone();
+        move |notice| notices.lock().unwrap().push(notice.message().to_owned())
+    });
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let rows = sqlx::raw_sql(
+        r#"
+DO $$
+BEGIN
+    RAISE NOTICE 'one';
+    RAISE NOTICE 'two';
+    RAISE WARNING 'three';
+END $$;
+
+SELECT generate_series(1, 3);
+"#,
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2].get::<i32, _>(0), 3);
+    assert_eq!(*notices.lock().unwrap(), ["one", "two", "three"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_mismatched_column_types() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query("SELECT 'foo'::text AS value")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = row.try_get::<i32, _>("value").unwrap_err();
+
+    let sqlx::Error::ColumnDecode { index, source } = err else {
+        panic!("expected `Error::ColumnDecode`, got {err:?}");
+    };
+
+    let message = source.to_string();
+
+    assert_eq!(index, r#""value""#);
+    assert!(message.contains("INT4"), "{message}");
+    assert!(message.contains("TEXT"), "{message}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_column_names_case_insensitively() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query(r#"SELECT 1::int4 AS "Foo", 2::int4 AS "FOO", 3::int4 AS "Bar""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get::<i32, _>("Foo")?, 1);
+    assert_eq!(row.try_get::<i32, _>("FOO")?, 2);
+    assert_eq!(row.try_get::<i32, _>("bar")?, 3);
+
+    match row.try_get::<i32, _>("foo") {
+        Err(sqlx::Error::ColumnAmbiguous { candidates, .. }) => {
+            assert_eq!(candidates, ["Foo", "FOO"]);
+        }
+        other => panic!("expected `Error::ColumnAmbiguous`, got {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_widens_narrower_integer_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // both the extended (binary) and simple (text) protocols
+    let row = sqlx::query("SELECT 12345::int2, (-7)::int4")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get::<i64, _>(0)?, 12345);
+    assert_eq!(row.try_get::<i32, _>(0)?, 12345);
+    assert_eq!(row.try_get::<i64, _>(1)?, -7);
+
+    let row = sqlx::raw_sql("SELECT 12345::int2, (-7)::int4")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get::<i64, _>(0)?, 12345);
+    assert_eq!(row.try_get::<i64, _>(1)?, -7);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_domain_and_citext_columns_as_base_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    tx.execute(
+        r#"
+CREATE DOMAIN email AS text CHECK (value LIKE '%@%');
+CREATE DOMAIN positive_int8 AS int8 CHECK (value > 0);
+CREATE TABLE accounts_with_domains (address email NOT NULL, handle citext NOT NULL, balance positive_int8 NOT NULL);
+INSERT INTO accounts_with_domains VALUES ('ferris@example.com', 'Ferris', 42);
+"#,
+    )
+    .await?;
+
+    let row = sqlx::query("SELECT address, handle, balance FROM accounts_with_domains")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    // the server describes domain columns by their base type
+    assert_eq!(row.columns()[0].type_info().name(), "TEXT");
+
+    // but parameters are described by the domain itself
+    let describe = (&mut *tx).describe("SELECT $1::email").await?;
+    let param = &describe.parameters().unwrap().unwrap_left()[0];
+
+    assert_eq!(param.name(), "email");
+    assert!(matches!(
+        param.kind(),
+        PgTypeKind::Domain(base) if *base == PgTypeInfo::with_name("text")
+    ));
+    // the domain is only compatible with its base type, not equal to it
+    assert_ne!(*param, PgTypeInfo::with_name("text"));
+    assert!(PgTypeInfo::with_name("text").type_compatible(param));
+    assert!(<String as sqlx::Type<Postgres>>::compatible(param));
+
+    assert_eq!(row.try_get::<String, _>("address")?, "ferris@example.com");
+    assert_eq!(row.try_get::<String, _>("handle")?, "Ferris");
+    assert_eq!(row.try_get::<i64, _>("balance")?, 42);
+    assert!(row.try_get::<i32, _>("balance").is_err());
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_returning_rows_with_rows_affected() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE returning_test (id SERIAL PRIMARY KEY, flag BOOLEAN)")
+        .await?;
+
+    let (ids, result) = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO returning_test (flag) VALUES (false), (false), (true) RETURNING id",
+    )
+    .fetch_all_with_result(&mut conn)
+    .await?;
+
+    assert_eq!(ids, [1, 2, 3]);
+    assert_eq!(result.rows_affected(), 3);
+
+    let (rows, result) =
+        sqlx::query("UPDATE returning_test SET flag = true WHERE flag = false RETURNING id, flag")
+            .fetch_all_with_result(&mut conn)
+            .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| row.get::<bool, _>("flag")));
+    assert_eq!(result.rows_affected(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_combines_results_of_multiple_statements() -> anyhow::Result<()> {
+    let mut conn = with_fixtures::<Postgres>(&["temp_users"]).await?;
+
+    // `execute()` sums the rows affected by each statement
+    let res = sqlx::raw_sql("INSERT INTO users VALUES (1), (2); INSERT INTO users VALUES (3);")
+        .execute(&mut *conn)
+        .await?;
+
+    assert_eq!(res.rows_affected(), 3);
+
+    let sql = "SELECT id FROM users WHERE id < 3 ORDER BY id; \
+               SELECT id FROM users WHERE id = 3;";
+
+    // `fetch_all()` returns the rows of every result set, in order
+    let ids: Vec<i32> = sqlx::raw_sql(sql)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    assert_eq!(ids, [1, 2, 3]);
+
+    // `fetch_one()` returns the first row of the first result set
+    let row = sqlx::raw_sql(sql).fetch_one(&mut *conn).await?;
+
+    assert_eq!(row.get::<i32, _>(0), 1);
+
+    // and the connection is left ready for the next query
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_optional_row_from_multiple_statements() -> anyhow::Result<()> {
+    sqlx_test::fetch_optional_from_multiple_statements::<Postgres>().await
+}
+
+#[sqlx_macros::test]
+async fn it_can_drop_fetch_stream_mid_result() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let query = "SELECT i FROM generate_series(1, 100000) i";
+
+    // both the extended and simple query protocols
+    {
+        let mut rows = sqlx::query(query).fetch(&mut conn);
+        let row = rows.try_next().await?.expect("at least one row");
+        assert_eq!(row.get::<i32, _>(0), 1);
+    }
+
+    let value: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(value, 42);
+
+    {
+        let mut rows = sqlx::raw_sql(query).fetch(&mut conn);
+        rows.try_next().await?.expect("at least one row");
+    }
+
+    let value: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(value, 42);
+
+    // a dropped stream inside a transaction doesn't break the transaction either
+    let mut tx = conn.begin().await?;
+
+    {
+        let mut rows = sqlx::query(query).fetch(&mut *tx);
+        rows.try_next().await?.expect("at least one row");
+    }
+
+    let value: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut *tx).await?;
+    assert_eq!(value, 42);
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resets_session_state_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .reset_on_release(true)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    conn.execute(
+        "SET sqlx.reset_test = 'dirty'; SET TIME ZONE 'Europe/Berlin'; \
+         CREATE TEMPORARY TABLE sqlx_reset_test (id INT)",
+    )
+    .await?;
+
+    // left open on purpose; the pool has to roll it back before `DISCARD ALL`
+    let tx = conn.begin().await?;
+    std::mem::forget(tx);
+
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+
+    let (same_pid, setting, time_zone): (i32, Option<String>, String) = sqlx::query_as(
+        "SELECT pg_backend_pid(), current_setting('sqlx.reset_test', true), current_setting('TimeZone')",
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    assert_eq!(same_pid, pid);
+
+    // a placeholder variable that was set once is reset to an empty string, not removed
+    assert_eq!(setting.as_deref().unwrap_or(""), "");
+    assert_ne!(time_zone, "Europe/Berlin");
+
+    assert!(conn.execute("SELECT * FROM sqlx_reset_test").await.is_err());
+
+    // the prepared statement was deallocated by the server, so this has to prepare it again
+    let _: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+async fn backend_pid(pool: &PgPool) -> sqlx::Result<i32> {
+    sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(pool)
+        .await
+}
+
+#[sqlx_macros::test]
+async fn it_recycles_connections_past_max_lifetime() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .max_lifetime(Duration::from_millis(500))
+        .idle_timeout(None)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let pid = backend_pid(&pool).await?;
+    assert_eq!(backend_pid(&pool).await?, pid);
+
+    sqlx_core::rt::sleep(Duration::from_millis(700)).await;
+
+    assert_ne!(backend_pid(&pool).await?, pid);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_recycles_connections_past_idle_timeout() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .max_lifetime(None)
+        .idle_timeout(Duration::from_millis(300))
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let pid = backend_pid(&pool).await?;
+
+    // a connection that keeps being used is never idle for long
+    for _ in 0..4 {
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+        assert_eq!(backend_pid(&pool).await?, pid);
+    }
+
+    sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+
+    assert_ne!(backend_pid(&pool).await?, pid);
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_does_not_try_acquire_expired_connections() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .max_lifetime(Duration::from_millis(500))
+        .idle_timeout(None)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let pid = backend_pid(&pool).await?;
+
+    // let the connection be returned to the pool
+    sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+
+    let mut conn = pool.try_acquire().expect("expected an idle connection");
+    let same_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(same_pid, pid);
+    conn.return_to_pool().await;
+
+    sqlx_core::rt::sleep(Duration::from_millis(700)).await;
+
+    // `try_acquire()` can't open a new connection, so there is none to hand out
+    assert!(pool.try_acquire().is_none());
+    assert!(pool.try_begin().await?.is_none());
+
+    assert_ne!(backend_pid(&pool).await?, pid);
+
+    Ok(())
+}
+
+#[cfg(feature = "debug-protocol")]
+#[sqlx_macros::test]
+async fn it_passes_messages_to_the_packet_inspector() -> anyhow::Result<()> {
+    use sqlx::postgres::inspect::{frame_name, Direction};
+    use std::sync::Mutex;
+
+    setup_if_needed();
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let options = options.packet_inspector({
+        let messages = messages.clone();
+        move |direction, frame| {
+            let name = frame_name(direction, frame).unwrap_or("Unknown");
+            messages.lock().unwrap().push((direction, name));
+        }
+    });
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    // the startup message and the response to it
+    assert_eq!(
+        messages.lock().unwrap().first(),
+        Some(&(Direction::Sent, "StartupMessage"))
+    );
+    assert_eq!(
+        messages.lock().unwrap().last(),
+        Some(&(Direction::Received, "ReadyForQuery"))
+    );
+
+    messages.lock().unwrap().clear();
+
+    let values: Vec<i32> = sqlx::query_scalar("SELECT $1::int4")
+        .bind(42_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(values, [42]);
+    assert_eq!(
+        *messages.lock().unwrap(),
+        [
+            // the statement is prepared first
+            (Direction::Sent, "Parse"),
+            (Direction::Sent, "Describe"),
+            (Direction::Sent, "Sync"),
+            (Direction::Received, "ParseComplete"),
+            (Direction::Received, "ParameterDescription"),
+            (Direction::Received, "RowDescription"),
+            (Direction::Received, "ReadyForQuery"),
+            (Direction::Sent, "Bind"),
+            (Direction::Sent, "Execute"),
+            // the unnamed portal is closed
+            (Direction::Sent, "Close"),
+            (Direction::Sent, "Sync"),
+            (Direction::Received, "BindComplete"),
+            (Direction::Received, "DataRow"),
+            (Direction::Received, "CommandComplete"),
+            (Direction::Received, "CloseComplete"),
+            (Direction::Received, "ReadyForQuery"),
+        ]
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sets_startup_options() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let options = options.options([
+        ("search_path", "myschema, public"),
+        ("statement_timeout", "5min"),
+    ]);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let search_path: String = sqlx::query_scalar("SHOW search_path")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let statement_timeout: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(search_path, "myschema, public");
+    assert_eq!(statement_timeout, "5min");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_reserved_startup_options() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let options = options.options([("client_encoding", "LATIN1")]);
+
+    let res = PgConnection::connect_with(&options).await;
+
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_with_unnamed_statements_only() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.unnamed_statements(true)).await?;
+
+    for i in 0..100_i32 {
+        let sql = format!("SELECT $1::int4 + {i}, $2::text");
+
+        let (sum, text): (i32, String) = sqlx::query_as(&sql)
+            .bind(i)
+            .bind("hello")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(sum, 2 * i);
+        assert_eq!(text, "hello");
+    }
+
+    // statements with no result set still work
+    let res = sqlx::query("SELECT pg_sleep($1)")
+        .bind(0.0_f64)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(res.rows_affected(), 1);
+
+    // types that weren't seen before are looked up after all the rows were received
+    let rows = sqlx::query(
+        "SELECT $1::int4 + n AS id, 'open'::status AS status, ARRAY['new'::status] AS statuses \
+         FROM generate_series(1, 3) n",
+    )
+    .bind(10_i32)
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2].try_get::<i32, _>("id")?, 13);
+    assert_eq!(rows[0].columns()[1].type_info().name(), "status");
+    assert_eq!(rows[0].columns()[2].type_info().name(), "status[]");
+
+    // encoding JSON needs the parameter type inferred by the server, which is described first
+    let value: String = sqlx::query_scalar("SELECT $1::json ->> 'key'")
+        .bind(sqlx::types::Json(serde_json::json!({ "key": "value" })))
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, "value");
+
+    // nothing must have been left behind on the server for a connection pooler to lose
+    let prepared: i64 = sqlx::query_scalar("SELECT count(*) FROM pg_prepared_statements")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(prepared, 0);
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    // a capacity of 1 means that before each statement (after the first)
+    // we will close the previous statement
+    options = options.statement_cache_capacity(1);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    for i in 0..5 {
+        let row = sqlx::query(&*format!("SELECT {i}::int4 AS val"))
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: i32 = row.get("val");
+
+        assert_eq!(i, val);
+    }
+
+    assert_eq!(1, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sets_application_name() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.application_name("some-name");
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let row = sqlx::query("select current_setting('application_name') as app_name")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let val: String = row.get("app_name");
+
+    assert_eq!("some-name", &val);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_replaces_pooled_connections_after_server_restart() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    // a restart of the server is simulated by terminating every backend of the pool,
+    // which fails the connections with `57P01` (`admin_shutdown`)
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.application_name("sqlx-restart-test");
+
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        // see the error instead of discarding the dead connection on acquire
+        .test_before_acquire(false)
+        .connect_with(options)
+        .await?;
+
+    let first_wave = (0..5).map(|_| sqlx::query("SELECT pg_sleep(0.1)").execute(&pool));
+    for res in futures::future::join_all(first_wave).await {
+        res?;
+    }
+
+    assert_eq!(pool.size(), 5);
+    assert_eq!(pool.generation(), 0);
+
+    // let the connections be returned to the pool
+    sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let terminated: i64 = sqlx::query_scalar(
+        "SELECT count(pg_terminate_backend(pid)) FROM pg_stat_activity \
+         WHERE application_name = 'sqlx-restart-test'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(terminated, 5);
+
+    // the first query gets a dead connection, which starts a new generation
+    let err = sqlx::query("SELECT 1")
+        .execute(&pool)
+        .await
+        .expect_err("expected the connection to be terminated");
+
+    assert!(
+        matches!(&err, sqlx::Error::Io(_))
+            || err.as_database_error().and_then(|e| e.code()).as_deref() == Some("57P01"),
+        "expected a connection-fatal error, got: {err:?}"
+    );
+    assert_eq!(pool.generation(), 1);
+
+    // the other dead connections were closed, so every query gets a fresh one
+    let second_wave = (0..5).map(|_| sqlx::query("SELECT pg_sleep(0.1)").execute(&pool));
+    for res in futures::future::join_all(second_wave).await {
+        res?;
+    }
+
+    assert_eq!(pool.generation(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_starts_one_generation_per_server_restart() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.application_name("sqlx-generation-test");
+
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect_with(options)
+        .await?;
+
+    let mut first = pool.acquire().await?;
+    let mut second = pool.acquire().await?;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let terminated: i64 = sqlx::query_scalar(
+        "SELECT count(pg_terminate_backend(pid)) FROM pg_stat_activity \
+         WHERE application_name = 'sqlx-generation-test'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
 
-    assert_eq!(1, conn.cached_statements_size());
-    conn.clear_cached_statements().await?;
-    assert_eq!(0, conn.cached_statements_size());
+    assert_eq!(terminated, 2);
 
-    for i in 0..2 {
-        let row = sqlx::query("SELECT $1 AS val")
-            .bind(Oid(i))
-            .persistent(false)
-            .fetch_one(&mut conn)
-            .await?;
+    // both connections fail outside of the pool's `Executor` impl
+    first.execute("SELECT 1").await.unwrap_err();
+    second.execute("SELECT 1").await.unwrap_err();
 
-        let val: Oid = row.get("val");
+    // the errors are seen when the connections are released,
+    // but only the first one starts a new generation
+    first.return_to_pool().await;
+    assert_eq!(pool.generation(), 1);
 
-        assert_eq!(Oid(i), val);
-    }
+    second.return_to_pool().await;
+    assert_eq!(pool.generation(), 1);
 
-    assert_eq!(0, conn.cached_statements_size());
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&pool).await?;
+    assert_eq!(one, 1);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
+async fn it_sees_fatal_errors_of_checked_out_connections_on_release() -> anyhow::Result<()> {
     sqlx_test::setup_if_needed();
 
-    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
 
-    // a capacity of 1 means that before each statement (after the first)
-    // we will close the previous statement
-    options = options.statement_cache_capacity(1);
+    // a connection exception that leaves the connection usable,
+    // so pinging it when it's released succeeds
+    let mut tx = pool.begin().await?;
+    let err = tx
+        .execute("DO $$ BEGIN RAISE EXCEPTION 'gone' USING ERRCODE = '08006'; END $$")
+        .await
+        .unwrap_err();
 
-    let mut conn = PgConnection::connect_with(&options).await?;
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("08006")
+    );
+    assert_eq!(pool.generation(), 0);
 
-    for i in 0..5 {
-        let row = sqlx::query(&*format!("SELECT {i}::int4 AS val"))
-            .fetch_one(&mut conn)
-            .await?;
+    tx.rollback().await?;
+    sqlx_core::rt::sleep(Duration::from_millis(100)).await;
 
-        let val: i32 = row.get("val");
+    assert_eq!(pool.generation(), 1);
 
-        assert_eq!(i, val);
-    }
+    // the connection was closed instead of being returned to the pool
+    assert_eq!(pool.num_idle(), 0);
 
-    assert_eq!(1, conn.cached_statements_size());
+    let mut conn = pool.acquire().await?;
+    conn.execute("SELECT 1").await?;
+    conn.return_to_pool().await;
+
+    assert_eq!(pool.generation(), 1);
+    assert_eq!(pool.num_idle(), 1);
 
     Ok(())
 }
 
+#[cfg(feature = "_rt-tokio")]
 #[sqlx_macros::test]
-async fn it_sets_application_name() -> anyhow::Result<()> {
+async fn it_connects_through_a_socket_connector() -> anyhow::Result<()> {
+    use sqlx::net::StreamSocket;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     sqlx_test::setup_if_needed();
 
-    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
-    options = options.application_name("some-name");
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let host = options.get_host().to_owned();
+    let port = options.get_port();
+
+    let connects = Arc::new(AtomicUsize::new(0));
+
+    // a "tunnel" to the server: an in-process pipe whose other end is forwarded over TCP
+    let options = options.socket_connector({
+        let connects = connects.clone();
+
+        move || {
+            let host = host.clone();
+            let connects = connects.clone();
+
+            async move {
+                connects.fetch_add(1, Ordering::SeqCst);
+
+                let (client, mut server) = tokio::io::duplex(8192);
+                let mut upstream = tokio::net::TcpStream::connect((&*host, port)).await?;
+
+                tokio::spawn(async move {
+                    let _ = tokio::io::copy_bidirectional(&mut server, &mut upstream).await;
+                });
+
+                Ok(StreamSocket::from_tokio(client))
+            }
+        }
+    });
 
     let mut conn = PgConnection::connect_with(&options).await?;
 
-    let row = sqlx::query("select current_setting('application_name') as app_name")
+    let value: i32 = sqlx::query_scalar("SELECT 1 + 1")
         .fetch_one(&mut conn)
         .await?;
+    assert_eq!(value, 2);
 
-    let val: String = row.get("app_name");
+    conn.close().await?;
+    assert_eq!(connects.load(Ordering::SeqCst), 1);
 
-    assert_eq!("some-name", &val);
+    // every connection opened by the pool goes through the connector
+    let pool = PgPoolOptions::new()
+        .max_connections(3)
+        .connect_with(options)
+        .await?;
+
+    let queries = (0..3).map(|_| sqlx::query("SELECT pg_sleep(0.1)").execute(&pool));
+    for res in futures::future::join_all(queries).await {
+        res?;
+    }
+
+    assert_eq!(pool.size(), 3);
+    assert_eq!(connects.load(Ordering::SeqCst), 4);
+
+    pool.close().await;
 
     Ok(())
 }
@@ -1125,6 +2609,88 @@ async fn test_listener_try_recv_buffered() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_listener_drain_and_recv_timeout() -> anyhow::Result<()> {
+    use sqlx::postgres::PgListener;
+    use std::time::Instant;
+
+    let mut notify_conn = new::<Postgres>().await?;
+
+    let mut listener = PgListener::connect(&env::var("DATABASE_URL")?).await?;
+    listener.listen("test_channel_drain").await?;
+
+    assert!(listener.drain()?.is_empty());
+
+    // Send five notifications while the listener is busy running a query.
+    let notify = sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+
+        for i in 0..5 {
+            notify_conn
+                .execute(format!("NOTIFY test_channel_drain, 'payload {i}'").as_str())
+                .await?;
+        }
+
+        anyhow::Ok(notify_conn)
+    });
+
+    listener.execute("SELECT pg_sleep(0.5)").await?;
+    let mut notify_conn = notify.await?;
+
+    let started = Instant::now();
+    let payloads: Vec<_> = listener
+        .drain()?
+        .iter()
+        .map(|n| n.payload().to_owned())
+        .collect();
+    assert!(started.elapsed() < Duration::from_millis(10));
+    assert_eq!(
+        payloads,
+        [
+            "payload 0",
+            "payload 1",
+            "payload 2",
+            "payload 3",
+            "payload 4"
+        ]
+    );
+    assert!(listener.drain()?.is_empty());
+
+    // Nothing is sent, so this waits until the deadline.
+    let started = Instant::now();
+    assert!(listener
+        .recv_timeout(Duration::from_millis(200))
+        .await?
+        .is_none());
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(200), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(400), "{elapsed:?}");
+
+    // Notifications sent in one transaction arrive together; those not returned
+    // by `recv_timeout()` are left in the buffer for `drain()`.
+    let mut txn = notify_conn.begin().await?;
+    for i in 0..3 {
+        txn.execute(format!("NOTIFY test_channel_drain, 'batch {i}'").as_str())
+            .await?;
+    }
+    txn.commit().await?;
+
+    let first = listener
+        .recv_timeout(Duration::from_secs(5))
+        .await?
+        .expect("expected a notification");
+    assert_eq!(first.payload(), "batch 0");
+
+    let rest: Vec<_> = listener
+        .drain()?
+        .iter()
+        .map(|n| n.payload().to_owned())
+        .collect();
+    assert_eq!(rest, ["batch 1", "batch 2"]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
     let pool = pool::<Postgres>().await?;
@@ -1181,6 +2747,79 @@ async fn test_pg_listener_implements_acquire() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Inserts a row in a transaction, which may be nested, and rolls it back.
+async fn count_rows_with_insert<'a, A>(conn: A) -> sqlx::Result<i64>
+where
+    A: sqlx::Acquire<'a, Database = Postgres>,
+{
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_test_acquire DEFAULT VALUES")
+        .execute(&mut *tx)
+        .await?;
+
+    let count = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_test_acquire")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.rollback().await?;
+
+    Ok(count)
+}
+
+async fn select_one<'a, A>(conn: A) -> sqlx::Result<i32>
+where
+    A: sqlx::Acquire<'a, Database = Postgres>,
+{
+    let mut conn = conn.acquire().await?;
+
+    sqlx::query_scalar("SELECT 1").fetch_one(&mut *conn).await
+}
+
+#[sqlx_macros::test]
+async fn it_is_generic_over_acquire() -> anyhow::Result<()> {
+    let pool = pool::<Postgres>().await?;
+
+    pool.execute(
+        "CREATE TABLE IF NOT EXISTS _sqlx_test_acquire (id SERIAL PRIMARY KEY); \
+         TRUNCATE _sqlx_test_acquire",
+    )
+    .await?;
+
+    // `&Pool`
+    assert_eq!(select_one(&pool).await?, 1);
+    assert_eq!(count_rows_with_insert(&pool).await?, 1);
+
+    // `&mut PoolConnection`
+    let mut pool_conn = pool.acquire().await?;
+    assert_eq!(select_one(&mut pool_conn).await?, 1);
+    assert_eq!(count_rows_with_insert(&mut pool_conn).await?, 1);
+
+    // `&mut PgConnection`
+    assert_eq!(select_one(&mut *pool_conn).await?, 1);
+    assert_eq!(count_rows_with_insert(&mut *pool_conn).await?, 1);
+
+    // `&mut Transaction`, which begins a nested transaction
+    let mut tx = pool_conn.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_test_acquire DEFAULT VALUES")
+        .execute(&mut *tx)
+        .await?;
+
+    assert_eq!(select_one(&mut tx).await?, 1);
+    assert_eq!(count_rows_with_insert(&mut tx).await?, 2);
+
+    // only the row inserted by the nested transaction was rolled back
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_test_acquire")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(count, 1);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_supports_domain_types_in_composite_domain_types() -> anyhow::Result<()> {
     // Only supported in Postgres 11+
@@ -1555,76 +3194,215 @@ VALUES
 async fn custom_type_resolution_respects_search_path() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    conn.execute(
-        r#"
-DROP TYPE IF EXISTS some_enum_type;
-DROP SCHEMA IF EXISTS another CASCADE;
+    conn.execute(
+        r#"
+DROP TYPE IF EXISTS some_enum_type;
+DROP SCHEMA IF EXISTS another CASCADE;
+
+CREATE SCHEMA another;
+CREATE TYPE some_enum_type AS ENUM ('a', 'b', 'c');
+CREATE TYPE another.some_enum_type AS ENUM ('d', 'e', 'f');
+    "#,
+    )
+    .await?;
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct SomeEnumType(String);
+
+    impl sqlx::Type<Postgres> for SomeEnumType {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("some_enum_type")
+        }
+
+        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+            *ty == Self::type_info()
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for SomeEnumType {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(<String as sqlx::Decode<Postgres>>::decode(value)?))
+        }
+    }
+
+    impl<'q> sqlx::Encode<'q, Postgres> for SomeEnumType {
+        fn encode_by_ref(
+            &self,
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, BoxDynError> {
+            <String as sqlx::Encode<Postgres>>::encode_by_ref(&self.0, buf)
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    sqlx::query("set search_path = 'another'")
+        .execute(&mut conn)
+        .await?;
+
+    let result = sqlx::query("SELECT 1 WHERE $1::some_enum_type = 'd'::some_enum_type;")
+        .bind(SomeEnumType("d".into()))
+        .fetch_all(&mut conn)
+        .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.len(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_server_num() -> anyhow::Result<()> {
+    let conn = new::<Postgres>().await?;
+
+    assert!(conn.server_version_num().is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_in() -> anyhow::Result<()> {
+    let mut conn = with_fixtures::<Postgres>(&["temp_users"]).await?;
+
+    let mut copy = conn
+        .copy_in_raw(
+            r#"
+        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
+    "#,
+        )
+        .await?;
+
+    copy.send("id\n1\n2\n".as_bytes()).await?;
+    let rows = copy.finish().await?;
+    assert_eq!(rows, 2);
+
+    // conn is safe for reuse
+    let value = sqlx::query("select 1 + 1")
+        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut *conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+// `it_rolls_back_fixtures_*` share the `todos` fixture and each change it, which the other
+// doesn't see as both are rolled back
+#[sqlx_macros::test]
+async fn it_rolls_back_fixtures_after_insert() -> anyhow::Result<()> {
+    let mut conn = with_fixtures::<Postgres>(&["todos"]).await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM todos")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    sqlx::query("INSERT INTO todos (id, description) VALUES (3, 'roll back the fixtures')")
+        .execute(&mut *conn)
+        .await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM todos")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    assert_eq!(count, 3);
+
+    // another session can't see the table until the fixture transaction commits,
+    // which it never does
+    assert!(!todos_exists_in_other_session().await?);
+
+    drop(conn);
+
+    assert!(!todos_exists_in_other_session().await?);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_fixtures_after_delete() -> anyhow::Result<()> {
+    let mut conn = with_fixtures::<Postgres>(&["todos"]).await?;
+
+    let done = sqlx::query("DELETE FROM todos WHERE done")
+        .execute(&mut *conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 1);
+
+    let descriptions: Vec<String> = sqlx::query_scalar("SELECT description FROM todos")
+        .fetch_all(&mut *conn)
+        .await?;
+
+    assert_eq!(descriptions, ["use the fixtures in tests"]);
+
+    // another session can't see the table until the fixture transaction commits,
+    // which it never does
+    assert!(!todos_exists_in_other_session().await?);
+
+    drop(conn);
+
+    assert!(!todos_exists_in_other_session().await?);
+
+    Ok(())
+}
+
+async fn todos_exists_in_other_session() -> anyhow::Result<bool> {
+    let mut conn = new::<Postgres>().await?;
 
-CREATE SCHEMA another;
-CREATE TYPE some_enum_type AS ENUM ('a', 'b', 'c');
-CREATE TYPE another.some_enum_type AS ENUM ('d', 'e', 'f');
-    "#,
+    Ok(
+        sqlx::query_scalar("SELECT to_regclass('todos') IS NOT NULL")
+            .fetch_one(&mut conn)
+            .await?,
     )
-    .await?;
-
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct SomeEnumType(String);
+}
 
-    impl sqlx::Type<Postgres> for SomeEnumType {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("some_enum_type")
-        }
+#[sqlx_macros::test]
+async fn it_commits_committed_fixtures() -> anyhow::Result<()> {
+    let conn = with_committed_fixtures::<Postgres>(&["fixture_commits"]).await?;
 
-        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
-            *ty == Self::type_info()
-        }
-    }
+    let mut other = new::<Postgres>().await?;
 
-    impl<'r> sqlx::Decode<'r, Postgres> for SomeEnumType {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(<String as sqlx::Decode<Postgres>>::decode(value)?))
-        }
-    }
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM fixture_commits")
+        .fetch_one(&mut other)
+        .await?;
 
-    impl<'q> sqlx::Encode<'q, Postgres> for SomeEnumType {
-        fn encode_by_ref(
-            &self,
-            buf: &mut sqlx::postgres::PgArgumentBuffer,
-        ) -> Result<sqlx::encode::IsNull, BoxDynError> {
-            <String as sqlx::Encode<Postgres>>::encode_by_ref(&self.0, buf)
-        }
-    }
+    assert_eq!(count, 1);
 
-    let mut conn = new::<Postgres>().await?;
+    drop(conn);
 
-    sqlx::query("set search_path = 'another'")
-        .execute(&mut conn)
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM fixture_commits")
+        .fetch_one(&mut other)
         .await?;
 
-    let result = sqlx::query("SELECT 1 WHERE $1::some_enum_type = 'd'::some_enum_type;")
-        .bind(SomeEnumType("d".into()))
-        .fetch_all(&mut conn)
-        .await;
+    assert_eq!(count, 1);
 
-    let result = result.unwrap();
-    assert_eq!(result.len(), 1);
+    other.execute("DROP TABLE fixture_commits").await?;
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_pg_server_num() -> anyhow::Result<()> {
-    let conn = new::<Postgres>().await?;
+async fn it_reports_failing_fixture_statement() -> anyhow::Result<()> {
+    let Err(err) = with_fixtures::<Postgres>(&["temp_users", "failing"]).await else {
+        panic!("expected the fixture to fail");
+    };
 
-    assert!(conn.server_version_num().is_some());
+    let message = format!("{err:#}");
+
+    assert!(
+        message.contains("statement #2 of fixture ") && message.contains("failing.sql"),
+        "{message}"
+    );
+    assert!(message.contains("fixture_does_not_exist"), "{message}");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_copy_in() -> anyhow::Result<()> {
+async fn it_can_abort_copy_in() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
     conn.execute(
         r#"
@@ -1633,7 +3411,7 @@ async fn it_can_copy_in() -> anyhow::Result<()> {
     )
     .await?;
 
-    let mut copy = conn
+    let copy = conn
         .copy_in_raw(
             r#"
         COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
@@ -1641,9 +3419,7 @@ async fn it_can_copy_in() -> anyhow::Result<()> {
         )
         .await?;
 
-    copy.send("id\n1\n2\n".as_bytes()).await?;
-    let rows = copy.finish().await?;
-    assert_eq!(rows, 2);
+    copy.abort("this is only a test").await?;
 
     // conn is safe for reuse
     let value = sqlx::query("select 1 + 1")
@@ -1657,32 +3433,70 @@ async fn it_can_copy_in() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_can_abort_copy_in() -> anyhow::Result<()> {
+async fn it_can_copy_typed_rows() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
+
     conn.execute(
-        r#"
-        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
-    "#,
+        "CREATE TEMPORARY TABLE copy_typed (id INT8 NOT NULL, name TEXT, data BYTEA, score FLOAT8)",
     )
     .await?;
 
-    let copy = conn
-        .copy_in_raw(
-            r#"
-        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
-    "#,
+    let rows: Vec<(i64, Option<String>, Vec<u8>, Option<f64>)> = (0..1000)
+        .map(|i| {
+            (
+                i,
+                (i % 3 != 0).then(|| format!("name {i}")),
+                vec![(i % 256) as u8; (i % 7) as usize],
+                (i % 5 != 0).then(|| i as f64 / 2.0),
+            )
+        })
+        .collect();
+
+    let mut copy = conn
+        .copy_in_typed("COPY copy_typed (id, name, data, score) FROM STDIN WITH (FORMAT binary)")
+        .await?;
+
+    // the arity is checked before anything is sent
+    assert!(copy.send_row((1_i64, "too few")).await.is_err());
+
+    for (id, name, data, score) in &rows {
+        copy.send_row((id, name.as_deref(), &data[..], score))
+            .await?;
+    }
+
+    assert_eq!(copy.finish().await?, 1000);
+
+    let copied: Vec<(i64, Option<String>, Vec<u8>, Option<f64>)> = conn
+        .copy_out_typed(
+            "COPY (SELECT id, name, data, score FROM copy_typed ORDER BY id) \
+             TO STDOUT WITH (FORMAT binary)",
         )
+        .await?
+        .try_collect()
         .await?;
 
-    copy.abort("this is only a test").await?;
+    assert_eq!(copied, rows);
 
-    // conn is safe for reuse
-    let value = sqlx::query("select 1 + 1")
-        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+    // a row of the wrong arity is an error, and the connection can still be used afterwards
+    let res = conn
+        .copy_out_typed::<(i64,)>("COPY copy_typed (id, name) TO STDOUT WITH (FORMAT binary)")
+        .await?
+        .try_collect::<Vec<_>>()
+        .await;
+
+    assert!(res.is_err());
+
+    // so is a textual `COPY`
+    assert!(conn
+        .copy_in_typed("COPY copy_typed (id) FROM STDIN")
+        .await
+        .is_err());
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM copy_typed")
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(2i32, value);
+    assert_eq!(count, 1000);
 
     Ok(())
 }
@@ -1719,6 +3533,90 @@ async fn it_can_copy_out() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_binds_arrays_to_any() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let names: &[&str] = &["a", "b"];
+
+    let matches: Vec<bool> = sqlx::query_scalar("SELECT x = ANY($1) FROM unnest($2::text[]) x")
+        .bind(names)
+        .bind(["a", "c"])
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(matches, [true, false]);
+
+    let strings = vec![String::from("b")];
+
+    let matches: bool = sqlx::query_scalar("SELECT 'b' = ANY($1)")
+        .bind(&strings[..])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(matches);
+
+    // `NULL` elements only make the comparison `NULL` if no other element is equal
+    let matches: Vec<Option<bool>> =
+        sqlx::query_scalar("SELECT x = ANY($1) FROM unnest($2::int8[]) x")
+            .bind(vec![Some(1_i64), None])
+            .bind([1_i64, 2])
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(matches, [Some(true), None]);
+
+    let elements: Vec<Option<i64>> = sqlx::query_scalar("SELECT $1::int8[]")
+        .bind(vec![Some(1_i64), None])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(elements, [Some(1), None]);
+
+    let has_nulls: bool = sqlx::query_scalar("SELECT array_position($1, NULL) IS NOT NULL")
+        .bind(&[None, Some("a")][..])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(has_nulls);
+
+    let matches: bool = sqlx::query_scalar("SELECT 1 = ANY($1)")
+        .bind([1_i32; 3])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(matches);
+
+    Ok(())
+}
+
+#[cfg(feature = "bigdecimal")]
+#[sqlx_macros::test]
+async fn it_binds_bigdecimal_slices_to_any() -> anyhow::Result<()> {
+    use sqlx::types::BigDecimal;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let values: Vec<BigDecimal> = vec!["1.5".parse()?, "-0.001".parse()?];
+
+    let matches: Vec<bool> = sqlx::query_scalar("SELECT x = ANY($1) FROM unnest($2::numeric[]) x")
+        .bind(&values[..])
+        .bind(["-0.001", "2"])
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(matches, [true, false]);
+
+    let elements: Vec<BigDecimal> = sqlx::query_scalar("SELECT $1::numeric[]")
+        .bind(&values[..])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(elements, values);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
     use sqlx::encode::IsNull;
@@ -2116,3 +4014,281 @@ async fn it_can_recover_from_copy_in_invalid_params() -> anyhow::Result<()> {
     )
     .await
 }
+
+#[sqlx_macros::test]
+async fn it_reads_rows_as_dynamic_values() -> anyhow::Result<()> {
+    use sqlx::{DynamicValue, FromRow};
+    use std::collections::HashMap;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = r#"
+        SELECT NULL::int4 AS "null", true AS "bool", 2::int2 AS small, -3::int8 AS big,
+            4::oid AS oid, 1.5::float8 AS double, 'text' AS text, '\xdeadbeef'::bytea AS bytes,
+            -12.3400::numeric(10, 4) AS numeric, 0.00012::numeric AS fraction,
+            123456789.5::numeric AS large, 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid AS uuid,
+            '{"a": 1}'::jsonb AS jsonb, 1 AS dup, 2 AS dup
+    "#;
+
+    let expected = vec![
+        ("null", DynamicValue::Null),
+        ("bool", DynamicValue::Bool(true)),
+        ("small", DynamicValue::Int(2)),
+        ("big", DynamicValue::Int(-3)),
+        ("oid", DynamicValue::UInt(4)),
+        ("double", DynamicValue::Double(1.5)),
+        ("text", DynamicValue::from("text")),
+        ("bytes", DynamicValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef])),
+        ("numeric", DynamicValue::from("-12.3400")),
+        ("fraction", DynamicValue::from("0.00012")),
+        ("large", DynamicValue::from("123456789.5")),
+        (
+            "uuid",
+            DynamicValue::from("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"),
+        ),
+        ("jsonb", DynamicValue::from(r#"{"a": 1}"#)),
+        ("dup", DynamicValue::Int(1)),
+        ("dup", DynamicValue::Int(2)),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_owned(), value))
+    .collect::<Vec<_>>();
+
+    // the binary format
+    let row: Vec<(String, DynamicValue)> = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+    assert_eq!(row, expected);
+
+    // the text format
+    let row = sqlx::raw_sql(sql).fetch_one(&mut conn).await?;
+    assert_eq!(Vec::<(String, DynamicValue)>::from_row(&row)?, expected);
+
+    let map: HashMap<String, DynamicValue> = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+    assert_eq!(map.len(), 14);
+    assert_eq!(map["text"], DynamicValue::from("text"));
+    assert_eq!(map["dup"], DynamicValue::Int(2));
+    assert!(map["null"].is_null());
+
+    // types without a text representation in the binary format
+    let sql = "SELECT '1 day'::interval AS interval";
+
+    let err = sqlx::query_as::<_, Vec<(String, DynamicValue)>>(sql)
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("expected a decode error");
+    assert!(
+        matches!(err, sqlx::Error::ColumnDecode { ref index, .. } if index == "\"interval\""),
+        "{err:?}"
+    );
+
+    let row = sqlx::raw_sql(sql).fetch_one(&mut conn).await?;
+    assert_eq!(
+        Vec::<(String, DynamicValue)>::from_row(&row)?,
+        [("interval".to_owned(), DynamicValue::from("1 day"))]
+    );
+
+    #[cfg(feature = "chrono")]
+    {
+        use sqlx::types::chrono::{NaiveDate, TimeZone, Utc};
+
+        let sql = "SELECT '2024-02-29'::date AS date, '2024-02-29 12:34:56+00'::timestamptz AS ts";
+
+        let map: HashMap<String, DynamicValue> = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+        assert_eq!(
+            map["date"],
+            DynamicValue::Date(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+        );
+        assert_eq!(
+            map["ts"],
+            DynamicValue::DateTimeUtc(Utc.with_ymd_and_hms(2024, 2, 29, 12, 34, 56).unwrap())
+        );
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_shares_statement_metadata_across_pool_connections() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .share_statement_metadata(true);
+
+    let pool = PgPoolOptions::new()
+        .min_connections(2)
+        .max_connections(2)
+        .connect_with(options)
+        .await?;
+
+    pool.execute(
+        "DROP TABLE IF EXISTS _sqlx_shared_metadata_1930; \
+         CREATE TABLE _sqlx_shared_metadata_1930 (id INTEGER PRIMARY KEY, name TEXT NOT NULL); \
+         INSERT INTO _sqlx_shared_metadata_1930 VALUES (1, 'a')",
+    )
+    .await?;
+
+    let sql = "SELECT * FROM _sqlx_shared_metadata_1930";
+
+    let mut conn_1 = pool.acquire().await?;
+    let mut conn_2 = pool.acquire().await?;
+
+    let statement_1 = conn_1.prepare(sql).await?;
+    let statement_2 = conn_2.prepare(sql).await?;
+
+    // both connections prepared the statement, but the second reused the columns of the first
+    assert_eq!(statement_2.columns().len(), 2);
+    assert!(std::ptr::eq(statement_1.columns(), statement_2.columns()));
+
+    conn_1
+        .execute("ALTER TABLE _sqlx_shared_metadata_1930 ADD COLUMN extra INTEGER")
+        .await?;
+
+    // the statement cached by the first connection was prepared before the change,
+    // which the server rejects once, after which it's prepared again
+    let err = sqlx::query(sql).fetch_one(&mut *conn_1).await.unwrap_err();
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("0A000")
+    );
+
+    let row = sqlx::query(sql).fetch_one(&mut *conn_1).await?;
+    assert_eq!(row.columns().len(), 3);
+    assert_eq!(row.try_get::<Option<i32>, _>("extra")?, None);
+
+    // the new shared metadata is reused by the other connection, which evicted its statement
+    // for the same error
+    assert!(sqlx::query(sql).fetch_one(&mut *conn_2).await.is_err());
+
+    let row = sqlx::query(sql).fetch_one(&mut *conn_2).await?;
+    assert_eq!(row.columns().len(), 3);
+    assert_eq!(row.try_get::<String, _>("name")?, "a");
+
+    let statement_1 = conn_1.prepare(sql).await?;
+    let statement_2 = conn_2.prepare(sql).await?;
+    assert!(std::ptr::eq(statement_1.columns(), statement_2.columns()));
+
+    conn_1
+        .execute("DROP TABLE _sqlx_shared_metadata_1930")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepares_statements_again_after_schema_change_in_transaction() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE _sqlx_stale_statement_1930 (id INTEGER); \
+         INSERT INTO _sqlx_stale_statement_1930 VALUES (1)",
+    )
+    .await?;
+
+    let sql = "SELECT * FROM _sqlx_stale_statement_1930";
+
+    sqlx::query(sql).fetch_one(&mut conn).await?;
+
+    let mut tx = conn.begin().await?;
+
+    tx.execute("ALTER TABLE _sqlx_stale_statement_1930 ADD COLUMN extra INTEGER")
+        .await?;
+
+    assert!(sqlx::query(sql).fetch_one(&mut *tx).await.is_err());
+
+    tx.rollback().await?;
+
+    // the statement was closed even though the transaction had failed
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    assert_eq!(row.columns().len(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_dropped_transaction_before_close() -> anyhow::Result<()> {
+    let other = new::<Postgres>().await?;
+
+    // the table would still be locked by the transaction if it weren't rolled back yet
+    sqlx_test::rolls_back_dropped_transaction_before_close::<Postgres, _, _>(
+        other,
+        new::<Postgres>,
+        "LOCK TABLE close_test IN ACCESS EXCLUSIVE MODE NOWAIT",
+    )
+    .await
+}
+
+#[cfg(feature = "chrono")]
+#[sqlx_macros::test]
+async fn it_decodes_timestamps_strictly() -> anyhow::Result<()> {
+    use sqlx::types::chrono::{AssumeUtc, DateTime, NaiveDate, NaiveDateTime, Utc};
+
+    setup_if_needed();
+
+    let naive = NaiveDate::from_ymd_opt(2024, 2, 29)
+        .unwrap()
+        .and_hms_opt(12, 34, 56)
+        .unwrap();
+    let sql = "SELECT '2024-02-29 12:34:56'::timestamp, '2024-02-29 12:34:56+00'::timestamptz";
+
+    // by default, the time zone is assumed or dropped
+    let mut conn = new::<Postgres>().await?;
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+
+    assert_eq!(
+        row.try_get_unchecked::<DateTime<Utc>, _>(0)?,
+        naive.and_utc()
+    );
+    assert_eq!(row.try_get_unchecked::<NaiveDateTime, _>(1)?, naive);
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .strict_timestamps(true);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+
+    let err = row
+        .try_get_unchecked::<DateTime<Utc>, _>(0)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("cannot decode TIMESTAMP as `DateTime<Tz>`"),
+        "{err}"
+    );
+
+    let err = row
+        .try_get_unchecked::<NaiveDateTime, _>(1)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("cannot decode TIMESTAMPTZ as `NaiveDateTime`"),
+        "{err}"
+    );
+
+    assert_eq!(row.try_get::<NaiveDateTime, _>(0)?, naive);
+    assert_eq!(row.try_get::<AssumeUtc, _>(0)?, AssumeUtc(naive.and_utc()));
+    assert_eq!(row.try_get::<DateTime<Utc>, _>(1)?, naive.and_utc());
+
+    // `COPY` doesn't report column types, but array elements carry theirs
+    let err = conn
+        .copy_out_typed::<(Vec<DateTime<Utc>>,)>(
+            "COPY (SELECT ARRAY['2024-02-29 12:34:56'::timestamp]) TO STDOUT WITH (FORMAT binary)",
+        )
+        .await?
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("cannot decode TIMESTAMP as `DateTime<Tz>`"),
+        "{err}"
+    );
+
+    let assumed: AssumeUtc = sqlx::query_scalar("SELECT $1")
+        .bind(AssumeUtc(naive.and_utc()))
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(assumed.naive_utc(), naive);
+
+    Ok(())
+}
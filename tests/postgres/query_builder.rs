@@ -1,5 +1,5 @@
 use sqlx::postgres::Postgres;
-use sqlx::query_builder::QueryBuilder;
+use sqlx::query_builder::{QueryBuilder, Upsert};
 use sqlx::Executor;
 use sqlx::Type;
 use sqlx::{Either, Execute};
@@ -97,8 +97,9 @@ fn test_query_builder_with_args() {
         .push_bind(42i32)
         .build();
 
+    let sql = query.sql().to_owned();
     let mut qb: QueryBuilder<'_, Postgres> =
-        QueryBuilder::with_arguments(query.sql(), query.take_arguments().unwrap().unwrap());
+        QueryBuilder::with_arguments(sql, query.take_arguments().unwrap().unwrap());
     let query = qb.push(" OR membership_level = ").push_bind(3i32).build();
 
     assert_eq!(
@@ -157,3 +158,49 @@ async fn test_max_number_of_binds() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_upsert() {
+    let qb = Upsert::<Postgres>::into("public.user\"s")
+        .columns(&["id", "name", "email"])
+        .values(0..2, |mut b, i| {
+            b.push_bind(i).push_bind("name").push_bind("email");
+        })
+        .conflict(&["id"])
+        .update(&["name", "email"])
+        .into_query_builder();
+
+    assert_eq!(
+        qb.sql(),
+        r#"INSERT INTO "public"."user""s" ("id", "name", "email") VALUES ($1, $2, $3), ($4, $5, $6) ON CONFLICT ("id") DO UPDATE SET "name" = excluded."name", "email" = excluded."email""#
+    );
+}
+
+#[test]
+fn test_upsert_without_update() {
+    let qb = Upsert::<Postgres>::into("users")
+        .columns(&["id"])
+        .values([1], |mut b, id| {
+            b.push_bind(id);
+        })
+        .into_query_builder();
+
+    assert_eq!(
+        qb.sql(),
+        r#"INSERT INTO "users" ("id") VALUES ($1) ON CONFLICT DO NOTHING"#
+    );
+}
+
+#[test]
+#[should_panic(expected = "`Upsert::conflict()` must be called")]
+fn test_upsert_update_without_conflict_panics() {
+    let _qb = Upsert::<Postgres>::into("users")
+        .columns(&["id", "name"])
+        .values([(1, "Alice")], |mut b, (id, name)| {
+            b.push_bind(id).push_bind(name);
+        })
+        .update(&["name"])
+        .into_query_builder();
+}
+
+sqlx_test::test_upsert_conflicting_rows!(test_upsert_updates_conflicting_rows(Postgres));
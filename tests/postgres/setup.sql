@@ -63,3 +63,10 @@ CREATE SCHEMA IF NOT EXISTS foo;
 CREATE TYPE foo."Foo" as ENUM ('Bar', 'Baz');
 
 CREATE TABLE mytable(f HSTORE);
+
+-- legacy camelCase columns, see `#[sqlx(rename_all = "camelCase")]`
+CREATE TABLE user_profiles (
+    "userId" INTEGER PRIMARY KEY,
+    "displayName" TEXT NOT NULL,
+    "emailAddress" TEXT
+);
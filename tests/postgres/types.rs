@@ -1,11 +1,14 @@
 extern crate time_ as time;
 
 use std::net::SocketAddr;
+use std::num::{NonZeroI32, NonZeroI64};
 use std::ops::Bound;
 
-use sqlx::postgres::types::{Oid, PgCiText, PgInterval, PgMoney, PgRange};
+use sqlx::postgres::types::{
+    Oid, PgCiText, PgInterval, PgMoney, PgRange, PgTsQuery, PgTsVector, PgXml,
+};
 use sqlx::postgres::Postgres;
-use sqlx_test::{new, test_decode_type, test_prepared_type, test_type};
+use sqlx_test::{new, test_decode_type, test_null_type, test_prepared_type, test_type};
 
 use sqlx_core::executor::Executor;
 use sqlx_core::types::Text;
@@ -15,6 +18,10 @@ test_type!(null<Option<i16>>(Postgres,
     "NULL::int2" == None::<i16>
 ));
 
+test_null_type!(option_i32<i32>(Postgres, "NULL::int4"));
+test_null_type!(option_string<String>(Postgres, "NULL::text", "NULL::varchar"));
+test_null_type!(option_bytes<Vec<u8>>(Postgres, "NULL::bytea"));
+
 test_type!(null_vec<Vec<Option<i16>>>(Postgres,
     "array[10,NULL,50]::int2[]" == vec![Some(10_i16), None, Some(50)],
 ));
@@ -61,6 +68,59 @@ test_type!(byte_array<[u8; 4]>(Postgres,
     "E'\\\\xDEADBEEF'::bytea" == [0xDE_u8, 0xAD, 0xBE, 0xEF],
 ));
 
+test_type!(byte_array_16<[u8; 16]>(Postgres,
+    "E'\\\\xb731678f636f4135bc6f19440c13bd19'::bytea"
+        == [0xb7_u8, 0x31, 0x67, 0x8f, 0x63, 0x6f, 0x41, 0x35, 0xbc, 0x6f, 0x19, 0x44, 0x0c, 0x13, 0xbd, 0x19],
+));
+
+test_type!(byte_array_32<[u8; 32]>(Postgres,
+    "E'\\\\xabababababababababababababababababababababababababababababababab'::bytea" == [0xAB_u8; 32],
+));
+
+test_type!(byte_array_option<Option<[u8; 4]>>(Postgres,
+    "E'\\\\xDEADBEEF'::bytea" == Some([0xDE_u8, 0xAD, 0xBE, 0xEF]),
+    "NULL::bytea" == None::<[u8; 4]>,
+));
+
+test_type!(byte_array_vec<Vec<[u8; 4]>>(Postgres,
+    "array[E'\\\\xDEADBEEF', E'\\\\x00000052']::bytea[]"
+        == vec![[0xDE_u8, 0xAD, 0xBE, 0xEF], [0, 0, 0, 0x52]],
+    "array[]::bytea[]" == Vec::<[u8; 4]>::new(),
+));
+
+test_type!(byte_array_option_vec<Vec<Option<[u8; 4]>>>(Postgres,
+    "array[E'\\\\xDEADBEEF', NULL]::bytea[]"
+        == vec![Some([0xDE_u8, 0xAD, 0xBE, 0xEF]), None],
+));
+
+#[sqlx_macros::test]
+async fn test_byte_array_rejects_wrong_length() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // prepared queries receive BYTEA in binary, simple queries as hex text
+    let prepared = sqlx::query_scalar::<_, [u8; 16]>("SELECT E'\\\\xDEADBEEF'::bytea")
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("expected an error decoding 4 bytes as [u8; 16]");
+
+    let row = conn.fetch_one("SELECT E'\\\\xDEADBEEF'::bytea").await?;
+    let unprepared = row
+        .try_get::<[u8; 16], _>(0)
+        .expect_err("expected an error decoding 4 bytes as [u8; 16]");
+
+    for err in [prepared, unprepared] {
+        assert!(
+            err.to_string()
+                .contains("expected 16 bytes to decode `[u8; 16]`, got 4 bytes"),
+            "{err}"
+        );
+    }
+
+    Ok(())
+}
+
 test_type!(str<&str>(Postgres,
     "'this is foo'" == "this is foo",
     "''" == "",
@@ -85,16 +145,58 @@ test_type!(string_vec<Vec<String>>(Postgres,
         == vec!["Hello, World", "", "Goodbye"],
 ));
 
+test_type!(string_vec_null<Vec<Option<String>>>(Postgres,
+    "array['NULL', NULL, '', 'a,b', '{}', 'back\\slash', ' padded ']::text[]"
+        == vec![Some("NULL".to_owned()), None, Some(String::new()), Some("a,b".to_owned()),
+                Some("{}".to_owned()), Some("back\\slash".to_owned()), Some(" padded ".to_owned())],
+));
+
 test_type!(string_array<[String; 3]>(Postgres,
     "array['one','two','three']::text[]" == ["one","two","three"],
 ));
 
+test_type!(char(
+    Postgres,
+    "'a'" == 'a',
+    "'é'::text" == 'é',
+    "'🦀'::varchar" == '🦀',
+    "'z'::char(1)" == 'z',
+));
+
 test_type!(i8(
     Postgres,
     "0::\"char\"" == 0_i8,
     "120::\"char\"" == 120_i8,
 ));
 
+#[sqlx_macros::test]
+async fn test_char_decodes_catalog_columns() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // `relkind` is a `"char"`, `relname` is a `name`
+    let sql = "SELECT relkind, relname FROM pg_class WHERE relname = 'pg_class'";
+
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<i8, _>("relkind")?, b'r' as i8);
+    assert_eq!(row.try_get::<char, _>("relkind")?, 'r');
+
+    // `i8` is not a 2-byte integer
+    let row = sqlx::query("SELECT 1::int2").fetch_one(&mut conn).await?;
+    assert!(row.try_get::<i8, _>(0).is_err());
+
+    // same in the text format
+    let row = conn.fetch_one(sql).await?;
+    assert_eq!(row.try_get::<char, _>("relkind")?, 'r');
+
+    let err = row.try_get::<char, _>("relname").unwrap_err();
+    assert!(err.to_string().contains("relname"), "{err}");
+    assert!(err.to_string().contains("8 characters"), "{err}");
+
+    Ok(())
+}
+
 test_type!(Oid(Postgres, "325235::oid" == Oid(325235),));
 
 test_type!(i16(
@@ -126,6 +228,100 @@ test_type!(i32_array<[i32; 4]>(Postgres,
 
 test_type!(i64(Postgres, "9358295312::bigint" == 9358295312_i64));
 
+test_decode_type!(numeric_f64<f64>(Postgres,
+    "1.5::numeric" == 1.5_f64,
+    "-12345678901234567890.125::numeric" == -12345678901234567890.125_f64,
+    "0.000000000000000000000000000001::numeric" == 1e-30_f64,
+    "1e300::numeric" == 1e300_f64,
+    "12345::numeric(5, -2)" == 12300_f64,
+    "'-Infinity'::numeric" == f64::NEG_INFINITY,
+));
+
+test_decode_type!(numeric_f32<f32>(Postgres,
+    "0.1::numeric" == 0.1_f32,
+    "-3.25::numeric" == -3.25_f32,
+    "1e38::numeric" == 1e38_f32,
+));
+
+#[sqlx_macros::test]
+async fn test_numeric_as_float_edge_cases() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let nan: f64 = sqlx::query_scalar("SELECT 'NaN'::numeric")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(nan.is_nan());
+
+    // values that don't fit are an error, rather than infinity
+    let err = sqlx::query_scalar::<_, f64>("SELECT 1e400::numeric")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("NUMERIC value is out of range for `f64`"),
+        "{err}"
+    );
+
+    let err = sqlx::query_scalar::<_, f32>("SELECT 1e39::numeric")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("NUMERIC value is out of range for `f32`"),
+        "{err}"
+    );
+
+    // values too small for the float type are rounded to zero
+    let tiny: f64 = sqlx::query_scalar("SELECT 1e-400::numeric")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(tiny, 0.0);
+
+    // the same, with the text protocol
+    let row = conn
+        .fetch_one("SELECT 'NaN'::numeric, 1e400::numeric")
+        .await?;
+    assert!(row.try_get::<f64, _>(0)?.is_nan());
+    assert!(row.try_get::<f64, _>(1).is_err());
+
+    Ok(())
+}
+
+test_type!(non_zero_i64<NonZeroI64>(Postgres,
+    "9358295312::bigint" == NonZeroI64::new(9358295312).unwrap(),
+    "-1::bigint" == NonZeroI64::new(-1).unwrap(),
+));
+
+test_type!(non_zero_i32_vec<Vec<NonZeroI32>>(Postgres,
+    "'{5,10,-50}'::int4[]" == vec![
+        NonZeroI32::new(5).unwrap(),
+        NonZeroI32::new(10).unwrap(),
+        NonZeroI32::new(-50).unwrap(),
+    ],
+));
+
+#[sqlx_macros::test]
+async fn test_non_zero_rejects_zero() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let err = sqlx::query_scalar::<_, NonZeroI64>("SELECT 0::int8")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("expected a non-zero value for `NonZeroI64`, got 0"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 test_type!(f32(Postgres, "9419.122::real" == 9419.122_f32));
 
 test_type!(f64(
@@ -137,6 +333,20 @@ test_type!(f64_vec<Vec<f64>>(Postgres,
     "'{939399419.1225182,-12.0}'::float8[]" == vec![939399419.1225182_f64, -12.0]
 ));
 
+test_type!(f32_infinity<f32>(Postgres,
+    "'Infinity'::real" == f32::INFINITY,
+    "'-Infinity'::real" == f32::NEG_INFINITY,
+));
+
+test_type!(f64_infinity<f64>(Postgres,
+    "'Infinity'::double precision" == f64::INFINITY,
+    "'-Infinity'::double precision" == f64::NEG_INFINITY,
+));
+
+test_type!(f64_vec_infinity<Vec<f64>>(Postgres,
+    "'{Infinity,-Infinity,0}'::float8[]" == vec![f64::INFINITY, f64::NEG_INFINITY, 0.0]
+));
+
 test_decode_type!(bool_tuple<(bool,)>(Postgres, "row(true)" == (true,)));
 
 test_decode_type!(num_tuple<(i32, i64, f64,)>(Postgres, "row(10,515::int8,3.124::float8)" == (10,515,3.124)));
@@ -199,6 +409,40 @@ test_type!(ipnetwork<sqlx::types::ipnetwork::IpNetwork>(Postgres,
             .unwrap(),
 ));
 
+#[cfg(feature = "ipnetwork")]
+test_type!(ipaddr<std::net::IpAddr>(Postgres,
+    "'127.0.0.1'::inet" == "127.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+    "'::ffff:1.2.3.0'::inet" == "::ffff:1.2.3.0".parse::<std::net::IpAddr>().unwrap(),
+    "'2001:4f8:3:ba::1'::inet" == "2001:4f8:3:ba::1".parse::<std::net::IpAddr>().unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+#[sqlx_macros::test]
+async fn test_ipaddr_rejects_network_and_zone() -> anyhow::Result<()> {
+    use sqlx::Row;
+    use std::net::IpAddr;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // a host address with a non-full mask can't be represented losslessly by `IpAddr`,
+    // neither in the binary (prepared) nor the text (unprepared) format
+    let row = sqlx::query("SELECT '8.8.8.8/24'::inet")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(row.try_get::<IpAddr, _>(0).is_err());
+
+    let row = conn.fetch_one("SELECT '2001:4f8:3:ba::/64'::inet").await?;
+    assert!(row.try_get::<IpAddr, _>(0).is_err());
+
+    // IPv6 zone identifiers aren't valid `inet` values
+    let res = sqlx::query("SELECT 'fe80::1%eth0'::inet")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 #[cfg(feature = "mac_address")]
 test_type!(mac_address<sqlx::types::mac_address::MacAddress>(Postgres,
     "'00:01:02:03:04:05'::macaddr"
@@ -258,6 +502,9 @@ mod chrono {
     };
 
     type PgTimeTz = sqlx::postgres::types::PgTimeTz<NaiveTime, FixedOffset>;
+    type PgTimestamp<T> = sqlx::postgres::types::PgTimestamp<T>;
+
+    use sqlx::Row;
 
     test_type!(chrono_date<NaiveDate>(Postgres,
         "DATE '2001-01-05'" == NaiveDate::from_ymd_opt(2001, 1, 5).unwrap(),
@@ -272,6 +519,28 @@ mod chrono {
         "'2019-01-02 05:10:20'::timestamp" == NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap()
     ));
 
+    test_type!(chrono_date_time_range_edges<NaiveDateTime>(Postgres,
+        "'262142-12-31 23:59:59.999999'::timestamp"
+            == NaiveDate::from_ymd_opt(262142, 12, 31).unwrap().and_hms_micro_opt(23, 59, 59, 999999).unwrap(),
+        "'1000-01-01 00:00:00'::timestamp" == NaiveDate::from_ymd_opt(1000, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()
+    ));
+
+    test_type!(chrono_date_time_infinity<PgTimestamp<NaiveDateTime>>(Postgres,
+        "'infinity'::timestamp" == PgTimestamp::<NaiveDateTime>::Infinity,
+        "'-infinity'::timestamp" == PgTimestamp::<NaiveDateTime>::NegInfinity,
+        "'2019-01-02 05:10:20'::timestamp"
+            == PgTimestamp::Value(NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap())
+    ));
+
+    test_type!(chrono_date_time_infinity_vec<Vec<PgTimestamp<NaiveDateTime>>>(Postgres,
+        "array['-infinity', '2019-01-02 05:10:20', 'infinity']::timestamp[]"
+            == vec![
+                PgTimestamp::NegInfinity,
+                PgTimestamp::Value(NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap()),
+                PgTimestamp::Infinity,
+            ]
+    ));
+
     test_type!(chrono_date_time_vec<Vec<NaiveDateTime>>(Postgres,
         "array['2019-01-02 05:10:20']::timestamp[]"
             == vec![NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap()]
@@ -298,6 +567,56 @@ mod chrono {
             ]
     ));
 
+    test_type!(chrono_date_time_tz_infinity<PgTimestamp<DateTime<Utc>>>(Postgres,
+        "'infinity'::timestamptz" == PgTimestamp::<DateTime<Utc>>::Infinity,
+        "'-infinity'::timestamptz" == PgTimestamp::<DateTime<Utc>>::NegInfinity,
+        "TIMESTAMPTZ '2019-01-02 05:10:20.115100'"
+            == PgTimestamp::Value(Utc.from_utc_datetime(
+                &NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_micro_opt(5, 10, 20, 115100).unwrap(),
+            ))
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_chrono_rejects_infinite_and_out_of_range_timestamps() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        for infinity in ["'infinity'", "'-infinity'"] {
+            let timestamp = format!("SELECT {infinity}::timestamp");
+            let timestamptz = format!("SELECT {infinity}::timestamptz");
+
+            // binary format
+            let err = sqlx::query_scalar::<_, NaiveDateTime>(&timestamp)
+                .fetch_one(&mut conn)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains(infinity), "{err}");
+
+            let err = sqlx::query_scalar::<_, DateTime<Utc>>(&timestamptz)
+                .fetch_one(&mut conn)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains(infinity), "{err}");
+
+            // text format
+            let row = conn.fetch_one(&*timestamp).await?;
+            let err = row.try_get::<NaiveDateTime, _>(0).unwrap_err();
+            assert!(err.to_string().contains(infinity), "{err}");
+
+            let row = conn.fetch_one(&*timestamptz).await?;
+            let err = row.try_get::<DateTime<Utc>, _>(0).unwrap_err();
+            assert!(err.to_string().contains(infinity), "{err}");
+        }
+
+        // later than `NaiveDateTime::MAX` but valid in Postgres
+        let res =
+            sqlx::query_scalar::<_, NaiveDateTime>("SELECT '294276-12-31 23:59:59'::timestamp")
+                .fetch_one(&mut conn)
+                .await;
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
     test_type!(chrono_time_tz<PgTimeTz>(Postgres,
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::east_opt(0).unwrap() },
         "TIMETZ '05:10:20.115100+06:30'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::east_opt(60 * 60 * 6 + 1800).unwrap() },
@@ -313,6 +632,9 @@ mod time_tests {
     use time::macros::{date, time};
 
     type PgTimeTz = sqlx::postgres::types::PgTimeTz<Time, UtcOffset>;
+    type PgTimestamp<T> = sqlx::postgres::types::PgTimestamp<T>;
+
+    use sqlx::Row;
 
     test_type!(time_date<Date>(
         Postgres,
@@ -340,6 +662,31 @@ mod time_tests {
                 .assume_utc()
     ));
 
+    test_type!(time_timestamp_infinity<PgTimestamp<OffsetDateTime>>(
+        Postgres,
+        "'infinity'::timestamptz" == PgTimestamp::<OffsetDateTime>::Infinity,
+        "'-infinity'::timestamptz" == PgTimestamp::<OffsetDateTime>::NegInfinity,
+        "TIMESTAMPTZ '2019-01-02 05:10:20.115100'"
+            == PgTimestamp::Value(date!(2019 - 1 - 2).with_time(time!(5:10:20.115100)).assume_utc())
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_time_rejects_infinite_timestamps() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let err = sqlx::query_scalar::<_, PrimitiveDateTime>("SELECT 'infinity'::timestamp")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'infinity'"), "{err}");
+
+        let row = conn.fetch_one("SELECT '-infinity'::timestamptz").await?;
+        let err = row.try_get::<OffsetDateTime, _>(0).unwrap_err();
+        assert!(err.to_string().contains("'-infinity'"), "{err}");
+
+        Ok(())
+    }
+
     test_prepared_type!(time_time_tz<PgTimeTz>(Postgres,
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(0).unwrap() },
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(0).unwrap() },
@@ -358,6 +705,7 @@ mod json {
     use sqlx::types::Json;
     use sqlx::{Executor, Row};
     use sqlx_test::new;
+    use std::collections::{BTreeMap, HashMap};
 
     // When testing JSON, coerce to JSONB for `=` comparison as `JSON = JSON` is not
     // supported in PostgreSQL
@@ -414,6 +762,45 @@ mod json {
             ]
     ));
 
+    test_type!(jsonb_btree_map<BTreeMap<String, JsonValue>>(
+        Postgres,
+        "'{\"name\": \"Joe\", \"friend\": {\"name\": \"Bob\", \"tags\": [1, 2]}, \"名前\": \"😎\"}'::jsonb"
+            == serde_json::from_value::<BTreeMap<String, JsonValue>>(json!({
+                "name": "Joe",
+                "friend": { "name": "Bob", "tags": [1, 2] },
+                "名前": "😎",
+            })).unwrap(),
+        "'{}'::jsonb" == BTreeMap::<String, JsonValue>::new(),
+    ));
+
+    test_type!(json_hash_map<HashMap<String, JsonValue>>(
+        Postgres,
+        "SELECT ({0}::jsonb is not distinct from $1::jsonb)::int4, {0} as _2, $2 as _3",
+        "'{\"name\": \"Joe\", \"friend\": {\"name\": \"Bob\"}}'::json"
+            == serde_json::from_value::<HashMap<String, JsonValue>>(json!({
+                "name": "Joe",
+                "friend": { "name": "Bob" },
+            })).unwrap(),
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_json_map_rejects_non_object() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let err = sqlx::query_scalar::<_, HashMap<String, JsonValue>>("SELECT '[1, 2]'::jsonb")
+            .fetch_one(&mut conn)
+            .await
+            .expect_err("expected an error decoding an array as a map");
+
+        assert!(
+            err.to_string()
+                .contains("expected a JSON object, found an array"),
+            "{err}"
+        );
+
+        Ok(())
+    }
+
     #[sqlx_macros::test]
     async fn test_json_raw_value() -> anyhow::Result<()> {
         let mut conn = new::<Postgres>().await?;
@@ -466,8 +853,112 @@ test_type!(bigdecimal<sqlx::types::BigDecimal>(Postgres,
     "0.00000002::numeric" == "0.00000002".parse::<sqlx::types::BigDecimal>().unwrap(),
     "12.34::numeric" == "12.34".parse::<sqlx::types::BigDecimal>().unwrap(),
     "12345.6789::numeric" == "12345.6789".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "0.00000000000000000001::numeric" == "0.00000000000000000001".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "-0.0000000000000000001234::numeric" == "-0.0000000000000000001234".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "1200000000::numeric" == "1200000000".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "1234567890123456789012345678901234567890.12345678901234567890::numeric(60, 20)"
+        == "1234567890123456789012345678901234567890.12345678901234567890".parse::<sqlx::types::BigDecimal>().unwrap(),
 ));
 
+#[cfg(feature = "bigdecimal")]
+#[sqlx_macros::test]
+async fn test_bigdecimal_scale_and_nan() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgNumericNaNError;
+    use sqlx::types::BigDecimal;
+    use sqlx::Row;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // trailing zeros are kept in both formats
+    let sql = "SELECT 1.50000::numeric(10, 5), 0.000::numeric, 'NaN'::numeric";
+
+    for row in [
+        sqlx::query(sql).fetch_one(&mut conn).await?,
+        conn.fetch_one(sql).await?,
+    ] {
+        let value: BigDecimal = row.try_get(0)?;
+        assert_eq!(value.as_bigint_and_exponent(), (150000.into(), 5));
+
+        let zero: BigDecimal = row.try_get(1)?;
+        assert_eq!(zero.as_bigint_and_exponent(), (0.into(), 3));
+
+        match row.try_get::<BigDecimal, _>(2) {
+            Err(sqlx::Error::ColumnDecode { source, .. }) => {
+                let err = source.downcast_ref::<PgNumericNaNError>().unwrap();
+                assert_eq!(err.type_name(), "BigDecimal");
+            }
+            res => panic!("expected a decode error, got {res:?}"),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "bigdecimal")]
+#[sqlx_macros::test]
+async fn test_bigdecimal_random_round_trip() -> anyhow::Result<()> {
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+    use sqlx::types::BigDecimal;
+
+    let mut conn = new::<Postgres>().await?;
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x5eed);
+
+    for _ in 0..500 {
+        // runs of zeros cover leading and trailing zero digit groups
+        let mut digits = |max_len: usize| -> String {
+            let len = rng.gen_range(0..max_len);
+            let zeros = rng.gen_bool(0.5);
+
+            (0..len)
+                .map(|_| match zeros && rng.gen_bool(0.8) {
+                    true => '0',
+                    false => char::from(b'0' + rng.gen_range(0..10)),
+                })
+                .collect::<String>()
+        };
+
+        let integer = digits(45);
+        let fraction = digits(45);
+
+        let mut text = match integer.trim_start_matches('0') {
+            "" => "0".to_owned(),
+            integer => integer.to_owned(),
+        };
+
+        if !fraction.is_empty() {
+            text = format!("{text}.{fraction}");
+        }
+
+        if rng.gen_bool(0.5) && text.contains(|c| ('1'..='9').contains(&c)) {
+            text.insert(0, '-');
+        }
+
+        let expected: BigDecimal = text.parse()?;
+
+        // Postgres parsing `text` is the oracle for both encoding and decoding
+        let (echoed, parsed, printed): (BigDecimal, BigDecimal, String) =
+            sqlx::query_as("SELECT $1, $2::numeric, $1::text")
+                .bind(&expected)
+                .bind(&text)
+                .fetch_one(&mut conn)
+                .await?;
+
+        for decoded in [&echoed, &parsed] {
+            assert_eq!(
+                decoded.as_bigint_and_exponent(),
+                expected.as_bigint_and_exponent(),
+                "{text}"
+            );
+        }
+
+        // `text` is formatted as Postgres would
+        assert_eq!(printed, text);
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "bigdecimal")]
 test_type!(numrange_bigdecimal<PgRange<sqlx::types::BigDecimal>>(Postgres,
     "'(1.3,2.4)'::numrange" == PgRange::from(
@@ -634,6 +1125,53 @@ test_prepared_type!(citext_array<Vec<PgCiText>>(Postgres,
     ],
 ));
 
+// `xml` has no equality operator, so it can only be checked by decoding
+test_decode_type!(xml<PgXml>(Postgres,
+    "'<book><title>Manual</title></book>'::xml" == PgXml("<book><title>Manual</title></book>".into()),
+));
+
+test_type!(tsvector<PgTsVector>(Postgres,
+    // lexemes are listed in the order Postgres sorts them
+    "'''cat'':2,3C ''it''''s'':1A ''sat'''::tsvector" == "'cat':2,3C 'it''s':1A 'sat'".parse::<PgTsVector>().unwrap(),
+));
+
+#[sqlx_macros::test]
+async fn test_tsvector_lexemes() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let document: PgTsVector =
+        sqlx::query_scalar("SELECT to_tsvector('english', 'The fat cats sat on the mat')")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(
+        document.lexemes().collect::<Vec<_>>(),
+        ["cat", "fat", "mat", "sat"]
+    );
+    assert_eq!(document.iter().next().unwrap().positions[0].position, 3);
+
+    let query: PgTsQuery = sqlx::query_scalar("SELECT to_tsquery('english', 'fat & (rat | cats)')")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let mut operands = query.operands().collect::<Vec<_>>();
+    operands.sort();
+    assert_eq!(operands, ["cat", "fat", "rat"]);
+
+    // both types round-trip through the binary format unchanged
+    let (matches, same_query): (bool, bool) =
+        sqlx::query_as("SELECT $1 @@ $2, $2 = to_tsquery('english', 'fat & (rat | cats)')")
+            .bind(&document)
+            .bind(&query)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert!(matches);
+    assert!(same_query);
+
+    Ok(())
+}
+
 // FIXME: needed to disable `ltree` tests in version that don't have a binary format for it
 // but `PgLTree` should just fall back to text format
 #[cfg(any(postgres_14, postgres_15))]
@@ -653,6 +1191,83 @@ test_type!(ltree_vec<Vec<sqlx::postgres::types::PgLTree>>(Postgres,
         ]
 ));
 
+// FIXME: needed to disable `ltree` tests in version that don't have a binary format for it
+// but `PgLQuery` should just fall back to text format
+// (`lquery` has no equality operator, so this can only test decoding)
+#[cfg(any(postgres_14, postgres_15))]
+test_decode_type!(lquery<sqlx::postgres::types::PgLQuery>(Postgres,
+    "'Top.*{1,2}.Foo*|Bar@%.!Baz.*{2,}'::lquery" == sqlx::postgres::types::PgLQuery::from_str("Top.*{1,2}.Foo*|Bar@%.!Baz.*{2,}").unwrap(),
+    "'*.Science.*{,3}'::lquery" == sqlx::postgres::types::PgLQuery::try_from_iter(["*", "Science", "*{,3}"]).unwrap(),
+));
+
+#[cfg(any(postgres_14, postgres_15))]
+#[sqlx_macros::test]
+async fn test_ltree_lquery_match() -> anyhow::Result<()> {
+    use sqlx::postgres::types::{PgLQuery, PgLTree};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let paths: Vec<PgLTree> = sqlx::query_scalar(
+        "SELECT path FROM unnest($1::ltree[]) AS path WHERE path ~ $2 ORDER BY path",
+    )
+    .bind(vec![
+        PgLTree::from_str("Top.Science.Astronomy")?,
+        PgLTree::try_from(vec!["Top".to_owned(), "Hobbies".to_owned()])?,
+        PgLTree::from_str("Top.Science.Astronomy.Cosmology")?,
+    ])
+    .bind(PgLQuery::from_str("Top.Science.*")?)
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(
+        paths,
+        [
+            PgLTree::from_str("Top.Science.Astronomy")?,
+            PgLTree::from_str("Top.Science.Astronomy.Cosmology")?,
+        ]
+    );
+
+    // `lquery` has no equality operator, so check that it round-trips through the database
+    let lquery = PgLQuery::from_str("Top.*{1,2}.Foo*|Bar@%.!Baz.*{2,}")?;
+    let decoded: PgLQuery = sqlx::query_scalar("SELECT $1")
+        .bind(&lquery)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(decoded, lquery);
+
+    let empty: PgLTree = sqlx::query_scalar("SELECT ''::ltree")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(empty.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_ltree_rejects_invalid_paths() {
+    use sqlx::postgres::types::{PgLTree, PgLTreeParseError};
+
+    assert!(matches!(
+        PgLTree::from_str("Top.Sci-ence"),
+        Err(PgLTreeParseError::InvalidLtreeLabel)
+    ));
+    assert!(matches!(
+        PgLTree::from_str("Top..Science"),
+        Err(PgLTreeParseError::InvalidLtreeLabel)
+    ));
+    assert!(matches!(
+        PgLTree::try_from_iter(["a".repeat(256)]),
+        Err(PgLTreeParseError::InvalidLtreeLabel)
+    ));
+    assert!(matches!(
+        PgLTree::try_from_iter(std::iter::repeat("a").take(65536)),
+        Err(PgLTreeParseError::TooManyLabels)
+    ));
+    assert!(PgLTree::try_from_iter(["a".repeat(255)]).is_ok());
+}
+
 #[sqlx_macros::test]
 async fn test_text_adapter() -> anyhow::Result<()> {
     #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
@@ -695,3 +1310,71 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+test_decode_type!(record<(i32, String)>(Postgres,
+    "(1, 'a')" == (1, "a".to_owned()),
+    "ROW(2, 'héllo, \"world\"')" == (2, "héllo, \"world\"".to_owned()),
+));
+
+test_decode_type!(record_null<(Option<i32>, Option<String>, Option<String>)>(Postgres,
+    "(NULL::int4, NULL::text, ''::text)" == (None, None, Some(String::new())),
+    "(1, ''::text, NULL::text)" == (Some(1), Some(String::new()), None),
+));
+
+test_decode_type!(record_nested<((i32, String), Option<(bool,)>)>(Postgres,
+    "((1, 'a b'), ROW(true))" == ((1, "a b".to_owned()), Some((true,))),
+));
+
+test_decode_type!(record_vec<Vec<(i64, String)>>(Postgres,
+    "(SELECT array_agg((id, name) ORDER BY id) FROM (VALUES (1::int8, 'a'), (2, 'b,c')) t(id, name))"
+        == vec![(1, "a".to_owned()), (2, "b,c".to_owned())],
+));
+
+#[sqlx_macros::test]
+async fn test_record_with_unknown_field_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let err = sqlx::query_scalar::<_, (i32, String)>("SELECT (1, 'new'::status)")
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("expected the field type to be unknown");
+
+    assert!(err.to_string().contains("field 1"), "{err}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_float_nan() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // `NaN != NaN`, so the `test_type!` macros can't be used here
+
+    // text format
+    let row = conn
+        .fetch_one("SELECT 'NaN'::real, 'NaN'::double precision, '{NaN,1}'::float8[]")
+        .await?;
+
+    assert!(row.try_get::<f32, _>(0)?.is_nan());
+    assert!(row.try_get::<f64, _>(1)?.is_nan());
+
+    let array: Vec<f64> = row.try_get(2)?;
+    assert!(array[0].is_nan());
+    assert_eq!(array[1], 1.0);
+
+    // binary format, round-tripped through a bind parameter
+    let (float4, float8, is_nan): (f32, f64, bool) =
+        sqlx::query_as("SELECT $1::real, $2::double precision, $2 = 'NaN'::double precision")
+            .bind(f32::NAN)
+            .bind(f64::NAN)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert!(float4.is_nan());
+    assert!(float8.is_nan());
+    assert!(is_nan);
+
+    Ok(())
+}
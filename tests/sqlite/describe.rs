@@ -1024,3 +1024,56 @@ async fn it_describes_analytical_function() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_nullability_of_mixed_expressions() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let info = conn
+        .describe(
+            "SELECT id, owner_id, count(*) OVER (), COALESCE(owner_id, 0), IFNULL(owner_id, 1), \
+             COALESCE(owner_id, NULL), CASE WHEN id > 1 THEN text ELSE 'x' END, \
+             CASE WHEN id > 1 THEN owner_id END \
+             FROM tweet",
+        )
+        .await?;
+
+    let nullable: Vec<_> = (0..info.columns().len())
+        .map(|i| info.nullable(i))
+        .collect();
+
+    assert_eq!(
+        nullable,
+        [
+            Some(false), // id INTEGER NOT NULL
+            Some(true),  // owner_id INTEGER
+            Some(false), // count(*)
+            Some(false), // COALESCE with a non-null fallback
+            Some(false), // IFNULL with a non-null fallback
+            Some(true),  // COALESCE with only nullable arguments
+            Some(false), // CASE where every branch is non-null
+            Some(true),  // CASE without ELSE
+        ]
+    );
+
+    let types: Vec<_> = info
+        .columns()
+        .iter()
+        .map(|column| column.type_info().name())
+        .collect();
+
+    assert_eq!(
+        types,
+        ["INTEGER", "INTEGER", "INTEGER", "INTEGER", "INTEGER", "INTEGER", "TEXT", "INTEGER"]
+    );
+
+    let info = conn
+        .describe("SELECT count(*), COALESCE(max(owner_id), 0), max(owner_id) FROM tweet")
+        .await?;
+
+    assert_eq!(info.nullable(0), Some(false));
+    assert_eq!(info.nullable(1), Some(false));
+    assert_eq!(info.nullable(2), Some(true));
+
+    Ok(())
+}
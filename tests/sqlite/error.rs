@@ -1,4 +1,5 @@
-use sqlx::{error::ErrorKind, sqlite::Sqlite, Connection, Executor};
+use sqlx::sqlite::{Sqlite, SqliteConnectOptions};
+use sqlx::{error::ErrorKind, Connection, Executor};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -70,3 +71,15 @@ async fn it_fails_with_check_violation() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_returns_structured_errors() -> anyhow::Result<()> {
+    sqlx_test::structured_errors::<Sqlite>().await?;
+
+    // and the ones specific to this driver
+    let err = "sqlite://db.sqlite?mode=sometimes"
+        .parse::<SqliteConnectOptions>()
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)), "{err:?}");
+    Ok(())
+}
@@ -1,12 +1,15 @@
 use futures::TryStreamExt;
 use rand::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
-use sqlx::sqlite::{SqliteConnectOptions, SqliteOperation, SqlitePoolOptions};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteOperation, SqlitePoolOptions, SqliteTransactionBehavior,
+    SqliteTransactionOptions,
+};
 use sqlx::{
     query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor, Row,
-    SqliteConnection, SqlitePool, Statement, TypeInfo,
+    SqliteConnection, SqlitePool, Statement, TypeInfo, Upsert,
 };
-use sqlx_test::new;
+use sqlx_test::{new, with_fixtures};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -81,6 +84,43 @@ async fn it_fetches_and_inflates_row() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_binds_optional_references() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let name = String::from("Ferris");
+    let some_name = Some(name.clone());
+    let id = 1_i64;
+
+    let row: (
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<i64>,
+        Option<i64>,
+    ) = sqlx::query_as("SELECT ?, ?, ?, ?, ?")
+        .bind(Some(&name))
+        .bind(&some_name)
+        .bind(Some("Ferris"))
+        .bind(Some(&id))
+        .bind(&None::<i64>)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(
+        row,
+        (
+            Some(name.clone()),
+            Some(name.clone()),
+            Some(name),
+            Some(1),
+            None
+        )
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_maths() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -96,6 +136,75 @@ async fn it_maths() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_maps_rows_into_struct() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq)]
+    struct Account {
+        id: i64,
+        name: String,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let accounts = sqlx::query(
+        "SELECT * FROM (VALUES (1, 'Herp Derpinson'), (2, 'Derp Herpinson')) ORDER BY 1",
+    )
+    .map(|row: SqliteRow| Account {
+        id: row.get(0),
+        name: row.get(1),
+    })
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(
+        accounts,
+        [
+            Account {
+                id: 1,
+                name: "Herp Derpinson".into()
+            },
+            Account {
+                id: 2,
+                name: "Derp Herpinson".into()
+            },
+        ]
+    );
+
+    let name = sqlx::query_as::<_, (i64, String)>("SELECT 1, 'Herp Derpinson'")
+        .map(|(_, name)| name)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(name, "Herp Derpinson");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_surfaces_try_map_error_mid_stream() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut s = sqlx::query("VALUES ('1'), ('2'), ('three'), ('4')")
+        .try_map(|row: SqliteRow| {
+            let text: String = row.try_get(0)?;
+
+            text.parse::<i32>().map_err(|e| sqlx::Error::ColumnDecode {
+                index: "0".into(),
+                source: e.into(),
+            })
+        })
+        .fetch(&mut conn);
+
+    assert_eq!(s.try_next().await?, Some(1));
+    assert_eq!(s.try_next().await?, Some(2));
+    assert!(matches!(
+        s.try_next().await,
+        Err(sqlx::Error::ColumnDecode { .. })
+    ));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_bind_multiple_statements_multiple_values() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -221,6 +330,26 @@ async fn it_opens_with_extension() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fails_to_connect_with_missing_extension() -> anyhow::Result<()> {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .extension("./no_such_sqlx_extension");
+
+    let err = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect_err("expected the extension to fail to load");
+
+    assert!(
+        err.to_string().contains("./no_such_sqlx_extension"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_opens_in_memory() -> anyhow::Result<()> {
     // If the filename is ":memory:", then a private, temporary in-memory database
@@ -241,6 +370,227 @@ async fn it_opens_temp_on_disk() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Counts the threads of this process named `name`, waiting up to a second for the count to
+/// drop to `expected`, as a thread may still be exiting.
+#[cfg(target_os = "linux")]
+async fn count_threads(name: &str, expected: usize) -> anyhow::Result<usize> {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(1);
+
+    loop {
+        let mut count = 0;
+
+        for task in std::fs::read_dir("/proc/self/task")? {
+            let comm = std::fs::read_to_string(task?.path().join("comm")).unwrap_or_default();
+
+            if comm.trim_end() == name {
+                count += 1;
+            }
+        }
+
+        if count <= expected || std::time::Instant::now() >= deadline {
+            return Ok(count);
+        }
+
+        sqlx_core::rt::sleep(std::time::Duration::from_millis(10)).await;
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[sqlx_macros::test]
+async fn it_joins_worker_thread_on_close() -> anyhow::Result<()> {
+    let conn = SqliteConnectOptions::new()
+        .in_memory(true)
+        .thread_name(|_| "sqlx-close-test".into())
+        .connect()
+        .await?;
+
+    assert_eq!(count_threads("sqlx-close-test", 1).await?, 1);
+
+    conn.close().await?;
+
+    assert_eq!(count_threads("sqlx-close-test", 0).await?, 0);
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[sqlx_macros::test]
+async fn it_bounds_shared_worker_threads() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteJournalMode;
+
+    let dir = tempfile::TempDir::new()?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("shared_worker_threads.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .thread_name(|_| "sqlx-shared".into())
+        .shared_worker_threads(4);
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .min_connections(32)
+        .max_connections(32)
+        .connect_with(options)
+        .await?;
+
+    pool.execute(
+        "CREATE TABLE counters (id INTEGER PRIMARY KEY, value INTEGER); \
+         WITH RECURSIVE n(i) AS (SELECT 0 UNION ALL SELECT i + 1 FROM n WHERE i < 127) \
+         INSERT INTO counters (id, value) SELECT i, i * 2 FROM n;",
+    )
+    .await?;
+
+    // concurrent writers would wait for each other on a shared thread, so the load reads
+    let tasks: Vec<_> = (0..128_i64)
+        .map(|i| {
+            let pool = pool.clone();
+
+            sqlx_core::rt::spawn(async move {
+                let mut tx = pool.begin().await?;
+
+                let value: i64 = sqlx::query_scalar("SELECT value FROM counters WHERE id = ?")
+                    .bind(i)
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+                let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM counters")
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+                tx.commit().await?;
+
+                anyhow::Ok((value, count))
+            })
+        })
+        .collect();
+
+    for (i, task) in (0_i64..).zip(tasks) {
+        assert_eq!(task.await?, (i * 2, 128));
+    }
+
+    assert_eq!(pool.size(), 32);
+    assert_eq!(count_threads("sqlx-shared", 4).await?, 4);
+
+    pool.close().await;
+    drop(pool);
+
+    assert_eq!(count_threads("sqlx-shared", 0).await?, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_other_connections_while_rows_are_unread() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::new()
+        .in_memory(true)
+        .row_buffer_size(1)
+        .shared_worker_threads(1);
+
+    let mut reader = SqliteConnection::connect_with(&options).await?;
+    let mut other = SqliteConnection::connect_with(&options).await?;
+
+    let mut rows = sqlx::query_scalar::<_, i64>(
+        "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000) \
+         SELECT i FROM n",
+    )
+    .fetch(&mut reader);
+
+    assert_eq!(rows.try_next().await?, Some(1));
+
+    // the thread runs the other connection while the rows of `reader` wait to be fetched
+    let value: i64 = sqlx::query_scalar("SELECT 42")
+        .fetch_one(&mut other)
+        .await?;
+    assert_eq!(value, 42);
+
+    let mut count = 1;
+    while rows.try_next().await?.is_some() {
+        count += 1;
+    }
+    assert_eq!(count, 1000);
+
+    drop(rows);
+    reader.close().await?;
+
+    // the other connection is still open on the thread
+    let value: i64 = sqlx::query_scalar("SELECT 43")
+        .fetch_one(&mut other)
+        .await?;
+    assert_eq!(value, 43);
+
+    other.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_pauses_queries_on_shared_worker_threads_until_rows_are_fetched() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteJournalMode;
+
+    let dir = tempfile::TempDir::new()?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("row_buffer_size.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Delete)
+        .busy_timeout(std::time::Duration::ZERO)
+        .row_buffer_size(1)
+        .shared_worker_threads(1);
+
+    let mut reader = SqliteConnection::connect_with(&options).await?;
+    let mut writer = SqliteConnection::connect_with(&options).await?;
+
+    writer
+        .execute(
+            "CREATE TABLE numbers (i INTEGER PRIMARY KEY); \
+             WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000) \
+             INSERT INTO numbers SELECT i FROM n;",
+        )
+        .await?;
+
+    let mut rows = sqlx::query_scalar::<_, i64>("SELECT i FROM numbers").fetch(&mut reader);
+
+    assert_eq!(rows.try_next().await?, Some(1));
+
+    // the query hasn't run to completion, so it still holds its read lock
+    let err = writer
+        .execute("INSERT INTO numbers VALUES (1001)")
+        .await
+        .expect_err("expected the database to be locked by the query");
+    assert!(err.to_string().contains("locked"), "{err}");
+
+    let mut count = 1;
+    while rows.try_next().await?.is_some() {
+        count += 1;
+    }
+    assert_eq!(count, 1000);
+
+    drop(rows);
+
+    writer.execute("INSERT INTO numbers VALUES (1001)").await?;
+
+    reader.close().await?;
+    writer.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_shared_worker_threads_with_shared_cache() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::new()
+        .in_memory(true)
+        .shared_cache(true)
+        .shared_worker_threads(1);
+
+    let err = SqliteConnection::connect_with(&options)
+        .await
+        .expect_err("expected shared cache to be rejected");
+
+    assert!(matches!(err, sqlx::Error::Configuration(_)), "{err:?}");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_fails_to_parse() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -307,28 +657,95 @@ async fn it_binds_dollar_parameters() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_executes_queries() -> anyhow::Result<()> {
+async fn it_checks_the_number_of_bound_arguments() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
 
-    let _ = conn
-        .execute(
-            r#"
-CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)
-            "#,
-        )
+    let err = sqlx::query_scalar::<_, i32>("SELECT ?, ?")
+        .bind(10_i32)
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("binding too few arguments should fail");
+
+    assert!(
+        matches!(
+            err,
+            sqlx::Error::ParameterCountMismatch {
+                expected: 2,
+                got: 1
+            }
+        ),
+        "{err:?}"
+    );
+
+    let err = sqlx::query_scalar::<_, i32>("SELECT $1")
+        .bind(10_i32)
+        .bind(11_i32)
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("binding too many arguments should fail");
+
+    assert!(
+        matches!(
+            err,
+            sqlx::Error::ParameterCountMismatch {
+                expected: 1,
+                got: 2
+            }
+        ),
+        "{err:?}"
+    );
+
+    // the parameters of all statements in the query are counted
+    let err = sqlx::query("CREATE TEMPORARY TABLE t (x INTEGER); INSERT INTO t VALUES (?)")
+        .bind(1_i32)
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await
+        .expect_err("binding too many arguments should fail");
+
+    assert!(
+        matches!(
+            err,
+            sqlx::Error::ParameterCountMismatch {
+                expected: 1,
+                got: 2
+            }
+        ),
+        "{err:?}"
+    );
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM t")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0, "the last statement should not have been executed");
+
+    // the connection is still usable
+    let v: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(10_i32)
+        .fetch_one(&mut conn)
         .await?;
 
+    assert_eq!(v, 10);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_queries() -> anyhow::Result<()> {
+    let mut conn = with_fixtures::<Sqlite>(&["temp_users"]).await?;
+
     for index in 1..=10_i32 {
         let done = sqlx::query("INSERT INTO users (id) VALUES (?)")
             .bind(index * 2)
-            .execute(&mut conn)
+            .execute(&mut *conn)
             .await?;
 
         assert_eq!(done.rows_affected(), 1);
     }
 
     let sum: i32 = sqlx::query_as("SELECT id FROM users")
-        .fetch(&mut conn)
+        .fetch(&mut *conn)
         .try_fold(0_i32, |acc, (x,): (i32,)| async move { Ok(acc + x) })
         .await?;
 
@@ -491,6 +908,25 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_does_not_cache_non_persistent_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    for i in 0..100 {
+        let val: i64 = sqlx::query_scalar(&format!("SELECT {i} + ?"))
+            .bind(1_i64)
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(i + 1, val);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -699,9 +1135,8 @@ async fn concurrent_read_and_write() {
         let mut conn = pool.acquire().await.unwrap();
 
         async move {
-            for i in 0u32..n {
+            for _ in 0u32..n {
                 sqlx::query("SELECT v FROM kv")
-                    .bind(i)
                     .fetch_all(&mut *conn)
                     .await
                     .unwrap();
@@ -1316,3 +1751,733 @@ async fn test_serialize_invalid_schema() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_backs_up_while_other_connections_write() -> anyhow::Result<()> {
+    use std::sync::atomic::AtomicU32;
+
+    let dir = tempfile::TempDir::new()?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("source.db"))
+        .create_if_missing(true);
+
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+
+    // every write inserts the same row into both tables, so a consistent snapshot
+    // has the same number of rows in each
+    sqlx::raw_sql(
+        "CREATE TABLE a (id INTEGER PRIMARY KEY, data BLOB NOT NULL);
+         CREATE TABLE b (id INTEGER PRIMARY KEY, data BLOB NOT NULL);
+         WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 2000)
+         INSERT INTO a SELECT i, randomblob(500) FROM n;
+         INSERT INTO b SELECT id, data FROM a;",
+    )
+    .execute(&mut conn)
+    .await?;
+
+    let mut writer = SqliteConnection::connect_with(&options).await?;
+
+    let write = sqlx_core::rt::spawn(async move {
+        for _ in 0..50 {
+            let mut tx = writer.begin().await.unwrap();
+
+            sqlx::query("INSERT INTO a (data) VALUES (randomblob(500))")
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+
+            sqlx::query("INSERT INTO b SELECT id, data FROM a WHERE id = last_insert_rowid()")
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+
+            tx.commit().await.unwrap();
+
+            sqlx_core::rt::sleep(std::time::Duration::from_millis(1)).await;
+        }
+    });
+
+    let steps = Arc::new(AtomicU32::new(0));
+    let destination = dir.path().join("backup.db");
+
+    conn.backup(&destination)
+        .pages_per_step(10)
+        .progress({
+            let steps = steps.clone();
+            move |remaining, total| {
+                assert!(remaining <= total);
+                steps.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .run()
+        .await?;
+
+    write.await;
+
+    // 2000 rows of 500 bytes take far more than 10 pages
+    assert!(steps.load(Ordering::SeqCst) > 1);
+
+    let mut backup = SqliteConnection::connect_with(
+        &SqliteConnectOptions::new()
+            .filename(&destination)
+            .read_only(true),
+    )
+    .await?;
+
+    let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&mut backup)
+        .await?;
+    assert_eq!(integrity, "ok");
+
+    let (a, b): (i64, i64) =
+        sqlx::query_as("SELECT (SELECT COUNT(*) FROM a), (SELECT COUNT(*) FROM b)")
+            .fetch_one(&mut backup)
+            .await?;
+    assert_eq!(a, b);
+    assert!((2000..=2050).contains(&a), "{a}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_backs_up_to_connection() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    sqlx::raw_sql("CREATE TABLE foo (bar INTEGER NOT NULL); INSERT INTO foo VALUES (1), (2), (3);")
+        .execute(&mut conn)
+        .await?;
+
+    let mut other = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.backup_to_connection(&mut other).run().await?;
+
+    let sum: i64 = sqlx::query_scalar("SELECT SUM(bar) FROM foo")
+        .fetch_one(&mut other)
+        .await?;
+    assert_eq!(sum, 6);
+
+    // both connections are usable afterwards
+    conn.execute("INSERT INTO foo VALUES (4)").await?;
+    other.execute("INSERT INTO foo VALUES (5)").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fails_to_back_up_to_invalid_path() -> anyhow::Result<()> {
+    let dir = tempfile::TempDir::new()?;
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    let err = conn
+        .backup(dir.path().join("no_such_dir").join("backup.db"))
+        .run()
+        .await
+        .expect_err("expected the backup to fail");
+
+    let sqlx::Error::Database(dbe) = err else {
+        panic!("expected DatabaseError: {err:?}")
+    };
+
+    // SQLITE_CANTOPEN
+    assert_eq!(dbe.code().as_deref(), Some("14"));
+
+    // the connection is still usable
+    conn.execute("SELECT 1").await?;
+
+    Ok(())
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn it_queries_with_blocking_connection() -> anyhow::Result<()> {
+    use sqlx::blocking::{Close, Connect, Connection};
+
+    let mut conn = Connection::<Sqlite>::connect("sqlite::memory:")?;
+
+    conn.execute(sqlx::query(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+    ))?;
+
+    let done = conn.execute(
+        sqlx::query("INSERT INTO users (name) VALUES (?), (?)")
+            .bind("Ferris")
+            .bind("Corro"),
+    )?;
+    assert_eq!(done.rows_affected(), 2);
+
+    let rows = conn.fetch_all(sqlx::query("SELECT name FROM users ORDER BY id"))?;
+    let names: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+    assert_eq!(names, ["Ferris", "Corro"]);
+
+    let row = conn.fetch_optional(sqlx::query("SELECT name FROM users WHERE id = 3"))?;
+    assert!(row.is_none());
+
+    let count: i64 =
+        conn.run(|conn| sqlx::query_scalar("SELECT COUNT(*) FROM users").fetch_one(conn))?;
+    assert_eq!(count, 2);
+
+    conn.close()?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_drop_fetch_stream_mid_result() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let query = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 100000) \
+                 SELECT i FROM n";
+
+    for persistent in [true, false] {
+        {
+            let mut rows = sqlx::query(query).persistent(persistent).fetch(&mut conn);
+            let row = rows.try_next().await?.expect("at least one row");
+            assert_eq!(row.get::<i64, _>(0), 1);
+        }
+
+        // the unread rows must not affect the next query
+        let value: i64 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+        assert_eq!(value, 42);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_returning_rows_with_rows_affected() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE returning_test (id INTEGER PRIMARY KEY, flag BOOLEAN)")
+        .await?;
+
+    let (ids, result) = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO returning_test (flag) VALUES (false), (false), (true) RETURNING id",
+    )
+    .fetch_all_with_result(&mut conn)
+    .await?;
+
+    assert_eq!(ids, [1, 2, 3]);
+    assert_eq!(result.rows_affected(), 3);
+
+    let (rows, result) =
+        sqlx::query("UPDATE returning_test SET flag = true WHERE flag = false RETURNING id, flag")
+            .fetch_all_with_result(&mut conn)
+            .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| row.get::<bool, _>("flag")));
+    assert_eq!(result.rows_affected(), 2);
+
+    let (ids, result) = sqlx::query_as::<_, (i64,)>("DELETE FROM returning_test RETURNING id")
+        .fetch_all_with_result(&mut conn)
+        .await?;
+
+    assert_eq!(ids.len(), 3);
+    assert_eq!(result.rows_affected(), 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_combines_results_of_multiple_statements() -> anyhow::Result<()> {
+    let mut conn = with_fixtures::<Sqlite>(&["temp_users"]).await?;
+
+    // `execute()` sums the rows affected by each statement
+    let res = sqlx::raw_sql("INSERT INTO users VALUES (1), (2); INSERT INTO users VALUES (3);")
+        .execute(&mut *conn)
+        .await?;
+
+    assert_eq!(res.rows_affected(), 3);
+
+    let sql = "SELECT id FROM users WHERE id < 3 ORDER BY id; \
+               SELECT id FROM users WHERE id = 3;";
+
+    // `fetch_all()` returns the rows of every result set, in order
+    let ids: Vec<i64> = sqlx::raw_sql(sql)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    assert_eq!(ids, [1, 2, 3]);
+
+    // `fetch_one()` returns the first row of the first result set
+    let row = sqlx::raw_sql(sql).fetch_one(&mut *conn).await?;
+
+    assert_eq!(row.get::<i64, _>(0), 1);
+
+    // and the connection is left ready for the next query
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_failing_fixture_statement() -> anyhow::Result<()> {
+    let Err(err) = with_fixtures::<Sqlite>(&["temp_users", "failing"]).await else {
+        panic!("expected the fixture to fail");
+    };
+
+    let message = format!("{err:#}");
+
+    assert!(
+        message.contains("statement #2 of fixture ") && message.contains("failing.sql"),
+        "{message}"
+    );
+    assert!(message.contains("fixture_does_not_exist"), "{message}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_optional_row_from_multiple_statements() -> anyhow::Result<()> {
+    sqlx_test::fetch_optional_from_multiple_statements::<Sqlite>().await
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_immediate_transactions() -> anyhow::Result<()> {
+    let dir = tempfile::TempDir::new()?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("begin_immediate.db"))
+        .create_if_missing(true)
+        .busy_timeout(std::time::Duration::ZERO);
+
+    let mut conn1 = SqliteConnection::connect_with(&options).await?;
+    let mut conn2 = SqliteConnection::connect_with(&options).await?;
+
+    conn1
+        .execute("CREATE TABLE accounts (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    // a deferred transaction does not take any lock until it touches the database
+    let tx = conn1.begin_with(SqliteTransactionOptions::new()).await?;
+    conn2
+        .execute("INSERT INTO accounts (id) VALUES (1)")
+        .await?;
+    tx.rollback().await?;
+
+    // an immediate transaction takes the write lock up front
+    let mut tx = conn1
+        .begin_with(SqliteTransactionOptions::new().behavior(SqliteTransactionBehavior::Immediate))
+        .await?;
+
+    let err = conn2
+        .execute("INSERT INTO accounts (id) VALUES (2)")
+        .await
+        .expect_err("expected the database to be locked");
+
+    assert!(err.to_string().contains("database is locked"), "{err}");
+
+    // options are ignored for savepoints
+    let savepoint = tx
+        .begin_with(SqliteTransactionOptions::new().behavior(SqliteTransactionBehavior::Exclusive))
+        .await?;
+    savepoint.commit().await?;
+    tx.commit().await?;
+
+    conn2
+        .execute("INSERT INTO accounts (id) VALUES (2)")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_pool_and_query_metrics() -> anyhow::Result<()> {
+    use sqlx::metrics::Metrics;
+    use std::sync::atomic::AtomicU64;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct CountingMetrics {
+        acquires: AtomicU64,
+        connects: AtomicU64,
+        disconnects: AtomicU64,
+        queries: AtomicU64,
+        query_errors: AtomicU64,
+        rows_returned: AtomicU64,
+    }
+
+    impl Metrics for CountingMetrics {
+        fn on_acquire(&self, _wait: Duration, result: Result<(), &sqlx::Error>) {
+            assert!(result.is_ok());
+            self.acquires.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_connect(&self) {
+            self.connects.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_disconnect(&self) {
+            self.disconnects.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_query(&self, _elapsed: Duration, rows: u64, result: Result<(), &sqlx::Error>) {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            self.rows_returned.fetch_add(rows, Ordering::SeqCst);
+
+            if result.is_err() {
+                self.query_errors.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    let metrics = Arc::new(CountingMetrics::default());
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .metrics(metrics.clone())
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    // `connect()` acquires and releases a connection to test it
+    assert_eq!(metrics.acquires.load(Ordering::SeqCst), 1);
+    assert_eq!(metrics.connects.load(Ordering::SeqCst), 1);
+
+    // the `PRAGMA`s run while establishing the connection are not counted
+    assert_eq!(metrics.queries.load(Ordering::SeqCst), 0);
+
+    let rows = pool.fetch_all("SELECT 1 UNION ALL SELECT 2").await?;
+    assert_eq!(rows.len(), 2);
+
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&pool).await?;
+    assert_eq!(one, 1);
+
+    pool.execute("SELECT * FROM no_such_table")
+        .await
+        .expect_err("expected the query to fail");
+
+    assert_eq!(metrics.acquires.load(Ordering::SeqCst), 4);
+    assert_eq!(metrics.connects.load(Ordering::SeqCst), 1);
+    assert_eq!(metrics.queries.load(Ordering::SeqCst), 3);
+    assert_eq!(metrics.query_errors.load(Ordering::SeqCst), 1);
+    assert_eq!(metrics.rows_returned.load(Ordering::SeqCst), 3);
+
+    pool.close().await;
+
+    assert_eq!(metrics.disconnects.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_rows_as_dynamic_values() -> anyhow::Result<()> {
+    use sqlx::DynamicValue;
+    use std::collections::HashMap;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let sql = "SELECT NULL AS \"null\", 1 AS int, 1.5 AS real, 'text' AS text, \
+               x'deadbeef' AS blob, 1 AS dup, 2 AS dup";
+
+    let row: Vec<(String, DynamicValue)> = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+    assert_eq!(
+        row,
+        [
+            ("null".to_owned(), DynamicValue::Null),
+            ("int".to_owned(), DynamicValue::Int(1)),
+            ("real".to_owned(), DynamicValue::Double(1.5)),
+            ("text".to_owned(), DynamicValue::from("text")),
+            (
+                "blob".to_owned(),
+                DynamicValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef])
+            ),
+            ("dup".to_owned(), DynamicValue::Int(1)),
+            ("dup".to_owned(), DynamicValue::Int(2)),
+        ]
+    );
+
+    // values are converted by their storage class, not the declared type of the column
+    let map: HashMap<String, DynamicValue> =
+        sqlx::query_as("SELECT is_sent, text FROM tweet WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(map["is_sent"], DynamicValue::Int(1));
+    assert_eq!(map["text"], DynamicValue::from("#sqlx is pretty cool!"));
+
+    Ok(())
+}
+
+#[test]
+fn it_generates_upserts() {
+    let qb = Upsert::<Sqlite>::into("main.user\"s")
+        .columns(&["id", "name"])
+        .values(0..2, |mut b, i| {
+            b.push_bind(i).push_bind("name");
+        })
+        .conflict(&["id"])
+        .update(&["name"])
+        .into_query_builder();
+
+    assert_eq!(
+        qb.sql(),
+        r#"INSERT INTO "main"."user""s" ("id", "name") VALUES (?, ?), (?, ?) ON CONFLICT ("id") DO UPDATE SET "name" = excluded."name""#
+    );
+}
+
+sqlx_test::test_upsert_conflicting_rows!(it_upserts_conflicting_rows(Sqlite));
+
+#[sqlx_macros::test]
+async fn it_serializes_concurrent_writes() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteJournalMode;
+
+    let dir = tempfile::TempDir::new()?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("writes.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .serialized_writes(true);
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(16)
+        .connect_with(options)
+        .await?;
+
+    sqlx::raw_sql(
+        "CREATE TABLE log (task INTEGER NOT NULL, i INTEGER NOT NULL);
+         CREATE TABLE counter (n INTEGER NOT NULL);
+         INSERT INTO counter VALUES (0);",
+    )
+    .execute(&pool)
+    .await?;
+
+    let tasks = 32;
+    let writes = 20;
+
+    let mut handles = Vec::new();
+
+    for task in 0..tasks {
+        let pool = pool.clone();
+
+        handles.push(sqlx_core::rt::spawn(async move {
+            for i in 0..writes {
+                if i % 2 == 0 {
+                    sqlx::query("INSERT INTO log (task, i) VALUES (?, ?)")
+                        .bind(task)
+                        .bind(i)
+                        .execute(&pool)
+                        .await?;
+                } else {
+                    let mut conn = pool.acquire().await?;
+                    let mut tx = conn
+                        .begin_with(
+                            SqliteTransactionOptions::new()
+                                .behavior(SqliteTransactionBehavior::Immediate),
+                        )
+                        .await?;
+
+                    let n: i64 = sqlx::query_scalar("SELECT n FROM counter")
+                        .fetch_one(&mut *tx)
+                        .await?;
+
+                    sqlx::query("UPDATE counter SET n = ?")
+                        .bind(n + 1)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    tx.commit().await?;
+                }
+            }
+
+            Ok::<_, sqlx::Error>(())
+        }));
+    }
+
+    // readers don't take the lock and run alongside the writers
+    for _ in 0..4 {
+        let pool = pool.clone();
+
+        handles.push(sqlx_core::rt::spawn(async move {
+            for _ in 0..writes {
+                sqlx::query("SELECT COUNT(*) FROM log")
+                    .fetch_one(&pool)
+                    .await?;
+            }
+
+            Ok(())
+        }));
+    }
+
+    for handle in handles {
+        // none of the writes should fail with `SQLITE_BUSY`
+        handle.await?;
+    }
+
+    let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM log")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(logged, tasks * writes / 2);
+
+    let n: i64 = sqlx::query_scalar("SELECT n FROM counter")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(n, tasks * writes / 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_while_writes_are_serialized() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteJournalMode;
+    use std::time::Duration;
+
+    let dir = tempfile::TempDir::new()?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("reads.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(Duration::from_millis(200))
+        .serialized_writes(true);
+
+    let mut writer = SqliteConnection::connect_with(&options).await?;
+    let mut reader = SqliteConnection::connect_with(&options).await?;
+    let mut other_writer = SqliteConnection::connect_with(&options).await?;
+
+    writer
+        .execute("CREATE TABLE kv (k INTEGER PRIMARY KEY, v INTEGER)")
+        .await?;
+
+    let mut tx = writer.begin().await?;
+    tx.execute("INSERT INTO kv (k, v) VALUES (1, 1)").await?;
+
+    // the open transaction holds the write lock, but reads don't wait for it
+    let count: i64 = sqlx_core::rt::timeout(
+        Duration::from_millis(100),
+        sqlx::query_scalar("SELECT COUNT(*) FROM kv").fetch_one(&mut reader),
+    )
+    .await??;
+    assert_eq!(count, 0);
+
+    // another writer waits for the lock for up to the busy timeout
+    let err = other_writer
+        .execute("INSERT INTO kv (k, v) VALUES (2, 2)")
+        .await
+        .expect_err("expected the write to time out waiting for the lock");
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("5")
+    );
+
+    let write = sqlx_core::rt::spawn(async move {
+        other_writer
+            .execute("INSERT INTO kv (k, v) VALUES (2, 2)")
+            .await
+    });
+
+    tx.commit().await?;
+
+    // the lock is released with the transaction
+    write.await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM kv")
+        .fetch_one(&mut reader)
+        .await?;
+    assert_eq!(count, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_serializes_writes_in_raw_immediate_transactions() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    let dir = tempfile::TempDir::new()?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("raw_begin.db"))
+        .create_if_missing(true)
+        .busy_timeout(Duration::from_secs(2))
+        .serialized_writes(true);
+
+    let mut writer = SqliteConnection::connect_with(&options).await?;
+    let mut other_writer = SqliteConnection::connect_with(&options).await?;
+
+    writer
+        .execute("CREATE TABLE kv (k INTEGER PRIMARY KEY, v INTEGER)")
+        .await?;
+
+    // takes SQLite's write lock, so it must take the shared lock as well
+    writer.execute("BEGIN IMMEDIATE").await?;
+
+    let write = sqlx_core::rt::spawn(async move {
+        other_writer
+            .execute("INSERT INTO kv (k, v) VALUES (2, 2)")
+            .await
+    });
+
+    sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+
+    // if the other writer held the shared lock while waiting for SQLite's,
+    // this would wait for it until one of them timed out
+    writer
+        .execute("INSERT INTO kv (k, v) VALUES (1, 1)")
+        .await?;
+    writer.execute("COMMIT").await?;
+
+    write.await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM kv")
+        .fetch_one(&mut writer)
+        .await?;
+    assert_eq!(count, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_dropped_transaction_before_close() -> anyhow::Result<()> {
+    let dir = tempfile::TempDir::new()?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("close.db"))
+        .create_if_missing(true);
+
+    // fail immediately instead of waiting if the database is still locked
+    let other =
+        SqliteConnection::connect_with(&options.clone().busy_timeout(std::time::Duration::ZERO))
+            .await?;
+
+    sqlx_test::rolls_back_dropped_transaction_before_close::<Sqlite, _, _>(
+        other,
+        || async { Ok(SqliteConnection::connect_with(&options).await?) },
+        "INSERT INTO close_test (id) VALUES (1)",
+    )
+    .await
+}
+
+#[sqlx_macros::test]
+async fn it_reports_vfs_name_and_filename() -> anyhow::Result<()> {
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path().join("filename.db");
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(&path)
+        .create_if_missing(true)
+        .connect()
+        .await?;
+
+    assert_eq!(conn.filename().await?, Some(path.canonicalize()?));
+    assert!(!conn.vfs_name().await?.is_empty());
+
+    // the worker resumes after each lock of the handle
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    let mut conn = SqliteConnectOptions::new()
+        .in_memory(true)
+        .connect()
+        .await?;
+    assert_eq!(conn.filename().await?, None);
+
+    Ok(())
+}
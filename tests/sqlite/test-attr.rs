@@ -1,5 +1,8 @@
 // The no-arg variant is covered by other tests already.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
 use sqlx::{Row, SqlitePool};
 
 const MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("tests/sqlite/migrations");
@@ -98,6 +101,43 @@ async fn it_gets_comments(pool: SqlitePool) -> sqlx::Result<()> {
     Ok(())
 }
 
+// These two tests insert into the same table while the other one is running;
+// each should only ever see its own row as they get separate databases.
+static PARALLEL_INSERTED: AtomicUsize = AtomicUsize::new(0);
+
+async fn insert_user_in_parallel(pool: SqlitePool, username: &str) -> sqlx::Result<()> {
+    sqlx::query(r#"INSERT INTO user(username) VALUES ($1)"#)
+        .bind(username)
+        .execute(&pool)
+        .await?;
+
+    PARALLEL_INSERTED.fetch_add(1, Ordering::SeqCst);
+
+    // Give the other test a chance to insert its row, unless it was filtered out.
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while PARALLEL_INSERTED.load(Ordering::SeqCst) < 2 && Instant::now() < deadline {
+        sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+    }
+
+    let usernames: Vec<String> = sqlx::query_scalar(r#"SELECT username FROM user"#)
+        .fetch_all(&pool)
+        .await?;
+
+    assert_eq!(usernames, [username]);
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "tests/sqlite/migrations")]
+async fn it_isolates_parallel_tests_1(pool: SqlitePool) -> sqlx::Result<()> {
+    insert_user_in_parallel(pool, "carol").await
+}
+
+#[sqlx::test(migrations = "tests/sqlite/migrations")]
+async fn it_isolates_parallel_tests_2(pool: SqlitePool) -> sqlx::Result<()> {
+    insert_user_in_parallel(pool, "dave").await
+}
+
 #[sqlx::test(
     migrations = "tests/sqlite/migrations",
     fixtures(path = "./fixtures", scripts("users", "posts"))
@@ -5,13 +5,17 @@ use sqlx_core::executor::Executor;
 use sqlx_core::row::Row;
 use sqlx_core::types::Text;
 use sqlx_test::new;
-use sqlx_test::test_type;
+use sqlx_test::{test_null_type, test_type};
 use std::net::SocketAddr;
 
 test_type!(null<Option<i32>>(Sqlite,
     "NULL" == None::<i32>
 ));
 
+test_null_type!(option_i32<i32>(Sqlite, "NULL", "CAST(NULL AS INTEGER)"));
+test_null_type!(option_string<String>(Sqlite, "NULL", "CAST(NULL AS TEXT)"));
+test_null_type!(option_bytes<Vec<u8>>(Sqlite, "NULL", "CAST(NULL AS BLOB)"));
+
 test_type!(bool(Sqlite, "FALSE" == false, "TRUE" == true));
 
 test_type!(i32(Sqlite, "94101" == 94101_i32));
@@ -30,6 +34,8 @@ test_type!(str<String>(Sqlite,
     "''" == ""
 ));
 
+test_type!(char(Sqlite, "'a'" == 'a', "'é'" == 'é', "'🦀'" == '🦀',));
+
 test_type!(bytes<Vec<u8>>(Sqlite,
     "X'DEADBEEF'"
         == vec![0xDE_u8, 0xAD, 0xBE, 0xEF],
@@ -39,6 +45,38 @@ test_type!(bytes<Vec<u8>>(Sqlite,
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+test_type!(byte_array_16<[u8; 16]>(Sqlite,
+    "X'b731678f636f4135bc6f19440c13bd19'"
+        == [0xb7_u8, 0x31, 0x67, 0x8f, 0x63, 0x6f, 0x41, 0x35, 0xbc, 0x6f, 0x19, 0x44, 0x0c, 0x13, 0xbd, 0x19],
+));
+
+test_type!(byte_array_32<[u8; 32]>(Sqlite,
+    "X'abababababababababababababababababababababababababababababababab'" == [0xAB_u8; 32],
+));
+
+test_type!(byte_array_option<Option<[u8; 4]>>(Sqlite,
+    "X'DEADBEEF'" == Some([0xDE_u8, 0xAD, 0xBE, 0xEF]),
+    "NULL" == None::<[u8; 4]>,
+));
+
+#[sqlx_macros::test]
+async fn test_byte_array_rejects_wrong_length() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let err = sqlx::query_scalar::<_, [u8; 16]>("SELECT X'DEADBEEF'")
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("expected an error decoding 4 bytes as [u8; 16]");
+
+    assert!(
+        err.to_string()
+            .contains("expected 16 bytes to decode `[u8; 16]`, got 4 bytes"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "json")]
 mod json_tests {
     use super::*;
@@ -99,17 +137,79 @@ mod chrono {
     use super::*;
     use sqlx::types::chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
 
-    test_type!(chrono_naive_date_time<NaiveDateTime>(Sqlite, "SELECT datetime({0}) is datetime(?), {0}, ?",
+    test_type!(chrono_naive_date_time<NaiveDateTime>(Sqlite, compare = "datetime({0}) is datetime(?)",
         "'2019-01-02 05:10:20'" == NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap()
     ));
 
-    test_type!(chrono_date_time_utc<DateTime::<Utc>>(Sqlite, "SELECT datetime({0}) is datetime(?), {0}, ?",
+    test_type!(chrono_date_time_utc<DateTime::<Utc>>(Sqlite, compare = "datetime({0}) is datetime(?)",
         "'1996-12-20T00:39:57+00:00'" == Utc.with_ymd_and_hms(1996, 12, 20, 0, 39, 57).unwrap()
     ));
 
-    test_type!(chrono_date_time_fixed_offset<DateTime::<FixedOffset>>(Sqlite, "SELECT datetime({0}) is datetime(?), {0}, ?",
+    test_type!(chrono_date_time_fixed_offset<DateTime::<FixedOffset>>(Sqlite, compare = "datetime({0}) is datetime(?)",
         "'2016-11-08T03:50:23-05:00'" == DateTime::<Utc>::from(FixedOffset::west_opt(5 * 3600).unwrap().with_ymd_and_hms(2016, 11, 08, 3, 50, 23).unwrap())
     ));
+
+    #[sqlx_macros::test]
+    async fn test_chrono_date_time_storage_formats() -> anyhow::Result<()> {
+        use sqlx::sqlite::types::{
+            SqliteDateTimeText, SqliteDateTimeUnixMillis, SqliteDateTimeUnixSeconds,
+        };
+
+        let mut conn = new::<Sqlite>().await?;
+
+        conn.execute(
+            r#"
+CREATE TEMPORARY TABLE chrono_events (id INTEGER PRIMARY KEY, at DATETIME NOT NULL);
+INSERT INTO chrono_events (id, at) VALUES
+    (1, '2024-03-05T06:07:08.250+02:00'),
+    (2, 1709611628),
+    (3, 1709611628250),
+    (4, julianday('2024-03-05 04:07:08.250'));
+            "#,
+        )
+        .await?;
+
+        let expected = Utc.with_ymd_and_hms(2024, 3, 5, 4, 7, 8).unwrap();
+        let expected_millis = Utc.timestamp_millis_opt(1709611628250).unwrap();
+
+        let rows: Vec<(i64, DateTime<Utc>)> =
+            sqlx::query_as("SELECT id, at FROM chrono_events ORDER BY id")
+                .fetch_all(&mut conn)
+                .await?;
+
+        assert_eq!(rows[0].1, expected_millis);
+        assert_eq!(rows[1].1, expected);
+        assert_eq!(rows[2].1, expected_millis);
+        // Julian days are only precise to the millisecond
+        assert!((rows[3].1 - expected_millis).num_milliseconds().abs() <= 1);
+
+        // small millisecond timestamps are taken to be in seconds, unless decoded as milliseconds
+        let (seconds, millis): (DateTime<Utc>, SqliteDateTimeUnixMillis<DateTime<Utc>>) =
+            sqlx::query_as("SELECT 86400000, 86400000")
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(seconds, Utc.with_ymd_and_hms(1972, 9, 27, 0, 0, 0).unwrap());
+        assert_eq!(millis.0, Utc.with_ymd_and_hms(1970, 1, 2, 0, 0, 0).unwrap());
+
+        let written: (String, String, i64, String, i64, String, NaiveDateTime) =
+            sqlx::query_as("SELECT ?1, typeof(?1), ?2, typeof(?2), ?3, typeof(?3), ?3")
+                .bind(SqliteDateTimeText(expected_millis))
+                .bind(SqliteDateTimeUnixSeconds(expected_millis))
+                .bind(SqliteDateTimeUnixMillis(expected_millis.naive_utc()))
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(written.0, "2024-03-05T04:07:08.250+00:00");
+        assert_eq!(written.1, "text");
+        assert_eq!(written.2, 1709611628);
+        assert_eq!(written.3, "integer");
+        assert_eq!(written.4, 1709611628250);
+        assert_eq!(written.5, "integer");
+        assert_eq!(written.6, expected_millis.naive_utc());
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
@@ -120,7 +220,7 @@ mod time_tests {
 
     test_type!(time_offset_date_time<OffsetDateTime>(
         Sqlite,
-        "SELECT datetime({0}) is datetime(?), {0}, ?",
+        compare = "datetime({0}) is datetime(?)",
         "'2015-11-19 01:01:39+01:00'" == datetime!(2015 - 11 - 19 1:01:39 +1),
         "'2014-10-18 00:00:38.697+00:00'" == datetime!(2014 - 10 - 18 00:00:38.697 +0),
         "'2013-09-17 23:59-01:00'" == datetime!(2013 - 9 - 17 23:59 -1),
@@ -130,7 +230,7 @@ mod time_tests {
 
     test_type!(time_primitive_date_time<PrimitiveDateTime>(
         Sqlite,
-        "SELECT datetime({0}) is datetime(?), {0}, ?",
+        compare = "datetime({0}) is datetime(?)",
         "'2019-01-02 05:10:20'" == datetime!(2019 - 1 - 2 5:10:20),
         "'2018-12-01 04:09:19.543'" == datetime!(2018 - 12 - 1 4:09:19.543),
         "'2017-11-30 03:08'" == datetime!(2017 - 11 - 30 3:08),
@@ -145,6 +245,76 @@ mod time_tests {
         "'2008-02-21T18:59Z'" == datetime!(2008 - 2 - 21 18:59:00),
     ));
 
+    #[sqlx_macros::test]
+    async fn test_time_date_time_storage_formats() -> anyhow::Result<()> {
+        use sqlx::sqlite::types::{
+            SqliteDateTimeText, SqliteDateTimeUnixMillis, SqliteDateTimeUnixSeconds,
+        };
+
+        let mut conn = new::<Sqlite>().await?;
+
+        conn.execute(
+            r#"
+CREATE TEMPORARY TABLE time_events (id INTEGER PRIMARY KEY, at DATETIME NOT NULL);
+INSERT INTO time_events (id, at) VALUES
+    (1, '2024-03-05T06:07:08.250+02:00'),
+    (2, 1709611628),
+    (3, 1709611628250),
+    (4, julianday('2024-03-05 04:07:08.250'));
+            "#,
+        )
+        .await?;
+
+        let expected = datetime!(2024 - 3 - 5 4:07:08 UTC);
+        let expected_millis = datetime!(2024 - 3 - 5 4:07:08.250 UTC);
+
+        let rows: Vec<(i64, OffsetDateTime)> =
+            sqlx::query_as("SELECT id, at FROM time_events ORDER BY id")
+                .fetch_all(&mut conn)
+                .await?;
+
+        assert_eq!(rows[0].1, expected_millis);
+        assert_eq!(rows[1].1, expected);
+        assert_eq!(rows[2].1, expected_millis);
+        // Julian days are only precise to the millisecond
+        assert!((rows[3].1 - expected_millis).whole_milliseconds().abs() <= 1);
+
+        let julian: PrimitiveDateTime = sqlx::query_scalar("SELECT julianday('2024-03-05 12:00')")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(julian, datetime!(2024 - 3 - 5 12:00));
+
+        // small millisecond timestamps are taken to be in seconds, unless decoded as milliseconds
+        let (seconds, millis): (OffsetDateTime, SqliteDateTimeUnixMillis<OffsetDateTime>) =
+            sqlx::query_as("SELECT 86400000, 86400000")
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(seconds, datetime!(1972 - 9 - 27 0:00 UTC));
+        assert_eq!(millis.0, datetime!(1970 - 1 - 2 0:00 UTC));
+
+        let written: (String, String, i64, String, i64, String, PrimitiveDateTime) =
+            sqlx::query_as("SELECT ?1, typeof(?1), ?2, typeof(?2), ?3, typeof(?3), ?3")
+                .bind(SqliteDateTimeText(expected_millis))
+                .bind(SqliteDateTimeUnixSeconds(expected_millis))
+                .bind(SqliteDateTimeUnixMillis(
+                    datetime!(2024 - 3 - 5 4:07:08.250),
+                ))
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(written.0, "2024-03-05T04:07:08.25Z");
+        assert_eq!(written.1, "text");
+        assert_eq!(written.2, 1709611628);
+        assert_eq!(written.3, "integer");
+        assert_eq!(written.4, 1709611628250);
+        assert_eq!(written.5, "integer");
+        assert_eq!(written.6, datetime!(2024 - 3 - 5 4:07:08.250));
+
+        Ok(())
+    }
+
     test_type!(time_date<Date>(
         Sqlite,
         "SELECT date({0}) is date(?), {0}, ?",
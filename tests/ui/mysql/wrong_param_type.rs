@@ -0,0 +1,9 @@
+fn main() {
+    let _query = sqlx::query!("SELECT * FROM tweet WHERE id = ?", "1");
+
+    let id = String::from("1");
+    let _query = sqlx::query!("SELECT * FROM tweet WHERE text = ? AND id = ?", "", id);
+
+    let id = Some("1");
+    let _query = sqlx::query!("SELECT * FROM tweet WHERE id = ?", id);
+}
@@ -0,0 +1,7 @@
+struct Record {
+    user_id: i32,
+}
+
+fn main() {
+    let _query = sqlx::query_as!(Record, rename_all = "camelCase", "select 1 as user_id");
+}